@@ -11,8 +11,8 @@ use model::positive::{
 use model::strategy::benchmark_strategies;
 
 use model::option::{
-    benchmark_binary_tree, benchmark_greeks, benchmark_maturities, benchmark_pricing,
-    benchmark_valuations,
+    benchmark_binary_tree, benchmark_greeks, benchmark_maturities, benchmark_payoffs,
+    benchmark_pricing, benchmark_valuations,
 };
 
 use model::position::{
@@ -29,6 +29,7 @@ criterion_group!(
     benchmark_math_operations,
     benchmark_comparisons,
     benchmark_pricing,
+    benchmark_payoffs,
     benchmark_greeks,
     benchmark_valuations,
     benchmark_binary_tree,
@@ -7,6 +7,8 @@
 use criterion::Criterion;
 use optionstratlib::greeks::Greeks;
 use optionstratlib::pnl::utils::PnLCalculator;
+use optionstratlib::pricing::monte_carlo::price_option_monte_carlo;
+use optionstratlib::pricing::{BatchBlackScholes, Payoff, PayoffInfo, PayoffInputs};
 use optionstratlib::{ExpirationDate, OptionStyle, OptionType, Options, Side};
 use positive::{Positive, pos_or_panic};
 use rust_decimal_macros::dec;
@@ -45,6 +47,62 @@ pub(crate) fn benchmark_pricing(c: &mut Criterion) {
         bencher.iter(|| black_box(option.calculate_price_telegraph(50)))
     });
 
+    let pricer = BatchBlackScholes::from_option(&option).unwrap();
+    let strikes: Vec<f64> = (0..1_000).map(|i| 50.0 + 0.1 * i as f64).collect();
+    let volatilities: Vec<f64> = (0..1_000).map(|i| 0.15 + 0.0001 * i as f64).collect();
+    group.bench_function("black_scholes_batch_1000", |bencher| {
+        bencher.iter(|| {
+            black_box(pricer.price_calls_and_puts(black_box(&strikes), black_box(&volatilities)))
+        })
+    });
+
+    group.finish();
+}
+
+pub(crate) fn benchmark_payoffs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Payoff Evaluation");
+    let option = create_test_option();
+    let chooser = OptionType::Chooser { choice_date: 30.0 };
+    let spots: Vec<f64> = (0..10_000).map(|i| 50.0 + 0.01 * i as f64).collect();
+    let final_prices: Vec<Positive> = spots.iter().map(|&s| pos_or_panic!(s)).collect();
+
+    group.bench_function("chooser_payoff_info_10000", |bencher| {
+        bencher.iter(|| {
+            final_prices
+                .iter()
+                .map(|&spot| {
+                    let info = PayoffInfo {
+                        spot,
+                        strike: option.strike_price,
+                        style: option.option_style,
+                        side: option.side,
+                        spot_prices: None,
+                        spot_min: None,
+                        spot_max: None,
+                    };
+                    chooser.payoff(black_box(&info))
+                })
+                .sum::<f64>()
+        })
+    });
+
+    let strike = option.strike_price.to_f64();
+    group.bench_function("chooser_payoff_f64_10000", |bencher| {
+        bencher.iter(|| {
+            spots
+                .iter()
+                .map(|&spot| {
+                    let inputs = PayoffInputs::new(spot, strike, option.option_style, option.side);
+                    chooser.payoff_f64(black_box(&inputs))
+                })
+                .sum::<f64>()
+        })
+    });
+
+    group.bench_function("price_option_monte_carlo_10000", |bencher| {
+        bencher.iter(|| black_box(price_option_monte_carlo(&option, black_box(&final_prices))))
+    });
+
     group.finish();
 }
 
@@ -0,0 +1,42 @@
+//! Iron condor cookbook recipe.
+//!
+//! Loads the S&P 500 chain, optimizes an iron condor by profit area, writes
+//! its payoff chart (with `--features plot`), simulates managing it over
+//! random paths and logs the results.
+//!
+//! ```bash
+//! cargo run --package examples_cookbook --bin cookbook_iron_condor
+//! ```
+use examples_cookbook::{IronCondorRecipe, run_iron_condor};
+use optionstratlib::prelude::*;
+
+fn main() -> Result<(), Error> {
+    setup_logger();
+    let mut recipe = IronCondorRecipe::new("./examples/Chains/SP500-18-oct-2024-5781.88.json");
+    recipe.chart_path = Some("Draws/Cookbook/iron_condor.png".into());
+
+    let report = run_iron_condor(&recipe)?;
+    let management = &report.management;
+
+    info!("Title: {}", report.title);
+    info!("Underlying: ${:.2}", report.underlying_price);
+    info!("Strikes: {:?}", report.strikes);
+    info!("Break Even Points: {:?}", report.break_even_points);
+    info!("Net Premium Received: ${:.2}", report.net_premium);
+    info!("Max Profit: ${:.2}", report.max_profit);
+    info!("Max Loss: ${:.2}", report.max_loss);
+    info!("Profit Area: {:.2}%", report.profit_area);
+    info!(
+        "Management: {} paths, {} defended, {} held to expiration",
+        management.simulations, management.defended, management.held_to_expiration
+    );
+    info!("Win Rate: {:.2}%", management.win_rate * dec!(100));
+    info!(
+        "P&L: avg ${:.2}, best ${:.2}, worst ${:.2}",
+        management.average_pnl, management.best_pnl, management.worst_pnl
+    );
+    if let Some(chart) = report.chart {
+        info!("Chart written to {:?}", chart);
+    }
+    Ok(())
+}
@@ -0,0 +1,240 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # OptionStratLib Cookbook
+//!
+//! End-to-end recipes combining several subsystems of the library. Each recipe
+//! is a plain function returning a report so the same code backs the runnable
+//! binaries in `src/bin` and the integration tests in `tests`.
+//!
+//! Charts are only written when the `plot` feature is enabled:
+//!
+//! ```bash
+//! cargo run --package examples_cookbook --bin cookbook_iron_condor
+//! cargo run --package examples_cookbook --features plot --bin cookbook_iron_condor
+//! ```
+
+use optionstratlib::prelude::*;
+use positive::pos_or_panic;
+use std::path::PathBuf;
+
+/// Walker used by the management simulation.
+struct Walker;
+
+impl WalkTypeAble<Positive, Positive> for Walker {}
+
+/// Inputs of the iron condor recipe.
+#[derive(Debug, Clone)]
+pub struct IronCondorRecipe {
+    /// JSON option chain to load.
+    pub chain_path: String,
+    /// Days to expiration of the condor.
+    pub days: Positive,
+    /// Number of simulated underlying paths.
+    pub simulations: usize,
+    /// Annualized volatility of the simulated paths.
+    pub volatility: Positive,
+    /// Where to write the payoff chart when the `plot` feature is enabled.
+    pub chart_path: Option<PathBuf>,
+}
+
+impl IronCondorRecipe {
+    /// Creates a recipe for the given chain with five days to expiration and 50 paths.
+    pub fn new<S: Into<String>>(chain_path: S) -> Self {
+        IronCondorRecipe {
+            chain_path: chain_path.into(),
+            days: pos_or_panic!(5.0),
+            simulations: 50,
+            volatility: pos_or_panic!(0.18),
+            chart_path: None,
+        }
+    }
+}
+
+/// Outcome of managing the condor over the simulated paths.
+///
+/// The management rule is the classic "defend the short strikes": the trade is
+/// closed as soon as the underlying trades through either short strike and is
+/// otherwise held to expiration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManagementSummary {
+    /// Number of simulated paths.
+    pub simulations: usize,
+    /// Paths closed early because a short strike was breached.
+    pub defended: usize,
+    /// Paths held to expiration.
+    pub held_to_expiration: usize,
+    /// Fraction of paths closed with a profit.
+    pub win_rate: Decimal,
+    /// Average P&L per path.
+    pub average_pnl: Decimal,
+    /// Best P&L of any path.
+    pub best_pnl: Decimal,
+    /// Worst P&L of any path.
+    pub worst_pnl: Decimal,
+}
+
+/// Report produced by [`run_iron_condor`].
+#[derive(Debug, Clone)]
+pub struct IronCondorReport {
+    /// Strategy title.
+    pub title: String,
+    /// Underlying price of the loaded chain.
+    pub underlying_price: Positive,
+    /// Long put, short put, short call and long call strikes.
+    pub strikes: [Positive; 4],
+    /// Break-even points of the optimized condor.
+    pub break_even_points: Vec<Positive>,
+    /// Net premium received.
+    pub net_premium: Decimal,
+    /// Maximum profit.
+    pub max_profit: Positive,
+    /// Maximum loss.
+    pub max_loss: Positive,
+    /// Profit area in percent.
+    pub profit_area: Decimal,
+    /// Management simulation results.
+    pub management: ManagementSummary,
+    /// Payoff chart written, if any.
+    pub chart: Option<PathBuf>,
+}
+
+/// Loads a chain, optimizes an iron condor on it, optionally plots it and
+/// simulates its management over random underlying paths.
+///
+/// # Errors
+///
+/// Returns an error if the chain cannot be loaded, the strategy cannot be
+/// evaluated or the simulation fails.
+pub fn run_iron_condor(recipe: &IronCondorRecipe) -> Result<IronCondorReport, Error> {
+    let option_chain = OptionChain::load_from_json(&recipe.chain_path)?;
+    let underlying_price = option_chain.underlying_price;
+
+    let mut strategy = IronCondor::new(
+        option_chain.symbol.clone(),
+        underlying_price, // underlying_price
+        Positive::ZERO,   // short_call_strike
+        Positive::ZERO,   // short_put_strike
+        Positive::ZERO,   // long_call_strike
+        Positive::ZERO,   // long_put_strike
+        ExpirationDate::Days(recipe.days),
+        Positive::ZERO, // implied_volatility
+        Decimal::ZERO,  // risk_free_rate
+        Positive::ZERO, // dividend_yield
+        Positive::ONE,  // quantity
+        Positive::ZERO, // premium_short_call
+        Positive::ZERO, // premium_short_put
+        Positive::ZERO, // premium_long_call
+        Positive::ZERO, // premium_long_put
+        Positive::ONE,  // open_fee
+        Positive::ONE,  // close_fee
+    );
+    strategy.get_best_area(&option_chain, FindOptimalSide::All);
+    strategy.validate();
+
+    let chart = write_chart(&strategy, recipe.chart_path.as_ref())?;
+    let management = simulate_management(&strategy, underlying_price, recipe)?;
+
+    Ok(IronCondorReport {
+        title: strategy.get_title(),
+        underlying_price,
+        strikes: [
+            strategy.long_put.option.strike_price,
+            strategy.short_put.option.strike_price,
+            strategy.short_call.option.strike_price,
+            strategy.long_call.option.strike_price,
+        ],
+        break_even_points: strategy.break_even_points.clone(),
+        net_premium: strategy.get_net_premium_received()?.to_dec(),
+        max_profit: strategy.get_max_profit()?,
+        max_loss: strategy.get_max_loss()?,
+        profit_area: strategy.get_profit_area()?,
+        management,
+        chart,
+    })
+}
+
+#[cfg(feature = "plot")]
+fn write_chart(strategy: &IronCondor, path: Option<&PathBuf>) -> Result<Option<PathBuf>, Error> {
+    match path {
+        Some(path) => {
+            strategy.write_png(path)?;
+            Ok(Some(path.clone()))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(not(feature = "plot"))]
+fn write_chart(_strategy: &IronCondor, _path: Option<&PathBuf>) -> Result<Option<PathBuf>, Error> {
+    Ok(None)
+}
+
+fn simulate_management(
+    strategy: &IronCondor,
+    underlying_price: Positive,
+    recipe: &IronCondorRecipe,
+) -> Result<ManagementSummary, Error> {
+    let n_steps = (recipe.days * 24.0).to_f64() as usize;
+    let dt = convert_time_frame(
+        Positive::ONE / recipe.days,
+        &TimeFrame::Hour,
+        &TimeFrame::Day,
+    );
+    let volatility_dt = volatility_for_dt(recipe.volatility, dt, TimeFrame::Hour, TimeFrame::Day)?;
+    let walk_params = WalkParams {
+        size: n_steps,
+        init_step: Step {
+            x: Xstep::new(
+                Positive::ONE,
+                TimeFrame::Hour,
+                ExpirationDate::Days(recipe.days),
+            ),
+            y: Ystep::new(0, underlying_price),
+        },
+        walk_type: WalkType::GeometricBrownian {
+            dt,
+            drift: Decimal::ZERO,
+            volatility: volatility_dt,
+        },
+        walker: Box::new(Walker),
+    };
+    let simulator = Simulator::new(
+        "Iron Condor Management".to_string(),
+        recipe.simulations,
+        &walk_params,
+        generator_positive,
+    );
+
+    let lower = strategy.short_put.option.strike_price;
+    let upper = strategy.short_call.option.strike_price;
+    let mut pnls = Vec::with_capacity(recipe.simulations);
+    let mut defended = 0;
+    for walk in simulator.get_random_walks() {
+        let prices: Vec<Positive> = walk.get_steps().iter().map(|s| *s.get_value()).collect();
+        let breach = prices.iter().find(|p| **p < lower || **p > upper);
+        let exit_price = match breach {
+            Some(price) => {
+                defended += 1;
+                *price
+            }
+            None => *prices.last().unwrap_or(&underlying_price),
+        };
+        pnls.push(strategy.calculate_profit_at(&exit_price)?);
+    }
+
+    let count = Decimal::from(pnls.len().max(1));
+    let winners = pnls.iter().filter(|p| **p > Decimal::ZERO).count();
+    Ok(ManagementSummary {
+        simulations: pnls.len(),
+        defended,
+        held_to_expiration: pnls.len() - defended,
+        win_rate: Decimal::from(winners) / count,
+        average_pnl: pnls.iter().sum::<Decimal>() / count,
+        best_pnl: pnls.iter().copied().max().unwrap_or_default(),
+        worst_pnl: pnls.iter().copied().min().unwrap_or_default(),
+    })
+}
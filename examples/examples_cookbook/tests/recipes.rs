@@ -0,0 +1,34 @@
+use examples_cookbook::{IronCondorRecipe, run_iron_condor};
+use optionstratlib::prelude::*;
+
+const SP500_CHAIN: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../Chains/SP500-18-oct-2024-5781.88.json"
+);
+
+#[test]
+fn test_iron_condor_recipe() {
+    let mut recipe = IronCondorRecipe::new(SP500_CHAIN);
+    recipe.simulations = 10;
+    let report = run_iron_condor(&recipe).unwrap();
+
+    let [long_put, short_put, short_call, long_call] = report.strikes;
+    assert!(long_put < short_put);
+    assert!(short_put < short_call);
+    assert!(short_call < long_call);
+    assert_eq!(report.break_even_points.len(), 2);
+    assert!(report.net_premium > Decimal::ZERO);
+
+    let management = &report.management;
+    assert_eq!(management.simulations, 10);
+    assert_eq!(management.defended + management.held_to_expiration, 10);
+    assert!(management.worst_pnl >= -report.max_loss.to_dec());
+    assert!(management.best_pnl <= report.max_profit.to_dec());
+    assert!(report.chart.is_none());
+}
+
+#[test]
+fn test_iron_condor_recipe_missing_chain() {
+    let recipe = IronCondorRecipe::new("does/not/exist.json");
+    assert!(run_iron_condor(&recipe).is_err());
+}
@@ -5,7 +5,9 @@
 ******************************************************************************/
 
 use optionstratlib::greeks::Greeks;
-use optionstratlib::model::types::{BarrierType, OptionStyle, OptionType, Side};
+use optionstratlib::model::types::{
+    BarrierType, DayCount, OptionStyle, OptionType, SettlementType, Side,
+};
 use optionstratlib::pricing::unified::{Priceable, PricingEngine};
 use optionstratlib::{ExpirationDate, Options};
 use positive::pos_or_panic;
@@ -62,6 +64,8 @@ fn main() {
                 option_style: *style,
                 dividend_yield: pos_or_panic!(dividend_yield),
                 exotic_params: None,
+                settlement_type: SettlementType::Physical,
+                day_count: DayCount::Act365Fixed,
             };
 
             let price = option.price(&PricingEngine::ClosedFormBS).unwrap();
@@ -45,16 +45,13 @@ fn main() {
             rainbow_second_asset_price: None,
             rainbow_second_asset_volatility: None,
             rainbow_second_asset_dividend: None,
-            rainbow_correlation: None,
             spread_second_asset_volatility: None,
             spread_second_asset_dividend: None,
-            spread_correlation: None,
             quanto_fx_volatility: None,
-            quanto_fx_correlation: None,
             quanto_foreign_rate: None,
             exchange_second_asset_volatility: None,
             exchange_second_asset_dividend: None,
-            exchange_correlation: None,
+            correlation: None,
         }),
     );
 
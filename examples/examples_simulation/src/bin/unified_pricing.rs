@@ -64,6 +64,8 @@ fn main() {
         option_style: OptionStyle::Call,
         dividend_yield: pos_or_panic!(0.02),
         exotic_params: None,
+        settlement_type: SettlementType::Physical,
+        day_count: DayCount::Act365Fixed,
     };
 
     println!("Option Details:");
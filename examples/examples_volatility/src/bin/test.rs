@@ -35,6 +35,8 @@ fn calculate_error(
         option_style,
         dividend_yield,
         exotic_params: None,
+        settlement_type: SettlementType::Physical,
+        day_count: DayCount::Act365Fixed,
     };
     let price = black_scholes(&option)?;
 
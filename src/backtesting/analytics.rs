@@ -0,0 +1,375 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Performance Analytics
+//!
+//! [`PerformanceAnalytics`] summarizes a backtest from its profit and loss
+//! curve and the results of its closed trades:
+//!
+//! * returns: total return and compound annual growth rate (CAGR);
+//! * risk: annualized volatility and downside deviation of the session
+//!   returns, Sharpe and Sortino ratios, and the deepest drawdown with the
+//!   longest time spent below a previous peak;
+//! * trades: win rate, profit factor and average winner and loser.
+//!
+//! Session returns are annualized with the number of sessions per year
+//! observed in the curve, so daily, weekly or intraday replays need no
+//! configuration. Ratios that cannot be computed, such as a Sharpe ratio
+//! without volatility or a profit factor without losing trades, are `None`.
+
+use crate::backtesting::engine::RuleBacktestReport;
+use crate::backtesting::metrics::GeneralPerformanceMetrics;
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const DAYS_PER_YEAR: Decimal = Decimal::from_parts(36525, 0, 0, false, 2);
+
+/// Performance of a backtest, serializable for reporting.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PerformanceAnalytics {
+    /// Capital at the start of the test.
+    pub initial_capital: Positive,
+    /// Capital plus profit or loss at the end of the test.
+    pub final_equity: Decimal,
+    /// Return over the whole test, as a fraction of the initial capital.
+    pub total_return: Decimal,
+    /// Compound annual growth rate, `None` if the test spans no time or the
+    /// capital was lost.
+    pub cagr: Option<Decimal>,
+    /// Annualized standard deviation of the session returns.
+    pub volatility: Option<Decimal>,
+    /// Annualized deviation of the session returns below the risk-free rate.
+    pub downside_deviation: Option<Decimal>,
+    /// Annualized excess return over volatility.
+    pub sharpe_ratio: Option<Decimal>,
+    /// Annualized excess return over downside deviation.
+    pub sortino_ratio: Option<Decimal>,
+    /// Largest fall from a peak of equity, as a fraction of the peak.
+    pub max_drawdown: Decimal,
+    /// Longest time in days spent below a previous peak, up to the end of
+    /// the test if equity has not recovered.
+    pub max_drawdown_duration: Positive,
+    /// Number of closed trades.
+    pub number_of_trades: usize,
+    /// Trades closed with a profit.
+    pub winners: usize,
+    /// Trades closed with a loss.
+    pub losers: usize,
+    /// Fraction of the trades closed with a profit.
+    pub win_rate: Option<Decimal>,
+    /// Gross profit of the winners over gross loss of the losers.
+    pub profit_factor: Option<Decimal>,
+    /// Average profit of the winners.
+    pub average_winner: Option<Decimal>,
+    /// Average loss of the losers, as a negative amount.
+    pub average_loser: Option<Decimal>,
+}
+
+impl PerformanceAnalytics {
+    /// Computes the analytics of a test.
+    ///
+    /// # Arguments
+    ///
+    /// * `profit_loss_curve` - Cumulative profit or loss after each session,
+    ///   in chronological order.
+    /// * `trade_results` - Profit or loss of every closed trade.
+    /// * `initial_capital` - Capital the profit or loss is measured against.
+    /// * `risk_free_rate` - Annual risk-free rate for the Sharpe and Sortino
+    ///   ratios.
+    pub fn compute(
+        profit_loss_curve: &[(DateTime<Utc>, Decimal)],
+        trade_results: &[Decimal],
+        initial_capital: Positive,
+        risk_free_rate: Decimal,
+    ) -> Self {
+        let capital = initial_capital.to_dec();
+        let equity: Vec<(DateTime<Utc>, Decimal)> = profit_loss_curve
+            .iter()
+            .map(|(timestamp, profit_loss)| (*timestamp, capital + profit_loss))
+            .collect();
+        let final_equity = equity.last().map_or(capital, |(_, value)| *value);
+        let total_return = if capital.is_zero() {
+            Decimal::ZERO
+        } else {
+            final_equity / capital - Decimal::ONE
+        };
+
+        let years = match (equity.first(), equity.last()) {
+            (Some((first, _)), Some((last, _))) => {
+                Decimal::from((*last - *first).num_seconds().max(0))
+                    / Decimal::from(SECONDS_PER_DAY)
+                    / DAYS_PER_YEAR
+            }
+            _ => Decimal::ZERO,
+        };
+        let cagr = (years > Decimal::ZERO && final_equity > Decimal::ZERO && !capital.is_zero())
+            .then(|| {
+                (final_equity / capital)
+                    .checked_ln()
+                    .and_then(|ln| (ln / years).checked_exp())
+                    .map(|growth| growth - Decimal::ONE)
+            })
+            .flatten();
+
+        let returns: Vec<Decimal> = equity
+            .windows(2)
+            .filter(|pair| pair[0].1 > Decimal::ZERO)
+            .map(|pair| pair[1].1 / pair[0].1 - Decimal::ONE)
+            .collect();
+        let risk = ReturnRisk::compute(&returns, years, risk_free_rate);
+        let (max_drawdown, max_drawdown_duration) = drawdown(&equity, capital);
+
+        let winners: Vec<Decimal> = trade_results
+            .iter()
+            .copied()
+            .filter(|result| *result > Decimal::ZERO)
+            .collect();
+        let losers: Vec<Decimal> = trade_results
+            .iter()
+            .copied()
+            .filter(|result| *result < Decimal::ZERO)
+            .collect();
+        let gross_profit: Decimal = winners.iter().sum();
+        let gross_loss: Decimal = losers.iter().sum();
+
+        PerformanceAnalytics {
+            initial_capital,
+            final_equity,
+            total_return,
+            cagr,
+            volatility: risk.as_ref().map(|risk| risk.volatility),
+            downside_deviation: risk.as_ref().map(|risk| risk.downside_deviation),
+            sharpe_ratio: risk.as_ref().and_then(|risk| risk.sharpe_ratio),
+            sortino_ratio: risk.as_ref().and_then(|risk| risk.sortino_ratio),
+            max_drawdown,
+            max_drawdown_duration,
+            number_of_trades: trade_results.len(),
+            winners: winners.len(),
+            losers: losers.len(),
+            win_rate: ratio(Decimal::from(winners.len()), trade_results.len()),
+            profit_factor: (!gross_loss.is_zero()).then(|| gross_profit / gross_loss.abs()),
+            average_winner: ratio(gross_profit, winners.len()),
+            average_loser: ratio(gross_loss, losers.len()),
+        }
+    }
+
+    /// The analytics in the layout of [`GeneralPerformanceMetrics`], for a
+    /// [`BacktestResult`](crate::backtesting::BacktestResult).
+    pub fn general_metrics(&self) -> GeneralPerformanceMetrics {
+        let calmar_ratio = match self.cagr {
+            Some(cagr) if self.max_drawdown > Decimal::ZERO => Some(cagr / self.max_drawdown),
+            _ => None,
+        };
+        let gain_loss_ratio = match (self.average_winner, self.average_loser) {
+            (Some(winner), Some(loser)) => Some(winner / loser.abs()),
+            _ => None,
+        };
+        GeneralPerformanceMetrics {
+            total_return: self.total_return,
+            annualized_return: self.cagr.unwrap_or_default(),
+            volatility: self.volatility.and_then(|v| Positive::new_decimal(v).ok()),
+            downside_deviation: self
+                .downside_deviation
+                .and_then(|v| Positive::new_decimal(v).ok()),
+            sharpe_ratio: self.sharpe_ratio,
+            sortino_ratio: self.sortino_ratio,
+            calmar_ratio,
+            win_rate: self.win_rate,
+            profit_factor: self.profit_factor,
+            avg_gain: self.average_winner,
+            avg_loss: self.average_loser,
+            gain_loss_ratio,
+        }
+    }
+}
+
+impl RuleBacktestReport {
+    /// Performance of the test on `initial_capital`, from its equity curve
+    /// and the trades it closed.
+    pub fn analytics(
+        &self,
+        initial_capital: Positive,
+        risk_free_rate: Decimal,
+    ) -> PerformanceAnalytics {
+        let trade_results: Vec<Decimal> = self
+            .closed_trades()
+            .map(|trade| trade.profit_loss)
+            .collect();
+        PerformanceAnalytics::compute(
+            &self.equity_curve,
+            &trade_results,
+            initial_capital,
+            risk_free_rate,
+        )
+    }
+}
+
+/// Annualized dispersion of session returns.
+struct ReturnRisk {
+    volatility: Decimal,
+    downside_deviation: Decimal,
+    sharpe_ratio: Option<Decimal>,
+    sortino_ratio: Option<Decimal>,
+}
+
+impl ReturnRisk {
+    /// `None` with fewer than two returns or when the curve spans no time.
+    fn compute(returns: &[Decimal], years: Decimal, risk_free_rate: Decimal) -> Option<Self> {
+        if returns.len() < 2 || years <= Decimal::ZERO {
+            return None;
+        }
+        let count = Decimal::from(returns.len());
+        let periods_per_year = count / years;
+        let annualizer = periods_per_year.sqrt()?;
+        let risk_free = risk_free_rate / periods_per_year;
+
+        let mean = returns.iter().sum::<Decimal>() / count;
+        let variance = returns
+            .iter()
+            .map(|r| (*r - mean) * (*r - mean))
+            .sum::<Decimal>()
+            / (count - Decimal::ONE);
+        let downside_variance = returns
+            .iter()
+            .map(|r| (*r - risk_free).min(Decimal::ZERO))
+            .map(|shortfall| shortfall * shortfall)
+            .sum::<Decimal>()
+            / count;
+        let deviation = variance.sqrt()?;
+        let downside = downside_variance.sqrt()?;
+        let excess = mean - risk_free;
+
+        Some(ReturnRisk {
+            volatility: deviation * annualizer,
+            downside_deviation: downside * annualizer,
+            sharpe_ratio: (!deviation.is_zero()).then(|| excess / deviation * annualizer),
+            sortino_ratio: (!downside.is_zero()).then(|| excess / downside * annualizer),
+        })
+    }
+}
+
+/// Deepest drawdown as a fraction of the peak, and longest time in days
+/// below a peak. The initial capital is the first peak.
+fn drawdown(equity: &[(DateTime<Utc>, Decimal)], capital: Decimal) -> (Decimal, Positive) {
+    let Some((start, _)) = equity.first() else {
+        return (Decimal::ZERO, Positive::ZERO);
+    };
+    let (mut peak, mut peak_time) = (capital, *start);
+    let mut max_drawdown = Decimal::ZERO;
+    let mut longest = chrono::Duration::zero();
+    // Time of the peak the equity is currently below, if any.
+    let mut below_since: Option<DateTime<Utc>> = None;
+    for (timestamp, value) in equity {
+        if *value >= peak {
+            if let Some(since) = below_since.take() {
+                longest = longest.max(*timestamp - since);
+            }
+            peak = *value;
+            peak_time = *timestamp;
+        } else {
+            below_since.get_or_insert(peak_time);
+            if peak > Decimal::ZERO {
+                max_drawdown = max_drawdown.max((peak - value) / peak);
+            }
+        }
+    }
+    if let (Some(since), Some((end, _))) = (below_since, equity.last()) {
+        longest = longest.max(*end - since);
+    }
+    let days = Decimal::from(longest.num_seconds()) / Decimal::from(SECONDS_PER_DAY);
+    (
+        max_drawdown,
+        Positive::new_decimal(days).unwrap_or(Positive::ZERO),
+    )
+}
+
+fn ratio(total: Decimal, count: usize) -> Option<Decimal> {
+    (count > 0).then(|| total / Decimal::from(count))
+}
+
+#[cfg(test)]
+mod tests_analytics {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn curve(values: &[Decimal]) -> Vec<(DateTime<Utc>, Decimal)> {
+        let start = Utc.with_ymd_and_hms(2030, 1, 1, 20, 0, 0).unwrap();
+        values
+            .iter()
+            .enumerate()
+            .map(|(day, value)| (start + Duration::days(day as i64), *value))
+            .collect()
+    }
+
+    #[test]
+    fn test_drawdown_and_trade_statistics() {
+        let curve = curve(&[dec!(0), dec!(100), dec!(-50), dec!(0), dec!(150), dec!(120)]);
+        let trades = [dec!(100), dec!(-150), dec!(200), dec!(-30)];
+        let analytics =
+            PerformanceAnalytics::compute(&curve, &trades, pos_or_panic!(1000.0), Decimal::ZERO);
+
+        assert_eq!(analytics.final_equity, dec!(1120));
+        assert_eq!(analytics.total_return, dec!(0.12));
+        // From 1100 to 950.
+        assert_eq!(analytics.max_drawdown.round_dp(6), dec!(0.136364));
+        // Below the peak of day 1 until day 4.
+        assert_eq!(analytics.max_drawdown_duration, pos_or_panic!(3.0));
+        assert_eq!(analytics.number_of_trades, 4);
+        assert_eq!(analytics.win_rate, Some(dec!(0.5)));
+        assert_eq!(analytics.profit_factor, Some(dec!(300) / dec!(180)));
+        assert_eq!(analytics.average_winner, Some(dec!(150)));
+        assert_eq!(analytics.average_loser, Some(dec!(-90)));
+        assert!(analytics.cagr.unwrap() > analytics.total_return);
+        assert!(analytics.sharpe_ratio.unwrap() > Decimal::ZERO);
+        assert!(analytics.sortino_ratio.unwrap() > analytics.sharpe_ratio.unwrap());
+
+        let metrics = analytics.general_metrics();
+        assert_eq!(metrics.total_return, dec!(0.12));
+        assert_eq!(metrics.gain_loss_ratio, Some(dec!(150) / dec!(90)));
+        assert!(metrics.calmar_ratio.is_some());
+
+        let json = serde_json::to_string(&analytics).unwrap();
+        let back: PerformanceAnalytics = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, analytics);
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        let empty = PerformanceAnalytics::compute(&[], &[], pos_or_panic!(1000.0), dec!(0.05));
+        assert_eq!(empty.final_equity, dec!(1000));
+        assert_eq!(empty.total_return, Decimal::ZERO);
+        assert_eq!(empty.cagr, None);
+        assert_eq!(empty.sharpe_ratio, None);
+        assert_eq!(empty.win_rate, None);
+        assert_eq!(empty.max_drawdown, Decimal::ZERO);
+
+        // Losing every session: never back above the initial capital.
+        let losing = curve(&[dec!(-10), dec!(-20), dec!(-30)]);
+        let analytics =
+            PerformanceAnalytics::compute(&losing, &[dec!(-30)], pos_or_panic!(100.0), dec!(0));
+        assert_eq!(analytics.max_drawdown, dec!(0.3));
+        assert_eq!(analytics.max_drawdown_duration, pos_or_panic!(2.0));
+        assert_eq!(analytics.profit_factor, Some(Decimal::ZERO));
+        assert_eq!(analytics.average_winner, None);
+        assert!(analytics.sharpe_ratio.unwrap() < Decimal::ZERO);
+        assert!(analytics.cagr.unwrap() < dec!(-0.99));
+    }
+
+    #[test]
+    fn test_rising_equity_has_no_drawdown() {
+        let rising = curve(&[dec!(10), dec!(20), dec!(30), dec!(40)]);
+        let analytics =
+            PerformanceAnalytics::compute(&rising, &[dec!(40)], pos_or_panic!(100.0), dec!(0));
+        assert_eq!(analytics.max_drawdown, Decimal::ZERO);
+        assert_eq!(analytics.max_drawdown_duration, Positive::ZERO);
+    }
+}
@@ -0,0 +1,585 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Commissions and Slippage
+//!
+//! Trading costs are split in two models:
+//!
+//! * a [`FeeModel`] returns the fees charged for an order, from the number of
+//!   contracts and the premium of each leg: per-contract and per-order
+//!   commissions, tiered schedules, exchange, clearing and regulatory fees,
+//!   or any combination of them;
+//! * a [`SlippageModel`] returns the price an order fills at, from the bid and
+//!   ask of the contract: at mid, by any [`QuotePricing`] convention such as
+//!   a fraction of the spread, or a fixed number of ticks from mid.
+//!
+//! [`TradingCosts`] bundles one of each and applies them in the same way
+//! whenever a position is opened, closed or rolled, in paper trading through
+//! its [`Position`] helpers and in backtests through
+//! [`RuleBacktest::with_costs`](crate::backtesting::RuleBacktest::with_costs).
+
+use crate::error::position::PositionError;
+use crate::model::Options;
+use crate::model::QuotePricing;
+use crate::model::position::Position;
+use crate::model::roll::{Roll, RollTarget};
+use crate::model::types::{Action, Side};
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Bid and ask of a contract, either of which may be missing.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub struct BidAsk {
+    /// Best bid.
+    pub bid: Option<Positive>,
+    /// Best ask.
+    pub ask: Option<Positive>,
+}
+
+impl BidAsk {
+    /// Creates a quote.
+    pub fn new(bid: Option<Positive>, ask: Option<Positive>) -> Self {
+        BidAsk { bid, ask }
+    }
+
+    /// Mid price, or the only side quoted.
+    pub fn mid(&self) -> Option<Positive> {
+        match (self.bid, self.ask) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / Positive::TWO),
+            (bid, ask) => bid.or(ask),
+        }
+    }
+
+    /// Width of the spread, when both sides are quoted.
+    pub fn spread(&self) -> Option<Positive> {
+        Some(self.ask?.saturating_sub(&self.bid?))
+    }
+}
+
+/// A leg of an order, as seen by a fee model.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FeeLeg {
+    /// Number of contracts.
+    pub quantity: Positive,
+    /// Premium per contract.
+    pub price: Positive,
+}
+
+impl FeeLeg {
+    /// Creates a fee leg.
+    pub fn new(quantity: Positive, price: Positive) -> Self {
+        FeeLeg { quantity, price }
+    }
+}
+
+/// Fees charged for an order.
+pub trait FeeModel: Debug + Send + Sync {
+    /// Total fees of an order made of `legs`. An empty order costs nothing.
+    fn order_fee(&self, legs: &[FeeLeg]) -> Positive;
+
+    /// Fees of an order with a single leg.
+    fn leg_fee(&self, quantity: Positive, price: Positive) -> Positive {
+        self.order_fee(&[FeeLeg::new(quantity, price)])
+    }
+}
+
+fn contracts(legs: &[FeeLeg]) -> Positive {
+    legs.iter().map(|leg| leg.quantity).sum()
+}
+
+/// No fees.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub struct NoFees;
+
+impl FeeModel for NoFees {
+    fn order_fee(&self, _legs: &[FeeLeg]) -> Positive {
+        Positive::ZERO
+    }
+}
+
+/// Commission per contract with a minimum per order.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PerContractFee {
+    /// Commission per contract.
+    pub rate: Positive,
+    /// Minimum commission of an order.
+    pub minimum: Positive,
+}
+
+impl PerContractFee {
+    /// Creates a per-contract commission without a minimum.
+    pub fn new(rate: Positive) -> Self {
+        PerContractFee {
+            rate,
+            minimum: Positive::ZERO,
+        }
+    }
+
+    /// Sets the minimum commission of an order.
+    pub fn with_minimum(mut self, minimum: Positive) -> Self {
+        self.minimum = minimum;
+        self
+    }
+}
+
+impl FeeModel for PerContractFee {
+    fn order_fee(&self, legs: &[FeeLeg]) -> Positive {
+        if legs.is_empty() {
+            return Positive::ZERO;
+        }
+        (self.rate * contracts(legs)).max(self.minimum)
+    }
+}
+
+/// Flat commission per order, whatever its size.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PerOrderFee {
+    /// Commission of an order.
+    pub amount: Positive,
+}
+
+impl FeeModel for PerOrderFee {
+    fn order_fee(&self, legs: &[FeeLeg]) -> Positive {
+        if legs.is_empty() {
+            Positive::ZERO
+        } else {
+            self.amount
+        }
+    }
+}
+
+/// A band of a tiered commission schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FeeTier {
+    /// Last contract of the order charged at this rate, `None` for no limit.
+    pub up_to: Option<Positive>,
+    /// Commission per contract within the band.
+    pub rate: Positive,
+}
+
+/// Commission per contract that changes with the size of the order.
+///
+/// Bands are marginal, like tax brackets: with bands up to 10 contracts at
+/// 0.65 and above at 0.50, an order of 15 contracts pays 10 × 0.65 + 5 × 0.50.
+/// Contracts beyond the last band are charged at its rate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TieredFee {
+    /// Bands in increasing order of `up_to`.
+    pub tiers: Vec<FeeTier>,
+}
+
+impl TieredFee {
+    /// Creates an empty schedule.
+    pub fn new() -> Self {
+        TieredFee { tiers: Vec::new() }
+    }
+
+    /// Adds a band charging `rate` per contract up to contract `up_to` of the order.
+    pub fn with_tier(mut self, up_to: Option<Positive>, rate: Positive) -> Self {
+        self.tiers.push(FeeTier { up_to, rate });
+        self
+    }
+}
+
+impl Default for TieredFee {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeeModel for TieredFee {
+    fn order_fee(&self, legs: &[FeeLeg]) -> Positive {
+        let mut remaining = contracts(legs);
+        let mut charged = Positive::ZERO;
+        let mut fee = Positive::ZERO;
+        for (index, tier) in self.tiers.iter().enumerate() {
+            if remaining == Positive::ZERO {
+                break;
+            }
+            let band = match tier.up_to {
+                Some(up_to) if index + 1 < self.tiers.len() => {
+                    up_to.saturating_sub(&charged).min(remaining)
+                }
+                _ => remaining,
+            };
+            fee += band * tier.rate;
+            charged += band;
+            remaining = remaining.saturating_sub(&band);
+        }
+        fee
+    }
+}
+
+/// Exchange, clearing and regulatory fees passed through by the broker.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExchangeFee {
+    /// Exchange fee per contract.
+    pub exchange_per_contract: Positive,
+    /// Clearing fee per contract.
+    pub clearing_per_contract: Positive,
+    /// Regulatory fee as a fraction of the premium traded.
+    pub regulatory_rate: Positive,
+}
+
+impl FeeModel for ExchangeFee {
+    fn order_fee(&self, legs: &[FeeLeg]) -> Positive {
+        let premium: Positive = legs.iter().map(|leg| leg.quantity * leg.price).sum();
+        (self.exchange_per_contract + self.clearing_per_contract) * contracts(legs)
+            + premium * self.regulatory_rate
+    }
+}
+
+/// Sum of several fee models, such as a commission plus exchange fees.
+#[derive(Debug, Clone, Default)]
+pub struct CombinedFees {
+    /// Models whose fees are added.
+    pub models: Vec<Arc<dyn FeeModel>>,
+}
+
+impl CombinedFees {
+    /// Creates an empty combination.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a fee model.
+    pub fn with<F: FeeModel + 'static>(mut self, model: F) -> Self {
+        self.models.push(Arc::new(model));
+        self
+    }
+}
+
+impl FeeModel for CombinedFees {
+    fn order_fee(&self, legs: &[FeeLeg]) -> Positive {
+        self.models.iter().map(|model| model.order_fee(legs)).sum()
+    }
+}
+
+/// Price an order fills at.
+pub trait SlippageModel: Debug + Send + Sync {
+    /// Fill price of buying or selling a contract quoted at `quote`, or
+    /// `None` if the contract has no quote.
+    fn fill_price(&self, action: Action, quote: &BidAsk) -> Option<Positive>;
+}
+
+/// Fills at the mid price.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub struct NoSlippage;
+
+impl SlippageModel for NoSlippage {
+    fn fill_price(&self, _action: Action, quote: &BidAsk) -> Option<Positive> {
+        quote.mid()
+    }
+}
+
+/// Fills at the execution price of the convention, such as
+/// [`QuotePricing::Slippage`] for a fraction of the spread paid over mid.
+/// Contracts quoted on one side only fill at that side.
+impl SlippageModel for QuotePricing {
+    fn fill_price(&self, action: Action, quote: &BidAsk) -> Option<Positive> {
+        let (Some(bid), Some(ask)) = (quote.bid, quote.ask) else {
+            return quote.mid();
+        };
+        let side = match action {
+            Action::Sell => Side::Short,
+            _ => Side::Long,
+        };
+        Some(self.execution_price(bid, ask, side))
+    }
+}
+
+/// Fills a fixed number of ticks from mid, never below zero.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FixedTicks {
+    /// Ticks given up on every fill.
+    pub ticks: Positive,
+    /// Size of a tick.
+    pub tick_size: Positive,
+}
+
+impl SlippageModel for FixedTicks {
+    fn fill_price(&self, action: Action, quote: &BidAsk) -> Option<Positive> {
+        let mid = quote.mid()?;
+        let slippage = self.ticks * self.tick_size;
+        Some(match action {
+            Action::Sell => mid.saturating_sub(&slippage),
+            _ => mid + slippage,
+        })
+    }
+}
+
+/// Fee and slippage models applied to every execution.
+#[derive(Debug, Clone)]
+pub struct TradingCosts {
+    /// Fees charged per order.
+    pub fees: Arc<dyn FeeModel>,
+    /// Fill prices relative to the quote.
+    pub slippage: Arc<dyn SlippageModel>,
+}
+
+impl Default for TradingCosts {
+    fn default() -> Self {
+        TradingCosts {
+            fees: Arc::new(NoFees),
+            slippage: Arc::new(NoSlippage),
+        }
+    }
+}
+
+/// Action that opens a position on `side`.
+fn opening_action(side: Side) -> Action {
+    match side {
+        Side::Long => Action::Buy,
+        Side::Short => Action::Sell,
+    }
+}
+
+/// Action that closes a position on `side`.
+fn closing_action(side: Side) -> Action {
+    match side {
+        Side::Long => Action::Sell,
+        Side::Short => Action::Buy,
+    }
+}
+
+impl TradingCosts {
+    /// Creates costs from a fee and a slippage model.
+    pub fn new<F, S>(fees: F, slippage: S) -> Self
+    where
+        F: FeeModel + 'static,
+        S: SlippageModel + 'static,
+    {
+        TradingCosts {
+            fees: Arc::new(fees),
+            slippage: Arc::new(slippage),
+        }
+    }
+
+    /// Fill price of opening a position on `side`.
+    pub fn open_price(&self, side: Side, quote: &BidAsk) -> Option<Positive> {
+        self.slippage.fill_price(opening_action(side), quote)
+    }
+
+    /// Fill price of closing a position on `side`.
+    pub fn close_price(&self, side: Side, quote: &BidAsk) -> Option<Positive> {
+        self.slippage.fill_price(closing_action(side), quote)
+    }
+
+    /// Fee per contract of a single leg order, the form positions store fees in.
+    pub fn fee_per_contract(&self, quantity: Positive, price: Positive) -> Positive {
+        if quantity == Positive::ZERO {
+            return Positive::ZERO;
+        }
+        self.fees.leg_fee(quantity, price) / quantity
+    }
+
+    /// Opens a position in `option` at the slipped price of `quote`. The
+    /// closing fee is estimated as the fee of closing at the same price.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the contract has no quote.
+    pub fn open_position(
+        &self,
+        option: Options,
+        quote: &BidAsk,
+        date: DateTime<Utc>,
+    ) -> Result<Position, PositionError> {
+        let price = self
+            .open_price(option.side, quote)
+            .ok_or_else(|| PositionError::invalid_position("No quote to open the position at"))?;
+        let fee = self.fee_per_contract(option.quantity, price);
+        Ok(Position::new(option, price, date, fee, fee, None, None))
+    }
+
+    /// Closes every open contract of `position` at the slipped price of
+    /// `quote` and returns the P&L it realizes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the contract has no quote or the position
+    /// is already closed.
+    pub fn close_position(
+        &self,
+        position: &mut Position,
+        quote: &BidAsk,
+        date: DateTime<Utc>,
+    ) -> Result<Decimal, PositionError> {
+        let price = self
+            .close_price(position.option.side, quote)
+            .ok_or_else(|| PositionError::invalid_position("No quote to close the position at"))?;
+        position.close_fee = self.fee_per_contract(position.option.quantity, price);
+        position.close_all(price, date)
+    }
+
+    /// Rolls `position`, closing it at the slipped price of `close_quote` and
+    /// opening the replacement at the slipped price of `open_quote`, with the
+    /// fees of both orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if either contract has no quote or the roll
+    /// is invalid.
+    pub fn roll_position(
+        &self,
+        position: &Position,
+        target: &RollTarget,
+        close_quote: &BidAsk,
+        open_quote: &BidAsk,
+        date: DateTime<Utc>,
+    ) -> Result<Roll, PositionError> {
+        let side = position.option.side;
+        let quantity = position.option.quantity;
+        let close_price = self.close_price(side, close_quote).ok_or_else(|| {
+            PositionError::invalid_position("No quote to close the rolled leg at")
+        })?;
+        let open_price = self
+            .open_price(side, open_quote)
+            .ok_or_else(|| PositionError::invalid_position("No quote to open the new leg at"))?;
+        let mut leg = position.clone();
+        leg.close_fee = self.fee_per_contract(quantity, close_price);
+        leg.open_fee = self.fee_per_contract(quantity, open_price);
+        leg.roll(target, close_price, open_price, date)
+    }
+}
+
+#[cfg(test)]
+mod tests_costs {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::types::{OptionStyle, OptionType};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn legs(quantities: &[f64]) -> Vec<FeeLeg> {
+        quantities
+            .iter()
+            .map(|q| FeeLeg::new(pos_or_panic!(*q), Positive::TWO))
+            .collect()
+    }
+
+    #[test]
+    fn test_fee_models() {
+        let per_contract = PerContractFee::new(pos_or_panic!(0.65)).with_minimum(Positive::ONE);
+        assert_eq!(per_contract.order_fee(&legs(&[1.0])), Positive::ONE);
+        assert_eq!(
+            per_contract.order_fee(&legs(&[2.0, 2.0])),
+            pos_or_panic!(2.6)
+        );
+        assert_eq!(per_contract.order_fee(&[]), Positive::ZERO);
+
+        let per_order = PerOrderFee {
+            amount: pos_or_panic!(4.95),
+        };
+        assert_eq!(
+            per_order.order_fee(&legs(&[10.0, 10.0])),
+            pos_or_panic!(4.95)
+        );
+
+        let tiered = TieredFee::new()
+            .with_tier(spos!(10.0), pos_or_panic!(0.65))
+            .with_tier(None, pos_or_panic!(0.5));
+        assert_eq!(tiered.order_fee(&legs(&[5.0])), pos_or_panic!(3.25));
+        assert_eq!(tiered.order_fee(&legs(&[10.0, 5.0])), pos_or_panic!(9.0));
+
+        let exchange = ExchangeFee {
+            exchange_per_contract: pos_or_panic!(0.3),
+            clearing_per_contract: pos_or_panic!(0.02),
+            regulatory_rate: pos_or_panic!(0.001),
+        };
+        // 4 contracts: 4 × 0.32 plus 0.1% of 8 in premium.
+        assert_eq!(exchange.order_fee(&legs(&[2.0, 2.0])), pos_or_panic!(1.288));
+
+        let combined = CombinedFees::new().with(per_contract).with(exchange);
+        assert_eq!(combined.order_fee(&legs(&[2.0, 2.0])), pos_or_panic!(3.888));
+    }
+
+    #[test]
+    fn test_slippage_models() {
+        let quote = BidAsk::new(spos!(1.0), spos!(1.2));
+        assert_eq!(quote.mid(), spos!(1.1));
+        assert_eq!(NoSlippage.fill_price(Action::Buy, &quote), spos!(1.1));
+
+        let half = QuotePricing::Slippage {
+            fraction: dec!(0.25),
+        };
+        assert_eq!(half.fill_price(Action::Buy, &quote), spos!(1.15));
+        assert_eq!(half.fill_price(Action::Sell, &quote), spos!(1.05));
+        let one_sided = BidAsk::new(None, spos!(1.2));
+        assert_eq!(half.fill_price(Action::Sell, &one_sided), spos!(1.2));
+        assert_eq!(half.fill_price(Action::Buy, &BidAsk::default()), None);
+
+        let ticks = FixedTicks {
+            ticks: Positive::TWO,
+            tick_size: pos_or_panic!(0.05),
+        };
+        assert_eq!(ticks.fill_price(Action::Buy, &quote), spos!(1.2));
+        let cheap = BidAsk::new(spos!(0.0), spos!(0.1));
+        assert_eq!(ticks.fill_price(Action::Sell, &cheap), Some(Positive::ZERO));
+    }
+
+    #[test]
+    fn test_positions_opened_closed_and_rolled_with_costs() {
+        let costs = TradingCosts::new(
+            PerContractFee::new(pos_or_panic!(0.5)),
+            QuotePricing::Natural,
+        );
+        let option = Options::new(
+            OptionType::European,
+            Side::Short,
+            "XYZ".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::TWO,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            OptionStyle::Put,
+            Positive::ZERO,
+            None,
+        );
+        let date = Utc::now();
+
+        // Selling at the bid.
+        let position = costs
+            .open_position(option, &BidAsk::new(spos!(3.0), spos!(3.2)), date)
+            .unwrap();
+        assert_eq!(position.premium, pos_or_panic!(3.0));
+        assert_eq!(position.open_fee, pos_or_panic!(0.5));
+
+        // Buying back at the ask: (3.0 - 2.2 - 0.5 - 0.5) × 2.
+        let mut closing = position.clone();
+        let realized = costs
+            .close_position(&mut closing, &BidAsk::new(spos!(2.0), spos!(2.2)), date)
+            .unwrap();
+        assert_eq!(realized, dec!(-0.4));
+        assert!(!closing.is_open());
+
+        let roll = costs
+            .roll_position(
+                &position,
+                &RollTarget::out(ExpirationDate::Days(pos_or_panic!(60.0))),
+                &BidAsk::new(spos!(2.0), spos!(2.2)),
+                &BidAsk::new(spos!(3.5), spos!(3.7)),
+                date,
+            )
+            .unwrap();
+        // Buy back at 2.2 and sell the new leg at 3.5, paying 0.5 on each.
+        assert_eq!(roll.fees, Positive::TWO);
+        assert_eq!(roll.net_credit, dec!(0.6));
+        assert_eq!(roll.replacement.premium, pos_or_panic!(3.5));
+        assert!(
+            costs
+                .open_position(roll.replacement.option.clone(), &BidAsk::default(), date)
+                .is_err()
+        );
+    }
+}
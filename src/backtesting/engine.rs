@@ -0,0 +1,961 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Rule-Based Backtest
+//!
+//! [`RuleBacktest`] replays the sessions of a [`HistoricalChainSource`] and
+//! trades a fixed structure of legs with [`EntryRule`] and [`ExitRule`]:
+//!
+//! 1. The ATM implied volatility of the front expiration is recorded, so IV
+//!    rank is available to the entry rules once history builds up.
+//! 2. Open trades are marked at the mid prices of their expiration. Trades
+//!    past expiration settle at intrinsic value; the others are closed when
+//!    an exit rule triggers.
+//! 3. While fewer trades than the maximum are open, expirations are scanned
+//!    from the nearest and a trade is opened in the first one that satisfies
+//!    the entry rule and quotes every leg.
+//!
+//! Days are measured from the time of each snapshot, not from the wall
+//! clock, so stored chains replay the same way whenever the test runs.
+//!
+//! A scheduled [`EventTimeline`] is passed to both rule sets, so trades can
+//! avoid or be closed ahead of earnings and other events. Its ex-dividend
+//! events drive early assignment: in the last session before an ex-date, a
+//! trade with a short in-the-money call whose extrinsic value is below the
+//! dividend is closed, the call at intrinsic value as its holder exercises
+//! to collect the dividend. The chains already price the underlying ex the
+//! dividend from the ex-date on, so marks need no further adjustment.
+//!
+//! Trades are opened and closed by the exit rules at the fill prices of the
+//! [`TradingCosts`] of the backtest, paying its fees on every order; marks
+//! stay at mid. Settlement at expiration has neither slippage nor fees. With
+//! a [`FillModel::Ladder`] the legs of every order fill together, worked
+//! from mid towards natural through a price ladder, instead of leg by leg at
+//! the prices of the slippage model; orders with a leg quoted on one side
+//! only still fill leg by leg.
+
+use crate::backtesting::costs::{BidAsk, FeeLeg, TradingCosts};
+use crate::backtesting::fills::{ComboLeg, ComboOrder, FillModel, LadderFillSimulator};
+use crate::backtesting::rules::{EntryContext, EntryRule, ExitContext, ExitRule, LegTarget};
+use crate::backtesting::source::{ChainSnapshot, HistoricalChainSource};
+use crate::backtesting::types::ExitReason;
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::error::ChainError;
+use crate::model::events::{EventTimeline, MarketEventKind};
+use crate::model::types::{OptionStyle, Side};
+use crate::volatility::IvHistoryDatabase;
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A leg of a backtest trade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BacktestLeg {
+    /// Whether the leg is bought or sold.
+    pub side: Side,
+    /// Whether the leg is a call or a put.
+    pub option_style: OptionStyle,
+    /// Strike of the leg.
+    pub strike: Positive,
+    /// Number of contracts.
+    pub quantity: Positive,
+    /// Price per contract at entry.
+    pub entry_price: Positive,
+    /// Latest mid price per contract, the exit price once the trade is closed.
+    pub mark: Positive,
+    /// Latest quoted delta per contract.
+    pub delta: Option<Decimal>,
+}
+
+impl BacktestLeg {
+    fn sign(&self) -> Decimal {
+        match self.side {
+            Side::Long => Decimal::ONE,
+            Side::Short => Decimal::NEGATIVE_ONE,
+        }
+    }
+
+    /// Signed value of the leg at `price`: positive when long.
+    pub fn value_at(&self, price: Positive) -> Decimal {
+        self.sign() * self.quantity.to_dec() * price.to_dec()
+    }
+
+    fn quote(&self, option: &OptionData) -> (BidAsk, Option<Decimal>) {
+        match self.option_style {
+            OptionStyle::Call => (
+                BidAsk::new(option.call_bid, option.call_ask),
+                option.delta_call,
+            ),
+            OptionStyle::Put => (
+                BidAsk::new(option.put_bid, option.put_ask),
+                option.delta_put,
+            ),
+        }
+    }
+
+    fn fee_leg(&self, price: Positive) -> FeeLeg {
+        FeeLeg::new(self.quantity, price)
+    }
+
+    fn intrinsic(&self, underlying_price: Positive) -> Positive {
+        match self.option_style {
+            OptionStyle::Call => underlying_price.saturating_sub(&self.strike),
+            OptionStyle::Put => self.strike.saturating_sub(&underlying_price),
+        }
+    }
+}
+
+/// A trade opened by a [`RuleBacktest`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BacktestTrade {
+    /// Time the trade was opened.
+    pub entry_date: DateTime<Utc>,
+    /// Time the trade was closed, `None` while open.
+    pub exit_date: Option<DateTime<Utc>>,
+    /// Expiration of the legs, as formatted by the chain.
+    pub expiration: String,
+    /// Expiration time of the legs.
+    pub expiration_date: DateTime<Utc>,
+    /// Legs of the trade.
+    pub legs: Vec<BacktestLeg>,
+    /// Net premium at entry: positive when paid, negative when received.
+    pub entry_cost: Decimal,
+    /// Fees paid on the orders of the trade.
+    pub fees: Positive,
+    /// Profit or loss after fees, unrealized while the trade is open.
+    pub profit_loss: Decimal,
+    /// Reason the trade was closed, `None` while open.
+    pub exit_reason: Option<ExitReason>,
+}
+
+impl BacktestTrade {
+    /// Whether the trade is still open.
+    pub fn is_open(&self) -> bool {
+        self.exit_date.is_none()
+    }
+
+    /// Net value of the legs at their marks.
+    pub fn value(&self) -> Decimal {
+        self.legs.iter().map(|leg| leg.value_at(leg.mark)).sum()
+    }
+
+    /// Profit or loss as a percentage of the premium paid or received.
+    pub fn profit_loss_percent(&self) -> Decimal {
+        if self.entry_cost.is_zero() {
+            return Decimal::ZERO;
+        }
+        self.profit_loss / self.entry_cost.abs() * Decimal::ONE_HUNDRED
+    }
+
+    /// Net delta of the trade, when every leg has a quoted delta.
+    pub fn net_delta(&self) -> Option<Decimal> {
+        self.legs
+            .iter()
+            .map(|leg| {
+                leg.delta
+                    .map(|delta| leg.sign() * leg.quantity.to_dec() * delta)
+            })
+            .sum()
+    }
+
+    fn mark_profit_loss(&mut self) {
+        self.profit_loss = self.value() - self.entry_cost - self.fees.to_dec();
+    }
+
+    fn close(&mut self, timestamp: DateTime<Utc>, reason: ExitReason) {
+        self.mark_profit_loss();
+        self.exit_date = Some(timestamp);
+        self.exit_reason = Some(reason);
+    }
+}
+
+/// Outcome of a [`RuleBacktest`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleBacktestReport {
+    /// Underlying symbol.
+    pub symbol: String,
+    /// Number of sessions replayed.
+    pub sessions: usize,
+    /// Trades in the order they were opened, including those still open.
+    pub trades: Vec<BacktestTrade>,
+    /// Cumulative realized and unrealized profit or loss after each session.
+    pub equity_curve: Vec<(DateTime<Utc>, Decimal)>,
+}
+
+impl RuleBacktestReport {
+    /// Trades that have been closed.
+    pub fn closed_trades(&self) -> impl Iterator<Item = &BacktestTrade> {
+        self.trades.iter().filter(|trade| !trade.is_open())
+    }
+
+    /// Trades still open at the end of the test.
+    pub fn open_trades(&self) -> impl Iterator<Item = &BacktestTrade> {
+        self.trades.iter().filter(|trade| trade.is_open())
+    }
+
+    /// Profit or loss of the closed trades.
+    pub fn realized_profit_loss(&self) -> Decimal {
+        self.closed_trades().map(|trade| trade.profit_loss).sum()
+    }
+}
+
+/// Backtest of a structure of legs traded by entry and exit rules.
+#[derive(Debug, Clone)]
+pub struct RuleBacktest {
+    /// Underlying symbol.
+    pub symbol: String,
+    /// Legs opened on every entry.
+    pub legs: Vec<LegTarget>,
+    /// Condition for opening a trade in an expiration.
+    pub entry: EntryRule,
+    /// Condition for closing an open trade.
+    pub exit: ExitRule,
+    /// Most trades open at the same time.
+    pub max_open_trades: usize,
+    /// Sessions of ATM implied volatility used for IV rank.
+    pub iv_window: usize,
+    /// Fees and slippage of every order.
+    pub costs: TradingCosts,
+    /// Whether orders fill leg by leg or as combos.
+    pub fills: FillModel,
+    /// Scheduled market events the rules can refer to.
+    pub events: EventTimeline,
+}
+
+impl RuleBacktest {
+    /// Creates a backtest that opens `legs` in every session with no open
+    /// trade and holds them to expiration.
+    pub fn new(symbol: &str, legs: Vec<LegTarget>) -> Self {
+        RuleBacktest {
+            symbol: symbol.to_string(),
+            legs,
+            entry: EntryRule::default(),
+            exit: ExitRule::default(),
+            max_open_trades: 1,
+            iv_window: crate::volatility::DEFAULT_IV_WINDOW,
+            costs: TradingCosts::default(),
+            fills: FillModel::default(),
+            events: EventTimeline::new(),
+        }
+    }
+
+    /// Sets the entry rule.
+    pub fn with_entry(mut self, entry: EntryRule) -> Self {
+        self.entry = entry;
+        self
+    }
+
+    /// Sets the exit rule.
+    pub fn with_exit(mut self, exit: ExitRule) -> Self {
+        self.exit = exit;
+        self
+    }
+
+    /// Sets the most trades open at the same time.
+    pub fn with_max_open_trades(mut self, max_open_trades: usize) -> Self {
+        self.max_open_trades = max_open_trades.max(1);
+        self
+    }
+
+    /// Sets the number of sessions used for IV rank.
+    pub fn with_iv_window(mut self, iv_window: usize) -> Self {
+        self.iv_window = iv_window.max(1);
+        self
+    }
+
+    /// Sets the fees and slippage of every order.
+    pub fn with_costs(mut self, costs: TradingCosts) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    /// Sets how orders are filled.
+    pub fn with_fill_model(mut self, fills: FillModel) -> Self {
+        self.fills = fills;
+        self
+    }
+
+    /// Sets the scheduled market events.
+    pub fn with_events(mut self, events: EventTimeline) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Replays every session of the symbol in `source`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the backtest has no legs, its price ladder
+    /// is invalid or a snapshot cannot be loaded.
+    pub fn run<S: HistoricalChainSource + ?Sized>(
+        &self,
+        source: &S,
+    ) -> Result<RuleBacktestReport, ChainError> {
+        if self.legs.is_empty() {
+            return Err("A rule backtest needs at least one leg".into());
+        }
+        let mut ladder = self.fills.simulator().map_err(|e| e.to_string())?;
+        let mut iv_history = IvHistoryDatabase::new(self.iv_window);
+        let mut trades: Vec<BacktestTrade> = Vec::new();
+        let mut equity_curve = Vec::new();
+        let closes: Vec<DateTime<Utc>> = source
+            .session_closes(&self.symbol)?
+            .into_iter()
+            .map(|(_, close)| close)
+            .collect();
+
+        for snapshot in source.sessions(&self.symbol)? {
+            let snapshot = snapshot?;
+            let timestamp = snapshot.timestamp;
+            let next_session = closes
+                .get(closes.partition_point(|close| *close <= timestamp))
+                .copied()
+                .unwrap_or(timestamp + chrono::Duration::days(1));
+            let mut expirations: Vec<(&OptionChain, DateTime<Utc>)> = snapshot
+                .chains
+                .iter()
+                .filter_map(|chain| Some((chain, expiration_of(chain)?)))
+                .filter(|(chain, _)| !chain.options.is_empty())
+                .collect();
+            expirations.sort_by_key(|(_, expiration)| *expiration);
+
+            if let Some((front, _)) = expirations
+                .iter()
+                .find(|(_, expiration)| *expiration > timestamp)
+            {
+                // Chains without an ATM implied volatility leave a gap in the history.
+                let _ = iv_history.record_provider(&self.symbol, timestamp, *front);
+            }
+            let iv_rank = iv_history.iv_rank(&self.symbol);
+
+            for trade in trades.iter_mut().filter(|trade| trade.is_open()) {
+                self.manage(trade, &snapshot, next_session, ladder.as_mut());
+            }
+
+            let mut open = trades.iter().filter(|trade| trade.is_open()).count();
+            for (chain, expiration) in &expirations {
+                if open >= self.max_open_trades {
+                    break;
+                }
+                if *expiration <= timestamp
+                    || trades.iter().any(|trade| {
+                        trade.is_open() && trade.expiration == chain.get_expiration_date()
+                    })
+                {
+                    continue;
+                }
+                let context = EntryContext {
+                    timestamp,
+                    chain,
+                    expiration: *expiration,
+                    days_to_expiration: days_between(timestamp, *expiration),
+                    iv_rank,
+                    events: &self.events,
+                };
+                if !self.entry.is_satisfied(&context) {
+                    continue;
+                }
+                if let Some(trade) = self.open(chain, *expiration, timestamp, ladder.as_mut()) {
+                    trades.push(trade);
+                    open += 1;
+                }
+            }
+
+            equity_curve.push((timestamp, trades.iter().map(|t| t.profit_loss).sum()));
+        }
+
+        Ok(RuleBacktestReport {
+            symbol: self.symbol.clone(),
+            sessions: equity_curve.len(),
+            trades,
+            equity_curve,
+        })
+    }
+
+    /// Opens the legs in `chain` at their fill prices, or `None` if a leg
+    /// has no strike at its delta or no quote.
+    fn open(
+        &self,
+        chain: &OptionChain,
+        expiration_date: DateTime<Utc>,
+        timestamp: DateTime<Utc>,
+        ladder: Option<&mut LadderFillSimulator>,
+    ) -> Option<BacktestTrade> {
+        let quoted = self
+            .legs
+            .iter()
+            .map(|target| {
+                let strike = chain
+                    .strike_for_delta(target.delta, target.option_style)
+                    .ok()?
+                    .nearest_strike;
+                let mut leg = BacktestLeg {
+                    side: target.side,
+                    option_style: target.option_style,
+                    strike,
+                    quantity: target.quantity,
+                    entry_price: Positive::ZERO,
+                    mark: Positive::ZERO,
+                    delta: None,
+                };
+                let option = find_strike(chain, strike)?;
+                let (quote, delta) = leg.quote(option);
+                leg.mark = quote.mid()?;
+                leg.delta = delta;
+                Some((leg, quote))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let prices = self.fill_prices(&quoted, false, ladder);
+        let legs = quoted
+            .into_iter()
+            .zip(prices)
+            .map(|((leg, _), price)| {
+                Some(BacktestLeg {
+                    entry_price: price?,
+                    ..leg
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let entry_cost = legs.iter().map(|leg| leg.value_at(leg.entry_price)).sum();
+        let order: Vec<FeeLeg> = legs
+            .iter()
+            .map(|leg| leg.fee_leg(leg.entry_price))
+            .collect();
+        let mut trade = BacktestTrade {
+            entry_date: timestamp,
+            exit_date: None,
+            expiration: chain.get_expiration_date(),
+            expiration_date,
+            legs,
+            entry_cost,
+            fees: self.costs.fees.order_fee(&order),
+            profit_loss: Decimal::ZERO,
+            exit_reason: None,
+        };
+        trade.mark_profit_loss();
+        Some(trade)
+    }
+
+    /// Marks an open trade to `snapshot`, settling it at expiration or
+    /// closing it on early assignment ahead of a dividend going ex before
+    /// `next_session` or when an exit rule triggers.
+    fn manage(
+        &self,
+        trade: &mut BacktestTrade,
+        snapshot: &ChainSnapshot,
+        next_session: DateTime<Utc>,
+        ladder: Option<&mut LadderFillSimulator>,
+    ) {
+        let timestamp = snapshot.timestamp;
+        let chain = snapshot.chain(&trade.expiration);
+
+        if timestamp >= trade.expiration_date {
+            let Some(underlying_price) = chain
+                .or(snapshot.chains.first())
+                .map(|chain| chain.underlying_price)
+            else {
+                return;
+            };
+            for leg in &mut trade.legs {
+                leg.mark = leg.intrinsic(underlying_price);
+                leg.delta = None;
+            }
+            trade.close(timestamp, ExitReason::Expiration);
+            return;
+        }
+
+        let Some(chain) = chain else {
+            return;
+        };
+        let mut quotes = Vec::with_capacity(trade.legs.len());
+        for leg in &mut trade.legs {
+            let mut quote = BidAsk::default();
+            if let Some(option) = find_strike(chain, leg.strike) {
+                let delta;
+                (quote, delta) = leg.quote(option);
+                leg.mark = quote.mid().unwrap_or(leg.mark);
+                leg.delta = delta.or(leg.delta);
+            }
+            quotes.push(quote);
+        }
+        trade.mark_profit_loss();
+
+        let dividend = self
+            .events
+            .dividends(&self.symbol, timestamp, next_session)
+            .filter(|(ex_date, _)| *ex_date <= trade.expiration_date)
+            .map(|(_, amount)| amount)
+            .max();
+        if let Some(dividend) = dividend {
+            let underlying_price = chain.underlying_price;
+            let assigned: Vec<bool> = trade
+                .legs
+                .iter()
+                .map(|leg| {
+                    let intrinsic = leg.intrinsic(underlying_price);
+                    leg.side == Side::Short
+                        && leg.option_style == OptionStyle::Call
+                        && intrinsic > Positive::ZERO
+                        && leg.mark.to_dec() - intrinsic.to_dec() < dividend.to_dec()
+                })
+                .collect();
+            if assigned.contains(&true) {
+                // Assigned legs settle at intrinsic without a commission.
+                let closing: Vec<(BacktestLeg, BidAsk)> = trade
+                    .legs
+                    .iter()
+                    .zip(&quotes)
+                    .zip(&assigned)
+                    .filter(|(_, assigned)| !**assigned)
+                    .map(|((leg, quote), _)| (leg.clone(), *quote))
+                    .collect();
+                let exit_prices = self.exit_prices(&closing, ladder);
+                let order: Vec<FeeLeg> = closing
+                    .iter()
+                    .zip(&exit_prices)
+                    .map(|((leg, _), price)| leg.fee_leg(*price))
+                    .collect();
+                if !order.is_empty() {
+                    trade.fees += self.costs.fees.order_fee(&order);
+                }
+                let mut exit_prices = exit_prices.into_iter();
+                for (leg, assigned) in trade.legs.iter_mut().zip(assigned) {
+                    leg.mark = if assigned {
+                        leg.intrinsic(underlying_price)
+                    } else {
+                        exit_prices.next().unwrap_or(leg.mark)
+                    };
+                }
+                trade.close(
+                    timestamp,
+                    ExitReason::Other("Assigned ahead of ex-dividend".to_string()),
+                );
+                return;
+            }
+        }
+
+        let context = ExitContext {
+            timestamp,
+            days_held: days_between(trade.entry_date, timestamp),
+            days_to_expiration: days_between(timestamp, trade.expiration_date),
+            profit_loss: trade.profit_loss,
+            profit_loss_percent: trade.profit_loss_percent(),
+            net_delta: trade.net_delta(),
+            days_to_events: self.days_to_events(timestamp, trade.expiration_date),
+        };
+        if let Some(reason) = self.exit.triggered(&context) {
+            let closing: Vec<(BacktestLeg, BidAsk)> =
+                trade.legs.iter().cloned().zip(quotes).collect();
+            let exit_prices = self.exit_prices(&closing, ladder);
+            let order: Vec<FeeLeg> = trade
+                .legs
+                .iter()
+                .zip(&exit_prices)
+                .map(|(leg, price)| leg.fee_leg(*price))
+                .collect();
+            trade.fees += self.costs.fees.order_fee(&order);
+            for (leg, price) in trade.legs.iter_mut().zip(exit_prices) {
+                leg.mark = price;
+            }
+            trade.close(timestamp, reason);
+        }
+    }
+
+    /// Fill prices of an order opening, or closing when `closing`, `legs` at
+    /// their quotes, `None` for the legs that cannot be filled.
+    fn fill_prices(
+        &self,
+        legs: &[(BacktestLeg, BidAsk)],
+        closing: bool,
+        ladder: Option<&mut LadderFillSimulator>,
+    ) -> Vec<Option<Positive>> {
+        if let Some(simulator) = ladder
+            && let Some(prices) = ladder_prices(simulator, legs, closing)
+        {
+            return prices.into_iter().map(Some).collect();
+        }
+        legs.iter()
+            .map(|(leg, quote)| {
+                if closing {
+                    self.costs.close_price(leg.side, quote)
+                } else {
+                    self.costs.open_price(leg.side, quote)
+                }
+            })
+            .collect()
+    }
+
+    /// Prices `legs` are closed at, their marks for the legs without a quote.
+    fn exit_prices(
+        &self,
+        legs: &[(BacktestLeg, BidAsk)],
+        ladder: Option<&mut LadderFillSimulator>,
+    ) -> Vec<Positive> {
+        self.fill_prices(legs, true, ladder)
+            .into_iter()
+            .zip(legs)
+            .map(|(price, (leg, _))| price.unwrap_or(leg.mark))
+            .collect()
+    }
+
+    /// Days until the next event of each kind up to `expiration`.
+    fn days_to_events(
+        &self,
+        timestamp: DateTime<Utc>,
+        expiration: DateTime<Utc>,
+    ) -> BTreeMap<MarketEventKind, Positive> {
+        let mut days = BTreeMap::new();
+        for event in self.events.between(&self.symbol, timestamp, expiration) {
+            days.entry(event.kind)
+                .or_insert_with(|| days_between(timestamp, event.timestamp));
+        }
+        days
+    }
+}
+
+/// Prices of `legs` filled together through the ladder, or `None` if a leg
+/// is not quoted on both sides. The combo unit is the smallest leg, so a
+/// 1×2 ratio spread of 5 and 10 contracts is worked as 5 units.
+fn ladder_prices(
+    simulator: &mut LadderFillSimulator,
+    legs: &[(BacktestLeg, BidAsk)],
+    closing: bool,
+) -> Option<Vec<Positive>> {
+    let units = legs.iter().map(|(leg, _)| leg.quantity).min()?;
+    if units == Positive::ZERO {
+        return None;
+    }
+    let combo = legs
+        .iter()
+        .map(|(leg, quote)| {
+            let side = match (leg.side, closing) {
+                (Side::Long, true) => Side::Short,
+                (Side::Short, true) => Side::Long,
+                (side, false) => side,
+            };
+            Some(ComboLeg::new(
+                side,
+                leg.quantity / units,
+                quote.bid?,
+                quote.ask?,
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let order = ComboOrder::new(combo).ok()?;
+    simulator.fill_legs(&order, units).ok()
+}
+
+fn expiration_of(chain: &OptionChain) -> Option<DateTime<Utc>> {
+    chain.get_expiration()?.get_date().ok()
+}
+
+fn find_strike(chain: &OptionChain, strike: Positive) -> Option<&OptionData> {
+    chain
+        .get_single_iter()
+        .find(|option| option.strike_price == strike)
+}
+
+/// Days from `from` to `to`, zero when `to` is earlier.
+fn days_between(from: DateTime<Utc>, to: DateTime<Utc>) -> Positive {
+    let seconds = (to - from).num_seconds().max(0);
+    Positive::new_decimal(Decimal::from(seconds) / Decimal::from(SECONDS_PER_DAY))
+        .unwrap_or(Positive::ZERO)
+}
+
+#[cfg(test)]
+mod tests_engine {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::backtesting::costs::{NoFees, PerContractFee};
+    use crate::backtesting::fills::PriceLadder;
+    use crate::backtesting::source::InMemoryChainSource;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::model::QuotePricing;
+    use crate::model::events::MarketEvent;
+    use chrono::{Duration, TimeZone};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn expiration() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2030, 6, 21, 18, 30, 0).unwrap()
+    }
+
+    fn chain(price: f64, volatility: f64) -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(100.0),
+            10,
+            spos!(5.0),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::new(price).unwrap())),
+                Some(ExpirationDate::DateTime(expiration())),
+                Some(Decimal::ZERO),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(volatility),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    fn source(sessions: &[(i64, f64, f64)]) -> InMemoryChainSource {
+        let start = Utc.with_ymd_and_hms(2030, 5, 1, 20, 0, 0).unwrap();
+        sessions
+            .iter()
+            .fold(InMemoryChainSource::new(), |source, (day, price, iv)| {
+                source.with_snapshot(
+                    "XYZ",
+                    start + Duration::days(*day),
+                    vec![chain(*price, *iv)],
+                )
+            })
+    }
+
+    fn strangle() -> Vec<LegTarget> {
+        vec![
+            LegTarget::new(Side::Short, OptionStyle::Put, dec!(0.2)),
+            LegTarget::new(Side::Short, OptionStyle::Call, dec!(0.2)),
+        ]
+    }
+
+    #[test]
+    fn test_profit_target_and_reentry() {
+        let source = source(&[(0, 100.0, 0.3), (1, 100.0, 0.3), (2, 100.0, 0.15)]);
+        let report = RuleBacktest::new("XYZ", strangle())
+            .with_exit(ExitRule::ProfitTarget(dec!(25)).or(ExitRule::StopLoss(dec!(100))))
+            .run(&source)
+            .unwrap();
+
+        assert_eq!(report.sessions, 3);
+        assert_eq!(report.trades.len(), 2);
+        let first = &report.trades[0];
+        assert!(first.entry_cost < Decimal::ZERO);
+        assert_eq!(first.legs[0].option_style, OptionStyle::Put);
+        assert!(first.legs[0].strike < first.legs[1].strike);
+        assert_eq!(first.exit_reason, Some(ExitReason::TargetReached));
+        assert!(first.profit_loss_percent() >= dec!(25));
+        // The trade is reopened in the session it was closed.
+        assert_eq!(report.trades[1].entry_date, first.exit_date.unwrap());
+        assert_eq!(report.open_trades().count(), 1);
+        assert_eq!(report.realized_profit_loss(), first.profit_loss);
+        assert_eq!(report.equity_curve[0].1, Decimal::ZERO);
+        assert_eq!(report.equity_curve[2].1, first.profit_loss);
+
+        let analytics = report.analytics(pos_or_panic!(10_000.0), Decimal::ZERO);
+        assert_eq!(analytics.number_of_trades, 1);
+        assert_eq!(analytics.win_rate, Some(Decimal::ONE));
+        assert_eq!(
+            analytics.final_equity,
+            dec!(10_000) + report.equity_curve[2].1
+        );
+    }
+
+    #[test]
+    fn test_expiration_settles_at_intrinsic() {
+        let source = source(&[(31, 100.0, 0.2), (52, 120.0, 0.2)]);
+        let long_call = vec![LegTarget::new(Side::Long, OptionStyle::Call, dec!(0.5))];
+        let report = RuleBacktest::new("XYZ", long_call)
+            .with_entry(EntryRule::dte_window(Positive::ZERO, pos_or_panic!(30.0)))
+            .run(&source)
+            .unwrap();
+
+        assert_eq!(report.trades.len(), 1);
+        let trade = &report.trades[0];
+        assert_eq!(trade.exit_reason, Some(ExitReason::Expiration));
+        assert_eq!(
+            trade.legs[0].mark,
+            Positive::HUNDRED + Positive::TWENTY - trade.legs[0].strike
+        );
+        assert_eq!(
+            trade.profit_loss,
+            trade.legs[0].mark.to_dec() - trade.entry_cost
+        );
+    }
+
+    #[test]
+    fn test_costs_reduce_profit() {
+        let source = source(&[(0, 100.0, 0.3), (1, 100.0, 0.3), (2, 100.0, 0.15)]);
+        let backtest =
+            RuleBacktest::new("XYZ", strangle()).with_exit(ExitRule::TimeStop(pos_or_panic!(2.0)));
+        let frictionless = backtest.run(&source).unwrap();
+        let costly = backtest
+            .with_costs(TradingCosts::new(
+                PerContractFee::new(pos_or_panic!(0.65)),
+                QuotePricing::Natural,
+            ))
+            .run(&source)
+            .unwrap();
+
+        let (base, trade) = (&frictionless.trades[0], &costly.trades[0]);
+        assert_eq!(
+            trade.exit_reason,
+            Some(ExitReason::Other("Time stop after 2 days".into()))
+        );
+        assert_eq!(base.fees, Positive::ZERO);
+        // Two legs opened and two closed.
+        assert_eq!(trade.fees, pos_or_panic!(2.6));
+        // Selling at the bid collects less premium than at mid.
+        assert!(trade.entry_cost > base.entry_cost);
+        assert!(trade.profit_loss < base.profit_loss - dec!(2.6));
+        assert_eq!(
+            trade.profit_loss,
+            trade.value() - trade.entry_cost - trade.fees.to_dec()
+        );
+    }
+
+    #[test]
+    fn test_ladder_fills() {
+        let source = source(&[(0, 100.0, 0.3), (1, 100.0, 0.3), (2, 100.0, 0.15)]);
+        let backtest =
+            RuleBacktest::new("XYZ", strangle()).with_exit(ExitRule::TimeStop(pos_or_panic!(2.0)));
+        let at_mid = backtest.run(&source).unwrap();
+        let at_natural = backtest
+            .clone()
+            .with_costs(TradingCosts::new(NoFees, QuotePricing::Natural))
+            .run(&source)
+            .unwrap();
+
+        // Certain fills at mid match per-leg fills at mid.
+        let patient = backtest
+            .clone()
+            .with_fill_model(FillModel::ladder(
+                PriceLadder {
+                    fill_probability_at_mid: Decimal::ONE,
+                    ..Default::default()
+                },
+                Some(1),
+            ))
+            .run(&source)
+            .unwrap();
+        assert_eq!(patient.trades[0], at_mid.trades[0]);
+
+        // Without liquidity before natural, both orders cross the spread.
+        let eager = backtest
+            .clone()
+            .with_fill_model(FillModel::ladder(
+                PriceLadder {
+                    rungs: 2,
+                    fill_probability_at_mid: Decimal::ZERO,
+                    ..Default::default()
+                },
+                Some(1),
+            ))
+            .run(&source)
+            .unwrap();
+        assert_eq!(eager.trades[0], at_natural.trades[0]);
+
+        let worked = backtest
+            .clone()
+            .with_fill_model(FillModel::ladder(PriceLadder::default(), Some(5)))
+            .run(&source)
+            .unwrap();
+        let trade = &worked.trades[0];
+        assert!(trade.entry_cost >= at_mid.trades[0].entry_cost);
+        assert!(trade.entry_cost <= at_natural.trades[0].entry_cost);
+
+        let invalid = PriceLadder {
+            rungs: 1,
+            ..Default::default()
+        };
+        assert!(
+            backtest
+                .with_fill_model(FillModel::ladder(invalid, None))
+                .run(&source)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_close_before_earnings() {
+        let source = source(&[(0, 100.0, 0.3), (1, 100.0, 0.3), (2, 100.0, 0.3)]);
+        let start = Utc.with_ymd_and_hms(2030, 5, 1, 20, 0, 0).unwrap();
+        let earnings = start + Duration::hours(60);
+        let events = EventTimeline::new().with_event(MarketEvent::earnings("XYZ", earnings));
+        let backtest = RuleBacktest::new("XYZ", strangle())
+            .with_exit(ExitRule::BeforeEvent {
+                kind: MarketEventKind::Earnings,
+                days: Positive::ONE,
+            })
+            .with_events(events);
+
+        let report = backtest.run(&source).unwrap();
+        let first = &report.trades[0];
+        assert_eq!(first.exit_date, Some(start + Duration::days(2)));
+        assert_eq!(
+            first.exit_reason,
+            Some(ExitReason::Other("Earnings in 0.5 days".to_string()))
+        );
+
+        // Avoiding the expirations spanning earnings blocks every entry.
+        let avoiding = backtest
+            .with_entry(EntryRule::AvoidEvent(MarketEventKind::Earnings))
+            .run(&source)
+            .unwrap();
+        assert!(avoiding.trades.is_empty());
+    }
+
+    #[test]
+    fn test_assignment_ahead_of_ex_dividend() {
+        let source = source(&[(0, 100.0, 0.3), (1, 125.0, 0.1), (2, 125.0, 0.1)]);
+        let start = Utc.with_ymd_and_hms(2030, 5, 1, 20, 0, 0).unwrap();
+        let ex_date = start + Duration::hours(36);
+        let short_call = vec![LegTarget::new(Side::Short, OptionStyle::Call, dec!(0.5))];
+        let backtest = |amount: f64| {
+            RuleBacktest::new("XYZ", short_call.clone())
+                .with_events(EventTimeline::new().with_event(MarketEvent::ex_dividend(
+                    "XYZ",
+                    ex_date,
+                    pos_or_panic!(amount),
+                )))
+                .run(&source)
+                .unwrap()
+        };
+
+        let report = backtest(10.0);
+        let trade = &report.trades[0];
+        assert_eq!(trade.exit_date, Some(start + Duration::days(1)));
+        assert_eq!(
+            trade.exit_reason,
+            Some(ExitReason::Other(
+                "Assigned ahead of ex-dividend".to_string()
+            ))
+        );
+        let call = &trade.legs[0];
+        assert_eq!(call.mark, pos_or_panic!(125.0) - call.strike);
+
+        // A dividend smaller than the extrinsic value of the call is not worth
+        // exercising for.
+        let report = backtest(0.001);
+        assert_eq!(report.trades.len(), 1);
+        assert!(report.trades[0].is_open());
+    }
+
+    #[test]
+    fn test_iv_rank_gates_entry() {
+        let source = source(&[(0, 100.0, 0.2), (1, 100.0, 0.4), (2, 100.0, 0.3)]);
+        let report = RuleBacktest::new("XYZ", strangle())
+            .with_entry(EntryRule::IvRankAtLeast(dec!(60)))
+            .with_exit(ExitRule::TimeStop(pos_or_panic!(1.0)))
+            .run(&source)
+            .unwrap();
+
+        assert_eq!(report.trades.len(), 1);
+        let trade = &report.trades[0];
+        assert_eq!(trade.entry_date, report.equity_curve[1].0);
+        assert!(matches!(trade.exit_reason, Some(ExitReason::Other(_))));
+
+        assert!(RuleBacktest::new("XYZ", Vec::new()).run(&source).is_err());
+    }
+}
@@ -0,0 +1,544 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Combo Order Fills
+//!
+//! Multi-leg orders are rarely filled at mid. A [`ComboOrder`] is usually
+//! worked as a single net price, starting at mid and repriced towards the
+//! natural price until it fills. [`LadderFillSimulator`] reproduces that
+//! process on a [`PriceLadder`], drawing fills and partial fills on every
+//! rung, and reports the average net price obtained.
+//!
+//! [`FillModel`] selects how a
+//! [`RuleBacktest`](crate::backtesting::RuleBacktest) fills its orders: leg
+//! by leg at the prices of its slippage model, or the legs of every entry and
+//! exit together through a price ladder.
+
+use crate::error::TradeError;
+use crate::model::types::Side;
+use crate::simulation::rng::seeded_rng;
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rand::RngExt;
+use rand::rngs::StdRng;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A single leg of a multi-leg combo order with its current quote.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ComboLeg {
+    /// Whether the leg is bought (`Long`) or sold (`Short`).
+    pub side: Side,
+    /// Number of contracts of this leg per combo unit (the leg ratio).
+    pub ratio: Positive,
+    /// Best bid of the leg.
+    pub bid: Positive,
+    /// Best ask of the leg.
+    pub ask: Positive,
+}
+
+impl ComboLeg {
+    /// Creates a combo leg.
+    pub fn new(side: Side, ratio: Positive, bid: Positive, ask: Positive) -> Self {
+        ComboLeg {
+            side,
+            ratio,
+            bid,
+            ask,
+        }
+    }
+
+    fn sign(&self) -> Decimal {
+        match self.side {
+            Side::Long => Decimal::ONE,
+            Side::Short => Decimal::NEGATIVE_ONE,
+        }
+    }
+}
+
+/// A multi-leg order priced as a single net amount.
+///
+/// Net prices are signed: positive values are debits paid, negative values are
+/// credits received. With this convention the natural price is always at or
+/// above the mid price, and working the order means moving from mid towards
+/// natural.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ComboOrder {
+    /// Legs of the combo.
+    pub legs: Vec<ComboLeg>,
+}
+
+impl ComboOrder {
+    /// Creates a combo order from its legs.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradeError` if the order has no legs or a leg has a crossed quote.
+    pub fn new(legs: Vec<ComboLeg>) -> Result<Self, TradeError> {
+        if legs.is_empty() {
+            return Err(TradeError::invalid_trade("Combo order without legs"));
+        }
+        if legs.iter().any(|leg| leg.bid > leg.ask) {
+            return Err(TradeError::invalid_trade("Combo leg with bid above ask"));
+        }
+        Ok(ComboOrder { legs })
+    }
+
+    /// Net price obtained by crossing every leg (buying at the ask, selling at the bid).
+    pub fn natural_price(&self) -> Decimal {
+        self.legs
+            .iter()
+            .map(|leg| {
+                let price = match leg.side {
+                    Side::Long => leg.ask,
+                    Side::Short => leg.bid,
+                };
+                leg.sign() * price.to_dec() * leg.ratio.to_dec()
+            })
+            .sum()
+    }
+
+    /// Net mid price of the combo.
+    pub fn mid_price(&self) -> Decimal {
+        self.legs
+            .iter()
+            .map(|leg| {
+                leg.sign() * (leg.bid.to_dec() + leg.ask.to_dec()) / Decimal::TWO
+                    * leg.ratio.to_dec()
+            })
+            .sum()
+    }
+
+    /// Price of the rung located `fraction` of the way from mid (0) to natural (1).
+    pub fn price_at(&self, fraction: Decimal) -> Decimal {
+        let mid = self.mid_price();
+        mid + (self.natural_price() - mid) * fraction.clamp(Decimal::ZERO, Decimal::ONE)
+    }
+
+    /// Price of every leg `fraction` of the way from its mid to its natural
+    /// side. The leg prices add up to [`ComboOrder::price_at`] the same
+    /// `fraction`.
+    pub fn leg_prices_at(&self, fraction: Decimal) -> Vec<Positive> {
+        let fraction = fraction.clamp(Decimal::ZERO, Decimal::ONE);
+        self.legs
+            .iter()
+            .map(|leg| {
+                let mid = (leg.bid.to_dec() + leg.ask.to_dec()) / Decimal::TWO;
+                let natural = match leg.side {
+                    Side::Long => leg.ask,
+                    Side::Short => leg.bid,
+                };
+                let price = mid + (natural.to_dec() - mid) * fraction;
+                Positive::new_decimal(price.max(Decimal::ZERO)).unwrap_or(Positive::ZERO)
+            })
+            .collect()
+    }
+}
+
+/// Configuration of a price ladder walked from mid towards natural.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PriceLadder {
+    /// Number of rungs, the first at mid and the last at natural.
+    pub rungs: usize,
+    /// Seconds the order rests on each rung before being repriced.
+    pub seconds_per_rung: u64,
+    /// Probability of a (partial) fill while resting at mid.
+    pub fill_probability_at_mid: Decimal,
+    /// Fraction of the remaining quantity filled on each fill event below natural.
+    pub partial_fill_ratio: Decimal,
+}
+
+impl Default for PriceLadder {
+    fn default() -> Self {
+        PriceLadder {
+            rungs: 5,
+            seconds_per_rung: 30,
+            fill_probability_at_mid: Decimal::new(2, 1),
+            partial_fill_ratio: Decimal::new(5, 1),
+        }
+    }
+}
+
+impl PriceLadder {
+    fn validate(&self) -> Result<(), TradeError> {
+        if self.rungs < 2 {
+            return Err(TradeError::invalid_trade(
+                "Price ladder needs at least two rungs (mid and natural)",
+            ));
+        }
+        let unit = Decimal::ZERO..=Decimal::ONE;
+        if !unit.contains(&self.fill_probability_at_mid) || !unit.contains(&self.partial_fill_ratio)
+        {
+            return Err(TradeError::invalid_trade(
+                "Ladder probabilities must lie in [0, 1]",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Fraction of the mid-to-natural distance at rung `index`.
+    fn fraction(&self, index: usize) -> Decimal {
+        Decimal::from(index) / Decimal::from(self.rungs - 1)
+    }
+
+    /// Fill probability on a rung, increasing linearly from mid to certainty at natural.
+    fn fill_probability(&self, fraction: Decimal) -> Decimal {
+        self.fill_probability_at_mid + (Decimal::ONE - self.fill_probability_at_mid) * fraction
+    }
+}
+
+/// A fill obtained while walking the ladder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LadderFill {
+    /// Rung on which the fill happened (0 = mid).
+    pub rung: usize,
+    /// Seconds elapsed since the order was first placed.
+    pub elapsed_seconds: u64,
+    /// Net combo price of the fill.
+    pub price: Decimal,
+    /// Number of combo units filled.
+    pub quantity: Positive,
+}
+
+/// Result of working a combo order through a price ladder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ComboFillResult {
+    /// Mid price when the order was placed.
+    pub mid_price: Decimal,
+    /// Natural price when the order was placed.
+    pub natural_price: Decimal,
+    /// Individual fills in chronological order.
+    pub fills: Vec<LadderFill>,
+    /// Total number of combo units filled.
+    pub filled_quantity: Positive,
+    /// Seconds until the order was completely filled.
+    pub time_to_fill_seconds: u64,
+}
+
+impl ComboFillResult {
+    /// Quantity weighted average net fill price.
+    pub fn average_price(&self) -> Option<Decimal> {
+        if self.filled_quantity == Positive::ZERO {
+            return None;
+        }
+        let notional: Decimal = self
+            .fills
+            .iter()
+            .map(|f| f.price * f.quantity.to_dec())
+            .sum();
+        Some(notional / self.filled_quantity.to_dec())
+    }
+
+    /// Per-unit cost paid over mid (always non-negative).
+    pub fn slippage_vs_mid(&self) -> Option<Decimal> {
+        Some(self.average_price()? - self.mid_price)
+    }
+
+    /// Per-unit improvement obtained over the natural price (always non-negative).
+    pub fn improvement_vs_natural(&self) -> Option<Decimal> {
+        Some(self.natural_price - self.average_price()?)
+    }
+
+    /// Fraction of the way from mid (0) to natural (1) of the average price,
+    /// zero when both prices are the same.
+    pub fn average_fraction(&self) -> Option<Decimal> {
+        let width = self.natural_price - self.mid_price;
+        if width.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+        Some(self.slippage_vs_mid()? / width)
+    }
+}
+
+/// Simulates fills for combo orders worked through a [`PriceLadder`].
+///
+/// The order rests on each rung for `seconds_per_rung`. On every rung a fill
+/// event happens with a probability that grows linearly from
+/// `fill_probability_at_mid` to one at natural; a fill event below natural
+/// fills `partial_fill_ratio` of the remaining quantity (at least one unit),
+/// while the natural rung fills everything that is left.
+#[derive(Debug)]
+pub struct LadderFillSimulator {
+    ladder: PriceLadder,
+    rng: StdRng,
+}
+
+impl LadderFillSimulator {
+    /// Creates a simulator; a seed makes the fills reproducible.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradeError` if the ladder configuration is invalid.
+    pub fn new(ladder: PriceLadder, seed: Option<u64>) -> Result<Self, TradeError> {
+        ladder.validate()?;
+        let rng = seeded_rng(seed);
+        Ok(LadderFillSimulator { ladder, rng })
+    }
+
+    /// Returns the ladder configuration.
+    pub fn ladder(&self) -> &PriceLadder {
+        &self.ladder
+    }
+
+    /// Works `quantity` combo units of `order` through the ladder.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradeError` if `quantity` is zero.
+    pub fn simulate(
+        &mut self,
+        order: &ComboOrder,
+        quantity: Positive,
+    ) -> Result<ComboFillResult, TradeError> {
+        if quantity == Positive::ZERO {
+            return Err(TradeError::invalid_trade(
+                "Combo quantity must be greater than zero",
+            ));
+        }
+        let mut remaining = quantity.to_dec();
+        let mut fills = Vec::new();
+        let mut elapsed = 0;
+
+        for rung in 0..self.ladder.rungs {
+            let fraction = self.ladder.fraction(rung);
+            let is_natural = rung + 1 == self.ladder.rungs;
+            let probability = self
+                .ladder
+                .fill_probability(fraction)
+                .to_f64()
+                .unwrap_or(0.0);
+            let hit = is_natural || self.rng.random::<f64>() < probability;
+            if hit {
+                let filled = if is_natural {
+                    remaining
+                } else {
+                    (remaining * self.ladder.partial_fill_ratio)
+                        .ceil()
+                        .max(Decimal::ONE)
+                        .min(remaining)
+                };
+                // Fills are reported at the end of the resting interval.
+                let offset = if is_natural {
+                    0
+                } else {
+                    self.ladder.seconds_per_rung
+                };
+                fills.push(LadderFill {
+                    rung,
+                    elapsed_seconds: elapsed + offset,
+                    price: order.price_at(fraction),
+                    quantity: Positive::new_decimal(filled)
+                        .map_err(|e| TradeError::invalid_trade(&e.to_string()))?,
+                });
+                remaining -= filled;
+                if remaining.is_zero() {
+                    elapsed += offset;
+                    break;
+                }
+            }
+            elapsed += self.ladder.seconds_per_rung;
+        }
+
+        Ok(ComboFillResult {
+            mid_price: order.mid_price(),
+            natural_price: order.natural_price(),
+            fills,
+            filled_quantity: quantity,
+            time_to_fill_seconds: elapsed,
+        })
+    }
+
+    /// Works `quantity` combo units of `order` through the ladder and
+    /// returns the price of every leg at the average fill.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradeError` if `quantity` is zero.
+    pub fn fill_legs(
+        &mut self,
+        order: &ComboOrder,
+        quantity: Positive,
+    ) -> Result<Vec<Positive>, TradeError> {
+        let result = self.simulate(order, quantity)?;
+        let fraction = result.average_fraction().unwrap_or(Decimal::ONE);
+        Ok(order.leg_prices_at(fraction))
+    }
+}
+
+/// How the orders of a backtest are filled.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub enum FillModel {
+    /// Every leg fills on its own at the price of the slippage model.
+    #[default]
+    PerLeg,
+    /// The legs of an order fill together as a combo worked through a
+    /// price ladder; a seed makes the fills reproducible.
+    Ladder {
+        /// Ladder the combo is worked through.
+        ladder: PriceLadder,
+        /// Seed of the fill simulation.
+        seed: Option<u64>,
+    },
+}
+
+impl FillModel {
+    /// Fills combos through `ladder`.
+    pub fn ladder(ladder: PriceLadder, seed: Option<u64>) -> Self {
+        FillModel::Ladder { ladder, seed }
+    }
+
+    /// Simulator of the ladder, `None` for per-leg fills.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TradeError` if the ladder configuration is invalid.
+    pub fn simulator(&self) -> Result<Option<LadderFillSimulator>, TradeError> {
+        match self {
+            FillModel::PerLeg => Ok(None),
+            FillModel::Ladder { ladder, seed } => {
+                LadderFillSimulator::new(ladder.clone(), *seed).map(Some)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_fills {
+    use super::*;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn vertical() -> ComboOrder {
+        ComboOrder::new(vec![
+            ComboLeg::new(
+                Side::Long,
+                Positive::ONE,
+                pos_or_panic!(5.0),
+                pos_or_panic!(5.4),
+            ),
+            ComboLeg::new(
+                Side::Short,
+                Positive::ONE,
+                pos_or_panic!(2.0),
+                pos_or_panic!(2.2),
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_natural_and_mid_prices() {
+        let order = vertical();
+        assert_eq!(order.natural_price(), dec!(3.4));
+        assert_eq!(order.mid_price(), dec!(3.1));
+        assert_eq!(order.price_at(dec!(0.5)), dec!(3.25));
+
+        let credit = ComboOrder::new(vec![ComboLeg::new(
+            Side::Short,
+            Positive::TWO,
+            pos_or_panic!(1.0),
+            pos_or_panic!(1.2),
+        )])
+        .unwrap();
+        assert_eq!(credit.natural_price(), dec!(-2.0));
+        assert_eq!(credit.mid_price(), dec!(-2.2));
+    }
+
+    #[test]
+    fn test_invalid_orders() {
+        assert!(ComboOrder::new(vec![]).is_err());
+        assert!(
+            ComboOrder::new(vec![ComboLeg::new(
+                Side::Long,
+                Positive::ONE,
+                pos_or_panic!(2.0),
+                pos_or_panic!(1.0),
+            )])
+            .is_err()
+        );
+        let ladder = PriceLadder {
+            rungs: 1,
+            ..Default::default()
+        };
+        assert!(LadderFillSimulator::new(ladder, None).is_err());
+    }
+
+    #[test]
+    fn test_certain_fill_at_mid() {
+        let ladder = PriceLadder {
+            fill_probability_at_mid: Decimal::ONE,
+            partial_fill_ratio: Decimal::ONE,
+            ..Default::default()
+        };
+        let mut simulator = LadderFillSimulator::new(ladder, Some(1)).unwrap();
+        let result = simulator.simulate(&vertical(), pos_or_panic!(3.0)).unwrap();
+        assert_eq!(result.fills.len(), 1);
+        assert_eq!(result.average_price(), Some(dec!(3.1)));
+        assert_eq!(result.slippage_vs_mid(), Some(Decimal::ZERO));
+        assert_eq!(result.time_to_fill_seconds, 30);
+    }
+
+    #[test]
+    fn test_no_liquidity_at_mid() {
+        let ladder = PriceLadder {
+            rungs: 4,
+            seconds_per_rung: 10,
+            fill_probability_at_mid: Decimal::ZERO,
+            partial_fill_ratio: Decimal::ONE,
+        };
+        let mut simulator = LadderFillSimulator::new(ladder, Some(7)).unwrap();
+        let result = simulator.simulate(&vertical(), Positive::ONE).unwrap();
+        assert_eq!(result.fills.len(), 1);
+        let fill = &result.fills[0];
+        assert!(fill.rung > 0);
+        assert!(fill.price > result.mid_price && fill.price <= dec!(3.4));
+        assert!(result.time_to_fill_seconds <= 30);
+        assert!(result.improvement_vs_natural().unwrap() >= Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_partial_fills_add_up() {
+        let mut simulator = LadderFillSimulator::new(PriceLadder::default(), Some(42)).unwrap();
+        let order = vertical();
+        for _ in 0..20 {
+            let result = simulator.simulate(&order, pos_or_panic!(10.0)).unwrap();
+            let total: Positive = result.fills.iter().map(|f| f.quantity).sum();
+            assert_eq!(total, pos_or_panic!(10.0));
+            let average = result.average_price().unwrap();
+            assert!(average >= order.mid_price() && average <= order.natural_price());
+            assert!(result.fills.windows(2).all(|w| w[0].rung < w[1].rung));
+        }
+    }
+
+    #[test]
+    fn test_leg_prices_add_up_to_the_fill() {
+        let order = vertical();
+        let prices = order.leg_prices_at(dec!(0.5));
+        assert_eq!(prices, vec![pos_or_panic!(5.3), pos_or_panic!(2.05)]);
+        assert_eq!(
+            prices[0].to_dec() - prices[1].to_dec(),
+            order.price_at(dec!(0.5))
+        );
+
+        let mut simulator = LadderFillSimulator::new(PriceLadder::default(), Some(3)).unwrap();
+        let result = simulator.simulate(&order, pos_or_panic!(4.0)).unwrap();
+        let fraction = result.average_fraction().unwrap();
+        assert_eq!(order.price_at(fraction), result.average_price().unwrap());
+        assert!(FillModel::default().simulator().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_seeded_simulation_is_reproducible() {
+        let order = vertical();
+        let mut a = LadderFillSimulator::new(PriceLadder::default(), Some(9)).unwrap();
+        let mut b = LadderFillSimulator::new(PriceLadder::default(), Some(9)).unwrap();
+        assert_eq!(
+            a.simulate(&order, pos_or_panic!(5.0)).unwrap(),
+            b.simulate(&order, pos_or_panic!(5.0)).unwrap()
+        );
+    }
+}
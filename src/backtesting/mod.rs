@@ -404,6 +404,62 @@ pub mod results;
 /// It is designed to be fully serializable (serde) for easy storage, reporting, or integration into larger analytics systems.
 pub mod types;
 
+/// This module simulates how multi-leg combo orders are filled during a backtest.
+///
+/// Instead of assuming instant fills at mid, a combo order is worked through a price ladder
+/// running from the net mid price to the natural price (crossing every leg). Each rung rests
+/// for a fixed time and may produce partial fills with a probability that increases towards
+/// natural, which yields realistic price improvement, slippage and time-to-fill figures.
+pub mod fills;
+
+/// This module defines where a backtest reads its historical option chains from.
+///
+/// The `HistoricalChainSource` trait returns the chains of a symbol as of a timestamp and
+/// replays them session by session. Archives held in memory and directories of CSV files
+/// are supported out of the box; other archives plug in by implementing the trait.
+pub mod source;
+
+/// This module describes mechanical entry and exit rules for the backtester.
+///
+/// Entry rules combine days-to-expiration windows and IV rank thresholds, legs are selected
+/// by target delta, and exit rules cover profit targets, stop losses, time stops, DTE exits
+/// and delta limits.
+pub mod rules;
+
+/// This module models the trading costs of backtests and paper trading.
+///
+/// Fee models cover per-contract and per-order commissions, tiered schedules and exchange,
+/// clearing and regulatory fees; slippage models fill at mid, at a fraction of the spread or
+/// a fixed number of ticks away. Both are applied when positions are opened, rolled and closed.
+pub mod costs;
+
+/// This module runs rule-based backtests over a historical chain source.
+///
+/// Every session is replayed in order: open trades are marked to the chain and closed by the
+/// exit rules or settled at expiration, and new trades are opened where the entry rule holds.
+pub mod engine;
+
+/// This module computes the performance analytics of a backtest.
+///
+/// CAGR, volatility, Sharpe and Sortino ratios, maximum drawdown and its duration, win rate,
+/// profit factor and average winner and loser are derived from the equity curve and the
+/// closed trades, in a serializable struct for reporting.
+pub mod analytics;
+
+/// This module runs walk-forward optimizations of rule-based backtests.
+///
+/// The history is split into in-sample and out-of-sample windows: candidate parameters are
+/// scored in sample, the best one is tested on the following out-of-sample part, and only the
+/// out-of-sample results are aggregated, guarding against overfitting.
+pub mod walk_forward;
+
+pub use analytics::*;
+pub use costs::*;
+pub use engine::*;
+pub use fills::*;
 pub use metrics::*;
 pub use results::*;
+pub use rules::*;
+pub use source::*;
 pub use types::*;
+pub use walk_forward::*;
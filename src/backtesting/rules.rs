@@ -0,0 +1,369 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Entry and Exit Rules
+//!
+//! Mechanical strategies are mostly described by a handful of rules: open a
+//! position when an expiration is inside a days-to-expiration window and IV
+//! rank is high, pick the strikes by delta, and close at a profit target, a
+//! stop loss or after some time. These types describe such rules so that
+//! [`RuleBacktest`](crate::backtesting::RuleBacktest) can test them without a
+//! custom driver.
+//!
+//! Rules compose: [`EntryRule::All`] and [`EntryRule::Any`] combine entry
+//! conditions, and [`ExitRule::Any`] closes a trade on the first exit rule
+//! that triggers. Percentages are expressed from 0 to 100, like IV rank.
+//!
+//! Scheduled events from an [`EventTimeline`] can gate both: entries can
+//! avoid expirations spanning an earnings release, and trades can be closed
+//! a number of days before one.
+
+use crate::backtesting::types::ExitReason;
+use crate::chains::chain::OptionChain;
+use crate::model::events::{EventTimeline, MarketEventKind};
+use crate::model::types::{OptionStyle, Side};
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Market state an entry rule is evaluated against: one expiration of the
+/// current snapshot.
+#[derive(Debug, Clone, Copy)]
+pub struct EntryContext<'a> {
+    /// Time of the snapshot.
+    pub timestamp: DateTime<Utc>,
+    /// Chain of the candidate expiration.
+    pub chain: &'a OptionChain,
+    /// Expiration time of the chain.
+    pub expiration: DateTime<Utc>,
+    /// Days from the snapshot to the expiration of the chain.
+    pub days_to_expiration: Positive,
+    /// IV rank (0-100) of the underlying, once enough history is recorded.
+    pub iv_rank: Option<Decimal>,
+    /// Scheduled market events.
+    pub events: &'a EventTimeline,
+}
+
+/// Condition for opening a trade in an expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EntryRule {
+    /// Days to expiration within `[min_days, max_days]`.
+    DteWindow {
+        /// Fewest days to expiration.
+        min_days: Positive,
+        /// Most days to expiration.
+        max_days: Positive,
+    },
+    /// IV rank at or above the threshold. Not satisfied without IV history.
+    IvRankAtLeast(Decimal),
+    /// IV rank at or below the threshold. Not satisfied without IV history.
+    IvRankAtMost(Decimal),
+    /// No event of this kind affects the underlying before the expiration.
+    AvoidEvent(MarketEventKind),
+    /// Every rule is satisfied. An empty list is always satisfied.
+    All(Vec<EntryRule>),
+    /// At least one rule is satisfied. An empty list is never satisfied.
+    Any(Vec<EntryRule>),
+}
+
+impl Default for EntryRule {
+    fn default() -> Self {
+        EntryRule::All(Vec::new())
+    }
+}
+
+impl EntryRule {
+    /// Days to expiration within `[min_days, max_days]`.
+    pub fn dte_window(min_days: Positive, max_days: Positive) -> Self {
+        EntryRule::DteWindow { min_days, max_days }
+    }
+
+    /// Requires both this rule and `other`.
+    pub fn and(self, other: EntryRule) -> Self {
+        match self {
+            EntryRule::All(mut rules) => {
+                rules.push(other);
+                EntryRule::All(rules)
+            }
+            rule => EntryRule::All(vec![rule, other]),
+        }
+    }
+
+    /// Whether the rule is satisfied in `context`.
+    pub fn is_satisfied(&self, context: &EntryContext) -> bool {
+        match self {
+            EntryRule::DteWindow { min_days, max_days } => {
+                context.days_to_expiration >= *min_days && context.days_to_expiration <= *max_days
+            }
+            EntryRule::IvRankAtLeast(threshold) => {
+                context.iv_rank.is_some_and(|rank| rank >= *threshold)
+            }
+            EntryRule::IvRankAtMost(threshold) => {
+                context.iv_rank.is_some_and(|rank| rank <= *threshold)
+            }
+            EntryRule::AvoidEvent(kind) => !context.events.has_event_between(
+                &context.chain.symbol,
+                *kind,
+                context.timestamp,
+                context.expiration,
+            ),
+            EntryRule::All(rules) => rules.iter().all(|rule| rule.is_satisfied(context)),
+            EntryRule::Any(rules) => rules.iter().any(|rule| rule.is_satisfied(context)),
+        }
+    }
+}
+
+/// A leg of the traded structure, selected by delta in the entry expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegTarget {
+    /// Whether the leg is bought or sold.
+    pub side: Side,
+    /// Whether the leg is a call or a put.
+    pub option_style: OptionStyle,
+    /// Target absolute delta of the leg.
+    pub delta: Decimal,
+    /// Contracts per trade.
+    pub quantity: Positive,
+}
+
+impl LegTarget {
+    /// Creates a one contract leg at the strike closest to `delta`.
+    pub fn new(side: Side, option_style: OptionStyle, delta: Decimal) -> Self {
+        LegTarget {
+            side,
+            option_style,
+            delta: delta.abs(),
+            quantity: Positive::ONE,
+        }
+    }
+
+    /// Sets the number of contracts per trade.
+    pub fn with_quantity(mut self, quantity: Positive) -> Self {
+        self.quantity = quantity;
+        self
+    }
+}
+
+/// State of an open trade an exit rule is evaluated against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitContext {
+    /// Time of the snapshot.
+    pub timestamp: DateTime<Utc>,
+    /// Days since the trade was opened.
+    pub days_held: Positive,
+    /// Days from the snapshot to the expiration of the trade.
+    pub days_to_expiration: Positive,
+    /// Unrealized profit or loss of the trade.
+    pub profit_loss: Decimal,
+    /// Unrealized profit or loss as a percentage of the premium paid or received.
+    pub profit_loss_percent: Decimal,
+    /// Net delta of the trade, when every leg has a quoted delta.
+    pub net_delta: Option<Decimal>,
+    /// Days until the next event of each kind affecting the underlying, up
+    /// to the expiration of the trade.
+    pub days_to_events: BTreeMap<MarketEventKind, Positive>,
+}
+
+/// Condition for closing an open trade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ExitRule {
+    /// Profit reaches this percentage of the premium paid or received.
+    ProfitTarget(Decimal),
+    /// Loss reaches this percentage of the premium paid or received.
+    StopLoss(Decimal),
+    /// The trade has been held for this many days.
+    TimeStop(Positive),
+    /// Days to expiration fall to this many days or fewer.
+    DteExit(Positive),
+    /// Absolute net delta of the trade reaches this value.
+    DeltaLimit(Decimal),
+    /// An event of this kind affects the underlying within this many days.
+    BeforeEvent {
+        /// Kind of the event.
+        kind: MarketEventKind,
+        /// Days before the event to close the trade.
+        days: Positive,
+    },
+    /// The first rule that triggers. An empty list never triggers, leaving
+    /// trades open until expiration.
+    Any(Vec<ExitRule>),
+}
+
+impl Default for ExitRule {
+    fn default() -> Self {
+        ExitRule::Any(Vec::new())
+    }
+}
+
+impl ExitRule {
+    /// Closes on this rule or `other`, whichever triggers first.
+    pub fn or(self, other: ExitRule) -> Self {
+        match self {
+            ExitRule::Any(mut rules) => {
+                rules.push(other);
+                ExitRule::Any(rules)
+            }
+            rule => ExitRule::Any(vec![rule, other]),
+        }
+    }
+
+    /// Reason for closing the trade, if the rule triggers in `context`.
+    pub fn triggered(&self, context: &ExitContext) -> Option<ExitReason> {
+        match self {
+            ExitRule::ProfitTarget(percent) => {
+                (context.profit_loss_percent >= *percent).then_some(ExitReason::TargetReached)
+            }
+            ExitRule::StopLoss(percent) => {
+                (context.profit_loss_percent <= -*percent).then_some(ExitReason::StopLoss)
+            }
+            ExitRule::TimeStop(days) => (context.days_held >= *days)
+                .then(|| ExitReason::Other(format!("Time stop after {days} days"))),
+            ExitRule::DteExit(days) => (context.days_to_expiration <= *days)
+                .then(|| ExitReason::Other(format!("{days} days to expiration"))),
+            ExitRule::DeltaLimit(limit) => context
+                .net_delta
+                .filter(|delta| delta.abs() >= *limit)
+                .map(|delta| {
+                    ExitReason::Other(format!("Net delta {} beyond limit", delta.round_dp(4)))
+                }),
+            ExitRule::BeforeEvent { kind, days } => context
+                .days_to_events
+                .get(kind)
+                .filter(|until| *until <= days)
+                .map(|until| ExitReason::Other(format!("{kind} in {} days", until.round_to(2)))),
+            ExitRule::Any(rules) => rules.iter().find_map(|rule| rule.triggered(context)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_rules {
+    use super::*;
+    use crate::chains::chain::OptionChain;
+    use crate::model::events::MarketEvent;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn exit_context(percent: Decimal, days_held: f64, dte: f64) -> ExitContext {
+        ExitContext {
+            timestamp: Utc::now(),
+            days_held: pos_or_panic!(days_held),
+            days_to_expiration: pos_or_panic!(dte),
+            profit_loss: percent,
+            profit_loss_percent: percent,
+            net_delta: Some(dec!(-0.3)),
+            days_to_events: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_entry_rules_compose() {
+        let chain = OptionChain::new(
+            "XYZ",
+            Positive::HUNDRED,
+            "2030-01-18".to_string(),
+            None,
+            None,
+        );
+        let events = EventTimeline::new();
+        let context = |dte: f64, iv_rank| EntryContext {
+            timestamp: Utc::now(),
+            chain: &chain,
+            expiration: Utc::now() + chrono::Duration::days(dte as i64),
+            days_to_expiration: pos_or_panic!(dte),
+            iv_rank,
+            events: &events,
+        };
+        let rule = EntryRule::dte_window(pos_or_panic!(30.0), pos_or_panic!(60.0))
+            .and(EntryRule::IvRankAtLeast(dec!(50)));
+
+        assert!(rule.is_satisfied(&context(45.0, Some(dec!(70)))));
+        assert!(!rule.is_satisfied(&context(20.0, Some(dec!(70)))));
+        assert!(!rule.is_satisfied(&context(45.0, Some(dec!(30)))));
+        assert!(!rule.is_satisfied(&context(45.0, None)));
+        assert!(EntryRule::default().is_satisfied(&context(1.0, None)));
+
+        let either = EntryRule::Any(vec![
+            EntryRule::IvRankAtMost(dec!(10)),
+            EntryRule::IvRankAtLeast(dec!(90)),
+        ]);
+        assert!(either.is_satisfied(&context(45.0, Some(dec!(95)))));
+        assert!(!either.is_satisfied(&context(45.0, Some(dec!(50)))));
+
+        let earnings = EventTimeline::new().with_event(MarketEvent::earnings(
+            "XYZ",
+            Utc::now() + chrono::Duration::days(20),
+        ));
+        let avoid = EntryRule::AvoidEvent(MarketEventKind::Earnings);
+        let spanning = EntryContext {
+            events: &earnings,
+            ..context(45.0, None)
+        };
+        assert!(!avoid.is_satisfied(&spanning));
+        let before = EntryContext {
+            events: &earnings,
+            ..context(10.0, None)
+        };
+        assert!(avoid.is_satisfied(&before));
+    }
+
+    #[test]
+    fn test_exit_rules_first_trigger_wins() {
+        let rule = ExitRule::ProfitTarget(dec!(50))
+            .or(ExitRule::StopLoss(dec!(200)))
+            .or(ExitRule::DteExit(pos_or_panic!(21.0)))
+            .or(ExitRule::TimeStop(pos_or_panic!(10.0)));
+
+        assert_eq!(
+            rule.triggered(&exit_context(dec!(55), 1.0, 40.0)),
+            Some(ExitReason::TargetReached)
+        );
+        assert_eq!(
+            rule.triggered(&exit_context(dec!(-250), 1.0, 40.0)),
+            Some(ExitReason::StopLoss)
+        );
+        assert!(matches!(
+            rule.triggered(&exit_context(dec!(10), 1.0, 21.0)),
+            Some(ExitReason::Other(_))
+        ));
+        assert!(matches!(
+            rule.triggered(&exit_context(dec!(10), 10.0, 30.0)),
+            Some(ExitReason::Other(_))
+        ));
+        assert_eq!(rule.triggered(&exit_context(dec!(10), 1.0, 40.0)), None);
+        assert_eq!(
+            ExitRule::default().triggered(&exit_context(dec!(500), 99.0, 0.0)),
+            None
+        );
+
+        let delta = ExitRule::DeltaLimit(dec!(0.25));
+        assert!(
+            delta
+                .triggered(&exit_context(Decimal::ZERO, 1.0, 40.0))
+                .is_some()
+        );
+
+        let before_earnings = ExitRule::BeforeEvent {
+            kind: MarketEventKind::Earnings,
+            days: pos_or_panic!(2.0),
+        };
+        let mut context = exit_context(Decimal::ZERO, 1.0, 40.0);
+        assert_eq!(before_earnings.triggered(&context), None);
+        context
+            .days_to_events
+            .insert(MarketEventKind::Earnings, pos_or_panic!(5.0));
+        assert_eq!(before_earnings.triggered(&context), None);
+        context
+            .days_to_events
+            .insert(MarketEventKind::Earnings, pos_or_panic!(1.5));
+        assert_eq!(
+            before_earnings.triggered(&context),
+            Some(ExitReason::Other("Earnings in 1.5 days".to_string()))
+        );
+    }
+}
@@ -0,0 +1,463 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Historical Chain Sources
+//!
+//! A backtest replays stored option chains through time. The
+//! [`HistoricalChainSource`] trait is the boundary between the backtester and
+//! wherever those chains are archived: it lists the snapshot times of a
+//! symbol and returns the chains (one per expiration) known at a given time.
+//!
+//! Lookups are *as of* the requested time: the most recent snapshot at or
+//! before it is returned, never a later one, so a backtest cannot peek into
+//! the future. Sessions group snapshots by calendar day (UTC), and
+//! [`HistoricalChainSource::sessions`] replays the last snapshot of each one.
+//!
+//! Two sources are provided:
+//!
+//! * [`InMemoryChainSource`] - snapshots held in memory, for tests and for
+//!   archives loaded by user code.
+//! * [`CsvDirectorySource`] - chains written with
+//!   [`OptionChain::save_to_csv`](crate::chains::chain::OptionChain::save_to_csv)
+//!   into one directory per symbol and snapshot:
+//!
+//! ```text
+//! <root>/<SYMBOL>/<YYYY-MM-DD>/<chain>.csv
+//! <root>/<SYMBOL>/<YYYY-MM-DDTHHMMSS>/<chain>.csv
+//! ```
+//!
+//!   Date-only directories are stamped at midnight UTC. Files are read when
+//!   a snapshot is requested, so large archives are not loaded up front.
+
+use crate::chains::chain::OptionChain;
+use crate::error::ChainError;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Chains of one symbol at one point in time, one per expiration.
+#[derive(Debug, Clone)]
+pub struct ChainSnapshot {
+    /// Underlying symbol.
+    pub symbol: String,
+    /// Time of the snapshot.
+    pub timestamp: DateTime<Utc>,
+    /// Chains of the snapshot, one per expiration.
+    pub chains: Vec<OptionChain>,
+}
+
+impl ChainSnapshot {
+    /// Chain with the given expiration, as formatted by
+    /// [`OptionChain::get_expiration_date`].
+    pub fn chain(&self, expiration: &str) -> Option<&OptionChain> {
+        self.chains
+            .iter()
+            .find(|chain| chain.get_expiration_date() == expiration)
+    }
+}
+
+/// Archive of historical option chains that can be replayed by a backtest.
+pub trait HistoricalChainSource {
+    /// Symbols with at least one snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the archive cannot be read.
+    fn symbols(&self) -> Result<Vec<String>, ChainError>;
+
+    /// Times of the snapshots of `symbol`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the archive cannot be read.
+    fn timestamps(&self, symbol: &str) -> Result<Vec<DateTime<Utc>>, ChainError>;
+
+    /// Loads the snapshot of `symbol` taken exactly at `timestamp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if there is no snapshot at `timestamp` or it
+    /// cannot be read.
+    fn load(&self, symbol: &str, timestamp: DateTime<Utc>) -> Result<ChainSnapshot, ChainError>;
+
+    /// Most recent snapshot of `symbol` at or before `timestamp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if there is no snapshot at or before
+    /// `timestamp` or it cannot be read.
+    fn snapshot_at(
+        &self,
+        symbol: &str,
+        timestamp: DateTime<Utc>,
+    ) -> Result<ChainSnapshot, ChainError> {
+        let known = self
+            .timestamps(symbol)?
+            .into_iter()
+            .rev()
+            .find(|t| *t <= timestamp)
+            .ok_or_else(|| format!("No snapshot of {symbol} at or before {timestamp}"))?;
+        self.load(symbol, known)
+    }
+
+    /// Chain of `symbol` with the given expiration, as of `timestamp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if there is no snapshot at or before
+    /// `timestamp` or it has no chain for the expiration.
+    fn chain_at(
+        &self,
+        symbol: &str,
+        timestamp: DateTime<Utc>,
+        expiration: &str,
+    ) -> Result<OptionChain, ChainError> {
+        let snapshot = self.snapshot_at(symbol, timestamp)?;
+        snapshot.chain(expiration).cloned().ok_or_else(|| {
+            format!(
+                "No chain of {symbol} expiring {expiration} at {}",
+                snapshot.timestamp
+            )
+            .into()
+        })
+    }
+
+    /// Trading sessions of `symbol` and the time of the last snapshot of each.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the archive cannot be read.
+    fn session_closes(&self, symbol: &str) -> Result<Vec<(NaiveDate, DateTime<Utc>)>, ChainError> {
+        let mut closes: BTreeMap<NaiveDate, DateTime<Utc>> = BTreeMap::new();
+        for timestamp in self.timestamps(symbol)? {
+            closes.insert(timestamp.date_naive(), timestamp);
+        }
+        Ok(closes.into_iter().collect())
+    }
+
+    /// Iterates over the last snapshot of every session of `symbol`, oldest
+    /// first. Snapshots are loaded as the iterator advances.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the archive cannot be read; each item
+    /// carries the error of loading its own snapshot.
+    fn sessions<'a>(
+        &'a self,
+        symbol: &'a str,
+    ) -> Result<Box<dyn Iterator<Item = Result<ChainSnapshot, ChainError>> + 'a>, ChainError> {
+        let closes = self.session_closes(symbol)?;
+        Ok(Box::new(
+            closes
+                .into_iter()
+                .map(move |(_, timestamp)| self.load(symbol, timestamp)),
+        ))
+    }
+}
+
+/// Historical chains held in memory.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryChainSource {
+    snapshots: BTreeMap<String, BTreeMap<DateTime<Utc>, Vec<OptionChain>>>,
+}
+
+impl InMemoryChainSource {
+    /// Creates an empty source.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a chain to the snapshot of `symbol` at `timestamp`, replacing a
+    /// chain with the same expiration.
+    pub fn insert(&mut self, symbol: &str, timestamp: DateTime<Utc>, chain: OptionChain) {
+        let chains = self
+            .snapshots
+            .entry(symbol.to_string())
+            .or_default()
+            .entry(timestamp)
+            .or_default();
+        chains.retain(|existing| existing.get_expiration_date() != chain.get_expiration_date());
+        chains.push(chain);
+    }
+
+    /// Adds the chains of a snapshot.
+    pub fn with_snapshot(
+        mut self,
+        symbol: &str,
+        timestamp: DateTime<Utc>,
+        chains: Vec<OptionChain>,
+    ) -> Self {
+        for chain in chains {
+            self.insert(symbol, timestamp, chain);
+        }
+        self
+    }
+}
+
+impl HistoricalChainSource for InMemoryChainSource {
+    fn symbols(&self) -> Result<Vec<String>, ChainError> {
+        Ok(self.snapshots.keys().cloned().collect())
+    }
+
+    fn timestamps(&self, symbol: &str) -> Result<Vec<DateTime<Utc>>, ChainError> {
+        Ok(self
+            .snapshots
+            .get(symbol)
+            .map(|series| series.keys().copied().collect())
+            .unwrap_or_default())
+    }
+
+    fn load(&self, symbol: &str, timestamp: DateTime<Utc>) -> Result<ChainSnapshot, ChainError> {
+        let chains = self
+            .snapshots
+            .get(symbol)
+            .and_then(|series| series.get(&timestamp))
+            .ok_or_else(|| format!("No snapshot of {symbol} at {timestamp}"))?;
+        Ok(ChainSnapshot {
+            symbol: symbol.to_string(),
+            timestamp,
+            chains: chains.clone(),
+        })
+    }
+}
+
+/// Historical chains stored as CSV files, one directory per symbol and snapshot.
+#[derive(Debug, Clone)]
+pub struct CsvDirectorySource {
+    root: PathBuf,
+    index: BTreeMap<String, BTreeMap<DateTime<Utc>, PathBuf>>,
+}
+
+impl CsvDirectorySource {
+    /// Indexes the snapshot directories under `root`. Directories whose names
+    /// are not dates or date-times are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if `root` cannot be read.
+    pub fn new<P: AsRef<Path>>(root: P) -> Result<Self, ChainError> {
+        let root = root.as_ref().to_path_buf();
+        let mut index = BTreeMap::new();
+        for symbol_dir in subdirectories(&root)? {
+            let Some(symbol) = symbol_dir.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let mut snapshots = BTreeMap::new();
+            for snapshot_dir in subdirectories(&symbol_dir)? {
+                if let Some(timestamp) = snapshot_dir
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(parse_snapshot_name)
+                {
+                    snapshots.insert(timestamp, snapshot_dir);
+                }
+            }
+            if !snapshots.is_empty() {
+                index.insert(symbol.to_string(), snapshots);
+            }
+        }
+        Ok(CsvDirectorySource { root, index })
+    }
+
+    /// Root directory of the archive.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory name of a snapshot taken at `timestamp`.
+    pub fn snapshot_name(timestamp: DateTime<Utc>) -> String {
+        timestamp.format("%Y-%m-%dT%H%M%S").to_string()
+    }
+
+    /// Writes the chains of a snapshot under `root`, creating the directories
+    /// as needed, and returns the directory of the snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if a directory or file cannot be written.
+    pub fn write_snapshot<P: AsRef<Path>>(
+        root: P,
+        symbol: &str,
+        timestamp: DateTime<Utc>,
+        chains: &[OptionChain],
+    ) -> Result<PathBuf, ChainError> {
+        let dir = root
+            .as_ref()
+            .join(symbol)
+            .join(Self::snapshot_name(timestamp));
+        fs::create_dir_all(&dir)?;
+        let path = dir.to_string_lossy();
+        for chain in chains {
+            chain.save_to_csv(&path)?;
+        }
+        Ok(dir)
+    }
+}
+
+impl HistoricalChainSource for CsvDirectorySource {
+    fn symbols(&self) -> Result<Vec<String>, ChainError> {
+        Ok(self.index.keys().cloned().collect())
+    }
+
+    fn timestamps(&self, symbol: &str) -> Result<Vec<DateTime<Utc>>, ChainError> {
+        Ok(self
+            .index
+            .get(symbol)
+            .map(|series| series.keys().copied().collect())
+            .unwrap_or_default())
+    }
+
+    fn load(&self, symbol: &str, timestamp: DateTime<Utc>) -> Result<ChainSnapshot, ChainError> {
+        let dir = self
+            .index
+            .get(symbol)
+            .and_then(|series| series.get(&timestamp))
+            .ok_or_else(|| format!("No snapshot of {symbol} at {timestamp}"))?;
+        let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+            .collect();
+        files.sort();
+        let chains = files
+            .iter()
+            .map(|path| OptionChain::load_from_csv(&path.to_string_lossy()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ChainSnapshot {
+            symbol: symbol.to_string(),
+            timestamp,
+            chains,
+        })
+    }
+}
+
+fn subdirectories(dir: &Path) -> Result<Vec<PathBuf>, ChainError> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn parse_snapshot_name(name: &str) -> Option<DateTime<Utc>> {
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(name, "%Y-%m-%dT%H%M%S") {
+        return Some(datetime.and_utc());
+    }
+    NaiveDate::parse_from_str(name, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc())
+}
+
+#[cfg(test)]
+mod tests_source {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use chrono::{Duration, TimeZone};
+    use positive::{Positive, pos_or_panic, spos};
+    use rust_decimal::Decimal;
+
+    fn chain(price: f64, expiration: ExpirationDate) -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(100.0),
+            5,
+            spos!(5.0),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::new(price).unwrap())),
+                Some(expiration),
+                Some(Decimal::ZERO),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    fn at(day: i64, hour: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 3, 0, 0, 0).unwrap()
+            + Duration::days(day)
+            + Duration::hours(hour)
+    }
+
+    fn expiry(day: u32) -> ExpirationDate {
+        ExpirationDate::DateTime(Utc.with_ymd_and_hms(2030, 4, day, 16, 0, 0).unwrap())
+    }
+
+    #[test]
+    fn test_in_memory_as_of_lookup_and_sessions() {
+        let source = InMemoryChainSource::new()
+            .with_snapshot("XYZ", at(0, 10), vec![chain(100.0, expiry(18))])
+            .with_snapshot(
+                "XYZ",
+                at(0, 15),
+                vec![chain(101.0, expiry(18)), chain(101.0, expiry(25))],
+            )
+            .with_snapshot("XYZ", at(1, 15), vec![chain(99.0, expiry(18))]);
+
+        assert_eq!(source.symbols().unwrap(), vec!["XYZ"]);
+        assert_eq!(source.timestamps("XYZ").unwrap().len(), 3);
+        assert!(source.timestamps("ABC").unwrap().is_empty());
+
+        let snapshot = source.snapshot_at("XYZ", at(0, 12)).unwrap();
+        assert_eq!(snapshot.timestamp, at(0, 10));
+        assert_eq!(snapshot.chains[0].underlying_price, Positive::HUNDRED);
+        assert!(source.snapshot_at("XYZ", at(0, 9)).is_err());
+
+        let expiration = chain(101.0, expiry(25)).get_expiration_date();
+        assert!(source.chain_at("XYZ", at(0, 16), &expiration).is_ok());
+        assert!(source.chain_at("XYZ", at(1, 16), &expiration).is_err());
+
+        let sessions: Vec<ChainSnapshot> = source
+            .sessions("XYZ")
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].timestamp, at(0, 15));
+        assert_eq!(sessions[0].chains.len(), 2);
+        assert_eq!(sessions[1].chains[0].underlying_price, pos_or_panic!(99.0));
+    }
+
+    #[test]
+    fn test_csv_directory_roundtrip() {
+        let root = std::env::temp_dir().join("optionstratlib_chain_source_test");
+        fs::remove_dir_all(&root).ok();
+        CsvDirectorySource::write_snapshot(
+            &root,
+            "XYZ",
+            at(0, 15),
+            &[chain(100.0, expiry(18)), chain(100.0, expiry(25))],
+        )
+        .unwrap();
+        CsvDirectorySource::write_snapshot(&root, "XYZ", at(1, 15), &[chain(102.0, expiry(18))])
+            .unwrap();
+        fs::create_dir_all(root.join("XYZ").join("2025-03-10")).unwrap();
+        fs::create_dir_all(root.join("XYZ").join("notes")).unwrap();
+
+        let source = CsvDirectorySource::new(&root).unwrap();
+        assert_eq!(source.symbols().unwrap(), vec!["XYZ"]);
+        let timestamps = source.timestamps("XYZ").unwrap();
+        assert_eq!(timestamps, vec![at(0, 15), at(1, 15), at(7, 0)]);
+
+        let snapshot = source.snapshot_at("XYZ", at(1, 20)).unwrap();
+        assert_eq!(snapshot.chains.len(), 1);
+        assert_eq!(snapshot.chains[0].underlying_price, pos_or_panic!(102.0));
+        assert_eq!(snapshot.chains[0].symbol, "XYZ");
+        assert!(!snapshot.chains[0].options.is_empty());
+        assert_eq!(source.load("XYZ", at(0, 15)).unwrap().chains.len(), 2);
+        assert!(source.load("XYZ", at(7, 0)).unwrap().chains.is_empty());
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
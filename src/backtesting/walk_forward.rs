@@ -0,0 +1,467 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Walk-Forward Optimization
+//!
+//! Parameters tuned on the whole history look better than they will trade.
+//! [`WalkForward`] guards against that overfitting by splitting the history
+//! of a symbol into consecutive windows:
+//!
+//! 1. every candidate set of parameters is backtested on the in-sample part
+//!    of the window and scored with a [`WalkForwardObjective`];
+//! 2. the best candidate is then backtested on the out-of-sample part that
+//!    follows, which it has not seen;
+//! 3. the window moves forward by the out-of-sample length.
+//!
+//! Only the out-of-sample results are chained into the aggregate equity
+//! curve and analytics of the [`WalkForwardReport`]. Windows are rolling by
+//! default, or anchored to the start of the history with
+//! [`WalkForward::anchored`].
+//!
+//! Each part is replayed through a [`WindowedSource`], so trades still open
+//! when a part ends are reported at their last mark and never leak into the
+//! next part.
+
+use crate::backtesting::analytics::PerformanceAnalytics;
+use crate::backtesting::engine::{RuleBacktest, RuleBacktestReport};
+use crate::backtesting::source::{ChainSnapshot, HistoricalChainSource};
+use crate::error::ChainError;
+use chrono::{DateTime, Duration, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// View of a [`HistoricalChainSource`] restricted to the snapshots in
+/// `[start, end)`.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowedSource<'a, S: ?Sized> {
+    source: &'a S,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+impl<'a, S: HistoricalChainSource + ?Sized> WindowedSource<'a, S> {
+    /// Restricts `source` to the snapshots taken from `start` up to, but not
+    /// including, `end`.
+    pub fn new(source: &'a S, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        WindowedSource { source, start, end }
+    }
+
+    fn contains(&self, timestamp: DateTime<Utc>) -> bool {
+        timestamp >= self.start && timestamp < self.end
+    }
+}
+
+impl<S: HistoricalChainSource + ?Sized> HistoricalChainSource for WindowedSource<'_, S> {
+    fn symbols(&self) -> Result<Vec<String>, ChainError> {
+        self.source.symbols()
+    }
+
+    fn timestamps(&self, symbol: &str) -> Result<Vec<DateTime<Utc>>, ChainError> {
+        Ok(self
+            .source
+            .timestamps(symbol)?
+            .into_iter()
+            .filter(|timestamp| self.contains(*timestamp))
+            .collect())
+    }
+
+    fn load(&self, symbol: &str, timestamp: DateTime<Utc>) -> Result<ChainSnapshot, ChainError> {
+        if !self.contains(timestamp) {
+            return Err(format!(
+                "Snapshot of {symbol} at {timestamp} is outside the window {} - {}",
+                self.start, self.end
+            )
+            .into());
+        }
+        self.source.load(symbol, timestamp)
+    }
+}
+
+/// Score maximized when choosing the parameters of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WalkForwardObjective {
+    /// Total return.
+    TotalReturn,
+    /// Annualized Sharpe ratio.
+    #[default]
+    SharpeRatio,
+    /// Annualized Sortino ratio.
+    SortinoRatio,
+    /// Gross profit over gross loss of the closed trades.
+    ProfitFactor,
+    /// Negated maximum drawdown, favouring the shallowest.
+    MaxDrawdown,
+}
+
+impl WalkForwardObjective {
+    /// Score of `analytics`, `None` when it cannot be computed.
+    pub fn score(&self, analytics: &PerformanceAnalytics) -> Option<Decimal> {
+        match self {
+            WalkForwardObjective::TotalReturn => Some(analytics.total_return),
+            WalkForwardObjective::SharpeRatio => analytics.sharpe_ratio,
+            WalkForwardObjective::SortinoRatio => analytics.sortino_ratio,
+            WalkForwardObjective::ProfitFactor => analytics.profit_factor,
+            WalkForwardObjective::MaxDrawdown => Some(-analytics.max_drawdown),
+        }
+    }
+}
+
+/// In-sample optimization and out-of-sample result of one window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalkForwardWindow<P> {
+    /// First time of the in-sample part.
+    pub in_sample_start: DateTime<Utc>,
+    /// End of the in-sample part and start of the out-of-sample part.
+    pub out_of_sample_start: DateTime<Utc>,
+    /// End of the out-of-sample part, excluded.
+    pub out_of_sample_end: DateTime<Utc>,
+    /// Parameters chosen in sample.
+    pub parameters: P,
+    /// In-sample score of the chosen parameters.
+    pub in_sample_score: Option<Decimal>,
+    /// Out-of-sample score of the chosen parameters.
+    pub out_of_sample_score: Option<Decimal>,
+    /// In-sample analytics of the chosen parameters.
+    pub in_sample: PerformanceAnalytics,
+    /// Out-of-sample backtest of the chosen parameters.
+    pub out_of_sample: RuleBacktestReport,
+}
+
+/// Outcome of a walk-forward optimization.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalkForwardReport<P> {
+    /// Windows in chronological order.
+    pub windows: Vec<WalkForwardWindow<P>>,
+    /// Out-of-sample profit or loss of every window, chained end to end.
+    pub equity_curve: Vec<(DateTime<Utc>, Decimal)>,
+    /// Analytics of the chained out-of-sample results.
+    pub out_of_sample: PerformanceAnalytics,
+}
+
+impl<P> WalkForwardReport<P> {
+    /// Average out-of-sample score over average in-sample score of the
+    /// windows where both are known. Values well below one suggest the
+    /// parameters are fitted to noise.
+    pub fn efficiency(&self) -> Option<Decimal> {
+        let scored: Vec<(Decimal, Decimal)> = self
+            .windows
+            .iter()
+            .filter_map(|window| Some((window.in_sample_score?, window.out_of_sample_score?)))
+            .collect();
+        let in_sample: Decimal = scored.iter().map(|(score, _)| *score).sum();
+        let out_of_sample: Decimal = scored.iter().map(|(_, score)| *score).sum();
+        (!scored.is_empty() && !in_sample.is_zero()).then(|| out_of_sample / in_sample)
+    }
+}
+
+/// Walk-forward optimization of a [`RuleBacktest`] over candidate parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalkForward {
+    /// Days of history each candidate is scored on.
+    pub in_sample_days: Positive,
+    /// Days the chosen candidate is then tested on, and the step between windows.
+    pub out_of_sample_days: Positive,
+    /// Whether every in-sample part starts at the beginning of the history.
+    pub anchored: bool,
+    /// Capital returns are measured against.
+    pub initial_capital: Positive,
+    /// Annual risk-free rate of the Sharpe and Sortino ratios.
+    pub risk_free_rate: Decimal,
+    /// Score maximized in sample.
+    pub objective: WalkForwardObjective,
+}
+
+impl WalkForward {
+    /// Creates rolling windows of `in_sample_days` followed by
+    /// `out_of_sample_days`, scored by Sharpe ratio on a capital of 10,000.
+    pub fn new(in_sample_days: Positive, out_of_sample_days: Positive) -> Self {
+        WalkForward {
+            in_sample_days,
+            out_of_sample_days,
+            anchored: false,
+            initial_capital: Positive::TEN_THOUSAND,
+            risk_free_rate: Decimal::ZERO,
+            objective: WalkForwardObjective::default(),
+        }
+    }
+
+    /// Starts every in-sample part at the beginning of the history.
+    pub fn anchored(mut self) -> Self {
+        self.anchored = true;
+        self
+    }
+
+    /// Sets the capital returns are measured against.
+    pub fn with_initial_capital(mut self, initial_capital: Positive) -> Self {
+        self.initial_capital = initial_capital;
+        self
+    }
+
+    /// Sets the annual risk-free rate.
+    pub fn with_risk_free_rate(mut self, risk_free_rate: Decimal) -> Self {
+        self.risk_free_rate = risk_free_rate;
+        self
+    }
+
+    /// Sets the score maximized in sample.
+    pub fn with_objective(mut self, objective: WalkForwardObjective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Optimizes `candidates` window by window over the history of `symbol`.
+    ///
+    /// `build` turns a candidate into the backtest to run. Ties and windows
+    /// where no candidate can be scored keep the earliest candidate.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if there are no candidates, the window lengths
+    /// are zero, the history is shorter than one window, or a backtest fails.
+    pub fn run<S, P, F>(
+        &self,
+        source: &S,
+        symbol: &str,
+        candidates: &[P],
+        build: F,
+    ) -> Result<WalkForwardReport<P>, ChainError>
+    where
+        S: HistoricalChainSource + ?Sized,
+        P: Clone,
+        F: Fn(&P) -> RuleBacktest,
+    {
+        if candidates.is_empty() {
+            return Err("Walk-forward optimization needs at least one candidate".into());
+        }
+        let in_sample = days(self.in_sample_days);
+        let out_of_sample = days(self.out_of_sample_days);
+        if in_sample <= Duration::zero() || out_of_sample <= Duration::zero() {
+            return Err("Walk-forward windows must be at least one second long".into());
+        }
+        let timestamps = source.timestamps(symbol)?;
+        let (Some(first), Some(last)) = (timestamps.first(), timestamps.last()) else {
+            return Err(format!("No snapshots of {symbol}").into());
+        };
+
+        let mut windows = Vec::new();
+        let mut equity_curve = Vec::new();
+        let mut trade_results = Vec::new();
+        let mut carried = Decimal::ZERO;
+        let mut out_of_sample_start = *first + in_sample;
+        while out_of_sample_start <= *last {
+            let in_sample_start = if self.anchored {
+                *first
+            } else {
+                out_of_sample_start - in_sample
+            };
+            let out_of_sample_end = out_of_sample_start + out_of_sample;
+
+            let training = WindowedSource::new(source, in_sample_start, out_of_sample_start);
+            let mut best: Option<(usize, Option<Decimal>, PerformanceAnalytics)> = None;
+            for (index, candidate) in candidates.iter().enumerate() {
+                let analytics = self.analytics(&build(candidate).run(&training)?);
+                let score = self.objective.score(&analytics);
+                let better = match (&best, score) {
+                    (None, _) => true,
+                    (Some((_, None, _)), Some(_)) => true,
+                    (Some((_, Some(best_score), _)), Some(score)) => score > *best_score,
+                    _ => false,
+                };
+                if better {
+                    best = Some((index, score, analytics));
+                }
+            }
+            let Some((index, in_sample_score, in_sample_analytics)) = best else {
+                break;
+            };
+
+            let parameters = candidates[index].clone();
+            let testing = WindowedSource::new(source, out_of_sample_start, out_of_sample_end);
+            let report = build(&parameters).run(&testing)?;
+            let analytics = self.analytics(&report);
+            equity_curve.extend(
+                report
+                    .equity_curve
+                    .iter()
+                    .map(|(timestamp, profit_loss)| (*timestamp, carried + profit_loss)),
+            );
+            carried = equity_curve.last().map_or(carried, |(_, value)| *value);
+            trade_results.extend(report.closed_trades().map(|trade| trade.profit_loss));
+
+            windows.push(WalkForwardWindow {
+                in_sample_start,
+                out_of_sample_start,
+                out_of_sample_end,
+                parameters,
+                in_sample_score,
+                out_of_sample_score: self.objective.score(&analytics),
+                in_sample: in_sample_analytics,
+                out_of_sample: report,
+            });
+            out_of_sample_start = out_of_sample_end;
+        }
+
+        if windows.is_empty() {
+            return Err(format!(
+                "History of {symbol} is shorter than an in-sample window of {} days",
+                self.in_sample_days
+            )
+            .into());
+        }
+        let out_of_sample = PerformanceAnalytics::compute(
+            &equity_curve,
+            &trade_results,
+            self.initial_capital,
+            self.risk_free_rate,
+        );
+        Ok(WalkForwardReport {
+            windows,
+            equity_curve,
+            out_of_sample,
+        })
+    }
+
+    fn analytics(&self, report: &RuleBacktestReport) -> PerformanceAnalytics {
+        report.analytics(self.initial_capital, self.risk_free_rate)
+    }
+}
+
+fn days(days: Positive) -> Duration {
+    let seconds = (days.to_dec() * Decimal::from(SECONDS_PER_DAY))
+        .to_i64()
+        .unwrap_or(i64::MAX / 1_000);
+    Duration::seconds(seconds)
+}
+
+#[cfg(test)]
+mod tests_walk_forward {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::backtesting::rules::{ExitRule, LegTarget};
+    use crate::backtesting::source::InMemoryChainSource;
+    use crate::chains::chain::OptionChain;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::model::types::{OptionStyle, Side};
+    use chrono::TimeZone;
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn chain(price: f64) -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(100.0),
+            10,
+            spos!(5.0),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::new(price).unwrap())),
+                Some(ExpirationDate::DateTime(
+                    Utc.with_ymd_and_hms(2030, 8, 16, 18, 30, 0).unwrap(),
+                )),
+                Some(Decimal::ZERO),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(0.25),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    fn source() -> InMemoryChainSource {
+        let start = Utc.with_ymd_and_hms(2030, 5, 1, 20, 0, 0).unwrap();
+        // The underlying rallies for most of the history.
+        (0..12).fold(InMemoryChainSource::new(), |source, day| {
+            source.with_snapshot(
+                "XYZ",
+                start + Duration::days(day),
+                vec![chain(100.0 + 2.0 * day as f64)],
+            )
+        })
+    }
+
+    fn backtest(side: &Side) -> RuleBacktest {
+        RuleBacktest::new(
+            "XYZ",
+            vec![LegTarget::new(*side, OptionStyle::Call, dec!(0.5))],
+        )
+        .with_exit(ExitRule::TimeStop(pos_or_panic!(1.0)))
+    }
+
+    #[test]
+    fn test_rolling_windows_choose_in_sample_winner() {
+        let walk_forward = WalkForward::new(pos_or_panic!(4.0), pos_or_panic!(2.0))
+            .with_objective(WalkForwardObjective::TotalReturn);
+        let report = walk_forward
+            .run(&source(), "XYZ", &[Side::Short, Side::Long], backtest)
+            .unwrap();
+
+        // Sessions on days 0 to 11: out-of-sample parts start on days 4, 6, 8 and 10.
+        assert_eq!(report.windows.len(), 4);
+        for window in &report.windows {
+            assert_eq!(window.parameters, Side::Long);
+            assert_eq!(
+                window.out_of_sample_start - window.in_sample_start,
+                Duration::days(4)
+            );
+            assert!(
+                window
+                    .out_of_sample
+                    .equity_curve
+                    .iter()
+                    .all(|(timestamp, _)| {
+                        *timestamp >= window.out_of_sample_start
+                            && *timestamp < window.out_of_sample_end
+                    })
+            );
+        }
+        assert_eq!(report.equity_curve.len(), 8);
+        let closed: Decimal = report
+            .windows
+            .iter()
+            .flat_map(|window| window.out_of_sample.closed_trades())
+            .map(|trade| trade.profit_loss)
+            .sum();
+        assert!(closed > Decimal::ZERO);
+        assert!(report.out_of_sample.total_return > Decimal::ZERO);
+        assert!(report.efficiency().is_some());
+    }
+
+    #[test]
+    fn test_anchored_windows_and_errors() {
+        let source = source();
+        let anchored = WalkForward::new(pos_or_panic!(6.0), pos_or_panic!(3.0)).anchored();
+        let report = anchored
+            .run(&source, "XYZ", &[Side::Long], backtest)
+            .unwrap();
+        let first = report.windows[0].in_sample_start;
+        assert_eq!(report.windows.len(), 2);
+        assert!(report.windows.iter().all(|w| w.in_sample_start == first));
+
+        let too_long = WalkForward::new(pos_or_panic!(30.0), pos_or_panic!(5.0));
+        assert!(
+            too_long
+                .run(&source, "XYZ", &[Side::Long], backtest)
+                .is_err()
+        );
+        assert!(anchored.run(&source, "XYZ", &[], backtest).is_err());
+        assert!(
+            anchored
+                .run(&source, "ABC", &[Side::Long], backtest)
+                .is_err()
+        );
+
+        let window = WindowedSource::new(&source, first, first + Duration::days(2));
+        assert_eq!(window.timestamps("XYZ").unwrap().len(), 2);
+        assert!(window.load("XYZ", first + Duration::days(2)).is_err());
+    }
+}
@@ -0,0 +1,778 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Chain Deserialization Adapters
+//!
+//! Market data vendors publish option chains in very different JSON layouts.
+//! This module defines the [`ChainAdapter`] trait, which turns a vendor payload
+//! into one [`OptionChain`] per expiration, together with two serde based
+//! adapters covering the most common shapes:
+//!
+//! * [`FlatArrayAdapter`] - one JSON object per contract in a flat array, each
+//!   row carrying its own expiration, strike and call/put flag.
+//! * [`NestedExpiryAdapter`] - per-expiry maps keyed by expiration and then by
+//!   strike (`callExpDateMap` / `putExpDateMap` style).
+//!
+//! Field names are normalized through serde aliases (`strike`, `strikePrice`,
+//! `strike_price`, ...), so most vendors can be integrated by choosing the
+//! adapter matching the payload shape. A new provider with an exotic layout only
+//! needs a single `ChainAdapter` implementation.
+//!
+//! Vendors quote implied volatility either as a fraction (0.25) or as a
+//! percentage (25.0), and a value such as 1.2 is valid in both units, so
+//! every adapter declares the [`VolatilityUnit`] of its payloads. Chains are
+//! only built from strikes with an implied volatility; quotes without one,
+//! such as the ticks of a live feed, can still be read as
+//! [`AdaptedQuotes`].
+//!
+//! ```rust
+//! use optionstratlib::chains::{ChainAdapter, FlatArrayAdapter};
+//!
+//! let payload = r#"{
+//!     "symbol": "XYZ",
+//!     "underlyingPrice": 100.0,
+//!     "options": [
+//!         {"expiration": "2030-01-18", "strike": 100, "type": "call", "bid": 4.9, "ask": 5.1, "iv": 0.2},
+//!         {"expiration": "2030-01-18", "strike": 100, "type": "put", "bid": 4.4, "ask": 4.6}
+//!     ]
+//! }"#;
+//! let chains = FlatArrayAdapter::default().adapt_str(payload).unwrap();
+//! assert_eq!(chains.len(), 1);
+//! assert_eq!(chains[0].options.len(), 1);
+//! ```
+
+use crate::ExpirationDate;
+use crate::chains::chain::OptionChain;
+use crate::error::ChainError;
+use crate::model::types::OptionStyle;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Unit in which a vendor quotes implied volatility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VolatilityUnit {
+    /// Fraction of one, 0.25 for 25%.
+    #[default]
+    Decimal,
+    /// Percentage, 25.0 for 25%.
+    Percent,
+}
+
+impl VolatilityUnit {
+    /// Converts `value` quoted in this unit into a fraction.
+    pub fn to_fraction(&self, value: Decimal) -> Decimal {
+        match self {
+            VolatilityUnit::Decimal => value,
+            VolatilityUnit::Percent => value / Decimal::ONE_HUNDRED,
+        }
+    }
+}
+
+/// Converts a vendor specific JSON payload into option chains.
+///
+/// Implementations read the quotes of a payload; the chains are built from
+/// them, one per expiration found in the payload, sorted by expiration
+/// string.
+pub trait ChainAdapter {
+    /// Reads the quotes of an already parsed JSON value, with implied
+    /// volatilities as fractions.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the payload does not have the expected shape or
+    /// contains invalid values.
+    fn quotes_value(&self, payload: &Value) -> Result<AdaptedQuotes, ChainError>;
+
+    /// Converts an already parsed JSON value into option chains.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if [`ChainAdapter::quotes_value`] fails, the
+    /// payload has no underlying symbol or price, or a strike has no implied
+    /// volatility.
+    fn adapt_value(&self, payload: &Value) -> Result<Vec<OptionChain>, ChainError> {
+        self.quotes_value(payload)?.into_chains()
+    }
+
+    /// Parses `payload` as JSON and reads its quotes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the text is not valid JSON or if
+    /// [`ChainAdapter::quotes_value`] fails.
+    fn quotes_str(&self, payload: &str) -> Result<AdaptedQuotes, ChainError> {
+        let value: Value = serde_json::from_str(payload)?;
+        self.quotes_value(&value)
+    }
+
+    /// Parses `payload` as JSON and converts it into option chains.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the text is not valid JSON or if
+    /// [`ChainAdapter::adapt_value`] fails.
+    fn adapt_str(&self, payload: &str) -> Result<Vec<OptionChain>, ChainError> {
+        let value: Value = serde_json::from_str(payload)?;
+        self.adapt_value(&value)
+    }
+}
+
+/// A single vendor quote for one contract, with field names normalized through aliases.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct VendorQuote {
+    /// Strike price of the contract.
+    #[serde(alias = "strikePrice", alias = "strike_price")]
+    pub strike: Option<Decimal>,
+    /// Call/put flag (`call`, `put`, `C`, `P`, case insensitive).
+    #[serde(
+        alias = "type",
+        alias = "putCall",
+        alias = "option_type",
+        alias = "optionType",
+        alias = "right"
+    )]
+    pub kind: Option<String>,
+    /// Expiration date, usually `YYYY-MM-DD`.
+    #[serde(
+        alias = "expiration_date",
+        alias = "expirationDate",
+        alias = "expiry",
+        alias = "expDate"
+    )]
+    pub expiration: Option<String>,
+    /// Best bid price.
+    #[serde(alias = "bid_price", alias = "bidPrice")]
+    pub bid: Option<Decimal>,
+    /// Best ask price.
+    #[serde(alias = "ask_price", alias = "askPrice")]
+    pub ask: Option<Decimal>,
+    /// Implied volatility, in the [`VolatilityUnit`] of the adapter until
+    /// the quote is read and as a fraction afterwards.
+    #[serde(
+        alias = "iv",
+        alias = "volatility",
+        alias = "impliedVolatility",
+        alias = "implied_vol"
+    )]
+    pub implied_volatility: Option<Decimal>,
+    /// Delta of the contract.
+    pub delta: Option<Decimal>,
+    /// Gamma of the contract.
+    pub gamma: Option<Decimal>,
+    /// Traded volume.
+    #[serde(alias = "totalVolume", alias = "vol")]
+    pub volume: Option<Decimal>,
+    /// Open interest.
+    #[serde(alias = "openInterest", alias = "oi")]
+    pub open_interest: Option<Decimal>,
+}
+
+impl VendorQuote {
+    /// Returns the option style encoded in `kind`, if recognizable.
+    pub fn style(&self) -> Option<OptionStyle> {
+        match self.kind.as_deref()?.trim().to_ascii_lowercase().as_str() {
+            "c" | "call" | "calls" => Some(OptionStyle::Call),
+            "p" | "put" | "puts" => Some(OptionStyle::Put),
+            _ => None,
+        }
+    }
+
+    /// Best bid, `None` when missing or a negative sentinel.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the bid is not a valid price.
+    pub fn bid_price(&self) -> Result<Option<Positive>, ChainError> {
+        to_positive(self.bid)
+    }
+
+    /// Best ask, `None` when missing or a negative sentinel.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the ask is not a valid price.
+    pub fn ask_price(&self) -> Result<Option<Positive>, ChainError> {
+        to_positive(self.ask)
+    }
+
+    /// Implied volatility, `None` when missing or a negative sentinel.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the volatility is not a valid value.
+    pub fn volatility(&self) -> Result<Option<Positive>, ChainError> {
+        to_positive(self.implied_volatility)
+    }
+}
+
+/// Underlying level information shared by every adapter.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct Envelope {
+    #[serde(alias = "underlying", alias = "underlyingSymbol", alias = "root")]
+    symbol: Option<String>,
+    #[serde(
+        alias = "underlyingPrice",
+        alias = "underlying_last",
+        alias = "spot",
+        alias = "last"
+    )]
+    underlying_price: Option<Decimal>,
+    #[serde(alias = "interestRate", alias = "rate")]
+    risk_free_rate: Option<Decimal>,
+    #[serde(alias = "dividendYield")]
+    dividend_yield: Option<Decimal>,
+}
+
+/// Call and put quotes sharing one strike.
+pub type StrikeQuotes = (Option<VendorQuote>, Option<VendorQuote>);
+
+/// Quotes read from a vendor payload, grouped by expiration and strike.
+#[derive(Debug, Clone, Default)]
+pub struct AdaptedQuotes {
+    /// Underlying symbol.
+    pub symbol: Option<String>,
+    /// Underlying price.
+    pub underlying_price: Option<Positive>,
+    /// Risk-free rate.
+    pub risk_free_rate: Option<Decimal>,
+    /// Dividend yield of the underlying.
+    pub dividend_yield: Option<Positive>,
+    /// Call and put quotes per expiration and strike, with implied
+    /// volatilities as fractions.
+    pub rows: BTreeMap<String, BTreeMap<Positive, StrikeQuotes>>,
+}
+
+impl AdaptedQuotes {
+    fn new(envelope: Envelope) -> Result<Self, ChainError> {
+        Ok(AdaptedQuotes {
+            symbol: envelope.symbol,
+            underlying_price: envelope
+                .underlying_price
+                .map(Positive::new_decimal)
+                .transpose()?,
+            risk_free_rate: envelope.risk_free_rate,
+            dividend_yield: envelope
+                .dividend_yield
+                .map(Positive::new_decimal)
+                .transpose()?,
+            rows: BTreeMap::new(),
+        })
+    }
+
+    fn push(
+        &mut self,
+        expiration: &str,
+        style: OptionStyle,
+        mut quote: VendorQuote,
+        unit: VolatilityUnit,
+    ) -> Result<(), ChainError> {
+        let strike = quote
+            .strike
+            .ok_or_else(|| ChainError::invalid_parameters("strike", "Quote without strike"))?;
+        let strike = Positive::new_decimal(strike)?;
+        quote.implied_volatility = quote.implied_volatility.map(|v| unit.to_fraction(v));
+        let entry = self
+            .rows
+            .entry(expiration.to_string())
+            .or_default()
+            .entry(strike)
+            .or_default();
+        match style {
+            OptionStyle::Call => entry.0 = Some(quote),
+            OptionStyle::Put => entry.1 = Some(quote),
+        }
+        Ok(())
+    }
+
+    /// Builds one chain per expiration. The implied volatility of a strike
+    /// is the one of its call, or of its put when the call has none.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the payload has no underlying symbol or
+    /// price, or a strike has no implied volatility.
+    pub fn into_chains(self) -> Result<Vec<OptionChain>, ChainError> {
+        let symbol = self.symbol.ok_or_else(|| {
+            ChainError::invalid_parameters("symbol", "Payload without underlying symbol")
+        })?;
+        let underlying_price = self.underlying_price.ok_or_else(|| {
+            ChainError::invalid_parameters("underlying_price", "Payload without underlying price")
+        })?;
+
+        let mut chains = Vec::with_capacity(self.rows.len());
+        for (expiration, strikes) in self.rows {
+            let _ = ExpirationDate::from_string(&expiration)?;
+            let mut chain = OptionChain::new(
+                &symbol,
+                underlying_price,
+                expiration.clone(),
+                self.risk_free_rate,
+                self.dividend_yield,
+            );
+            for (strike, (call, put)) in strikes {
+                let call = call.unwrap_or_default();
+                let put = put.unwrap_or_default();
+                let implied_volatility =
+                    call.volatility()?.or(put.volatility()?).ok_or_else(|| {
+                        ChainError::invalid_parameters(
+                            "implied_volatility",
+                            &format!("No implied volatility at strike {strike} of {expiration}"),
+                        )
+                    })?;
+                let volume = match (call.volume, put.volume) {
+                    (None, None) => None,
+                    (c, p) => Some(Positive::new_decimal(
+                        c.unwrap_or_default() + p.unwrap_or_default(),
+                    )?),
+                };
+                let open_interest = match (call.open_interest, put.open_interest) {
+                    (None, None) => None,
+                    (c, p) => Some(decimal_to_u64(
+                        c.unwrap_or_default() + p.unwrap_or_default(),
+                    )?),
+                };
+                chain.add_option(
+                    strike,
+                    call.bid_price()?,
+                    call.ask_price()?,
+                    put.bid_price()?,
+                    put.ask_price()?,
+                    implied_volatility,
+                    call.delta,
+                    put.delta,
+                    call.gamma.or(put.gamma),
+                    volume,
+                    open_interest,
+                    None,
+                );
+            }
+            chains.push(chain);
+        }
+        Ok(chains)
+    }
+}
+
+/// Converts an optional vendor price into a `Positive`, treating negative sentinels as missing.
+fn to_positive(value: Option<Decimal>) -> Result<Option<Positive>, ChainError> {
+    match value {
+        Some(v) if v.is_sign_negative() => Ok(None),
+        Some(v) => Ok(Some(Positive::new_decimal(v)?)),
+        None => Ok(None),
+    }
+}
+
+fn decimal_to_u64(value: Decimal) -> Result<u64, ChainError> {
+    use num_traits::ToPrimitive;
+    value
+        .trunc()
+        .to_u64()
+        .ok_or_else(|| ChainError::invalid_parameters("open_interest", "Invalid open interest"))
+}
+
+/// Reads the envelope, falling back to the adapter values for the symbol and
+/// the underlying price the payload does not carry.
+fn read_envelope(
+    payload: &Value,
+    symbol: &Option<String>,
+    underlying_price: &Option<Positive>,
+) -> Result<Envelope, ChainError> {
+    let mut envelope = if payload.is_object() {
+        Envelope::deserialize(payload)?
+    } else {
+        Envelope::default()
+    };
+    if envelope.symbol.is_none() {
+        envelope.symbol = symbol.clone();
+    }
+    if envelope.underlying_price.is_none() {
+        envelope.underlying_price = underlying_price.map(|price| price.to_dec());
+    }
+    Ok(envelope)
+}
+
+/// Adapter for payloads with one object per contract in a flat array.
+///
+/// The array is taken from the first of `array_keys` present in the payload,
+/// or the payload itself if it is already an array. Every row must carry an
+/// expiration, a strike and a call/put flag.
+#[derive(Debug, Clone)]
+pub struct FlatArrayAdapter {
+    /// Candidate keys holding the contract array, tried in order.
+    pub array_keys: Vec<String>,
+    /// Symbol to use when the payload does not carry one.
+    pub symbol: Option<String>,
+    /// Underlying price to use when the payload does not carry one.
+    pub underlying_price: Option<Positive>,
+    /// Unit of the implied volatilities of the payload.
+    pub volatility_unit: VolatilityUnit,
+}
+
+impl Default for FlatArrayAdapter {
+    fn default() -> Self {
+        FlatArrayAdapter {
+            array_keys: ["options", "quotes", "contracts", "data", "results"]
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            symbol: None,
+            underlying_price: None,
+            volatility_unit: VolatilityUnit::Decimal,
+        }
+    }
+}
+
+impl FlatArrayAdapter {
+    /// Creates an adapter for bare arrays that carry no underlying information.
+    pub fn with_underlying(symbol: &str, underlying_price: Positive) -> Self {
+        FlatArrayAdapter {
+            symbol: Some(symbol.to_string()),
+            underlying_price: Some(underlying_price),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the unit of the implied volatilities of the payload.
+    pub fn with_volatility_unit(mut self, volatility_unit: VolatilityUnit) -> Self {
+        self.volatility_unit = volatility_unit;
+        self
+    }
+}
+
+impl ChainAdapter for FlatArrayAdapter {
+    fn quotes_value(&self, payload: &Value) -> Result<AdaptedQuotes, ChainError> {
+        let rows = match payload {
+            Value::Array(rows) => rows,
+            Value::Object(map) => self
+                .array_keys
+                .iter()
+                .find_map(|key| map.get(key).and_then(Value::as_array))
+                .ok_or_else(|| {
+                    ChainError::invalid_parameters("payload", "No contract array found")
+                })?,
+            _ => {
+                return Err(ChainError::invalid_parameters(
+                    "payload",
+                    "Expected a JSON object or array",
+                ));
+            }
+        };
+        let envelope = read_envelope(payload, &self.symbol, &self.underlying_price)?;
+
+        let mut quotes = AdaptedQuotes::new(envelope)?;
+        for row in rows {
+            let quote = VendorQuote::deserialize(row)?;
+            let style = quote.style().ok_or_else(|| {
+                ChainError::invalid_parameters("type", "Quote without a call/put flag")
+            })?;
+            let expiration = quote.expiration.clone().ok_or_else(|| {
+                ChainError::invalid_parameters("expiration", "Quote without expiration")
+            })?;
+            quotes.push(&expiration, style, quote, self.volatility_unit)?;
+        }
+        Ok(quotes)
+    }
+}
+
+/// Adapter for payloads grouping contracts by expiration and then by strike.
+///
+/// The expected layout is:
+///
+/// ```json
+/// {
+///   "symbol": "XYZ",
+///   "underlyingPrice": 100.0,
+///   "callExpDateMap": { "2030-01-18:30": { "100.0": [ { "bid": 1.0, "ask": 1.2 } ] } },
+///   "putExpDateMap":  { "2030-01-18:30": { "100.0": [ { "bid": 0.9, "ask": 1.1 } ] } }
+/// }
+/// ```
+///
+/// Expiration keys may carry a `:<days>` suffix, which is stripped. The strike
+/// level may hold either a single quote object or an array of quotes, in which
+/// case the first one is used. Implied volatilities are read as percentages,
+/// as in the vendors using this layout, unless another unit is set.
+#[derive(Debug, Clone)]
+pub struct NestedExpiryAdapter {
+    /// Key of the call map.
+    pub call_key: String,
+    /// Key of the put map.
+    pub put_key: String,
+    /// Symbol to use when the payload does not carry one.
+    pub symbol: Option<String>,
+    /// Underlying price to use when the payload does not carry one.
+    pub underlying_price: Option<Positive>,
+    /// Unit of the implied volatilities of the payload.
+    pub volatility_unit: VolatilityUnit,
+}
+
+impl Default for NestedExpiryAdapter {
+    fn default() -> Self {
+        NestedExpiryAdapter {
+            call_key: "callExpDateMap".to_string(),
+            put_key: "putExpDateMap".to_string(),
+            symbol: None,
+            underlying_price: None,
+            volatility_unit: VolatilityUnit::Percent,
+        }
+    }
+}
+
+impl NestedExpiryAdapter {
+    /// Creates an adapter reading calls and puts from custom keys.
+    pub fn with_keys(call_key: &str, put_key: &str) -> Self {
+        NestedExpiryAdapter {
+            call_key: call_key.to_string(),
+            put_key: put_key.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the unit of the implied volatilities of the payload.
+    pub fn with_volatility_unit(mut self, volatility_unit: VolatilityUnit) -> Self {
+        self.volatility_unit = volatility_unit;
+        self
+    }
+
+    fn collect(
+        &self,
+        map: Option<&Value>,
+        style: OptionStyle,
+        quotes: &mut AdaptedQuotes,
+    ) -> Result<(), ChainError> {
+        let Some(map) = map else {
+            return Ok(());
+        };
+        let expirations = map.as_object().ok_or_else(|| {
+            ChainError::invalid_parameters("expirations", "Expected an object keyed by expiration")
+        })?;
+        for (expiration_key, strikes) in expirations {
+            let expiration = expiration_key
+                .split(':')
+                .next()
+                .unwrap_or(expiration_key)
+                .to_string();
+            let strikes = strikes.as_object().ok_or_else(|| {
+                ChainError::invalid_parameters("strikes", "Expected an object keyed by strike")
+            })?;
+            for (strike_key, contracts) in strikes {
+                let contract = match contracts {
+                    Value::Array(items) => match items.first() {
+                        Some(first) => first,
+                        None => continue,
+                    },
+                    other => other,
+                };
+                let mut quote = VendorQuote::deserialize(contract)?;
+                if quote.strike.is_none() {
+                    quote.strike =
+                        Some(strike_key.parse::<Decimal>().map_err(|e| {
+                            ChainError::invalid_parameters("strike", &e.to_string())
+                        })?);
+                }
+                quotes.push(&expiration, style, quote, self.volatility_unit)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ChainAdapter for NestedExpiryAdapter {
+    fn quotes_value(&self, payload: &Value) -> Result<AdaptedQuotes, ChainError> {
+        if !payload.is_object() {
+            return Err(ChainError::invalid_parameters(
+                "payload",
+                "Expected a JSON object",
+            ));
+        }
+        let envelope = read_envelope(payload, &self.symbol, &self.underlying_price)?;
+        let mut quotes = AdaptedQuotes::new(envelope)?;
+        self.collect(payload.get(&self.call_key), OptionStyle::Call, &mut quotes)?;
+        self.collect(payload.get(&self.put_key), OptionStyle::Put, &mut quotes)?;
+        Ok(quotes)
+    }
+}
+
+#[cfg(test)]
+mod tests_adapters {
+    use super::*;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    const FLAT: &str = r#"{
+        "underlying": "XYZ",
+        "last": 101.5,
+        "rate": 0.04,
+        "quotes": [
+            {"expirationDate": "2030-01-18", "strikePrice": 100, "putCall": "CALL", "bidPrice": 5.0, "askPrice": 5.2, "impliedVolatility": 0.225, "delta": 0.55, "totalVolume": 10, "openInterest": 100},
+            {"expirationDate": "2030-01-18", "strikePrice": 100, "putCall": "PUT", "bidPrice": 3.0, "askPrice": 3.3, "delta": -0.45, "totalVolume": 5, "openInterest": 50},
+            {"expirationDate": "2030-01-18", "strikePrice": 105, "putCall": "C", "bidPrice": 2.0, "askPrice": 2.2, "iv": 0.21},
+            {"expirationDate": "2030-02-15", "strikePrice": 100, "putCall": "C", "bidPrice": 6.0, "askPrice": 6.4, "iv": 0.23}
+        ]
+    }"#;
+
+    #[test]
+    fn test_flat_array_adapter() {
+        let chains = FlatArrayAdapter::default().adapt_str(FLAT).unwrap();
+        assert_eq!(chains.len(), 2);
+        let first = &chains[0];
+        assert_eq!(first.symbol, "XYZ");
+        assert_eq!(first.underlying_price, pos_or_panic!(101.5));
+        assert_eq!(first.risk_free_rate, Some(dec!(0.04)));
+        assert_eq!(first.get_expiration_date(), "2030-01-18");
+        assert_eq!(first.options.len(), 2);
+
+        let atm = first.options.iter().next().unwrap();
+        assert_eq!(atm.strike_price, Positive::HUNDRED);
+        assert_eq!(atm.call_bid, Some(pos_or_panic!(5.0)));
+        assert_eq!(atm.put_ask, Some(pos_or_panic!(3.3)));
+        assert_eq!(atm.implied_volatility, pos_or_panic!(0.225));
+        assert_eq!(atm.delta_call, Some(dec!(0.55)));
+        assert_eq!(atm.delta_put, Some(dec!(-0.45)));
+        assert_eq!(atm.volume, Some(pos_or_panic!(15.0)));
+        assert_eq!(atm.open_interest, Some(150));
+        assert!(atm.call_middle.is_some());
+    }
+
+    #[test]
+    fn test_flat_array_bare_array_requires_underlying() {
+        let payload = r#"[{"expiration": "2030-01-18", "strike": 50, "type": "put", "bid": 1, "ask": 1.1, "iv": 0.3}]"#;
+        assert!(FlatArrayAdapter::default().adapt_str(payload).is_err());
+        let chains = FlatArrayAdapter::with_underlying("ABC", pos_or_panic!(52.0))
+            .adapt_str(payload)
+            .unwrap();
+        assert_eq!(chains[0].symbol, "ABC");
+        assert_eq!(chains[0].options.len(), 1);
+    }
+
+    #[test]
+    fn test_volatility_units_and_missing_volatility() {
+        let payload = r#"{"symbol": "X", "underlyingPrice": 10, "options": [
+            {"expiration": "2030-01-18", "strike": 10, "type": "c", "bid": 1, "ask": 1.1, "iv": 1.2},
+            {"expiration": "2030-01-18", "strike": 12, "type": "p", "bid": 2, "ask": 2.2}
+        ]}"#;
+        // A strike without implied volatility cannot be built into a chain...
+        assert!(FlatArrayAdapter::default().adapt_str(payload).is_err());
+        // ...but its quotes can still be read.
+        let quotes = FlatArrayAdapter::default().quotes_str(payload).unwrap();
+        let strikes = &quotes.rows["2030-01-18"];
+        let (call, _) = &strikes[&Positive::TEN];
+        assert_eq!(
+            call.as_ref().unwrap().volatility().unwrap(),
+            Some(pos_or_panic!(1.2))
+        );
+        let (_, put) = &strikes[&pos_or_panic!(12.0)];
+        assert_eq!(put.as_ref().unwrap().volatility().unwrap(), None);
+
+        let percent = FlatArrayAdapter::default()
+            .with_volatility_unit(VolatilityUnit::Percent)
+            .quotes_str(payload)
+            .unwrap();
+        let (call, _) = &percent.rows["2030-01-18"][&Positive::TEN];
+        assert_eq!(
+            call.as_ref().unwrap().volatility().unwrap(),
+            Some(pos_or_panic!(0.012))
+        );
+
+        // Adapter values only fill what the payload does not carry.
+        let quotes = FlatArrayAdapter::with_underlying("ABC", Positive::ONE)
+            .quotes_str(payload)
+            .unwrap();
+        assert_eq!(quotes.symbol.as_deref(), Some("X"));
+        assert_eq!(quotes.underlying_price, Some(Positive::TEN));
+    }
+
+    #[test]
+    fn test_negative_volatility_sentinel_is_missing() {
+        let payload = r#"{"symbol": "X", "underlyingPrice": 10, "options": [
+            {"expiration": "2030-01-18", "strike": 10, "type": "c", "bid": 1, "ask": 1.1, "implied_volatility": -1},
+            {"expiration": "2030-01-18", "strike": 10, "type": "p", "bid": 0.5, "ask": 0.6, "implied_volatility": 0.25}
+        ]}"#;
+        let quotes = FlatArrayAdapter::default().quotes_str(payload).unwrap();
+        let (call, _) = &quotes.rows["2030-01-18"][&Positive::TEN];
+        assert_eq!(call.as_ref().unwrap().volatility().unwrap(), None);
+
+        // The strike falls back to the volatility of its put.
+        let chains = FlatArrayAdapter::default().adapt_str(payload).unwrap();
+        let option = chains[0].options.iter().next().unwrap();
+        assert_eq!(option.implied_volatility, pos_or_panic!(0.25));
+        assert_eq!(option.call_bid, Some(Positive::ONE));
+    }
+
+    #[test]
+    fn test_flat_array_rejects_missing_type() {
+        let payload = r#"{"symbol": "X", "underlyingPrice": 10, "options": [{"expiration": "2030-01-18", "strike": 10}]}"#;
+        assert!(FlatArrayAdapter::default().adapt_str(payload).is_err());
+    }
+
+    #[test]
+    fn test_flat_array_rejects_bad_expiration() {
+        let payload = r#"{"symbol": "X", "underlyingPrice": 10, "options": [{"expiration": "someday", "strike": 10, "type": "c"}]}"#;
+        assert!(FlatArrayAdapter::default().adapt_str(payload).is_err());
+    }
+
+    #[test]
+    fn test_nested_expiry_adapter() {
+        let payload = r#"{
+            "symbol": "XYZ",
+            "underlyingPrice": 100.0,
+            "callExpDateMap": {
+                "2030-01-18:30": {
+                    "95.0": [{"bid": 6.0, "ask": 6.3, "volatility": 25.0, "delta": 0.7}],
+                    "100.0": [{"bid": 3.0, "ask": 3.2, "volatility": 24.0}]
+                }
+            },
+            "putExpDateMap": {
+                "2030-01-18:30": {
+                    "100.0": {"bid": 2.8, "ask": 3.0, "delta": -0.5}
+                }
+            }
+        }"#;
+        let chains = NestedExpiryAdapter::default().adapt_str(payload).unwrap();
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].get_expiration_date(), "2030-01-18");
+        assert_eq!(chains[0].options.len(), 2);
+        let strike_100 = chains[0]
+            .options
+            .iter()
+            .find(|o| o.strike_price == Positive::HUNDRED)
+            .unwrap();
+        assert_eq!(strike_100.call_ask, Some(pos_or_panic!(3.2)));
+        assert_eq!(strike_100.put_bid, Some(pos_or_panic!(2.8)));
+        assert_eq!(strike_100.implied_volatility, pos_or_panic!(0.24));
+        assert_eq!(strike_100.delta_put, Some(dec!(-0.5)));
+    }
+
+    #[test]
+    fn test_nested_expiry_custom_keys() {
+        let payload = r#"{"root": "Q", "spot": 20, "calls": {"2030-03-15": {"20": [{"bid": 1, "ask": 1.5, "volatility": 30}]}}}"#;
+        let chains = NestedExpiryAdapter::with_keys("calls", "puts")
+            .adapt_str(payload)
+            .unwrap();
+        assert_eq!(chains[0].symbol, "Q");
+        assert_eq!(chains[0].options.len(), 1);
+        assert_eq!(
+            chains[0].options.iter().next().unwrap().implied_volatility,
+            pos_or_panic!(0.3)
+        );
+    }
+
+    #[test]
+    fn test_vendor_quote_style() {
+        let mut quote = VendorQuote {
+            kind: Some(" Call ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(quote.style(), Some(OptionStyle::Call));
+        quote.kind = Some("P".to_string());
+        assert_eq!(quote.style(), Some(OptionStyle::Put));
+        quote.kind = Some("straddle".to_string());
+        assert_eq!(quote.style(), None);
+    }
+}
@@ -0,0 +1,328 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Bulk Chain Pricing
+//!
+//! Prices and Greeks for every strike of an [`OptionChain`] in one call,
+//! returned as columns aligned with the strikes of the chain.
+//!
+//! All options of a chain share their underlying price, expiration, rate and
+//! dividend yield, so with the closed-form engine the discount factors are
+//! computed once per chain and `d1`, `d2` and the normal CDF evaluations once
+//! per strike for both the call and the put, through [`BatchBlackScholes`].
+//! Other engines fall back to pricing each contract individually.
+
+use crate::chains::chain::OptionChain;
+use crate::error::ChainError;
+use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
+use crate::pricing::{BatchBlackScholes, PricingEngine, price_option};
+use crate::{ExpirationDate, Options};
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Call and put prices of every strike of a chain.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChainPrices {
+    /// Strikes of the chain, in ascending order.
+    pub strikes: Vec<Positive>,
+    /// Call price per strike.
+    pub call_prices: Vec<Decimal>,
+    /// Put price per strike.
+    pub put_prices: Vec<Decimal>,
+}
+
+/// Call and put Greeks of every strike of a chain, per unit of a long
+/// position.
+///
+/// Vega and rho are per 1% change and theta per day, as in
+/// [`crate::greeks::Greeks`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChainGreeks {
+    /// Strikes of the chain, in ascending order.
+    pub strikes: Vec<Positive>,
+    /// Call delta per strike.
+    pub call_delta: Vec<Decimal>,
+    /// Put delta per strike.
+    pub put_delta: Vec<Decimal>,
+    /// Gamma per strike, equal for calls and puts.
+    pub gamma: Vec<Decimal>,
+    /// Vega per strike, equal for calls and puts.
+    pub vega: Vec<Decimal>,
+    /// Call theta per strike.
+    pub call_theta: Vec<Decimal>,
+    /// Put theta per strike.
+    pub put_theta: Vec<Decimal>,
+    /// Call rho per strike.
+    pub call_rho: Vec<Decimal>,
+    /// Put rho per strike.
+    pub put_rho: Vec<Decimal>,
+}
+
+impl OptionChain {
+    /// Prices the call and the put of every strike with `engine`, measuring
+    /// the time to expiration with `day_count`.
+    ///
+    /// With [`PricingEngine::ClosedFormBS`] the whole chain is priced by a
+    /// single [`BatchBlackScholes`] pass; other engines price each contract
+    /// with [`price_option`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the chain has no valid expiration, has
+    /// already expired or a contract cannot be priced.
+    pub fn price_all(
+        &self,
+        engine: &PricingEngine,
+        day_count: &DayCount,
+    ) -> Result<ChainPrices, ChainError> {
+        let strikes: Vec<Positive> = self.options.iter().map(|row| row.strike_price).collect();
+        match engine {
+            PricingEngine::ClosedFormBS => {
+                let (pricer, strike_column, volatilities) = self.batch_inputs(day_count)?;
+                let (calls, puts) = pricer
+                    .price_calls_and_puts(&strike_column, &volatilities)
+                    .map_err(|e| ChainError::invalid_price_calculation(&e.to_string()))?;
+                Ok(ChainPrices {
+                    strikes,
+                    call_prices: to_decimals(&calls)?,
+                    put_prices: to_decimals(&puts)?,
+                })
+            }
+            PricingEngine::MonteCarlo { .. } => {
+                let expiration = self.shared_expiration()?;
+                let mut call_prices = Vec::with_capacity(strikes.len());
+                let mut put_prices = Vec::with_capacity(strikes.len());
+                for row in &self.options {
+                    for (style, prices) in [
+                        (OptionStyle::Call, &mut call_prices),
+                        (OptionStyle::Put, &mut put_prices),
+                    ] {
+                        let option = self.contract(
+                            expiration,
+                            row.strike_price,
+                            row.implied_volatility,
+                            style,
+                            day_count,
+                        );
+                        let price = price_option(&option, engine)
+                            .map_err(|e| ChainError::invalid_price_calculation(&e.to_string()))?;
+                        prices.push(price.to_dec());
+                    }
+                }
+                Ok(ChainPrices {
+                    strikes,
+                    call_prices,
+                    put_prices,
+                })
+            }
+        }
+    }
+
+    /// Computes the Black-Scholes Greeks of the call and the put of every
+    /// strike in a single [`BatchBlackScholes`] pass, measuring the time to
+    /// expiration and theta with `day_count`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the chain has no valid expiration or has
+    /// already expired.
+    pub fn greeks_all(&self, day_count: &DayCount) -> Result<ChainGreeks, ChainError> {
+        let (pricer, strike_column, volatilities) = self.batch_inputs(day_count)?;
+        let greeks = pricer
+            .greeks(&strike_column, &volatilities)
+            .map_err(|e| ChainError::invalid_price_calculation(&e.to_string()))?;
+        Ok(ChainGreeks {
+            strikes: self.options.iter().map(|row| row.strike_price).collect(),
+            call_delta: to_decimals(&greeks.call_delta)?,
+            put_delta: to_decimals(&greeks.put_delta)?,
+            gamma: to_decimals(&greeks.gamma)?,
+            vega: to_decimals(&greeks.vega)?,
+            call_theta: to_decimals(&greeks.call_theta)?,
+            put_theta: to_decimals(&greeks.put_theta)?,
+            call_rho: to_decimals(&greeks.call_rho)?,
+            put_rho: to_decimals(&greeks.put_rho)?,
+        })
+    }
+
+    fn shared_expiration(&self) -> Result<ExpirationDate, ChainError> {
+        self.get_expiration().ok_or_else(|| {
+            ChainError::invalid_parameters(
+                "expiration_date",
+                &format!(
+                    "cannot parse chain expiration '{}'",
+                    self.get_expiration_date()
+                ),
+            )
+        })
+    }
+
+    fn batch_inputs(
+        &self,
+        day_count: &DayCount,
+    ) -> Result<(BatchBlackScholes, Vec<f64>, Vec<f64>), ChainError> {
+        let expiration = self.shared_expiration()?;
+        let pricer = BatchBlackScholes::new(
+            self.underlying_price,
+            day_count.year_fraction(&expiration)?,
+            self.risk_free_rate.unwrap_or(Decimal::ZERO),
+            self.dividend_yield.unwrap_or(Positive::ZERO),
+        )
+        .map(|pricer| pricer.with_day_count(day_count))
+        .map_err(|e| ChainError::invalid_price_calculation(&e.to_string()))?;
+        let strikes = self
+            .options
+            .iter()
+            .map(|row| row.strike_price.to_f64())
+            .collect();
+        let volatilities = self
+            .options
+            .iter()
+            .map(|row| row.implied_volatility.to_f64())
+            .collect();
+        Ok((pricer, strikes, volatilities))
+    }
+
+    fn contract(
+        &self,
+        expiration_date: ExpirationDate,
+        strike_price: Positive,
+        implied_volatility: Positive,
+        option_style: OptionStyle,
+        day_count: &DayCount,
+    ) -> Options {
+        Options {
+            option_type: OptionType::European,
+            side: Side::Long,
+            underlying_symbol: self.symbol.clone(),
+            strike_price,
+            expiration_date,
+            implied_volatility,
+            quantity: Positive::ONE,
+            underlying_price: self.underlying_price,
+            risk_free_rate: self.risk_free_rate.unwrap_or(Decimal::ZERO),
+            option_style,
+            dividend_yield: self.dividend_yield.unwrap_or(Positive::ZERO),
+            exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: day_count.clone(),
+        }
+    }
+}
+
+fn to_decimals(values: &[f64]) -> Result<Vec<Decimal>, ChainError> {
+    values
+        .iter()
+        .map(|value| {
+            Decimal::from_f64(*value).ok_or_else(|| {
+                ChainError::invalid_price_calculation("value is not representable as Decimal")
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_bulk {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::greeks::{delta, gamma, theta};
+    use crate::pricing::black_scholes;
+    use num_traits::ToPrimitive;
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn build_chain() -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(1000.0),
+            10,
+            spos!(5.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::HUNDRED)),
+                Some(ExpirationDate::Days(pos_or_panic!(45.0))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    #[test]
+    fn test_price_all_matches_per_option_pricing() {
+        let chain = build_chain();
+        let expiration = chain.get_expiration().unwrap();
+        for day_count in [DayCount::Act365Fixed, DayCount::Act360] {
+            let prices = chain
+                .price_all(&PricingEngine::ClosedFormBS, &day_count)
+                .unwrap();
+            assert_eq!(prices.strikes.len(), chain.options.len());
+
+            for (i, row) in chain.options.iter().enumerate() {
+                assert_eq!(prices.strikes[i], row.strike_price);
+                for (style, price) in [
+                    (OptionStyle::Call, prices.call_prices[i]),
+                    (OptionStyle::Put, prices.put_prices[i]),
+                ] {
+                    let option = chain.contract(
+                        expiration,
+                        row.strike_price,
+                        row.implied_volatility,
+                        style,
+                        &day_count,
+                    );
+                    let expected = black_scholes(&option).unwrap();
+                    assert!((price - expected).abs() < dec!(1e-8));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_greeks_all_matches_per_option_greeks() {
+        let chain = build_chain();
+        let expiration = chain.get_expiration().unwrap();
+        for day_count in [DayCount::Act365Fixed, DayCount::Act360] {
+            let greeks = chain.greeks_all(&day_count).unwrap();
+            for (i, row) in chain.options.iter().enumerate() {
+                let call = chain.contract(
+                    expiration,
+                    row.strike_price,
+                    row.implied_volatility,
+                    OptionStyle::Call,
+                    &day_count,
+                );
+                let mut put = call.clone();
+                put.option_style = OptionStyle::Put;
+                let close = |a: Decimal, b: Decimal| (a - b).abs().to_f64().unwrap() < 1e-8;
+                assert!(close(greeks.call_delta[i], delta(&call).unwrap()));
+                assert!(close(greeks.put_delta[i], delta(&put).unwrap()));
+                assert!(close(greeks.gamma[i], gamma(&call).unwrap()));
+                assert!(close(greeks.call_theta[i], theta(&call).unwrap()));
+                assert!(close(greeks.put_theta[i], theta(&put).unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_price_all_requires_expiration() {
+        let mut chain = build_chain();
+        chain.update_expiration_date("not a date".to_string());
+        assert!(
+            chain
+                .price_all(&PricingEngine::ClosedFormBS, &DayCount::Act365Fixed)
+                .is_err()
+        );
+        assert!(chain.greeks_all(&DayCount::Act365Fixed).is_err());
+    }
+}
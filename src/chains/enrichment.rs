@@ -0,0 +1,462 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Chain Enrichment Pipeline
+//!
+//! Batch enrichment of many option chains (one per underlying). Each chain is
+//! processed independently: implied volatilities are re-solved from mid
+//! prices, Greeks are refreshed and a liquidity score is computed per strike.
+//! With the `parallel` feature, chains and the implied volatilities of the
+//! strikes of a chain are processed on the rayon thread pool.
+//!
+//! The pipeline reports per-symbol progress through a user supplied callback
+//! and can be cancelled cooperatively through a [`CancellationToken`], which
+//! makes it suitable for nightly analytics jobs over hundreds of symbols.
+//!
+//! ```rust
+//! use optionstratlib::chains::chain::OptionChain;
+//! use optionstratlib::chains::{CancellationToken, ChainEnrichmentPipeline, EnrichmentConfig};
+//!
+//! let chains: Vec<OptionChain> = Vec::new();
+//! let pipeline = ChainEnrichmentPipeline::new(EnrichmentConfig::default());
+//! let token = CancellationToken::new();
+//! let reports = pipeline.run(chains, &token, |progress| {
+//!     println!("{}: {}/{}", progress.symbol, progress.completed, progress.total);
+//! });
+//! assert!(reports.is_empty());
+//! ```
+
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::error::ChainError;
+use crate::model::types::{OptionStyle, Side};
+use crate::utils::parallel::map_ordered;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use tracing::{debug, warn};
+
+/// Volume at which the volume component of the liquidity score reaches 0.5.
+const LIQUIDITY_VOLUME_HALF_POINT: Decimal = dec!(100);
+
+/// Open interest at which the open interest component of the liquidity score reaches 0.5.
+const LIQUIDITY_OPEN_INTEREST_HALF_POINT: Decimal = dec!(1000);
+
+/// Selects which enrichment steps are applied to every chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnrichmentConfig {
+    /// Re-solve the implied volatility of each strike from the quoted mid price.
+    pub solve_iv: bool,
+    /// Recompute delta and gamma for every strike.
+    pub compute_greeks: bool,
+    /// Compute a liquidity score per strike from spreads, volume and open interest.
+    pub compute_liquidity: bool,
+}
+
+impl Default for EnrichmentConfig {
+    fn default() -> Self {
+        EnrichmentConfig {
+            solve_iv: true,
+            compute_greeks: true,
+            compute_liquidity: true,
+        }
+    }
+}
+
+/// Cooperative cancellation flag shared between the caller and the pipeline.
+///
+/// Cloning the token shares the same underlying flag, so a clone can be handed
+/// to another thread (e.g. a signal handler) and used to stop a running batch.
+/// Chains that have already started are allowed to finish; chains that have
+/// not started yet are reported as [`EnrichmentStatus::Cancelled`].
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new, non-cancelled token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of every pipeline observing this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` once [`CancellationToken::cancel`] has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Final state of a single symbol in an enrichment batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EnrichmentStatus {
+    /// The chain was fully enriched.
+    Completed,
+    /// Enrichment failed; the message describes the error.
+    Failed(String),
+    /// The batch was cancelled before this chain was processed.
+    Cancelled,
+}
+
+/// Progress notification emitted once per symbol as it finishes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnrichmentProgress {
+    /// Symbol of the chain that just finished.
+    pub symbol: String,
+    /// Number of chains finished so far, including this one.
+    pub completed: usize,
+    /// Total number of chains in the batch.
+    pub total: usize,
+    /// Outcome for this symbol.
+    pub status: EnrichmentStatus,
+}
+
+/// Liquidity score for both sides of a single strike.
+///
+/// Scores are in the `[0, 1]` range, higher meaning more liquid. `None` means
+/// that no market data was available to score that side.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidityScore {
+    /// Strike price the score refers to.
+    pub strike_price: Positive,
+    /// Liquidity score of the call at this strike.
+    pub call_score: Option<Decimal>,
+    /// Liquidity score of the put at this strike.
+    pub put_score: Option<Decimal>,
+}
+
+/// A chain after enrichment together with the derived per-strike data.
+#[derive(Debug, Clone)]
+pub struct EnrichedChain {
+    /// The enriched option chain.
+    pub chain: OptionChain,
+    /// Liquidity scores per strike, empty if liquidity scoring was disabled.
+    pub liquidity: Vec<LiquidityScore>,
+    /// Number of strikes whose implied volatility could not be solved.
+    pub iv_failures: usize,
+}
+
+/// Per-symbol result of an enrichment batch.
+#[derive(Debug)]
+pub struct EnrichmentReport {
+    /// Symbol of the processed chain.
+    pub symbol: String,
+    /// Outcome of the enrichment.
+    pub status: EnrichmentStatus,
+    /// The enriched chain, present only when `status` is [`EnrichmentStatus::Completed`].
+    pub result: Option<EnrichedChain>,
+}
+
+/// Parallel enrichment pipeline for multi-underlying boards.
+#[derive(Debug, Clone, Default)]
+pub struct ChainEnrichmentPipeline {
+    config: EnrichmentConfig,
+}
+
+impl ChainEnrichmentPipeline {
+    /// Creates a pipeline applying the steps selected in `config`.
+    pub fn new(config: EnrichmentConfig) -> Self {
+        ChainEnrichmentPipeline { config }
+    }
+
+    /// Returns the configuration of this pipeline.
+    pub fn config(&self) -> &EnrichmentConfig {
+        &self.config
+    }
+
+    /// Enriches every chain in parallel.
+    ///
+    /// `on_progress` is invoked once per chain, from whichever worker thread
+    /// finished it, so it must be `Sync`. The returned reports keep the order
+    /// of the input chains regardless of completion order.
+    ///
+    /// # Arguments
+    ///
+    /// * `chains` - The chains to enrich, typically one per underlying.
+    /// * `token` - Cancellation token checked before each chain starts.
+    /// * `on_progress` - Callback receiving an [`EnrichmentProgress`] per finished chain.
+    pub fn run<F>(
+        &self,
+        chains: Vec<OptionChain>,
+        token: &CancellationToken,
+        on_progress: F,
+    ) -> Vec<EnrichmentReport>
+    where
+        F: Fn(&EnrichmentProgress) + Sync,
+    {
+        let total = chains.len();
+        let completed = AtomicUsize::new(0);
+
+        map_ordered(chains, |chain| {
+            let symbol = chain.symbol.clone();
+            let (status, result) = if token.is_cancelled() {
+                (EnrichmentStatus::Cancelled, None)
+            } else {
+                match self.enrich(chain) {
+                    Ok(enriched) => (EnrichmentStatus::Completed, Some(enriched)),
+                    Err(e) => {
+                        warn!("Enrichment failed for {}: {}", symbol, e);
+                        (EnrichmentStatus::Failed(e.to_string()), None)
+                    }
+                }
+            };
+            let done = completed.fetch_add(1, Ordering::SeqCst) + 1;
+            on_progress(&EnrichmentProgress {
+                symbol: symbol.clone(),
+                completed: done,
+                total,
+                status: status.clone(),
+            });
+            EnrichmentReport {
+                symbol,
+                status,
+                result,
+            }
+        })
+    }
+
+    /// Enriches a single chain synchronously using the configured steps.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the chain parameters cannot be propagated to its
+    /// strikes.
+    pub fn enrich(&self, mut chain: OptionChain) -> Result<EnrichedChain, ChainError> {
+        chain.set_optiondata_extra_params()?;
+        chain.update_mid_prices();
+
+        let mut iv_failures = 0;
+        if self.config.solve_iv {
+            let solved = map_ordered(
+                chain.options.iter().cloned().collect(),
+                |mut option_data| {
+                    let failed = solve_implied_volatility(&mut option_data).is_err();
+                    (option_data, failed)
+                },
+            );
+            let mut options = BTreeSet::new();
+            for (option_data, failed) in solved {
+                if failed {
+                    iv_failures += 1;
+                }
+                options.insert(option_data);
+            }
+            chain.options = options;
+        }
+
+        if self.config.compute_greeks {
+            chain.update_greeks();
+        }
+
+        let liquidity = if self.config.compute_liquidity {
+            chain.options.iter().map(liquidity_score).collect()
+        } else {
+            Vec::new()
+        };
+
+        debug!(
+            "Enriched {} ({} strikes, {} IV failures)",
+            chain.symbol,
+            chain.options.len(),
+            iv_failures
+        );
+        Ok(EnrichedChain {
+            chain,
+            liquidity,
+            iv_failures,
+        })
+    }
+}
+
+/// Solves the implied volatility of a strike from its call mid price, falling
+/// back to the put mid price, and stores it in the option data.
+fn solve_implied_volatility(option_data: &mut OptionData) -> Result<(), ChainError> {
+    let (call_mid, put_mid) = option_data.get_mid_prices();
+    let (style, market_price) = match (call_mid, put_mid) {
+        (Some(price), _) if price > Positive::ZERO => (OptionStyle::Call, price),
+        (_, Some(price)) if price > Positive::ZERO => (OptionStyle::Put, price),
+        _ => {
+            return Err(ChainError::invalid_prices(
+                None,
+                None,
+                "No mid price available to solve implied volatility",
+            ));
+        }
+    };
+    let option = option_data.get_option(Side::Long, style)?;
+    let iv = option
+        .calculate_implied_volatility(market_price.to_dec())
+        .map_err(|e| ChainError::invalid_volatility(None, &e.to_string()))?;
+    option_data.set_volatility(&iv);
+    Ok(())
+}
+
+/// Scores one side of a strike from its relative spread, volume and open interest.
+///
+/// Each available component is mapped to `[0, 1]` and the score is their mean:
+/// the spread component is `1 - min(spread / mid, 1)`, while volume and open
+/// interest use the saturating form `x / (x + half_point)`.
+fn side_score(
+    spread_per: Option<Positive>,
+    volume: Option<Positive>,
+    open_interest: Option<u64>,
+) -> Option<Decimal> {
+    let spread_per = spread_per?;
+    let mut components = vec![Decimal::ONE - spread_per.to_dec().min(Decimal::ONE)];
+    if let Some(volume) = volume {
+        components.push(volume.to_dec() / (volume.to_dec() + LIQUIDITY_VOLUME_HALF_POINT));
+    }
+    if let Some(open_interest) = open_interest {
+        let oi = Decimal::from(open_interest);
+        components.push(oi / (oi + LIQUIDITY_OPEN_INTEREST_HALF_POINT));
+    }
+    let count = Decimal::from(components.len());
+    Some(components.into_iter().sum::<Decimal>() / count)
+}
+
+/// Computes the [`LiquidityScore`] of a strike.
+pub(crate) fn liquidity_score(option_data: &OptionData) -> LiquidityScore {
+    LiquidityScore {
+        strike_price: option_data.strike_price,
+        call_score: side_score(
+            option_data.get_call_spread_per(),
+            option_data.volume,
+            option_data.open_interest,
+        ),
+        put_score: side_score(
+            option_data.get_put_spread_per(),
+            option_data.volume,
+            option_data.open_interest,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests_enrichment {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use std::sync::Mutex;
+
+    fn build_chain(symbol: &str, price: f64) -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            symbol.to_string(),
+            spos!(1000.0),
+            5,
+            spos!(5.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(pos_or_panic!(price))),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some(symbol.to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    #[test]
+    fn test_cancellation_token() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_enrich_single_chain() {
+        let pipeline = ChainEnrichmentPipeline::default();
+        let enriched = pipeline.enrich(build_chain("AAA", 100.0)).unwrap();
+        let strikes = enriched.chain.options.len();
+        assert!(strikes > 0);
+        assert_eq!(enriched.liquidity.len(), strikes);
+        assert!(enriched.iv_failures < strikes);
+        for option in enriched.chain.options.iter() {
+            assert!(option.delta_call.is_some());
+            assert!(option.implied_volatility > Positive::ZERO);
+        }
+        for score in enriched.liquidity.iter() {
+            if let Some(call) = score.call_score {
+                assert!(call >= Decimal::ZERO && call <= Decimal::ONE);
+            }
+        }
+    }
+
+    #[test]
+    fn test_enrich_without_liquidity() {
+        let pipeline = ChainEnrichmentPipeline::new(EnrichmentConfig {
+            solve_iv: false,
+            compute_greeks: false,
+            compute_liquidity: false,
+        });
+        let enriched = pipeline.enrich(build_chain("AAA", 100.0)).unwrap();
+        assert!(enriched.liquidity.is_empty());
+        assert_eq!(enriched.iv_failures, 0);
+    }
+
+    #[test]
+    fn test_run_reports_progress_in_input_order() {
+        let pipeline = ChainEnrichmentPipeline::default();
+        let chains = vec![
+            build_chain("AAA", 100.0),
+            build_chain("BBB", 50.0),
+            build_chain("CCC", 200.0),
+        ];
+        let seen = Mutex::new(Vec::new());
+        let reports = pipeline.run(chains, &CancellationToken::new(), |progress| {
+            assert_eq!(progress.total, 3);
+            seen.lock().unwrap().push(progress.completed);
+        });
+        let symbols: Vec<&str> = reports.iter().map(|r| r.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["AAA", "BBB", "CCC"]);
+        assert!(
+            reports
+                .iter()
+                .all(|r| r.status == EnrichmentStatus::Completed && r.result.is_some())
+        );
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(seen, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_run_cancelled() {
+        let pipeline = ChainEnrichmentPipeline::default();
+        let token = CancellationToken::new();
+        token.cancel();
+        let reports = pipeline.run(vec![build_chain("AAA", 100.0)], &token, |progress| {
+            assert_eq!(progress.status, EnrichmentStatus::Cancelled);
+        });
+        assert_eq!(reports[0].status, EnrichmentStatus::Cancelled);
+        assert!(reports[0].result.is_none());
+    }
+
+    #[test]
+    fn test_side_score() {
+        assert_eq!(side_score(None, spos!(10.0), Some(10)), None);
+        assert_eq!(
+            side_score(Some(Positive::ZERO), None, None),
+            Some(Decimal::ONE)
+        );
+        let score = side_score(Some(Positive::ONE), spos!(100.0), Some(1000)).unwrap();
+        assert_eq!(score, Decimal::ONE / dec!(3));
+    }
+}
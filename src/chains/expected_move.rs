@@ -0,0 +1,298 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Expected Move
+//!
+//! The move of the underlying the options market prices in by expiration,
+//! computed for each expiration in one of two ways:
+//!
+//! * **Straddle**: the mid price of the at-the-money straddle. It is what a
+//!   buyer of the straddle needs the underlying to move to break even, and
+//!   under a lognormal distribution it is close to `0.8·σ·√T·S`, the mean
+//!   absolute move.
+//! * **Implied volatility**: one standard deviation of the underlying at
+//!   expiration, `σ·√T·S`, from the at-the-money implied volatility, with
+//!   `T` annualized by the day-count convention of the options.
+//!
+//! The range around the underlying price is used to place the short strikes
+//! of range strategies, such as iron condors, outside the expected move.
+
+use crate::chains::chain::OptionChain;
+use crate::error::ChainError;
+use crate::model::types::DayCount;
+use positive::Positive;
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Method used to compute the expected move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ExpectedMoveMethod {
+    /// Mid price of the at-the-money straddle.
+    Straddle,
+    /// One standard deviation from the at-the-money implied volatility.
+    ImpliedVolatility,
+}
+
+/// Expected move of the underlying by one expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExpectedMove {
+    /// Expiration of the chain.
+    pub expiration: String,
+    /// Method of the estimate.
+    pub method: ExpectedMoveMethod,
+    /// Price of the underlying.
+    pub underlying_price: Positive,
+    /// Listed strike closest to the underlying price.
+    pub atm_strike: Positive,
+    /// Expected move, in price units.
+    pub expected_move: Positive,
+}
+
+impl ExpectedMove {
+    /// Expected move as a fraction of the underlying price.
+    pub fn percent(&self) -> Decimal {
+        if self.underlying_price == Positive::ZERO {
+            return Decimal::ZERO;
+        }
+        self.expected_move.to_dec() / self.underlying_price.to_dec()
+    }
+
+    /// Lower bound of the expected range.
+    pub fn lower(&self) -> Positive {
+        self.underlying_price.saturating_sub(&self.expected_move)
+    }
+
+    /// Upper bound of the expected range.
+    pub fn upper(&self) -> Positive {
+        self.underlying_price + self.expected_move
+    }
+
+    /// Range of `multiple` expected moves around the underlying price.
+    pub fn range(&self, multiple: Positive) -> (Positive, Positive) {
+        let distance = self.expected_move * multiple;
+        (
+            self.underlying_price.saturating_sub(&distance),
+            self.underlying_price + distance,
+        )
+    }
+
+    /// Listed put and call strikes just outside `multiple` expected moves:
+    /// the highest strike at or below the lower bound and the lowest strike
+    /// at or above the upper bound. These are the short strikes of an iron
+    /// condor or strangle sized to the expected move.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the chain lists no strike beyond one of the
+    /// bounds.
+    pub fn short_strikes(
+        &self,
+        chain: &OptionChain,
+        multiple: Positive,
+    ) -> Result<(Positive, Positive), ChainError> {
+        let (lower, upper) = self.range(multiple);
+        let strikes = chain.get_strikes()?;
+        let put = strikes
+            .iter()
+            .filter(|strike| **strike <= lower)
+            .max()
+            .ok_or_else(|| format!("No strike at or below {lower} in chain {}", chain.symbol))?;
+        let call = strikes
+            .iter()
+            .filter(|strike| **strike >= upper)
+            .min()
+            .ok_or_else(|| format!("No strike at or above {upper} in chain {}", chain.symbol))?;
+        Ok((*put, *call))
+    }
+}
+
+impl OptionChain {
+    /// Expected move of the underlying by the expiration of this chain.
+    ///
+    /// The implied volatility method annualizes the time to expiration with
+    /// `day_count`, the convention the options of the chain are priced with.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the chain is empty, the at-the-money strike
+    /// has no call or put mid price for the straddle method, or the
+    /// expiration of the chain cannot be parsed for the implied volatility
+    /// method.
+    pub fn expected_move(
+        &self,
+        method: ExpectedMoveMethod,
+        day_count: &DayCount,
+    ) -> Result<ExpectedMove, ChainError> {
+        let atm = self.atm_option_data()?;
+        let expected_move = match method {
+            ExpectedMoveMethod::Straddle => match (atm.call_middle, atm.put_middle) {
+                (Some(call), Some(put)) => call + put,
+                _ => {
+                    return Err(format!(
+                        "No straddle mid price at strike {} in chain {}",
+                        atm.strike_price, self.symbol
+                    )
+                    .into());
+                }
+            },
+            ExpectedMoveMethod::ImpliedVolatility => {
+                let expiration = self.get_expiration().ok_or_else(|| {
+                    format!(
+                        "Invalid expiration {} in chain {}",
+                        self.get_expiration_date(),
+                        self.symbol
+                    )
+                })?;
+                let years = day_count.year_fraction(&expiration)?;
+                let deviation = years.to_dec().sqrt().unwrap_or(Decimal::ZERO);
+                self.underlying_price * atm.implied_volatility * deviation
+            }
+        };
+        Ok(ExpectedMove {
+            expiration: self.get_expiration_date(),
+            method,
+            underlying_price: self.underlying_price,
+            atm_strike: atm.strike_price,
+            expected_move,
+        })
+    }
+}
+
+/// Expected move of the underlying by every expiration.
+///
+/// Chains are returned in the order given.
+///
+/// # Errors
+///
+/// Returns the first `ChainError` raised by [`OptionChain::expected_move`].
+pub fn expected_moves_by_expiry(
+    chains: &[OptionChain],
+    method: ExpectedMoveMethod,
+    day_count: &DayCount,
+) -> Result<Vec<ExpectedMove>, ChainError> {
+    chains
+        .iter()
+        .map(|chain| chain.expected_move(method, day_count))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_expected_move {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn build_chain(days: f64) -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(1000.0),
+            20,
+            spos!(1.0),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::HUNDRED)),
+                Some(ExpirationDate::Days(pos_or_panic!(days))),
+                Some(Decimal::ZERO),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    #[test]
+    fn test_straddle_close_to_mean_absolute_move() {
+        let chain = build_chain(365.0);
+        let straddle = chain
+            .expected_move(ExpectedMoveMethod::Straddle, &DayCount::Act365Fixed)
+            .unwrap();
+        let implied = chain
+            .expected_move(
+                ExpectedMoveMethod::ImpliedVolatility,
+                &DayCount::Act365Fixed,
+            )
+            .unwrap();
+
+        assert_eq!(straddle.atm_strike, Positive::HUNDRED);
+        // One standard deviation over a year at 20% volatility.
+        assert!((implied.expected_move.to_dec() - dec!(20)).abs() < dec!(0.1));
+        assert!((implied.percent() - dec!(0.2)).abs() < dec!(0.001));
+        // The straddle prices in about 0.8 standard deviations.
+        let ratio = straddle.expected_move.to_dec() / implied.expected_move.to_dec();
+        assert!((ratio - dec!(0.8)).abs() < dec!(0.03));
+
+        // 365 days are more than a year under ACT/360.
+        let act360 = chain
+            .expected_move(ExpectedMoveMethod::ImpliedVolatility, &DayCount::Act360)
+            .unwrap();
+        let scale = (dec!(365) / dec!(360)).sqrt().unwrap();
+        assert!(
+            (act360.expected_move.to_dec() - implied.expected_move.to_dec() * scale).abs()
+                < dec!(0.000001)
+        );
+    }
+
+    #[test]
+    fn test_range_and_short_strikes() {
+        let chain = build_chain(30.0);
+        let expected = chain
+            .expected_move(
+                ExpectedMoveMethod::ImpliedVolatility,
+                &DayCount::Act365Fixed,
+            )
+            .unwrap();
+        assert_eq!(expected.lower() + expected.expected_move, Positive::HUNDRED);
+        assert_eq!(
+            expected.range(Positive::ONE),
+            (expected.lower(), expected.upper())
+        );
+
+        let (put, call) = expected.short_strikes(&chain, Positive::ONE).unwrap();
+        assert!(put <= expected.lower() && expected.lower() - put < Positive::ONE);
+        assert!(call >= expected.upper() && call - expected.upper() < Positive::ONE);
+        assert!(
+            expected
+                .short_strikes(&chain, pos_or_panic!(100.0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_by_expiry_and_missing_quotes() {
+        let chains = vec![build_chain(10.0), build_chain(60.0)];
+        let moves = expected_moves_by_expiry(
+            &chains,
+            ExpectedMoveMethod::Straddle,
+            &DayCount::Act365Fixed,
+        )
+        .unwrap();
+        assert_eq!(moves.len(), 2);
+        assert!(moves[1].expected_move > moves[0].expected_move);
+
+        let mut chain = build_chain(30.0);
+        chain.mutate_single_options(|od| od.put_middle = None);
+        assert!(
+            chain
+                .expected_move(ExpectedMoveMethod::Straddle, &DayCount::Act365Fixed)
+                .is_err()
+        );
+        assert!(
+            chain
+                .expected_move(
+                    ExpectedMoveMethod::ImpliedVolatility,
+                    &DayCount::Act365Fixed
+                )
+                .is_ok()
+        );
+    }
+}
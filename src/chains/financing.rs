@@ -0,0 +1,420 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Box Spread and Conversion Screener
+//!
+//! Boxes, conversions and reversals pay a fixed amount at expiration whatever
+//! the price of the underlying, so their quoted prices imply an interest
+//! rate. Comparing it with the rate at which the trader can finance
+//! positions shows when the options market lends or borrows more cheaply:
+//!
+//! * **Long box**: buy the call and sell the put at the lower strike, sell
+//!   the call and buy the put at the upper strike. Pays the strike width, so
+//!   buying it lends money at the implied rate.
+//! * **Short box**: the opposite trade, which borrows the price of the box.
+//! * **Conversion**: buy the underlying, sell the call and buy the put at the
+//!   same strike. Pays the strike, so it lends the cost of the position.
+//! * **Reversal**: sell the underlying, buy the call and sell the put, which
+//!   borrows the proceeds against repaying the strike.
+//!
+//! Trades are priced at the bid or ask of every leg, with mid prices
+//! standing in for missing sides, and rates are continuously compounded.
+//! Conversions and reversals discount the underlying by its dividend yield.
+
+use crate::chains::chain::OptionChain;
+use crate::chains::sanity::quote;
+use crate::error::ChainError;
+use positive::Positive;
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use utoipa::ToSchema;
+
+/// Trade that locks in a fixed payoff at expiration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum FinancingTrade {
+    /// Buy a box, lending the price of the box.
+    LongBox,
+    /// Sell a box, borrowing the price of the box.
+    ShortBox,
+    /// Buy the underlying against a short synthetic, lending its cost.
+    Conversion,
+    /// Sell the underlying against a long synthetic, borrowing the proceeds.
+    Reversal,
+}
+
+impl FinancingTrade {
+    /// Whether the trade lends money, as opposed to borrowing it.
+    pub fn is_lending(&self) -> bool {
+        matches!(self, FinancingTrade::LongBox | FinancingTrade::Conversion)
+    }
+}
+
+/// Rate implied by one trade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FinancingQuote {
+    /// Kind of trade.
+    pub trade: FinancingTrade,
+    /// Strike of a conversion or reversal, lower strike of a box.
+    pub lower_strike: Positive,
+    /// Upper strike of a box, `None` for conversions and reversals.
+    pub upper_strike: Option<Positive>,
+    /// Amount lent or borrowed today.
+    pub present_value: Positive,
+    /// Amount repaid at expiration.
+    pub payoff: Positive,
+    /// Annualized continuously compounded rate implied by the trade.
+    pub implied_rate: Decimal,
+    /// Rate advantage over the financing rate: how much more the trade earns
+    /// when lending, or how much less it costs when borrowing.
+    pub edge: Decimal,
+}
+
+/// Implied financing rates of one expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ImpliedFinancingRates {
+    /// Expiration of the chain.
+    pub expiration: String,
+    /// Time to expiration in years.
+    pub years: Positive,
+    /// Rate the quotes are compared with.
+    pub financing_rate: Decimal,
+    /// Highest rate earned by lending through a long box or a conversion.
+    pub lending_rate: Option<Decimal>,
+    /// Lowest rate paid by borrowing through a short box or a reversal.
+    pub borrowing_rate: Option<Decimal>,
+    /// Trades whose edge exceeds the minimum of the screener, best first.
+    pub opportunities: Vec<FinancingQuote>,
+}
+
+/// Screens chains for boxes, conversions and reversals priced away from a
+/// financing rate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FinancingScreener {
+    /// Annualized continuously compounded rate at which the trader lends and borrows.
+    pub financing_rate: Decimal,
+    /// Minimum annualized edge over the financing rate to report a trade.
+    pub min_edge: Decimal,
+    /// Widest box, in strike units, to consider.
+    pub max_box_width: Option<Positive>,
+}
+
+impl FinancingScreener {
+    /// Creates a screener against `financing_rate` reporting any positive edge.
+    pub fn new(financing_rate: Decimal) -> Self {
+        FinancingScreener {
+            financing_rate,
+            min_edge: Decimal::ZERO,
+            max_box_width: None,
+        }
+    }
+
+    /// Sets the minimum annualized edge of a reported trade.
+    pub fn with_min_edge(mut self, min_edge: Decimal) -> Self {
+        self.min_edge = min_edge;
+        self
+    }
+
+    /// Limits boxes to strikes at most `width` apart.
+    pub fn with_max_box_width(mut self, width: Positive) -> Self {
+        self.max_box_width = Some(width);
+        self
+    }
+
+    /// Quotes the implied rate of every box, conversion and reversal of the chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the expiration of the chain cannot be parsed
+    /// or has passed.
+    pub fn quotes(&self, chain: &OptionChain) -> Result<Vec<FinancingQuote>, ChainError> {
+        let years = years_of(chain)?;
+        let t = years.to_dec();
+        let dividend = chain.dividend_yield.unwrap_or(Positive::ZERO).to_dec();
+        let spot = chain.underlying_price.to_dec() * (-dividend * t).exp();
+
+        let legs: Vec<_> = chain
+            .options
+            .iter()
+            .map(|od| {
+                (
+                    od.strike_price,
+                    quote(od.call_bid, od.call_ask, od.call_middle),
+                    quote(od.put_bid, od.put_ask, od.put_middle),
+                )
+            })
+            .collect();
+
+        let mut quotes = Vec::new();
+        let mut push = |trade: FinancingTrade,
+                        lower_strike: Positive,
+                        upper_strike: Option<Positive>,
+                        present_value: Decimal,
+                        payoff: Positive| {
+            if present_value <= Decimal::ZERO {
+                return;
+            }
+            let Ok(present_value) = Positive::new_decimal(present_value) else {
+                return;
+            };
+            let implied_rate = (payoff / present_value).to_dec().ln() / t;
+            let edge = if trade.is_lending() {
+                implied_rate - self.financing_rate
+            } else {
+                self.financing_rate - implied_rate
+            };
+            quotes.push(FinancingQuote {
+                trade,
+                lower_strike,
+                upper_strike,
+                present_value,
+                payoff,
+                implied_rate,
+                edge,
+            });
+        };
+
+        for (strike, call, put) in &legs {
+            let (Some((call_bid, call_ask)), Some((put_bid, put_ask))) = (call, put) else {
+                continue;
+            };
+            push(
+                FinancingTrade::Conversion,
+                *strike,
+                None,
+                spot - call_bid + put_ask,
+                *strike,
+            );
+            push(
+                FinancingTrade::Reversal,
+                *strike,
+                None,
+                spot - call_ask + put_bid,
+                *strike,
+            );
+        }
+
+        for (i, (lower, lower_call, lower_put)) in legs.iter().enumerate() {
+            let (Some((lc_bid, lc_ask)), Some((lp_bid, lp_ask))) = (lower_call, lower_put) else {
+                continue;
+            };
+            for (upper, upper_call, upper_put) in &legs[i + 1..] {
+                let width = *upper - *lower;
+                if self.max_box_width.is_some_and(|max| width > max) {
+                    break;
+                }
+                let (Some((uc_bid, uc_ask)), Some((up_bid, up_ask))) = (upper_call, upper_put)
+                else {
+                    continue;
+                };
+                push(
+                    FinancingTrade::LongBox,
+                    *lower,
+                    Some(*upper),
+                    lc_ask - uc_bid + up_ask - lp_bid,
+                    width,
+                );
+                push(
+                    FinancingTrade::ShortBox,
+                    *lower,
+                    Some(*upper),
+                    lc_bid - uc_ask + up_bid - lp_ask,
+                    width,
+                );
+            }
+        }
+        Ok(quotes)
+    }
+
+    /// Implied lending and borrowing rates of the chain and the trades that
+    /// beat the financing rate by the minimum edge.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the expiration of the chain cannot be parsed
+    /// or has passed.
+    pub fn screen(&self, chain: &OptionChain) -> Result<ImpliedFinancingRates, ChainError> {
+        let quotes = self.quotes(chain)?;
+        let lending_rate = quotes
+            .iter()
+            .filter(|quote| quote.trade.is_lending())
+            .map(|quote| quote.implied_rate)
+            .max();
+        let borrowing_rate = quotes
+            .iter()
+            .filter(|quote| !quote.trade.is_lending())
+            .map(|quote| quote.implied_rate)
+            .min();
+        let mut opportunities: Vec<FinancingQuote> = quotes
+            .into_iter()
+            .filter(|quote| quote.edge > self.min_edge)
+            .collect();
+        opportunities.sort_by_key(|quote| Reverse(quote.edge));
+
+        Ok(ImpliedFinancingRates {
+            expiration: chain.get_expiration_date(),
+            years: years_of(chain)?,
+            financing_rate: self.financing_rate,
+            lending_rate,
+            borrowing_rate,
+            opportunities,
+        })
+    }
+
+    /// Screens every expiration, in the order given.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `ChainError` raised by [`FinancingScreener::screen`].
+    pub fn screen_by_expiry(
+        &self,
+        chains: &[OptionChain],
+    ) -> Result<Vec<ImpliedFinancingRates>, ChainError> {
+        chains.iter().map(|chain| self.screen(chain)).collect()
+    }
+}
+
+/// Time to expiration of a chain, which must be positive.
+fn years_of(chain: &OptionChain) -> Result<Positive, ChainError> {
+    let years = chain
+        .get_expiration()
+        .ok_or_else(|| {
+            format!(
+                "Invalid expiration {} in chain {}",
+                chain.get_expiration_date(),
+                chain.symbol
+            )
+        })?
+        .get_years()?;
+    if years == Positive::ZERO {
+        return Err(format!("Chain {} has expired", chain.symbol).into());
+    }
+    Ok(years)
+}
+
+#[cfg(test)]
+mod tests_financing {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn build_chain(days: f64) -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(1000.0),
+            5,
+            spos!(5.0),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            pos_or_panic!(0.01),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::HUNDRED)),
+                Some(ExpirationDate::Days(pos_or_panic!(days))),
+                Some(dec!(0.04)),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    #[test]
+    fn test_rates_bracket_pricing_rate() {
+        let chain = build_chain(180.0);
+        let rates = FinancingScreener::new(dec!(0.04))
+            .with_min_edge(dec!(0.001))
+            .screen(&chain)
+            .unwrap();
+        let lending = rates.lending_rate.unwrap();
+        let borrowing = rates.borrowing_rate.unwrap();
+        // Bid-ask spreads make lending earn less and borrowing cost more.
+        assert!(lending < borrowing);
+        assert!((lending - dec!(0.04)).abs() < dec!(0.01));
+        assert!((borrowing - dec!(0.04)).abs() < dec!(0.01));
+        assert!(rates.opportunities.is_empty());
+
+        let cheap_money = FinancingScreener::new(Decimal::ZERO)
+            .with_min_edge(dec!(0.01))
+            .screen(&chain)
+            .unwrap();
+        assert!(!cheap_money.opportunities.is_empty());
+        assert!(
+            cheap_money
+                .opportunities
+                .iter()
+                .all(|quote| quote.trade.is_lending() && quote.edge > dec!(0.01))
+        );
+        assert!(
+            cheap_money
+                .opportunities
+                .windows(2)
+                .all(|pair| pair[0].edge >= pair[1].edge)
+        );
+    }
+
+    #[test]
+    fn test_mispriced_put_and_box_width() {
+        let mut chain = build_chain(90.0);
+        let mut option = chain
+            .options
+            .iter()
+            .find(|od| od.strike_price == Positive::HUNDRED)
+            .cloned()
+            .unwrap();
+        chain.options.remove(&option);
+        option.put_bid = option.put_bid.map(|bid| bid + Positive::TWO);
+        option.put_ask = option.put_ask.map(|ask| ask + Positive::TWO);
+        chain.options.insert(option);
+
+        let rates = FinancingScreener::new(dec!(0.04))
+            .with_min_edge(dec!(0.02))
+            .screen(&chain)
+            .unwrap();
+        // Selling the rich put lets the reversal borrow below zero.
+        let reversal = rates
+            .opportunities
+            .iter()
+            .find(|quote| quote.trade == FinancingTrade::Reversal)
+            .unwrap();
+        assert_eq!(reversal.lower_strike, Positive::HUNDRED);
+        assert!(reversal.implied_rate < Decimal::ZERO);
+        assert!(rates.borrowing_rate.unwrap() <= reversal.implied_rate);
+        assert!(
+            rates
+                .opportunities
+                .windows(2)
+                .all(|pair| pair[0].edge >= pair[1].edge)
+        );
+
+        let narrow = FinancingScreener::new(dec!(0.04))
+            .with_max_box_width(pos_or_panic!(5.0))
+            .quotes(&chain)
+            .unwrap();
+        assert!(narrow.iter().all(|quote| {
+            quote
+                .upper_strike
+                .is_none_or(|upper| upper - quote.lower_strike <= pos_or_panic!(5.0))
+        }));
+    }
+
+    #[test]
+    fn test_by_expiry() {
+        let chains = vec![build_chain(30.0), build_chain(365.0)];
+        let rates = FinancingScreener::new(dec!(0.04))
+            .screen_by_expiry(&chains)
+            .unwrap();
+        assert_eq!(rates.len(), 2);
+        assert!(rates[1].years > rates[0].years);
+        // The same spreads cost less per year on longer expirations.
+        let spread =
+            |r: &ImpliedFinancingRates| r.borrowing_rate.unwrap() - r.lending_rate.unwrap();
+        assert!(spread(&rates[1]) < spread(&rates[0]));
+    }
+}
@@ -225,10 +225,64 @@ mod optiondata;
 
 mod generators;
 
+/// * `enrichment` - Parallel enrichment of many chains (IV, Greeks, liquidity) with progress reporting
+mod enrichment;
+
+/// * `expected_move` - Straddle and implied volatility expected move per expiry
+mod expected_move;
+
+/// * `adapters` - Pluggable deserialization of vendor specific JSON chain payloads
+mod adapters;
+
+/// * `query` - Fluent filtering of chain contracts for strategy leg selection
+mod query;
+
+/// * `strike_selection` - Selection of strikes per expiry by target delta, with interpolation
+mod strike_selection;
+
+/// Incremental merging of streaming quote updates into chains
+mod streaming;
+
+/// * `bulk` - Columnar prices and Greeks for every strike of a chain in one pass
+mod bulk;
+
+/// * `sanity` - Put-call parity, crossed market and time value checks of chain quotes
+mod sanity;
+
+/// * `financing` - Box spread and conversion/reversal screener with implied financing rates
+mod financing;
+
+/// * `provider` - Vendor independent source of spot quotes, chain snapshots and historical bars
+mod provider;
+
+/// * `pipeline` - Consumption of raw streaming quote messages with strategy metric notifications
+#[cfg(feature = "async")]
+mod pipeline;
+
+pub use adapters::{
+    AdaptedQuotes, ChainAdapter, FlatArrayAdapter, NestedExpiryAdapter, StrikeQuotes, VendorQuote,
+    VolatilityUnit,
+};
+pub use bulk::{ChainGreeks, ChainPrices};
 pub use chain::OptionChain;
+pub use enrichment::{
+    CancellationToken, ChainEnrichmentPipeline, EnrichedChain, EnrichmentConfig,
+    EnrichmentProgress, EnrichmentReport, EnrichmentStatus, LiquidityScore,
+};
+pub use expected_move::{ExpectedMove, ExpectedMoveMethod, expected_moves_by_expiry};
+pub use financing::{FinancingQuote, FinancingScreener, FinancingTrade, ImpliedFinancingRates};
 pub use generators::{generator_optionchain, generator_positive};
 pub use legs::StrategyLegs;
 pub use optiondata::OptionData;
 pub use options::{DeltasInStrike, OptionsInStrike};
+#[cfg(feature = "async")]
+pub use pipeline::{PipelineStats, QuotePipeline, StrategyMetrics};
+#[cfg(feature = "async")]
+pub use provider::{AsyncMarketDataProvider, BlockingProvider};
+pub use provider::{InMemoryMarketData, MarketDataProvider, SpotQuote};
+pub use query::{ChainCandidate, ChainQuery};
 pub use rnd::{RNDAnalysis, RNDParameters, RNDResult};
+pub use sanity::{ChainDiagnostics, QuoteIssue, QuoteIssueKind, SanityConfig};
+pub use streaming::{AppliedUpdates, DirtyMetrics, QuoteSink, QuoteUpdate};
+pub use strike_selection::{DeltaStrike, DeltaStrikeSelection, strikes_for_delta_by_expiry};
 pub use utils::OptionChainBuildParams;
@@ -8,7 +8,7 @@ use crate::chains::{DeltasInStrike, OptionsInStrike};
 use crate::error::ChainError;
 use crate::error::chains::OptionDataErrorKind;
 use crate::greeks::{delta, gamma};
-use crate::model::Position;
+use crate::model::{Position, QuotePricing};
 use crate::strategies::{BasicAble, FindOptimalSide};
 use crate::{ExpirationDate, OptionStyle, Options, Side};
 use chrono::{DateTime, Utc};
@@ -690,6 +690,65 @@ impl OptionData {
         ))
     }
 
+    /// Returns the bid and ask of one side of this strike, if both are quoted.
+    pub fn get_quote(&self, option_style: OptionStyle) -> Option<(Positive, Positive)> {
+        match option_style {
+            OptionStyle::Call => Some((self.call_bid?, self.call_ask?)),
+            OptionStyle::Put => Some((self.put_bid?, self.put_ask?)),
+        }
+    }
+
+    /// Returns the price at which a position of `side` would be opened under `pricing`.
+    ///
+    /// Returns `None` if the requested side is not fully quoted.
+    pub fn get_entry_price(
+        &self,
+        side: Side,
+        option_style: OptionStyle,
+        pricing: QuotePricing,
+    ) -> Option<Positive> {
+        let (bid, ask) = self.get_quote(option_style)?;
+        Some(pricing.entry_price(bid, ask, side))
+    }
+
+    /// Returns the price at which a position of `side` would be closed under `pricing`.
+    ///
+    /// Returns `None` if the requested side is not fully quoted.
+    pub fn get_exit_price(
+        &self,
+        side: Side,
+        option_style: OptionStyle,
+        pricing: QuotePricing,
+    ) -> Option<Positive> {
+        let (bid, ask) = self.get_quote(option_style)?;
+        Some(pricing.exit_price(bid, ask, side))
+    }
+
+    /// Builds a `Position` whose premium is the entry price under `pricing`.
+    ///
+    /// Behaves like [`OptionData::get_position`], which always prices at the
+    /// natural side, but lets the caller open at mid or with a slippage model.
+    /// Falls back to the Black-Scholes price when the side is not quoted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the option cannot be built or priced.
+    pub fn get_position_with_pricing(
+        &self,
+        side: Side,
+        option_style: OptionStyle,
+        pricing: QuotePricing,
+        date: Option<DateTime<Utc>>,
+        open_fee: Option<Positive>,
+        close_fee: Option<Positive>,
+    ) -> Result<Position, ChainError> {
+        let mut position = self.get_position(side, option_style, date, open_fee, close_fee)?;
+        if let Some(premium) = self.get_entry_price(side, option_style, pricing) {
+            position.premium = premium;
+        }
+        Ok(position)
+    }
+
     pub(super) fn get_options_in_strike(&self) -> Result<OptionsInStrike, ChainError> {
         let mut option: Options = self.get_option(Side::Long, OptionStyle::Call)?;
         option.option_style = OptionStyle::Call;
@@ -1767,6 +1826,41 @@ mod tests_get_position {
 
         assert_pos_relative_eq!(position.premium, bs_price_positive, pos_or_panic!(0.00001));
     }
+
+    #[test]
+    fn test_get_position_with_pricing() {
+        let option_data = create_test_option_data();
+        let natural = option_data
+            .get_position_with_pricing(
+                Side::Long,
+                OptionStyle::Call,
+                QuotePricing::Natural,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(natural.premium, pos_or_panic!(10.0));
+        let mid = option_data
+            .get_position_with_pricing(
+                Side::Short,
+                OptionStyle::Put,
+                QuotePricing::Mid,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(mid.premium, pos_or_panic!(8.75));
+        assert_eq!(
+            option_data.get_exit_price(Side::Long, OptionStyle::Call, QuotePricing::Natural),
+            spos!(9.5)
+        );
+        assert_eq!(
+            option_data.get_quote(OptionStyle::Put),
+            Some((pos_or_panic!(8.5), pos_or_panic!(9.0)))
+        );
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,405 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Streaming Quote Pipeline
+//!
+//! [`QuotePipeline`] consumes the raw messages of a live feed, typically the
+//! text frames of a vendor WebSocket forwarded into a `tokio` channel by the
+//! application, and keeps an [`OptionChain`] up to date with them:
+//!
+//! 1. every message is read with a [`ChainAdapter`]; its quotes for the
+//!    expiration of the pipeline are turned into [`QuoteUpdate`]s for the
+//!    underlying and for every listed strike they cover, keeping the implied
+//!    volatility of the chain where the message has none;
+//! 2. the updates are merged into the chain with
+//!    [`OptionChain::apply_update`], and [`DirtyMetrics`] works out which of
+//!    the watched strategies they touch;
+//! 3. the net Greeks and the P&L at mid of each touched strategy are
+//!    recomputed and broadcast as a [`StrategyMetrics`] to every subscriber.
+//!
+//! Messages that cannot be normalized are counted and skipped, so a bad
+//! frame does not stop the stream; strikes not listed in the chain are
+//! ignored. The pipeline is available with the `async` feature.
+
+use crate::chains::chain::OptionChain;
+use crate::chains::{ChainAdapter, DirtyMetrics, QuoteUpdate};
+use crate::error::ChainError;
+use crate::greeks::{Greeks, NetGreeks};
+use crate::model::position::Position;
+use crate::model::types::OptionStyle;
+use crate::strategies::base::Positionable;
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tokio::sync::{broadcast, mpsc};
+use tracing::warn;
+use utoipa::ToSchema;
+
+/// Metrics broadcast to subscribers that can fall behind before missing some.
+const SUBSCRIBER_CAPACITY: usize = 1024;
+
+/// Metrics of a watched strategy after a quote update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StrategyMetrics {
+    /// Name the strategy is watched under.
+    pub name: String,
+    /// Time the metrics were computed.
+    pub timestamp: DateTime<Utc>,
+    /// Underlying price of the chain.
+    pub underlying_price: Positive,
+    /// Net Greeks at the quoted implied volatilities, `None` if they cannot
+    /// be computed.
+    pub net_greeks: Option<NetGreeks>,
+    /// Unrealized P&L at mid prices, `None` if a leg has no quote.
+    pub profit_loss: Option<Decimal>,
+}
+
+/// Counters of the messages handled by a [`QuotePipeline`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PipelineStats {
+    /// Messages received.
+    pub messages: usize,
+    /// Messages that could not be normalized or applied.
+    pub rejected: usize,
+    /// Quote updates that changed the chain.
+    pub updates: usize,
+    /// Strategy metrics published.
+    pub published: usize,
+}
+
+/// Keeps an option chain and the metrics of strategies on it up to date from
+/// a stream of raw vendor messages.
+#[derive(Debug)]
+pub struct QuotePipeline<A: ChainAdapter> {
+    adapter: A,
+    chain: OptionChain,
+    strategies: BTreeMap<String, Vec<Position>>,
+    dirty: DirtyMetrics,
+    sender: broadcast::Sender<StrategyMetrics>,
+    stats: PipelineStats,
+}
+
+impl<A: ChainAdapter> QuotePipeline<A> {
+    /// Creates a pipeline normalizing messages with `adapter` into `chain`.
+    pub fn new(adapter: A, chain: OptionChain) -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIBER_CAPACITY);
+        QuotePipeline {
+            adapter,
+            chain,
+            strategies: BTreeMap::new(),
+            dirty: DirtyMetrics::new(),
+            sender,
+            stats: PipelineStats::default(),
+        }
+    }
+
+    /// Watches the legs of `positions` under `name`.
+    pub fn watch(&mut self, name: &str, positions: Vec<Position>) {
+        self.dirty.watch(
+            name,
+            positions
+                .iter()
+                .map(|position| position.option.strike_price),
+        );
+        self.strategies.insert(name.to_string(), positions);
+    }
+
+    /// Watches the legs of `strategy` under `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the strategy positions cannot be read.
+    pub fn watch_strategy<S: Positionable>(
+        &mut self,
+        name: &str,
+        strategy: &S,
+    ) -> Result<(), ChainError> {
+        let positions = strategy
+            .get_positions()
+            .map_err(|e| ChainError::invalid_parameters("strategy", &e.to_string()))?
+            .into_iter()
+            .cloned()
+            .collect();
+        self.watch(name, positions);
+        Ok(())
+    }
+
+    /// Stops watching `name`.
+    pub fn unwatch(&mut self, name: &str) {
+        self.strategies.remove(name);
+        self.dirty.unwatch(name);
+    }
+
+    /// Receives the metrics of every touched strategy from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<StrategyMetrics> {
+        self.sender.subscribe()
+    }
+
+    /// Chain as of the last message.
+    pub fn chain(&self) -> &OptionChain {
+        &self.chain
+    }
+
+    /// Counters of the messages handled so far.
+    pub fn stats(&self) -> PipelineStats {
+        self.stats
+    }
+
+    /// Normalizes and applies one raw message, then publishes and returns the
+    /// metrics of the strategies it touched.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the message cannot be normalized or an
+    /// update cannot be applied; updates before it remain applied.
+    pub fn process(&mut self, message: &str) -> Result<Vec<StrategyMetrics>, ChainError> {
+        self.stats.messages += 1;
+        let result = self.apply(message);
+        if result.is_err() {
+            self.stats.rejected += 1;
+        }
+        result?;
+
+        let metrics: Vec<StrategyMetrics> = self
+            .dirty
+            .take_dirty()
+            .into_iter()
+            .filter_map(|name| {
+                let positions = self.strategies.get(&name)?;
+                Some(self.metrics(&name, positions))
+            })
+            .collect();
+        for update in &metrics {
+            // Nobody may be subscribed yet.
+            let _ = self.sender.send(update.clone());
+        }
+        self.stats.published += metrics.len();
+        Ok(metrics)
+    }
+
+    /// Processes every message of `messages` until the channel is closed,
+    /// logging and skipping the ones that fail, and returns the pipeline.
+    pub async fn run(mut self, mut messages: mpsc::Receiver<String>) -> Self {
+        while let Some(message) = messages.recv().await {
+            if let Err(e) = self.process(&message) {
+                warn!("Skipping quote message: {e}");
+            }
+        }
+        self
+    }
+
+    fn apply(&mut self, message: &str) -> Result<(), ChainError> {
+        let expiration = self.chain.get_expiration_date();
+        let quotes = self.adapter.quotes_str(message)?;
+        let Some(strikes) = quotes.rows.get(&expiration) else {
+            return Ok(());
+        };
+
+        let mut updates: Vec<QuoteUpdate> = quotes
+            .underlying_price
+            .map(|price| QuoteUpdate::Underlying { price })
+            .into_iter()
+            .collect();
+        for (strike, (call, put)) in strikes {
+            if !self
+                .chain
+                .options
+                .iter()
+                .any(|listed| listed.strike_price == *strike)
+            {
+                continue;
+            }
+            // A strike has a single implied volatility, the one of its call
+            // or else of its put, as when chains are built.
+            let mut implied_volatility = None;
+            for quote in [call, put].into_iter().flatten() {
+                implied_volatility = implied_volatility.or(quote.volatility()?);
+            }
+            for (option_style, quote) in [(OptionStyle::Call, call), (OptionStyle::Put, put)] {
+                let Some(quote) = quote else {
+                    continue;
+                };
+                updates.push(QuoteUpdate::Option {
+                    strike: *strike,
+                    option_style,
+                    bid: quote.bid_price()?,
+                    ask: quote.ask_price()?,
+                    implied_volatility: implied_volatility.take(),
+                });
+            }
+        }
+        for update in &updates {
+            let applied = self.chain.apply_update(update)?;
+            if !applied.is_empty() {
+                self.stats.updates += 1;
+                self.dirty.mark(&applied);
+            }
+        }
+        Ok(())
+    }
+
+    fn metrics(&self, name: &str, positions: &[Position]) -> StrategyMetrics {
+        let underlying_price = self.chain.underlying_price;
+        let mut profit_loss = Some(Decimal::ZERO);
+        let mut net_greeks = Some(NetGreeks {
+            delta: Decimal::ZERO,
+            gamma: Decimal::ZERO,
+            theta: Decimal::ZERO,
+            vega: Decimal::ZERO,
+            rho: Decimal::ZERO,
+        });
+        for position in positions {
+            let quote = self
+                .chain
+                .options
+                .iter()
+                .find(|option| option.strike_price == position.option.strike_price);
+            let mid = quote.and_then(|option| match position.option.option_style {
+                OptionStyle::Call => option.call_middle,
+                OptionStyle::Put => option.put_middle,
+            });
+            profit_loss = match (profit_loss, mid) {
+                (Some(total), Some(mid)) => {
+                    position.unrealized_pnl(mid).ok().map(|pnl| total + pnl)
+                }
+                _ => None,
+            };
+
+            let mut marked = position.clone();
+            marked.option.underlying_price = underlying_price;
+            if let Some(option) = quote {
+                marked.option.implied_volatility = option.implied_volatility;
+            }
+            net_greeks = match (net_greeks, marked.net_greeks()) {
+                (Some(net), Ok(leg)) => Some(NetGreeks {
+                    delta: net.delta + leg.delta,
+                    gamma: net.gamma + leg.gamma,
+                    theta: net.theta + leg.theta,
+                    vega: net.vega + leg.vega,
+                    rho: net.rho + leg.rho,
+                }),
+                _ => None,
+            };
+        }
+        StrategyMetrics {
+            name: name.to_string(),
+            timestamp: Utc::now(),
+            underlying_price,
+            net_greeks,
+            profit_loss,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_pipeline {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::FlatArrayAdapter;
+    use crate::model::option::Options;
+    use crate::model::types::{OptionType, Side};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn chain() -> OptionChain {
+        FlatArrayAdapter::default()
+            .adapt_str(&message(100.0, 5.0))
+            .unwrap()
+            .remove(0)
+    }
+
+    fn message(price: f64, call_bid: f64) -> String {
+        format!(
+            r#"{{"symbol": "XYZ", "underlyingPrice": {price}, "options": [
+                {{"expiration": "2030-01-18", "strike": 100, "type": "call", "bid": {call_bid}, "ask": {}, "iv": 0.2}},
+                {{"expiration": "2030-01-18", "strike": 100, "type": "put", "bid": 4.0, "ask": 4.2}},
+                {{"expiration": "2030-01-18", "strike": 105, "type": "call", "bid": 2.0, "ask": 2.2, "iv": 0.2}}
+            ]}}"#,
+            call_bid + 0.2
+        )
+    }
+
+    fn short_call() -> Position {
+        let option = Options::new(
+            OptionType::European,
+            Side::Short,
+            "XYZ".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            pos_or_panic!(5.1),
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_process_publishes_touched_strategies() {
+        let mut pipeline = QuotePipeline::new(FlatArrayAdapter::default(), chain());
+        pipeline.watch("short call", vec![short_call()]);
+        let mut receiver = pipeline.subscribe();
+
+        // A repeated message changes nothing and publishes nothing.
+        assert!(pipeline.process(&message(100.0, 5.0)).unwrap().is_empty());
+
+        let metrics = pipeline.process(&message(102.0, 6.0)).unwrap();
+        assert_eq!(metrics.len(), 1);
+        let update = &metrics[0];
+        assert_eq!(update.name, "short call");
+        assert_eq!(update.underlying_price, pos_or_panic!(102.0));
+        // Sold at 5.1, now quoted at 6.1.
+        assert_eq!(update.profit_loss, Some(dec!(-1.0)));
+        assert!(update.net_greeks.unwrap().delta < Decimal::ZERO);
+        assert_eq!(receiver.try_recv().unwrap(), *update);
+
+        assert!(pipeline.process("not json").is_err());
+        assert_eq!(
+            pipeline.stats(),
+            PipelineStats {
+                messages: 3,
+                rejected: 1,
+                updates: 2,
+                published: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_consumes_channel_until_closed() {
+        let mut pipeline = QuotePipeline::new(FlatArrayAdapter::default(), chain());
+        pipeline.watch("short call", vec![short_call()]);
+        let mut receiver = pipeline.subscribe();
+        let (sender, messages) = mpsc::channel(8);
+        let task = tokio::spawn(pipeline.run(messages));
+
+        sender.send(message(101.0, 5.5)).await.unwrap();
+        sender.send("{}".to_string()).await.unwrap();
+        sender.send(message(103.0, 6.5)).await.unwrap();
+        drop(sender);
+        let pipeline = task.await.unwrap();
+
+        assert_eq!(pipeline.chain().underlying_price, pos_or_panic!(103.0));
+        assert_eq!(pipeline.stats().rejected, 1);
+        let first = receiver.recv().await.unwrap();
+        let second = receiver.recv().await.unwrap();
+        assert_eq!(first.underlying_price, pos_or_panic!(101.0));
+        assert_eq!(second.profit_loss, Some(dec!(-1.5)));
+    }
+}
@@ -0,0 +1,410 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Market Data Providers
+//!
+//! [`MarketDataProvider`] is the single trait an application implements to
+//! wire the crate to a market data vendor. It serves the three kinds of data
+//! the rest of the crate consumes:
+//!
+//! * spot quotes of the underlying, as a [`SpotQuote`];
+//! * snapshots of the listed option chains, one [`OptionChain`] per
+//!   expiration, as the [`ChainSnapshot`] replayed by the backtester;
+//! * historical bars of the underlying, as [`OhlcvCandle`]s, for volatility
+//!   estimators and backtests.
+//!
+//! Vendor payloads are usually turned into chains with a
+//! [`ChainAdapter`](crate::chains::ChainAdapter). [`InMemoryMarketData`]
+//! serves fixed data and is handy in tests and examples.
+//!
+//! With the `async` feature, [`AsyncMarketDataProvider`] is the same trait
+//! for vendors reached over the network; any synchronous provider can be
+//! used where an asynchronous one is expected by wrapping it in a
+//! [`BlockingProvider`], which runs its calls on the blocking thread pool of
+//! `tokio`.
+
+use crate::backtesting::ChainSnapshot;
+use crate::chains::chain::OptionChain;
+use crate::error::ChainError;
+use crate::utils::{OhlcvCandle, TimeFrame};
+use chrono::{DateTime, NaiveDate, Utc};
+use positive::Positive;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+#[cfg(feature = "async")]
+use std::sync::Arc;
+
+/// Latest quote of an underlying.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SpotQuote {
+    /// Underlying symbol.
+    pub symbol: String,
+    /// Time of the quote.
+    pub timestamp: DateTime<Utc>,
+    /// Last traded price.
+    pub price: Positive,
+    /// Best bid, if quoted.
+    pub bid: Option<Positive>,
+    /// Best ask, if quoted.
+    pub ask: Option<Positive>,
+}
+
+impl SpotQuote {
+    /// Creates a quote with only a last price.
+    pub fn new(symbol: &str, timestamp: DateTime<Utc>, price: Positive) -> Self {
+        SpotQuote {
+            symbol: symbol.to_string(),
+            timestamp,
+            price,
+            bid: None,
+            ask: None,
+        }
+    }
+
+    /// Sets the best bid and ask.
+    pub fn with_bid_ask(mut self, bid: Positive, ask: Positive) -> Self {
+        self.bid = Some(bid);
+        self.ask = Some(ask);
+        self
+    }
+
+    /// Mid of the bid and ask, or the last price when either is missing.
+    pub fn mid(&self) -> Positive {
+        match (self.bid, self.ask) {
+            (Some(bid), Some(ask)) => (bid + ask) / Positive::TWO,
+            _ => self.price,
+        }
+    }
+}
+
+/// Source of spot quotes, option chains and historical bars.
+pub trait MarketDataProvider {
+    /// Latest quote of `symbol`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the symbol is unknown or the vendor fails.
+    fn spot(&self, symbol: &str) -> Result<SpotQuote, ChainError>;
+
+    /// Current option chains of `symbol`, one per expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the symbol is unknown or the vendor fails.
+    fn chain_snapshot(&self, symbol: &str) -> Result<ChainSnapshot, ChainError>;
+
+    /// Bars of `symbol` at `timeframe` from `from` to `to`, both inclusive,
+    /// oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the symbol is unknown or the vendor fails.
+    fn historical_bars(
+        &self,
+        symbol: &str,
+        timeframe: TimeFrame,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<OhlcvCandle>, ChainError>;
+
+    /// Current chain of `symbol` with the given expiration, as formatted by
+    /// [`OptionChain::get_expiration_date`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the snapshot cannot be loaded or has no
+    /// chain for the expiration.
+    fn chain(&self, symbol: &str, expiration: &str) -> Result<OptionChain, ChainError> {
+        let snapshot = self.chain_snapshot(symbol)?;
+        snapshot
+            .chain(expiration)
+            .cloned()
+            .ok_or_else(|| format!("No chain of {symbol} expiring {expiration}").into())
+    }
+}
+
+/// Market data held in memory.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryMarketData {
+    spots: BTreeMap<String, SpotQuote>,
+    snapshots: BTreeMap<String, ChainSnapshot>,
+    bars: BTreeMap<String, Vec<(TimeFrame, OhlcvCandle)>>,
+}
+
+impl InMemoryMarketData {
+    /// Creates an empty provider.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the quote of its symbol.
+    pub fn with_spot(mut self, quote: SpotQuote) -> Self {
+        self.spots.insert(quote.symbol.clone(), quote);
+        self
+    }
+
+    /// Sets the chains of the symbol of `snapshot`.
+    pub fn with_snapshot(mut self, snapshot: ChainSnapshot) -> Self {
+        self.snapshots.insert(snapshot.symbol.clone(), snapshot);
+        self
+    }
+
+    /// Adds bars of `symbol` at `timeframe`.
+    pub fn with_bars(mut self, symbol: &str, timeframe: TimeFrame, bars: Vec<OhlcvCandle>) -> Self {
+        let stored = self.bars.entry(symbol.to_string()).or_default();
+        stored.extend(bars.into_iter().map(|bar| (timeframe, bar)));
+        stored.sort_by(|(_, a), (_, b)| (a.date, &a.time).cmp(&(b.date, &b.time)));
+        self
+    }
+}
+
+impl MarketDataProvider for InMemoryMarketData {
+    fn spot(&self, symbol: &str) -> Result<SpotQuote, ChainError> {
+        self.spots
+            .get(symbol)
+            .cloned()
+            .ok_or_else(|| format!("No quote of {symbol}").into())
+    }
+
+    fn chain_snapshot(&self, symbol: &str) -> Result<ChainSnapshot, ChainError> {
+        self.snapshots
+            .get(symbol)
+            .cloned()
+            .ok_or_else(|| format!("No chains of {symbol}").into())
+    }
+
+    fn historical_bars(
+        &self,
+        symbol: &str,
+        timeframe: TimeFrame,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<OhlcvCandle>, ChainError> {
+        let bars = self
+            .bars
+            .get(symbol)
+            .ok_or_else(|| format!("No bars of {symbol}"))?;
+        Ok(bars
+            .iter()
+            .filter(|(frame, bar)| *frame == timeframe && bar.date >= from && bar.date <= to)
+            .map(|(_, bar)| bar.clone())
+            .collect())
+    }
+}
+
+/// Asynchronous source of spot quotes, option chains and historical bars.
+///
+/// See [`MarketDataProvider`].
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncMarketDataProvider: Send + Sync {
+    /// Latest quote of `symbol`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the symbol is unknown or the vendor fails.
+    async fn spot(&self, symbol: &str) -> Result<SpotQuote, ChainError>;
+
+    /// Current option chains of `symbol`, one per expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the symbol is unknown or the vendor fails.
+    async fn chain_snapshot(&self, symbol: &str) -> Result<ChainSnapshot, ChainError>;
+
+    /// Bars of `symbol` at `timeframe` from `from` to `to`, both inclusive,
+    /// oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the symbol is unknown or the vendor fails.
+    async fn historical_bars(
+        &self,
+        symbol: &str,
+        timeframe: TimeFrame,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<OhlcvCandle>, ChainError>;
+}
+
+/// Runs a synchronous [`MarketDataProvider`] on the blocking thread pool, so
+/// it can be used as an [`AsyncMarketDataProvider`].
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub struct BlockingProvider<P> {
+    inner: Arc<P>,
+}
+
+#[cfg(feature = "async")]
+impl<P> BlockingProvider<P> {
+    /// Wraps `provider`.
+    pub fn new(provider: P) -> Self {
+        BlockingProvider {
+            inner: Arc::new(provider),
+        }
+    }
+
+    /// The wrapped provider.
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "async")]
+impl<P> Clone for BlockingProvider<P> {
+    fn clone(&self) -> Self {
+        BlockingProvider {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<P: MarketDataProvider + Send + Sync + 'static> BlockingProvider<P> {
+    async fn run<T, F>(&self, call: F) -> Result<T, ChainError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&P) -> Result<T, ChainError> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        tokio::task::spawn_blocking(move || call(&inner))
+            .await
+            .map_err(|e| ChainError::invalid_parameters("async_task", &e.to_string()))?
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<P: MarketDataProvider + Send + Sync + 'static> AsyncMarketDataProvider
+    for BlockingProvider<P>
+{
+    async fn spot(&self, symbol: &str) -> Result<SpotQuote, ChainError> {
+        let symbol = symbol.to_string();
+        self.run(move |provider| provider.spot(&symbol)).await
+    }
+
+    async fn chain_snapshot(&self, symbol: &str) -> Result<ChainSnapshot, ChainError> {
+        let symbol = symbol.to_string();
+        self.run(move |provider| provider.chain_snapshot(&symbol))
+            .await
+    }
+
+    async fn historical_bars(
+        &self,
+        symbol: &str,
+        timeframe: TimeFrame,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<OhlcvCandle>, ChainError> {
+        let symbol = symbol.to_string();
+        self.run(move |provider| provider.historical_bars(&symbol, timeframe, from, to))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests_provider {
+    use super::*;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn bar(day: u32, close: rust_decimal::Decimal) -> OhlcvCandle {
+        OhlcvCandle {
+            date: NaiveDate::from_ymd_opt(2030, 1, day).unwrap(),
+            time: "00:00:00".to_string(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1_000,
+        }
+    }
+
+    fn provider() -> InMemoryMarketData {
+        let chain = OptionChain::new(
+            "XYZ",
+            Positive::HUNDRED,
+            "2030-01-18".to_string(),
+            None,
+            None,
+        );
+        InMemoryMarketData::new()
+            .with_spot(
+                SpotQuote::new("XYZ", Utc::now(), Positive::HUNDRED)
+                    .with_bid_ask(pos_or_panic!(99.9), pos_or_panic!(100.3)),
+            )
+            .with_snapshot(ChainSnapshot {
+                symbol: "XYZ".to_string(),
+                timestamp: Utc::now(),
+                chains: vec![chain],
+            })
+            .with_bars(
+                "XYZ",
+                TimeFrame::Day,
+                vec![bar(3, dec!(101)), bar(1, dec!(99)), bar(2, dec!(100))],
+            )
+    }
+
+    #[test]
+    fn test_in_memory_provider() {
+        let provider = provider();
+
+        let quote = provider.spot("XYZ").unwrap();
+        assert_eq!(quote.mid(), pos_or_panic!(100.1));
+        assert!(provider.spot("ABC").is_err());
+
+        let expiration = provider.chain_snapshot("XYZ").unwrap().chains[0].get_expiration_date();
+        assert_eq!(provider.chain("XYZ", &expiration).unwrap().symbol, "XYZ");
+        assert!(provider.chain("XYZ", "2031-01-17").is_err());
+
+        let from = NaiveDate::from_ymd_opt(2030, 1, 2).unwrap();
+        let to = NaiveDate::from_ymd_opt(2030, 1, 3).unwrap();
+        let closes: Vec<_> = provider
+            .historical_bars("XYZ", TimeFrame::Day, from, to)
+            .unwrap()
+            .into_iter()
+            .map(|bar| bar.close)
+            .collect();
+        assert_eq!(closes, vec![dec!(100), dec!(101)]);
+        assert!(
+            provider
+                .historical_bars("XYZ", TimeFrame::Hour, from, to)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_blocking_provider() {
+        let provider = BlockingProvider::new(provider());
+        let from = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2030, 1, 31).unwrap();
+
+        assert_eq!(
+            AsyncMarketDataProvider::spot(&provider, "XYZ")
+                .await
+                .unwrap(),
+            provider.inner().spot("XYZ").unwrap()
+        );
+        let snapshot = AsyncMarketDataProvider::chain_snapshot(&provider, "XYZ")
+            .await
+            .unwrap();
+        assert_eq!(snapshot.chains.len(), 1);
+        let bars =
+            AsyncMarketDataProvider::historical_bars(&provider, "XYZ", TimeFrame::Day, from, to)
+                .await
+                .unwrap();
+        assert_eq!(bars.len(), 3);
+        assert!(
+            AsyncMarketDataProvider::spot(&provider, "ABC")
+                .await
+                .is_err()
+        );
+    }
+}
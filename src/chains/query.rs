@@ -0,0 +1,488 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Chain Query
+//!
+//! A fluent selection API over one or several [`OptionChain`]s, used to narrow
+//! down candidate contracts for strategy leg selection.
+//!
+//! Every criterion is optional; contracts missing the data required by an
+//! active criterion (for example a delta filter on a strike without delta) are
+//! excluded.
+//!
+//! ```rust
+//! use optionstratlib::chains::OptionChain;
+//! use optionstratlib::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+//! use optionstratlib::ExpirationDate;
+//! use positive::{pos_or_panic, spos};
+//! use rust_decimal_macros::dec;
+//!
+//! let params = OptionChainBuildParams::new(
+//!     "XYZ".to_string(),
+//!     None,
+//!     10,
+//!     spos!(5.0),
+//!     dec!(-0.2),
+//!     dec!(0.1),
+//!     pos_or_panic!(0.02),
+//!     2,
+//!     OptionDataPriceParams::new(
+//!         Some(Box::new(pos_or_panic!(100.0))),
+//!         Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+//!         Some(dec!(0.05)),
+//!         spos!(0.0),
+//!         Some("XYZ".to_string()),
+//!     ),
+//!     pos_or_panic!(0.2),
+//! );
+//! let chain = OptionChain::build_chain(&params).unwrap();
+//! let candidates = chain
+//!     .query()
+//!     .puts()
+//!     .dte_between(pos_or_panic!(20.0), pos_or_panic!(45.0))
+//!     .delta_between(dec!(0.15), dec!(0.35))
+//!     .max_spread(pos_or_panic!(0.5))
+//!     .execute();
+//! assert!(candidates.iter().all(|c| c.delta.unwrap().abs() <= dec!(0.35)));
+//! ```
+
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::model::types::OptionStyle;
+use positive::Positive;
+use rust_decimal::Decimal;
+
+/// A contract selected by a [`ChainQuery`].
+#[derive(Debug, Clone)]
+pub struct ChainCandidate<'a> {
+    /// Chain the contract belongs to.
+    pub chain: &'a OptionChain,
+    /// Strike row holding the contract.
+    pub option_data: &'a OptionData,
+    /// Call or put.
+    pub option_style: OptionStyle,
+    /// Strike price of the contract.
+    pub strike_price: Positive,
+    /// Best bid, if quoted.
+    pub bid: Option<Positive>,
+    /// Best ask, if quoted.
+    pub ask: Option<Positive>,
+    /// Delta of the contract, if known.
+    pub delta: Option<Decimal>,
+    /// Days to expiration, if the expiration can be resolved.
+    pub days_to_expiration: Option<Positive>,
+    /// Strike divided by the underlying price.
+    pub moneyness: Decimal,
+}
+
+impl ChainCandidate<'_> {
+    /// Returns the bid/ask spread, if both sides are quoted.
+    pub fn spread(&self) -> Option<Decimal> {
+        Some(self.ask?.to_dec() - self.bid?.to_dec())
+    }
+
+    /// Returns the mid price, if both sides are quoted.
+    pub fn mid(&self) -> Option<Decimal> {
+        Some((self.ask?.to_dec() + self.bid?.to_dec()) / Decimal::TWO)
+    }
+}
+
+/// Fluent filter over the contracts of one or several option chains.
+///
+/// Built with [`OptionChain::query`] or [`ChainQuery::over`], refined with the
+/// chained filter methods and evaluated with [`ChainQuery::execute`].
+#[derive(Debug, Clone)]
+pub struct ChainQuery<'a> {
+    chains: Vec<&'a OptionChain>,
+    option_style: Option<OptionStyle>,
+    dte: Option<(Positive, Positive)>,
+    delta: Option<(Decimal, Decimal)>,
+    moneyness: Option<(Decimal, Decimal)>,
+    strikes: Option<(Positive, Positive)>,
+    min_open_interest: Option<u64>,
+    min_volume: Option<Positive>,
+    max_spread: Option<Positive>,
+    max_spread_percentage: Option<Positive>,
+}
+
+impl<'a> ChainQuery<'a> {
+    /// Creates a query over every contract of the given chains.
+    pub fn over<I>(chains: I) -> Self
+    where
+        I: IntoIterator<Item = &'a OptionChain>,
+    {
+        ChainQuery {
+            chains: chains.into_iter().collect(),
+            option_style: None,
+            dte: None,
+            delta: None,
+            moneyness: None,
+            strikes: None,
+            min_open_interest: None,
+            min_volume: None,
+            max_spread: None,
+            max_spread_percentage: None,
+        }
+    }
+
+    /// Keeps only calls.
+    pub fn calls(self) -> Self {
+        self.style(OptionStyle::Call)
+    }
+
+    /// Keeps only puts.
+    pub fn puts(self) -> Self {
+        self.style(OptionStyle::Put)
+    }
+
+    /// Keeps only contracts of the given style.
+    pub fn style(mut self, option_style: OptionStyle) -> Self {
+        self.option_style = Some(option_style);
+        self
+    }
+
+    /// Keeps contracts whose days to expiration lie in `[min, max]`.
+    pub fn dte_between(mut self, min: Positive, max: Positive) -> Self {
+        self.dte = Some((min, max));
+        self
+    }
+
+    /// Keeps contracts whose absolute delta lies in `[min, max]`.
+    ///
+    /// Bounds are compared against `|delta|`, so the same range selects calls
+    /// and puts of similar moneyness.
+    pub fn delta_between(mut self, min: Decimal, max: Decimal) -> Self {
+        self.delta = Some((min.abs(), max.abs()));
+        self
+    }
+
+    /// Keeps contracts whose moneyness (strike / underlying price) lies in `[min, max]`.
+    pub fn moneyness_between(mut self, min: Decimal, max: Decimal) -> Self {
+        self.moneyness = Some((min, max));
+        self
+    }
+
+    /// Keeps contracts whose strike lies in `[min, max]`.
+    pub fn strike_between(mut self, min: Positive, max: Positive) -> Self {
+        self.strikes = Some((min, max));
+        self
+    }
+
+    /// Keeps strikes with at least `open_interest` open contracts.
+    pub fn min_open_interest(mut self, open_interest: u64) -> Self {
+        self.min_open_interest = Some(open_interest);
+        self
+    }
+
+    /// Keeps strikes with at least `volume` traded contracts.
+    pub fn min_volume(mut self, volume: Positive) -> Self {
+        self.min_volume = Some(volume);
+        self
+    }
+
+    /// Keeps contracts whose absolute bid/ask spread is at most `spread`.
+    pub fn max_spread(mut self, spread: Positive) -> Self {
+        self.max_spread = Some(spread);
+        self
+    }
+
+    /// Keeps contracts whose bid/ask spread, as a percentage of the mid price, is at most `percentage`.
+    pub fn max_spread_percentage(mut self, percentage: Positive) -> Self {
+        self.max_spread_percentage = Some(percentage);
+        self
+    }
+
+    /// Evaluates the query, returning matching contracts ordered by chain, strike and style.
+    pub fn execute(&self) -> Vec<ChainCandidate<'a>> {
+        let styles: &[OptionStyle] = match self.option_style {
+            Some(OptionStyle::Call) => &[OptionStyle::Call],
+            Some(OptionStyle::Put) => &[OptionStyle::Put],
+            None => &[OptionStyle::Call, OptionStyle::Put],
+        };
+        let mut candidates = Vec::new();
+        for &chain in &self.chains {
+            let chain_dte = chain.get_expiration().and_then(|e| e.get_days().ok());
+            for option_data in chain.get_single_iter() {
+                if !self.matches_strike(chain, option_data) {
+                    continue;
+                }
+                for &option_style in styles {
+                    let candidate = candidate(chain, option_data, option_style, chain_dte);
+                    if self.matches_contract(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+        }
+        candidates
+    }
+
+    /// Returns the number of matching contracts.
+    pub fn count(&self) -> usize {
+        self.execute().len()
+    }
+
+    /// Returns the matching contract whose absolute delta is closest to `target`.
+    pub fn closest_delta(&self, target: Decimal) -> Option<ChainCandidate<'a>> {
+        let target = target.abs();
+        self.execute()
+            .into_iter()
+            .filter(|c| c.delta.is_some())
+            .min_by_key(|c| (c.delta.unwrap_or_default().abs() - target).abs())
+    }
+
+    fn matches_strike(&self, chain: &OptionChain, option_data: &OptionData) -> bool {
+        let strike = option_data.strike_price;
+        if let Some((min, max)) = self.strikes
+            && (strike < min || strike > max)
+        {
+            return false;
+        }
+        if let Some(min) = self.min_open_interest
+            && option_data.open_interest.is_none_or(|oi| oi < min)
+        {
+            return false;
+        }
+        if let Some(min) = self.min_volume
+            && option_data.volume.is_none_or(|v| v < min)
+        {
+            return false;
+        }
+        if let Some((min, max)) = self.moneyness {
+            if chain.underlying_price == Positive::ZERO {
+                return false;
+            }
+            let moneyness = strike.to_dec() / chain.underlying_price.to_dec();
+            if moneyness < min || moneyness > max {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn matches_contract(&self, candidate: &ChainCandidate<'_>) -> bool {
+        if let Some((min, max)) = self.dte {
+            match candidate.days_to_expiration {
+                Some(dte) if dte >= min && dte <= max => {}
+                _ => return false,
+            }
+        }
+        if let Some((min, max)) = self.delta {
+            match candidate.delta {
+                Some(delta) if delta.abs() >= min && delta.abs() <= max => {}
+                _ => return false,
+            }
+        }
+        if let Some(max) = self.max_spread {
+            match candidate.spread() {
+                Some(spread) if spread <= max.to_dec() => {}
+                _ => return false,
+            }
+        }
+        if let Some(max) = self.max_spread_percentage {
+            match (candidate.spread(), candidate.mid()) {
+                (Some(spread), Some(mid)) if !mid.is_zero() => {
+                    if spread / mid * Decimal::ONE_HUNDRED > max.to_dec() {
+                        return false;
+                    }
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+fn candidate<'a>(
+    chain: &'a OptionChain,
+    option_data: &'a OptionData,
+    option_style: OptionStyle,
+    chain_dte: Option<Positive>,
+) -> ChainCandidate<'a> {
+    let (bid, ask, delta) = match option_style {
+        OptionStyle::Call => (
+            option_data.call_bid,
+            option_data.call_ask,
+            option_data.delta_call,
+        ),
+        OptionStyle::Put => (
+            option_data.put_bid,
+            option_data.put_ask,
+            option_data.delta_put,
+        ),
+    };
+    let days_to_expiration = option_data
+        .expiration_date
+        .as_ref()
+        .and_then(|e| e.get_days().ok())
+        .or(chain_dte);
+    let moneyness = if chain.underlying_price == Positive::ZERO {
+        Decimal::ZERO
+    } else {
+        option_data.strike_price.to_dec() / chain.underlying_price.to_dec()
+    };
+    ChainCandidate {
+        chain,
+        option_data,
+        option_style,
+        strike_price: option_data.strike_price,
+        bid,
+        ask,
+        delta,
+        days_to_expiration,
+        moneyness,
+    }
+}
+
+impl OptionChain {
+    /// Starts a fluent [`ChainQuery`] over the contracts of this chain.
+    pub fn query(&self) -> ChainQuery<'_> {
+        ChainQuery::over(std::iter::once(self))
+    }
+}
+
+#[cfg(test)]
+mod tests_query {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn build_chain(days: f64) -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(1000.0),
+            10,
+            spos!(5.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::HUNDRED)),
+                Some(ExpirationDate::Days(pos_or_panic!(days))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        let mut chain = OptionChain::build_chain(&params).unwrap();
+        chain.update_greeks();
+        chain
+    }
+
+    #[test]
+    fn test_query_without_filters_returns_both_sides() {
+        let chain = build_chain(30.0);
+        let strikes = chain.get_single_iter().count();
+        assert_eq!(chain.query().count(), strikes * 2);
+        assert_eq!(chain.query().calls().count(), strikes);
+        assert!(
+            chain
+                .query()
+                .puts()
+                .execute()
+                .iter()
+                .all(|c| c.option_style == OptionStyle::Put)
+        );
+    }
+
+    #[test]
+    fn test_query_delta_range() {
+        let chain = build_chain(30.0);
+        let candidates = chain
+            .query()
+            .puts()
+            .delta_between(dec!(-0.1), dec!(-0.5))
+            .execute();
+        assert!(!candidates.is_empty());
+        for candidate in candidates {
+            let delta = candidate.delta.unwrap().abs();
+            assert!(delta >= dec!(0.1) && delta <= dec!(0.5));
+        }
+    }
+
+    #[test]
+    fn test_query_dte_across_chains() {
+        let near = build_chain(10.0);
+        let far = build_chain(60.0);
+        let chains = [near, far];
+        let candidates = ChainQuery::over(chains.iter())
+            .calls()
+            .dte_between(pos_or_panic!(30.0), pos_or_panic!(90.0))
+            .execute();
+        assert_eq!(candidates.len(), chains[1].get_single_iter().count());
+    }
+
+    #[test]
+    fn test_query_moneyness_and_strikes() {
+        let chain = build_chain(30.0);
+        let candidates = chain
+            .query()
+            .calls()
+            .moneyness_between(dec!(0.95), dec!(1.05))
+            .execute();
+        assert!(candidates.iter().all(
+            |c| c.strike_price >= pos_or_panic!(95.0) && c.strike_price <= pos_or_panic!(105.0)
+        ));
+        let strikes = chain
+            .query()
+            .calls()
+            .strike_between(pos_or_panic!(95.0), pos_or_panic!(105.0))
+            .count();
+        assert_eq!(strikes, candidates.len());
+    }
+
+    #[test]
+    fn test_query_liquidity_filters() {
+        let mut chain = build_chain(30.0);
+        chain.mutate_single_options(|od| {
+            od.open_interest = Some(if od.strike_price > Positive::HUNDRED {
+                500
+            } else {
+                10
+            });
+        });
+        let liquid = chain.query().calls().min_open_interest(100).execute();
+        assert!(liquid.iter().all(|c| c.strike_price > Positive::HUNDRED));
+        assert_eq!(chain.query().max_spread(Positive::ZERO).count(), 0);
+        assert_eq!(
+            chain
+                .query()
+                .max_spread_percentage(pos_or_panic!(1000.0))
+                .count(),
+            chain
+                .query()
+                .execute()
+                .iter()
+                .filter(|c| c.mid().is_some_and(|m| !m.is_zero()))
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_query_missing_data_excluded() {
+        let mut chain = build_chain(30.0);
+        chain.mutate_single_options(|od| od.delta_call = None);
+        assert_eq!(
+            chain
+                .query()
+                .calls()
+                .delta_between(Decimal::ZERO, Decimal::ONE)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_closest_delta() {
+        let chain = build_chain(30.0);
+        let candidate = chain.query().calls().closest_delta(dec!(0.5)).unwrap();
+        assert!((candidate.delta.unwrap() - dec!(0.5)).abs() < dec!(0.1));
+    }
+}
@@ -0,0 +1,385 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Chain Sanity Checks
+//!
+//! Static no-arbitrage checks on the quotes of a chain, run before strategies
+//! are built on it. Each strike is checked for:
+//!
+//! * **Crossed markets**: a bid above the ask of the same contract.
+//! * **Negative time value**: an option offered below its lower bound, so it
+//!   could be bought and exercised (or held to expiration) at a profit. The
+//!   bound is the intrinsic value for American options and the discounted
+//!   intrinsic value of the forward for European options.
+//! * **Put-call parity**: for European options the call minus the put must
+//!   trade at `S·e^{-qT} - K·e^{-rT}`; for American options it must lie
+//!   between `S·e^{-qT} - K` and `S - K·e^{-rT}`. A violation means the
+//!   conversion (sell the call, buy the put and the underlying) or the
+//!   reversal locks in a profit at the quoted bids and asks.
+//!
+//! Mid prices stand in for missing bids and asks. Violations smaller than
+//! the tolerance of the [`SanityConfig`] are ignored, so rounding of quotes
+//! does not flag every strike.
+
+use crate::chains::chain::OptionChain;
+use crate::error::ChainError;
+use crate::model::types::OptionStyle;
+use positive::Positive;
+use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use utoipa::ToSchema;
+
+/// Settings of the chain sanity checks.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SanityConfig {
+    /// Violations up to this amount, in price units, are ignored.
+    pub tolerance: Positive,
+    /// Whether the options can be exercised early, which widens the parity
+    /// bounds and uses the undiscounted intrinsic value.
+    pub american: bool,
+}
+
+impl Default for SanityConfig {
+    fn default() -> Self {
+        SanityConfig {
+            tolerance: Positive::new_decimal(dec!(0.01)).unwrap_or(Positive::ZERO),
+            american: false,
+        }
+    }
+}
+
+impl SanityConfig {
+    /// Sets the tolerance in price units.
+    pub fn with_tolerance(mut self, tolerance: Positive) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Checks the quotes against the bounds of American options.
+    pub fn american(mut self) -> Self {
+        self.american = true;
+        self
+    }
+}
+
+/// Kind of quote violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum QuoteIssueKind {
+    /// The bid is above the ask.
+    CrossedMarket,
+    /// The option is offered below its lower bound.
+    NegativeTimeValue,
+    /// The call and put at the strike violate put-call parity.
+    ParityViolation,
+}
+
+/// A violation found at one strike.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct QuoteIssue {
+    /// Strike of the quotes.
+    pub strike: Positive,
+    /// Contract of the violation, `None` when both the call and the put are involved.
+    pub option_style: Option<OptionStyle>,
+    /// Kind of violation.
+    pub kind: QuoteIssueKind,
+    /// Size of the violation in price units.
+    pub amount: Decimal,
+    /// Human readable description.
+    pub message: String,
+}
+
+/// Result of the sanity checks of one chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChainDiagnostics {
+    /// Symbol of the chain.
+    pub symbol: String,
+    /// Expiration of the chain.
+    pub expiration: String,
+    /// Number of strikes checked.
+    pub strikes_checked: usize,
+    /// Violations found, ordered by strike.
+    pub issues: Vec<QuoteIssue>,
+}
+
+impl ChainDiagnostics {
+    /// Whether no violation was found.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Number of violations of `kind`.
+    pub fn count(&self, kind: QuoteIssueKind) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.kind == kind)
+            .count()
+    }
+
+    /// Strikes with at least one violation.
+    pub fn flagged_strikes(&self) -> BTreeSet<Positive> {
+        self.issues.iter().map(|issue| issue.strike).collect()
+    }
+}
+
+/// Bid and ask of one contract, with the mid price standing in for missing sides.
+pub(crate) fn quote(
+    bid: Option<Positive>,
+    ask: Option<Positive>,
+    mid: Option<Positive>,
+) -> Option<(Decimal, Decimal)> {
+    let bid = bid.or(mid)?.to_dec();
+    let ask = ask.or(mid)?.to_dec();
+    Some((bid, ask))
+}
+
+impl OptionChain {
+    /// Checks the quotes of the chain for crossed markets, negative time
+    /// value and put-call parity violations.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the expiration of the chain cannot be parsed.
+    pub fn check_quotes(&self, config: &SanityConfig) -> Result<ChainDiagnostics, ChainError> {
+        let years = self
+            .get_expiration()
+            .ok_or_else(|| {
+                format!(
+                    "Invalid expiration {} in chain {}",
+                    self.get_expiration_date(),
+                    self.symbol
+                )
+            })?
+            .get_years()?
+            .to_dec();
+        let rate = self.risk_free_rate.unwrap_or(Decimal::ZERO);
+        let dividend = self.dividend_yield.unwrap_or(Positive::ZERO).to_dec();
+        let spot = self.underlying_price.to_dec();
+        let discounted_spot = spot * (-dividend * years).exp();
+        let discount = (-rate * years).exp();
+        let tolerance = config.tolerance.to_dec();
+
+        let mut issues = Vec::new();
+        let mut strikes_checked = 0;
+        for option in &self.options {
+            strikes_checked += 1;
+            let strike = option.strike_price;
+            let k = strike.to_dec();
+            let call = quote(option.call_bid, option.call_ask, option.call_middle);
+            let put = quote(option.put_bid, option.put_ask, option.put_middle);
+
+            for (style, bid, ask) in [
+                (OptionStyle::Call, option.call_bid, option.call_ask),
+                (OptionStyle::Put, option.put_bid, option.put_ask),
+            ] {
+                if let (Some(bid), Some(ask)) = (bid, ask)
+                    && bid > ask
+                {
+                    issues.push(QuoteIssue {
+                        strike,
+                        option_style: Some(style),
+                        kind: QuoteIssueKind::CrossedMarket,
+                        amount: (bid - ask).to_dec(),
+                        message: format!("{style} bid {bid} above ask {ask}"),
+                    });
+                }
+            }
+
+            let (call_floor, put_floor) = if config.american {
+                (spot - k, k - spot)
+            } else {
+                (
+                    discounted_spot - k * discount,
+                    k * discount - discounted_spot,
+                )
+            };
+            for (style, quotes, floor) in [
+                (OptionStyle::Call, call, call_floor),
+                (OptionStyle::Put, put, put_floor),
+            ] {
+                if let Some((_, ask)) = quotes
+                    && floor - ask > tolerance
+                {
+                    issues.push(QuoteIssue {
+                        strike,
+                        option_style: Some(style),
+                        kind: QuoteIssueKind::NegativeTimeValue,
+                        amount: floor - ask,
+                        message: format!(
+                            "{style} offered at {ask} below its lower bound {}",
+                            floor.round_dp(4)
+                        ),
+                    });
+                }
+            }
+
+            if let (Some((call_bid, call_ask)), Some((put_bid, put_ask))) = (call, put) {
+                let (lower, upper) = if config.american {
+                    (discounted_spot - k, spot - k * discount)
+                } else {
+                    let forward = discounted_spot - k * discount;
+                    (forward, forward)
+                };
+                // Selling the call and buying the put earns at most `call_bid - put_ask`,
+                // buying the call and selling the put costs at least `call_ask - put_bid`.
+                let conversion = call_bid - put_ask - upper;
+                let reversal = lower - (call_ask - put_bid);
+                let (amount, trade) = if conversion >= reversal {
+                    (conversion, "conversion")
+                } else {
+                    (reversal, "reversal")
+                };
+                if amount > tolerance {
+                    issues.push(QuoteIssue {
+                        strike,
+                        option_style: None,
+                        kind: QuoteIssueKind::ParityViolation,
+                        amount,
+                        message: format!(
+                            "Put-call parity violated, {trade} locks in {}",
+                            amount.round_dp(4)
+                        ),
+                    });
+                }
+            }
+        }
+
+        Ok(ChainDiagnostics {
+            symbol: self.symbol.clone(),
+            expiration: self.get_expiration_date(),
+            strikes_checked,
+            issues,
+        })
+    }
+
+    /// Removes the strikes flagged by `diagnostics` from the chain.
+    pub fn remove_flagged_strikes(&mut self, diagnostics: &ChainDiagnostics) {
+        let flagged = diagnostics.flagged_strikes();
+        self.options
+            .retain(|option| !flagged.contains(&option.strike_price));
+    }
+}
+
+#[cfg(test)]
+mod tests_sanity {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::OptionData;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+
+    fn build_chain() -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(1000.0),
+            10,
+            spos!(5.0),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::HUNDRED)),
+                Some(ExpirationDate::Days(pos_or_panic!(60.0))),
+                Some(dec!(0.03)),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    fn set_strike<F: FnOnce(&mut OptionData)>(chain: &mut OptionChain, strike: Positive, f: F) {
+        let mut option = chain
+            .options
+            .iter()
+            .find(|od| od.strike_price == strike)
+            .cloned()
+            .unwrap();
+        chain.options.remove(&option);
+        f(&mut option);
+        chain.options.insert(option);
+    }
+
+    #[test]
+    fn test_generated_chain_is_clean() {
+        let chain = build_chain();
+        let diagnostics = chain.check_quotes(&SanityConfig::default()).unwrap();
+        assert!(diagnostics.is_clean(), "{:?}", diagnostics.issues);
+        assert_eq!(diagnostics.strikes_checked, chain.options.len());
+
+        // Deep in-the-money European puts trade below intrinsic value, which
+        // only early exercise turns into an arbitrage.
+        let american = chain
+            .check_quotes(&SanityConfig::default().american())
+            .unwrap();
+        assert!(american.count(QuoteIssueKind::NegativeTimeValue) > 0);
+        assert!(american.issues.iter().all(|issue| {
+            issue.kind == QuoteIssueKind::NegativeTimeValue
+                && issue.option_style == Some(OptionStyle::Put)
+                && issue.strike > chain.underlying_price
+        }));
+    }
+
+    #[test]
+    fn test_crossed_market_and_negative_time_value() {
+        let mut chain = build_chain();
+        set_strike(&mut chain, pos_or_panic!(95.0), |od| {
+            od.put_bid = od.put_ask.map(|ask| ask + Positive::ONE);
+        });
+        set_strike(&mut chain, pos_or_panic!(80.0), |od| {
+            od.call_ask = spos!(15.0);
+            od.call_bid = spos!(14.9);
+        });
+        let diagnostics = chain.check_quotes(&SanityConfig::default()).unwrap();
+        assert_eq!(diagnostics.count(QuoteIssueKind::CrossedMarket), 1);
+        let crossed = &diagnostics.issues[diagnostics
+            .issues
+            .iter()
+            .position(|issue| issue.kind == QuoteIssueKind::CrossedMarket)
+            .unwrap()];
+        assert_eq!(crossed.strike, pos_or_panic!(95.0));
+        assert_eq!(crossed.option_style, Some(OptionStyle::Put));
+        assert_eq!(crossed.amount, Decimal::ONE);
+
+        let cheap = diagnostics
+            .issues
+            .iter()
+            .find(|issue| issue.kind == QuoteIssueKind::NegativeTimeValue)
+            .unwrap();
+        assert_eq!(cheap.strike, pos_or_panic!(80.0));
+    }
+
+    #[test]
+    fn test_parity_violation_and_removal() {
+        let mut chain = build_chain();
+        set_strike(&mut chain, Positive::HUNDRED, |od| {
+            od.put_bid = od.put_bid.map(|bid| bid + Positive::TWO);
+            od.put_ask = od.put_ask.map(|ask| ask + Positive::TWO);
+        });
+        let diagnostics = chain.check_quotes(&SanityConfig::default()).unwrap();
+        assert_eq!(diagnostics.issues.len(), 1);
+        let issue = &diagnostics.issues[0];
+        assert_eq!(issue.kind, QuoteIssueKind::ParityViolation);
+        assert!(issue.message.contains("reversal"));
+        assert!(issue.amount > Decimal::ONE && issue.amount < Positive::TWO.to_dec());
+
+        let wide = SanityConfig::default().with_tolerance(Positive::TWO);
+        assert!(chain.check_quotes(&wide).unwrap().is_clean());
+
+        let before = chain.options.len();
+        chain.remove_flagged_strikes(&diagnostics);
+        assert_eq!(chain.options.len(), before - 1);
+        assert!(
+            chain
+                .check_quotes(&SanityConfig::default())
+                .unwrap()
+                .is_clean()
+        );
+    }
+}
@@ -0,0 +1,459 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Streaming Quote Updates
+//!
+//! Live feeds deliver ticks one contract at a time. Instead of rebuilding the
+//! whole [`OptionChain`] on every tick, updates are merged incrementally with
+//! [`OptionChain::apply_update`]. Any consumer of ticks implements
+//! [`QuoteSink`]; the chain itself is one, and so is [`DirtyMetrics`], which
+//! flags the strategies whose legs were touched by an update so that only
+//! their metrics need to be recomputed.
+//!
+//! Updating a quote refreshes the mid prices of the strike but not its
+//! Greeks; call [`OptionChain::update_greeks`] when they are needed.
+
+use crate::chains::chain::OptionChain;
+use crate::error::ChainError;
+use crate::model::types::OptionStyle;
+use crate::strategies::base::Positionable;
+use positive::Positive;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use utoipa::ToSchema;
+
+/// A single tick delivered by a quote feed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum QuoteUpdate {
+    /// New quote for one side of one strike. Fields left as `None` keep their current value.
+    Option {
+        /// Strike of the updated contract.
+        strike: Positive,
+        /// Call or put.
+        option_style: OptionStyle,
+        /// New bid.
+        bid: Option<Positive>,
+        /// New ask.
+        ask: Option<Positive>,
+        /// New implied volatility of the strike.
+        implied_volatility: Option<Positive>,
+    },
+    /// New underlying price. Affects every strike of the chain.
+    Underlying {
+        /// Last underlying price.
+        price: Positive,
+    },
+}
+
+impl QuoteUpdate {
+    /// Creates a bid/ask update for one side of a strike.
+    pub fn quote(
+        strike: Positive,
+        option_style: OptionStyle,
+        bid: Positive,
+        ask: Positive,
+    ) -> Self {
+        QuoteUpdate::Option {
+            strike,
+            option_style,
+            bid: Some(bid),
+            ask: Some(ask),
+            implied_volatility: None,
+        }
+    }
+
+    /// Creates an implied volatility update for a strike.
+    pub fn implied_volatility(strike: Positive, implied_volatility: Positive) -> Self {
+        QuoteUpdate::Option {
+            strike,
+            option_style: OptionStyle::Call,
+            bid: None,
+            ask: None,
+            implied_volatility: Some(implied_volatility),
+        }
+    }
+
+    /// Strike touched by the update, or `None` for underlying updates.
+    pub fn strike(&self) -> Option<Positive> {
+        match self {
+            QuoteUpdate::Option { strike, .. } => Some(*strike),
+            QuoteUpdate::Underlying { .. } => None,
+        }
+    }
+}
+
+/// Strikes affected by one or more applied updates.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AppliedUpdates {
+    /// Strikes whose quotes or implied volatility changed.
+    pub strikes: BTreeSet<Positive>,
+    /// Whether the underlying price changed.
+    pub underlying_changed: bool,
+}
+
+impl AppliedUpdates {
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.strikes.is_empty() && !self.underlying_changed
+    }
+}
+
+/// Consumer of streaming quote updates.
+pub trait QuoteSink {
+    /// Handles one update.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the update cannot be applied.
+    fn on_quote(&mut self, update: &QuoteUpdate) -> Result<(), ChainError>;
+
+    /// Handles a batch of updates in order, stopping at the first error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `ChainError` raised by [`QuoteSink::on_quote`].
+    fn on_quotes(&mut self, updates: &[QuoteUpdate]) -> Result<(), ChainError> {
+        updates.iter().try_for_each(|update| self.on_quote(update))
+    }
+}
+
+impl OptionChain {
+    /// Merges one quote update into the chain.
+    ///
+    /// Returns the strikes actually changed; an update repeating the current
+    /// values changes nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChainError::OptionDataError` if the strike is not listed in the
+    /// chain or the resulting bid is above the ask.
+    pub fn apply_update(&mut self, update: &QuoteUpdate) -> Result<AppliedUpdates, ChainError> {
+        let mut applied = AppliedUpdates::default();
+        match update {
+            QuoteUpdate::Underlying { price } => {
+                if self.underlying_price != *price {
+                    self.underlying_price = *price;
+                    applied.underlying_changed = true;
+                }
+            }
+            QuoteUpdate::Option {
+                strike,
+                option_style,
+                bid,
+                ask,
+                implied_volatility,
+            } => {
+                let current = self
+                    .options
+                    .iter()
+                    .find(|od| od.strike_price == *strike)
+                    .ok_or_else(|| {
+                        ChainError::invalid_strike(strike.to_f64(), "Strike not in chain")
+                    })?;
+                let mut option_data = current.clone();
+                let (bid_field, ask_field) = match option_style {
+                    OptionStyle::Call => (&mut option_data.call_bid, &mut option_data.call_ask),
+                    OptionStyle::Put => (&mut option_data.put_bid, &mut option_data.put_ask),
+                };
+                if bid.is_some() {
+                    *bid_field = *bid;
+                }
+                if ask.is_some() {
+                    *ask_field = *ask;
+                }
+                if let (Some(b), Some(a)) = (*bid_field, *ask_field)
+                    && b > a
+                {
+                    return Err(ChainError::invalid_prices(
+                        Some(b.to_f64()),
+                        Some(a.to_f64()),
+                        "Bid above ask",
+                    ));
+                }
+                if let Some(iv) = implied_volatility {
+                    option_data.implied_volatility = *iv;
+                }
+                option_data.set_mid_prices();
+
+                if option_data != *current {
+                    self.options.replace(option_data);
+                    applied.strikes.insert(*strike);
+                }
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Merges a batch of quote updates, returning every strike changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `ChainError` raised by [`OptionChain::apply_update`];
+    /// updates before it remain applied.
+    pub fn apply_updates(&mut self, updates: &[QuoteUpdate]) -> Result<AppliedUpdates, ChainError> {
+        let mut applied = AppliedUpdates::default();
+        for update in updates {
+            let step = self.apply_update(update)?;
+            applied.strikes.extend(step.strikes);
+            applied.underlying_changed |= step.underlying_changed;
+        }
+        Ok(applied)
+    }
+}
+
+impl QuoteSink for OptionChain {
+    fn on_quote(&mut self, update: &QuoteUpdate) -> Result<(), ChainError> {
+        self.apply_update(update).map(|_| ())
+    }
+}
+
+/// Tracks which strategies have stale metrics after quote updates.
+///
+/// Strategies are registered by name together with the strikes of their legs.
+/// An update touching one of those strikes, or the underlying price, marks the
+/// strategy dirty until its metrics are recomputed and it is cleared.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DirtyMetrics {
+    watched: BTreeMap<String, BTreeSet<Positive>>,
+    dirty: BTreeSet<String>,
+}
+
+impl DirtyMetrics {
+    /// Creates an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Watches `name` for updates on the given strikes.
+    pub fn watch<I: IntoIterator<Item = Positive>>(&mut self, name: &str, strikes: I) {
+        self.watched
+            .insert(name.to_string(), strikes.into_iter().collect());
+    }
+
+    /// Watches `name` for updates on the strikes of the strategy's legs.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the strategy positions cannot be read.
+    pub fn watch_strategy<S: Positionable>(
+        &mut self,
+        name: &str,
+        strategy: &S,
+    ) -> Result<(), ChainError> {
+        let strikes = strategy
+            .get_positions()
+            .map_err(|e| ChainError::invalid_parameters("strategy", &e.to_string()))?
+            .into_iter()
+            .map(|position| position.option.strike_price)
+            .collect::<Vec<_>>();
+        self.watch(name, strikes);
+        Ok(())
+    }
+
+    /// Stops watching `name`.
+    pub fn unwatch(&mut self, name: &str) {
+        self.watched.remove(name);
+        self.dirty.remove(name);
+    }
+
+    /// Marks dirty every watched strategy affected by `applied`.
+    pub fn mark(&mut self, applied: &AppliedUpdates) {
+        for (name, strikes) in &self.watched {
+            if applied.underlying_changed || !strikes.is_disjoint(&applied.strikes) {
+                self.dirty.insert(name.clone());
+            }
+        }
+    }
+
+    /// Returns `true` if the metrics of `name` are stale.
+    pub fn is_dirty(&self, name: &str) -> bool {
+        self.dirty.contains(name)
+    }
+
+    /// Names of the strategies with stale metrics.
+    pub fn dirty(&self) -> impl Iterator<Item = &str> {
+        self.dirty.iter().map(String::as_str)
+    }
+
+    /// Marks the metrics of `name` as fresh.
+    pub fn clear(&mut self, name: &str) {
+        self.dirty.remove(name);
+    }
+
+    /// Returns and clears the names of the strategies with stale metrics.
+    pub fn take_dirty(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.dirty).into_iter().collect()
+    }
+}
+
+impl QuoteSink for DirtyMetrics {
+    fn on_quote(&mut self, update: &QuoteUpdate) -> Result<(), ChainError> {
+        let mut applied = AppliedUpdates::default();
+        match update.strike() {
+            Some(strike) => {
+                applied.strikes.insert(strike);
+            }
+            None => applied.underlying_changed = true,
+        }
+        self.mark(&applied);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_streaming {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn build_chain() -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(1000.0),
+            10,
+            spos!(5.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::HUNDRED)),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    #[test]
+    fn test_apply_quote_update() {
+        let mut chain = build_chain();
+        let strike = Positive::HUNDRED;
+        let update = QuoteUpdate::quote(
+            strike,
+            OptionStyle::Call,
+            pos_or_panic!(3.0),
+            pos_or_panic!(3.2),
+        );
+        let applied = chain.apply_update(&update).unwrap();
+        assert_eq!(applied.strikes.len(), 1);
+        let option_data = chain.get_optiondata_with_strike(&strike).unwrap();
+        assert_eq!(option_data.call_bid, Some(pos_or_panic!(3.0)));
+        assert_eq!(option_data.call_middle, Some(pos_or_panic!(3.1)));
+
+        // Same values again change nothing.
+        assert!(chain.apply_update(&update).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_apply_iv_and_underlying_updates() {
+        let mut chain = build_chain();
+        let applied = chain
+            .apply_updates(&[
+                QuoteUpdate::implied_volatility(pos_or_panic!(105.0), pos_or_panic!(0.35)),
+                QuoteUpdate::Underlying {
+                    price: pos_or_panic!(101.0),
+                },
+            ])
+            .unwrap();
+        assert!(applied.underlying_changed);
+        assert!(applied.strikes.contains(&pos_or_panic!(105.0)));
+        assert_eq!(chain.underlying_price, pos_or_panic!(101.0));
+        let option_data = chain
+            .get_optiondata_with_strike(&pos_or_panic!(105.0))
+            .unwrap();
+        assert_eq!(option_data.implied_volatility, pos_or_panic!(0.35));
+    }
+
+    #[test]
+    fn test_invalid_updates() {
+        let mut chain = build_chain();
+        let unknown = QuoteUpdate::quote(
+            pos_or_panic!(101.5),
+            OptionStyle::Put,
+            Positive::ONE,
+            Positive::TWO,
+        );
+        assert!(chain.apply_update(&unknown).is_err());
+        let crossed = QuoteUpdate::quote(
+            Positive::HUNDRED,
+            OptionStyle::Put,
+            Positive::TWO,
+            Positive::ONE,
+        );
+        assert!(chain.on_quote(&crossed).is_err());
+    }
+
+    #[test]
+    fn test_dirty_metrics() {
+        let mut tracker = DirtyMetrics::new();
+        tracker.watch("condor", [pos_or_panic!(90.0), pos_or_panic!(110.0)]);
+        tracker.watch("straddle", [Positive::HUNDRED]);
+
+        tracker
+            .on_quote(&QuoteUpdate::quote(
+                pos_or_panic!(110.0),
+                OptionStyle::Call,
+                Positive::ONE,
+                Positive::TWO,
+            ))
+            .unwrap();
+        assert!(tracker.is_dirty("condor"));
+        assert!(!tracker.is_dirty("straddle"));
+
+        tracker.clear("condor");
+        tracker
+            .on_quote(&QuoteUpdate::Underlying {
+                price: pos_or_panic!(99.0),
+            })
+            .unwrap();
+        assert_eq!(tracker.take_dirty(), vec!["condor", "straddle"]);
+        assert_eq!(tracker.dirty().count(), 0);
+    }
+
+    #[test]
+    fn test_watch_strategy_legs() {
+        use crate::model::types::Side;
+        use crate::model::utils::create_sample_position;
+        use crate::strategies::base::Positionable;
+
+        struct OneLeg(crate::model::Position);
+        impl Positionable for OneLeg {
+            fn get_positions(
+                &self,
+            ) -> Result<Vec<&crate::model::Position>, crate::error::PositionError> {
+                Ok(vec![&self.0])
+            }
+        }
+        let leg = OneLeg(create_sample_position(
+            OptionStyle::Call,
+            Side::Short,
+            Positive::HUNDRED,
+            Positive::ONE,
+            pos_or_panic!(105.0),
+            pos_or_panic!(0.2),
+        ));
+        let mut tracker = DirtyMetrics::new();
+        tracker.watch_strategy("short_call", &leg).unwrap();
+
+        let mut chain = build_chain();
+        let applied = chain
+            .apply_update(&QuoteUpdate::quote(
+                pos_or_panic!(105.0),
+                OptionStyle::Call,
+                Positive::ONE,
+                Positive::TWO,
+            ))
+            .unwrap();
+        tracker.mark(&applied);
+        assert!(tracker.is_dirty("short_call"));
+    }
+}
@@ -0,0 +1,245 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Strike Selection by Delta
+//!
+//! Most strategies are specified in delta terms ("sell the 30 delta put"). The
+//! helpers in this module locate, for each expiration, the listed strike whose
+//! delta is closest to a target and the strike obtained by interpolating
+//! linearly between the two listed strikes that bracket the target.
+//!
+//! Targets are compared in absolute value, so `0.30` and `-0.30` select the
+//! same put. Strikes without a delta for the requested side are ignored; call
+//! [`OptionChain::update_greeks`] first if the chain has no Greeks.
+
+use crate::chains::chain::OptionChain;
+use crate::error::ChainError;
+use crate::error::chains::OptionDataErrorKind;
+use crate::model::types::OptionStyle;
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Strike selected for a target delta on one side of one expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DeltaStrike {
+    /// Call or put.
+    pub option_style: OptionStyle,
+    /// Absolute target delta.
+    pub target_delta: Decimal,
+    /// Listed strike whose absolute delta is closest to the target.
+    pub nearest_strike: Positive,
+    /// Delta of the nearest listed strike.
+    pub nearest_delta: Decimal,
+    /// Strike interpolated between the two listed strikes bracketing the
+    /// target, or `None` if the target lies outside the listed deltas.
+    pub interpolated_strike: Option<Positive>,
+}
+
+/// Call and put strikes selected for a target delta on one expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DeltaStrikeSelection {
+    /// Expiration of the chain.
+    pub expiration: String,
+    /// Selected call strike.
+    pub call: DeltaStrike,
+    /// Selected put strike.
+    pub put: DeltaStrike,
+}
+
+impl OptionChain {
+    /// Selects the strike matching `target_delta` for one side of this chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChainError::OptionDataError` if no strike has a delta for the
+    /// requested side.
+    pub fn strike_for_delta(
+        &self,
+        target_delta: Decimal,
+        option_style: OptionStyle,
+    ) -> Result<DeltaStrike, ChainError> {
+        let target = target_delta.abs();
+        let mut points: Vec<(Positive, Decimal)> = self
+            .get_single_iter()
+            .filter_map(|od| {
+                let delta = match option_style {
+                    OptionStyle::Call => od.delta_call,
+                    OptionStyle::Put => od.delta_put,
+                }?;
+                Some((od.strike_price, delta))
+            })
+            .collect();
+        if points.is_empty() {
+            return Err(ChainError::OptionDataError(
+                OptionDataErrorKind::InvalidDelta {
+                    delta: target_delta.to_f64(),
+                    reason: format!("No {option_style:?} deltas available in chain"),
+                },
+            ));
+        }
+        points.sort_by_key(|(strike, _)| *strike);
+
+        let (nearest_strike, nearest_delta) = points
+            .iter()
+            .min_by_key(|(_, delta)| (delta.abs() - target).abs())
+            .copied()
+            .unwrap_or(points[0]);
+
+        let interpolated_strike = points.windows(2).find_map(|pair| {
+            let (k0, d0) = (pair[0].0.to_dec(), pair[0].1.abs());
+            let (k1, d1) = (pair[1].0.to_dec(), pair[1].1.abs());
+            let (low, high) = if d0 <= d1 { (d0, d1) } else { (d1, d0) };
+            if target < low || target > high {
+                return None;
+            }
+            if d1 == d0 {
+                return Positive::new_decimal(k0).ok();
+            }
+            let strike = k0 + (k1 - k0) * (target - d0) / (d1 - d0);
+            Positive::new_decimal(strike).ok()
+        });
+
+        Ok(DeltaStrike {
+            option_style,
+            target_delta: target,
+            nearest_strike,
+            nearest_delta,
+            interpolated_strike,
+        })
+    }
+
+    /// Selects the call and put strikes matching `target_delta` for this chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChainError::OptionDataError` if either side has no deltas.
+    pub fn strikes_for_delta(
+        &self,
+        target_delta: Decimal,
+    ) -> Result<DeltaStrikeSelection, ChainError> {
+        Ok(DeltaStrikeSelection {
+            expiration: self.get_expiration_date(),
+            call: self.strike_for_delta(target_delta, OptionStyle::Call)?,
+            put: self.strike_for_delta(target_delta, OptionStyle::Put)?,
+        })
+    }
+}
+
+/// Selects the call and put strikes matching `target_delta` for every expiration.
+///
+/// Chains are returned in the order given.
+///
+/// # Errors
+///
+/// Returns the first `ChainError` raised by [`OptionChain::strikes_for_delta`].
+pub fn strikes_for_delta_by_expiry(
+    chains: &[OptionChain],
+    target_delta: Decimal,
+) -> Result<Vec<DeltaStrikeSelection>, ChainError> {
+    chains
+        .iter()
+        .map(|chain| chain.strikes_for_delta(target_delta))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_strike_selection {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn build_chain(days: f64) -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(1000.0),
+            10,
+            spos!(1.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::HUNDRED)),
+                Some(ExpirationDate::Days(pos_or_panic!(days))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        let mut chain = OptionChain::build_chain(&params).unwrap();
+        chain.update_greeks();
+        chain
+    }
+
+    #[test]
+    fn test_strike_for_delta_call_and_put() {
+        let chain = build_chain(30.0);
+        let call = chain
+            .strike_for_delta(dec!(0.3), OptionStyle::Call)
+            .unwrap();
+        let put = chain
+            .strike_for_delta(dec!(-0.3), OptionStyle::Put)
+            .unwrap();
+        assert!(call.nearest_strike > Positive::HUNDRED);
+        assert!(put.nearest_strike < Positive::HUNDRED);
+        assert_eq!(put.target_delta, dec!(0.3));
+        assert!((call.nearest_delta - dec!(0.3)).abs() < dec!(0.1));
+    }
+
+    #[test]
+    fn test_interpolated_strike_brackets_target() {
+        let chain = build_chain(30.0);
+        let call = chain
+            .strike_for_delta(dec!(0.3), OptionStyle::Call)
+            .unwrap();
+        let interpolated = call.interpolated_strike.unwrap();
+        let strikes = chain.get_strikes().unwrap();
+        let below = strikes
+            .iter()
+            .filter(|k| **k <= interpolated)
+            .max()
+            .unwrap();
+        let above = strikes
+            .iter()
+            .filter(|k| **k >= interpolated)
+            .min()
+            .unwrap();
+        assert!(*above - *below <= pos_or_panic!(1.0));
+    }
+
+    #[test]
+    fn test_target_outside_listed_deltas() {
+        let chain = build_chain(30.0);
+        let call = chain
+            .strike_for_delta(dec!(0.0000001), OptionStyle::Call)
+            .unwrap();
+        assert!(call.interpolated_strike.is_none());
+    }
+
+    #[test]
+    fn test_missing_deltas() {
+        let mut chain = build_chain(30.0);
+        chain.mutate_single_options(|od| od.delta_put = None);
+        assert!(chain.strike_for_delta(dec!(0.3), OptionStyle::Put).is_err());
+        assert!(chain.strike_for_delta(dec!(0.3), OptionStyle::Call).is_ok());
+    }
+
+    #[test]
+    fn test_strikes_for_delta_by_expiry() {
+        let chains = vec![build_chain(10.0), build_chain(60.0)];
+        let selections = strikes_for_delta_by_expiry(&chains, dec!(0.25)).unwrap();
+        assert_eq!(selections.len(), 2);
+        // Longer dated 25 delta calls sit further out of the money.
+        assert!(selections[1].call.nearest_strike >= selections[0].call.nearest_strike);
+        assert!(selections[1].put.nearest_strike <= selections[0].put.nearest_strike);
+    }
+}
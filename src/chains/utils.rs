@@ -5,8 +5,8 @@
 ******************************************************************************/
 use positive::{Positive, pos_or_panic};
 
-use crate::chains::OptionData;
 use crate::chains::chain::{SKEW_SLOPE, SKEW_SMILE_CURVE};
+use crate::chains::{OptionData, StrategyLegs};
 use crate::error::chains::ChainError;
 use crate::model::ExpirationDate;
 use crate::model::utils::ToRound;
@@ -71,6 +71,54 @@ pub enum OptionDataGroup<'a> {
     Any(Vec<&'a OptionData>),
 }
 
+impl<'a> OptionDataGroup<'a> {
+    /// Converts the group into the matching `StrategyLegs` layout, keeping the
+    /// order of the options.
+    ///
+    /// Returns `None` for groups without a multi-leg layout (a single option,
+    /// or any number of options other than two, three, four or six).
+    pub fn to_strategy_legs(&self) -> Option<StrategyLegs<'a>> {
+        match self {
+            OptionDataGroup::Two(first, second) => Some(StrategyLegs::TwoLegs { first, second }),
+            OptionDataGroup::Three(first, second, third) => Some(StrategyLegs::ThreeLegs {
+                first,
+                second,
+                third,
+            }),
+            OptionDataGroup::Four(first, second, third, fourth) => Some(StrategyLegs::FourLegs {
+                first,
+                second,
+                third,
+                fourth,
+            }),
+            OptionDataGroup::Any(options) => match options.as_slice() {
+                [first, second] => Some(StrategyLegs::TwoLegs { first, second }),
+                [first, second, third] => Some(StrategyLegs::ThreeLegs {
+                    first,
+                    second,
+                    third,
+                }),
+                [first, second, third, fourth] => Some(StrategyLegs::FourLegs {
+                    first,
+                    second,
+                    third,
+                    fourth,
+                }),
+                [first, second, third, fourth, fifth, sixth] => Some(StrategyLegs::SixLegs {
+                    first,
+                    second,
+                    third,
+                    fourth,
+                    fifth,
+                    sixth,
+                }),
+                _ => None,
+            },
+            OptionDataGroup::One(_) => None,
+        }
+    }
+}
+
 /// Parameters for building an option chain dataset.
 ///
 /// This structure encapsulates all necessary configuration parameters to generate
@@ -14,9 +14,8 @@ use crate::geometrics::{
     RangeMetrics, RiskMetrics, ShapeMetrics, SplineInterpolation, TrendMetrics,
 };
 use crate::utils::Len;
+use crate::utils::parallel::map_ordered;
 use crate::visualization::{Graph, GraphData};
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use rayon::prelude::*;
 use rust_decimal::{Decimal, MathematicalOps};
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
@@ -192,12 +191,12 @@ impl GeometricObject<Point2D, Decimal> for Curve {
                 };
                 let step_size = (t_end - t_start) / Decimal::from(steps);
 
-                let points: Result<BTreeSet<Point2D>, CurveError> = (0..=steps)
-                    .into_par_iter()
-                    .map(|i| {
+                let points: Result<BTreeSet<Point2D>, CurveError> =
+                    map_ordered((0..=steps).collect(), |i| {
                         let t = t_start + step_size * Decimal::from(i);
                         f(t).map_err(|e| CurveError::ConstructionError(e.to_string()))
                     })
+                    .into_iter()
                     .collect();
 
                 points.map(Curve::new)
@@ -1365,10 +1364,9 @@ impl Arithmetic<Curve> for Curve {
         let steps = 100; // Configurable number of interpolation points
         let step_size = (max_x - min_x) / Decimal::from(steps);
 
-        // Interpolate and perform operation using parallel iterator
-        let result_points: Result<Vec<Point2D>, CurveError> = (0..=steps)
-            .into_par_iter()
-            .map(|i| {
+        // Interpolate and perform operation, in parallel with the `parallel` feature
+        let result_points: Result<Vec<Point2D>, CurveError> =
+            map_ordered((0..=steps).collect(), |i| {
                 let x = min_x + step_size * Decimal::from(i);
 
                 // Interpolate y values for each curve
@@ -1386,18 +1384,15 @@ impl Arithmetic<Curve> for Curve {
 
                 // Perform the specified operation on interpolated y values
                 let result_y: Decimal = match operation {
-                    MergeOperation::Add => y_values.par_iter().sum(),
-                    MergeOperation::Subtract => {
-                        // Use Rayon's fold to parallelize subtraction
-                        y_values
-                            .par_iter()
-                            .enumerate()
-                            .map(|(i, &val)| if i == 0 { val } else { -val })
-                            .reduce(|| Decimal::ZERO, |a, b| a + b)
-                    }
-                    MergeOperation::Multiply => y_values.par_iter().product(),
+                    MergeOperation::Add => y_values.iter().sum(),
+                    MergeOperation::Subtract => y_values
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &val)| if i == 0 { val } else { -val })
+                        .sum(),
+                    MergeOperation::Multiply => y_values.iter().product(),
                     MergeOperation::Divide => y_values
-                        .par_iter()
+                        .iter()
                         .enumerate()
                         .map(|(i, &val)| {
                             if i == 0 {
@@ -1408,14 +1403,14 @@ impl Arithmetic<Curve> for Curve {
                                 Decimal::ONE / val
                             }
                         })
-                        .reduce(|| Decimal::ONE, |a, b| a * b),
+                        .product(),
                     MergeOperation::Max => y_values
-                        .par_iter()
+                        .iter()
                         .cloned()
                         .max_by(|a, b| a.partial_cmp(b).unwrap())
                         .unwrap_or(Decimal::ZERO),
                     MergeOperation::Min => y_values
-                        .par_iter()
+                        .iter()
                         .cloned()
                         .min_by(|a, b| a.partial_cmp(b).unwrap())
                         .unwrap_or(Decimal::ZERO),
@@ -1423,6 +1418,7 @@ impl Arithmetic<Curve> for Curve {
 
                 Ok(Point2D::new(x, result_y))
             })
+            .into_iter()
             .collect();
 
         // Handle potential errors during parallel processing
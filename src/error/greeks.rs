@@ -509,6 +509,12 @@ impl From<expiration_date::error::ExpirationDateError> for GreeksError {
     }
 }
 
+impl From<crate::error::OptionsError> for GreeksError {
+    fn from(err: crate::error::OptionsError) -> Self {
+        GreeksError::StdError(err.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests_error_greeks {
     use super::*;
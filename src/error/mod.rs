@@ -30,6 +30,10 @@
 //! * `MetricsError` - Performance and risk metrics calculation errors
 //! * `SurfaceError` - Volatility and pricing surface construction errors
 //!
+//! ### Unified
+//! * `OptionStratError` - Wraps every error above, plus calendar and IO errors,
+//!   for applications that propagate failures through a single type
+//!
 //! ## Usage Example
 //!
 //! ```rust
@@ -224,9 +228,10 @@ mod volatility;
 /// * Date and decimal parsing issues
 mod csv;
 
-/// ### Unified Error Type
+/// ### Unified Error Type (`OptionStratError`)
 /// Top-level error type that encompasses all errors in the library.
-/// Provides a single error type for unified error handling across modules.
+/// Provides a single error type for unified error handling across modules,
+/// including calendar and IO failures.
 pub mod unified;
 
 pub use chains::ChainError;
@@ -247,5 +252,5 @@ pub use strategies::StrategyError;
 pub use surfaces::SurfaceError;
 pub use trade::TradeError;
 pub use transaction::TransactionError;
-pub use unified::Error;
+pub use unified::{Error, OptionStratError, OptionStratResult};
 pub use volatility::VolatilityError;
@@ -393,6 +393,12 @@ impl From<crate::error::PricingError> for ProbabilityError {
     }
 }
 
+impl From<crate::error::OptionsError> for ProbabilityError {
+    fn from(error: crate::error::OptionsError) -> Self {
+        ProbabilityError::StdError(error.to_string())
+    }
+}
+
 impl From<crate::error::DecimalError> for ProbabilityError {
     fn from(error: crate::error::DecimalError) -> Self {
         ProbabilityError::StdError(error.to_string())
@@ -456,6 +462,7 @@ impl From<StrategyError> for ProbabilityError {
                     ))
                 }
             },
+            StrategyError::ValidationError(kind) => ProbabilityError::StdError(kind.to_string()),
             StrategyError::StdError { reason: msg } => ProbabilityError::StdError(msg),
             StrategyError::NotImplemented => {
                 ProbabilityError::StdError("Strategy not implemented".to_string())
@@ -13,10 +13,11 @@
 //! ## Error Types
 //!
 //! ### Strategy Error (`StrategyError`)
-//! The main error enum with four categories:
+//! The main error enum with five categories:
 //! * `PriceError` - For price calculation failures
 //! * `BreakEvenError` - For break-even point calculation errors
 //! * `ProfitLossError` - For profit/loss calculation failures
+//! * `ValidationError` - For malformed strategy legs
 //! * `OperationError` - For strategy operation errors
 //!
 //! ### Price Errors (`PriceErrorKind`)
@@ -35,6 +36,13 @@
 //! * Maximum loss calculation errors
 //! * Profit range calculation errors
 //!
+//! ### Validation Errors (`ValidationErrorKind`)
+//! Reports malformed strategy legs:
+//! * Missing legs or a leg count that does not match the strategy type
+//! * Mixed underlyings and zero quantities
+//! * Invalid or inconsistent expirations
+//! * Sides and styles incompatible with the strategy type
+//!
 //! ## Integration with Probability Analysis
 //!
 //! Implements conversion from `StrategyError` to `ProbabilityError` for seamless
@@ -69,6 +77,8 @@
 //! Provides `StrategyResult<T>` for convenient error handling in strategy operations.
 use crate::error::common::OperationErrorKind;
 use crate::error::{GreeksError, OptionsError, PositionError, SimulationError, TradeError};
+use crate::model::types::{OptionStyle, Side};
+use crate::strategies::base::StrategyType;
 use thiserror::Error;
 
 /// Represents the different types of errors that can occur in options trading strategies.
@@ -83,6 +93,7 @@ use thiserror::Error;
 /// * `PriceError` - Errors related to pricing operations such as invalid prices or ranges
 /// * `BreakEvenError` - Errors encountered when calculating strategy break-even points
 /// * `ProfitLossError` - Errors related to profit/loss calculations including maximum values
+/// * `ValidationError` - Inconsistent legs such as mixed underlyings or incompatible sides
 /// * `OperationError` - General strategy operation errors including unsupported operations
 /// * `StdError` - Standard errors with a descriptive reason
 /// * `NotImplemented` - For features or operations that are not yet implemented
@@ -106,6 +117,10 @@ pub enum StrategyError {
     #[error("Profit/loss error: {0}")]
     ProfitLossError(ProfitLossErrorKind),
 
+    /// Errors found while validating the legs of a strategy
+    #[error("Validation error: {0}")]
+    ValidationError(ValidationErrorKind),
+
     /// Errors related to strategy operations
     #[error("Operation error: {0}")]
     OperationError(OperationErrorKind),
@@ -270,6 +285,78 @@ pub enum ProfitLossErrorKind {
     },
 }
 
+/// Represents the inconsistencies found when validating the legs of a strategy.
+///
+/// Each variant identifies the offending leg by its index in the strategy's
+/// positions, so malformed strategies can be reported precisely instead of
+/// failing later in pricing or analysis.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ValidationErrorKind {
+    /// The strategy has no legs.
+    #[error("Strategy has no legs")]
+    NoLegs,
+
+    /// The number of legs does not match the declared strategy type.
+    #[error("{strategy} expects {expected} legs, found {found}")]
+    LegCount {
+        /// Declared strategy type
+        strategy: StrategyType,
+        /// Number of legs the strategy type requires
+        expected: usize,
+        /// Number of legs found
+        found: usize,
+    },
+
+    /// A leg is written on a different underlying than the first leg.
+    #[error("Leg {leg} is on underlying {found}, expected {expected}")]
+    MixedUnderlying {
+        /// Index of the offending leg
+        leg: usize,
+        /// Underlying symbol of the first leg
+        expected: String,
+        /// Underlying symbol of the offending leg
+        found: String,
+    },
+
+    /// A leg has a zero quantity.
+    #[error("Leg {leg} has zero quantity")]
+    ZeroQuantity {
+        /// Index of the offending leg
+        leg: usize,
+    },
+
+    /// A leg has an expiration that cannot be used.
+    #[error("Leg {leg} has an invalid expiration: {reason}")]
+    InvalidExpiration {
+        /// Index of the offending leg
+        leg: usize,
+        /// Detailed explanation of why the expiration is invalid
+        reason: String,
+    },
+
+    /// The expirations of the legs are inconsistent with the strategy type.
+    #[error("Expirations are inconsistent with {strategy}: {reason}")]
+    ExpirationMismatch {
+        /// Declared strategy type
+        strategy: StrategyType,
+        /// Detailed explanation of the inconsistency
+        reason: String,
+    },
+
+    /// A leg has a side and style that do not belong to the strategy type.
+    #[error("Leg {leg} ({side} {style}) is not compatible with {strategy}")]
+    IncompatibleLeg {
+        /// Declared strategy type
+        strategy: StrategyType,
+        /// Index of the offending leg
+        leg: usize,
+        /// Side of the offending leg
+        side: Side,
+        /// Style of the offending leg
+        style: OptionStyle,
+    },
+}
+
 /// A specialized result type for strategy operations.
 ///
 /// This type alias provides a convenient way to handle results from strategy-related
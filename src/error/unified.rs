@@ -6,12 +6,22 @@
 
 //! Unified error type for the entire library.
 //!
-//! This module provides a top-level `Error` enum that encapsulates all specific
-//! error types from different modules, enabling unified error handling across
-//! the library.
+//! This module provides the top-level [`OptionStratError`] enum that
+//! encapsulates all specific error types from different modules, enabling
+//! unified error handling across the library. Every module error converts
+//! into it with `?`, so applications can propagate pricing, chain, strategy,
+//! calendar and IO failures through a single [`OptionStratResult`].
+//!
+//! [`Error`] is kept as an alias of [`OptionStratError`].
 
 use thiserror::Error;
 
+/// Alias of [`OptionStratError`], kept for backwards compatibility.
+pub type Error = OptionStratError;
+
+/// Result type whose error is the unified [`OptionStratError`].
+pub type OptionStratResult<T> = Result<T, OptionStratError>;
+
 /// Top-level error type that encompasses all errors in the library.
 ///
 /// This enum uses `#[error(transparent)]` to delegate error display to the
@@ -21,15 +31,17 @@ use thiserror::Error;
 /// # Examples
 ///
 /// ```
-/// use optionstratlib::error::{Error, PricingError};
+/// use optionstratlib::error::{OptionStratError, OptionStratResult, PricingError};
 ///
-/// fn example() -> Result<(), Error> {
+/// fn example() -> OptionStratResult<()> {
 ///     // Errors are automatically converted using From trait
 ///     Err(PricingError::method_error("test", "failed").into())
 /// }
+///
+/// assert!(matches!(example(), Err(OptionStratError::Pricing(_))));
 /// ```
 #[derive(Error, Debug)]
-pub enum Error {
+pub enum OptionStratError {
     /// Options-related errors.
     #[error(transparent)]
     Options(#[from] crate::error::OptionsError),
@@ -106,25 +118,87 @@ pub enum Error {
     #[error(transparent)]
     Trade(#[from] crate::error::TradeError),
 
+    /// Calendar and expiration date errors.
+    #[error(transparent)]
+    Calendar(#[from] expiration_date::error::ExpirationDateError),
+
+    /// Input/output errors, such as reading or writing data files.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// CSV reading and writing errors.
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+
+    /// Arrow record batch errors.
+    #[cfg(feature = "arrow")]
+    #[error(transparent)]
+    Arrow(#[from] arrow_schema::ArrowError),
+
+    /// Parquet encoding errors.
+    #[cfg(feature = "arrow")]
+    #[error(transparent)]
+    Parquet(#[from] parquet::errors::ParquetError),
+
     /// Generic error with a custom message.
     #[error("{0}")]
     Other(String),
 }
 
-impl From<Box<dyn std::error::Error>> for Error {
+impl From<Box<dyn std::error::Error>> for OptionStratError {
     fn from(err: Box<dyn std::error::Error>) -> Self {
-        Error::Other(err.to_string())
+        OptionStratError::Other(err.to_string())
     }
 }
 
-impl From<String> for Error {
+impl From<String> for OptionStratError {
     fn from(msg: String) -> Self {
-        Error::Other(msg)
+        OptionStratError::Other(msg)
     }
 }
 
-impl From<&str> for Error {
+impl From<&str> for OptionStratError {
     fn from(msg: &str) -> Self {
-        Error::Other(msg.to_string())
+        OptionStratError::Other(msg.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests_unified {
+    use super::*;
+    use crate::error::{ChainError, PricingError, StrategyError};
+    use expiration_date::error::ExpirationDateError;
+
+    fn propagate<E: Into<OptionStratError>>(error: E) -> OptionStratResult<()> {
+        Err(error.into())
+    }
+
+    #[test]
+    fn test_module_errors_convert() {
+        assert!(matches!(
+            propagate(PricingError::other("failed")),
+            Err(OptionStratError::Pricing(_))
+        ));
+        assert!(matches!(
+            propagate(ChainError::invalid_price_calculation("failed")),
+            Err(OptionStratError::Chain(_))
+        ));
+        assert!(matches!(
+            propagate(StrategyError::NotImplemented),
+            Err(OptionStratError::Strategy(_))
+        ));
+        assert!(matches!(
+            propagate(std::io::Error::other("disk")),
+            Err(OptionStratError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_calendar_error_keeps_message() {
+        let source = ExpirationDateError::ParseError("bad date".to_string());
+        let message = source.to_string();
+        let error: Error = source.into();
+        assert!(matches!(error, OptionStratError::Calendar(_)));
+        assert_eq!(error.to_string(), message);
     }
 }
@@ -5,6 +5,7 @@
 ******************************************************************************/
 use crate::constants::{TRADING_DAYS, ZERO};
 use crate::error::greeks::GreeksError;
+use crate::greeks::intermediates::{DRate, intermediates};
 use crate::greeks::utils::{big_n, d1, d2, n};
 use crate::model::types::{OptionStyle, OptionType};
 use crate::{Options, Side};
@@ -101,6 +102,60 @@ pub struct GreeksSnapshot {
     pub color: Decimal,
 }
 
+/// Net first-order Greeks of a group of option legs.
+///
+/// Unlike the aggregate values returned by [`Greeks::greeks`], every Greek is
+/// signed by the side of its leg, so short legs contribute negative gamma and
+/// vega and positive theta. Each value is already scaled by the quantity of
+/// the leg.
+#[derive(DebugPretty, DisplaySimple, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct NetGreeks {
+    /// Net sensitivity to changes in the underlying asset's price
+    pub delta: Decimal,
+    /// Net rate of change of delta with respect to the underlying asset's price
+    pub gamma: Decimal,
+    /// Net daily time decay
+    pub theta: Decimal,
+    /// Net sensitivity to changes in implied volatility
+    pub vega: Decimal,
+    /// Net sensitivity to changes in the risk-free interest rate
+    pub rho: Decimal,
+}
+
+impl NetGreeks {
+    /// Returns the Greeks of a single option, signed by its side and scaled by
+    /// its quantity.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if any Greek calculation fails.
+    pub fn of_option(option: &Options) -> Result<Self, GreeksError> {
+        // Delta is already signed by side; the other Greeks are not.
+        let sign = if option.is_long() {
+            Decimal::ONE
+        } else {
+            Decimal::NEGATIVE_ONE
+        };
+        Ok(Self {
+            delta: delta(option)?,
+            gamma: sign * gamma(option)?,
+            theta: sign * theta(option)?,
+            vega: sign * vega(option)?,
+            rho: sign * rho(option)?,
+        })
+    }
+
+    /// Returns `true` if the absolute net delta does not exceed `tolerance`.
+    pub fn is_delta_neutral(&self, tolerance: Decimal) -> bool {
+        self.delta.abs() <= tolerance
+    }
+
+    /// Returns `true` if the position earns time decay, i.e. its net theta is positive.
+    pub fn is_theta_positive(&self) -> bool {
+        self.theta > Decimal::ZERO
+    }
+}
+
 /// Trait that provides option Greeks calculation functionality for financial instruments.
 ///
 /// The `Greeks` trait enables implementing types to calculate option sensitivity metrics
@@ -184,6 +239,34 @@ pub trait Greeks {
         })
     }
 
+    /// Calculates the net delta, gamma, theta, vega and rho of all options.
+    ///
+    /// Each Greek is signed by the side of its option and scaled by its
+    /// quantity, so the result describes the exposure of the combined
+    /// position.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or any Greek calculation fails.
+    fn net_greeks(&self) -> Result<NetGreeks, GreeksError> {
+        let mut net = NetGreeks {
+            delta: Decimal::ZERO,
+            gamma: Decimal::ZERO,
+            theta: Decimal::ZERO,
+            vega: Decimal::ZERO,
+            rho: Decimal::ZERO,
+        };
+        for option in self.get_options()? {
+            let greeks = NetGreeks::of_option(option)?;
+            net.delta += greeks.delta;
+            net.gamma += greeks.gamma;
+            net.theta += greeks.theta;
+            net.vega += greeks.vega;
+            net.rho += greeks.rho;
+        }
+        Ok(net)
+    }
+
     /// Calculates the aggregate delta value for all options.
     ///
     /// Delta measures the rate of change in an option's price with respect to
@@ -449,7 +532,7 @@ pub trait Greeks {
 /// use optionstratlib::constants::ZERO;
 /// use optionstratlib::greeks::delta;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic, Positive};
 /// let option = Options {
 ///     option_type: OptionType::European,
@@ -464,6 +547,8 @@ pub trait Greeks {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "AAPL".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match delta(&option) {
@@ -475,7 +560,7 @@ pub fn delta(option: &Options) -> Result<Decimal, GreeksError> {
     if !matches!(option.option_type, OptionType::European) {
         return crate::greeks::numerical::numerical_delta(option);
     }
-    let expiration_date = option.expiration_date.get_years()?;
+    let expiration_date = option.time_to_expiration()?;
 
     // For an option when the time to expiration is zero (i.e., at the moment of expiration),
     // the delta takes discrete values based solely on whether the option is In-The-Money (ITM) or
@@ -518,8 +603,6 @@ pub fn delta(option: &Options) -> Result<Decimal, GreeksError> {
         };
     }
 
-    let dividend_yield: Positive = option.dividend_yield;
-
     let sign = if option.is_long() {
         Decimal::ONE
     } else {
@@ -544,18 +627,11 @@ pub fn delta(option: &Options) -> Result<Decimal, GreeksError> {
         };
     }
 
-    let d1 = d1(
-        option.underlying_price,
-        option.strike_price,
-        option.risk_free_rate,
-        expiration_date,
-        option.implied_volatility,
-    )?;
-
-    let div_date = (-expiration_date.to_dec() * dividend_yield).exp();
+    let values = intermediates(option, expiration_date, DRate::RiskFree)?;
+    let div_date = values.dividend_discount;
     let delta = match option.option_style {
-        OptionStyle::Call => sign * big_n(d1)? * div_date,
-        OptionStyle::Put => sign * (big_n(d1)? - Decimal::ONE) * div_date,
+        OptionStyle::Call => sign * values.big_n_d1 * div_date,
+        OptionStyle::Put => sign * (values.big_n_d1 - Decimal::ONE) * div_date,
     };
     let delta: Decimal = delta.clamp(Decimal::NEGATIVE_ONE, Decimal::ONE);
     let quantity: Decimal = option.quantity.into();
@@ -617,7 +693,7 @@ pub fn delta(option: &Options) -> Result<Decimal, GreeksError> {
 /// use tracing::{error, info};
 /// use optionstratlib::greeks::gamma;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic,Positive};
 /// let option = Options {
 ///     option_type: OptionType::European,
@@ -632,6 +708,8 @@ pub fn delta(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match gamma(&option) {
@@ -653,26 +731,19 @@ pub fn gamma(option: &Options) -> Result<Decimal, GreeksError> {
     if option.implied_volatility == ZERO {
         return Ok(Decimal::ZERO);
     }
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    let expiration_date: Positive = option.time_to_expiration()?;
     if expiration_date == Decimal::ZERO {
         // At expiration, gamma is 0 for all cases
         return Ok(Decimal::ZERO);
     }
 
-    let d1 = d1(
-        option.underlying_price,
-        option.strike_price,
-        option.risk_free_rate,
-        expiration_date,
-        option.implied_volatility,
-    )?;
+    let values = intermediates(option, expiration_date, DRate::RiskFree)?;
 
-    let dividend_yield: Decimal = option.dividend_yield.into();
     let underlying_price: Decimal = option.underlying_price.into();
     let implied_volatility: Positive = option.implied_volatility;
 
-    let gamma: Decimal = (expiration_date.to_dec() * -dividend_yield).exp() * n(d1)?
-        / (underlying_price * implied_volatility * expiration_date.sqrt().to_dec());
+    let gamma: Decimal = values.dividend_discount * values.n_d1
+        / (underlying_price * implied_volatility * values.sqrt_time.to_dec());
 
     let quantity: Decimal = option.quantity.into();
     Ok(gamma * quantity)
@@ -743,7 +814,8 @@ pub fn gamma(option: &Options) -> Result<Decimal, GreeksError> {
 ///    ```
 /// 3. Apply the corresponding formula for Call or Put options, accounting for the effect of
 ///    dividends (\( e^{-qT} \)) and risk-free rate (\( e^{-rT} \)).
-/// 4. Multiply the resulting Theta by the quantity of options.
+/// 4. Multiply the resulting Theta by the quantity of options and divide it by the days per
+///    year of the option's day-count convention to obtain the decay per day.
 ///
 /// # Example
 ///
@@ -752,7 +824,7 @@ pub fn gamma(option: &Options) -> Result<Decimal, GreeksError> {
 /// use tracing::{error, info};
 /// use optionstratlib::greeks::theta;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic,Positive};
 /// let option = Options {
 ///     option_type: OptionType::European,
@@ -767,6 +839,8 @@ pub fn gamma(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match theta(&option) {
@@ -781,25 +855,12 @@ pub fn gamma(option: &Options) -> Result<Decimal, GreeksError> {
 /// - A negative Theta is typical for long positions, as the option loses extrinsic value over time.
 /// - If the implied volatility is zero, Theta may be close to zero for far-out-of-the-money options.
 pub fn theta(option: &Options) -> Result<Decimal, GreeksError> {
-    let t = option.expiration_date.get_years()?;
+    let t = option.time_to_expiration()?;
     if t == Decimal::ZERO {
         return Ok(Decimal::ZERO);
     }
 
-    let d1 = d1(
-        option.underlying_price,
-        option.strike_price,
-        option.risk_free_rate,
-        t,
-        option.implied_volatility,
-    )?;
-    let d2 = d2(
-        option.underlying_price,
-        option.strike_price,
-        option.risk_free_rate,
-        t,
-        option.implied_volatility,
-    )?;
+    let values = intermediates(option, t, DRate::RiskFree)?;
 
     let s = option.underlying_price.to_dec();
     let k = option.strike_price.to_dec();
@@ -808,23 +869,25 @@ pub fn theta(option: &Options) -> Result<Decimal, GreeksError> {
     let sigma = option.implied_volatility.to_dec();
 
     // Common term using n
-    let common_term = -(s * n(d1)? * sigma) / (Decimal::TWO * t.sqrt());
+    let common_term = -(s * values.n_d1 * sigma) / (Decimal::TWO * values.sqrt_time);
 
-    // Pre-calculate discount factors
-    let exp_minus_rt = (-r * t).exp();
-    let exp_minus_qt = (-q * t).exp();
+    // Discount factors
+    let exp_minus_rt = values.rate_discount;
+    let exp_minus_qt = values.dividend_discount;
 
     let theta = match option.option_style {
         OptionStyle::Call => {
-            common_term - r * k * exp_minus_rt * big_n(d2)? + q * s * exp_minus_qt * big_n(d1)?
+            common_term - r * k * exp_minus_rt * values.big_n_d2
+                + q * s * exp_minus_qt * values.big_n_d1
         }
         OptionStyle::Put => {
-            common_term + r * k * exp_minus_rt * big_n(-d2)? - q * s * exp_minus_qt * big_n(-d1)?
+            common_term + r * k * exp_minus_rt * values.big_n_minus_d2
+                - q * s * exp_minus_qt * values.big_n_minus_d1
         }
     };
 
     // Adjust for quantity and convert to daily value
-    Ok((theta * option.quantity.to_dec()) / Decimal::from(365))
+    Ok((theta * option.quantity.to_dec()) / option.day_count.days_per_year())
 }
 
 /// Computes the vega of an option.
@@ -882,7 +945,7 @@ pub fn theta(option: &Options) -> Result<Decimal, GreeksError> {
 /// use tracing::{error, info};
 /// use optionstratlib::greeks::vega;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic,Positive};
 ///
 /// let option = Options {
@@ -898,6 +961,8 @@ pub fn theta(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match vega(&option) {
@@ -913,27 +978,18 @@ pub fn theta(option: &Options) -> Result<Decimal, GreeksError> {
 /// - For shorter time to expiration, Vega is smaller as the sensitivity to volatility diminishes.
 /// - A positive Vega indicates that an increase in implied volatility will increase the option's value.
 pub fn vega(option: &Options) -> Result<Decimal, GreeksError> {
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    let expiration_date: Positive = option.time_to_expiration()?;
     if expiration_date == Decimal::ZERO {
         // At expiration, volatility has no impact on option price
         return Ok(Decimal::ZERO);
     }
-    let d1 = d1(
-        option.underlying_price,
-        option.strike_price,
-        option.risk_free_rate,
-        expiration_date,
-        option.implied_volatility,
-    )?;
+    let values = intermediates(option, expiration_date, DRate::RiskFree)?;
 
-    let dividend_yield: Positive = option.dividend_yield;
     let underlying_price: Decimal = option.underlying_price.to_dec();
 
-    let vega: Decimal = underlying_price
-        * (-expiration_date.to_dec() * dividend_yield).exp()
-        * n(d1)?
-        * expiration_date.sqrt()
-        / Decimal::ONE_HUNDRED; // percentage of change in volatility
+    let vega: Decimal =
+        underlying_price * values.dividend_discount * values.n_d1 * values.sqrt_time
+            / Decimal::ONE_HUNDRED; // percentage of change in volatility
 
     let quantity: Decimal = option.quantity.into();
     Ok(vega * quantity)
@@ -1006,7 +1062,7 @@ pub fn vega(option: &Options) -> Result<Decimal, GreeksError> {
 /// use tracing::{error, info};
 /// use optionstratlib::greeks::rho;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic,Positive};
 ///
 /// let option = Options {
@@ -1022,6 +1078,8 @@ pub fn vega(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match rho(&option) {
@@ -1038,39 +1096,22 @@ pub fn vega(option: &Options) -> Result<Decimal, GreeksError> {
 /// - Put options have negative rho values, as an increase in interest rates decreases their value.
 pub fn rho(option: &Options) -> Result<Decimal, GreeksError> {
     // Get time to expiration first and validate
-    let t = option.expiration_date.get_years()?;
+    let t = option.time_to_expiration()?;
     if t == Decimal::ZERO {
         return Ok(Decimal::ZERO);
     }
 
-    // Use existing d2 function
-    let d2 = d2(
-        option.underlying_price,
-        option.strike_price,
-        option.risk_free_rate,
-        t,
-        option.implied_volatility,
-    )?;
+    let values = intermediates(option, t, DRate::RiskFree)?;
 
     let k = option.strike_price.to_dec();
-    let r = option.risk_free_rate;
-
-    // Calculate discount factor once
-    let e_rt = (-r * t).exp();
 
     // Calculate base rho without sign
-    let base_rho = k * t * e_rt;
+    let base_rho = k * t * values.rate_discount;
 
     // Calculate final rho based on option type
     let rho = match option.option_style {
-        OptionStyle::Call => {
-            let n_d2 = big_n(d2)?;
-            base_rho * n_d2
-        }
-        OptionStyle::Put => {
-            let n_minus_d2 = big_n(-d2)?;
-            -base_rho * n_minus_d2
-        }
+        OptionStyle::Call => base_rho * values.big_n_d2,
+        OptionStyle::Put => -base_rho * values.big_n_minus_d2,
     };
 
     // Adjust for quantity and convert to basis points
@@ -1139,7 +1180,7 @@ pub fn rho(option: &Options) -> Result<Decimal, GreeksError> {
 /// use tracing::{error, info};
 /// use optionstratlib::greeks::rho_d;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic, Positive};
 ///
 /// let option = Options {
@@ -1155,6 +1196,8 @@ pub fn rho(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match rho_d(&option) {
@@ -1172,7 +1215,7 @@ pub fn rho(option: &Options) -> Result<Decimal, GreeksError> {
 /// - This calculation assumes that dividends are continuously compounded at the dividend yield rate.
 /// - \( Rho_d \) is generally more significant for options with longer times to expiration.
 pub fn rho_d(option: &Options) -> Result<Decimal, GreeksError> {
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    let expiration_date: Positive = option.time_to_expiration()?;
     let d1 = d1(
         option.underlying_price,
         option.strike_price,
@@ -1268,7 +1311,7 @@ pub fn alpha(option: &Options) -> Result<Decimal, GreeksError> {
 /// use tracing::{error, info};
 /// use optionstratlib::greeks::vanna;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic,Positive};
 /// let option = Options {
 ///     option_type: OptionType::European,
@@ -1283,6 +1326,8 @@ pub fn alpha(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match vanna(&option) {
@@ -1300,7 +1345,7 @@ pub fn vanna(option: &Options) -> Result<Decimal, GreeksError> {
         return Ok(Decimal::ZERO);
     }
 
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    let expiration_date: Positive = option.time_to_expiration()?;
     let d1 = d1(
         option.underlying_price,
         option.strike_price,
@@ -1380,7 +1425,7 @@ pub fn vanna(option: &Options) -> Result<Decimal, GreeksError> {
 /// use tracing::{error, info};
 /// use optionstratlib::greeks::vomma;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic,Positive};
 ///
 /// let option = Options {
@@ -1396,6 +1441,8 @@ pub fn vanna(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match vomma(&option) {
@@ -1414,7 +1461,7 @@ pub fn vanna(option: &Options) -> Result<Decimal, GreeksError> {
 /// If you think the implied volatility will be volatile in the short term
 /// you should typically try to find options with high Vomma.
 pub fn vomma(option: &Options) -> Result<Decimal, GreeksError> {
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    let expiration_date: Positive = option.time_to_expiration()?;
     if expiration_date == Decimal::ZERO {
         // At expiration, volatility has no impact on option price
         return Ok(Decimal::ZERO);
@@ -1492,7 +1539,7 @@ pub fn vomma(option: &Options) -> Result<Decimal, GreeksError> {
 /// use tracing::{error, info};
 /// use optionstratlib::greeks::veta;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic,Positive};
 ///
 /// let option = Options {
@@ -1508,6 +1555,8 @@ pub fn vomma(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match veta(&option) {
@@ -1521,7 +1570,7 @@ pub fn vomma(option: &Options) -> Result<Decimal, GreeksError> {
 ///   the number of days per year to reduce the value to the percentage change in
 ///   vega per one day.
 pub fn veta(option: &Options) -> Result<Decimal, GreeksError> {
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    let expiration_date: Positive = option.time_to_expiration()?;
     if expiration_date == Decimal::ZERO {
         // At expiration, volatility has no impact on option price
         return Ok(Decimal::ZERO);
@@ -1642,7 +1691,7 @@ pub fn veta(option: &Options) -> Result<Decimal, GreeksError> {
 /// use tracing::{error, info};
 /// use optionstratlib::greeks::charm;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic, Positive};
 /// let option = Options {
 ///     option_type: OptionType::European,
@@ -1657,6 +1706,8 @@ pub fn veta(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match charm(&option) {
@@ -1670,7 +1721,7 @@ pub fn veta(option: &Options) -> Result<Decimal, GreeksError> {
 /// - With zero DTE Charm can be considered as zero.
 /// - Charm effects are more pronounced near expiration.
 pub fn charm(option: &Options) -> Result<Decimal, GreeksError> {
-    let tau = option.expiration_date.get_years()?;
+    let tau = option.time_to_expiration()?;
     // if DTE is zero we can assume Charm is also zero
     if tau == Decimal::ZERO {
         return Ok(Decimal::ZERO);
@@ -1705,7 +1756,7 @@ pub fn charm(option: &Options) -> Result<Decimal, GreeksError> {
         }
     };
     // Adjust for quantity and convert to daily value
-    Ok((charm * option.quantity) / Decimal::from(365))
+    Ok((charm * option.quantity) / option.day_count.days_per_year())
 }
 
 /// Computes the Color of an option.
@@ -1775,7 +1826,7 @@ pub fn charm(option: &Options) -> Result<Decimal, GreeksError> {
 /// use tracing::{error, info};
 /// use optionstratlib::greeks::color;
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use positive::{pos_or_panic,Positive};
 /// let option = Options {
 ///     option_type: OptionType::European,
@@ -1790,6 +1841,8 @@ pub fn charm(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// match color(&option) {
@@ -1804,7 +1857,7 @@ pub fn charm(option: &Options) -> Result<Decimal, GreeksError> {
 /// - When volatility increases Color sensitivity decrease.
 /// - Deep ITM and OTM options have negligible Color.
 pub fn color(option: &Options) -> Result<Decimal, GreeksError> {
-    let tau = option.expiration_date.get_years()?;
+    let tau = option.time_to_expiration()?;
     // if DTE is zero we can assume Color is also zero
     if tau == Decimal::ZERO {
         return Ok(Decimal::ZERO);
@@ -1833,7 +1886,8 @@ pub fn color(option: &Options) -> Result<Decimal, GreeksError> {
     let numerator = (Decimal::TWO * (r - q) * tau) - (d2 * sigma * tau.sqrt());
     let denominator = sigma * tau.sqrt();
     let factor2 = (Decimal::TWO * q * tau) + Decimal::ONE + ((numerator / denominator) * d1);
-    let color = (-exp_minus_qt * factor1 * factor2 * option.quantity) / Decimal::from(365);
+    let color =
+        (-exp_minus_qt * factor1 * factor2 * option.quantity) / option.day_count.days_per_year();
     Ok(color)
 }
 
@@ -2385,7 +2439,7 @@ pub mod tests_vega_equation {
 #[cfg(test)]
 pub mod tests_rho_equations {
     use super::*;
-    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
     use crate::{ExpirationDate, assert_decimal_eq};
     use approx::assert_relative_eq;
     use num_traits::ToPrimitive;
@@ -2407,6 +2461,8 @@ pub mod tests_rho_equations {
             option_style: style,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
@@ -2777,6 +2833,51 @@ mod tests_greeks_trait {
         );
     }
 
+    #[test]
+    fn test_net_greeks_short_straddle() {
+        let collection = TestOptionCollection {
+            options: vec![
+                create_test_option(Side::Short, OptionStyle::Call, Positive::TWO),
+                create_test_option(Side::Short, OptionStyle::Put, Positive::TWO),
+            ],
+        };
+        let net = collection.net_greeks().unwrap();
+        let call = create_test_option(Side::Long, OptionStyle::Call, Positive::TWO);
+        let put = create_test_option(Side::Long, OptionStyle::Put, Positive::TWO);
+
+        assert_decimal_eq!(
+            net.delta,
+            -(delta(&call).unwrap() + delta(&put).unwrap()),
+            dec!(0.0000001)
+        );
+        assert_decimal_eq!(net.gamma, dec!(-0.276683056), dec!(0.000001));
+        assert_decimal_eq!(
+            net.vega,
+            -(vega(&call).unwrap() + vega(&put).unwrap()),
+            dec!(0.0000001)
+        );
+        assert!(net.is_theta_positive());
+        assert!(!net.is_delta_neutral(dec!(0.01)));
+        assert!(net.is_delta_neutral(dec!(0.2)));
+    }
+
+    #[test]
+    fn test_net_greeks_offsetting_legs() {
+        let collection = TestOptionCollection {
+            options: vec![
+                create_test_option(Side::Long, OptionStyle::Call, Positive::ONE),
+                create_test_option(Side::Short, OptionStyle::Call, Positive::ONE),
+            ],
+        };
+        let net = collection.net_greeks().unwrap();
+        assert_eq!(net.delta, Decimal::ZERO);
+        assert_eq!(net.gamma, Decimal::ZERO);
+        assert_eq!(net.theta, Decimal::ZERO);
+        assert_eq!(net.vega, Decimal::ZERO);
+        assert_eq!(net.rho, Decimal::ZERO);
+        assert!(net.is_delta_neutral(Decimal::ZERO));
+    }
+
     #[test]
     fn test_greeks_simple_validation() {
         let option = Options::new(
@@ -0,0 +1,235 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Pricing Intermediates
+//!
+//! Memoization of the quantities shared by the closed-form price and Greeks
+//! of an option: `√T`, the rate and dividend discount factors, `d1`, `d2`,
+//! `N(±d1)`, `N(±d2)` and `n(d1)`. Asking for the price, delta, gamma, theta,
+//! vega and rho of the same option computes them once.
+//!
+//! Entries are keyed by every input of the formulas — underlying price,
+//! strike, volatility, rate, dividend yield and time to expiry — so changing
+//! any field of an [`Options`], or the passage of time for date-based
+//! expirations, selects a new entry and stale values are never reused. The
+//! cache is thread local and keeps the last [`CACHE_CAPACITY`] entries.
+
+use crate::Options;
+use crate::error::greeks::GreeksError;
+use crate::greeks::utils::{big_n, d1, n};
+use positive::Positive;
+use rust_decimal::{Decimal, MathematicalOps};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// Number of options whose intermediates are kept per thread.
+const CACHE_CAPACITY: usize = 32;
+
+/// Rate used as drift in `d1` and `d2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DRate {
+    /// The cost of carry `r - q`, used by the Black-Scholes price.
+    CostOfCarry,
+    /// The risk-free rate `r`, used by the Greeks equations.
+    RiskFree,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Key {
+    underlying_price: Positive,
+    strike_price: Positive,
+    implied_volatility: Positive,
+    risk_free_rate: Decimal,
+    dividend_yield: Positive,
+    time_to_expiry: Positive,
+    d_rate: DRate,
+}
+
+/// Quantities shared by the closed-form price and Greeks of an option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Intermediates {
+    /// `√T`.
+    pub sqrt_time: Positive,
+    /// `e^(-rT)`.
+    pub rate_discount: Decimal,
+    /// `e^(-qT)`.
+    pub dividend_discount: Decimal,
+    /// `d1`.
+    pub d1: Decimal,
+    /// `d2`.
+    pub d2: Decimal,
+    /// `N(d1)`.
+    pub big_n_d1: Decimal,
+    /// `N(-d1)`.
+    pub big_n_minus_d1: Decimal,
+    /// `N(d2)`.
+    pub big_n_d2: Decimal,
+    /// `N(-d2)`.
+    pub big_n_minus_d2: Decimal,
+    /// `n(d1)`.
+    pub n_d1: Decimal,
+}
+
+thread_local! {
+    static CACHE: RefCell<VecDeque<(Key, Intermediates)>> =
+        RefCell::new(VecDeque::with_capacity(CACHE_CAPACITY));
+}
+
+/// Returns the intermediates of `option` at `time_to_expiry` years, computing
+/// them only if they are not cached yet.
+///
+/// # Errors
+///
+/// Returns the errors of [`d1`] for zero prices, volatilities or times.
+pub(crate) fn intermediates(
+    option: &Options,
+    time_to_expiry: Positive,
+    d_rate: DRate,
+) -> Result<Intermediates, GreeksError> {
+    let key = Key {
+        underlying_price: option.underlying_price,
+        strike_price: option.strike_price,
+        implied_volatility: option.implied_volatility,
+        risk_free_rate: option.risk_free_rate,
+        dividend_yield: option.dividend_yield,
+        time_to_expiry,
+        d_rate,
+    };
+    let cached = CACHE.with(|cache| {
+        cache
+            .borrow()
+            .iter()
+            .find(|(entry, _)| *entry == key)
+            .map(|(_, values)| *values)
+    });
+    if let Some(values) = cached {
+        return Ok(values);
+    }
+
+    let values = compute(&key)?;
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.len() == CACHE_CAPACITY {
+            cache.pop_front();
+        }
+        cache.push_back((key, values));
+    });
+    Ok(values)
+}
+
+fn compute(key: &Key) -> Result<Intermediates, GreeksError> {
+    let t = key.time_to_expiry;
+    let r = key.risk_free_rate;
+    let q = key.dividend_yield.to_dec();
+    let drift = match key.d_rate {
+        DRate::CostOfCarry => r - q,
+        DRate::RiskFree => r,
+    };
+    let d1 = d1(
+        key.underlying_price,
+        key.strike_price,
+        drift,
+        t,
+        key.implied_volatility,
+    )?;
+    let sqrt_time = t.sqrt();
+    let d2 = d1 - key.implied_volatility * sqrt_time;
+
+    Ok(Intermediates {
+        sqrt_time,
+        rate_discount: (-r * t).exp(),
+        dividend_discount: (-q * t).exp(),
+        d1,
+        d2,
+        big_n_d1: big_n(d1)?,
+        big_n_minus_d1: big_n(-d1)?,
+        big_n_d2: big_n(d2)?,
+        big_n_minus_d2: big_n(-d2)?,
+        n_d1: n(d1)?,
+    })
+}
+
+#[cfg(test)]
+fn cached_entries() -> usize {
+    CACHE.with(|cache| cache.borrow().len())
+}
+
+#[cfg(test)]
+mod tests_intermediates {
+    use super::*;
+    use crate::greeks::utils::d2;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use expiration_date::ExpirationDate;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn option() -> Options {
+        Options::new(
+            OptionType::European,
+            Side::Long,
+            "TEST".to_string(),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(60.0)),
+            pos_or_panic!(0.25),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            OptionStyle::Call,
+            pos_or_panic!(0.02),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_intermediates_match_direct_computation() {
+        let option = option();
+        let t = option.time_to_expiration().unwrap();
+        let values = intermediates(&option, t, DRate::RiskFree).unwrap();
+        let args = (
+            option.underlying_price,
+            option.strike_price,
+            option.risk_free_rate,
+            t,
+            option.implied_volatility,
+        );
+        assert_eq!(
+            values.d1,
+            d1(args.0, args.1, args.2, args.3, args.4).unwrap()
+        );
+        assert_eq!(
+            values.d2,
+            d2(args.0, args.1, args.2, args.3, args.4).unwrap()
+        );
+        assert_eq!(values.big_n_minus_d2, big_n(-values.d2).unwrap());
+        assert_eq!(values.rate_discount, (-dec!(0.05) * t).exp());
+
+        let carry = intermediates(&option, t, DRate::CostOfCarry).unwrap();
+        assert!(carry.d1 < values.d1);
+        assert_eq!(carry.dividend_discount, values.dividend_discount);
+    }
+
+    #[test]
+    fn test_intermediates_are_reused_and_invalidated() {
+        let mut option = option();
+        option.strike_price = pos_or_panic!(97.5);
+        let t = option.time_to_expiration().unwrap();
+        let entries = cached_entries();
+        let first = intermediates(&option, t, DRate::RiskFree).unwrap();
+        assert_eq!(cached_entries(), (entries + 1).min(CACHE_CAPACITY));
+        assert_eq!(intermediates(&option, t, DRate::RiskFree).unwrap(), first);
+        assert_eq!(cached_entries(), (entries + 1).min(CACHE_CAPACITY));
+
+        option.implied_volatility = pos_or_panic!(0.3);
+        let changed = intermediates(&option, t, DRate::RiskFree).unwrap();
+        assert_ne!(changed.d1, first.d1);
+
+        for i in 0..(2 * CACHE_CAPACITY) {
+            option.strike_price = Positive::new(50.0 + i as f64).unwrap();
+            intermediates(&option, t, DRate::RiskFree).unwrap();
+        }
+        assert_eq!(cached_entries(), CACHE_CAPACITY);
+    }
+}
@@ -48,7 +48,7 @@ use positive::pos_or_panic;
 //!     delta, gamma, rho, theta, vanna, vega, veta, vomma, charm, color
 //! };
 //! use optionstratlib::{ExpirationDate, Options};
-//! use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+//! use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 //! use positive::pos_or_panic;
 //! use positive::Positive;
 //!
@@ -66,6 +66,8 @@ use positive::pos_or_panic;
 //!             option_style: OptionStyle::Call,
 //!             dividend_yield: pos_or_panic!(0.01),
 //!             exotic_params: None,
+//!             settlement_type: SettlementType::Physical,
+//!             day_count: DayCount::Act365Fixed,
 //!         };
 //!
 //! // Calculate Greeks
@@ -184,13 +186,14 @@ use positive::pos_or_panic;
 //! ```
 
 mod equations;
+mod intermediates;
 pub mod numerical;
 mod utils;
 
 pub use equations::{
-    Greek, Greeks, GreeksSnapshot, charm, color, delta, gamma, rho, rho_d, theta, vanna, vega,
-    veta, vomma,
+    Greek, Greeks, GreeksSnapshot, NetGreeks, charm, color, delta, gamma, rho, rho_d, theta, vanna,
+    vega, veta, vomma,
 };
-pub(crate) use utils::calculate_d_values;
+pub(crate) use intermediates::{DRate, Intermediates, intermediates};
 pub use utils::calculate_delta_neutral_sizes;
 pub use utils::{big_n, d1, d2, n};
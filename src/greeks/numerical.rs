@@ -94,7 +94,7 @@ pub fn numerical_vega(option: &Options) -> Result<Decimal, GreeksError> {
 ///
 /// Theta measures the rate of decay of the option's value over time.
 pub fn numerical_theta(option: &Options) -> Result<Decimal, GreeksError> {
-    let t = option.expiration_date.get_years()?;
+    let t = option.time_to_expiration()?;
     if t < H {
         return Ok(Decimal::ZERO);
     }
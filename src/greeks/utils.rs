@@ -8,6 +8,7 @@ use crate::Options;
 use crate::constants::PI;
 use crate::error::decimal::DecimalError;
 use crate::error::greeks::{GreeksError, InputErrorKind, MathErrorKind};
+use crate::greeks::intermediates::{DRate, intermediates};
 use crate::model::decimal::f64_to_decimal;
 use crate::strategies::DELTA_THRESHOLD;
 use core::f64;
@@ -411,23 +412,10 @@ pub fn big_n(x: Decimal) -> Result<Decimal, DecimalError> {
 ///     - `d1_value`: The calculated d1 value.
 ///     - `d2_value`: The calculated d2 value.
 ///
+#[allow(dead_code)]
 pub(crate) fn calculate_d_values(option: &Options) -> Result<(Decimal, Decimal), GreeksError> {
-    let b = option.risk_free_rate - option.dividend_yield.to_dec();
-    let d1_value = d1(
-        option.underlying_price,
-        option.strike_price,
-        b,
-        option.expiration_date.get_years()?,
-        option.implied_volatility,
-    );
-    let d2_value = d2(
-        option.underlying_price,
-        option.strike_price,
-        b,
-        option.expiration_date.get_years()?,
-        option.implied_volatility,
-    );
-    Ok((d1_value?, d2_value?))
+    let values = intermediates(option, option.time_to_expiration()?, DRate::CostOfCarry)?;
+    Ok((values.d1, values.d2))
 }
 
 /// Calculates the optimal position sizes for two positions to achieve delta neutrality
@@ -548,7 +536,7 @@ mod tests_calculate_delta_neutral_sizes {
 #[cfg(test)]
 mod tests_calculate_d_values {
     use super::*;
-    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 
     use approx::assert_relative_eq;
     use positive::pos_or_panic;
@@ -569,6 +557,8 @@ mod tests_calculate_d_values {
             option_style: OptionStyle::Call,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
         let (d1_value, d2_value) = calculate_d_values(&option).unwrap();
 
@@ -2146,7 +2136,7 @@ mod tests_calculate_d_values_bis {
     use super::*;
     use crate::assert_decimal_eq;
     use crate::model::ExpirationDate;
-    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
     use positive::pos_or_panic;
     use rust_decimal_macros::dec;
 
@@ -2165,6 +2155,8 @@ mod tests_calculate_d_values_bis {
             option_style: OptionStyle::Call,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
         let (d1, d2) = calculate_d_values(&option).unwrap();
         assert_decimal_eq!(d1, dec!(0.1003), dec!(0.0001));
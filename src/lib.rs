@@ -282,6 +282,8 @@
 //! +option_style: OptionStyle
 //! +dividend_yield: Positive
 //! +exotic_params: Option~ExoticParams~
+//! +settlement_type: SettlementType
+//! +day_count: DayCount
 //! +calculate_price_black_scholes()
 //! +calculate_price_binomial()
 //! +time_to_expiration()
@@ -683,6 +685,10 @@
 //!
 //! - `plotly`: Enables interactive visualization using plotly.rs
 //! - `async`: Enables asynchronous I/O operations for OptionChain and OHLCV data
+//! - `parallel` (enabled by default): Runs curve and surface generation, Monte Carlo path generation, implied volatility solving and optimization sweeps on the rayon thread pool; disable default features for single-threaded builds
+//! - `arrow`: Exports option chains and simulated price paths as Apache Arrow record batches and Parquet files
+//! - `visualization`: Renders payoff diagrams with their T+0 curve, Greeks curves and volatility smiles to PNG and SVG files with plotters
+//! - `ibkr`: Imports option trades and open positions from Interactive Brokers Flex Query XML reports
 //!
 //! ### Building from Source
 //!
@@ -1196,6 +1202,13 @@ pub mod volatility;
 /// cross-expiration analysis and visualization.
 pub mod series;
 
+/// * `ohlc` - In-memory time series of underlying prices as OHLCV bars.
+///
+/// Stores the price history of an underlying as open, high, low, close and volume
+/// bars, with append and lookup APIs, resampling to longer intervals and gap
+/// handling. Feeds the historical volatility estimators and backtests.
+pub mod ohlc;
+
 /// * `prelude` - Convenient re-exports of commonly used types and traits.
 ///
 /// The prelude module provides a single import point for the most frequently used
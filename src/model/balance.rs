@@ -4,7 +4,11 @@
    Date: 22/9/25
 ******************************************************************************/
 
+use crate::model::currency::Currency;
+use crate::model::leg::SpotPosition;
+use crate::model::position::Position;
 use crate::model::types::UnderlyingAssetType;
+use crate::strategies::base::Strategy;
 use num_traits::ToPrimitive;
 use positive::Positive;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
@@ -191,13 +195,31 @@ pub struct MarginInfo {
 /// Represents a portfolio containing multiple option balances.
 ///
 /// This struct provides functionality to manage and analyze a collection
-/// of option positions across different exchanges.
+/// of option positions across different exchanges. Besides exchange balances,
+/// it holds positions and strategies on any number of underlyings together
+/// with the cash of the account; see the `portfolio` module for valuation,
+/// grouping, Greeks and margin of those holdings.
 #[derive(DebugPretty, DisplaySimple, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct Portfolio {
     /// Collection of option balances
     pub balances: Vec<Balance>,
     /// Name or identifier for the portfolio
     pub name: String,
+    /// Standalone option positions
+    #[serde(default)]
+    pub positions: Vec<Position>,
+    /// Multi-leg strategies
+    #[serde(default)]
+    pub strategies: Vec<Strategy>,
+    /// Shares of the underlyings, such as those delivered by exercise or assignment
+    #[serde(default)]
+    pub spot_positions: Vec<SpotPosition>,
+    /// Cash balance of the account, negative when borrowing
+    #[serde(default)]
+    pub cash: Decimal,
+    /// Currency of the cash and of every amount reported by the portfolio
+    #[serde(default)]
+    pub base_currency: Currency,
 }
 
 impl Portfolio {
@@ -214,6 +236,11 @@ impl Portfolio {
         Self {
             balances: Vec::new(),
             name,
+            positions: Vec::new(),
+            strategies: Vec::new(),
+            spot_positions: Vec::new(),
+            cash: Decimal::ZERO,
+            base_currency: Currency::default(),
         }
     }
 
@@ -346,9 +373,12 @@ impl Portfolio {
     ///
     /// # Returns
     ///
-    /// `true` if the portfolio has no balances
+    /// `true` if the portfolio has no balances, positions or strategies
     pub fn is_empty(&self) -> bool {
         self.balances.is_empty()
+            && self.positions.is_empty()
+            && self.strategies.is_empty()
+            && self.spot_positions.is_empty()
     }
 }
 
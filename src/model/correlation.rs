@@ -0,0 +1,536 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Correlation Matrix
+//!
+//! Correlations between the returns of several underlyings, shared by the
+//! multi-asset exotic options, the Monte Carlo simulations and the portfolio
+//! Value-at-Risk.
+//!
+//! A `CorrelationMatrix` is always a valid correlation matrix: square,
+//! symmetric, with a unit diagonal, entries between -1 and 1 and positive
+//! semi-definite. Estimates that break positive semi-definiteness, which is
+//! common when the pairs are estimated separately or over different windows,
+//! can be repaired into the nearest valid correlation matrix with
+//! [`CorrelationMatrix::repair`] (Higham, 2002).
+
+use crate::error::OptionsError;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Smallest eigenvalue accepted as positive semi-definite, to absorb rounding.
+const EIGENVALUE_TOLERANCE: f64 = 1e-10;
+
+/// Iterations of the nearest-correlation repair.
+const MAX_REPAIR_ITERATIONS: usize = 200;
+
+/// Decimal places kept by the nearest-correlation repair.
+const REPAIR_DECIMALS: u32 = 12;
+
+/// Validated correlation matrix of a set of named assets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CorrelationMatrix {
+    symbols: Vec<String>,
+    values: Vec<Vec<Decimal>>,
+}
+
+impl CorrelationMatrix {
+    /// Creates a correlation matrix of `symbols`, with `values[i][j]` the
+    /// correlation between `symbols[i]` and `symbols[j]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OptionsError::ValidationError` if the symbols are repeated,
+    /// the matrix is not square with one row per symbol, or it is not a
+    /// valid correlation matrix.
+    pub fn new(symbols: Vec<String>, values: Vec<Vec<Decimal>>) -> Result<Self, OptionsError> {
+        let matrix = CorrelationMatrix { symbols, values };
+        matrix.validate()?;
+        Ok(matrix)
+    }
+
+    /// Correlation matrix of uncorrelated assets.
+    pub fn identity(symbols: &[&str]) -> Self {
+        let size = symbols.len();
+        CorrelationMatrix {
+            symbols: symbols.iter().map(|symbol| symbol.to_string()).collect(),
+            values: (0..size)
+                .map(|row| {
+                    (0..size)
+                        .map(|column| {
+                            if row == column {
+                                Decimal::ONE
+                            } else {
+                                Decimal::ZERO
+                            }
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Correlation matrix of two assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OptionsError::ValidationError` if the symbols are equal or the
+    /// correlation is not between -1 and 1.
+    pub fn pair(first: &str, second: &str, correlation: Decimal) -> Result<Self, OptionsError> {
+        CorrelationMatrix::new(
+            vec![first.to_string(), second.to_string()],
+            vec![
+                vec![Decimal::ONE, correlation],
+                vec![correlation, Decimal::ONE],
+            ],
+        )
+    }
+
+    /// Two-asset matrix built without the checks, as deserialization would.
+    #[cfg(test)]
+    pub(crate) fn unchecked_pair(first: &str, second: &str, correlation: Decimal) -> Self {
+        CorrelationMatrix {
+            symbols: vec![first.to_string(), second.to_string()],
+            values: vec![
+                vec![Decimal::ONE, correlation],
+                vec![correlation, Decimal::ONE],
+            ],
+        }
+    }
+
+    /// Nearest valid correlation matrix to `values` in the Frobenius norm,
+    /// found by alternating projections (Higham, 2002). The input is made
+    /// symmetric by averaging it with its transpose.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OptionsError::ValidationError` if the symbols are repeated,
+    /// the matrix is not square with one row per symbol, or it has entries
+    /// that cannot be represented as floating point numbers.
+    pub fn repair(symbols: Vec<String>, values: Vec<Vec<Decimal>>) -> Result<Self, OptionsError> {
+        let matrix = CorrelationMatrix { symbols, values };
+        matrix.validate_shape()?;
+        let size = matrix.len();
+        let raw = matrix.to_f64()?;
+        let input: Vec<Vec<f64>> = (0..size)
+            .map(|row| {
+                (0..size)
+                    .map(|column| (raw[row][column] + raw[column][row]) / 2.0)
+                    .collect()
+            })
+            .collect();
+
+        let mut correction = vec![vec![0.0; size]; size];
+        let mut unit = input;
+        for _ in 0..MAX_REPAIR_ITERATIONS {
+            let shifted = subtract(&unit, &correction);
+            let projected = clip_eigenvalues(&shifted);
+            correction = subtract(&projected, &shifted);
+            let mut next = projected.clone();
+            for (index, row) in next.iter_mut().enumerate() {
+                row[index] = 1.0;
+            }
+            let change = frobenius(&subtract(&next, &unit));
+            unit = next;
+            if change < EIGENVALUE_TOLERANCE && frobenius(&subtract(&unit, &projected)) < 1e-8 {
+                break;
+            }
+        }
+
+        // Clipping once more and rescaling to a unit diagonal keeps the
+        // result positive semi-definite whatever the residual of the loop.
+        let projected = clip_eigenvalues(&unit);
+        let scale: Vec<f64> = (0..size)
+            .map(|index| projected[index][index].max(f64::MIN_POSITIVE).sqrt())
+            .collect();
+        let mut values = vec![vec![Decimal::ONE; size]; size];
+        for row in 0..size {
+            for column in 0..row {
+                let correlation =
+                    (projected[row][column] / (scale[row] * scale[column])).clamp(-1.0, 1.0);
+                let correlation = Decimal::from_f64(correlation)
+                    .unwrap_or(Decimal::ZERO)
+                    .round_dp(REPAIR_DECIMALS);
+                values[row][column] = correlation;
+                values[column][row] = correlation;
+            }
+        }
+        CorrelationMatrix::new(matrix.symbols, values)
+    }
+
+    /// Checks that the matrix is a valid correlation matrix. Matrices built
+    /// with the constructors always are; this is for deserialized ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OptionsError::ValidationError` describing the first problem
+    /// found.
+    pub fn validate(&self) -> Result<(), OptionsError> {
+        self.validate_shape()?;
+        for (row, values) in self.values.iter().enumerate() {
+            if values[row] != Decimal::ONE {
+                return Err(invalid(&format!(
+                    "correlation of {} with itself is {}, not 1",
+                    self.symbols[row], values[row]
+                )));
+            }
+            for (column, value) in values.iter().enumerate().take(row) {
+                if !(Decimal::NEGATIVE_ONE..=Decimal::ONE).contains(value) {
+                    return Err(invalid(&format!(
+                        "correlation of {} and {} is {value}, not between -1 and 1",
+                        self.symbols[row], self.symbols[column]
+                    )));
+                }
+                if self.values[column][row] != *value {
+                    return Err(invalid(&format!(
+                        "correlation of {} and {} is not symmetric",
+                        self.symbols[row], self.symbols[column]
+                    )));
+                }
+            }
+        }
+        let smallest = self.min_eigenvalue()?;
+        if smallest < -EIGENVALUE_TOLERANCE {
+            return Err(invalid(&format!(
+                "matrix is not positive semi-definite (smallest eigenvalue {smallest:.6})"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Symbols of the assets, in the order of the rows.
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+
+    /// Rows of the matrix.
+    pub fn values(&self) -> &[Vec<Decimal>] {
+        &self.values
+    }
+
+    /// Number of assets.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Whether the matrix has no assets.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Row of `symbol`, if it is in the matrix.
+    pub fn index_of(&self, symbol: &str) -> Option<usize> {
+        self.symbols
+            .iter()
+            .position(|candidate| candidate == symbol)
+    }
+
+    /// Correlation between two assets, if both are in the matrix.
+    pub fn get(&self, first: &str, second: &str) -> Option<Decimal> {
+        Some(self.values[self.index_of(first)?][self.index_of(second)?])
+    }
+
+    /// Correlation between the assets of two rows, if both exist.
+    pub fn at(&self, row: usize, column: usize) -> Option<Decimal> {
+        self.values.get(row)?.get(column).copied()
+    }
+
+    /// Correlation matrix of `symbols`, taken from this one. Symbols missing
+    /// from this matrix are uncorrelated with every other asset.
+    pub fn subset(&self, symbols: &[&str]) -> Self {
+        let rows: Vec<Option<usize>> = symbols.iter().map(|symbol| self.index_of(symbol)).collect();
+        let mut subset = CorrelationMatrix::identity(symbols);
+        for (row, first) in rows.iter().enumerate() {
+            for (column, second) in rows.iter().enumerate() {
+                if let (Some(first), Some(second)) = (first, second) {
+                    subset.values[row][column] = self.values[*first][*second];
+                }
+            }
+        }
+        subset
+    }
+
+    /// Lower triangular Cholesky factor `L` of the matrix, `L·Lᵀ = C`. Rows
+    /// of perfectly dependent assets get zeros past the diagonal pivot.
+    pub fn cholesky(&self) -> Vec<Vec<f64>> {
+        let matrix = self.to_f64().unwrap_or_default();
+        let size = matrix.len();
+        let mut lower = vec![vec![0.0; size]; size];
+        for row in 0..size {
+            for column in 0..=row {
+                let dot: f64 = (0..column)
+                    .map(|index| lower[row][index] * lower[column][index])
+                    .sum();
+                if row == column {
+                    lower[row][row] = (matrix[row][row] - dot).max(0.0).sqrt();
+                } else if lower[column][column] > EIGENVALUE_TOLERANCE {
+                    lower[row][column] = (matrix[row][column] - dot) / lower[column][column];
+                }
+            }
+        }
+        lower
+    }
+
+    /// Turns independent standard normal draws, one per asset, into draws
+    /// with this correlation.
+    pub fn correlate(&self, independent: &[f64]) -> Vec<f64> {
+        self.cholesky()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(independent)
+                    .map(|(weight, draw)| weight * draw)
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Smallest eigenvalue of the matrix.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OptionsError::ValidationError` if an entry cannot be
+    /// represented as a floating point number.
+    pub fn min_eigenvalue(&self) -> Result<f64, OptionsError> {
+        Ok(symmetric_eigen(&self.to_f64()?)
+            .0
+            .into_iter()
+            .fold(f64::INFINITY, f64::min))
+    }
+
+    fn validate_shape(&self) -> Result<(), OptionsError> {
+        if self.values.len() != self.symbols.len()
+            || self
+                .values
+                .iter()
+                .any(|row| row.len() != self.symbols.len())
+        {
+            return Err(invalid(&format!(
+                "matrix must be {size}x{size}, one row and column per symbol",
+                size = self.symbols.len()
+            )));
+        }
+        for (index, symbol) in self.symbols.iter().enumerate() {
+            if self.symbols[..index].contains(symbol) {
+                return Err(invalid(&format!("symbol {symbol} appears more than once")));
+            }
+        }
+        Ok(())
+    }
+
+    fn to_f64(&self) -> Result<Vec<Vec<f64>>, OptionsError> {
+        self.values
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|value| {
+                        value
+                            .to_f64()
+                            .ok_or_else(|| invalid(&format!("entry {value} is not representable")))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn invalid(reason: &str) -> OptionsError {
+    OptionsError::validation_error("correlation", reason)
+}
+
+fn subtract(left: &[Vec<f64>], right: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| left.iter().zip(right).map(|(l, r)| l - r).collect())
+        .collect()
+}
+
+fn frobenius(matrix: &[Vec<f64>]) -> f64 {
+    matrix
+        .iter()
+        .flatten()
+        .map(|value| value * value)
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Projection onto the positive semi-definite matrices: the matrix rebuilt
+/// with its negative eigenvalues set to zero.
+fn clip_eigenvalues(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let (eigenvalues, eigenvectors) = symmetric_eigen(matrix);
+    let size = matrix.len();
+    let mut result = vec![vec![0.0; size]; size];
+    for (index, eigenvalue) in eigenvalues.iter().enumerate() {
+        let eigenvalue = eigenvalue.max(0.0);
+        for row in 0..size {
+            for column in 0..size {
+                result[row][column] +=
+                    eigenvalue * eigenvectors[row][index] * eigenvectors[column][index];
+            }
+        }
+    }
+    result
+}
+
+/// Eigenvalues and eigenvectors (as columns) of a symmetric matrix, by
+/// cyclic Jacobi rotations.
+fn symmetric_eigen(matrix: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let size = matrix.len();
+    let mut a = matrix.to_vec();
+    let mut vectors: Vec<Vec<f64>> = (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|column| if row == column { 1.0 } else { 0.0 })
+                .collect()
+        })
+        .collect();
+    for _ in 0..100 {
+        let off_diagonal: f64 = (0..size)
+            .flat_map(|row| {
+                (0..size)
+                    .filter(move |column| *column != row)
+                    .map(move |c| (row, c))
+            })
+            .map(|(row, column)| a[row][column] * a[row][column])
+            .sum();
+        if off_diagonal < 1e-22 {
+            break;
+        }
+        for p in 0..size {
+            for q in p + 1..size {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (row_p, row_q) = (a[p].clone(), a[q].clone());
+                a[p] = row_p
+                    .iter()
+                    .zip(&row_q)
+                    .map(|(x, y)| c * x - s * y)
+                    .collect();
+                a[q] = row_p
+                    .iter()
+                    .zip(&row_q)
+                    .map(|(x, y)| s * x + c * y)
+                    .collect();
+                for row in vectors.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..size).map(|index| a[index][index]).collect(), vectors)
+}
+
+#[cfg(test)]
+mod tests_correlation {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn symbols(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn inconsistent() -> Vec<Vec<Decimal>> {
+        // A and B, and A and C, move together while B and C move apart.
+        vec![
+            vec![dec!(1), dec!(0.9), dec!(0.9)],
+            vec![dec!(0.9), dec!(1), dec!(-0.9)],
+            vec![dec!(0.9), dec!(-0.9), dec!(1)],
+        ]
+    }
+
+    #[test]
+    fn test_pair_and_lookup() {
+        let matrix = CorrelationMatrix::pair("AAA", "BBB", dec!(0.4)).unwrap();
+        assert_eq!(matrix.len(), 2);
+        assert_eq!(matrix.get("BBB", "AAA"), Some(dec!(0.4)));
+        assert_eq!(matrix.get("AAA", "AAA"), Some(Decimal::ONE));
+        assert_eq!(matrix.get("AAA", "CCC"), None);
+        assert_eq!(matrix.at(0, 1), Some(dec!(0.4)));
+        assert!(CorrelationMatrix::pair("AAA", "BBB", dec!(1.5)).is_err());
+        assert!(CorrelationMatrix::pair("AAA", "AAA", dec!(0.5)).is_err());
+    }
+
+    #[test]
+    fn test_invalid_matrices() {
+        let names = symbols(&["A", "B", "C"]);
+        assert!(CorrelationMatrix::new(names.clone(), inconsistent()).is_err());
+        let mut asymmetric = CorrelationMatrix::identity(&["A", "B", "C"]).values;
+        asymmetric[0][1] = dec!(0.2);
+        assert!(CorrelationMatrix::new(names.clone(), asymmetric).is_err());
+        let mut diagonal = CorrelationMatrix::identity(&["A", "B", "C"]).values;
+        diagonal[2][2] = dec!(0.9);
+        assert!(CorrelationMatrix::new(names.clone(), diagonal).is_err());
+        assert!(CorrelationMatrix::new(names, vec![vec![Decimal::ONE]]).is_err());
+    }
+
+    #[test]
+    fn test_repair_finds_nearby_valid_matrix() {
+        let names = symbols(&["A", "B", "C"]);
+        let repaired = CorrelationMatrix::repair(names.clone(), inconsistent()).unwrap();
+        assert!(repaired.validate().is_ok());
+        assert!(repaired.min_eigenvalue().unwrap() > -EIGENVALUE_TOLERANCE);
+        // The pairs shrink to ±0.5, where the matrix becomes singular.
+        let ab = repaired.get("A", "B").unwrap();
+        let bc = repaired.get("B", "C").unwrap();
+        assert!((ab - dec!(0.5)).abs() < dec!(0.000001));
+        assert!((bc + dec!(0.5)).abs() < dec!(0.000001));
+        assert_eq!(repaired.get("A", "C"), Some(ab));
+
+        // A valid matrix is left as it is.
+        let valid = CorrelationMatrix::pair("A", "B", dec!(0.3)).unwrap();
+        let unchanged =
+            CorrelationMatrix::repair(symbols(&["A", "B"]), valid.values.clone()).unwrap();
+        assert!((unchanged.get("A", "B").unwrap() - dec!(0.3)).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn test_cholesky_and_correlate() {
+        let matrix = CorrelationMatrix::new(
+            symbols(&["A", "B", "C"]),
+            vec![
+                vec![dec!(1), dec!(0.5), dec!(0.2)],
+                vec![dec!(0.5), dec!(1), dec!(0.3)],
+                vec![dec!(0.2), dec!(0.3), dec!(1)],
+            ],
+        )
+        .unwrap();
+        let lower = matrix.cholesky();
+        for row in 0..3 {
+            for column in 0..3 {
+                let product: f64 = (0..3).map(|k| lower[row][k] * lower[column][k]).sum();
+                let expected = matrix.at(row, column).unwrap().to_f64().unwrap();
+                assert!((product - expected).abs() < 1e-12);
+            }
+        }
+        assert_eq!(matrix.correlate(&[1.0, 0.0, 0.0])[1], 0.5);
+
+        let perfect = CorrelationMatrix::pair("A", "B", Decimal::ONE).unwrap();
+        assert_eq!(perfect.correlate(&[0.7, -3.0]), vec![0.7, 0.7]);
+    }
+
+    #[test]
+    fn test_subset() {
+        let matrix = CorrelationMatrix::pair("A", "B", dec!(0.6)).unwrap();
+        let subset = matrix.subset(&["B", "X", "A"]);
+        assert_eq!(subset.get("A", "B"), Some(dec!(0.6)));
+        assert_eq!(subset.get("A", "X"), Some(Decimal::ZERO));
+        assert!(subset.validate().is_ok());
+    }
+}
@@ -0,0 +1,162 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Currencies and FX Rates
+//!
+//! Positions are denominated in a [`Currency`], and a [`Portfolio`] reports
+//! its value, P&L and Greeks in its base currency. Amounts are converted with
+//! an [`FxRateProvider`]; [`FxRates`] is a static table of rates quoted
+//! against a single currency, from which every cross rate can be derived.
+//!
+//! [`Portfolio`]: crate::model::Portfolio
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use utoipa::ToSchema;
+
+/// ISO 4217 style currency code, such as `USD` or `EUR`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct Currency(String);
+
+impl Currency {
+    /// Creates a currency from its code, which is stored in upper case.
+    pub fn new(code: &str) -> Self {
+        Currency(code.trim().to_uppercase())
+    }
+
+    /// United States dollar, the default currency of positions.
+    pub fn usd() -> Self {
+        Currency::new("USD")
+    }
+
+    /// Code of the currency.
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::usd()
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Source of exchange rates between currencies.
+pub trait FxRateProvider {
+    /// Units of `to` worth one unit of `from`, or `None` if the rate is
+    /// unknown. The rate of a currency to itself is always one.
+    fn rate(&self, from: &Currency, to: &Currency) -> Option<Decimal>;
+
+    /// Converts `amount` from one currency to another, or returns `None` if
+    /// the rate is unknown.
+    fn convert(&self, amount: Decimal, from: &Currency, to: &Currency) -> Option<Decimal> {
+        self.rate(from, to).map(|rate| amount * rate)
+    }
+}
+
+/// Table of exchange rates quoted against a single currency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FxRates {
+    /// Currency in which every rate is quoted.
+    pub quote: Currency,
+    /// Units of the quote currency worth one unit of each currency.
+    pub rates: BTreeMap<Currency, Decimal>,
+}
+
+impl FxRates {
+    /// Creates an empty table quoted in `quote`.
+    pub fn new(quote: Currency) -> Self {
+        FxRates {
+            quote,
+            rates: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the number of units of the quote currency worth one unit of
+    /// `currency`. Non-positive rates are ignored.
+    pub fn set_rate(&mut self, currency: Currency, rate: Decimal) {
+        if rate > Decimal::ZERO {
+            self.rates.insert(currency, rate);
+        }
+    }
+
+    /// Builder-style variant of [`FxRates::set_rate`].
+    pub fn with_rate(mut self, currency: Currency, rate: Decimal) -> Self {
+        self.set_rate(currency, rate);
+        self
+    }
+
+    /// Value of one unit of `currency` in the quote currency.
+    fn quote_rate(&self, currency: &Currency) -> Option<Decimal> {
+        if *currency == self.quote {
+            Some(Decimal::ONE)
+        } else {
+            self.rates.get(currency).copied()
+        }
+    }
+}
+
+impl FxRateProvider for FxRates {
+    fn rate(&self, from: &Currency, to: &Currency) -> Option<Decimal> {
+        if from == to {
+            return Some(Decimal::ONE);
+        }
+        Some(self.quote_rate(from)? / self.quote_rate(to)?)
+    }
+}
+
+#[cfg(test)]
+mod tests_currency {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn rates() -> FxRates {
+        FxRates::new(Currency::usd())
+            .with_rate(Currency::new("eur"), dec!(1.1))
+            .with_rate(Currency::new("GBP"), dec!(1.25))
+    }
+
+    #[test]
+    fn test_currency_code() {
+        assert_eq!(Currency::new(" eur ").code(), "EUR");
+        assert_eq!(Currency::default(), Currency::usd());
+        assert_eq!(Currency::new("jpy").to_string(), "JPY");
+        assert_eq!(serde_json::to_string(&Currency::usd()).unwrap(), "\"USD\"");
+    }
+
+    #[test]
+    fn test_direct_and_cross_rates() {
+        let rates = rates();
+        let usd = Currency::usd();
+        let eur = Currency::new("EUR");
+        let gbp = Currency::new("GBP");
+
+        assert_eq!(rates.rate(&eur, &eur), Some(Decimal::ONE));
+        assert_eq!(rates.rate(&eur, &usd), Some(dec!(1.1)));
+        assert_eq!(rates.convert(dec!(110), &usd, &eur), Some(dec!(100)));
+        assert_eq!(
+            rates.convert(dec!(100), &gbp, &eur).unwrap().round_dp(4),
+            dec!(113.6364)
+        );
+        assert_eq!(rates.rate(&Currency::new("CHF"), &usd), None);
+    }
+
+    #[test]
+    fn test_non_positive_rates_ignored() {
+        let mut rates = FxRates::new(Currency::usd());
+        rates.set_rate(Currency::new("EUR"), Decimal::ZERO);
+        assert!(rates.rates.is_empty());
+    }
+}
@@ -5,9 +5,8 @@
 ******************************************************************************/
 use crate::error::decimal::DecimalError;
 use crate::geometrics::HasX;
+use crate::simulation::rng::standard_normal;
 use num_traits::{FromPrimitive, ToPrimitive};
-use rand::distr::Distribution;
-use rand_distr::Normal;
 use rust_decimal::{Decimal, MathematicalOps};
 use rust_decimal_macros::dec;
 
@@ -208,7 +207,8 @@ pub fn f64_to_decimal(value: f64) -> Result<Decimal, DecimalError> {
 ///
 /// # Returns
 ///
-/// A `Positive` value sampled from a standard normal distribution.
+/// A `Positive` value sampled from a standard normal distribution. Inside a
+/// [`with_seed`](crate::simulation::rng::with_seed) scope the draws are reproducible.
 ///
 /// # Examples
 ///
@@ -218,11 +218,7 @@ pub fn f64_to_decimal(value: f64) -> Result<Decimal, DecimalError> {
 /// let normal = decimal_normal_sample();
 /// ```
 pub fn decimal_normal_sample() -> Decimal {
-    let mut t_rng = rand::rng();
-    // SAFETY: Normal::new(0.0, 1.0) is always valid (mean=0, std=1 are valid parameters)
-    let normal =
-        Normal::new(0.0, 1.0).expect("standard normal distribution parameters are always valid");
-    Decimal::from_f64(normal.sample(&mut t_rng)).unwrap_or(Decimal::ZERO)
+    Decimal::from_f64(standard_normal()).unwrap_or(Decimal::ZERO)
 }
 
 impl HasX for Decimal {
@@ -347,6 +343,7 @@ mod tests_random_generation {
     use super::*;
     use approx::assert_relative_eq;
     use rand::distr::Distribution;
+    use rand_distr::Normal;
     use std::collections::HashMap;
 
     #[test]
@@ -0,0 +1,386 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Scheduled Market Events
+//!
+//! Earnings releases, ex-dividend dates and central bank meetings move
+//! options in ways a price process alone does not capture. An
+//! [`EventTimeline`] holds such events in time order, either for a single
+//! underlying or for the whole market, and answers the questions simulations
+//! ask about them: what happens between two dates, which event of a kind
+//! comes next, and, through [`EventTimeline::clock`], which events fire at
+//! each step of a simulation clock.
+//!
+//! The rule-based backtester and the P&L path simulator both consume a
+//! timeline, so rules such as "close before earnings" can be expressed with
+//! [`ExitRule::BeforeEvent`](crate::backtesting::ExitRule::BeforeEvent).
+
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// Kind of a scheduled market event.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, ToSchema,
+)]
+pub enum MarketEventKind {
+    /// Earnings release of a company.
+    Earnings,
+    /// Ex-dividend date of an underlying.
+    ExDividend,
+    /// Federal Open Market Committee rate decision.
+    Fomc,
+    /// Any other scheduled event.
+    Other,
+}
+
+impl fmt::Display for MarketEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarketEventKind::Earnings => write!(f, "Earnings"),
+            MarketEventKind::ExDividend => write!(f, "Ex-dividend"),
+            MarketEventKind::Fomc => write!(f, "FOMC"),
+            MarketEventKind::Other => write!(f, "Event"),
+        }
+    }
+}
+
+/// An event scheduled at a point in time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct MarketEvent {
+    /// Time of the event.
+    pub timestamp: DateTime<Utc>,
+    /// Underlying affected by the event, `None` for market-wide events.
+    pub symbol: Option<String>,
+    /// Kind of the event.
+    pub kind: MarketEventKind,
+    /// Free text description.
+    #[serde(default)]
+    pub description: String,
+    /// Dividend per share of an ex-dividend event.
+    #[serde(default)]
+    pub amount: Option<Positive>,
+}
+
+impl MarketEvent {
+    /// Creates a market-wide event.
+    pub fn new(kind: MarketEventKind, timestamp: DateTime<Utc>) -> Self {
+        MarketEvent {
+            timestamp,
+            symbol: None,
+            kind,
+            description: String::new(),
+            amount: None,
+        }
+    }
+
+    /// Earnings release of `symbol`.
+    pub fn earnings(symbol: &str, timestamp: DateTime<Utc>) -> Self {
+        Self::new(MarketEventKind::Earnings, timestamp).with_symbol(symbol)
+    }
+
+    /// Ex-dividend date of `symbol` for a dividend of `amount` per share.
+    pub fn ex_dividend(symbol: &str, timestamp: DateTime<Utc>, amount: Positive) -> Self {
+        let mut event = Self::new(MarketEventKind::ExDividend, timestamp).with_symbol(symbol);
+        event.amount = Some(amount);
+        event
+    }
+
+    /// FOMC rate decision, which affects every underlying.
+    pub fn fomc(timestamp: DateTime<Utc>) -> Self {
+        Self::new(MarketEventKind::Fomc, timestamp)
+    }
+
+    /// Restricts the event to `symbol`.
+    pub fn with_symbol(mut self, symbol: &str) -> Self {
+        self.symbol = Some(symbol.to_string());
+        self
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Whether the event affects `symbol`.
+    pub fn applies_to(&self, symbol: &str) -> bool {
+        self.symbol.as_deref().is_none_or(|own| own == symbol)
+    }
+}
+
+/// Market events in time order.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(from = "Vec<MarketEvent>", into = "Vec<MarketEvent>")]
+pub struct EventTimeline {
+    events: Vec<MarketEvent>,
+}
+
+impl From<Vec<MarketEvent>> for EventTimeline {
+    fn from(events: Vec<MarketEvent>) -> Self {
+        events
+            .into_iter()
+            .fold(EventTimeline::new(), EventTimeline::with_event)
+    }
+}
+
+impl From<EventTimeline> for Vec<MarketEvent> {
+    fn from(timeline: EventTimeline) -> Self {
+        timeline.events
+    }
+}
+
+impl EventTimeline {
+    /// Creates an empty timeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an event.
+    pub fn with_event(mut self, event: MarketEvent) -> Self {
+        self.insert(event);
+        self
+    }
+
+    /// Adds an event, after the events already scheduled at the same time.
+    pub fn insert(&mut self, event: MarketEvent) {
+        let index = self
+            .events
+            .partition_point(|known| known.timestamp <= event.timestamp);
+        self.events.insert(index, event);
+    }
+
+    /// Every event, oldest first.
+    pub fn events(&self) -> &[MarketEvent] {
+        &self.events
+    }
+
+    /// Number of events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether the timeline has no events.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Events affecting `symbol` after `from` and up to and including `to`.
+    pub fn between<'a>(
+        &'a self,
+        symbol: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> impl Iterator<Item = &'a MarketEvent> + 'a {
+        let start = self.events.partition_point(|event| event.timestamp <= from);
+        self.events[start..]
+            .iter()
+            .take_while(move |event| event.timestamp <= to)
+            .filter(move |event| event.applies_to(symbol))
+    }
+
+    /// First event of `kind` affecting `symbol` after `after`.
+    pub fn next_event(
+        &self,
+        symbol: &str,
+        kind: MarketEventKind,
+        after: DateTime<Utc>,
+    ) -> Option<&MarketEvent> {
+        let start = self
+            .events
+            .partition_point(|event| event.timestamp <= after);
+        self.events[start..]
+            .iter()
+            .find(|event| event.kind == kind && event.applies_to(symbol))
+    }
+
+    /// Whether an event of `kind` affects `symbol` after `from` and up to
+    /// and including `to`.
+    pub fn has_event_between(
+        &self,
+        symbol: &str,
+        kind: MarketEventKind,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> bool {
+        self.between(symbol, from, to)
+            .any(|event| event.kind == kind)
+    }
+
+    /// Number of events of `kind` affecting `symbol` after `from` and up
+    /// to and including `to`.
+    pub fn count_between(
+        &self,
+        symbol: &str,
+        kind: MarketEventKind,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> usize {
+        self.between(symbol, from, to)
+            .filter(|event| event.kind == kind)
+            .count()
+    }
+
+    /// Dividend schedule of `symbol`: ex-dates after `from` and up to and
+    /// including `to`, with the dividend per share.
+    pub fn dividends<'a>(
+        &'a self,
+        symbol: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> impl Iterator<Item = (DateTime<Utc>, Positive)> + 'a {
+        self.between(symbol, from, to)
+            .filter_map(|event| match event.kind {
+                MarketEventKind::ExDividend => Some((event.timestamp, event.amount?)),
+                _ => None,
+            })
+    }
+
+    /// Steps a simulation clock through `times`, which must be in increasing
+    /// order, yielding at every time the events affecting `symbol` since the
+    /// previous one. The first time only receives the events scheduled
+    /// exactly at it.
+    pub fn clock<'a, I>(&'a self, symbol: &'a str, times: I) -> EventClock<'a, I::IntoIter>
+    where
+        I: IntoIterator<Item = DateTime<Utc>>,
+    {
+        EventClock {
+            timeline: self,
+            symbol,
+            times: times.into_iter(),
+            previous: None,
+        }
+    }
+}
+
+/// One step of an [`EventClock`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockTick<'a> {
+    /// Time of the step.
+    pub timestamp: DateTime<Utc>,
+    /// Events since the previous step, oldest first.
+    pub events: Vec<&'a MarketEvent>,
+}
+
+impl ClockTick<'_> {
+    /// Whether an event of `kind` fires at this step.
+    pub fn has(&self, kind: MarketEventKind) -> bool {
+        self.events.iter().any(|event| event.kind == kind)
+    }
+}
+
+/// Iterator over the steps of a simulation clock, created by
+/// [`EventTimeline::clock`].
+#[derive(Debug, Clone)]
+pub struct EventClock<'a, I> {
+    timeline: &'a EventTimeline,
+    symbol: &'a str,
+    times: I,
+    previous: Option<DateTime<Utc>>,
+}
+
+impl<'a, I: Iterator<Item = DateTime<Utc>>> Iterator for EventClock<'a, I> {
+    type Item = ClockTick<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let timestamp = self.times.next()?;
+        let from = self
+            .previous
+            .unwrap_or(timestamp - chrono::Duration::nanoseconds(1));
+        self.previous = Some(timestamp);
+        Some(ClockTick {
+            timestamp,
+            events: self
+                .timeline
+                .between(self.symbol, from, timestamp)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests_events {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn day(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2030, 5, day, 20, 0, 0).unwrap()
+    }
+
+    fn timeline() -> EventTimeline {
+        EventTimeline::new()
+            .with_event(MarketEvent::fomc(day(15)).with_description("Rate decision"))
+            .with_event(MarketEvent::earnings("XYZ", day(10)))
+            .with_event(MarketEvent::earnings("ABC", day(12)))
+            .with_event(MarketEvent::ex_dividend("XYZ", day(20), Positive::ONE))
+    }
+
+    #[test]
+    fn test_queries_filter_by_symbol_and_time() {
+        let timeline = timeline();
+        assert_eq!(timeline.len(), 4);
+        assert_eq!(timeline.events()[0].timestamp, day(10));
+
+        let kinds: Vec<MarketEventKind> = timeline
+            .between("XYZ", day(1), day(31))
+            .map(|event| event.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MarketEventKind::Earnings,
+                MarketEventKind::Fomc,
+                MarketEventKind::ExDividend
+            ]
+        );
+        // The lower bound is excluded and the upper bound included.
+        assert_eq!(timeline.between("XYZ", day(10), day(15)).count(), 1);
+
+        let next = timeline
+            .next_event("ABC", MarketEventKind::Earnings, day(1))
+            .unwrap();
+        assert_eq!(next.timestamp, day(12));
+        assert!(
+            timeline
+                .next_event("XYZ", MarketEventKind::Earnings, day(10))
+                .is_none()
+        );
+        assert!(timeline.has_event_between("ABC", MarketEventKind::Fomc, day(14), day(16)));
+        assert!(!timeline.has_event_between("ABC", MarketEventKind::ExDividend, day(1), day(31)));
+        assert_eq!(
+            timeline.count_between("XYZ", MarketEventKind::Earnings, day(1), day(31)),
+            1
+        );
+        let dividends: Vec<_> = timeline.dividends("XYZ", day(1), day(31)).collect();
+        assert_eq!(dividends, vec![(day(20), Positive::ONE)]);
+        assert_eq!(timeline.dividends("ABC", day(1), day(31)).count(), 0);
+
+        let json = serde_json::to_string(&timeline).unwrap();
+        let back: EventTimeline = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, timeline);
+    }
+
+    #[test]
+    fn test_clock_delivers_each_event_once() {
+        let timeline = timeline();
+        let times = (0..7).map(|week| day(3) + Duration::days(week * 4));
+        let ticks: Vec<ClockTick> = timeline.clock("XYZ", times).collect();
+
+        assert_eq!(ticks.len(), 7);
+        assert!(ticks[0].events.is_empty());
+        // Day 11 follows the earnings of day 10.
+        assert!(ticks[2].has(MarketEventKind::Earnings));
+        assert!(ticks[3].has(MarketEventKind::Fomc));
+        assert!(ticks[5].has(MarketEventKind::ExDividend));
+        let delivered: usize = ticks.iter().map(|tick| tick.events.len()).sum();
+        assert_eq!(delivered, 3);
+
+        let at_event: Vec<ClockTick> = timeline.clock("XYZ", [day(10)]).collect();
+        assert_eq!(at_event[0].events.len(), 1);
+    }
+}
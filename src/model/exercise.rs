@@ -0,0 +1,535 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Exercise and Assignment
+//!
+//! Settles option legs that are exercised, assigned or reach expiration,
+//! following the [`SettlementType`] of each option:
+//!
+//! * **Cash settlement** pays the intrinsic value. The leg is closed at that
+//!   price and realizes its P&L like any other close, including its closing
+//!   fee.
+//! * **Physical settlement** delivers the underlying at the strike. The premium
+//!   and the fees of the leg are carried into the price of the shares, so a
+//!   call exercised or a put assigned opens shares whose cost basis is the
+//!   strike adjusted by the premium, and the option leg itself realizes nothing.
+//!
+//! Legs that expire out of the money are closed at zero without a closing fee.
+//!
+//! A [`Portfolio`] applies the resulting cash to its balance and books the
+//! shares in its spot positions, closing existing shares on the opposite side
+//! first. The premium is assumed to have been settled in cash when the leg
+//! was opened.
+
+use crate::error::position::PositionError;
+use crate::model::Portfolio;
+use crate::model::leg::SpotPosition;
+use crate::model::lifecycle::{Fill, FillKind, PositionStatus};
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, SettlementType, Side};
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+/// What happened to an option leg at settlement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum SettlementAction {
+    /// The leg expired out of the money.
+    Expired,
+    /// The long leg was exercised.
+    Exercised,
+    /// The short leg was assigned.
+    Assigned,
+}
+
+/// Result of settling contracts of an option leg.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SettlementEvent {
+    /// Symbol of the underlying.
+    pub underlying_symbol: String,
+    /// Style of the option.
+    pub option_style: OptionStyle,
+    /// Strike price of the option.
+    pub strike_price: Positive,
+    /// What happened to the leg.
+    pub action: SettlementAction,
+    /// How the leg was settled.
+    pub settlement_type: SettlementType,
+    /// Number of contracts settled.
+    pub quantity: Positive,
+    /// Price of the underlying used for the settlement.
+    pub underlying_price: Positive,
+    /// Cash received (positive) or paid (negative), net of fees.
+    pub cash_flow: Decimal,
+    /// Fees charged by the settlement.
+    pub fees: Positive,
+    /// Shares bought (long) or sold (short) on physical delivery, at a price
+    /// adjusted by the premium and the fees of the leg.
+    pub shares: Option<SpotPosition>,
+    /// P&L realized by the settlement.
+    pub realized_pnl: Decimal,
+}
+
+impl Position {
+    /// Settles every open contract at expiration: in-the-money contracts are
+    /// exercised or assigned and the rest expire worthless.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the position is closed.
+    pub fn settle_expiration(
+        &mut self,
+        underlying_price: Positive,
+        date: DateTime<Utc>,
+    ) -> Result<SettlementEvent, PositionError> {
+        let quantity = self.option.quantity;
+        if self.intrinsic_value_at(underlying_price) > Decimal::ZERO {
+            return self.exercise(quantity, underlying_price, date);
+        }
+        self.ensure_settleable(quantity)?;
+
+        let realized = match self.option.side {
+            Side::Long => -(self.premium.to_dec() + self.open_fee.to_dec()),
+            Side::Short => self.premium.to_dec() - self.open_fee.to_dec(),
+        } * quantity.to_dec();
+        self.close_contracts(quantity, Positive::ZERO, Positive::ZERO, realized, date);
+        Ok(self.event(
+            SettlementAction::Expired,
+            quantity,
+            underlying_price,
+            Decimal::ZERO,
+            Positive::ZERO,
+            None,
+            realized,
+        ))
+    }
+
+    /// Exercises (long legs) or assigns (short legs) `quantity` contracts at
+    /// `underlying_price`, settling them in cash or shares.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the position is closed, or `quantity` is
+    /// zero or larger than the open quantity.
+    pub fn exercise(
+        &mut self,
+        quantity: Positive,
+        underlying_price: Positive,
+        date: DateTime<Utc>,
+    ) -> Result<SettlementEvent, PositionError> {
+        self.ensure_settleable(quantity)?;
+        let action = match self.option.side {
+            Side::Long => SettlementAction::Exercised,
+            Side::Short => SettlementAction::Assigned,
+        };
+        let fees = self.close_fee * quantity;
+
+        match self.option.settlement_type {
+            SettlementType::Cash => {
+                let intrinsic = self.intrinsic_value_at(underlying_price);
+                let amount = intrinsic * quantity.to_dec();
+                let cash_flow = match self.option.side {
+                    Side::Long => amount,
+                    Side::Short => -amount,
+                } - fees.to_dec();
+                let realized = self.close_partial(
+                    quantity,
+                    Positive::new_decimal(intrinsic).unwrap_or(Positive::ZERO),
+                    date,
+                )?;
+                Ok(self.event(
+                    action,
+                    quantity,
+                    underlying_price,
+                    cash_flow,
+                    fees,
+                    None,
+                    realized,
+                ))
+            }
+            SettlementType::Physical => {
+                // Long calls and short puts buy the underlying at the strike,
+                // long puts and short calls sell it.
+                let buys = matches!(
+                    (self.option.option_style, self.option.side),
+                    (OptionStyle::Call, Side::Long) | (OptionStyle::Put, Side::Short)
+                );
+                let strike = self.option.strike_price.to_dec();
+                let premium = match self.option.side {
+                    Side::Long => self.premium.to_dec(),
+                    Side::Short => -self.premium.to_dec(),
+                };
+                let costs = self.open_fee.to_dec() + self.close_fee.to_dec();
+                let (side, price, cash_flow) = if buys {
+                    let price = strike + premium + costs;
+                    let cash_flow = -(strike + self.close_fee.to_dec()) * quantity.to_dec();
+                    (Side::Long, price, cash_flow)
+                } else {
+                    let price = strike - premium - costs;
+                    let cash_flow = (strike - self.close_fee.to_dec()) * quantity.to_dec();
+                    (Side::Short, price, cash_flow)
+                };
+                let shares = SpotPosition::new(
+                    self.option.underlying_symbol.clone(),
+                    quantity,
+                    Positive::new_decimal(price).unwrap_or(Positive::ZERO),
+                    side,
+                    date,
+                    Positive::ZERO,
+                    Positive::ZERO,
+                );
+                self.close_contracts(
+                    quantity,
+                    self.option.strike_price,
+                    self.close_fee,
+                    Decimal::ZERO,
+                    date,
+                );
+                Ok(self.event(
+                    action,
+                    quantity,
+                    underlying_price,
+                    cash_flow,
+                    fees,
+                    Some(shares),
+                    Decimal::ZERO,
+                ))
+            }
+        }
+    }
+
+    /// Intrinsic value per unit of the option at `underlying_price`.
+    fn intrinsic_value_at(&self, underlying_price: Positive) -> Decimal {
+        let spot = underlying_price.to_dec();
+        let strike = self.option.strike_price.to_dec();
+        match self.option.option_style {
+            OptionStyle::Call => (spot - strike).max(Decimal::ZERO),
+            OptionStyle::Put => (strike - spot).max(Decimal::ZERO),
+        }
+    }
+
+    fn ensure_settleable(&self, quantity: Positive) -> Result<(), PositionError> {
+        if !self.is_open() {
+            return Err(PositionError::invalid_position(
+                "Cannot settle contracts of a closed position",
+            ));
+        }
+        if quantity == Positive::ZERO || quantity > self.option.quantity {
+            return Err(PositionError::invalid_position_size(
+                quantity.to_f64(),
+                &format!(
+                    "Quantity to settle must be between zero and the open quantity {}",
+                    self.option.quantity
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn close_contracts(
+        &mut self,
+        quantity: Positive,
+        price: Positive,
+        fee: Positive,
+        realized: Decimal,
+        date: DateTime<Utc>,
+    ) {
+        self.realized_pnl += realized;
+        self.option.quantity = self.option.quantity - quantity;
+        if self.option.quantity == Positive::ZERO {
+            self.status = PositionStatus::Closed;
+        }
+        self.fills.push(Fill::new(
+            self.option.side,
+            FillKind::Close,
+            quantity,
+            price,
+            fee,
+            date,
+        ));
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn event(
+        &self,
+        action: SettlementAction,
+        quantity: Positive,
+        underlying_price: Positive,
+        cash_flow: Decimal,
+        fees: Positive,
+        shares: Option<SpotPosition>,
+        realized_pnl: Decimal,
+    ) -> SettlementEvent {
+        SettlementEvent {
+            underlying_symbol: self.option.underlying_symbol.clone(),
+            option_style: self.option.option_style,
+            strike_price: self.option.strike_price,
+            action,
+            settlement_type: self.option.settlement_type,
+            quantity,
+            underlying_price,
+            cash_flow,
+            fees,
+            shares,
+            realized_pnl,
+        }
+    }
+}
+
+impl Portfolio {
+    /// Settles every open position that has reached expiration, standalone or
+    /// strategy leg, at the settlement price of its underlying.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError`, before settling anything, if
+    /// `settlement_prices` lacks the price of an expiring underlying.
+    pub fn settle_expirations(
+        &mut self,
+        settlement_prices: &BTreeMap<String, Positive>,
+        date: DateTime<Utc>,
+    ) -> Result<Vec<SettlementEvent>, PositionError> {
+        let mut expiring = Vec::new();
+        for position in self
+            .positions
+            .iter_mut()
+            .chain(self.strategies.iter_mut().flat_map(|s| s.legs.iter_mut()))
+            .filter(|position| position.is_open())
+        {
+            let days = position
+                .option
+                .expiration_date
+                .get_days()
+                .map_err(|e| PositionError::invalid_position(&e.to_string()))?;
+            if days == Positive::ZERO {
+                let symbol = &position.option.underlying_symbol;
+                let price = settlement_prices.get(symbol).ok_or_else(|| {
+                    PositionError::invalid_position(&format!("No settlement price for {symbol}"))
+                })?;
+                expiring.push((position, *price));
+            }
+        }
+
+        let mut events = expiring
+            .into_iter()
+            .map(|(position, price)| position.settle_expiration(price, date))
+            .collect::<Result<Vec<_>, _>>()?;
+        for event in &mut events {
+            self.book_settlement(event);
+        }
+        Ok(events)
+    }
+
+    /// Exercises or assigns `quantity` contracts of the standalone position at
+    /// `index`, booking the resulting cash and shares.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if there is no position at `index` or it
+    /// cannot settle `quantity` contracts.
+    pub fn exercise_position(
+        &mut self,
+        index: usize,
+        quantity: Positive,
+        underlying_price: Positive,
+        date: DateTime<Utc>,
+    ) -> Result<SettlementEvent, PositionError> {
+        let position = self
+            .positions
+            .get_mut(index)
+            .ok_or_else(|| PositionError::invalid_position(&format!("No position at {index}")))?;
+        let mut event = position.exercise(quantity, underlying_price, date)?;
+        self.book_settlement(&mut event);
+        Ok(event)
+    }
+
+    /// Applies the cash of a settlement and books its shares, adding the P&L
+    /// of the shares closed to the event.
+    fn book_settlement(&mut self, event: &mut SettlementEvent) {
+        self.cash += event.cash_flow;
+        if let Some(shares) = &event.shares {
+            event.realized_pnl += self.book_shares(shares.clone());
+        }
+    }
+
+    /// Books shares in the spot positions: existing shares on the opposite
+    /// side are closed first, and the rest is merged into the position on the
+    /// same side at the average price. Returns the P&L of the shares closed.
+    fn book_shares(&mut self, mut shares: SpotPosition) -> Decimal {
+        let mut realized = Decimal::ZERO;
+        for existing in self
+            .spot_positions
+            .iter_mut()
+            .filter(|spot| spot.symbol == shares.symbol && spot.side != shares.side)
+        {
+            let closed = existing.quantity.min(shares.quantity);
+            let gain = shares.cost_basis.to_dec() - existing.cost_basis.to_dec();
+            realized += match existing.side {
+                Side::Long => gain,
+                Side::Short => -gain,
+            } * closed.to_dec();
+            existing.quantity = existing.quantity - closed;
+            shares.quantity = shares.quantity - closed;
+            if shares.quantity == Positive::ZERO {
+                break;
+            }
+        }
+        self.spot_positions
+            .retain(|spot| spot.quantity > Positive::ZERO);
+
+        if shares.quantity > Positive::ZERO {
+            match self
+                .spot_positions
+                .iter_mut()
+                .find(|spot| spot.symbol == shares.symbol && spot.side == shares.side)
+            {
+                Some(existing) => {
+                    let quantity = existing.quantity + shares.quantity;
+                    existing.cost_basis =
+                        (existing.initial_value() + shares.initial_value()) / quantity;
+                    existing.quantity = quantity;
+                }
+                None => self.spot_positions.push(shares),
+            }
+        }
+        realized
+    }
+}
+
+#[cfg(test)]
+mod tests_exercise {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::option::Options;
+    use crate::model::types::OptionType;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn position(style: OptionStyle, side: Side, settlement_type: SettlementType) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            "XYZ".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(Positive::ZERO),
+            pos_or_panic!(0.2),
+            Positive::HUNDRED,
+            Positive::HUNDRED,
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        )
+        .with_settlement_type(settlement_type);
+        Position::new(
+            option,
+            pos_or_panic!(3.0),
+            Utc::now(),
+            pos_or_panic!(0.01),
+            pos_or_panic!(0.02),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_expired_worthless() {
+        let mut short_put = position(OptionStyle::Put, Side::Short, SettlementType::Physical);
+        let event = short_put
+            .settle_expiration(pos_or_panic!(105.0), Utc::now())
+            .unwrap();
+        assert_eq!(event.action, SettlementAction::Expired);
+        assert_eq!(event.cash_flow, Decimal::ZERO);
+        assert_eq!(event.realized_pnl, dec!(299));
+        assert_eq!(short_put.realized_pnl, dec!(299));
+        assert!(!short_put.is_open());
+        assert!(event.shares.is_none());
+    }
+
+    #[test]
+    fn test_cash_settlement() {
+        let mut long_call = position(OptionStyle::Call, Side::Long, SettlementType::Cash);
+        let event = long_call
+            .settle_expiration(pos_or_panic!(110.0), Utc::now())
+            .unwrap();
+        assert_eq!(event.action, SettlementAction::Exercised);
+        assert_eq!(event.cash_flow, dec!(998));
+        assert_eq!(event.fees, Positive::TWO);
+        // (10 - 3 - 0.01 - 0.02) per unit.
+        assert_eq!(event.realized_pnl, dec!(697));
+        assert!(!long_call.is_open());
+    }
+
+    #[test]
+    fn test_physical_delivery_cost_basis() {
+        let mut short_put = position(OptionStyle::Put, Side::Short, SettlementType::Physical);
+        let event = short_put
+            .exercise(pos_or_panic!(40.0), pos_or_panic!(95.0), Utc::now())
+            .unwrap();
+        assert_eq!(event.action, SettlementAction::Assigned);
+        assert_eq!(event.cash_flow, dec!(-4000.8));
+        assert_eq!(event.realized_pnl, Decimal::ZERO);
+        let shares = event.shares.unwrap();
+        assert_eq!(shares.side, Side::Long);
+        assert_eq!(shares.quantity, pos_or_panic!(40.0));
+        assert_eq!(shares.cost_basis, pos_or_panic!(97.03));
+        assert_eq!(short_put.option.quantity, pos_or_panic!(60.0));
+        assert!(short_put.is_open());
+
+        let mut long_put = position(OptionStyle::Put, Side::Long, SettlementType::Physical);
+        let shares = long_put
+            .exercise(Positive::HUNDRED, pos_or_panic!(95.0), Utc::now())
+            .unwrap()
+            .shares
+            .unwrap();
+        assert_eq!(shares.side, Side::Short);
+        assert_eq!(shares.cost_basis, pos_or_panic!(96.97));
+    }
+
+    #[test]
+    fn test_portfolio_settles_into_shares() {
+        let mut portfolio = Portfolio::new("Wheel".to_string());
+        portfolio.deposit(pos_or_panic!(20000.0));
+        portfolio.add_position(position(
+            OptionStyle::Put,
+            Side::Short,
+            SettlementType::Physical,
+        ));
+        let prices = BTreeMap::from([("XYZ".to_string(), pos_or_panic!(95.0))]);
+        let events = portfolio.settle_expirations(&prices, Utc::now()).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(portfolio.cash, dec!(9998));
+        assert_eq!(portfolio.spot_positions.len(), 1);
+        assert_eq!(portfolio.spot_positions[0].cost_basis, pos_or_panic!(97.03));
+        assert!(portfolio.open_positions().is_empty());
+
+        // Calls assigned against the shares close them and realize their P&L.
+        let mut call = position(OptionStyle::Call, Side::Short, SettlementType::Physical);
+        call.option.expiration_date = ExpirationDate::Days(pos_or_panic!(10.0));
+        portfolio.add_position(call);
+        let event = portfolio
+            .exercise_position(1, Positive::HUNDRED, pos_or_panic!(104.0), Utc::now())
+            .unwrap();
+        assert_eq!(event.action, SettlementAction::Assigned);
+        // Sold at 100 + 3 - 0.03 = 102.97 against a basis of 97.03.
+        assert_eq!(event.realized_pnl, dec!(594));
+        assert!(portfolio.spot_positions.is_empty());
+        assert_eq!(portfolio.cash, dec!(19996));
+
+        let missing = BTreeMap::new();
+        portfolio.add_position(position(
+            OptionStyle::Call,
+            Side::Long,
+            SettlementType::Cash,
+        ));
+        assert!(portfolio.settle_expirations(&missing, Utc::now()).is_err());
+        assert!(portfolio.positions[2].is_open());
+    }
+}
@@ -0,0 +1,580 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # FIX Execution Reports
+//!
+//! Ingests FIX 4.4 execution reports (`35=8`) for listed options, as sent by
+//! brokers on a drop copy session, and applies them to positions so the
+//! portfolio can be reconciled against the broker.
+//!
+//! An [`ExecutionReportAdapter`] turns a raw message into an
+//! [`ExecutionReport`]; [`Fix44Adapter`] is the reference implementation for
+//! tag=value messages delimited by SOH or, as in logs, by `|`. Messages other
+//! than trades are skipped, and trade corrections and busts are rejected
+//! since they require amending fills already booked.
+//!
+//! The option contract is read from the instrument tags `201` (PutOrCall),
+//! `202` (StrikePrice) and `541` (MaturityDate) or `200` (MaturityMonthYear
+//! with a day). Without them, `55` (Symbol) is parsed as an OCC option symbol
+//! such as `AAPL  261218C00150000`.
+//!
+//! A report is applied with [`Position::apply_execution`] or
+//! [`Portfolio::apply_execution`]. Executions in the direction of a position
+//! add contracts to it, executions in the opposite direction close them, and
+//! executions matching no open position of a portfolio open a new one. The
+//! commission of the report, per contract (`13=1`) or for the whole execution
+//! (`13=3`, the default), becomes the fee of the fill.
+//!
+//! ```rust
+//! use optionstratlib::model::fix::{ExecutionReportAdapter, Fix44Adapter};
+//! use positive::pos_or_panic;
+//!
+//! let message = "8=FIX.4.4|9=0|35=8|37=O1|17=E1|150=F|39=2|55=SPY|167=OPT|201=1|\
+//!                202=450|541=20261218|54=1|32=2|31=7.5|12=1.3|13=3|60=20261001-14:30:00|";
+//! let report = Fix44Adapter.execution_report(message).unwrap().unwrap();
+//! let position = report.to_position(pos_or_panic!(448.0), pos_or_panic!(0.18)).unwrap();
+//!
+//! assert_eq!(position.option.quantity, pos_or_panic!(2.0));
+//! assert_eq!(position.premium, pos_or_panic!(7.5));
+//! assert_eq!(position.open_fee, pos_or_panic!(0.65));
+//! ```
+
+use crate::ExpirationDate;
+use crate::error::PositionError;
+use crate::model::Portfolio;
+use crate::model::currency::Currency;
+use crate::model::lifecycle::FillKind;
+use crate::model::occ::OccSymbol;
+use crate::model::option::Options;
+use crate::model::position::Position;
+use crate::model::position_builder::{FeeBasis, PositionBuilder};
+use crate::model::types::{Action, OptionStyle, OptionType, Side};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Field delimiter of FIX messages.
+pub const SOH: char = '\u{1}';
+
+/// Time of day, in UTC, given to expiration dates read from a report.
+const EXPIRATION_TIME: (u32, u32) = (18, 30);
+
+/// A FIX message as an ordered list of tag=value fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixMessage {
+    fields: Vec<(u32, String)>,
+}
+
+impl FixMessage {
+    /// Parses a tag=value message delimited by SOH or `|`.
+    ///
+    /// When the message carries a checksum (tag `10`), it is verified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for a field without `=`, a non-numeric tag or a
+    /// checksum that does not match the message.
+    pub fn parse(raw: &str) -> Result<Self, PositionError> {
+        let normalized = raw.replace('|', &SOH.to_string());
+        let fields = normalized
+            .split(SOH)
+            .filter(|field| !field.trim().is_empty())
+            .map(|field| {
+                let (tag, value) = field.split_once('=').ok_or_else(|| {
+                    PositionError::invalid_position(&format!("FIX field '{field}' has no value"))
+                })?;
+                let tag = tag.trim().parse().map_err(|_| {
+                    PositionError::invalid_position(&format!("FIX tag '{tag}' is not a number"))
+                })?;
+                Ok((tag, value.to_string()))
+            })
+            .collect::<Result<Vec<_>, PositionError>>()?;
+        let message = FixMessage { fields };
+
+        if let Some(expected) = message.get(10) {
+            let trailer = format!("{SOH}10=");
+            if let Some(end) = normalized.rfind(&trailer) {
+                let sum = normalized.as_bytes()[..=end]
+                    .iter()
+                    .fold(0u32, |sum, byte| sum + u32::from(*byte))
+                    % 256;
+                if expected.parse::<u32>().ok() != Some(sum) {
+                    return Err(PositionError::invalid_position(&format!(
+                        "FIX checksum {expected} does not match the message checksum {sum:03}"
+                    )));
+                }
+            }
+        }
+        Ok(message)
+    }
+
+    /// Value of the first field with `tag`.
+    pub fn get(&self, tag: u32) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == tag)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Message type, tag `35`.
+    pub fn msg_type(&self) -> Option<&str> {
+        self.get(35)
+    }
+
+    fn required(&self, tag: u32, name: &str) -> Result<&str, PositionError> {
+        self.get(tag).ok_or_else(|| {
+            PositionError::invalid_position(&format!("FIX tag {tag} ({name}) is missing"))
+        })
+    }
+
+    fn positive(&self, tag: u32, name: &str) -> Result<Positive, PositionError> {
+        let value = self.required(tag, name)?;
+        Decimal::from_str(value)
+            .ok()
+            .and_then(|value| Positive::new_decimal(value).ok())
+            .ok_or_else(|| {
+                PositionError::invalid_position(&format!(
+                    "FIX tag {tag} ({name}) has an invalid value '{value}'"
+                ))
+            })
+    }
+}
+
+/// A fill of an option order reported by a broker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionReport {
+    /// Execution identifier, tag `17`.
+    pub exec_id: String,
+    /// Broker order identifier, tag `37`.
+    pub order_id: String,
+    /// Symbol of the underlying.
+    pub underlying_symbol: String,
+    /// Call or put.
+    pub option_style: OptionStyle,
+    /// Strike price of the contract.
+    pub strike_price: Positive,
+    /// Expiration date of the contract.
+    pub expiration: NaiveDate,
+    /// Whether the contracts were bought or sold, tag `54`.
+    pub action: Action,
+    /// Number of contracts filled, tag `32`.
+    pub quantity: Positive,
+    /// Price per contract, tag `31`.
+    pub price: Positive,
+    /// Time of the execution, tag `60`.
+    pub transact_time: DateTime<Utc>,
+    /// Whether the execution opened or closed contracts, tag `77`, when reported.
+    pub position_effect: Option<FillKind>,
+    /// Commission of the execution, tag `12`.
+    pub commission: Positive,
+    /// How the commission is charged, tag `13`.
+    pub commission_basis: FeeBasis,
+    /// Currency of the price and commission, tag `15`, when reported.
+    pub currency: Option<Currency>,
+    /// Broker identifier of the contract, tag `48`, when reported.
+    pub security_id: Option<String>,
+}
+
+impl ExecutionReport {
+    /// Reads an execution report from a parsed message.
+    ///
+    /// Returns `None` for messages that are not execution reports, execution
+    /// reports that do not carry a trade, and instruments other than options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for trade corrections and busts, and for trades with
+    /// missing or invalid fields.
+    pub fn from_message(message: &FixMessage) -> Result<Option<Self>, PositionError> {
+        if message.msg_type() != Some("8") {
+            return Ok(None);
+        }
+        match message.required(150, "ExecType")? {
+            "F" | "1" | "2" => {}
+            "G" | "H" => {
+                return Err(PositionError::invalid_position(
+                    "FIX trade corrections and cancels must be reconciled manually",
+                ));
+            }
+            _ => return Ok(None),
+        }
+        if message
+            .get(167)
+            .is_some_and(|security_type| security_type != "OPT")
+        {
+            return Ok(None);
+        }
+
+        let (root, option_style, strike_price, expiration) = match message.get(201) {
+            Some(put_or_call) => {
+                let style = match put_or_call {
+                    "0" => OptionStyle::Put,
+                    "1" => OptionStyle::Call,
+                    other => {
+                        return Err(PositionError::invalid_position(&format!(
+                            "FIX tag 201 (PutOrCall) has an invalid value '{other}'"
+                        )));
+                    }
+                };
+                let maturity = message
+                    .get(541)
+                    .or_else(|| message.get(200))
+                    .ok_or_else(|| {
+                        PositionError::invalid_position("FIX tag 541 (MaturityDate) is missing")
+                    })?;
+                (
+                    message.required(55, "Symbol")?.to_string(),
+                    style,
+                    message.positive(202, "StrikePrice")?,
+                    parse_date(maturity)?,
+                )
+            }
+            None => {
+                let symbol: OccSymbol = message.required(55, "Symbol")?.parse()?;
+                (symbol.root, symbol.style, symbol.strike, symbol.expiration)
+            }
+        };
+
+        let action = match message.required(54, "Side")? {
+            "1" => Action::Buy,
+            "2" | "5" => Action::Sell,
+            other => {
+                return Err(PositionError::invalid_position(&format!(
+                    "FIX tag 54 (Side) has an unsupported value '{other}'"
+                )));
+            }
+        };
+        let position_effect = match message.get(77) {
+            Some("O") => Some(FillKind::Open),
+            Some("C") => Some(FillKind::Close),
+            _ => None,
+        };
+        let commission = match message.get(12) {
+            Some(_) => message.positive(12, "Commission")?,
+            None => Positive::ZERO,
+        };
+        let commission_basis = match message.get(13) {
+            Some("1") => FeeBasis::PerContract,
+            _ => FeeBasis::PerOrder,
+        };
+        let transact_time = message.required(60, "TransactTime")?;
+        let transact_time = NaiveDateTime::parse_from_str(transact_time, "%Y%m%d-%H:%M:%S%.f")
+            .map_err(|_| {
+                PositionError::invalid_position(&format!(
+                    "FIX tag 60 (TransactTime) has an invalid value '{transact_time}'"
+                ))
+            })?
+            .and_utc();
+
+        Ok(Some(ExecutionReport {
+            exec_id: message.required(17, "ExecID")?.to_string(),
+            order_id: message.required(37, "OrderID")?.to_string(),
+            underlying_symbol: message.get(311).map(str::to_string).unwrap_or(root),
+            option_style,
+            strike_price,
+            expiration,
+            action,
+            quantity: message.positive(32, "LastQty")?,
+            price: message.positive(31, "LastPx")?,
+            transact_time,
+            position_effect,
+            commission,
+            commission_basis,
+            currency: message.get(15).map(Currency::new),
+            security_id: message.get(48).map(str::to_string),
+        }))
+    }
+
+    /// Commission per contract of the execution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for a per-execution commission on zero contracts.
+    pub fn fee_per_contract(&self) -> Result<Positive, PositionError> {
+        self.commission_basis
+            .per_contract(self.commission, self.quantity)
+    }
+
+    /// Returns `true` if `option` is the contract of the report.
+    pub fn matches(&self, option: &Options) -> bool {
+        option.underlying_symbol == self.underlying_symbol
+            && option.option_style == self.option_style
+            && option.strike_price == self.strike_price
+            && option
+                .expiration_date
+                .get_date()
+                .is_ok_and(|date| date.date_naive() == self.expiration)
+    }
+
+    /// Opens a position from the report.
+    ///
+    /// The report does not describe the market, so the price of the
+    /// underlying and the implied volatility of the contract are given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report closes contracts or the position is
+    /// invalid.
+    pub fn to_position(
+        &self,
+        underlying_price: Positive,
+        implied_volatility: Positive,
+    ) -> Result<Position, PositionError> {
+        if self.position_effect == Some(FillKind::Close) {
+            return Err(PositionError::invalid_position(&format!(
+                "execution {} closes contracts of a position that does not exist",
+                self.exec_id
+            )));
+        }
+        let side = match self.action {
+            Action::Sell => Side::Short,
+            _ => Side::Long,
+        };
+        let (hour, minute) = EXPIRATION_TIME;
+        let expiration = NaiveTime::from_hms_opt(hour, minute, 0)
+            .map(|time| Utc.from_utc_datetime(&self.expiration.and_time(time)))
+            .ok_or_else(|| PositionError::invalid_position("invalid expiration time"))?;
+        let option = Options::new(
+            OptionType::European,
+            side,
+            self.underlying_symbol.clone(),
+            self.strike_price,
+            ExpirationDate::DateTime(expiration),
+            implied_volatility,
+            self.quantity,
+            underlying_price,
+            Decimal::ZERO,
+            self.option_style,
+            Positive::ZERO,
+            None,
+        );
+
+        let mut builder = PositionBuilder::new()
+            .option(option)
+            .premium(self.price)
+            .filled_at(self.transact_time)
+            .open_fee(self.commission, self.commission_basis);
+        if let Some(security_id) = &self.security_id {
+            builder = builder.epic(security_id.clone());
+        }
+        if let Some(currency) = &self.currency {
+            builder = builder.currency(currency.clone());
+        }
+        builder.build()
+    }
+}
+
+/// Converts broker messages into execution reports.
+pub trait ExecutionReportAdapter {
+    /// Reads the execution report carried by `message`.
+    ///
+    /// Returns `None` for messages that do not report a fill of an option.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message is malformed or reports a fill that
+    /// cannot be read.
+    fn execution_report(&self, message: &str) -> Result<Option<ExecutionReport>, PositionError>;
+}
+
+/// Reference [`ExecutionReportAdapter`] for FIX 4.4 tag=value messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Fix44Adapter;
+
+impl ExecutionReportAdapter for Fix44Adapter {
+    fn execution_report(&self, message: &str) -> Result<Option<ExecutionReport>, PositionError> {
+        ExecutionReport::from_message(&FixMessage::parse(message)?)
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, PositionError> {
+    NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| {
+        PositionError::invalid_position(&format!("FIX maturity '{value}' is not a YYYYMMDD date"))
+    })
+}
+
+impl Position {
+    /// Applies an execution of the contract of the position.
+    ///
+    /// Executions in the direction of the position add contracts, and the
+    /// others close contracts and return the P&L they realize. The commission
+    /// of a closing execution becomes the closing fee of the position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report is for another contract, its position
+    /// effect contradicts its direction, or the fill cannot be applied.
+    pub fn apply_execution(
+        &mut self,
+        report: &ExecutionReport,
+    ) -> Result<Option<Decimal>, PositionError> {
+        if !report.matches(&self.option) {
+            return Err(PositionError::invalid_position(&format!(
+                "execution {} is not for the contract of the position",
+                report.exec_id
+            )));
+        }
+        let opens = matches!(
+            (self.option.side, report.action),
+            (Side::Long, Action::Buy) | (Side::Short, Action::Sell)
+        );
+        let kind = if opens {
+            FillKind::Open
+        } else {
+            FillKind::Close
+        };
+        if report.position_effect.is_some_and(|effect| effect != kind) {
+            return Err(PositionError::invalid_position(&format!(
+                "execution {} is reported as {:?} but is a {:?} of the position",
+                report.exec_id, report.position_effect, kind
+            )));
+        }
+
+        let fee = report.fee_per_contract()?;
+        match kind {
+            FillKind::Open => {
+                self.increase(report.quantity, report.price, fee, report.transact_time)?;
+                Ok(None)
+            }
+            FillKind::Close => {
+                if report.commission > Positive::ZERO {
+                    self.close_fee = fee;
+                }
+                self.close_partial(report.quantity, report.price, report.transact_time)
+                    .map(Some)
+            }
+        }
+    }
+}
+
+impl Portfolio {
+    /// Applies an execution to the first open position on its contract, or
+    /// opens a position when there is none.
+    ///
+    /// `underlying_price` and `implied_volatility` are only used to open a
+    /// position. Strategy legs are not matched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the execution cannot be applied to the matching
+    /// position or cannot open a new one.
+    pub fn apply_execution(
+        &mut self,
+        report: &ExecutionReport,
+        underlying_price: Positive,
+        implied_volatility: Positive,
+    ) -> Result<Option<Decimal>, PositionError> {
+        match self
+            .positions
+            .iter_mut()
+            .find(|position| position.is_open() && report.matches(&position.option))
+        {
+            Some(position) => position.apply_execution(report),
+            None => {
+                self.positions
+                    .push(report.to_position(underlying_price, implied_volatility)?);
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_fix {
+    use super::*;
+    use crate::model::lifecycle::PositionStatus;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn report(side: &str, quantity: &str, price: &str, extra: &str) -> ExecutionReport {
+        let message = format!(
+            "8=FIX.4.4|35=8|37=O1|17=E{side}{quantity}|150=F|39=2|55=SPY|167=OPT|201=0|202=440|\
+             541=20261218|54={side}|32={quantity}|31={price}|60=20261001-14:30:00.250|{extra}"
+        );
+        Fix44Adapter.execution_report(&message).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_parse_execution_report() {
+        let report = report("2", "3", "4.2", "12=0.5|13=1|15=usd|48=SPY261218P440|77=O|");
+        assert_eq!(report.underlying_symbol, "SPY");
+        assert_eq!(report.option_style, OptionStyle::Put);
+        assert_eq!(report.strike_price, pos_or_panic!(440.0));
+        assert_eq!(
+            report.expiration,
+            NaiveDate::from_ymd_opt(2026, 12, 18).unwrap()
+        );
+        assert_eq!(report.action, Action::Sell);
+        assert_eq!(report.position_effect, Some(FillKind::Open));
+        assert_eq!(report.fee_per_contract().unwrap(), pos_or_panic!(0.5));
+        assert_eq!(report.currency, Some(Currency::new("USD")));
+        assert_eq!(
+            report.transact_time,
+            Utc.with_ymd_and_hms(2026, 10, 1, 14, 30, 0).unwrap()
+                + chrono::Duration::milliseconds(250)
+        );
+    }
+
+    #[test]
+    fn test_occ_symbol_and_checksum() {
+        let body = "8=FIX.4.4\u{1}35=8\u{1}37=O2\u{1}17=E9\u{1}150=F\u{1}\
+                    55=AAPL  261218C00152500\u{1}54=1\u{1}32=1\u{1}31=3\u{1}60=20261001-15:00:00\u{1}";
+        let sum = body.bytes().map(u32::from).sum::<u32>() % 256;
+        let report = Fix44Adapter
+            .execution_report(&format!("{body}10={sum:03}\u{1}"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.underlying_symbol, "AAPL");
+        assert_eq!(report.option_style, OptionStyle::Call);
+        assert_eq!(report.strike_price, pos_or_panic!(152.5));
+
+        let corrupted = format!("{body}10={:03}\u{1}", (sum + 1) % 256);
+        assert!(Fix44Adapter.execution_report(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_non_trades_are_skipped() {
+        let new_order = "8=FIX.4.4|35=8|37=O1|17=E1|150=0|39=0|55=SPY|54=1|";
+        assert!(Fix44Adapter.execution_report(new_order).unwrap().is_none());
+        let heartbeat = "8=FIX.4.4|35=0|";
+        assert!(Fix44Adapter.execution_report(heartbeat).unwrap().is_none());
+        let equity = "8=FIX.4.4|35=8|37=O1|17=E1|150=F|55=SPY|167=CS|54=1|";
+        assert!(Fix44Adapter.execution_report(equity).unwrap().is_none());
+        let bust = "8=FIX.4.4|35=8|37=O1|17=E1|150=H|55=SPY|54=1|";
+        assert!(Fix44Adapter.execution_report(bust).is_err());
+        assert!(Fix44Adapter.execution_report("8=FIX.4.4|35").is_err());
+    }
+
+    #[test]
+    fn test_portfolio_reconciliation() {
+        let mut portfolio = Portfolio::new("drop copy".to_string());
+        let spot = pos_or_panic!(445.0);
+        let iv = pos_or_panic!(0.2);
+
+        let open = report("2", "2", "5", "12=2|77=O|");
+        assert_eq!(portfolio.apply_execution(&open, spot, iv).unwrap(), None);
+        assert_eq!(portfolio.positions.len(), 1);
+        assert_eq!(portfolio.positions[0].option.side, Side::Short);
+        assert_eq!(portfolio.positions[0].open_fee, Positive::ONE);
+
+        let add = report("2", "2", "7", "");
+        portfolio.apply_execution(&add, spot, iv).unwrap();
+        assert_eq!(portfolio.positions.len(), 1);
+        assert_eq!(portfolio.positions[0].option.quantity, pos_or_panic!(4.0));
+        assert_eq!(portfolio.positions[0].premium, pos_or_panic!(6.0));
+
+        let close = report("1", "4", "2", "77=C|");
+        let realized = portfolio.apply_execution(&close, spot, iv).unwrap();
+        assert_eq!(realized, Some(dec!(14.0)));
+        assert_eq!(portfolio.positions[0].status, PositionStatus::Closed);
+        assert_eq!(portfolio.positions[0].fills.len(), 3);
+
+        let contradicting = report("1", "1", "2", "77=O|");
+        let mut position = open.to_position(spot, iv).unwrap();
+        assert!(position.apply_execution(&contradicting).is_err());
+    }
+}
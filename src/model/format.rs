@@ -105,10 +105,6 @@ impl fmt::Display for ExoticParams {
             fields.push(format!("Rainbow Second Asset Dividend: {div}"));
         }
 
-        if let Some(corr) = self.rainbow_correlation {
-            fields.push(format!("Rainbow Correlation: {corr:.4}"));
-        }
-
         if let Some(ref vol) = self.spread_second_asset_volatility {
             fields.push(format!("Spread Second Asset Volatility: {vol}"));
         }
@@ -117,18 +113,10 @@ impl fmt::Display for ExoticParams {
             fields.push(format!("Spread Second Asset Dividend: {div}"));
         }
 
-        if let Some(corr) = self.spread_correlation {
-            fields.push(format!("Spread Correlation: {corr:.4}"));
-        }
-
         if let Some(ref vol) = self.quanto_fx_volatility {
             fields.push(format!("Quanto FX Volatility: {vol}"));
         }
 
-        if let Some(corr) = self.quanto_fx_correlation {
-            fields.push(format!("Quanto FX Correlation: {corr:.4}"));
-        }
-
         if let Some(rate) = self.quanto_foreign_rate {
             fields.push(format!("Quanto Foreign Rate: {rate:.4}"));
         }
@@ -141,8 +129,12 @@ impl fmt::Display for ExoticParams {
             fields.push(format!("Exchange Second Asset Dividend: {div}"));
         }
 
-        if let Some(corr) = self.exchange_correlation {
-            fields.push(format!("Exchange Correlation: {corr:.4}"));
+        if let Some(ref correlation) = self.correlation {
+            fields.push(format!(
+                "Correlation: {} ({:.4})",
+                correlation.symbols().join("/"),
+                self.second_asset_correlation().unwrap_or_default()
+            ));
         }
 
         write!(f, "{}", fields.join(", "))
@@ -212,7 +204,9 @@ impl fmt::Debug for Strategy {
 #[cfg(test)]
 mod tests_options {
     use super::*;
-    use crate::model::types::{BarrierType, OptionStyle, OptionType, Side};
+    use crate::model::types::{
+        BarrierType, DayCount, OptionStyle, OptionType, SettlementType, Side,
+    };
 
     use chrono::{NaiveDate, TimeZone, Utc};
     use expiration_date::ExpirationDate;
@@ -238,6 +232,8 @@ mod tests_options {
             option_style: OptionStyle::Call,
             dividend_yield: pos_or_panic!(0.02),
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
 
         let debug_output = format!("{options:?}");
@@ -279,6 +275,8 @@ mod tests_options {
             option_style: OptionStyle::Call,
             dividend_yield: pos_or_panic!(0.02),
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
 
         let display_output = format!("{options}");
@@ -308,16 +306,13 @@ mod tests_options {
             rainbow_second_asset_price: None,
             rainbow_second_asset_volatility: None,
             rainbow_second_asset_dividend: None,
-            rainbow_correlation: None,
             spread_second_asset_volatility: None,
             spread_second_asset_dividend: None,
-            spread_correlation: None,
             quanto_fx_volatility: None,
-            quanto_fx_correlation: None,
             quanto_foreign_rate: None,
             exchange_second_asset_volatility: None,
             exchange_second_asset_dividend: None,
-            exchange_correlation: None,
+            correlation: None,
         };
         let naive_date = NaiveDate::from_ymd_opt(2024, 8, 8)
             .expect("Invalid date")
@@ -341,6 +336,8 @@ mod tests_options {
             option_style: OptionStyle::Call,
             dividend_yield: pos_or_panic!(0.01),
             exotic_params: Some(exotic_params),
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
 
         let display_output = format!("{options}");
@@ -353,7 +350,7 @@ mod tests_options {
             Quantity: 5\n\
             Risk-free Rate: 1.50%\n\
             Dividend Yield: 1%\n\
-            Exotic Parameters: ExoticParams { spot_prices: None, spot_min: None, spot_max: None, cliquet_local_cap: None, cliquet_local_floor: None, cliquet_global_cap: None, cliquet_global_floor: None, rainbow_second_asset_price: None, rainbow_second_asset_volatility: None, rainbow_second_asset_dividend: None, rainbow_correlation: None, spread_second_asset_volatility: None, spread_second_asset_dividend: None, spread_correlation: None, quanto_fx_volatility: None, quanto_fx_correlation: None, quanto_foreign_rate: None, exchange_second_asset_volatility: None, exchange_second_asset_dividend: None, exchange_correlation: None }";
+            Exotic Parameters: ExoticParams { spot_prices: None, spot_min: None, spot_max: None, cliquet_local_cap: None, cliquet_local_floor: None, cliquet_global_cap: None, cliquet_global_floor: None, rainbow_second_asset_price: None, rainbow_second_asset_volatility: None, rainbow_second_asset_dividend: None, spread_second_asset_volatility: None, spread_second_asset_dividend: None, quanto_fx_volatility: None, quanto_foreign_rate: None, exchange_second_asset_volatility: None, exchange_second_asset_dividend: None, correlation: None }";
 
         assert_eq!(display_output, expected_output);
     }
@@ -429,7 +426,10 @@ mod tests_expiration_date {
 #[cfg(test)]
 mod tests_position_type_display_debug {
     use super::*;
+    use crate::model::types::{DayCount, SettlementType};
+    use crate::model::{Currency, PositionStatus};
     use crate::{OptionStyle, OptionType, Side};
+    use rust_decimal::Decimal;
 
     use chrono::{DateTime, NaiveDate, TimeZone, Utc};
     use expiration_date::ExpirationDate;
@@ -455,6 +455,8 @@ mod tests_position_type_display_debug {
                 option_style: OptionStyle::Call,
                 dividend_yield: pos_or_panic!(0.02),
                 exotic_params: None,
+                settlement_type: SettlementType::Physical,
+                day_count: DayCount::Act365Fixed,
             },
             Utc.from_utc_datetime(&naive_date),
         )
@@ -471,6 +473,10 @@ mod tests_position_type_display_debug {
             close_fee: pos_or_panic!(0.45),
             epic: Some("Epic123".to_string()),
             extra_fields: None,
+            status: PositionStatus::Open,
+            fills: Vec::new(),
+            realized_pnl: Decimal::ZERO,
+            currency: Currency::default(),
         };
 
         let expected_display = "Position Details:\n\
@@ -502,6 +508,10 @@ mod tests_position_type_display_debug {
             close_fee: pos_or_panic!(0.45),
             epic: Some("Epic123".to_string()),
             extra_fields: None,
+            status: PositionStatus::Open,
+            fills: Vec::new(),
+            realized_pnl: Decimal::ZERO,
+            currency: Currency::default(),
         };
 
         let expected_debug = "Position { \
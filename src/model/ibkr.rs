@@ -0,0 +1,614 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Interactive Brokers Flex Reports
+//!
+//! Imports the option trades and open positions of an Interactive Brokers
+//! Flex Query XML report into a [`Portfolio`].
+//!
+//! Available with the `ibkr` feature.
+//!
+//! [`FlexImporter::parse`] reads the `<Trade>` and `<OpenPosition>` elements
+//! of option contracts (`assetCategory="OPT"`); other asset classes and the
+//! order or closed-lot summaries of trades are skipped. Contracts are
+//! identified by their [`OccSymbol`], taken from the `symbol` attribute or
+//! built from `underlyingSymbol`, `putCall`, `strike` and `expiry`.
+//!
+//! [`FlexImporter::import`] then rebuilds the positions:
+//!
+//! 1. Contracts held before the first trade of the report, which is the
+//!    quantity of the `<OpenPosition>` snapshot minus the net quantity
+//!    traded, are opened at their cost basis price.
+//! 2. Trades are replayed in chronological order as fills, with
+//!    [`Portfolio::apply_execution`]: opening trades add contracts and
+//!    closing trades realize P&L. IB reports commissions as negative
+//!    amounts; their absolute value becomes the fee of the fill.
+//!
+//! Every position keeps the OCC symbol of its contract in `epic`. Flex
+//! timestamps are local to the account and converted to UTC with the
+//! [`ExchangeTimeZone`] of the importer. The reports do not describe the
+//! market, so the importer is given the implied volatility and the prices
+//! of the underlyings to create the options with.
+//!
+//! ```rust
+//! use optionstratlib::model::ibkr::FlexImporter;
+//! use positive::pos_or_panic;
+//!
+//! let xml = r#"<FlexQueryResponse><FlexStatements><FlexStatement accountId="U123">
+//!   <Trades>
+//!     <Trade assetCategory="OPT" symbol="AAPL  261218C00150000" underlyingSymbol="AAPL"
+//!            buySell="BUY" quantity="2" tradePrice="4.1" ibCommission="-1.3"
+//!            dateTime="20261001;103000" openCloseIndicator="O" tradeID="1" currency="USD"/>
+//!   </Trades>
+//! </FlexStatement></FlexStatements></FlexQueryResponse>"#;
+//!
+//! let portfolio = FlexImporter::new(pos_or_panic!(0.25))
+//!     .underlying_price("AAPL", pos_or_panic!(148.0))
+//!     .import(xml)
+//!     .unwrap();
+//!
+//! let position = &portfolio.positions[0];
+//! assert_eq!(position.epic.as_deref(), Some("AAPL  261218C00150000"));
+//! assert_eq!(position.open_fee, pos_or_panic!(0.65));
+//! ```
+
+use crate::error::PositionError;
+use crate::model::Portfolio;
+use crate::model::currency::Currency;
+use crate::model::fix::ExecutionReport;
+use crate::model::lifecycle::FillKind;
+use crate::model::occ::OccSymbol;
+use crate::model::position_builder::FeeBasis;
+use crate::model::types::{Action, OptionStyle, Side};
+use crate::utils::ExchangeTimeZone;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use positive::Positive;
+use roxmltree::{Document, Node};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+/// Formats of the `dateTime` attributes of Flex reports.
+const DATE_TIME_FORMATS: [&str; 6] = [
+    "%Y%m%d;%H%M%S",
+    "%Y%m%d;%H:%M:%S",
+    "%Y-%m-%d;%H:%M:%S",
+    "%Y%m%d %H%M%S",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d, %H:%M:%S",
+];
+
+/// Formats of the date attributes of Flex reports.
+const DATE_FORMATS: [&str; 2] = ["%Y%m%d", "%Y-%m-%d"];
+
+/// An option trade of a Flex report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlexTrade {
+    /// IB trade identifier, `tradeID`.
+    pub trade_id: String,
+    /// IB order identifier, `ibOrderID`.
+    pub order_id: String,
+    /// Account of the trade, `accountId`.
+    pub account_id: Option<String>,
+    /// Traded contract.
+    pub contract: OccSymbol,
+    /// Symbol of the underlying, `underlyingSymbol`.
+    pub underlying_symbol: String,
+    /// Whether the contracts were bought or sold.
+    pub action: Action,
+    /// Number of contracts traded.
+    pub quantity: Positive,
+    /// Price per contract, `tradePrice`.
+    pub price: Positive,
+    /// Commission charged for the whole trade, `ibCommission`.
+    pub commission: Positive,
+    /// Currency of the trade, `currency`.
+    pub currency: Option<Currency>,
+    /// Time of the execution, in UTC.
+    pub executed_at: DateTime<Utc>,
+    /// Whether the trade opened or closed contracts, `openCloseIndicator`.
+    pub open_close: Option<FillKind>,
+}
+
+impl FlexTrade {
+    /// Signed number of contracts, positive when bought.
+    fn signed_quantity(&self) -> Decimal {
+        match self.action {
+            Action::Sell => -self.quantity.to_dec(),
+            _ => self.quantity.to_dec(),
+        }
+    }
+
+    /// Converts the trade into an execution report.
+    pub fn to_execution_report(&self) -> ExecutionReport {
+        ExecutionReport {
+            exec_id: self.trade_id.clone(),
+            order_id: self.order_id.clone(),
+            underlying_symbol: self.underlying_symbol.clone(),
+            option_style: self.contract.style,
+            strike_price: self.contract.strike,
+            expiration: self.contract.expiration,
+            action: self.action,
+            quantity: self.quantity,
+            price: self.price,
+            transact_time: self.executed_at,
+            position_effect: self.open_close,
+            commission: self.commission,
+            commission_basis: FeeBasis::PerOrder,
+            currency: self.currency.clone(),
+            security_id: Some(self.contract.to_string()),
+        }
+    }
+}
+
+/// An open option position of a Flex report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlexOpenPosition {
+    /// Account of the position, `accountId`.
+    pub account_id: Option<String>,
+    /// Held contract.
+    pub contract: OccSymbol,
+    /// Symbol of the underlying, `underlyingSymbol`.
+    pub underlying_symbol: String,
+    /// Whether the contracts are held long or short.
+    pub side: Side,
+    /// Number of contracts held.
+    pub quantity: Positive,
+    /// Average cost per contract, fees included, `costBasisPrice`.
+    pub cost_basis_price: Positive,
+    /// Closing price of the contract, `markPrice`.
+    pub mark_price: Option<Positive>,
+    /// Currency of the position, `currency`.
+    pub currency: Option<Currency>,
+    /// Time the position was opened, `openDateTime`, in UTC.
+    pub opened_at: Option<DateTime<Utc>>,
+    /// Date of the snapshot, `reportDate`, at midnight UTC.
+    pub report_date: Option<DateTime<Utc>>,
+}
+
+impl FlexOpenPosition {
+    /// Signed number of contracts, positive when long.
+    fn signed_quantity(&self) -> Decimal {
+        match self.side {
+            Side::Short => -self.quantity.to_dec(),
+            Side::Long => self.quantity.to_dec(),
+        }
+    }
+}
+
+/// Option trades and open positions read from a Flex report.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlexReport {
+    /// Account of the first statement, `accountId`.
+    pub account_id: Option<String>,
+    /// Option trades, in the order of the report.
+    pub trades: Vec<FlexTrade>,
+    /// Open option positions at the end of the report.
+    pub open_positions: Vec<FlexOpenPosition>,
+}
+
+/// Importer of Interactive Brokers Flex reports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlexImporter {
+    timezone: ExchangeTimeZone,
+    implied_volatility: Positive,
+    underlying_prices: BTreeMap<String, Positive>,
+}
+
+impl FlexImporter {
+    /// Creates an importer giving `implied_volatility` to the options it
+    /// creates, with report times in New York time.
+    pub fn new(implied_volatility: Positive) -> Self {
+        FlexImporter {
+            timezone: ExchangeTimeZone::NewYork,
+            implied_volatility,
+            underlying_prices: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the time zone of the times of the report.
+    pub fn timezone(mut self, timezone: ExchangeTimeZone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Sets the price of an underlying, required for every underlying of the
+    /// imported options.
+    pub fn underlying_price(mut self, symbol: impl Into<String>, price: Positive) -> Self {
+        self.underlying_prices.insert(symbol.into(), price);
+        self
+    }
+
+    /// Reads the option trades and open positions of a Flex report.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed or an option element has
+    /// missing or invalid attributes.
+    pub fn parse(&self, xml: &str) -> Result<FlexReport, PositionError> {
+        let document = Document::parse(xml)
+            .map_err(|e| PositionError::invalid_position(&format!("invalid Flex report: {e}")))?;
+        let mut report = FlexReport {
+            account_id: document
+                .descendants()
+                .find(|node| node.has_tag_name("FlexStatement"))
+                .and_then(|node| node.attribute("accountId"))
+                .map(str::to_string),
+            ..FlexReport::default()
+        };
+
+        for node in document
+            .descendants()
+            .filter(|node| node.attribute("assetCategory") == Some("OPT"))
+        {
+            let detail = node.attribute("levelOfDetail");
+            match node.tag_name().name() {
+                "Trade" if detail.is_none_or(|detail| detail == "EXECUTION") => {
+                    report.trades.push(self.trade(node)?)
+                }
+                "OpenPosition" if detail.is_none_or(|detail| detail == "SUMMARY") => {
+                    report.open_positions.push(self.open_position(node)?)
+                }
+                _ => {}
+            }
+        }
+        Ok(report)
+    }
+
+    /// Reads a Flex report and rebuilds its positions into a portfolio.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report cannot be parsed or its positions
+    /// cannot be rebuilt, see [`FlexImporter::import_report`].
+    pub fn import(&self, xml: &str) -> Result<Portfolio, PositionError> {
+        self.import_report(&self.parse(xml)?)
+    }
+
+    /// Rebuilds the positions of a parsed report into a portfolio named
+    /// after its account.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an underlying has no price, a trade cannot be
+    /// applied, or a contract was held before the report and closed in it,
+    /// since its cost is then unknown.
+    pub fn import_report(&self, report: &FlexReport) -> Result<Portfolio, PositionError> {
+        let mut portfolio = Portfolio::new(
+            report
+                .account_id
+                .clone()
+                .unwrap_or_else(|| "IBKR".to_string()),
+        );
+        let mut trades: Vec<&FlexTrade> = report.trades.iter().collect();
+        trades.sort_by_key(|trade| trade.executed_at);
+
+        let mut traded: HashMap<&OccSymbol, Decimal> = HashMap::new();
+        for trade in &trades {
+            *traded.entry(&trade.contract).or_default() += trade.signed_quantity();
+        }
+        for held in &report.open_positions {
+            let before = held.signed_quantity() - traded.remove(&held.contract).unwrap_or_default();
+            if before == Decimal::ZERO {
+                continue;
+            }
+            let seed = ExecutionReport {
+                exec_id: format!("{} opening", held.contract),
+                order_id: String::new(),
+                underlying_symbol: held.underlying_symbol.clone(),
+                option_style: held.contract.style,
+                strike_price: held.contract.strike,
+                expiration: held.contract.expiration,
+                action: if before > Decimal::ZERO {
+                    Action::Buy
+                } else {
+                    Action::Sell
+                },
+                quantity: Positive::new_decimal(before.abs())
+                    .map_err(|e| PositionError::invalid_position(&e.to_string()))?,
+                price: held.cost_basis_price,
+                transact_time: held
+                    .opened_at
+                    .or(held.report_date)
+                    .or_else(|| trades.first().map(|trade| trade.executed_at))
+                    .unwrap_or_else(Utc::now),
+                position_effect: Some(FillKind::Open),
+                commission: Positive::ZERO,
+                commission_basis: FeeBasis::PerOrder,
+                currency: held.currency.clone(),
+                security_id: Some(held.contract.to_string()),
+            };
+            portfolio.positions.push(seed.to_position(
+                self.underlying_price_of(&held.underlying_symbol)?,
+                self.implied_volatility,
+            )?);
+        }
+        for trade in trades {
+            portfolio.apply_execution(
+                &trade.to_execution_report(),
+                self.underlying_price_of(&trade.underlying_symbol)?,
+                self.implied_volatility,
+            )?;
+        }
+        Ok(portfolio)
+    }
+
+    fn underlying_price_of(&self, symbol: &str) -> Result<Positive, PositionError> {
+        self.underlying_prices.get(symbol).copied().ok_or_else(|| {
+            PositionError::invalid_position(&format!("no underlying price for {symbol}"))
+        })
+    }
+
+    fn trade(&self, node: Node) -> Result<FlexTrade, PositionError> {
+        let contract = contract(node)?;
+        let quantity = decimal(node, "quantity")?;
+        let action = match node.attribute("buySell") {
+            Some(side) if side.starts_with("BUY") => Action::Buy,
+            Some(side) if side.starts_with("SELL") => Action::Sell,
+            _ if quantity < Decimal::ZERO => Action::Sell,
+            _ => Action::Buy,
+        };
+        let executed_at = match node.attribute("dateTime") {
+            Some(value) => self.date_time(value)?,
+            None => self.date(node, "tradeDate")?,
+        };
+        Ok(FlexTrade {
+            trade_id: node
+                .attribute("tradeID")
+                .or_else(|| node.attribute("ibExecID"))
+                .unwrap_or_default()
+                .to_string(),
+            order_id: node.attribute("ibOrderID").unwrap_or_default().to_string(),
+            account_id: node.attribute("accountId").map(str::to_string),
+            underlying_symbol: underlying_symbol(node, &contract),
+            contract,
+            action,
+            quantity: positive(node, "quantity", quantity.abs())?,
+            price: positive(node, "tradePrice", decimal(node, "tradePrice")?)?,
+            commission: match node.attribute("ibCommission") {
+                Some(_) => positive(node, "ibCommission", decimal(node, "ibCommission")?.abs())?,
+                None => Positive::ZERO,
+            },
+            currency: node.attribute("currency").map(Currency::new),
+            executed_at,
+            open_close: match node.attribute("openCloseIndicator") {
+                Some("O") => Some(FillKind::Open),
+                Some("C") => Some(FillKind::Close),
+                _ => None,
+            },
+        })
+    }
+
+    fn open_position(&self, node: Node) -> Result<FlexOpenPosition, PositionError> {
+        let contract = contract(node)?;
+        let quantity = decimal(node, "position")?;
+        Ok(FlexOpenPosition {
+            account_id: node.attribute("accountId").map(str::to_string),
+            underlying_symbol: underlying_symbol(node, &contract),
+            contract,
+            side: if quantity < Decimal::ZERO {
+                Side::Short
+            } else {
+                Side::Long
+            },
+            quantity: positive(node, "position", quantity.abs())?,
+            cost_basis_price: positive(
+                node,
+                "costBasisPrice",
+                decimal(node, "costBasisPrice")?.abs(),
+            )?,
+            mark_price: match node.attribute("markPrice") {
+                Some(_) => Some(positive(node, "markPrice", decimal(node, "markPrice")?)?),
+                None => None,
+            },
+            currency: node.attribute("currency").map(Currency::new),
+            opened_at: match node.attribute("openDateTime") {
+                Some(value) if !value.is_empty() => Some(self.date_time(value)?),
+                _ => None,
+            },
+            report_date: match node.attribute("reportDate") {
+                Some(_) => Some(self.date(node, "reportDate")?),
+                None => None,
+            },
+        })
+    }
+
+    fn date_time(&self, value: &str) -> Result<DateTime<Utc>, PositionError> {
+        DATE_TIME_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+            .map(|local| self.timezone.to_utc(local.date(), local.time()))
+            .ok_or_else(|| {
+                PositionError::invalid_position(&format!("invalid Flex date and time '{value}'"))
+            })
+    }
+
+    fn date(&self, node: Node, attribute: &str) -> Result<DateTime<Utc>, PositionError> {
+        Ok(parse_date(node, attribute)?
+            .and_time(NaiveTime::MIN)
+            .and_utc())
+    }
+}
+
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Result<&'a str, PositionError> {
+    node.attribute(name).ok_or_else(|| {
+        PositionError::invalid_position(&format!(
+            "Flex {} has no {name} attribute",
+            node.tag_name().name()
+        ))
+    })
+}
+
+fn invalid(node: Node, name: &str, value: impl std::fmt::Display) -> PositionError {
+    PositionError::invalid_position(&format!(
+        "Flex {} has an invalid {name} '{value}'",
+        node.tag_name().name()
+    ))
+}
+
+fn decimal(node: Node, name: &str) -> Result<Decimal, PositionError> {
+    let value = attribute(node, name)?;
+    Decimal::from_str(value).map_err(|_| invalid(node, name, value))
+}
+
+fn positive(node: Node, name: &str, value: Decimal) -> Result<Positive, PositionError> {
+    Positive::new_decimal(value).map_err(|_| invalid(node, name, value))
+}
+
+fn parse_date(node: Node, name: &str) -> Result<NaiveDate, PositionError> {
+    let value = attribute(node, name)?;
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .ok_or_else(|| invalid(node, name, value))
+}
+
+/// Contract of an option element, from its OCC symbol or its attributes.
+fn contract(node: Node) -> Result<OccSymbol, PositionError> {
+    if let Some(symbol) = node
+        .attribute("symbol")
+        .and_then(|symbol| symbol.parse::<OccSymbol>().ok())
+    {
+        return Ok(symbol);
+    }
+    let style = match attribute(node, "putCall")? {
+        "C" | "Call" => OptionStyle::Call,
+        "P" | "Put" => OptionStyle::Put,
+        other => return Err(invalid(node, "putCall", other)),
+    };
+    Ok(OccSymbol::new(
+        attribute(node, "underlyingSymbol")?,
+        parse_date(node, "expiry")?,
+        style,
+        positive(node, "strike", decimal(node, "strike")?)?,
+    ))
+}
+
+fn underlying_symbol(node: Node, contract: &OccSymbol) -> String {
+    node.attribute("underlyingSymbol")
+        .filter(|symbol| !symbol.is_empty())
+        .unwrap_or(&contract.root)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests_ibkr {
+    use super::*;
+    use crate::model::lifecycle::PositionStatus;
+    use chrono::TimeZone;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    const REPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<FlexQueryResponse queryName="options" type="AF">
+  <FlexStatements count="1">
+    <FlexStatement accountId="U1234567" fromDate="20261001" toDate="20261031">
+      <Trades>
+        <Trade accountId="U1234567" assetCategory="OPT" symbol="SPY   261218P00440000"
+               underlyingSymbol="SPY" putCall="P" strike="440" expiry="20261218"
+               buySell="SELL" quantity="-3" tradePrice="5.2" ibCommission="-3.15"
+               currency="USD" dateTime="20261002;093512" openCloseIndicator="O"
+               tradeID="101" ibOrderID="9001" levelOfDetail="EXECUTION"/>
+        <Trade accountId="U1234567" assetCategory="OPT" symbol="SPY   261218P00440000"
+               underlyingSymbol="SPY" buySell="SELL" quantity="-3" tradePrice="5.2"
+               ibCommission="-3.15" currency="USD" dateTime="20261002;093512"
+               openCloseIndicator="O" levelOfDetail="ORDER"/>
+        <Trade accountId="U1234567" assetCategory="OPT" symbol="SPY   261218P00440000"
+               underlyingSymbol="SPY" buySell="BUY" quantity="1" tradePrice="2.2"
+               ibCommission="-1.05" currency="USD" dateTime="20261020;140000"
+               openCloseIndicator="C" tradeID="102" levelOfDetail="EXECUTION"/>
+        <Trade accountId="U1234567" assetCategory="STK" symbol="SPY" buySell="BUY"
+               quantity="100" tradePrice="445" dateTime="20261003;100000" tradeID="103"/>
+        <Trade accountId="U1234567" assetCategory="OPT" symbol="AAPL  261120C00150000"
+               underlyingSymbol="AAPL" buySell="SELL" quantity="-1" tradePrice="6.5"
+               ibCommission="-0.7" currency="USD" dateTime="20261015;120000"
+               openCloseIndicator="C" tradeID="104" levelOfDetail="EXECUTION"/>
+      </Trades>
+      <OpenPositions>
+        <OpenPosition accountId="U1234567" assetCategory="OPT" symbol="SPY   261218P00440000"
+                      underlyingSymbol="SPY" position="-2" costBasisPrice="5.195"
+                      markPrice="2.1" currency="USD" reportDate="20261031"
+                      levelOfDetail="SUMMARY"/>
+        <OpenPosition accountId="U1234567" assetCategory="OPT" underlyingSymbol="AAPL"
+                      putCall="C" strike="150" expiry="2026-11-20" position="2"
+                      costBasisPrice="3.4" currency="USD" openDateTime="20260915;101500"
+                      reportDate="20261031" levelOfDetail="SUMMARY"/>
+      </OpenPositions>
+    </FlexStatement>
+  </FlexStatements>
+</FlexQueryResponse>"#;
+
+    fn importer() -> FlexImporter {
+        FlexImporter::new(pos_or_panic!(0.2))
+            .underlying_price("SPY", pos_or_panic!(445.0))
+            .underlying_price("AAPL", pos_or_panic!(152.0))
+    }
+
+    #[test]
+    fn test_parse_report() {
+        let report = importer().parse(REPORT).unwrap();
+        assert_eq!(report.account_id.as_deref(), Some("U1234567"));
+        assert_eq!(report.trades.len(), 3);
+        assert_eq!(report.open_positions.len(), 2);
+
+        let trade = &report.trades[0];
+        assert_eq!(trade.action, Action::Sell);
+        assert_eq!(trade.quantity, pos_or_panic!(3.0));
+        assert_eq!(trade.commission, pos_or_panic!(3.15));
+        assert_eq!(trade.contract.to_string(), "SPY   261218P00440000");
+        assert_eq!(
+            trade.executed_at,
+            Utc.with_ymd_and_hms(2026, 10, 2, 13, 35, 12).unwrap()
+        );
+
+        let aapl = &report.open_positions[1];
+        assert_eq!(aapl.contract.to_string(), "AAPL  261120C00150000");
+        assert_eq!(aapl.side, Side::Long);
+        assert_eq!(
+            aapl.opened_at,
+            Some(Utc.with_ymd_and_hms(2026, 9, 15, 14, 15, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_import_rebuilds_positions() {
+        let portfolio = importer().import(REPORT).unwrap();
+        assert_eq!(portfolio.name, "U1234567");
+        assert_eq!(portfolio.positions.len(), 2);
+
+        let aapl = &portfolio.positions[0];
+        assert_eq!(aapl.epic.as_deref(), Some("AAPL  261120C00150000"));
+        assert_eq!(aapl.option.side, Side::Long);
+        assert_eq!(aapl.option.quantity, Positive::TWO);
+        assert_eq!(aapl.premium, pos_or_panic!(3.4));
+        assert_eq!(aapl.fills.len(), 2);
+        assert_eq!(aapl.realized_pnl, dec!(2.4));
+
+        let spy = &portfolio.positions[1];
+        assert_eq!(spy.epic.as_deref(), Some("SPY   261218P00440000"));
+        assert_eq!(spy.option.side, Side::Short);
+        assert_eq!(spy.option.quantity, Positive::TWO);
+        assert_eq!(spy.open_fee, pos_or_panic!(1.05));
+        assert_eq!(spy.status, PositionStatus::Open);
+        assert_eq!(spy.realized_pnl, dec!(0.9));
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let without_price = FlexImporter::new(pos_or_panic!(0.2)).import(REPORT);
+        assert!(without_price.is_err());
+
+        let aapl = REPORT
+            .find(
+                r#"<OpenPosition accountId="U1234567" assetCategory="OPT" underlyingSymbol="AAPL""#,
+            )
+            .unwrap();
+        let end = aapl + REPORT[aapl..].find("/>").unwrap() + 2;
+        let closed_before = format!("{}{}", &REPORT[..aapl], &REPORT[end..]);
+        assert!(importer().import(&closed_before).is_err());
+        assert!(importer().import("<FlexQueryResponse>").is_err());
+    }
+}
@@ -0,0 +1,521 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Trade Journal
+//!
+//! An append-only ledger of everything that happens to the positions of an
+//! account: opens, increases, partial and full closes, rolls and
+//! adjustments. Each [`JournalEntry`] carries its timestamp, the fees paid,
+//! the cash that moved, the P&L realized if any, and references to the
+//! positions involved.
+//!
+//! Entries are kept in chronological order and can be filtered with a
+//! [`JournalQuery`], or exported to JSON and CSV for audit trails and
+//! performance review.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! let mut journal = Journal::new();
+//! journal.record_open(&position);
+//! let realized = position.close_partial(Positive::ONE, price, Utc::now())?;
+//! journal.record_fill(&position, position.fills.last().unwrap(), Some(realized));
+//! let closes = journal.query(&JournalQuery::new().kind(JournalEventKind::PartialClose));
+//! ```
+
+use crate::model::ExpirationDate;
+use crate::model::lifecycle::{Fill, FillKind};
+use crate::model::position::Position;
+use crate::model::roll::Roll;
+use crate::model::types::{Action, OptionStyle, Side};
+use crate::strategies::adjustments::{SuggestedAction, SuggestedAdjustment};
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, Write};
+
+/// Type of event recorded in the journal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JournalEventKind {
+    /// A position was opened.
+    Open,
+    /// Contracts were added to an open position.
+    Increase,
+    /// Some contracts of a position were closed.
+    PartialClose,
+    /// The last contracts of a position were closed.
+    Close,
+    /// A position was rolled into a new strike and/or expiration.
+    Roll,
+    /// An adjustment was applied to a strategy.
+    Adjustment,
+}
+
+/// Identifies a position involved in a journal event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionRef {
+    /// Identifier of the position in an external system, if any.
+    pub epic: Option<String>,
+    /// Symbol of the underlying.
+    pub symbol: String,
+    /// Strike price of the option.
+    pub strike: Positive,
+    /// Call or put.
+    pub style: OptionStyle,
+    /// Long or short.
+    pub side: Side,
+    /// Expiration of the option.
+    pub expiration: ExpirationDate,
+}
+
+impl From<&Position> for PositionRef {
+    fn from(position: &Position) -> Self {
+        PositionRef {
+            epic: position.epic.clone(),
+            symbol: position.option.underlying_symbol.clone(),
+            strike: position.option.strike_price,
+            style: position.option.option_style,
+            side: position.option.side,
+            expiration: position.option.expiration_date,
+        }
+    }
+}
+
+/// A single event of the journal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Unique identifier of the entry.
+    pub id: uuid::Uuid,
+    /// When the event happened.
+    pub timestamp: DateTime<Utc>,
+    /// Type of event.
+    pub kind: JournalEventKind,
+    /// Positions involved, e.g. the closed and the opened leg of a roll.
+    pub positions: Vec<PositionRef>,
+    /// Number of contracts involved.
+    pub quantity: Positive,
+    /// Total fees paid.
+    pub fees: Positive,
+    /// Cash received (positive) or paid (negative), net of fees.
+    pub cash_flow: Decimal,
+    /// P&L realized by the event, if it closed contracts.
+    pub realized_pnl: Option<Decimal>,
+    /// Identifier of a related record, such as the id of a roll.
+    pub reference: Option<uuid::Uuid>,
+    /// Free-form notes.
+    pub notes: Option<String>,
+}
+
+/// Filter of journal entries; unset criteria match every entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JournalQuery {
+    /// Only entries of this type.
+    pub kind: Option<JournalEventKind>,
+    /// Only entries involving a position on this underlying.
+    pub symbol: Option<String>,
+    /// Only entries at or after this time.
+    pub from: Option<DateTime<Utc>>,
+    /// Only entries at or before this time.
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl JournalQuery {
+    /// Creates a query matching every entry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the query to one type of event.
+    pub fn kind(mut self, kind: JournalEventKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Restricts the query to one underlying.
+    pub fn symbol(mut self, symbol: &str) -> Self {
+        self.symbol = Some(symbol.to_string());
+        self
+    }
+
+    /// Restricts the query to a time window, both ends included.
+    pub fn between(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+
+    /// Returns `true` if the entry matches every criterion of the query.
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        self.kind.is_none_or(|kind| entry.kind == kind)
+            && self.symbol.as_ref().is_none_or(|symbol| {
+                entry
+                    .positions
+                    .iter()
+                    .any(|position| &position.symbol == symbol)
+            })
+            && self.from.is_none_or(|from| entry.timestamp >= from)
+            && self.to.is_none_or(|to| entry.timestamp <= to)
+    }
+}
+
+/// Chronological ledger of position events.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Journal {
+    entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Creates an empty journal.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every entry, oldest first.
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Records an entry, keeping the journal in chronological order.
+    pub fn record(&mut self, entry: JournalEntry) -> &JournalEntry {
+        let index = self
+            .entries
+            .partition_point(|existing| existing.timestamp <= entry.timestamp);
+        self.entries.insert(index, entry);
+        &self.entries[index]
+    }
+
+    /// Records the opening of a position.
+    pub fn record_open(&mut self, position: &Position) -> &JournalEntry {
+        let opening = Fill::new(
+            position.option.side,
+            FillKind::Open,
+            position.option.quantity,
+            position.premium,
+            position.open_fee,
+            position.date,
+        );
+        let fill = position.fills.first().unwrap_or(&opening);
+        self.record(fill_entry(JournalEventKind::Open, position, fill, None))
+    }
+
+    /// Records a fill of a position, after it has been applied to the position.
+    ///
+    /// The type of event is derived from the fill: the first opening fill is
+    /// an open, later ones are increases, and a closing fill is a close when
+    /// it leaves no open contracts and a partial close otherwise.
+    pub fn record_fill(
+        &mut self,
+        position: &Position,
+        fill: &Fill,
+        realized_pnl: Option<Decimal>,
+    ) -> &JournalEntry {
+        let kind = match fill.kind {
+            FillKind::Open if position.fills.first() == Some(fill) => JournalEventKind::Open,
+            FillKind::Open => JournalEventKind::Increase,
+            FillKind::Close if !position.is_open() && position.fills.last() == Some(fill) => {
+                JournalEventKind::Close
+            }
+            FillKind::Close => JournalEventKind::PartialClose,
+        };
+        self.record(fill_entry(kind, position, fill, realized_pnl))
+    }
+
+    /// Records the roll of `original` into the replacement leg of `roll`.
+    pub fn record_roll(&mut self, original: &Position, roll: &Roll) -> &JournalEntry {
+        self.record(JournalEntry {
+            id: uuid::Uuid::new_v4(),
+            timestamp: roll.replacement.date,
+            kind: JournalEventKind::Roll,
+            positions: vec![original.into(), (&roll.replacement).into()],
+            quantity: original.option.quantity,
+            fees: roll.fees,
+            cash_flow: roll.net_credit,
+            realized_pnl: None,
+            reference: Some(roll.id),
+            notes: Some(format!("{:?}", roll.kind)),
+        })
+    }
+
+    /// Records an adjustment applied to `position` at `timestamp`.
+    pub fn record_adjustment(
+        &mut self,
+        position: &Position,
+        adjustment: &SuggestedAdjustment,
+        timestamp: DateTime<Utc>,
+    ) -> &JournalEntry {
+        let quantity = position.option.quantity;
+        let (mut positions, fees, cash_flow, reference) = match &adjustment.action {
+            SuggestedAction::Roll(roll) => (
+                vec![(&roll.replacement).into()],
+                roll.fees,
+                roll.net_credit,
+                Some(roll.id),
+            ),
+            SuggestedAction::Close { close_price } => {
+                let fees = position.close_fee * quantity;
+                let premium = close_price.to_dec() * quantity.to_dec();
+                let cash_flow = match position.option.side {
+                    Side::Long => premium,
+                    Side::Short => -premium,
+                } - fees.to_dec();
+                (Vec::new(), fees, cash_flow, None)
+            }
+        };
+        positions.insert(0, position.into());
+        self.record(JournalEntry {
+            id: uuid::Uuid::new_v4(),
+            timestamp,
+            kind: JournalEventKind::Adjustment,
+            positions,
+            quantity,
+            fees,
+            cash_flow,
+            realized_pnl: None,
+            reference,
+            notes: Some(format!("{}: {}", adjustment.rule, adjustment.reason)),
+        })
+    }
+
+    /// Entries matching the query, oldest first.
+    pub fn query(&self, query: &JournalQuery) -> Vec<&JournalEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| query.matches(entry))
+            .collect()
+    }
+
+    /// Total fees of the entries matching the query.
+    pub fn total_fees(&self, query: &JournalQuery) -> Positive {
+        self.query(query).iter().map(|entry| entry.fees).sum()
+    }
+
+    /// Total realized P&L of the entries matching the query.
+    pub fn total_realized_pnl(&self, query: &JournalQuery) -> Decimal {
+        self.query(query)
+            .iter()
+            .filter_map(|entry| entry.realized_pnl)
+            .sum()
+    }
+
+    /// Total cash flow of the entries matching the query.
+    pub fn total_cash_flow(&self, query: &JournalQuery) -> Decimal {
+        self.query(query).iter().map(|entry| entry.cash_flow).sum()
+    }
+
+    /// Writes the journal as CSV, one row per entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `csv::Error` if writing fails.
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), csv::Error> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        wtr.write_record([
+            "Id",
+            "Timestamp",
+            "Kind",
+            "Positions",
+            "Quantity",
+            "Fees",
+            "Cash Flow",
+            "Realized PnL",
+            "Reference",
+            "Notes",
+        ])?;
+        for entry in &self.entries {
+            let positions = entry
+                .positions
+                .iter()
+                .map(|position| {
+                    format!(
+                        "{} {:?} {:?} {}",
+                        position.symbol, position.side, position.style, position.strike
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" | ");
+            wtr.write_record(&[
+                entry.id.to_string(),
+                entry.timestamp.to_rfc3339(),
+                format!("{:?}", entry.kind),
+                positions,
+                entry.quantity.to_string(),
+                entry.fees.to_string(),
+                entry.cash_flow.to_string(),
+                entry
+                    .realized_pnl
+                    .map(|pnl| pnl.to_string())
+                    .unwrap_or_default(),
+                entry
+                    .reference
+                    .map(|reference| reference.to_string())
+                    .unwrap_or_default(),
+                entry.notes.clone().unwrap_or_default(),
+            ])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// Saves the journal as CSV at `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `csv::Error` if the file cannot be created or written.
+    pub fn save_to_csv(&self, file_path: &str) -> Result<(), csv::Error> {
+        self.write_csv(File::create(file_path)?)
+    }
+
+    /// Saves the journal as JSON at `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `io::Error` if serialization fails or the file cannot be written.
+    pub fn save_to_json(&self, file_path: &str) -> io::Result<()> {
+        let json = serde_json::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        File::create(file_path)?.write_all(json.as_bytes())
+    }
+}
+
+/// Builds the entry of a fill of `position`.
+fn fill_entry(
+    kind: JournalEventKind,
+    position: &Position,
+    fill: &Fill,
+    realized_pnl: Option<Decimal>,
+) -> JournalEntry {
+    let fees = fill.fee * fill.quantity;
+    let premium = fill.price.to_dec() * fill.quantity.to_dec();
+    let cash_flow = match fill.action {
+        Action::Sell => premium,
+        _ => -premium,
+    } - fees.to_dec();
+    JournalEntry {
+        id: uuid::Uuid::new_v4(),
+        timestamp: fill.date,
+        kind,
+        positions: vec![position.into()],
+        quantity: fill.quantity,
+        fees,
+        cash_flow,
+        realized_pnl,
+        reference: None,
+        notes: None,
+    }
+}
+
+#[cfg(test)]
+mod tests_journal {
+    use super::*;
+    use crate::model::roll::RollTarget;
+    use crate::model::utils::create_sample_option_simplest;
+    use chrono::Duration;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn short_call(date: DateTime<Utc>) -> Position {
+        let mut option = create_sample_option_simplest(OptionStyle::Call, Side::Short);
+        option.quantity = pos_or_panic!(2.0);
+        Position::new(
+            option,
+            pos_or_panic!(5.0),
+            date,
+            pos_or_panic!(0.5),
+            pos_or_panic!(0.5),
+            Some("EPIC".to_string()),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_lifecycle_is_journaled_in_order() {
+        let start = Utc::now() - Duration::days(10);
+        let mut position = short_call(start);
+        let mut journal = Journal::new();
+        let open = journal.record_open(&position);
+        assert_eq!(open.kind, JournalEventKind::Open);
+        assert_eq!(open.cash_flow, dec!(9));
+        assert_eq!(open.positions[0].epic.as_deref(), Some("EPIC"));
+
+        let realized = position
+            .close_partial(Positive::ONE, pos_or_panic!(2.0), start + Duration::days(2))
+            .unwrap();
+        let fill = position.fills.last().unwrap().clone();
+        journal.record_fill(&position, &fill, Some(realized));
+        let realized = position
+            .close_all(pos_or_panic!(1.0), start + Duration::days(1))
+            .unwrap();
+        let fill = position.fills.last().unwrap().clone();
+        journal.record_fill(&position, &fill, Some(realized));
+
+        let kinds: Vec<_> = journal.entries().iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                JournalEventKind::Open,
+                JournalEventKind::Close,
+                JournalEventKind::PartialClose
+            ]
+        );
+        let everything = JournalQuery::new();
+        assert_eq!(journal.total_realized_pnl(&everything), dec!(5));
+        assert_eq!(journal.total_fees(&everything), pos_or_panic!(2.0));
+        assert_eq!(journal.total_cash_flow(&everything), dec!(5));
+    }
+
+    #[test]
+    fn test_roll_and_queries() {
+        let now = Utc::now();
+        let position = short_call(now - Duration::days(5));
+        let roll = position
+            .roll(
+                &RollTarget::to_strike(pos_or_panic!(110.0)),
+                pos_or_panic!(3.0),
+                pos_or_panic!(1.5),
+                now,
+            )
+            .unwrap();
+        let mut journal = Journal::new();
+        journal.record_open(&position);
+        let entry = journal.record_roll(&position, &roll).clone();
+        assert_eq!(entry.reference, Some(roll.id));
+        assert_eq!(entry.positions.len(), 2);
+        assert_eq!(entry.positions[1].strike, pos_or_panic!(110.0));
+        assert_eq!(entry.cash_flow, roll.net_credit);
+
+        let rolls = journal.query(&JournalQuery::new().kind(JournalEventKind::Roll));
+        assert_eq!(rolls.len(), 1);
+        assert!(
+            journal
+                .query(&JournalQuery::new().symbol("NONE"))
+                .is_empty()
+        );
+        let recent = JournalQuery::new().between(now - Duration::days(1), now);
+        assert_eq!(journal.query(&recent).len(), 1);
+
+        let mut csv = Vec::new();
+        journal.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.contains(&roll.id.to_string()));
+
+        let json = serde_json::to_string(&journal).unwrap();
+        let restored: Journal = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, journal);
+    }
+}
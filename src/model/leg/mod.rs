@@ -22,7 +22,7 @@
 //! ## Key Types
 //!
 //! - `Leg` - Unified enum for all leg types
-//! - `SpotPosition` - Spot/underlying asset position
+//! - `SpotPosition` - Spot/underlying asset position (`StockLeg` for shares)
 //! - `FuturePosition` - Futures contract position
 //! - `PerpetualPosition` - Perpetual swap position
 //! - `MarginType` - Cross vs Isolated margin mode
@@ -107,5 +107,5 @@ pub mod traits;
 pub use future::FuturePosition;
 pub use leg_enum::Leg;
 pub use perpetual::{MarginType, PerpetualPosition};
-pub use spot::SpotPosition;
+pub use spot::{SHARES_PER_CONTRACT, SpotPosition, StockLeg};
 pub use traits::{Expirable, Fundable, LegAble, Marginable};
@@ -44,6 +44,18 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+/// Number of shares of the underlying delivered by one standard equity option contract.
+///
+/// Strategies mixing shares and options express option quantities in shares,
+/// so per-contract fees are divided by this value.
+pub const SHARES_PER_CONTRACT: Positive = Positive::HUNDRED;
+
+/// A position in shares of the underlying stock.
+///
+/// Stock legs are plain spot positions; the alias makes equity strategies such
+/// as covered calls, protective puts and collars read naturally.
+pub type StockLeg = SpotPosition;
+
 /// Represents a spot/underlying asset position.
 ///
 /// A spot position represents direct ownership of an asset without any derivative
@@ -210,6 +222,22 @@ impl SpotPosition {
         }
     }
 
+    /// Returns the number of option contracts these units cover.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use optionstratlib::model::leg::SpotPosition;
+    /// use positive::{pos_or_panic, Positive};
+    ///
+    /// let shares = SpotPosition::long("AAPL".to_string(), pos_or_panic!(300.0), pos_or_panic!(150.0));
+    /// assert_eq!(shares.covered_contracts(), pos_or_panic!(3.0));
+    /// ```
+    #[must_use]
+    pub fn covered_contracts(&self) -> Positive {
+        self.quantity / SHARES_PER_CONTRACT
+    }
+
     /// Calculates the break-even price including fees.
     ///
     /// For long positions: cost_basis + (total_fees / quantity)
@@ -0,0 +1,350 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Position Lifecycle
+//!
+//! Tracks what happens to a [`Position`] after it is opened: contracts added
+//! at a new price, partial and full closes, and the P&L they realize.
+//!
+//! Every execution is recorded as a [`Fill`]. Adding contracts averages the
+//! premium and opening fee of the position; closing contracts realizes their
+//! P&L, computed like [`Position::unrealized_pnl`] so that the realized P&L
+//! plus the unrealized P&L of the open contracts always gives the total P&L.
+//! A position with no open contracts left is [`PositionStatus::Closed`].
+
+use crate::error::position::PositionError;
+use crate::model::position::Position;
+use crate::model::types::{Action, Side};
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Whether a position still holds open contracts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum PositionStatus {
+    /// Some contracts are still open.
+    #[default]
+    Open,
+    /// Every contract has been closed.
+    Closed,
+}
+
+/// Whether a fill opened or closed contracts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum FillKind {
+    /// The fill opened or added contracts.
+    Open,
+    /// The fill closed contracts.
+    Close,
+}
+
+/// An execution on a position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Fill {
+    /// Time of the execution.
+    #[serde(with = "crate::model::types::datetime_format")]
+    pub date: DateTime<Utc>,
+    /// Whether the fill opened or closed contracts.
+    pub kind: FillKind,
+    /// Whether the contracts were bought or sold.
+    pub action: Action,
+    /// Number of contracts.
+    pub quantity: Positive,
+    /// Price per contract.
+    pub price: Positive,
+    /// Fee per contract.
+    pub fee: Positive,
+}
+
+impl Fill {
+    /// Creates a fill on a position with the given side.
+    pub fn new(
+        side: Side,
+        kind: FillKind,
+        quantity: Positive,
+        price: Positive,
+        fee: Positive,
+        date: DateTime<Utc>,
+    ) -> Self {
+        let action = match (side, kind) {
+            (Side::Long, FillKind::Open) | (Side::Short, FillKind::Close) => Action::Buy,
+            (Side::Long, FillKind::Close) | (Side::Short, FillKind::Open) => Action::Sell,
+        };
+        Fill {
+            date,
+            kind,
+            action,
+            quantity,
+            price,
+            fee,
+        }
+    }
+}
+
+impl Position {
+    /// Returns `true` while the position holds open contracts.
+    pub fn is_open(&self) -> bool {
+        self.status == PositionStatus::Open
+    }
+
+    /// Number of contracts closed so far.
+    pub fn closed_quantity(&self) -> Positive {
+        self.fills
+            .iter()
+            .filter(|fill| fill.kind == FillKind::Close)
+            .map(|fill| fill.quantity)
+            .sum()
+    }
+
+    /// Adds contracts to an open position.
+    ///
+    /// The premium and opening fee of the position become the averages of the
+    /// existing and new contracts, weighted by quantity.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the position is closed or `quantity` is zero.
+    pub fn increase(
+        &mut self,
+        quantity: Positive,
+        price: Positive,
+        fee: Positive,
+        date: DateTime<Utc>,
+    ) -> Result<(), PositionError> {
+        if !self.is_open() {
+            return Err(PositionError::invalid_position(
+                "Cannot add contracts to a closed position",
+            ));
+        }
+        if quantity == Positive::ZERO {
+            return Err(PositionError::invalid_position_size(
+                0.0,
+                "Quantity to add must be greater than zero",
+            ));
+        }
+
+        let current = self.option.quantity;
+        let total = current + quantity;
+        self.premium = (self.premium * current + price * quantity) / total;
+        self.open_fee = (self.open_fee * current + fee * quantity) / total;
+        self.option.quantity = total;
+        self.fills.push(Fill::new(
+            self.option.side,
+            FillKind::Open,
+            quantity,
+            price,
+            fee,
+            date,
+        ));
+        Ok(())
+    }
+
+    /// Closes part of the position and returns the P&L it realizes.
+    ///
+    /// The closed contracts are charged their share of the opening fee and the
+    /// closing fee of the position.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the position is closed, or `quantity` is
+    /// zero or larger than the open quantity.
+    pub fn close_partial(
+        &mut self,
+        quantity: Positive,
+        price: Positive,
+        date: DateTime<Utc>,
+    ) -> Result<Decimal, PositionError> {
+        if !self.is_open() {
+            return Err(PositionError::invalid_position(
+                "Cannot close contracts of a closed position",
+            ));
+        }
+        if quantity == Positive::ZERO || quantity > self.option.quantity {
+            return Err(PositionError::invalid_position_size(
+                quantity.to_f64(),
+                &format!(
+                    "Quantity to close must be between zero and the open quantity {}",
+                    self.option.quantity
+                ),
+            ));
+        }
+
+        let per_contract = match self.option.side {
+            Side::Long => price.to_dec() - self.premium.to_dec(),
+            Side::Short => self.premium.to_dec() - price.to_dec(),
+        } - self.open_fee.to_dec()
+            - self.close_fee.to_dec();
+        let realized = per_contract * quantity.to_dec();
+
+        self.realized_pnl += realized;
+        self.option.quantity = self.option.quantity - quantity;
+        if self.option.quantity == Positive::ZERO {
+            self.status = PositionStatus::Closed;
+        }
+        self.fills.push(Fill::new(
+            self.option.side,
+            FillKind::Close,
+            quantity,
+            price,
+            self.close_fee,
+            date,
+        ));
+        Ok(realized)
+    }
+
+    /// Closes every open contract and returns the P&L it realizes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the position is already closed.
+    pub fn close_all(
+        &mut self,
+        price: Positive,
+        date: DateTime<Utc>,
+    ) -> Result<Decimal, PositionError> {
+        self.close_partial(self.option.quantity, price, date)
+    }
+
+    /// Realized P&L plus the unrealized P&L of the open contracts at `price`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the unrealized P&L cannot be computed.
+    pub fn total_pnl(&self, price: Positive) -> Result<Decimal, PositionError> {
+        Ok(self.realized_pnl + self.unrealized_pnl(price)?)
+    }
+}
+
+#[cfg(test)]
+mod tests_lifecycle {
+    use super::*;
+    use crate::model::types::OptionStyle;
+    use crate::model::utils::create_sample_option_simplest;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn short_put() -> Position {
+        let mut option = create_sample_option_simplest(OptionStyle::Put, Side::Short);
+        option.quantity = pos_or_panic!(4.0);
+        Position::new(
+            option,
+            pos_or_panic!(5.0),
+            Utc::now(),
+            pos_or_panic!(0.5),
+            pos_or_panic!(0.5),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_lifecycle_serde_roundtrip() {
+        use crate::model::currency::Currency;
+        use chrono::TimeZone;
+
+        let opened = Utc.with_ymd_and_hms(2026, 3, 2, 14, 30, 0).unwrap();
+        let closed = opened + chrono::Duration::milliseconds(86_400_123);
+        let mut position = short_put();
+        position.date = opened;
+        position.fills[0].date = opened;
+        position.currency = Currency::new("eur");
+        position
+            .close_partial(pos_or_panic!(1.0), pos_or_panic!(2.0), closed)
+            .unwrap();
+
+        let value = serde_json::to_value(&position).unwrap();
+        assert_eq!(value["date"], "2026-03-02T14:30:00+00:00");
+        assert_eq!(value["fills"][1]["date"], "2026-03-03T14:30:00.123+00:00");
+        let restored: Position = serde_json::from_value(value).unwrap();
+        assert_eq!(restored, position);
+
+        let offset = r#""2026-03-02T15:30:00+01:00""#;
+        let mut document = serde_json::to_value(&position).unwrap();
+        document["date"] = serde_json::from_str(offset).unwrap();
+        let restored: Position = serde_json::from_value(document).unwrap();
+        assert_eq!(restored.date, opened);
+    }
+
+    #[test]
+    fn test_new_position_records_opening_fill() {
+        let position = short_put();
+        assert!(position.is_open());
+        assert_eq!(position.fills.len(), 1);
+        assert_eq!(position.fills[0].kind, FillKind::Open);
+        assert_eq!(position.fills[0].action, Action::Sell);
+        assert_eq!(position.fills[0].quantity, pos_or_panic!(4.0));
+        assert_eq!(position.realized_pnl, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_partial_close_then_full_close() {
+        let mut position = short_put();
+        let total_before = position.total_pnl(pos_or_panic!(3.0)).unwrap();
+
+        let realized = position
+            .close_partial(Positive::ONE, pos_or_panic!(3.0), Utc::now())
+            .unwrap();
+        // (5 - 3 - 0.5 - 0.5) on one contract.
+        assert_eq!(realized, dec!(1));
+        assert_eq!(position.option.quantity, pos_or_panic!(3.0));
+        assert_eq!(position.fills[1].action, Action::Buy);
+        assert_eq!(
+            position.total_pnl(pos_or_panic!(3.0)).unwrap(),
+            total_before
+        );
+
+        let realized = position.close_all(pos_or_panic!(7.0), Utc::now()).unwrap();
+        assert_eq!(realized, dec!(-9));
+        assert_eq!(position.realized_pnl, dec!(-8));
+        assert_eq!(position.status, PositionStatus::Closed);
+        assert_eq!(position.closed_quantity(), pos_or_panic!(4.0));
+        assert_eq!(
+            position.unrealized_pnl(pos_or_panic!(7.0)).unwrap(),
+            Decimal::ZERO
+        );
+
+        assert!(
+            position
+                .close_partial(Positive::ONE, pos_or_panic!(1.0), Utc::now())
+                .is_err()
+        );
+        assert!(
+            position
+                .increase(
+                    Positive::ONE,
+                    pos_or_panic!(1.0),
+                    Positive::ZERO,
+                    Utc::now()
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_increase_averages_premium_and_fee() {
+        let mut position = short_put();
+        position
+            .increase(
+                pos_or_panic!(4.0),
+                pos_or_panic!(7.0),
+                Positive::ZERO,
+                Utc::now(),
+            )
+            .unwrap();
+        assert_eq!(position.option.quantity, pos_or_panic!(8.0));
+        assert_eq!(position.premium, pos_or_panic!(6.0));
+        assert_eq!(position.open_fee, pos_or_panic!(0.25));
+        assert_eq!(position.fills.len(), 2);
+
+        assert!(
+            position
+                .close_partial(pos_or_panic!(9.0), pos_or_panic!(1.0), Utc::now())
+                .is_err()
+        );
+    }
+}
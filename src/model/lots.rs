@@ -0,0 +1,305 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Lot Accounting
+//!
+//! Tracks every opening fill of a contract as a separate [`Lot`] with its own
+//! price and fee, and matches closing fills against the lots in FIFO or LIFO
+//! order. Each match produces a [`LotClose`] with the P&L realized by that
+//! lot, computed like [`Position::close_partial`]: the price difference
+//! signed by the side of the position, less the opening fee of the lot and
+//! the closing fee of the fill.
+//!
+//! A [`LotLedger`] can be fed fills as they happen, or rebuilt at any time
+//! from the fill history of a position with [`Position::lots`].
+
+use crate::error::position::PositionError;
+use crate::model::lifecycle::{Fill, FillKind};
+use crate::model::position::Position;
+use crate::model::types::Side;
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Order in which closing fills consume the open lots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum LotMethod {
+    /// First in, first out: the oldest lots are closed first.
+    #[default]
+    Fifo,
+    /// Last in, first out: the newest lots are closed first.
+    Lifo,
+}
+
+/// Contracts opened by a single fill.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Lot {
+    /// When the lot was opened.
+    pub opened: DateTime<Utc>,
+    /// Contracts opened by the fill.
+    pub original_quantity: Positive,
+    /// Contracts of the lot still open.
+    pub quantity: Positive,
+    /// Opening price per contract.
+    pub price: Positive,
+    /// Opening fee per contract.
+    pub fee: Positive,
+}
+
+/// Contracts of one lot closed by a closing fill.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LotClose {
+    /// When the lot was opened.
+    pub opened: DateTime<Utc>,
+    /// When the contracts were closed.
+    pub closed: DateTime<Utc>,
+    /// Contracts closed.
+    pub quantity: Positive,
+    /// Opening price per contract.
+    pub open_price: Positive,
+    /// Closing price per contract.
+    pub close_price: Positive,
+    /// P&L realized by the closed contracts, after fees.
+    pub realized_pnl: Decimal,
+}
+
+/// Open lots and realized closes of one contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LotLedger {
+    /// Side of the position the lots belong to.
+    pub side: Side,
+    /// Order in which closing fills consume the lots.
+    pub method: LotMethod,
+    /// Lots with open contracts, oldest first.
+    pub lots: Vec<Lot>,
+    /// Every lot close, in the order the closes happened.
+    pub closes: Vec<LotClose>,
+}
+
+impl LotLedger {
+    /// Creates an empty ledger.
+    pub fn new(side: Side, method: LotMethod) -> Self {
+        LotLedger {
+            side,
+            method,
+            lots: Vec::new(),
+            closes: Vec::new(),
+        }
+    }
+
+    /// Replays a fill history into a new ledger.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if a closing fill exceeds the open contracts.
+    pub fn from_fills(
+        side: Side,
+        method: LotMethod,
+        fills: &[Fill],
+    ) -> Result<Self, PositionError> {
+        let mut ledger = LotLedger::new(side, method);
+        for fill in fills {
+            ledger.apply(fill)?;
+        }
+        Ok(ledger)
+    }
+
+    /// Applies a fill, opening a lot or closing contracts of the open lots.
+    ///
+    /// # Returns
+    ///
+    /// The lot closes produced by the fill, empty for an opening fill.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if a closing fill exceeds the open contracts.
+    pub fn apply(&mut self, fill: &Fill) -> Result<Vec<LotClose>, PositionError> {
+        match fill.kind {
+            FillKind::Open => {
+                self.open(fill.quantity, fill.price, fill.fee, fill.date);
+                Ok(Vec::new())
+            }
+            FillKind::Close => self.close(fill.quantity, fill.price, fill.fee, fill.date),
+        }
+    }
+
+    /// Opens a new lot.
+    pub fn open(
+        &mut self,
+        quantity: Positive,
+        price: Positive,
+        fee: Positive,
+        opened: DateTime<Utc>,
+    ) {
+        if quantity > Positive::ZERO {
+            self.lots.push(Lot {
+                opened,
+                original_quantity: quantity,
+                quantity,
+                price,
+                fee,
+            });
+        }
+    }
+
+    /// Closes `quantity` contracts at `price`, consuming lots in the order of
+    /// the ledger method.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if `quantity` exceeds the open contracts.
+    pub fn close(
+        &mut self,
+        quantity: Positive,
+        price: Positive,
+        fee: Positive,
+        closed: DateTime<Utc>,
+    ) -> Result<Vec<LotClose>, PositionError> {
+        if quantity > self.open_quantity() {
+            return Err(PositionError::invalid_position_size(
+                quantity.to_f64(),
+                &format!(
+                    "Cannot close more than the {} open contracts",
+                    self.open_quantity()
+                ),
+            ));
+        }
+
+        let mut remaining = quantity;
+        let mut closes = Vec::new();
+        while remaining > Positive::ZERO {
+            let index = match self.method {
+                LotMethod::Fifo => 0,
+                LotMethod::Lifo => self.lots.len() - 1,
+            };
+            let lot = &mut self.lots[index];
+            let matched = remaining.min(lot.quantity);
+            let per_contract = match self.side {
+                Side::Long => price.to_dec() - lot.price.to_dec(),
+                Side::Short => lot.price.to_dec() - price.to_dec(),
+            } - lot.fee.to_dec()
+                - fee.to_dec();
+            closes.push(LotClose {
+                opened: lot.opened,
+                closed,
+                quantity: matched,
+                open_price: lot.price,
+                close_price: price,
+                realized_pnl: per_contract * matched.to_dec(),
+            });
+
+            lot.quantity = lot.quantity - matched;
+            remaining = remaining - matched;
+            if lot.quantity == Positive::ZERO {
+                self.lots.remove(index);
+            }
+        }
+        self.closes.extend(closes.iter().cloned());
+        Ok(closes)
+    }
+
+    /// Contracts still open across all lots.
+    pub fn open_quantity(&self) -> Positive {
+        self.lots.iter().map(|lot| lot.quantity).sum()
+    }
+
+    /// Total P&L realized by the lot closes.
+    pub fn realized_pnl(&self) -> Decimal {
+        self.closes.iter().map(|close| close.realized_pnl).sum()
+    }
+}
+
+impl Position {
+    /// Rebuilds the lots of the position from its fill history.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the fill history closes more contracts
+    /// than it opens.
+    pub fn lots(&self, method: LotMethod) -> Result<LotLedger, PositionError> {
+        LotLedger::from_fills(self.option.side, method, &self.fills)
+    }
+}
+
+#[cfg(test)]
+mod tests_lots {
+    use super::*;
+    use crate::model::types::OptionStyle;
+    use crate::model::utils::create_sample_option_simplest;
+    use chrono::Duration;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn scaled_in_long_call() -> Position {
+        let start = Utc::now() - Duration::days(3);
+        let mut option = create_sample_option_simplest(OptionStyle::Call, Side::Long);
+        option.quantity = Positive::TWO;
+        let mut position = Position::new(
+            option,
+            pos_or_panic!(5.0),
+            start,
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        );
+        position
+            .increase(
+                Positive::TWO,
+                pos_or_panic!(7.0),
+                Positive::ZERO,
+                start + Duration::days(1),
+            )
+            .unwrap();
+        position
+            .close_partial(pos_or_panic!(3.0), pos_or_panic!(8.0), Utc::now())
+            .unwrap();
+        position
+    }
+
+    #[test]
+    fn test_fifo_closes_oldest_lot_first() {
+        let position = scaled_in_long_call();
+        let ledger = position.lots(LotMethod::Fifo).unwrap();
+        assert_eq!(ledger.closes.len(), 2);
+        assert_eq!(ledger.closes[0].open_price, pos_or_panic!(5.0));
+        assert_eq!(ledger.closes[0].realized_pnl, dec!(6));
+        assert_eq!(ledger.closes[1].open_price, pos_or_panic!(7.0));
+        assert_eq!(ledger.closes[1].realized_pnl, dec!(1));
+        assert_eq!(ledger.realized_pnl(), dec!(7));
+        assert_eq!(ledger.open_quantity(), Positive::ONE);
+        assert_eq!(ledger.lots[0].price, pos_or_panic!(7.0));
+    }
+
+    #[test]
+    fn test_lifo_closes_newest_lot_first() {
+        let position = scaled_in_long_call();
+        let ledger = position.lots(LotMethod::Lifo).unwrap();
+        assert_eq!(ledger.realized_pnl(), dec!(5));
+        assert_eq!(ledger.lots.len(), 1);
+        assert_eq!(ledger.lots[0].price, pos_or_panic!(5.0));
+        assert_eq!(ledger.lots[0].original_quantity, Positive::TWO);
+    }
+
+    #[test]
+    fn test_short_lots_with_fees_and_overclose() {
+        let now = Utc::now();
+        let mut ledger = LotLedger::new(Side::Short, LotMethod::Fifo);
+        ledger.open(Positive::ONE, pos_or_panic!(4.0), pos_or_panic!(0.5), now);
+        let closes = ledger
+            .close(Positive::ONE, pos_or_panic!(1.0), pos_or_panic!(0.5), now)
+            .unwrap();
+        assert_eq!(closes[0].realized_pnl, dec!(2));
+        assert!(ledger.lots.is_empty());
+        assert!(
+            ledger
+                .close(Positive::ONE, Positive::ONE, Positive::ZERO, now)
+                .is_err()
+        );
+    }
+}
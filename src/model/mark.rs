@@ -0,0 +1,257 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Mark-to-Market
+//!
+//! Revalues a [`Position`] against a fresh [`OptionChain`]. The mark is the
+//! quote of the contract in the chain, priced with a [`QuotePricing`]
+//! convention (the mid by default). When the chain has no quote for the
+//! strike of the position, the contract is priced with Black-Scholes using
+//! the underlying price of the chain and the implied volatility of the strike,
+//! or of the at-the-money strike if the position strike is not listed.
+//!
+//! The chain is expected to hold the expiration of the position.
+
+use crate::chains::OptionChain;
+use crate::chains::OptionData;
+use crate::error::position::PositionError;
+use crate::model::QuotePricing;
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, Side};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Where the mark price of a position comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum MarkSource {
+    /// Bid and ask quotes of the contract.
+    Quote,
+    /// Mid price published in the chain, without bid and ask.
+    Mid,
+    /// Black-Scholes price with the market data of the chain.
+    Model,
+}
+
+/// Valuation of a position at current market prices.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PositionMark {
+    /// Price of one contract.
+    pub price: Positive,
+    /// Where the price comes from.
+    pub source: MarkSource,
+    /// Underlying price used for the valuation.
+    pub underlying_price: Positive,
+    /// Value of the position: positive for long positions, negative for
+    /// short ones, which would have to be bought back.
+    pub market_value: Decimal,
+    /// Unrealized P&L of the open contracts at the mark price.
+    pub unrealized_pnl: Decimal,
+    /// Days since the position was opened.
+    pub days_held: Positive,
+}
+
+impl Position {
+    /// Marks the position to the mid price of its contract in `chain`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the contract has no quote and cannot be
+    /// priced with the model.
+    pub fn mark(&self, chain: &OptionChain) -> Result<PositionMark, PositionError> {
+        self.mark_with(chain, &QuotePricing::Mid)
+    }
+
+    /// Marks the position to the price at which it could be closed in
+    /// `chain` under the given quote pricing convention.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the contract has no quote and cannot be
+    /// priced with the model.
+    pub fn mark_with(
+        &self,
+        chain: &OptionChain,
+        pricing: &QuotePricing,
+    ) -> Result<PositionMark, PositionError> {
+        let listed = chain
+            .get_optiondata_with_strike(&self.option.strike_price)
+            .ok()
+            .filter(|data| data.strike_price == self.option.strike_price);
+
+        let quoted = listed.and_then(|data| self.quote(data, pricing));
+        let (price, source) = match quoted {
+            Some(quoted) => quoted,
+            None => (self.model_price(chain, listed)?, MarkSource::Model),
+        };
+
+        let quantity = self.option.quantity.to_dec();
+        let market_value = match self.option.side {
+            Side::Long => price.to_dec() * quantity,
+            Side::Short => -price.to_dec() * quantity,
+        };
+        Ok(PositionMark {
+            price,
+            source,
+            underlying_price: chain.underlying_price,
+            market_value,
+            unrealized_pnl: self.unrealized_pnl(price)?,
+            days_held: self.days_held()?,
+        })
+    }
+
+    /// Price of the contract from the quotes of the chain, if there are any.
+    fn quote(&self, data: &OptionData, pricing: &QuotePricing) -> Option<(Positive, MarkSource)> {
+        let (bid, ask, middle) = match self.option.option_style {
+            OptionStyle::Call => (data.call_bid, data.call_ask, data.call_middle),
+            OptionStyle::Put => (data.put_bid, data.put_ask, data.put_middle),
+        };
+        match (bid, ask, middle) {
+            (Some(bid), Some(ask), _) => Some((
+                pricing.exit_price(bid, ask, self.option.side),
+                MarkSource::Quote,
+            )),
+            (_, _, Some(middle)) => Some((middle, MarkSource::Mid)),
+            _ => None,
+        }
+    }
+
+    /// Black-Scholes price of the contract with the market data of the chain.
+    fn model_price(
+        &self,
+        chain: &OptionChain,
+        listed: Option<&OptionData>,
+    ) -> Result<Positive, PositionError> {
+        let mut option = self.option.clone();
+        option.underlying_price = chain.underlying_price;
+        if let Some(data) = listed {
+            option.implied_volatility = data.implied_volatility;
+        } else if let Ok(atm_volatility) = chain.get_atm_implied_volatility() {
+            option.implied_volatility = *atm_volatility;
+        }
+        let price = option
+            .calculate_price_black_scholes()
+            .map_err(|e| PositionError::invalid_position(&e.to_string()))?;
+        Positive::new_decimal(price.abs())
+            .map_err(|e| PositionError::invalid_position(&e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests_mark {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::Options;
+    use crate::model::types::OptionType;
+    use chrono::{Duration, Utc};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn chain() -> OptionChain {
+        let mut chain = OptionChain::new(
+            "TEST",
+            Positive::HUNDRED,
+            "2030-01-01".to_string(),
+            None,
+            None,
+        );
+        chain.add_option(
+            Positive::HUNDRED,
+            Some(pos_or_panic!(4.0)),
+            Some(pos_or_panic!(4.4)),
+            Some(pos_or_panic!(3.0)),
+            Some(pos_or_panic!(3.2)),
+            pos_or_panic!(0.2),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        chain.add_option(
+            pos_or_panic!(105.0),
+            None,
+            None,
+            None,
+            None,
+            pos_or_panic!(0.25),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        chain
+    }
+
+    fn position(strike: Positive, style: OptionStyle, side: Side) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.3),
+            Positive::TWO,
+            pos_or_panic!(90.0),
+            Decimal::ZERO,
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            pos_or_panic!(3.5),
+            Utc::now() - Duration::days(5),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_mark_to_quote() {
+        let long_call = position(Positive::HUNDRED, OptionStyle::Call, Side::Long);
+        let mark = long_call.mark(&chain()).unwrap();
+        assert_eq!(mark.source, MarkSource::Quote);
+        assert_eq!(mark.price, pos_or_panic!(4.2));
+        assert_eq!(mark.market_value, dec!(8.4));
+        assert_eq!(mark.unrealized_pnl, dec!(1.4));
+        assert_eq!(mark.days_held, pos_or_panic!(5.0));
+
+        let short_put = position(Positive::HUNDRED, OptionStyle::Put, Side::Short);
+        let mark = short_put
+            .mark_with(&chain(), &QuotePricing::Natural)
+            .unwrap();
+        assert_eq!(mark.price, pos_or_panic!(3.2));
+        assert_eq!(mark.market_value, dec!(-6.4));
+        assert_eq!(mark.unrealized_pnl, dec!(0.6));
+    }
+
+    #[test]
+    fn test_mark_falls_back_to_model() {
+        let chain = chain();
+        let unquoted = position(pos_or_panic!(105.0), OptionStyle::Call, Side::Long);
+        let mark = unquoted.mark(&chain).unwrap();
+        assert_eq!(mark.source, MarkSource::Model);
+        assert_eq!(mark.underlying_price, Positive::HUNDRED);
+
+        let mut expected = unquoted.option.clone();
+        expected.underlying_price = Positive::HUNDRED;
+        expected.implied_volatility = pos_or_panic!(0.25);
+        let expected = expected.calculate_price_black_scholes().unwrap();
+        assert_eq!(mark.price.to_dec(), expected);
+
+        let unlisted = position(pos_or_panic!(110.0), OptionStyle::Call, Side::Long);
+        let mark = unlisted.mark(&chain).unwrap();
+        assert_eq!(mark.source, MarkSource::Model);
+        assert!(mark.price > Positive::ZERO);
+    }
+}
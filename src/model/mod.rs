@@ -95,29 +95,89 @@
 //! info!("Debug View: {:?}", option);
 //! ```
 
+/// Validated correlation matrices of several underlyings.
+pub mod correlation;
+
+/// Currencies of positions and FX rates to convert between them.
+pub mod currency;
+
 /// Core utilities for handling decimal numbers in financial calculations.
 pub mod decimal;
 
+/// Exercise, assignment and expiration of option legs into cash or shares.
+pub mod exercise;
+
+/// Scheduled earnings, dividend and macro events on a simulation timeline.
+pub mod events;
+
+/// Ingestion of FIX 4.4 execution reports into position fills.
+pub mod fix;
 /// Formatting utilities for displaying financial data and calculations.
 mod format;
 
+/// Import of Interactive Brokers Flex reports into portfolios.
+#[cfg(feature = "ibkr")]
+pub mod ibkr;
+
+/// Chronological ledger of opens, closes, rolls and adjustments.
+pub mod journal;
+
+/// OCC symbology of US listed options.
+pub mod occ;
+
+/// Fill history, partial closes and realized P&L of positions.
+pub mod lifecycle;
+
+/// FIFO/LIFO lot accounting with per-lot realized P&L.
+pub mod lots;
+
+/// Mark-to-market valuation of positions against an option chain.
+pub mod mark;
+
 /// Components for options contract modeling and analysis, including Greeks and pricing models.
 pub mod option;
 
+/// Validating builder for options contracts.
+pub mod option_builder;
+
+/// Invariants of options contracts.
+mod option_validation;
+
+/// Portfolio valuation, grouping, net Greeks and margin across underlyings.
+pub mod portfolio;
+
 /// Definitions and utilities for managing trading positions, including risk metrics and exposure tracking.
 pub mod position;
 
+/// Builder for positions with explicit fee semantics.
+pub mod position_builder;
+
+/// CSV export and import of positions and strategies, one row per leg.
+pub mod position_csv;
+
 mod positive_ext;
 
 /// Tools for analyzing and visualizing profit ranges across different market scenarios.
 mod profit_range;
 
+/// Bid/ask aware execution pricing conventions (mid, natural, slippage).
+mod quote_pricing;
+
+/// Rolling positions to a new strike and/or expiration with linked trades.
+pub mod roll;
+
+/// Import of Tastytrade and thinkorswim CSV trade histories.
+pub mod trade_history;
+
 /// Common type definitions used throughout the options strategy library.
 pub mod types;
 
 /// Utility functions supporting various operations across the library.
 pub mod utils;
 
+/// Schema-versioned JSON persistence that preserves unknown fields.
+pub mod versioned;
+
 /// Components for defining and working with chart axes in strategy visualizations.
 mod axis;
 
@@ -130,10 +190,32 @@ mod trade;
 
 pub use axis::BasicAxisTypes;
 pub use balance::*;
+pub use correlation::CorrelationMatrix;
+pub use currency::{Currency, FxRateProvider, FxRates};
+pub use events::{ClockTick, EventClock, EventTimeline, MarketEvent, MarketEventKind};
+pub use exercise::{SettlementAction, SettlementEvent};
 pub use expiration::ExpirationDate;
 pub use expiration::ExpirationDateError;
+pub use fix::{ExecutionReport, ExecutionReportAdapter, Fix44Adapter, FixMessage};
+#[cfg(feature = "ibkr")]
+pub use ibkr::{FlexImporter, FlexOpenPosition, FlexReport, FlexTrade};
+pub use journal::{Journal, JournalEntry, JournalEventKind, JournalQuery, PositionRef};
+pub use lifecycle::{Fill, FillKind, PositionStatus};
+pub use lots::{Lot, LotClose, LotLedger, LotMethod};
+pub use mark::{MarkSource, PositionMark};
+pub use occ::OccSymbol;
 pub use option::Options;
+pub use option_builder::OptionsBuilder;
 pub use position::Position;
+pub use position_builder::{FeeBasis, PositionBuilder};
+pub use position_csv::{
+    POSITION_CSV_HEADERS, read_positions_csv, read_strategies_csv, write_positions_csv,
+    write_strategies_csv,
+};
 pub use profit_range::ProfitLossRange;
+pub use quote_pricing::QuotePricing;
+pub use roll::{Roll, RollKind, RollTarget, StrikeAdjustment};
 pub use trade::{Trade, TradeAble, TradeStatus, TradeStatusAble, save_trades};
-pub use types::{OptionStyle, OptionType, RainbowType, Side};
+pub use trade_history::{OptionDescription, TradeHistoryFormat, TradeHistoryImporter};
+pub use types::{DayCount, OptionStyle, OptionType, RainbowType, SettlementType, Side};
+pub use versioned::{SCHEMA_VERSION, Versioned, VersionedJson};
@@ -0,0 +1,148 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # OCC Option Symbols
+//!
+//! Reads and writes the OCC (Options Clearing Corporation) symbology of US
+//! listed options, used by brokers and clearing firms to identify contracts:
+//!
+//! ```text
+//! AAPL  261218C00152500
+//! └root┘└date┘│└strike┘
+//!             └ C or P
+//! ```
+//!
+//! The root is padded with spaces to six characters, the expiration is
+//! written as `YYMMDD`, and the strike in thousandths of a dollar on eight
+//! digits. Symbols without the padding are accepted when parsing.
+
+use crate::error::PositionError;
+use crate::model::types::OptionStyle;
+use chrono::NaiveDate;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use std::fmt;
+use std::str::FromStr;
+
+/// Contract identified by an OCC option symbol.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OccSymbol {
+    /// Option root, usually the symbol of the underlying.
+    pub root: String,
+    /// Expiration date.
+    pub expiration: NaiveDate,
+    /// Call or put.
+    pub style: OptionStyle,
+    /// Strike price.
+    pub strike: Positive,
+}
+
+impl OccSymbol {
+    /// Creates the symbol of a contract.
+    pub fn new(
+        root: impl Into<String>,
+        expiration: NaiveDate,
+        style: OptionStyle,
+        strike: Positive,
+    ) -> Self {
+        OccSymbol {
+            root: root.into(),
+            expiration,
+            style,
+            strike,
+        }
+    }
+}
+
+impl FromStr for OccSymbol {
+    type Err = PositionError;
+
+    fn from_str(symbol: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || PositionError::invalid_position(&format!("'{symbol}' is not an OCC option symbol"));
+        let trimmed = symbol.trim();
+        if trimmed.len() < 16 || !trimmed.is_ascii() {
+            return Err(invalid());
+        }
+        let (rest, strike) = trimmed.split_at(trimmed.len() - 8);
+        let (rest, style) = rest.split_at(rest.len() - 1);
+        let (root, date) = rest.split_at(rest.len() - 6);
+        let style = match style {
+            "C" => OptionStyle::Call,
+            "P" => OptionStyle::Put,
+            _ => return Err(invalid()),
+        };
+        let strike = strike
+            .parse::<i64>()
+            .ok()
+            .and_then(|strike| Positive::new_decimal(Decimal::new(strike, 3)).ok())
+            .ok_or_else(invalid)?;
+        let expiration = NaiveDate::parse_from_str(date, "%y%m%d").map_err(|_| invalid())?;
+        let root = root.trim();
+        if root.is_empty() || root.len() > 6 {
+            return Err(invalid());
+        }
+        Ok(OccSymbol::new(root, expiration, style, strike))
+    }
+}
+
+impl fmt::Display for OccSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let style = match self.style {
+            OptionStyle::Call => 'C',
+            OptionStyle::Put => 'P',
+        };
+        let strike = (self.strike.to_dec() * Decimal::ONE_THOUSAND)
+            .round()
+            .to_u64()
+            .unwrap_or_default();
+        write!(
+            f,
+            "{:<6}{}{}{:08}",
+            self.root,
+            self.expiration.format("%y%m%d"),
+            style,
+            strike
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests_occ {
+    use super::*;
+    use positive::pos_or_panic;
+
+    #[test]
+    fn test_roundtrip() {
+        let symbol: OccSymbol = "AAPL  261218C00152500".parse().unwrap();
+        assert_eq!(symbol.root, "AAPL");
+        assert_eq!(
+            symbol.expiration,
+            NaiveDate::from_ymd_opt(2026, 12, 18).unwrap()
+        );
+        assert_eq!(symbol.style, OptionStyle::Call);
+        assert_eq!(symbol.strike, pos_or_panic!(152.5));
+        assert_eq!(symbol.to_string(), "AAPL  261218C00152500");
+
+        let compact: OccSymbol = "SPXW261218P05800000".parse().unwrap();
+        assert_eq!(compact.root, "SPXW");
+        assert_eq!(compact.strike, pos_or_panic!(5800.0));
+        assert_eq!(compact.to_string(), "SPXW  261218P05800000");
+    }
+
+    #[test]
+    fn test_invalid_symbols() {
+        for symbol in [
+            "AAPL",
+            "AAPL  261218X00152500",
+            "AAPL  261318C00152500",
+            "261218C00152500",
+        ] {
+            assert!(symbol.parse::<OccSymbol>().is_err(), "{symbol}");
+        }
+    }
+}
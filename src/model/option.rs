@@ -1,11 +1,15 @@
 use crate::ExpirationDate;
 use crate::chains::OptionData;
-use crate::constants::{IV_TOLERANCE, MAX_ITERATIONS_IV, ZERO};
+use crate::constants::{IV_TOLERANCE, MAX_ITERATIONS_IV};
 use crate::error::{
     GreeksError, OptionsError, OptionsResult, PricingError, StrategyError, VolatilityError,
 };
+use crate::f2du;
 use crate::greeks::Greeks;
-use crate::model::types::{OptionBasicType, OptionStyle, OptionType, Side};
+use crate::model::correlation::CorrelationMatrix;
+use crate::model::types::{
+    DayCount, OptionBasicType, OptionStyle, OptionType, SettlementType, Side,
+};
 use crate::model::utils::calculate_optimal_price_range;
 use crate::pnl::utils::{PnL, PnLCalculator};
 use crate::pricing::monte_carlo::price_option_monte_carlo;
@@ -23,7 +27,7 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use tracing::{error, trace};
+use tracing::trace;
 use utoipa::ToSchema;
 
 /// Result type for binomial tree pricing models, containing:
@@ -39,7 +43,10 @@ type PriceBinomialTree = OptionsResult<(Decimal, Vec<Vec<Decimal>>, Vec<Vec<Deci
 /// (which depend on minimum/maximum prices during the option's lifetime).
 ///
 /// Each field is optional since different exotic option types require different parameters.
+/// Unknown fields are rejected, so documents still carrying the pairwise correlations
+/// replaced by `correlation` fail to load instead of losing them.
 #[derive(Clone, Default, PartialEq, Serialize, Deserialize, Debug, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct ExoticParams {
     /// Historical spot prices, primarily used for Asian options which
     /// depend on the average price of the underlying asset.
@@ -74,27 +81,15 @@ pub struct ExoticParams {
     /// Dividend yield of the second underlying asset for Rainbow options.
     pub rainbow_second_asset_dividend: Option<Positive>, // Rainbow
 
-    /// Correlation between the two underlying assets for Rainbow options.
-    /// Must be between -1.0 and 1.0.
-    pub rainbow_correlation: Option<Decimal>, // Rainbow
-
     /// Volatility of the second underlying asset for Spread options.
     pub spread_second_asset_volatility: Option<Positive>, // Spread
 
     /// Dividend yield of the second underlying asset for Spread options.
     pub spread_second_asset_dividend: Option<Positive>, // Spread
 
-    /// Correlation between the two underlying assets for Spread options.
-    /// Must be between -1.0 and 1.0.
-    pub spread_correlation: Option<Decimal>, // Spread
-
     /// Volatility of the exchange rate for Quanto options.
     pub quanto_fx_volatility: Option<Positive>, // Quanto
 
-    /// Correlation between the underlying asset and the exchange rate for Quanto options.
-    /// Must be between -1.0 and 1.0.
-    pub quanto_fx_correlation: Option<Decimal>, // Quanto
-
     /// Foreign risk-free interest rate for Quanto options.
     pub quanto_foreign_rate: Option<Decimal>, // Quanto
 
@@ -104,9 +99,21 @@ pub struct ExoticParams {
     /// Dividend yield of the second underlying asset for Exchange options.
     pub exchange_second_asset_dividend: Option<Positive>, // Exchange
 
-    /// Correlation between the two underlying assets for Exchange options.
-    /// Must be between -1.0 and 1.0.
-    pub exchange_correlation: Option<Decimal>, // Exchange
+    /// Correlation of the underlying asset, first in the matrix, with the
+    /// second asset of Rainbow, Spread and Exchange options or with the
+    /// exchange rate of Quanto options, second in the matrix.
+    pub correlation: Option<CorrelationMatrix>, // Rainbow, Spread, Quanto, Exchange
+}
+
+impl ExoticParams {
+    /// Correlation between the first two assets of `correlation`: the
+    /// underlying and the second asset, or the exchange rate of Quanto
+    /// options.
+    pub fn second_asset_correlation(&self) -> Option<Decimal> {
+        self.correlation
+            .as_ref()
+            .and_then(|correlation| correlation.at(0, 1))
+    }
 }
 
 /// Represents a financial option contract with its essential parameters and characteristics.
@@ -119,6 +126,10 @@ pub struct ExoticParams {
 /// The `Options` struct supports both standard option types and exotic options through
 /// the optional `exotic_params` field, making it versatile for various financial modeling
 /// scenarios.
+///
+/// Prefer [`Options::new`] or [`OptionsBuilder`](crate::model::OptionsBuilder) over struct
+/// literals: new fields such as `settlement_type` and `day_count` get their defaults there,
+/// while struct literals have to list every field.
 #[derive(Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct Options {
     /// Specifies whether this is a European or American option
@@ -163,6 +174,15 @@ pub struct Options {
     /// Additional parameters required for exotic option types like Asian or Lookback options.
     /// This field is None for standard (vanilla) options.
     pub exotic_params: Option<ExoticParams>,
+
+    /// Whether the option delivers the underlying or pays cash when it is
+    /// exercised or assigned.
+    #[serde(default)]
+    pub settlement_type: SettlementType,
+
+    /// Day-count convention used to annualize the time to expiration.
+    #[serde(default)]
+    pub day_count: DayCount,
 }
 
 impl Options {
@@ -224,9 +244,23 @@ impl Options {
             option_style,
             dividend_yield,
             exotic_params,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
+    /// Sets how the option is settled on exercise or assignment.
+    pub fn with_settlement_type(mut self, settlement_type: SettlementType) -> Self {
+        self.settlement_type = settlement_type;
+        self
+    }
+
+    /// Sets the day-count convention used to annualize the time to expiration.
+    pub fn with_day_count(mut self, day_count: DayCount) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
     /// Updates option parameters using data from an OptionData structure.
     ///
     /// This method updates the option's strike price and implied volatility based on the
@@ -246,8 +280,8 @@ impl Options {
     /// Calculates the time to expiration of the option in years.
     ///
     /// This function computes the time remaining until the option's expiration date,
-    /// expressed as a positive decimal value representing years. This is a key parameter
-    /// used in option pricing models.
+    /// expressed as a positive decimal value representing years under the option's
+    /// day-count convention. This is a key parameter used in option pricing models.
     ///
     /// # Returns
     ///
@@ -255,7 +289,7 @@ impl Options {
     ///   as a Positive value, or an error if the calculation failed.
     ///
     pub fn time_to_expiration(&self) -> OptionsResult<Positive> {
-        Ok(self.expiration_date.get_years()?)
+        Ok(self.day_count.year_fraction(&self.expiration_date)?)
     }
 
     /// Determines if the option position is long (purchased).
@@ -457,7 +491,7 @@ impl Options {
             spot_max: None,
         };
         let payoff = self.option_type.payoff(&payoff_info) * self.quantity.to_f64();
-        Ok(Decimal::from_f64(payoff).unwrap_or_default())
+        f2du!(payoff).map_err(|e| OptionsError::payoff_error(&e.to_string()))
     }
 
     /// Calculates the financial payoff value of the option at a specific underlying price.
@@ -486,7 +520,7 @@ impl Options {
             spot_max: None,
         };
         let price = self.option_type.payoff(&payoff_info) * self.quantity.to_f64();
-        Ok(Decimal::from_f64(price).unwrap_or_default())
+        f2du!(price).map_err(|e| OptionsError::payoff_error(&e.to_string()))
     }
 
     /// Calculates the intrinsic value of the option.
@@ -513,7 +547,7 @@ impl Options {
             spot_max: None,
         };
         let iv = self.option_type.payoff(&payoff_info) * self.quantity.to_f64();
-        Ok(Decimal::from_f64(iv).unwrap_or_default())
+        f2du!(iv).map_err(|e| OptionsError::payoff_error(&e.to_string()))
     }
 
     /// Determines whether an option is "in-the-money" based on its current price relative to strike price.
@@ -551,50 +585,6 @@ impl Options {
         Ok((option_price - intrinsic_value).max(Decimal::ZERO))
     }
 
-    /// Validates that the option parameters are in a valid state for calculations.
-    ///
-    /// This function performs comprehensive validation of the option's critical parameters
-    /// to ensure they meet basic requirements for meaningful financial calculations.
-    /// It logs detailed error messages when validation fails.
-    ///
-    /// Validation checks include:
-    /// - Underlying symbol is not empty
-    /// - Implied volatility is non-negative
-    /// - Quantity is non-zero
-    /// - Risk-free rate is non-negative
-    /// - Strike price is positive and non-zero
-    /// - Underlying price is positive and non-zero
-    ///
-    /// # Returns
-    /// `true` if all parameters are valid, `false` if any validation fails
-    pub(crate) fn validate(&self) -> bool {
-        if self.underlying_symbol == *"" {
-            error!("Underlying symbol is empty");
-            return false;
-        }
-        if self.implied_volatility < ZERO {
-            error!("Implied volatility is less than zero");
-            return false;
-        }
-        if self.quantity == ZERO {
-            error!("Quantity is equal to zero");
-            return false;
-        }
-        if self.risk_free_rate < Decimal::ZERO {
-            error!("Risk free rate is less than zero");
-            return false;
-        }
-        if self.strike_price == Positive::ZERO {
-            error!("Strike is zero");
-            return false;
-        }
-        if self.underlying_price == Positive::ZERO {
-            error!("Underlying price is zero");
-            return false;
-        }
-        true
-    }
-
     /// **calculate_implied_volatility**:
     ///
     /// This function estimates the implied volatility of an option based on its market price
@@ -765,6 +755,8 @@ impl TryFrom<&OptionData> for Options {
             option_style: OptionStyle::Call,
             dividend_yield: option_data.dividend_yield.unwrap_or(Positive::ZERO),
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         })
     }
 }
@@ -784,6 +776,8 @@ impl Default for Options {
             option_style: OptionStyle::Call,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 }
@@ -1055,6 +1049,7 @@ impl Graph for Options {
 mod tests_options {
     use super::*;
     use crate::model::utils::create_sample_option_simplest;
+    use crate::utils::calendar::MarketCalendar;
 
     use approx::assert_relative_eq;
     use chrono::{Duration, Utc};
@@ -1097,6 +1092,38 @@ mod tests_options {
         assert!(option_with_datetime.time_to_expiration().unwrap() < 61.0 / 365.0);
     }
 
+    #[test]
+    fn test_time_to_expiration_day_count() {
+        let option = create_sample_option_simplest(OptionStyle::Call, Side::Long)
+            .with_day_count(DayCount::Act360);
+        assert_relative_eq!(
+            option.time_to_expiration().unwrap().to_f64(),
+            30.0 / 360.0,
+            epsilon = 0.0001
+        );
+
+        let trading = option
+            .clone()
+            .with_day_count(DayCount::Act252(MarketCalendar::Nyse));
+        let years = trading.time_to_expiration().unwrap().to_f64();
+        // 30 calendar days hold at least 18 trading days.
+        assert!(years > 17.0 / 252.0 && years < 23.0 / 252.0);
+
+        // Theta is reported per day of the convention: with the same time to
+        // expiration in years, the decay per day scales with the days per year.
+        let mut act360 = option.clone();
+        act360.expiration_date = ExpirationDate::Days(pos_or_panic!(36.0));
+        let mut act365 = option.with_day_count(DayCount::Act365Fixed);
+        act365.expiration_date = ExpirationDate::Days(pos_or_panic!(36.5));
+        let theta_360 = crate::greeks::theta(&act360).unwrap();
+        let theta_365 = crate::greeks::theta(&act365).unwrap();
+        assert_relative_eq!(
+            (theta_360 * dec!(360)).to_f64().unwrap(),
+            (theta_365 * dec!(365)).to_f64().unwrap(),
+            epsilon = 1e-6
+        );
+    }
+
     #[test]
     fn test_is_long_and_short() {
         let long_option = create_sample_option_simplest(OptionStyle::Call, Side::Long);
@@ -1220,41 +1247,97 @@ mod tests_valid_option {
             option_style: OptionStyle::Call,
             dividend_yield: pos_or_panic!(0.01),
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
     #[test]
     fn test_valid_option() {
         let option = create_valid_option();
-        assert!(option.validate());
+        assert!(option.validate().is_ok());
     }
 
     #[test]
     fn test_empty_underlying_symbol() {
         let mut option = create_valid_option();
         option.underlying_symbol = "".to_string();
-        assert!(!option.validate());
+        assert!(option.validate().is_err());
     }
 
     #[test]
     fn test_zero_strike_price() {
         let mut option = create_valid_option();
         option.strike_price = Positive::ZERO;
-        assert!(!option.validate());
+        assert!(option.validate().is_err());
     }
 
     #[test]
     fn test_zero_quantity() {
         let mut option = create_valid_option();
         option.quantity = Positive::ZERO;
-        assert!(!option.validate());
+        assert!(option.validate().is_err());
     }
 
     #[test]
     fn test_zero_underlying_price() {
         let mut option = create_valid_option();
         option.underlying_price = Positive::ZERO;
-        assert!(!option.validate());
+        assert!(option.validate().is_err());
+    }
+
+    fn invalid_field(option: &Options) -> String {
+        match option.validate() {
+            Err(OptionsError::ValidationError { field, .. }) => field,
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_implied_volatility_out_of_range() {
+        let mut option = create_valid_option();
+        // Zero marks a volatility that is not known yet.
+        option.implied_volatility = Positive::ZERO;
+        assert!(option.validate().is_ok());
+        option.implied_volatility = pos_or_panic!(150.0);
+        assert_eq!(invalid_field(&option), "implied_volatility");
+    }
+
+    #[test]
+    fn test_knock_out_barrier_orientation() {
+        use crate::model::types::BarrierType;
+        let mut option = create_valid_option();
+        option.option_type = OptionType::Barrier {
+            barrier_type: BarrierType::UpAndOut,
+            barrier_level: 90.0,
+            rebate: None,
+        };
+        assert_eq!(invalid_field(&option), "barrier_level");
+        option.option_style = OptionStyle::Put;
+        assert!(option.validate().is_ok());
+        option.option_type = OptionType::Barrier {
+            barrier_type: BarrierType::DownAndOut,
+            barrier_level: 110.0,
+            rebate: None,
+        };
+        assert_eq!(invalid_field(&option), "barrier_level");
+    }
+
+    #[test]
+    fn test_required_exotic_params() {
+        let mut option = create_valid_option();
+        option.option_type = OptionType::Quanto { exchange_rate: 1.1 };
+        assert_eq!(invalid_field(&option), "exotic_params");
+        option.exotic_params = Some(ExoticParams {
+            quanto_fx_volatility: Some(pos_or_panic!(0.1)),
+            ..Default::default()
+        });
+        assert_eq!(invalid_field(&option), "correlation");
+        if let Some(params) = option.exotic_params.as_mut() {
+            params.correlation =
+                Some(CorrelationMatrix::pair("SPX", "EURUSD", dec!(-0.3)).unwrap());
+        }
+        assert!(option.validate().is_ok());
     }
 }
 
@@ -2474,4 +2557,117 @@ mod tests_serialize_deserialize {
             serde_json::from_str(&serialized).expect("Failed to deserialize");
         assert_eq!(options, deserialized);
     }
+
+    fn exotic_option() -> Options {
+        use crate::model::types::BarrierType;
+        use crate::utils::calendar::MarketCalendar;
+        use chrono::{TimeZone, Utc};
+        use rust_decimal_macros::dec;
+
+        let params = ExoticParams {
+            spot_prices: Some(vec![pos_or_panic!(98.5), pos_or_panic!(101.25)]),
+            spot_min: Some(dec!(97.1)),
+            spot_max: Some(dec!(103.9)),
+            cliquet_local_cap: Some(dec!(0.05)),
+            cliquet_local_floor: Some(dec!(-0.02)),
+            cliquet_global_cap: Some(dec!(0.2)),
+            cliquet_global_floor: Some(dec!(0.0)),
+            rainbow_second_asset_price: Some(pos_or_panic!(50.0)),
+            rainbow_second_asset_volatility: Some(pos_or_panic!(0.3)),
+            rainbow_second_asset_dividend: Some(pos_or_panic!(0.01)),
+            spread_second_asset_volatility: Some(pos_or_panic!(0.25)),
+            spread_second_asset_dividend: Some(pos_or_panic!(0.02)),
+            quanto_fx_volatility: Some(pos_or_panic!(0.1)),
+            quanto_foreign_rate: Some(dec!(0.01)),
+            exchange_second_asset_volatility: Some(pos_or_panic!(0.35)),
+            exchange_second_asset_dividend: Some(pos_or_panic!(0.015)),
+            correlation: Some(CorrelationMatrix::pair("SPX", "NDX", dec!(0.4)).unwrap()),
+        };
+        let expiration = Utc.with_ymd_and_hms(2030, 6, 21, 20, 0, 0).unwrap();
+        Options::new(
+            OptionType::Barrier {
+                barrier_type: BarrierType::UpAndOut,
+                barrier_level: 120.5,
+                rebate: Some(1.5),
+            },
+            Side::Short,
+            "SPX".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::DateTime(expiration),
+            pos_or_panic!(0.18),
+            pos_or_panic!(3.0),
+            pos_or_panic!(99.75),
+            dec!(0.043),
+            OptionStyle::Put,
+            pos_or_panic!(0.013),
+            Some(params),
+        )
+        .with_settlement_type(SettlementType::Cash)
+        .with_day_count(DayCount::Act252(MarketCalendar::Nyse))
+    }
+
+    #[test]
+    fn test_exotic_options_roundtrip() {
+        let options = exotic_option();
+        let serialized = serde_json::to_string(&options).expect("Failed to serialize");
+        let deserialized: Options =
+            serde_json::from_str(&serialized).expect("Failed to deserialize");
+        assert_eq!(options, deserialized);
+    }
+
+    #[test]
+    fn test_stable_field_names() {
+        let value = serde_json::to_value(exotic_option()).unwrap();
+        let keys = |value: &serde_json::Value| -> Vec<String> {
+            let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+        assert_eq!(
+            keys(&value),
+            [
+                "day_count",
+                "dividend_yield",
+                "exotic_params",
+                "expiration_date",
+                "implied_volatility",
+                "option_style",
+                "option_type",
+                "quantity",
+                "risk_free_rate",
+                "settlement_type",
+                "side",
+                "strike_price",
+                "underlying_price",
+                "underlying_symbol",
+            ]
+        );
+        assert_eq!(keys(&value["exotic_params"]).len(), 17);
+        // Decimals are written as strings so no precision is lost.
+        assert_eq!(value["exotic_params"]["correlation"]["values"][0][1], "0.4");
+
+        // Documents written before settlement and day count existed still load.
+        let mut legacy = value;
+        let object = legacy.as_object_mut().unwrap();
+        object.remove("settlement_type");
+        object.remove("day_count");
+        let options: Options = serde_json::from_value(legacy).unwrap();
+        assert_eq!(options.settlement_type, SettlementType::Physical);
+        assert_eq!(options.day_count, DayCount::Act365Fixed);
+    }
+
+    #[test]
+    fn test_legacy_correlation_fields_are_rejected() {
+        for field in [
+            "rainbow_correlation",
+            "spread_correlation",
+            "quanto_fx_correlation",
+            "exchange_correlation",
+        ] {
+            let mut value = serde_json::to_value(exotic_option()).unwrap();
+            value["exotic_params"][field] = serde_json::json!("0.4");
+            let error = serde_json::from_value::<Options>(value).unwrap_err();
+            assert!(error.to_string().contains(field));
+        }
+    }
 }
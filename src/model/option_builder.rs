@@ -0,0 +1,457 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Options Builder
+//!
+//! [`OptionsBuilder`] assembles an [`Options`] contract field by field and
+//! validates it on [`OptionsBuilder::build`], returning an [`OptionsError`]
+//! instead of producing a contract that fails later during pricing.
+//!
+//! The builder checks that the underlying symbol, strike, expiration,
+//! underlying price and implied volatility were provided and that the
+//! expiration is not in the past, unless [`OptionsBuilder::allow_expired`]
+//! was set. The assembled contract must then pass [`Options::validate`].
+//!
+//! ```rust
+//! use optionstratlib::model::OptionsBuilder;
+//! use optionstratlib::{ExpirationDate, OptionStyle, Side};
+//! use positive::pos_or_panic;
+//!
+//! let option = OptionsBuilder::new()
+//!     .symbol("AAPL")
+//!     .side(Side::Long)
+//!     .option_style(OptionStyle::Call)
+//!     .strike_price(pos_or_panic!(150.0))
+//!     .underlying_price(pos_or_panic!(148.0))
+//!     .expiration_date(ExpirationDate::Days(pos_or_panic!(30.0)))
+//!     .implied_volatility(pos_or_panic!(0.25))
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(option.underlying_symbol, "AAPL");
+//!
+//! let missing_strike = OptionsBuilder::new()
+//!     .symbol("AAPL")
+//!     .underlying_price(pos_or_panic!(148.0))
+//!     .expiration_date(ExpirationDate::Days(pos_or_panic!(30.0)))
+//!     .implied_volatility(pos_or_panic!(0.25))
+//!     .build();
+//! assert!(missing_strike.is_err());
+//! ```
+
+use crate::ExpirationDate;
+use crate::error::OptionsError;
+use crate::model::option::{ExoticParams, Options};
+use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
+use chrono::Utc;
+use positive::Positive;
+use rust_decimal::Decimal;
+
+/// Validating builder for [`Options`].
+///
+/// Optional fields default to a long European call on one contract, with
+/// zero rate and dividend yield, physical settlement and the Act/365 Fixed
+/// day count, the same defaults as [`Options::new`].
+#[derive(Debug, Clone)]
+pub struct OptionsBuilder {
+    option_type: OptionType,
+    side: Side,
+    underlying_symbol: Option<String>,
+    strike_price: Option<Positive>,
+    expiration_date: Option<ExpirationDate>,
+    implied_volatility: Option<Positive>,
+    quantity: Positive,
+    underlying_price: Option<Positive>,
+    risk_free_rate: Decimal,
+    option_style: OptionStyle,
+    dividend_yield: Positive,
+    exotic_params: Option<ExoticParams>,
+    settlement_type: SettlementType,
+    day_count: DayCount,
+    allow_expired: bool,
+}
+
+impl Default for OptionsBuilder {
+    fn default() -> Self {
+        OptionsBuilder {
+            option_type: OptionType::European,
+            side: Side::Long,
+            underlying_symbol: None,
+            strike_price: None,
+            expiration_date: None,
+            implied_volatility: None,
+            quantity: Positive::ONE,
+            underlying_price: None,
+            risk_free_rate: Decimal::ZERO,
+            option_style: OptionStyle::Call,
+            dividend_yield: Positive::ZERO,
+            exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
+            allow_expired: false,
+        }
+    }
+}
+
+impl OptionsBuilder {
+    /// Creates a builder with the default optional fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the option type, European by default.
+    pub fn option_type(mut self, option_type: OptionType) -> Self {
+        self.option_type = option_type;
+        self
+    }
+
+    /// Sets the side of the position, long by default.
+    pub fn side(mut self, side: Side) -> Self {
+        self.side = side;
+        self
+    }
+
+    /// Sets the symbol of the underlying asset. Required.
+    pub fn symbol(mut self, underlying_symbol: impl Into<String>) -> Self {
+        self.underlying_symbol = Some(underlying_symbol.into());
+        self
+    }
+
+    /// Sets the strike price. Required.
+    pub fn strike_price(mut self, strike_price: Positive) -> Self {
+        self.strike_price = Some(strike_price);
+        self
+    }
+
+    /// Sets the expiration date. Required.
+    pub fn expiration_date(mut self, expiration_date: ExpirationDate) -> Self {
+        self.expiration_date = Some(expiration_date);
+        self
+    }
+
+    /// Sets the implied volatility. Required.
+    pub fn implied_volatility(mut self, implied_volatility: Positive) -> Self {
+        self.implied_volatility = Some(implied_volatility);
+        self
+    }
+
+    /// Sets the number of contracts, one by default.
+    pub fn quantity(mut self, quantity: Positive) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    /// Sets the price of the underlying asset. Required.
+    pub fn underlying_price(mut self, underlying_price: Positive) -> Self {
+        self.underlying_price = Some(underlying_price);
+        self
+    }
+
+    /// Sets the risk-free rate, zero by default.
+    pub fn risk_free_rate(mut self, risk_free_rate: Decimal) -> Self {
+        self.risk_free_rate = risk_free_rate;
+        self
+    }
+
+    /// Sets whether the option is a call or a put, a call by default.
+    pub fn option_style(mut self, option_style: OptionStyle) -> Self {
+        self.option_style = option_style;
+        self
+    }
+
+    /// Sets the dividend yield of the underlying, zero by default.
+    pub fn dividend_yield(mut self, dividend_yield: Positive) -> Self {
+        self.dividend_yield = dividend_yield;
+        self
+    }
+
+    /// Sets the exotic parameters.
+    pub fn exotic_params(mut self, exotic_params: ExoticParams) -> Self {
+        self.exotic_params = Some(exotic_params);
+        self
+    }
+
+    /// Sets the settlement type, physical by default.
+    pub fn settlement_type(mut self, settlement_type: SettlementType) -> Self {
+        self.settlement_type = settlement_type;
+        self
+    }
+
+    /// Sets the day-count convention, Act/365 Fixed by default.
+    pub fn day_count(mut self, day_count: DayCount) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Accepts an expiration date in the past, for example to rebuild an
+    /// expired contract from history.
+    pub fn allow_expired(mut self, allow_expired: bool) -> Self {
+        self.allow_expired = allow_expired;
+        self
+    }
+
+    /// Validates the fields and builds the option.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OptionsError::ValidationError` naming the first field that is
+    /// missing or invalid, as reported by [`Options::validate`].
+    pub fn build(self) -> Result<Options, OptionsError> {
+        let underlying_symbol = required(self.underlying_symbol, "underlying_symbol")?;
+        let strike_price = required(self.strike_price, "strike_price")?;
+        let underlying_price = required(self.underlying_price, "underlying_price")?;
+        let implied_volatility = required(self.implied_volatility, "implied_volatility")?;
+        let expiration_date = required(self.expiration_date, "expiration_date")?;
+        if let ExpirationDate::DateTime(date) = expiration_date
+            && !self.allow_expired
+            && date < Utc::now()
+        {
+            return Err(OptionsError::validation_error(
+                "expiration_date",
+                &format!("{date} is in the past"),
+            ));
+        }
+
+        let option = Options {
+            option_type: self.option_type,
+            side: self.side,
+            underlying_symbol,
+            strike_price,
+            expiration_date,
+            implied_volatility,
+            quantity: self.quantity,
+            underlying_price,
+            risk_free_rate: self.risk_free_rate,
+            option_style: self.option_style,
+            dividend_yield: self.dividend_yield,
+            exotic_params: self.exotic_params,
+            settlement_type: self.settlement_type,
+            day_count: self.day_count,
+        };
+        option.validate()?;
+        Ok(option)
+    }
+}
+
+impl Options {
+    /// Returns a validating [`OptionsBuilder`].
+    pub fn builder() -> OptionsBuilder {
+        OptionsBuilder::new()
+    }
+}
+
+fn required<T>(value: Option<T>, field: &str) -> Result<T, OptionsError> {
+    value.ok_or_else(|| OptionsError::validation_error(field, "is required"))
+}
+
+#[cfg(test)]
+mod tests_options_builder {
+    use super::*;
+    use crate::model::CorrelationMatrix;
+    use crate::model::types::{BarrierType, RainbowType};
+    use chrono::Duration;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn builder() -> OptionsBuilder {
+        Options::builder()
+            .symbol("SPY")
+            .strike_price(Positive::HUNDRED)
+            .underlying_price(pos_or_panic!(105.0))
+            .expiration_date(ExpirationDate::Days(pos_or_panic!(30.0)))
+            .implied_volatility(pos_or_panic!(0.2))
+    }
+
+    fn invalid_field(result: Result<Options, OptionsError>) -> String {
+        match result {
+            Err(OptionsError::ValidationError { field, .. }) => field,
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_build_matches_constructor() {
+        let built = builder()
+            .side(Side::Short)
+            .option_style(OptionStyle::Put)
+            .quantity(pos_or_panic!(2.0))
+            .risk_free_rate(dec!(0.05))
+            .dividend_yield(pos_or_panic!(0.01))
+            .build()
+            .unwrap();
+        let expected = Options::new(
+            OptionType::European,
+            Side::Short,
+            "SPY".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(2.0),
+            pos_or_panic!(105.0),
+            dec!(0.05),
+            OptionStyle::Put,
+            pos_or_panic!(0.01),
+            None,
+        );
+        assert_eq!(built, expected);
+    }
+
+    #[test]
+    fn test_missing_and_non_positive_fields() {
+        assert_eq!(
+            invalid_field(OptionsBuilder::new().build()),
+            "underlying_symbol"
+        );
+        assert_eq!(
+            invalid_field(builder().symbol(" ").build()),
+            "underlying_symbol"
+        );
+        assert_eq!(
+            invalid_field(builder().strike_price(Positive::ZERO).build()),
+            "strike_price"
+        );
+        assert_eq!(
+            invalid_field(builder().quantity(Positive::ZERO).build()),
+            "quantity"
+        );
+    }
+
+    #[test]
+    fn test_implied_volatility_bounds() {
+        assert!(builder().implied_volatility(Positive::ZERO).build().is_ok());
+        assert_eq!(
+            invalid_field(builder().implied_volatility(pos_or_panic!(101.0)).build()),
+            "implied_volatility"
+        );
+    }
+
+    #[test]
+    fn test_expired_dates_need_opt_in() {
+        let past = ExpirationDate::DateTime(Utc::now() - Duration::days(3));
+        assert_eq!(
+            invalid_field(builder().expiration_date(past).build()),
+            "expiration_date"
+        );
+        assert!(
+            builder()
+                .expiration_date(past)
+                .allow_expired(true)
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_option_type_parameters() {
+        let barrier = OptionType::Barrier {
+            barrier_type: BarrierType::UpAndOut,
+            barrier_level: -1.0,
+            rebate: None,
+        };
+        assert_eq!(
+            invalid_field(builder().option_type(barrier).build()),
+            "barrier_level"
+        );
+        let bermuda = OptionType::Bermuda {
+            exercise_dates: vec![],
+        };
+        assert_eq!(
+            invalid_field(builder().option_type(bermuda).build()),
+            "exercise_dates"
+        );
+        let power = OptionType::Power { exponent: 2.0 };
+        assert!(builder().option_type(power).build().is_ok());
+    }
+
+    #[test]
+    fn test_exotic_params_must_match_option_type() {
+        let cliquet_params = ExoticParams {
+            cliquet_local_cap: Some(dec!(0.05)),
+            ..Default::default()
+        };
+        assert_eq!(
+            invalid_field(builder().exotic_params(cliquet_params.clone()).build()),
+            "cliquet_local_cap"
+        );
+        let cliquet = OptionType::Cliquet {
+            reset_dates: vec![30.0, 60.0],
+        };
+        assert!(
+            builder()
+                .option_type(cliquet)
+                .exotic_params(cliquet_params)
+                .build()
+                .is_ok()
+        );
+
+        let extremes = ExoticParams {
+            spot_min: Some(dec!(90)),
+            spot_max: Some(dec!(110)),
+            ..Default::default()
+        };
+        let barrier = OptionType::Barrier {
+            barrier_type: BarrierType::DownAndOut,
+            barrier_level: 85.0,
+            rebate: Some(1.0),
+        };
+        assert!(
+            builder()
+                .option_type(barrier)
+                .exotic_params(extremes)
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_required_exotic_params() {
+        let rainbow = OptionType::Rainbow {
+            num_assets: 2,
+            rainbow_type: RainbowType::BestOf,
+        };
+        assert_eq!(
+            invalid_field(builder().option_type(rainbow.clone()).build()),
+            "exotic_params"
+        );
+        let partial = ExoticParams {
+            rainbow_second_asset_price: Some(pos_or_panic!(50.0)),
+            correlation: Some(CorrelationMatrix::unchecked_pair("A", "B", dec!(1.5))),
+            ..Default::default()
+        };
+        assert_eq!(
+            invalid_field(
+                builder()
+                    .option_type(rainbow.clone())
+                    .exotic_params(partial.clone())
+                    .build()
+            ),
+            "rainbow_second_asset_volatility"
+        );
+        let complete = ExoticParams {
+            rainbow_second_asset_volatility: Some(pos_or_panic!(0.3)),
+            ..partial
+        };
+        assert_eq!(
+            invalid_field(
+                builder()
+                    .option_type(rainbow.clone())
+                    .exotic_params(complete.clone())
+                    .build()
+            ),
+            "correlation"
+        );
+        let valid = ExoticParams {
+            correlation: Some(CorrelationMatrix::pair("A", "B", dec!(0.4)).unwrap()),
+            ..complete
+        };
+        assert!(
+            builder()
+                .option_type(rainbow)
+                .exotic_params(valid)
+                .build()
+                .is_ok()
+        );
+    }
+}
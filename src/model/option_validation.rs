@@ -0,0 +1,398 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Options Validation
+//!
+//! Invariants of an [`Options`] contract, checked by [`Options::validate`]:
+//!
+//! - the underlying symbol is not empty,
+//! - the strike, underlying price and quantity are strictly positive and the
+//!   risk-free rate is not negative,
+//! - the implied volatility is zero, for legs whose volatility is not known
+//!   yet, or lies within the bounds accepted by the pricing models,
+//! - the parameters embedded in the [`OptionType`] are well formed and
+//!   compatible with the strike, for example a knock-out barrier on the side
+//!   of the strike where the option can still pay,
+//! - the [`ExoticParams`] only carry fields that belong to the option type
+//!   and include the ones its pricing model requires.
+
+use crate::constants::{MAX_VOLATILITY, MIN_VOLATILITY};
+use crate::error::OptionsError;
+use crate::model::option::{ExoticParams, Options};
+use crate::model::types::{BarrierType, OptionStyle, OptionType};
+use positive::Positive;
+use rust_decimal::Decimal;
+
+impl Options {
+    /// Checks the invariants of the contract.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OptionsError::ValidationError` naming the first field that
+    /// breaks an invariant.
+    pub fn validate(&self) -> Result<(), OptionsError> {
+        check(
+            !self.underlying_symbol.trim().is_empty(),
+            "underlying_symbol",
+            "must not be empty",
+        )?;
+        strictly_positive(self.strike_price, "strike_price")?;
+        strictly_positive(self.underlying_price, "underlying_price")?;
+        strictly_positive(self.quantity, "quantity")?;
+        check(
+            self.risk_free_rate >= Decimal::ZERO,
+            "risk_free_rate",
+            "must not be negative",
+        )?;
+        check(
+            self.implied_volatility == Positive::ZERO
+                || (MIN_VOLATILITY..=MAX_VOLATILITY).contains(&self.implied_volatility),
+            "implied_volatility",
+            &format!(
+                "{} is neither zero nor within [{MIN_VOLATILITY}, {MAX_VOLATILITY}]",
+                self.implied_volatility
+            ),
+        )?;
+
+        validate_option_type(&self.option_type)?;
+        validate_barrier_orientation(&self.option_type, self.option_style, self.strike_price)?;
+        match &self.exotic_params {
+            Some(params) => validate_exotic_params(&self.option_type, params),
+            None => match required_exotic_fields(&self.option_type).first() {
+                Some(field) => Err(OptionsError::validation_error(
+                    "exotic_params",
+                    &format!("{} options require {field}", type_name(&self.option_type)),
+                )),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+fn strictly_positive(value: Positive, field: &str) -> Result<(), OptionsError> {
+    check(value > Positive::ZERO, field, "must be greater than zero")
+}
+
+/// Rejects knock-out barriers that sit where the option would have to pay,
+/// which makes the option worthless: an up-and-out call needs its barrier
+/// above the strike and a down-and-out put below it.
+fn validate_barrier_orientation(
+    option_type: &OptionType,
+    style: OptionStyle,
+    strike: Positive,
+) -> Result<(), OptionsError> {
+    let OptionType::Barrier {
+        barrier_type,
+        barrier_level,
+        ..
+    } = option_type
+    else {
+        return Ok(());
+    };
+    let strike = strike.to_f64();
+    match (barrier_type, style) {
+        (BarrierType::UpAndOut, OptionStyle::Call) => check(
+            *barrier_level > strike,
+            "barrier_level",
+            "an up-and-out call needs its barrier above the strike",
+        ),
+        (BarrierType::DownAndOut, OptionStyle::Put) => check(
+            *barrier_level < strike,
+            "barrier_level",
+            "a down-and-out put needs its barrier below the strike",
+        ),
+        _ => Ok(()),
+    }
+}
+
+fn type_name(option_type: &OptionType) -> &'static str {
+    match option_type {
+        OptionType::European => "European",
+        OptionType::American => "American",
+        OptionType::Bermuda { .. } => "Bermuda",
+        OptionType::Asian { .. } => "Asian",
+        OptionType::Barrier { .. } => "Barrier",
+        OptionType::Binary { .. } => "Binary",
+        OptionType::Lookback { .. } => "Lookback",
+        OptionType::Compound { .. } => "Compound",
+        OptionType::Chooser { .. } => "Chooser",
+        OptionType::Cliquet { .. } => "Cliquet",
+        OptionType::Rainbow { .. } => "Rainbow",
+        OptionType::Spread { .. } => "Spread",
+        OptionType::Quanto { .. } => "Quanto",
+        OptionType::Exchange { .. } => "Exchange",
+        OptionType::Power { .. } => "Power",
+    }
+}
+
+fn check(condition: bool, field: &str, reason: &str) -> Result<(), OptionsError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(OptionsError::validation_error(field, reason))
+    }
+}
+
+fn non_negative_times(times: &[f64], field: &str) -> Result<(), OptionsError> {
+    check(
+        times.iter().all(|t| t.is_finite() && *t >= 0.0),
+        field,
+        "must contain finite, non-negative times",
+    )
+}
+
+fn validate_option_type(option_type: &OptionType) -> Result<(), OptionsError> {
+    match option_type {
+        OptionType::European
+        | OptionType::American
+        | OptionType::Asian { .. }
+        | OptionType::Binary { .. }
+        | OptionType::Lookback { .. } => Ok(()),
+        OptionType::Bermuda { exercise_dates } => {
+            check(
+                !exercise_dates.is_empty(),
+                "exercise_dates",
+                "Bermuda options need at least one exercise date",
+            )?;
+            non_negative_times(exercise_dates, "exercise_dates")
+        }
+        OptionType::Barrier {
+            barrier_level,
+            rebate,
+            ..
+        } => {
+            check(
+                barrier_level.is_finite() && *barrier_level > 0.0,
+                "barrier_level",
+                "must be finite and greater than zero",
+            )?;
+            check(
+                rebate.is_none_or(|r| r.is_finite() && r >= 0.0),
+                "rebate",
+                "must be finite and non-negative",
+            )
+        }
+        OptionType::Compound { underlying_option } => {
+            check(
+                !matches!(**underlying_option, OptionType::Compound { .. }),
+                "underlying_option",
+                "nested compound options are not supported",
+            )?;
+            validate_option_type(underlying_option)
+        }
+        OptionType::Chooser { choice_date } => check(
+            choice_date.is_finite() && *choice_date >= 0.0,
+            "choice_date",
+            "must be finite and non-negative",
+        ),
+        OptionType::Cliquet { reset_dates } => non_negative_times(reset_dates, "reset_dates"),
+        OptionType::Rainbow { num_assets, .. } => check(
+            *num_assets >= 2,
+            "num_assets",
+            "Rainbow options need at least two assets",
+        ),
+        OptionType::Spread { second_asset } | OptionType::Exchange { second_asset } => check(
+            second_asset.is_finite() && *second_asset > 0.0,
+            "second_asset",
+            "must be finite and greater than zero",
+        ),
+        OptionType::Quanto { exchange_rate } => check(
+            exchange_rate.is_finite() && *exchange_rate > 0.0,
+            "exchange_rate",
+            "must be finite and greater than zero",
+        ),
+        OptionType::Power { exponent } => check(
+            exponent.is_finite() && *exponent > 0.0,
+            "exponent",
+            "must be finite and greater than zero",
+        ),
+    }
+}
+
+/// Owner of the exotic parameter fields shared by the options on two assets.
+const MULTI_ASSET: &str = "multi-asset";
+
+/// Exotic parameter fields the pricing model of `option_type` cannot do without.
+fn required_exotic_fields(option_type: &OptionType) -> &'static [&'static str] {
+    match option_type {
+        OptionType::Rainbow { .. } => &[
+            "rainbow_second_asset_price",
+            "rainbow_second_asset_volatility",
+        ],
+        OptionType::Spread { .. } => &["spread_second_asset_volatility", "correlation"],
+        OptionType::Quanto { .. } => &["quanto_fx_volatility", "correlation"],
+        OptionType::Exchange { .. } => &["exchange_second_asset_volatility", "correlation"],
+        _ => &[],
+    }
+}
+
+/// Names of the exotic parameter fields that are set, paired with the option
+/// types they belong to.
+fn present_exotic_fields(params: &ExoticParams) -> Vec<(&'static str, &'static str)> {
+    let fields = [
+        ("spot_prices", "Asian", params.spot_prices.is_some()),
+        ("spot_min", "Lookback", params.spot_min.is_some()),
+        ("spot_max", "Lookback", params.spot_max.is_some()),
+        (
+            "cliquet_local_cap",
+            "Cliquet",
+            params.cliquet_local_cap.is_some(),
+        ),
+        (
+            "cliquet_local_floor",
+            "Cliquet",
+            params.cliquet_local_floor.is_some(),
+        ),
+        (
+            "cliquet_global_cap",
+            "Cliquet",
+            params.cliquet_global_cap.is_some(),
+        ),
+        (
+            "cliquet_global_floor",
+            "Cliquet",
+            params.cliquet_global_floor.is_some(),
+        ),
+        (
+            "rainbow_second_asset_price",
+            "Rainbow",
+            params.rainbow_second_asset_price.is_some(),
+        ),
+        (
+            "rainbow_second_asset_volatility",
+            "Rainbow",
+            params.rainbow_second_asset_volatility.is_some(),
+        ),
+        (
+            "rainbow_second_asset_dividend",
+            "Rainbow",
+            params.rainbow_second_asset_dividend.is_some(),
+        ),
+        (
+            "spread_second_asset_volatility",
+            "Spread",
+            params.spread_second_asset_volatility.is_some(),
+        ),
+        (
+            "spread_second_asset_dividend",
+            "Spread",
+            params.spread_second_asset_dividend.is_some(),
+        ),
+        (
+            "quanto_fx_volatility",
+            "Quanto",
+            params.quanto_fx_volatility.is_some(),
+        ),
+        (
+            "quanto_foreign_rate",
+            "Quanto",
+            params.quanto_foreign_rate.is_some(),
+        ),
+        (
+            "exchange_second_asset_volatility",
+            "Exchange",
+            params.exchange_second_asset_volatility.is_some(),
+        ),
+        (
+            "exchange_second_asset_dividend",
+            "Exchange",
+            params.exchange_second_asset_dividend.is_some(),
+        ),
+        ("correlation", MULTI_ASSET, params.correlation.is_some()),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, _, present)| *present)
+        .map(|(field, owner, _)| (field, owner))
+        .collect()
+}
+
+/// Returns whether a field owned by `owner` may be set on `option_type`.
+fn accepts_field(option_type: &OptionType, owner: &str) -> bool {
+    match option_type {
+        OptionType::Compound { underlying_option } => accepts_field(underlying_option, owner),
+        // Barrier payoffs read the observed extremes of the path.
+        OptionType::Barrier { .. } => owner == "Lookback",
+        OptionType::Rainbow { .. }
+        | OptionType::Spread { .. }
+        | OptionType::Quanto { .. }
+        | OptionType::Exchange { .. }
+            if owner == MULTI_ASSET =>
+        {
+            true
+        }
+        other => type_name(other) == owner,
+    }
+}
+
+fn validate_bounds(
+    floor: Option<Decimal>,
+    cap: Option<Decimal>,
+    field: &str,
+) -> Result<(), OptionsError> {
+    match (floor, cap) {
+        (Some(floor), Some(cap)) => check(
+            floor <= cap,
+            field,
+            &format!("floor {floor} is above cap {cap}"),
+        ),
+        _ => Ok(()),
+    }
+}
+
+fn validate_exotic_params(
+    option_type: &OptionType,
+    params: &ExoticParams,
+) -> Result<(), OptionsError> {
+    let present = present_exotic_fields(params);
+    if let Some((field, owner)) = present
+        .iter()
+        .find(|(_, owner)| !accepts_field(option_type, owner))
+    {
+        return Err(OptionsError::validation_error(
+            field,
+            &format!(
+                "is a {owner} parameter and cannot be used with {} options",
+                type_name(option_type)
+            ),
+        ));
+    }
+    if let Some(field) = required_exotic_fields(option_type)
+        .iter()
+        .find(|field| !present.iter().any(|(name, _)| name == *field))
+    {
+        return Err(OptionsError::validation_error(
+            field,
+            &format!("is required by {} options", type_name(option_type)),
+        ));
+    }
+
+    if let Some(spot_prices) = &params.spot_prices {
+        check(!spot_prices.is_empty(), "spot_prices", "must not be empty")?;
+    }
+    validate_bounds(params.spot_min, params.spot_max, "spot_min")?;
+    validate_bounds(
+        params.cliquet_local_floor,
+        params.cliquet_local_cap,
+        "cliquet_local_floor",
+    )?;
+    validate_bounds(
+        params.cliquet_global_floor,
+        params.cliquet_global_cap,
+        "cliquet_global_floor",
+    )?;
+    match &params.correlation {
+        Some(correlation) => {
+            check(
+                correlation.len() >= 2,
+                "correlation",
+                "must include the underlying and the second asset",
+            )?;
+            correlation.validate()
+        }
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,677 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Portfolio
+//!
+//! Account-level view of the positions and strategies held in a
+//! [`Portfolio`], across any number of underlyings:
+//!
+//! * cash movements with [`Portfolio::deposit`] and [`Portfolio::withdraw`],
+//! * grouping of the open positions by underlying symbol,
+//! * market value and net liquidation value, marking every position to the
+//!   option chain of its underlying and every share holding to its
+//!   underlying price,
+//! * net Greeks of the whole portfolio through the [`Greeks`] trait, and per
+//!   underlying with [`Portfolio::net_greeks_by_underlying`],
+//! * margin requirement with the [`SPANMargin`], [`RegTMargin`] and
+//!   [`ScenarioMargin`] calculators,
+//! * early-assignment and pin risk of the short positions,
+//! * delta in terms of an index with [`BetaWeighting`],
+//! * revaluation under spot, volatility and rate shocks with [`StressTest`],
+//! * Value-at-Risk and expected shortfall of the positions and shares with
+//!   [`ValueAtRisk`],
+//! * value, P&L and Greeks in the base currency of the portfolio, converting
+//!   positions denominated in other currencies with an [`FxRateProvider`].
+//!
+//! Strategy legs count as positions of the portfolio. Closed positions are
+//! ignored by every calculation.
+
+use crate::chains::OptionChain;
+use crate::error::position::PositionError;
+use crate::error::{GreeksError, OptionsError, PricingError};
+use crate::greeks::{Greeks, NetGreeks};
+use crate::model::Portfolio;
+use crate::model::currency::{Currency, FxRateProvider};
+use crate::model::option::Options;
+use crate::model::position::Position;
+use crate::model::types::Side;
+use crate::risk::{
+    AssignmentRisk, BetaWeightedReport, BetaWeighting, EarlyAssignmentAnalysis, MarginReport,
+    PinRisk, PinRiskCheck, RegTMargin, SPANMargin, ScenarioMargin, ScenarioMarginReport,
+    StressReport, StressTest, ValueAtRisk, VarReport,
+};
+use crate::strategies::base::Strategy;
+use positive::Positive;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+impl Portfolio {
+    /// Adds a standalone position to the portfolio.
+    pub fn add_position(&mut self, position: Position) {
+        self.positions.push(position);
+    }
+
+    /// Adds a strategy to the portfolio.
+    pub fn add_strategy(&mut self, strategy: Strategy) {
+        self.strategies.push(strategy);
+    }
+
+    /// Adds `amount` to the cash of the portfolio.
+    pub fn deposit(&mut self, amount: Positive) {
+        self.cash += amount.to_dec();
+    }
+
+    /// Takes `amount` from the cash of the portfolio, which may become negative.
+    pub fn withdraw(&mut self, amount: Positive) {
+        self.cash -= amount.to_dec();
+    }
+
+    /// Open positions of the portfolio: the standalone positions followed by
+    /// the legs of every strategy.
+    pub fn open_positions(&self) -> Vec<&Position> {
+        self.positions
+            .iter()
+            .chain(self.strategies.iter().flat_map(|strategy| &strategy.legs))
+            .filter(|position| position.is_open())
+            .collect()
+    }
+
+    /// Underlying symbols with open positions, in alphabetical order.
+    pub fn underlyings(&self) -> Vec<String> {
+        self.positions_by_underlying().into_keys().collect()
+    }
+
+    /// Open positions grouped by underlying symbol.
+    pub fn positions_by_underlying(&self) -> BTreeMap<String, Vec<&Position>> {
+        let mut groups: BTreeMap<String, Vec<&Position>> = BTreeMap::new();
+        for position in self.open_positions() {
+            groups
+                .entry(position.option.underlying_symbol.clone())
+                .or_default()
+                .push(position);
+        }
+        groups
+    }
+
+    /// Market value of the open positions, marking each one to the mid
+    /// price of its contract in the chain of its underlying. Long positions
+    /// add value and short positions subtract it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if `chains` lacks the chain of an underlying
+    /// or a position cannot be marked.
+    pub fn market_value(&self, chains: &[OptionChain]) -> Result<Decimal, PositionError> {
+        let mut value = Decimal::ZERO;
+        for position in self.open_positions() {
+            value += position.mark(chain_for(chains, position)?)?.market_value;
+        }
+        Ok(value + self.mark_spot_positions(chains)?.0)
+    }
+
+    /// Unrealized P&L of the open positions at the prices of `chains`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if `chains` lacks the chain of an underlying
+    /// or a position cannot be marked.
+    pub fn unrealized_pnl(&self, chains: &[OptionChain]) -> Result<Decimal, PositionError> {
+        let mut pnl = Decimal::ZERO;
+        for position in self.open_positions() {
+            pnl += position.mark(chain_for(chains, position)?)?.unrealized_pnl;
+        }
+        Ok(pnl + self.mark_spot_positions(chains)?.1)
+    }
+
+    /// Cash plus the value of the balances and the market value of the open
+    /// positions: what the account would be worth if everything were closed
+    /// at current prices.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the market value cannot be computed.
+    pub fn net_liquidation_value(&self, chains: &[OptionChain]) -> Result<Decimal, PositionError> {
+        Ok(self.cash + self.get_total_value().to_dec() + self.market_value(chains)?)
+    }
+
+    /// Net Greeks of the open positions of each underlying.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the Greeks of a position cannot be computed.
+    pub fn net_greeks_by_underlying(&self) -> Result<BTreeMap<String, NetGreeks>, GreeksError> {
+        let mut greeks = BTreeMap::new();
+        for (underlying, positions) in self.positions_by_underlying() {
+            let mut net = NetGreeks {
+                delta: Decimal::ZERO,
+                gamma: Decimal::ZERO,
+                theta: Decimal::ZERO,
+                vega: Decimal::ZERO,
+                rho: Decimal::ZERO,
+            };
+            for position in positions {
+                let position_greeks = position.net_greeks()?;
+                net.delta += position_greeks.delta;
+                net.gamma += position_greeks.gamma;
+                net.theta += position_greeks.theta;
+                net.vega += position_greeks.vega;
+                net.rho += position_greeks.rho;
+            }
+            greeks.insert(underlying, net);
+        }
+        Ok(greeks)
+    }
+
+    /// Delta of the open positions and shares in units of the index of
+    /// `weighting`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if an underlying has no beta or the delta of
+    /// a position cannot be computed.
+    pub fn beta_weighted_delta(
+        &self,
+        weighting: &BetaWeighting,
+    ) -> Result<BetaWeightedReport, OptionsError> {
+        let shares: Vec<_> = self.spot_positions.iter().collect();
+        weighting.calculate(&self.open_positions(), &shares)
+    }
+
+    /// Margin required by the open positions, as the sum of the SPAN margin
+    /// of each one.
+    pub fn margin_requirement(&self, span: &SPANMargin) -> Decimal {
+        self.open_positions()
+            .into_iter()
+            .map(|position| span.calculate_margin(position))
+            .sum()
+    }
+
+    /// Reg-T margin of the open positions, pairing the legs of each underlying
+    /// into spreads, straddles and naked options.
+    pub fn reg_t_margin(&self, margin: &RegTMargin) -> MarginReport {
+        margin.calculate(&self.open_positions(), &[])
+    }
+
+    /// Scenario-based margin of the open positions, stressing each underlying
+    /// over the grid of `engine`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if a position cannot be priced.
+    pub fn scenario_margin(
+        &self,
+        engine: &ScenarioMargin,
+    ) -> Result<ScenarioMarginReport, PricingError> {
+        engine.calculate(&self.open_positions())
+    }
+
+    /// Early-assignment risk of the short open positions, with legs indexed
+    /// in the order of [`Portfolio::open_positions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if a position cannot be priced.
+    pub fn early_assignment_risk(
+        &self,
+        analysis: &EarlyAssignmentAnalysis,
+    ) -> Result<Vec<AssignmentRisk>, OptionsError> {
+        analysis.analyze(&self.open_positions())
+    }
+
+    /// Short open positions at risk of pinning near expiration, with legs
+    /// indexed in the order of [`Portfolio::open_positions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if the delta of a position cannot be computed.
+    pub fn pin_risk(&self, check: &PinRiskCheck) -> Result<Vec<PinRisk>, OptionsError> {
+        check.check(&self.open_positions())
+    }
+
+    /// Revaluation grid of the open positions and shares under every scenario
+    /// of `engine`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if a position cannot be priced.
+    pub fn stress_test(&self, engine: &StressTest) -> Result<StressReport, OptionsError> {
+        let shares: Vec<_> = self.spot_positions.iter().collect();
+        engine.run(&self.open_positions(), &shares)
+    }
+
+    /// Delta-gamma parametric Value-at-Risk of the open positions and shares.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if the Value-at-Risk cannot be computed.
+    pub fn parametric_var(&self, var: &ValueAtRisk) -> Result<VarReport, OptionsError> {
+        let shares: Vec<_> = self.spot_positions.iter().collect();
+        var.parametric(&self.open_positions(), &shares)
+    }
+
+    /// Monte Carlo Value-at-Risk of the open positions and shares, with full
+    /// revaluation of every position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if the Value-at-Risk cannot be computed.
+    pub fn monte_carlo_var(&self, var: &ValueAtRisk) -> Result<VarReport, OptionsError> {
+        let shares: Vec<_> = self.spot_positions.iter().collect();
+        var.monte_carlo(&self.open_positions(), &shares)
+    }
+
+    /// Net liquidation value left after setting aside the margin requirement.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the net liquidation value cannot be computed.
+    pub fn excess_liquidity(
+        &self,
+        chains: &[OptionChain],
+        span: &SPANMargin,
+    ) -> Result<Decimal, PositionError> {
+        Ok(self.net_liquidation_value(chains)? - self.margin_requirement(span))
+    }
+}
+
+impl Portfolio {
+    /// Market value of the open positions in the base currency of the
+    /// portfolio, converting each position from its own currency with `fx`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if a position cannot be marked or `fx` has
+    /// no rate for its currency.
+    pub fn market_value_in_base(
+        &self,
+        chains: &[OptionChain],
+        fx: &dyn FxRateProvider,
+    ) -> Result<Decimal, PositionError> {
+        let mut value = Decimal::ZERO;
+        for position in self.open_positions() {
+            let market_value = position.mark(chain_for(chains, position)?)?.market_value;
+            value += self.to_base(market_value, &position.currency, fx)?;
+        }
+        Ok(value + self.mark_spot_positions(chains)?.0)
+    }
+
+    /// Unrealized P&L of the open positions in the base currency of the
+    /// portfolio.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if a position cannot be marked or `fx` has
+    /// no rate for its currency.
+    pub fn unrealized_pnl_in_base(
+        &self,
+        chains: &[OptionChain],
+        fx: &dyn FxRateProvider,
+    ) -> Result<Decimal, PositionError> {
+        let mut pnl = Decimal::ZERO;
+        for position in self.open_positions() {
+            let unrealized_pnl = position.mark(chain_for(chains, position)?)?.unrealized_pnl;
+            pnl += self.to_base(unrealized_pnl, &position.currency, fx)?;
+        }
+        Ok(pnl + self.mark_spot_positions(chains)?.1)
+    }
+
+    /// P&L realized by every position of the portfolio, open or closed, in
+    /// its base currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if `fx` has no rate for the currency of a
+    /// position.
+    pub fn realized_pnl_in_base(&self, fx: &dyn FxRateProvider) -> Result<Decimal, PositionError> {
+        let mut pnl = Decimal::ZERO;
+        for position in self
+            .positions
+            .iter()
+            .chain(self.strategies.iter().flat_map(|strategy| &strategy.legs))
+        {
+            pnl += self.to_base(position.realized_pnl, &position.currency, fx)?;
+        }
+        Ok(pnl)
+    }
+
+    /// Net liquidation value in the base currency of the portfolio. Cash and
+    /// balances are already in the base currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the market value cannot be computed.
+    pub fn net_liquidation_value_in_base(
+        &self,
+        chains: &[OptionChain],
+        fx: &dyn FxRateProvider,
+    ) -> Result<Decimal, PositionError> {
+        Ok(self.cash + self.get_total_value().to_dec() + self.market_value_in_base(chains, fx)?)
+    }
+
+    /// Net Greeks of the open positions with theta, vega and rho converted to
+    /// the base currency of the portfolio. Delta and gamma are counts of the
+    /// underlying and are not converted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the Greeks of a position cannot be computed
+    /// or `fx` has no rate for its currency.
+    pub fn net_greeks_in_base(&self, fx: &dyn FxRateProvider) -> Result<NetGreeks, GreeksError> {
+        let mut net = NetGreeks {
+            delta: Decimal::ZERO,
+            gamma: Decimal::ZERO,
+            theta: Decimal::ZERO,
+            vega: Decimal::ZERO,
+            rho: Decimal::ZERO,
+        };
+        for position in self.open_positions() {
+            let rate = fx
+                .rate(&position.currency, &self.base_currency)
+                .ok_or_else(|| missing_rate(&position.currency, &self.base_currency))?;
+            let greeks = position.net_greeks()?;
+            net.delta += greeks.delta;
+            net.gamma += greeks.gamma;
+            net.theta += greeks.theta * rate;
+            net.vega += greeks.vega * rate;
+            net.rho += greeks.rho * rate;
+        }
+        Ok(net)
+    }
+
+    /// Market value and unrealized P&L of the spot positions at the underlying
+    /// price of their chains. Shares are held in the base currency.
+    fn mark_spot_positions(
+        &self,
+        chains: &[OptionChain],
+    ) -> Result<(Decimal, Decimal), PositionError> {
+        let mut value = Decimal::ZERO;
+        let mut pnl = Decimal::ZERO;
+        for spot in &self.spot_positions {
+            let chain = chains
+                .iter()
+                .find(|chain| chain.symbol == spot.symbol)
+                .ok_or_else(|| {
+                    PositionError::invalid_position(&format!("No option chain for {}", spot.symbol))
+                })?;
+            let quantity = spot.quantity.to_dec();
+            let price = chain.underlying_price.to_dec();
+            let gain = (price - spot.cost_basis.to_dec()) * quantity;
+            match spot.side {
+                Side::Long => {
+                    value += price * quantity;
+                    pnl += gain;
+                }
+                Side::Short => {
+                    value -= price * quantity;
+                    pnl -= gain;
+                }
+            }
+        }
+        Ok((value, pnl))
+    }
+
+    /// Converts `amount` from `currency` to the base currency.
+    fn to_base(
+        &self,
+        amount: Decimal,
+        currency: &Currency,
+        fx: &dyn FxRateProvider,
+    ) -> Result<Decimal, PositionError> {
+        fx.convert(amount, currency, &self.base_currency)
+            .ok_or_else(|| {
+                PositionError::invalid_position(&missing_rate(currency, &self.base_currency))
+            })
+    }
+}
+
+/// Message for a missing exchange rate.
+fn missing_rate(from: &Currency, to: &Currency) -> String {
+    format!("No FX rate from {from} to {to}")
+}
+
+/// Option chain of the underlying of `position`.
+fn chain_for<'a>(
+    chains: &'a [OptionChain],
+    position: &Position,
+) -> Result<&'a OptionChain, PositionError> {
+    let symbol = &position.option.underlying_symbol;
+    chains
+        .iter()
+        .find(|chain| &chain.symbol == symbol)
+        .ok_or_else(|| PositionError::invalid_position(&format!("No option chain for {symbol}")))
+}
+
+/// Greeks of every open position of the portfolio.
+impl Greeks for Portfolio {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self
+            .open_positions()
+            .into_iter()
+            .map(|position| &position.option)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests_portfolio {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::currency::FxRates;
+    use crate::model::leg::SpotPosition;
+    use crate::model::types::{OptionStyle, OptionType};
+    use crate::strategies::base::StrategyType;
+    use chrono::Utc;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn position(symbol: &str, style: OptionStyle, side: Side) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            symbol.to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            pos_or_panic!(3.0),
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    fn chain(symbol: &str) -> OptionChain {
+        let mut chain = OptionChain::new(
+            symbol,
+            Positive::HUNDRED,
+            "2030-01-01".to_string(),
+            None,
+            None,
+        );
+        chain.add_option(
+            Positive::HUNDRED,
+            Some(pos_or_panic!(4.0)),
+            Some(pos_or_panic!(4.4)),
+            Some(pos_or_panic!(2.0)),
+            Some(pos_or_panic!(2.2)),
+            pos_or_panic!(0.2),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        chain
+    }
+
+    fn portfolio() -> Portfolio {
+        let mut portfolio = Portfolio::new("Account".to_string());
+        portfolio.deposit(pos_or_panic!(10000.0));
+        portfolio.add_position(position("AAA", OptionStyle::Put, Side::Short));
+
+        let mut strategy = Strategy::new(
+            "Straddle".to_string(),
+            StrategyType::LongStraddle,
+            "Long straddle".to_string(),
+        );
+        strategy
+            .legs
+            .push(position("BBB", OptionStyle::Call, Side::Long));
+        strategy
+            .legs
+            .push(position("BBB", OptionStyle::Put, Side::Long));
+        portfolio.add_strategy(strategy);
+        portfolio
+    }
+
+    #[test]
+    fn test_grouping_and_cash() {
+        let mut portfolio = portfolio();
+        assert!(!portfolio.is_empty());
+        assert_eq!(portfolio.underlyings(), vec!["AAA", "BBB"]);
+        assert_eq!(portfolio.positions_by_underlying()["BBB"].len(), 2);
+
+        portfolio.withdraw(pos_or_panic!(500.0));
+        assert_eq!(portfolio.cash, dec!(9500));
+
+        portfolio.positions[0]
+            .close_all(Positive::ONE, Utc::now())
+            .unwrap();
+        assert_eq!(portfolio.underlyings(), vec!["BBB"]);
+    }
+
+    #[test]
+    fn test_valuation() {
+        let portfolio = portfolio();
+        let chains = vec![chain("AAA"), chain("BBB")];
+        // Short put marked at 2.1, long call at 4.2 and long put at 2.1.
+        assert_eq!(portfolio.market_value(&chains).unwrap(), dec!(4.2));
+        assert_eq!(portfolio.unrealized_pnl(&chains).unwrap(), dec!(1.2));
+        assert_eq!(
+            portfolio.net_liquidation_value(&chains).unwrap(),
+            dec!(10004.2)
+        );
+        assert!(portfolio.market_value(&chains[..1]).is_err());
+
+        let mut with_shares = portfolio.clone();
+        with_shares.spot_positions.push(SpotPosition::long(
+            "AAA".to_string(),
+            pos_or_panic!(10.0),
+            pos_or_panic!(95.0),
+        ));
+        assert_eq!(with_shares.market_value(&chains).unwrap(), dec!(1004.2));
+        assert_eq!(with_shares.unrealized_pnl(&chains).unwrap(), dec!(51.2));
+
+        let reg_t = portfolio.reg_t_margin(&RegTMargin::default());
+        assert_eq!(reg_t.components.len(), 3);
+        assert!(reg_t.total.initial > Decimal::ZERO);
+
+        let scenario = portfolio
+            .scenario_margin(&ScenarioMargin::default())
+            .unwrap();
+        assert_eq!(scenario.groups.len(), 2);
+
+        let var = ValueAtRisk::one_day(dec!(0.99))
+            .with_simulations(500)
+            .with_seed(3);
+        let parametric = portfolio.parametric_var(&var).unwrap();
+        assert_eq!(parametric.underlyings.len(), 2);
+        assert!(parametric.var > Decimal::ZERO);
+        assert!(with_shares.monte_carlo_var(&var).unwrap().var > Decimal::ZERO);
+
+        let stress = with_shares.stress_test(&StressTest::default()).unwrap();
+        assert_eq!(stress.positions.len(), 4);
+        assert_eq!(stress.pnl_by_underlying().len(), 2);
+
+        let span = SPANMargin::new(dec!(0.1), dec!(0.05), dec!(0.1));
+        let margin = portfolio.margin_requirement(&span);
+        assert!(margin > Decimal::ZERO);
+        assert_eq!(
+            portfolio.excess_liquidity(&chains, &span).unwrap(),
+            dec!(10004.2) - margin
+        );
+    }
+
+    #[test]
+    fn test_net_greeks() {
+        let portfolio = portfolio();
+        let total = portfolio.net_greeks().unwrap();
+        let by_underlying = portfolio.net_greeks_by_underlying().unwrap();
+        assert_eq!(by_underlying.len(), 2);
+        assert!(by_underlying["AAA"].delta > Decimal::ZERO);
+        assert!(by_underlying["BBB"].gamma > Decimal::ZERO);
+        assert_eq!(
+            by_underlying["AAA"].delta + by_underlying["BBB"].delta,
+            total.delta
+        );
+        assert_eq!(
+            by_underlying["AAA"].vega + by_underlying["BBB"].vega,
+            total.vega
+        );
+    }
+
+    #[test]
+    fn test_beta_weighted_delta() {
+        let portfolio = portfolio();
+        let weighting = BetaWeighting::new("SPX", pos_or_panic!(400.0))
+            .with_beta("AAA", dec!(1.2))
+            .with_beta("BBB", dec!(0.8));
+        let report = portfolio.beta_weighted_delta(&weighting).unwrap();
+        let by_underlying = portfolio.net_greeks_by_underlying().unwrap();
+        assert_eq!(report.underlyings.len(), 2);
+        assert_eq!(report.underlyings[0].delta, by_underlying["AAA"].delta);
+        assert_eq!(
+            report.total_delta,
+            by_underlying["AAA"].delta * dec!(0.3) + by_underlying["BBB"].delta * dec!(0.2)
+        );
+    }
+
+    #[test]
+    fn test_base_currency_reporting() {
+        let mut portfolio = portfolio();
+        for leg in portfolio.strategies[0].legs.iter_mut() {
+            leg.currency = Currency::new("EUR");
+        }
+        let chains = vec![chain("AAA"), chain("BBB")];
+        let fx = FxRates::new(Currency::usd()).with_rate(Currency::new("EUR"), dec!(1.1));
+
+        // Short put at -2.1 USD, long call and put at 4.2 + 2.1 EUR.
+        assert_eq!(
+            portfolio.market_value_in_base(&chains, &fx).unwrap(),
+            dec!(4.83)
+        );
+        assert_eq!(
+            portfolio
+                .net_liquidation_value_in_base(&chains, &fx)
+                .unwrap(),
+            dec!(10004.83)
+        );
+        assert_eq!(portfolio.realized_pnl_in_base(&fx).unwrap(), Decimal::ZERO);
+
+        let native = portfolio.net_greeks_by_underlying().unwrap();
+        let base = portfolio.net_greeks_in_base(&fx).unwrap();
+        assert_eq!(base.delta, native["AAA"].delta + native["BBB"].delta);
+        assert_eq!(
+            base.vega.round_dp(12),
+            (native["AAA"].vega + native["BBB"].vega * dec!(1.1)).round_dp(12)
+        );
+
+        let missing = FxRates::new(Currency::usd());
+        assert!(portfolio.market_value_in_base(&chains, &missing).is_err());
+        assert!(portfolio.net_greeks_in_base(&missing).is_err());
+    }
+}
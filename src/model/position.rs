@@ -10,6 +10,8 @@ use crate::error::{
     GreeksError, PositionError, PricingError, StrategyError, TradeError, TransactionError,
 };
 use crate::greeks::Greeks;
+use crate::model::currency::Currency;
+use crate::model::lifecycle::{Fill, FillKind, PositionStatus};
 use crate::model::trade::TradeStatusAble;
 use crate::model::types::{Action, OptionBasicType, OptionStyle, Side};
 use crate::model::{Trade, TradeAble, TradeStatus};
@@ -71,6 +73,7 @@ pub struct Position {
 
     /// The date and time when the position was opened, used for calculating
     /// time-based metrics like days held and days to expiration.
+    #[serde(with = "crate::model::types::datetime_format")]
     pub date: DateTime<Utc>,
 
     /// The fee paid to open the position per contract. This typically includes
@@ -86,6 +89,22 @@ pub struct Position {
 
     /// Additional custom data fields for the position stored as JSON
     pub extra_fields: Option<serde_json::Value>,
+
+    /// Whether the position still holds open contracts.
+    #[serde(default)]
+    pub status: PositionStatus,
+
+    /// Executions that opened, increased or closed the position, oldest first.
+    #[serde(default)]
+    pub fills: Vec<Fill>,
+
+    /// P&L realized by the contracts closed so far.
+    #[serde(default)]
+    pub realized_pnl: Decimal,
+
+    /// Currency in which the premium, fees and P&L of the position are denominated.
+    #[serde(default)]
+    pub currency: Currency,
 }
 
 impl Position {
@@ -144,6 +163,14 @@ impl Position {
         epic: Option<String>,
         extra_fields: Option<serde_json::Value>,
     ) -> Self {
+        let opening = Fill::new(
+            option.side,
+            FillKind::Open,
+            option.quantity,
+            premium,
+            open_fee,
+            date,
+        );
         Position {
             option,
             premium,
@@ -152,6 +179,10 @@ impl Position {
             close_fee,
             epic,
             extra_fields,
+            status: PositionStatus::Open,
+            fills: vec![opening],
+            realized_pnl: Decimal::ZERO,
+            currency: Currency::default(),
         }
     }
 
@@ -387,6 +418,41 @@ impl Position {
         }
     }
 
+    /// Calculates the profit or loss of the position at a valuation horizon.
+    ///
+    /// Positions expiring at or before `horizon` are settled at intrinsic value,
+    /// exactly as [`Position::pnl_at_expiration`]. Positions expiring later are
+    /// marked with Black-Scholes using the time remaining after the horizon and
+    /// the option's current implied volatility. This is what multi-expiry
+    /// strategies such as calendar spreads need to value the far leg when the
+    /// near leg expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - Underlying price at the horizon.
+    /// * `horizon` - Date at which the position is valued.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the days to expiration or the model price
+    /// cannot be computed.
+    pub fn pnl_at_horizon(
+        &self,
+        price: &Positive,
+        horizon: &ExpirationDate,
+    ) -> Result<Decimal, PricingError> {
+        let days_to_expiration = self.option.expiration_date.get_days()?;
+        let days_to_horizon = horizon.get_days()?;
+        if days_to_expiration <= days_to_horizon {
+            return self.pnl_at_expiration(&Some(price));
+        }
+        let mut marked = self.option.clone();
+        marked.underlying_price = *price;
+        marked.expiration_date = ExpirationDate::Days(days_to_expiration - days_to_horizon);
+        let value = marked.calculate_price_black_scholes()? * self.option.quantity.to_dec();
+        Ok(value - self.total_cost()? + self.premium_received()?)
+    }
+
     /// Calculates the unrealized profit and loss (PnL) for an options position at a given price.
     ///
     /// This method computes the current theoretical profit or loss of the position if it were
@@ -694,8 +760,8 @@ impl Position {
             debug!("Premium must be greater than zero for short positions.");
             return false;
         }
-        if !self.option.validate() {
-            debug!("Option is not valid.");
+        if let Err(e) = self.option.validate() {
+            debug!("Option is not valid: {e}");
             return false;
         }
         true
@@ -712,6 +778,10 @@ impl Default for Position {
             close_fee: Positive::ZERO,
             epic: None,
             extra_fields: None,
+            status: PositionStatus::Open,
+            fills: Vec::new(),
+            realized_pnl: Decimal::ZERO,
+            currency: Currency::default(),
         }
     }
 }
@@ -1166,7 +1236,7 @@ mod tests_position {
     use super::*;
 
     use crate::constants::ZERO;
-    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 
     use chrono::Duration;
     use num_traits::ToPrimitive;
@@ -1194,6 +1264,8 @@ mod tests_position {
             option_style,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
@@ -1865,6 +1937,19 @@ mod tests_valid_position {
         assert!(!position.validate());
     }
 
+    #[test]
+    fn test_zero_volatility_leg_is_valid() {
+        let position = create_sample_position(
+            OptionStyle::Call,
+            Side::Short,
+            pos_or_panic!(90.0),
+            Positive::ONE,
+            pos_or_panic!(95.0),
+            Positive::ZERO,
+        );
+        assert!(position.validate());
+    }
+
     #[test]
     fn test_zero_fees() {
         let mut position = create_sample_position(
@@ -1885,7 +1970,7 @@ mod tests_valid_position {
 mod tests_position_break_even {
     use super::*;
 
-    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 
     use positive::pos_or_panic;
     use rust_decimal_macros::dec;
@@ -1911,6 +1996,8 @@ mod tests_position_break_even {
             option_style,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
@@ -2117,7 +2204,7 @@ mod tests_position_break_even {
 mod tests_position_max_loss_profit {
     use super::*;
 
-    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 
     use approx::assert_relative_eq;
     use positive::pos_or_panic;
@@ -2144,6 +2231,8 @@ mod tests_position_max_loss_profit {
             option_style,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
@@ -0,0 +1,288 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Position Builder
+//!
+//! [`PositionBuilder`] assembles a [`Position`] from named fields instead of
+//! the positional arguments of [`Position::new`], where the premium and the
+//! two fees are easy to swap.
+//!
+//! [`Position`] stores its fees per contract. Brokers often quote a flat fee
+//! per order instead, so every fee given to the builder states its
+//! [`FeeBasis`], and per-order amounts are spread over the option quantity
+//! when the position is built.
+//!
+//! ```rust
+//! use optionstratlib::model::{FeeBasis, PositionBuilder};
+//! use optionstratlib::model::utils::create_sample_option;
+//! use optionstratlib::{OptionStyle, Side};
+//! use positive::pos_or_panic;
+//!
+//! let option = create_sample_option(
+//!     OptionStyle::Call,
+//!     Side::Long,
+//!     pos_or_panic!(100.0),
+//!     pos_or_panic!(4.0),
+//!     pos_or_panic!(105.0),
+//!     pos_or_panic!(0.2),
+//! );
+//! let position = PositionBuilder::new()
+//!     .option(option)
+//!     .premium(pos_or_panic!(2.5))
+//!     .open_fee(pos_or_panic!(0.65), FeeBasis::PerContract)
+//!     .close_fee(pos_or_panic!(2.0), FeeBasis::PerOrder)
+//!     .epic("SPX.C105")
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(position.close_fee, pos_or_panic!(0.5));
+//! assert_eq!(position.fees().unwrap(), pos_or_panic!(4.6));
+//! ```
+
+use crate::error::PositionError;
+use crate::model::currency::Currency;
+use crate::model::option::Options;
+use crate::model::position::Position;
+use crate::model::types::Side;
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// How a fee amount is charged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum FeeBasis {
+    /// The amount is charged for every contract.
+    #[default]
+    PerContract,
+    /// The amount is charged once for the whole order.
+    PerOrder,
+}
+
+impl FeeBasis {
+    /// Converts `fee` into a per-contract fee for an order of `quantity` contracts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for a per-order fee on an order of zero contracts.
+    pub fn per_contract(
+        &self,
+        fee: Positive,
+        quantity: Positive,
+    ) -> Result<Positive, PositionError> {
+        match self {
+            FeeBasis::PerContract => Ok(fee),
+            FeeBasis::PerOrder if quantity == Positive::ZERO => {
+                Err(PositionError::invalid_position_size(
+                    0.0,
+                    "a per-order fee cannot be spread over zero contracts",
+                ))
+            }
+            FeeBasis::PerOrder => Ok(fee / quantity),
+        }
+    }
+}
+
+/// Builder for [`Position`].
+///
+/// The option and the premium are required. Fees default to zero, the fill
+/// timestamp to the time of [`PositionBuilder::build`] and the currency to
+/// the default [`Currency`].
+#[derive(Debug, Clone, Default)]
+pub struct PositionBuilder {
+    option: Option<Options>,
+    premium: Option<Positive>,
+    filled_at: Option<DateTime<Utc>>,
+    open_fee: (Positive, FeeBasis),
+    close_fee: (Positive, FeeBasis),
+    epic: Option<String>,
+    extra_fields: Option<serde_json::Value>,
+    currency: Currency,
+}
+
+impl PositionBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the option contract. Required.
+    pub fn option(mut self, option: Options) -> Self {
+        self.option = Some(option);
+        self
+    }
+
+    /// Sets the premium paid or received per contract. Required.
+    pub fn premium(mut self, premium: Positive) -> Self {
+        self.premium = Some(premium);
+        self
+    }
+
+    /// Sets the time the opening order was filled.
+    pub fn filled_at(mut self, filled_at: DateTime<Utc>) -> Self {
+        self.filled_at = Some(filled_at);
+        self
+    }
+
+    /// Sets the fee paid to open the position.
+    pub fn open_fee(mut self, fee: Positive, basis: FeeBasis) -> Self {
+        self.open_fee = (fee, basis);
+        self
+    }
+
+    /// Sets the fee expected to close the position.
+    pub fn close_fee(mut self, fee: Positive, basis: FeeBasis) -> Self {
+        self.close_fee = (fee, basis);
+        self
+    }
+
+    /// Sets the identifier of the position in an external system.
+    pub fn epic(mut self, epic: impl Into<String>) -> Self {
+        self.epic = Some(epic.into());
+        self
+    }
+
+    /// Sets additional custom data stored with the position.
+    pub fn extra_fields(mut self, extra_fields: serde_json::Value) -> Self {
+        self.extra_fields = Some(extra_fields);
+        self
+    }
+
+    /// Sets the currency of the premium, fees and P&L.
+    pub fn currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    /// Builds the position, with an opening fill at the fill timestamp.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the option or the premium is missing, if a short
+    /// position has no premium, or if a per-order fee is given for an option
+    /// of zero contracts.
+    pub fn build(self) -> Result<Position, PositionError> {
+        let option = self
+            .option
+            .ok_or_else(|| PositionError::invalid_position("option is required"))?;
+        let premium = self
+            .premium
+            .ok_or_else(|| PositionError::invalid_position("premium is required"))?;
+        if option.side == Side::Short && premium == Positive::ZERO {
+            return Err(PositionError::invalid_position(
+                "short positions must receive a premium",
+            ));
+        }
+        let (open_fee, open_basis) = self.open_fee;
+        let (close_fee, close_basis) = self.close_fee;
+        let open_fee = open_basis.per_contract(open_fee, option.quantity)?;
+        let close_fee = close_basis.per_contract(close_fee, option.quantity)?;
+
+        let mut position = Position::new(
+            option,
+            premium,
+            self.filled_at.unwrap_or_else(Utc::now),
+            open_fee,
+            close_fee,
+            self.epic,
+            self.extra_fields,
+        );
+        position.currency = self.currency;
+        Ok(position)
+    }
+}
+
+impl Position {
+    /// Returns a [`PositionBuilder`].
+    pub fn builder() -> PositionBuilder {
+        PositionBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests_position_builder {
+    use super::*;
+    use crate::model::types::OptionStyle;
+    use crate::model::utils::create_sample_option;
+    use chrono::TimeZone;
+    use positive::pos_or_panic;
+    use serde_json::json;
+
+    fn option(side: Side, quantity: Positive) -> Options {
+        create_sample_option(
+            OptionStyle::Put,
+            side,
+            Positive::HUNDRED,
+            quantity,
+            pos_or_panic!(95.0),
+            pos_or_panic!(0.2),
+        )
+    }
+
+    #[test]
+    fn test_build_matches_constructor() {
+        let date = Utc.with_ymd_and_hms(2026, 10, 1, 15, 30, 0).unwrap();
+        let built = Position::builder()
+            .option(option(Side::Long, Positive::TWO))
+            .premium(pos_or_panic!(1.5))
+            .filled_at(date)
+            .open_fee(pos_or_panic!(0.5), FeeBasis::PerContract)
+            .close_fee(pos_or_panic!(0.5), FeeBasis::PerContract)
+            .epic("EPIC")
+            .extra_fields(json!({"account": "A1"}))
+            .build()
+            .unwrap();
+        let expected = Position::new(
+            option(Side::Long, Positive::TWO),
+            pos_or_panic!(1.5),
+            date,
+            pos_or_panic!(0.5),
+            pos_or_panic!(0.5),
+            Some("EPIC".to_string()),
+            Some(json!({"account": "A1"})),
+        );
+        assert_eq!(built, expected);
+    }
+
+    #[test]
+    fn test_per_order_fees_are_spread_over_contracts() {
+        let position = Position::builder()
+            .option(option(Side::Long, pos_or_panic!(4.0)))
+            .premium(Positive::ONE)
+            .open_fee(pos_or_panic!(2.0), FeeBasis::PerOrder)
+            .build()
+            .unwrap();
+        assert_eq!(position.open_fee, pos_or_panic!(0.5));
+        assert_eq!(position.close_fee, Positive::ZERO);
+        assert_eq!(position.fees().unwrap(), pos_or_panic!(2.0));
+        assert_eq!(position.fills[0].fee, pos_or_panic!(0.5));
+
+        let empty = Position::builder()
+            .option(option(Side::Long, Positive::ZERO))
+            .premium(Positive::ONE)
+            .close_fee(Positive::ONE, FeeBasis::PerOrder)
+            .build();
+        assert!(empty.is_err());
+    }
+
+    #[test]
+    fn test_missing_fields_and_short_without_premium() {
+        assert!(Position::builder().premium(Positive::ONE).build().is_err());
+        assert!(
+            Position::builder()
+                .option(option(Side::Long, Positive::ONE))
+                .build()
+                .is_err()
+        );
+        assert!(
+            Position::builder()
+                .option(option(Side::Short, Positive::ONE))
+                .premium(Positive::ZERO)
+                .build()
+                .is_err()
+        );
+    }
+}
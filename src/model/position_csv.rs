@@ -0,0 +1,484 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Position CSV
+//!
+//! Flattened CSV export and import of positions and multi-leg strategies, to
+//! exchange books with spreadsheets and broker upload formats.
+//!
+//! Every row describes one leg with the columns of [`POSITION_CSV_HEADERS`].
+//! Legs of the same strategy share a `group_id`, and the strategy name and
+//! type are repeated on each of them; stand-alone positions leave the three
+//! columns empty. Expirations are written as an RFC 3339 date or as a number
+//! of days, and timestamps as RFC 3339.
+//!
+//! Only the contract and the opening trade are exported: the fill history of
+//! a position is not, and importing a row opens a new position with a single
+//! opening fill. Rows describe European or American options; exotic option
+//! types are rejected on export.
+//!
+//! ```rust
+//! use optionstratlib::model::utils::create_sample_position;
+//! use optionstratlib::model::{read_positions_csv, write_positions_csv};
+//! use optionstratlib::{OptionStyle, Side};
+//! use positive::{Positive, pos_or_panic};
+//!
+//! let position = create_sample_position(
+//!     OptionStyle::Put,
+//!     Side::Short,
+//!     Positive::HUNDRED,
+//!     Positive::ONE,
+//!     pos_or_panic!(95.0),
+//!     pos_or_panic!(0.2),
+//! );
+//! let mut csv = Vec::new();
+//! write_positions_csv(std::slice::from_ref(&position), &mut csv).unwrap();
+//!
+//! let restored = read_positions_csv(csv.as_slice()).unwrap();
+//! assert_eq!(restored[0].option.strike_price, pos_or_panic!(95.0));
+//! assert_eq!(restored[0].premium, position.premium);
+//! ```
+
+use crate::ExpirationDate;
+use crate::error::{OptionStratResult, PositionError};
+use crate::model::currency::Currency;
+use crate::model::option::Options;
+use crate::model::position::Position;
+use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
+use crate::strategies::StrategyBuilder;
+use crate::strategies::base::{Strategy, StrategyType};
+use chrono::{DateTime, Utc};
+use csv::StringRecord;
+use positive::Positive;
+use rust_decimal::Decimal;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+/// Columns of a position CSV, in order.
+pub const POSITION_CSV_HEADERS: [&str; 22] = [
+    "group_id",
+    "strategy_name",
+    "strategy_kind",
+    "symbol",
+    "option_type",
+    "style",
+    "side",
+    "strike",
+    "expiration",
+    "quantity",
+    "underlying_price",
+    "implied_volatility",
+    "risk_free_rate",
+    "dividend_yield",
+    "premium",
+    "open_fee",
+    "close_fee",
+    "opened_at",
+    "epic",
+    "currency",
+    "settlement",
+    "extra_fields",
+];
+
+/// Strategy columns of a row: group id, name and type.
+type Group<'a> = Option<(String, &'a str, &'a StrategyType)>;
+
+fn leg_record(group: Group<'_>, position: &Position) -> OptionStratResult<Vec<String>> {
+    let option = &position.option;
+    let option_type = match option.option_type {
+        OptionType::European => "European",
+        OptionType::American => "American",
+        _ => {
+            return Err(PositionError::invalid_position(
+                "CSV rows only describe European or American options",
+            )
+            .into());
+        }
+    };
+    let expiration = match option.expiration_date {
+        ExpirationDate::DateTime(date) => date.to_rfc3339(),
+        ExpirationDate::Days(days) => days.to_string(),
+    };
+    let extra_fields = match &position.extra_fields {
+        Some(value) => serde_json::to_string(value)
+            .map_err(|e| PositionError::invalid_position(&e.to_string()))?,
+        None => String::new(),
+    };
+    let (group_id, name, kind) = match group {
+        Some((id, name, kind)) => (id, name.to_string(), kind.to_string()),
+        None => Default::default(),
+    };
+    Ok(vec![
+        group_id,
+        name,
+        kind,
+        option.underlying_symbol.clone(),
+        option_type.to_string(),
+        match option.option_style {
+            OptionStyle::Call => "Call",
+            OptionStyle::Put => "Put",
+        }
+        .to_string(),
+        match option.side {
+            Side::Long => "Long",
+            Side::Short => "Short",
+        }
+        .to_string(),
+        option.strike_price.to_string(),
+        expiration,
+        option.quantity.to_string(),
+        option.underlying_price.to_string(),
+        option.implied_volatility.to_string(),
+        option.risk_free_rate.to_string(),
+        option.dividend_yield.to_string(),
+        position.premium.to_string(),
+        position.open_fee.to_string(),
+        position.close_fee.to_string(),
+        position.date.to_rfc3339(),
+        position.epic.clone().unwrap_or_default(),
+        position.currency.to_string(),
+        match option.settlement_type {
+            SettlementType::Physical => "Physical",
+            SettlementType::Cash => "Cash",
+        }
+        .to_string(),
+        extra_fields,
+    ])
+}
+
+fn write_records<'a, W: Write>(
+    rows: impl IntoIterator<Item = (Group<'a>, &'a Position)>,
+    writer: W,
+) -> OptionStratResult<()> {
+    let mut wtr = csv::Writer::from_writer(writer);
+    wtr.write_record(POSITION_CSV_HEADERS)?;
+    for (group, position) in rows {
+        wtr.write_record(leg_record(group, position)?)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Writes stand-alone positions as CSV, one row per position.
+///
+/// # Errors
+///
+/// Returns an error if a position holds an exotic option or writing fails.
+pub fn write_positions_csv<W: Write>(positions: &[Position], writer: W) -> OptionStratResult<()> {
+    write_records(positions.iter().map(|position| (None, position)), writer)
+}
+
+/// Writes strategies as CSV, one row per leg. The legs of the n-th strategy
+/// share the group id `n`, starting at one.
+///
+/// # Errors
+///
+/// Returns an error if a leg holds an exotic option or writing fails.
+pub fn write_strategies_csv<W: Write>(strategies: &[Strategy], writer: W) -> OptionStratResult<()> {
+    write_records(
+        strategies.iter().enumerate().flat_map(|(i, strategy)| {
+            strategy.legs.iter().map(move |leg| {
+                (
+                    Some(((i + 1).to_string(), strategy.name.as_str(), &strategy.kind)),
+                    leg,
+                )
+            })
+        }),
+        writer,
+    )
+}
+
+/// One parsed row: its group and the position it describes.
+struct LegRow {
+    group_id: String,
+    strategy_name: String,
+    strategy_kind: String,
+    position: Position,
+}
+
+/// Field accessor of a CSV row, reporting errors with the row number and column.
+struct Row<'a> {
+    number: usize,
+    headers: &'a StringRecord,
+    record: &'a StringRecord,
+}
+
+impl Row<'_> {
+    fn get(&self, column: &str) -> OptionStratResult<&str> {
+        self.headers
+            .iter()
+            .position(|header| header == column)
+            .and_then(|index| self.record.get(index))
+            .map(str::trim)
+            .ok_or_else(|| self.error(column, "missing column"))
+    }
+
+    fn error(&self, column: &str, reason: &str) -> crate::error::OptionStratError {
+        PositionError::invalid_position(&format!("row {}, {column}: {reason}", self.number)).into()
+    }
+
+    fn parse<T: FromStr>(&self, column: &str) -> OptionStratResult<T>
+    where
+        T::Err: std::fmt::Display,
+    {
+        let value = self.get(column)?;
+        value
+            .parse()
+            .map_err(|e: T::Err| self.error(column, &format!("invalid value '{value}': {e}")))
+    }
+
+    fn positive(&self, column: &str) -> OptionStratResult<Positive> {
+        let value: Decimal = self.parse(column)?;
+        Positive::new_decimal(value).map_err(|e| self.error(column, &e.to_string()))
+    }
+
+    fn date(&self, column: &str) -> OptionStratResult<DateTime<Utc>> {
+        let value = self.get(column)?;
+        DateTime::parse_from_rfc3339(value)
+            .map(|date| date.with_timezone(&Utc))
+            .map_err(|e| self.error(column, &format!("invalid date '{value}': {e}")))
+    }
+
+    fn optional(&self, column: &str) -> OptionStratResult<Option<String>> {
+        let value = self.get(column)?;
+        Ok((!value.is_empty()).then(|| value.to_string()))
+    }
+
+    fn leg(&self) -> OptionStratResult<LegRow> {
+        let option_type = match self.get("option_type")? {
+            "European" => OptionType::European,
+            "American" => OptionType::American,
+            other => return Err(self.error("option_type", &format!("unsupported type '{other}'"))),
+        };
+        let style = match self.get("style")? {
+            "Call" => OptionStyle::Call,
+            "Put" => OptionStyle::Put,
+            other => return Err(self.error("style", &format!("unknown style '{other}'"))),
+        };
+        let side = match self.get("side")? {
+            "Long" => Side::Long,
+            "Short" => Side::Short,
+            other => return Err(self.error("side", &format!("unknown side '{other}'"))),
+        };
+        let settlement = match self.get("settlement")? {
+            "" | "Physical" => SettlementType::Physical,
+            "Cash" => SettlementType::Cash,
+            other => return Err(self.error("settlement", &format!("unknown settlement '{other}'"))),
+        };
+        let expiration = if self.get("expiration")?.contains('-') {
+            ExpirationDate::DateTime(self.date("expiration")?)
+        } else {
+            ExpirationDate::Days(self.positive("expiration")?)
+        };
+        let extra_fields = match self.optional("extra_fields")? {
+            Some(json) => Some(
+                serde_json::from_str(&json)
+                    .map_err(|e| self.error("extra_fields", &e.to_string()))?,
+            ),
+            None => None,
+        };
+        let currency = match self.optional("currency")? {
+            Some(code) => Currency::new(&code),
+            None => Currency::default(),
+        };
+
+        let option = Options::new(
+            option_type,
+            side,
+            self.get("symbol")?.to_string(),
+            self.positive("strike")?,
+            expiration,
+            self.positive("implied_volatility")?,
+            self.positive("quantity")?,
+            self.positive("underlying_price")?,
+            self.parse("risk_free_rate")?,
+            style,
+            self.positive("dividend_yield")?,
+            None,
+        )
+        .with_settlement_type(settlement)
+        .with_day_count(DayCount::Act365Fixed);
+        let mut position = Position::new(
+            option,
+            self.positive("premium")?,
+            self.date("opened_at")?,
+            self.positive("open_fee")?,
+            self.positive("close_fee")?,
+            self.optional("epic")?,
+            extra_fields,
+        );
+        position.currency = currency;
+        Ok(LegRow {
+            group_id: self.get("group_id")?.to_string(),
+            strategy_name: self.get("strategy_name")?.to_string(),
+            strategy_kind: self.get("strategy_kind")?.to_string(),
+            position,
+        })
+    }
+}
+
+fn read_rows<R: Read>(reader: R) -> OptionStratResult<Vec<LegRow>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let headers = rdr.headers()?.clone();
+    rdr.records()
+        .enumerate()
+        .map(|(index, record)| {
+            let record = record?;
+            Row {
+                number: index + 1,
+                headers: &headers,
+                record: &record,
+            }
+            .leg()
+        })
+        .collect()
+}
+
+/// Reads positions from CSV, ignoring the strategy columns.
+///
+/// # Errors
+///
+/// Returns an error naming the row and column of the first invalid field,
+/// or the error of the CSV reader.
+pub fn read_positions_csv<R: Read>(reader: R) -> OptionStratResult<Vec<Position>> {
+    Ok(read_rows(reader)?
+        .into_iter()
+        .map(|row| row.position)
+        .collect())
+}
+
+/// Reads strategies from CSV, grouping legs by `group_id` in order of first
+/// appearance. Every row without a group id becomes a strategy of its own.
+///
+/// Strategies are assembled with a [`StrategyBuilder`], so their metrics are
+/// derived from the legs and a missing strategy type is inferred.
+///
+/// # Errors
+///
+/// Returns an error naming the row and column of the first invalid field,
+/// an unknown strategy type, legs inconsistent with their strategy type, or
+/// the error of the CSV reader.
+pub fn read_strategies_csv<R: Read>(reader: R) -> OptionStratResult<Vec<Strategy>> {
+    let mut groups: Vec<(String, Vec<LegRow>)> = Vec::new();
+    for row in read_rows(reader)? {
+        match groups
+            .iter_mut()
+            .find(|(id, _)| !row.group_id.is_empty() && *id == row.group_id)
+        {
+            Some((_, legs)) => legs.push(row),
+            None => groups.push((row.group_id.clone(), vec![row])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(group_id, legs)| {
+            let mut builder = StrategyBuilder::new();
+            if let Some(first) = legs.first() {
+                if !first.strategy_name.is_empty() {
+                    builder = builder.name(first.strategy_name.clone());
+                }
+                if !first.strategy_kind.is_empty() {
+                    let kind = StrategyType::from_str(&first.strategy_kind).map_err(|_| {
+                        PositionError::invalid_position(&format!(
+                            "group {group_id}: unknown strategy type '{}'",
+                            first.strategy_kind
+                        ))
+                    })?;
+                    builder = builder.kind(kind);
+                }
+            }
+            Ok(builder
+                .legs(legs.into_iter().map(|row| row.position))
+                .build()?)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_position_csv {
+    use super::*;
+    use crate::model::utils::create_sample_option;
+    use chrono::TimeZone;
+    use positive::pos_or_panic;
+    use serde_json::json;
+
+    fn leg(style: OptionStyle, side: Side, strike: f64, premium: f64) -> Position {
+        let option = create_sample_option(
+            style,
+            side,
+            Positive::HUNDRED,
+            Positive::ONE,
+            pos_or_panic!(strike),
+            pos_or_panic!(0.2),
+        );
+        Position::builder()
+            .option(option)
+            .premium(pos_or_panic!(premium))
+            .filled_at(Utc.with_ymd_and_hms(2026, 9, 1, 14, 0, 0).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_positions_roundtrip() {
+        let mut position = leg(OptionStyle::Put, Side::Short, 95.0, 2.5);
+        position.epic = Some("OP.D.SPX.95P".to_string());
+        position.extra_fields = Some(json!({"account": "A1, main"}));
+        position.currency = Currency::new("EUR");
+        position.option.expiration_date =
+            ExpirationDate::DateTime(Utc.with_ymd_and_hms(2026, 12, 18, 21, 0, 0).unwrap());
+        position.option.settlement_type = SettlementType::Cash;
+
+        let mut csv = Vec::new();
+        write_positions_csv(std::slice::from_ref(&position), &mut csv).unwrap();
+        let text = String::from_utf8(csv.clone()).unwrap();
+        assert_eq!(text.lines().next().unwrap(), POSITION_CSV_HEADERS.join(","));
+
+        let restored = read_positions_csv(csv.as_slice()).unwrap();
+        assert_eq!(restored, vec![position]);
+    }
+
+    #[test]
+    fn test_strategies_roundtrip_groups_legs() {
+        let spread = StrategyBuilder::new()
+            .name("Bull call")
+            .leg(leg(OptionStyle::Call, Side::Long, 95.0, 7.0))
+            .leg(leg(OptionStyle::Call, Side::Short, 105.0, 3.0))
+            .build()
+            .unwrap();
+        let short_put = StrategyBuilder::new()
+            .leg(leg(OptionStyle::Put, Side::Short, 90.0, 1.0))
+            .build()
+            .unwrap();
+
+        let mut csv = Vec::new();
+        write_strategies_csv(&[spread.clone(), short_put.clone()], &mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv.clone()).unwrap().lines().count(), 4);
+
+        let restored = read_strategies_csv(csv.as_slice()).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert!(restored[0] == spread);
+        assert!(restored[1] == short_put);
+    }
+
+    #[test]
+    fn test_invalid_rows_name_the_field() {
+        let mut csv = Vec::new();
+        write_positions_csv(&[leg(OptionStyle::Call, Side::Long, 100.0, 4.0)], &mut csv).unwrap();
+        let text = String::from_utf8(csv)
+            .unwrap()
+            .replace(",Call,", ",Straddle,");
+        let error = read_positions_csv(text.as_bytes()).unwrap_err().to_string();
+        assert!(error.contains("row 1, style"), "{error}");
+
+        let mut exotic = leg(OptionStyle::Call, Side::Long, 100.0, 4.0);
+        exotic.option.option_type = OptionType::Power { exponent: 2.0 };
+        assert!(write_positions_csv(&[exotic], Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+use crate::model::types::Side;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// Convention used to turn a bid/ask quote into an execution price.
+///
+/// Entry prices are the prices paid or received when a position is opened and
+/// exit prices those obtained when it is closed. Adverse conventions always
+/// move against the trader: buying happens above mid and selling below it.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub enum QuotePricing {
+    /// Mid point of bid and ask.
+    #[default]
+    Mid,
+    /// Crossing the spread: buy at the ask, sell at the bid.
+    Natural,
+    /// Mid moved against the trader by a fraction of the spread.
+    ///
+    /// A fraction of `0` is equivalent to [`QuotePricing::Mid`] and `0.5` to
+    /// [`QuotePricing::Natural`]; values are clamped to `[0, 0.5]`.
+    Slippage {
+        /// Fraction of the bid/ask spread paid over mid.
+        fraction: Decimal,
+    },
+}
+
+impl QuotePricing {
+    /// Execution price when trading `side` at the given quote.
+    ///
+    /// `Side::Long` buys and `Side::Short` sells.
+    pub fn execution_price(&self, bid: Positive, ask: Positive, side: Side) -> Positive {
+        let (bid, ask) = (bid.to_dec(), ask.to_dec());
+        let mid = (bid + ask) / Decimal::TWO;
+        let spread = (ask - bid).max(Decimal::ZERO);
+        let adverse = match self {
+            QuotePricing::Mid => Decimal::ZERO,
+            QuotePricing::Natural => spread / Decimal::TWO,
+            QuotePricing::Slippage { fraction } => {
+                spread * (*fraction).clamp(Decimal::ZERO, Decimal::new(5, 1))
+            }
+        };
+        let price = match side {
+            Side::Long => mid + adverse,
+            Side::Short => mid - adverse,
+        };
+        Positive::new_decimal(price.max(Decimal::ZERO)).unwrap_or(Positive::ZERO)
+    }
+
+    /// Price at which a position of `side` is opened.
+    pub fn entry_price(&self, bid: Positive, ask: Positive, side: Side) -> Positive {
+        self.execution_price(bid, ask, side)
+    }
+
+    /// Price at which a position of `side` is closed (a long sells, a short buys back).
+    pub fn exit_price(&self, bid: Positive, ask: Positive, side: Side) -> Positive {
+        let closing_side = match side {
+            Side::Long => Side::Short,
+            Side::Short => Side::Long,
+        };
+        self.execution_price(bid, ask, closing_side)
+    }
+}
+
+impl fmt::Display for QuotePricing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuotePricing::Mid => write!(f, "Mid"),
+            QuotePricing::Natural => write!(f, "Natural"),
+            QuotePricing::Slippage { fraction } => write!(f, "Mid ± {fraction} spread"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_quote_pricing {
+    use super::*;
+    use positive::pos_or_panic;
+
+    #[test]
+    fn test_mid_and_natural() {
+        let (bid, ask) = (pos_or_panic!(1.0), pos_or_panic!(1.2));
+        assert_eq!(
+            QuotePricing::Mid.entry_price(bid, ask, Side::Long),
+            pos_or_panic!(1.1)
+        );
+        assert_eq!(QuotePricing::Natural.entry_price(bid, ask, Side::Long), ask);
+        assert_eq!(
+            QuotePricing::Natural.entry_price(bid, ask, Side::Short),
+            bid
+        );
+        assert_eq!(QuotePricing::Natural.exit_price(bid, ask, Side::Long), bid);
+        assert_eq!(QuotePricing::Natural.exit_price(bid, ask, Side::Short), ask);
+    }
+
+    #[test]
+    fn test_slippage() {
+        let (bid, ask) = (pos_or_panic!(1.0), pos_or_panic!(2.0));
+        let pricing = QuotePricing::Slippage {
+            fraction: Decimal::new(25, 2),
+        };
+        assert_eq!(
+            pricing.entry_price(bid, ask, Side::Long),
+            pos_or_panic!(1.75)
+        );
+        assert_eq!(
+            pricing.entry_price(bid, ask, Side::Short),
+            pos_or_panic!(1.25)
+        );
+        let capped = QuotePricing::Slippage {
+            fraction: Decimal::TEN,
+        };
+        assert_eq!(capped.entry_price(bid, ask, Side::Long), ask);
+    }
+
+    #[test]
+    fn test_crossed_quote_has_no_spread() {
+        let price =
+            QuotePricing::Natural.entry_price(pos_or_panic!(2.0), pos_or_panic!(1.0), Side::Long);
+        assert_eq!(price, pos_or_panic!(1.5));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(QuotePricing::default().to_string(), "Mid");
+        assert_eq!(QuotePricing::Natural.to_string(), "Natural");
+    }
+}
@@ -0,0 +1,385 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Position Rolling
+//!
+//! Rolling closes an existing option leg and opens a replacement of the same
+//! side and style at a new strike, a new expiration, or both:
+//!
+//! * rolling **out** moves the leg to a later expiration,
+//! * rolling **up** or **down** moves the leg to a higher or lower strike,
+//! * rolling **out and up** (or down) does both at once.
+//!
+//! A [`Roll`] records the closing and opening trades, linked by a shared
+//! identifier, together with the net credit or debit of the operation
+//! including the closing fee of the old leg and the opening fee of the new one.
+
+use crate::error::position::PositionError;
+use crate::model::ExpirationDate;
+use crate::model::position::Position;
+use crate::model::trade::{Trade, TradeAble, TradeStatus};
+use crate::model::types::{Action, Side};
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use utoipa::ToSchema;
+
+/// How the strike of the rolled leg changes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum StrikeAdjustment {
+    /// Keep the current strike.
+    Keep,
+    /// Move to the given strike.
+    To(Positive),
+    /// Move the strike by the given amount, up if positive and down if negative.
+    Shift(Decimal),
+}
+
+/// Strike and expiration of the replacement leg.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RollTarget {
+    /// How the strike changes.
+    pub strike: StrikeAdjustment,
+    /// New expiration, or `None` to keep the current one.
+    pub expiration: Option<ExpirationDate>,
+}
+
+impl RollTarget {
+    /// Rolls out to a later expiration at the same strike.
+    pub fn out(expiration: ExpirationDate) -> Self {
+        RollTarget {
+            strike: StrikeAdjustment::Keep,
+            expiration: Some(expiration),
+        }
+    }
+
+    /// Rolls to a new strike in the same expiration.
+    pub fn to_strike(strike: Positive) -> Self {
+        RollTarget {
+            strike: StrikeAdjustment::To(strike),
+            expiration: None,
+        }
+    }
+
+    /// Rolls by shifting the strike in the same expiration.
+    pub fn shift_strike(shift: Decimal) -> Self {
+        RollTarget {
+            strike: StrikeAdjustment::Shift(shift),
+            expiration: None,
+        }
+    }
+
+    /// Also moves the replacement leg to a new expiration.
+    pub fn with_expiration(mut self, expiration: ExpirationDate) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+
+    /// Strike of the replacement of a leg with the given strike.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if a shift takes the strike to zero or below.
+    pub fn strike_for(&self, current: Positive) -> Result<Positive, PositionError> {
+        match self.strike {
+            StrikeAdjustment::Keep => Ok(current),
+            StrikeAdjustment::To(strike) => Ok(strike),
+            StrikeAdjustment::Shift(shift) => {
+                let strike = current.to_dec() + shift;
+                if strike <= Decimal::ZERO {
+                    return Err(PositionError::invalid_position(&format!(
+                        "Shifting strike {current} by {shift} does not give a positive strike"
+                    )));
+                }
+                Ok(Positive::new_decimal(strike)
+                    .map_err(|e| PositionError::invalid_position(&e.to_string()))?)
+            }
+        }
+    }
+}
+
+/// Direction of a roll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum RollKind {
+    /// Later expiration, same strike.
+    Out,
+    /// Higher strike, same expiration.
+    Up,
+    /// Lower strike, same expiration.
+    Down,
+    /// Later expiration and higher strike.
+    OutAndUp,
+    /// Later expiration and lower strike.
+    OutAndDown,
+    /// Any other combination, such as a roll to an earlier expiration.
+    Other,
+}
+
+impl RollKind {
+    fn classify(strike: Ordering, expiration: Ordering) -> Option<Self> {
+        match (expiration, strike) {
+            (Ordering::Equal, Ordering::Equal) => None,
+            (Ordering::Greater, Ordering::Equal) => Some(RollKind::Out),
+            (Ordering::Equal, Ordering::Greater) => Some(RollKind::Up),
+            (Ordering::Equal, Ordering::Less) => Some(RollKind::Down),
+            (Ordering::Greater, Ordering::Greater) => Some(RollKind::OutAndUp),
+            (Ordering::Greater, Ordering::Less) => Some(RollKind::OutAndDown),
+            _ => Some(RollKind::Other),
+        }
+    }
+}
+
+/// Result of rolling a position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Roll {
+    /// Identifier shared by the closing and opening trades.
+    pub id: uuid::Uuid,
+    /// Direction of the roll.
+    pub kind: RollKind,
+    /// Trade closing the existing leg.
+    pub closing_trade: Trade,
+    /// Trade opening the replacement leg.
+    pub opening_trade: Trade,
+    /// The replacement leg.
+    pub replacement: Position,
+    /// Closing fee of the old leg plus opening fee of the new one.
+    pub fees: Positive,
+    /// Net cash flow of the roll after fees: a credit if positive, a debit if negative.
+    pub net_credit: Decimal,
+}
+
+impl Roll {
+    /// Returns `true` if the roll brings in cash after fees.
+    pub fn is_credit(&self) -> bool {
+        self.net_credit > Decimal::ZERO
+    }
+}
+
+impl Position {
+    /// Rolls the position into a new strike and/or expiration.
+    ///
+    /// The existing leg is closed at `close_price` and the replacement, with
+    /// the same side, style, quantity and fee schedule, is opened at
+    /// `open_price` on `date`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PositionError` if the target leaves the leg unchanged, the
+    /// new strike is not positive, or an expiration cannot be resolved.
+    pub fn roll(
+        &self,
+        target: &RollTarget,
+        close_price: Positive,
+        open_price: Positive,
+        date: DateTime<Utc>,
+    ) -> Result<Roll, PositionError> {
+        let strike = target.strike_for(self.option.strike_price)?;
+        let expiration = target.expiration.unwrap_or(self.option.expiration_date);
+        let days = |expiration: &ExpirationDate| {
+            expiration
+                .get_days()
+                .map_err(|e| PositionError::invalid_position(&e.to_string()))
+        };
+        let kind = RollKind::classify(
+            strike.cmp(&self.option.strike_price),
+            days(&expiration)?.cmp(&days(&self.option.expiration_date)?),
+        )
+        .ok_or_else(|| PositionError::invalid_position("Roll target leaves the leg unchanged"))?;
+
+        let mut option = self.option.clone();
+        option.strike_price = strike;
+        option.expiration_date = expiration;
+        let replacement = Position::new(
+            option,
+            open_price,
+            date,
+            self.open_fee,
+            self.close_fee,
+            self.epic.clone(),
+            self.extra_fields.clone(),
+        );
+
+        let id = uuid::Uuid::new_v4();
+        let linked_trade = |position: &Position,
+                            action: Action,
+                            premium: Positive,
+                            fee: Positive,
+                            status: TradeStatus,
+                            leg: &str|
+         -> Result<Trade, PositionError> {
+            let mut trade = position
+                .trade()
+                .map_err(|e| PositionError::invalid_position(&e.to_string()))?;
+            trade.action = action;
+            trade.premium = premium;
+            trade.fee = fee;
+            trade.status = status;
+            trade.notes = Some(format!("roll {id}: {leg}"));
+            trade.set_timestamp(date);
+            Ok(trade)
+        };
+
+        let (close_action, open_action) = match self.option.side {
+            Side::Long => (Action::Sell, Action::Buy),
+            Side::Short => (Action::Buy, Action::Sell),
+        };
+        let closing_trade = linked_trade(
+            self,
+            close_action,
+            close_price,
+            self.close_fee,
+            TradeStatus::Closed,
+            "close",
+        )?;
+        let opening_trade = linked_trade(
+            &replacement,
+            open_action,
+            open_price,
+            replacement.open_fee,
+            TradeStatus::Open,
+            "open",
+        )?;
+
+        let quantity = self.option.quantity;
+        let fees = (self.close_fee + replacement.open_fee) * quantity;
+        let premium_flow = (close_price.to_dec() - open_price.to_dec()) * quantity.to_dec();
+        let net_credit = match self.option.side {
+            Side::Long => premium_flow,
+            Side::Short => -premium_flow,
+        } - fees.to_dec();
+
+        Ok(Roll {
+            id,
+            kind,
+            closing_trade,
+            opening_trade,
+            replacement,
+            fees,
+            net_credit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests_roll {
+    use super::*;
+    use crate::model::Options;
+    use crate::model::types::{OptionStyle, OptionType};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn short_call() -> Position {
+        let option = Options::new(
+            OptionType::European,
+            Side::Short,
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(10.0)),
+            pos_or_panic!(0.2),
+            Positive::TWO,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            pos_or_panic!(2.0),
+            Utc::now(),
+            pos_or_panic!(0.5),
+            pos_or_panic!(0.5),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_roll_out_and_up_for_credit() {
+        let position = short_call();
+        let target = RollTarget::to_strike(pos_or_panic!(105.0))
+            .with_expiration(ExpirationDate::Days(pos_or_panic!(40.0)));
+        let roll = position
+            .roll(&target, pos_or_panic!(3.0), pos_or_panic!(4.5), Utc::now())
+            .unwrap();
+
+        assert_eq!(roll.kind, RollKind::OutAndUp);
+        assert_eq!(roll.replacement.option.strike_price, pos_or_panic!(105.0));
+        assert_eq!(roll.replacement.premium, pos_or_panic!(4.5));
+        assert_eq!(roll.replacement.option.side, Side::Short);
+        // (4.5 - 3.0) * 2 received, (0.5 + 0.5) * 2 in fees.
+        assert_eq!(roll.fees, Positive::TWO);
+        assert_eq!(roll.net_credit, dec!(1));
+        assert!(roll.is_credit());
+
+        assert_eq!(roll.closing_trade.action, Action::Buy);
+        assert_eq!(roll.closing_trade.status, TradeStatus::Closed);
+        assert_eq!(roll.opening_trade.action, Action::Sell);
+        assert_eq!(roll.opening_trade.strike, pos_or_panic!(105.0));
+        let link = format!("roll {}", roll.id);
+        assert!(
+            roll.closing_trade
+                .notes
+                .as_ref()
+                .unwrap()
+                .starts_with(&link)
+        );
+        assert!(
+            roll.opening_trade
+                .notes
+                .as_ref()
+                .unwrap()
+                .starts_with(&link)
+        );
+    }
+
+    #[test]
+    fn test_roll_down_long_leg_for_debit() {
+        let mut position = short_call();
+        position.option.side = Side::Long;
+        let roll = position
+            .roll(
+                &RollTarget::shift_strike(dec!(-5)),
+                pos_or_panic!(1.0),
+                pos_or_panic!(3.0),
+                Utc::now(),
+            )
+            .unwrap();
+        assert_eq!(roll.kind, RollKind::Down);
+        assert_eq!(roll.replacement.option.strike_price, pos_or_panic!(95.0));
+        assert_eq!(roll.net_credit, dec!(-6));
+        assert_eq!(roll.closing_trade.action, Action::Sell);
+    }
+
+    #[test]
+    fn test_roll_out_and_invalid_targets() {
+        let position = short_call();
+        let roll = position
+            .roll(
+                &RollTarget::out(ExpirationDate::Days(pos_or_panic!(30.0))),
+                pos_or_panic!(1.0),
+                pos_or_panic!(2.0),
+                Utc::now(),
+            )
+            .unwrap();
+        assert_eq!(roll.kind, RollKind::Out);
+
+        let unchanged = RollTarget::to_strike(Positive::HUNDRED);
+        assert!(
+            position
+                .roll(&unchanged, Positive::ONE, Positive::ONE, Utc::now())
+                .is_err()
+        );
+        let below_zero = RollTarget::shift_strike(dec!(-100));
+        assert!(
+            position
+                .roll(&below_zero, Positive::ONE, Positive::ONE, Utc::now())
+                .is_err()
+        );
+    }
+}
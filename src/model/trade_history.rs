@@ -0,0 +1,580 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Broker Trade History Import
+//!
+//! Imports the CSV trade exports of retail brokers into a [`Portfolio`]:
+//!
+//! - **Tastytrade** transaction history: rows of `Type` `Trade` on equity
+//!   options, with fills described as `Sold 2 SPY 12/20/24 Put 440.00 @ 5.20`.
+//!   The `Action` column, such as `SELL_TO_OPEN`, gives the position effect,
+//!   and `Commissions` plus `Fees` the fee of the fill.
+//! - **thinkorswim** account statement: the `TRD` rows of its Cash Balance
+//!   section, with fills described as
+//!   `SOLD -2 SPY 100 (Weeklys) 20 DEC 24 440 PUT @5.20`, charged the `Misc
+//!   Fees` and `Commissions & Fees` columns. Other sections of the statement
+//!   are skipped.
+//!
+//! Option descriptions are read with [`OptionDescription`]. Descriptions of
+//! several legs at once, such as thinkorswim verticals, are rejected: their
+//! legs are listed one by one in the Account Trade History section.
+//!
+//! Every fill becomes an [`ExecutionReport`] and is replayed in chronological
+//! order with [`Portfolio::apply_execution`], so positions keep their fills,
+//! fees and realized P&L, and their OCC symbol in `epic`. Exports do not
+//! describe the market, so the importer is given the implied volatility and
+//! the prices of the underlyings to create the options with.
+//!
+//! ```rust
+//! use optionstratlib::model::trade_history::{TradeHistoryFormat, TradeHistoryImporter};
+//! use positive::pos_or_panic;
+//!
+//! let csv = "\
+//! Date,Type,Sub Type,Action,Symbol,Instrument Type,Description,Value,Quantity,Average Price,Commissions,Fees,Multiplier,Order #,Currency
+//! 2026-10-01T14:30:00+0000,Trade,Sell to Open,SELL_TO_OPEN,SPY   261218P00440000,Equity Option,Sold 2 SPY 12/18/26 Put 440.00 @ 5.20,1040.00,2,520.00,-2.00,-0.28,100,1001,USD
+//! ";
+//! let portfolio = TradeHistoryImporter::new(TradeHistoryFormat::Tastytrade, pos_or_panic!(0.2))
+//!     .underlying_price("SPY", pos_or_panic!(445.0))
+//!     .import(csv.as_bytes())
+//!     .unwrap();
+//!
+//! let position = &portfolio.positions[0];
+//! assert_eq!(position.premium, pos_or_panic!(5.2));
+//! assert_eq!(position.open_fee, pos_or_panic!(1.14));
+//! ```
+
+use crate::error::PositionError;
+use crate::model::Portfolio;
+use crate::model::fix::ExecutionReport;
+use crate::model::lifecycle::FillKind;
+use crate::model::occ::OccSymbol;
+use crate::model::position_builder::FeeBasis;
+use crate::model::types::{Action, OptionStyle};
+use crate::utils::ExchangeTimeZone;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use csv::StringRecord;
+use positive::Positive;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::str::FromStr;
+
+/// CSV export formats of [`TradeHistoryImporter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TradeHistoryFormat {
+    /// Tastytrade transaction history.
+    Tastytrade,
+    /// thinkorswim account statement.
+    ThinkOrSwim,
+}
+
+/// A single-leg option fill, as described by a broker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionDescription {
+    /// Whether the contracts were bought or sold.
+    pub action: Action,
+    /// Number of contracts.
+    pub quantity: Positive,
+    /// Symbol of the underlying.
+    pub underlying_symbol: String,
+    /// Expiration date.
+    pub expiration: NaiveDate,
+    /// Call or put.
+    pub style: OptionStyle,
+    /// Strike price.
+    pub strike: Positive,
+    /// Price per contract, when described.
+    pub price: Option<Positive>,
+}
+
+impl OptionDescription {
+    /// OCC symbol of the described contract.
+    pub fn occ_symbol(&self) -> OccSymbol {
+        OccSymbol::new(
+            self.underlying_symbol.clone(),
+            self.expiration,
+            self.style,
+            self.strike,
+        )
+    }
+}
+
+impl FromStr for OptionDescription {
+    type Err = PositionError;
+
+    /// Reads a Tastytrade (`Bought 1 AAPL 11/20/26 Call 150.00 @ 2.35`) or
+    /// thinkorswim (`BOT +1 AAPL 100 20 NOV 26 150 CALL @2.35`) description.
+    fn from_str(description: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| {
+            PositionError::invalid_position(&format!(
+                "cannot read option description '{description}': {reason}"
+            ))
+        };
+        let tokens: Vec<&str> = description.split_whitespace().collect();
+        if tokens.len() < 6 {
+            return Err(invalid("too few fields"));
+        }
+        let action = match tokens[0].to_ascii_uppercase().as_str() {
+            "BOUGHT" | "BOT" | "BUY" => Action::Buy,
+            "SOLD" | "SELL" => Action::Sell,
+            _ => return Err(invalid("unknown action")),
+        };
+        let quantity = Decimal::from_str(tokens[1].trim_start_matches('+'))
+            .ok()
+            .and_then(|quantity| Positive::new_decimal(quantity.abs()).ok())
+            .ok_or_else(|| invalid("invalid quantity"))?;
+        let underlying_symbol = tokens[2].to_string();
+
+        let style_at = tokens
+            .iter()
+            .position(|token| matches!(token.to_ascii_uppercase().as_str(), "CALL" | "PUT"))
+            .ok_or_else(|| invalid("no call or put"))?;
+        let style = match tokens[style_at].to_ascii_uppercase().as_str() {
+            "CALL" => OptionStyle::Call,
+            _ => OptionStyle::Put,
+        };
+        let expiration = tokens
+            .iter()
+            .find_map(|token| NaiveDate::parse_from_str(token, "%m/%d/%y").ok())
+            .or_else(|| {
+                tokens.windows(3).find_map(|window| {
+                    NaiveDate::parse_from_str(&window.join(" "), "%d %b %y").ok()
+                })
+            })
+            .ok_or_else(|| invalid("no expiration date"))?;
+        let strike_token = match tokens.get(style_at + 1) {
+            Some(token) if !token.starts_with('@') => token,
+            _ if style_at > 0 => tokens[style_at - 1],
+            _ => return Err(invalid("no strike")),
+        };
+        let strike = Decimal::from_str(strike_token)
+            .ok()
+            .and_then(|strike| Positive::new_decimal(strike).ok())
+            .ok_or_else(|| invalid("invalid strike, only single legs are supported"))?;
+        let price = match tokens.iter().position(|token| token.starts_with('@')) {
+            Some(at) => {
+                let price = match tokens[at].trim_start_matches('@') {
+                    "" => tokens.get(at + 1).copied().unwrap_or_default(),
+                    price => price,
+                };
+                Some(
+                    Decimal::from_str(price)
+                        .ok()
+                        .and_then(|price| Positive::new_decimal(price).ok())
+                        .ok_or_else(|| invalid("invalid price"))?,
+                )
+            }
+            None => None,
+        };
+
+        Ok(OptionDescription {
+            action,
+            quantity,
+            underlying_symbol,
+            expiration,
+            style,
+            strike,
+            price,
+        })
+    }
+}
+
+/// Importer of broker trade history exports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeHistoryImporter {
+    format: TradeHistoryFormat,
+    timezone: ExchangeTimeZone,
+    implied_volatility: Positive,
+    underlying_prices: BTreeMap<String, Positive>,
+}
+
+impl TradeHistoryImporter {
+    /// Creates an importer of `format` exports giving `implied_volatility` to
+    /// the options it creates, with local times in New York time.
+    pub fn new(format: TradeHistoryFormat, implied_volatility: Positive) -> Self {
+        TradeHistoryImporter {
+            format,
+            timezone: ExchangeTimeZone::NewYork,
+            implied_volatility,
+            underlying_prices: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the time zone of local times, used by thinkorswim statements.
+    pub fn timezone(mut self, timezone: ExchangeTimeZone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Sets the price of an underlying, required for every underlying of the
+    /// imported options.
+    pub fn underlying_price(mut self, symbol: impl Into<String>, price: Positive) -> Self {
+        self.underlying_prices.insert(symbol.into(), price);
+        self
+    }
+
+    /// Reads the option fills of an export, in the order of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the row of the first fill that cannot be read,
+    /// or the error of the CSV reader.
+    pub fn parse<R: Read>(&self, reader: R) -> Result<Vec<ExecutionReport>, PositionError> {
+        match self.format {
+            TradeHistoryFormat::Tastytrade => self.parse_tastytrade(reader),
+            TradeHistoryFormat::ThinkOrSwim => self.parse_thinkorswim(reader),
+        }
+    }
+
+    /// Reads an export and replays its fills into a portfolio.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the export cannot be read, an underlying has no
+    /// price, or a fill cannot be applied, such as the close of a position
+    /// opened before the export.
+    pub fn import<R: Read>(&self, reader: R) -> Result<Portfolio, PositionError> {
+        let mut reports = self.parse(reader)?;
+        reports.sort_by_key(|report| report.transact_time);
+
+        let mut portfolio = Portfolio::new(format!("{:?}", self.format));
+        for report in &reports {
+            let underlying_price = self
+                .underlying_prices
+                .get(&report.underlying_symbol)
+                .copied()
+                .ok_or_else(|| {
+                    PositionError::invalid_position(&format!(
+                        "no underlying price for {}",
+                        report.underlying_symbol
+                    ))
+                })?;
+            portfolio.apply_execution(report, underlying_price, self.implied_volatility)?;
+        }
+        Ok(portfolio)
+    }
+
+    fn parse_tastytrade<R: Read>(&self, reader: R) -> Result<Vec<ExecutionReport>, PositionError> {
+        let mut rdr = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let headers = rdr.headers().map_err(csv_error)?.clone();
+        let mut reports = Vec::new();
+        for (index, record) in rdr.records().enumerate() {
+            let row = Row {
+                number: index + 1,
+                headers: &headers,
+                record: &record.map_err(csv_error)?,
+            };
+            if row.get("Type") != Some("Trade")
+                || row.get("Instrument Type") != Some("Equity Option")
+            {
+                continue;
+            }
+            let description: OptionDescription = row.required("Description")?.parse()?;
+            let date = row.required("Date")?;
+            let transact_time = DateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S%z")
+                .map(|date| date.with_timezone(&Utc))
+                .map_err(|_| row.error("Date", "invalid date"))?;
+            let position_effect = row.get("Action").and_then(|action| {
+                if action.ends_with("OPEN") {
+                    Some(FillKind::Open)
+                } else if action.ends_with("CLOSE") {
+                    Some(FillKind::Close)
+                } else {
+                    None
+                }
+            });
+            let price = match description.price {
+                Some(price) => price,
+                None => {
+                    let multiplier = row.amount("Multiplier")?;
+                    let average = row.amount("Average Price")?;
+                    if multiplier == Decimal::ZERO {
+                        return Err(row.error("Multiplier", "no price in the row"));
+                    }
+                    Positive::new_decimal((average / multiplier).abs())
+                        .map_err(|e| row.error("Average Price", &e.to_string()))?
+                }
+            };
+            let commission = row.amount("Commissions")?.abs() + row.amount("Fees")?.abs();
+            reports.push(report(
+                format!("{}-{}", row.get("Order #").unwrap_or_default(), row.number),
+                row.get("Order #").unwrap_or_default().to_string(),
+                &description,
+                price,
+                commission,
+                transact_time,
+                position_effect,
+            )?);
+        }
+        Ok(reports)
+    }
+
+    fn parse_thinkorswim<R: Read>(&self, reader: R) -> Result<Vec<ExecutionReport>, PositionError> {
+        let rdr = csv::ReaderBuilder::new()
+            .flexible(true)
+            .has_headers(false)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let mut headers: Option<StringRecord> = None;
+        let mut reports = Vec::new();
+        for (index, record) in rdr.into_records().enumerate() {
+            let record = record.map_err(csv_error)?;
+            if record.get(0) == Some("DATE") && record.get(1) == Some("TIME") {
+                headers = Some(record);
+                continue;
+            }
+            let Some(section) = &headers else {
+                continue;
+            };
+            if record.len() < section.len() {
+                headers = None;
+                continue;
+            }
+            let row = Row {
+                number: index + 1,
+                headers: section,
+                record: &record,
+            };
+            if row.get("TYPE") != Some("TRD") {
+                continue;
+            }
+            let description: OptionDescription = match row.required("DESCRIPTION") {
+                Ok(text) if text.contains(" CALL") || text.contains(" PUT") => text.parse()?,
+                _ => continue,
+            };
+            let date = format!("{} {}", row.required("DATE")?, row.required("TIME")?);
+            let local = NaiveDateTime::parse_from_str(&date, "%m/%d/%y %H:%M:%S")
+                .map_err(|_| row.error("DATE", "invalid date"))?;
+            let price = description
+                .price
+                .ok_or_else(|| row.error("DESCRIPTION", "no price"))?;
+            let commission =
+                row.amount("Misc Fees")?.abs() + row.amount("Commissions & Fees")?.abs();
+            let reference = row.get("REF #").unwrap_or_default().to_string();
+            reports.push(report(
+                format!("{reference}-{}", row.number),
+                reference,
+                &description,
+                price,
+                commission,
+                self.timezone.to_utc(local.date(), local.time()),
+                None,
+            )?);
+        }
+        Ok(reports)
+    }
+}
+
+fn report(
+    exec_id: String,
+    order_id: String,
+    description: &OptionDescription,
+    price: Positive,
+    commission: Decimal,
+    transact_time: DateTime<Utc>,
+    position_effect: Option<FillKind>,
+) -> Result<ExecutionReport, PositionError> {
+    Ok(ExecutionReport {
+        exec_id,
+        order_id,
+        underlying_symbol: description.underlying_symbol.clone(),
+        option_style: description.style,
+        strike_price: description.strike,
+        expiration: description.expiration,
+        action: description.action,
+        quantity: description.quantity,
+        price,
+        transact_time,
+        position_effect,
+        commission: Positive::new_decimal(commission)
+            .map_err(|e| PositionError::invalid_position(&e.to_string()))?,
+        commission_basis: FeeBasis::PerOrder,
+        currency: None,
+        security_id: Some(description.occ_symbol().to_string()),
+    })
+}
+
+fn csv_error(error: csv::Error) -> PositionError {
+    PositionError::invalid_position(&format!("invalid trade history: {error}"))
+}
+
+/// Field accessor of a CSV row, reporting errors with the row number and column.
+struct Row<'a> {
+    number: usize,
+    headers: &'a StringRecord,
+    record: &'a StringRecord,
+}
+
+impl Row<'_> {
+    fn get(&self, column: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .position(|header| header == column)
+            .and_then(|index| self.record.get(index))
+    }
+
+    fn required(&self, column: &str) -> Result<&str, PositionError> {
+        self.get(column)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| self.error(column, "missing value"))
+    }
+
+    fn error(&self, column: &str, reason: &str) -> PositionError {
+        PositionError::invalid_position(&format!("row {}, {column}: {reason}", self.number))
+    }
+
+    /// Reads an amount such as `-1,040.00` or `($2.50)`; missing amounts are zero.
+    fn amount(&self, column: &str) -> Result<Decimal, PositionError> {
+        let value = self.get(column).unwrap_or_default();
+        let negative = value.starts_with('(') && value.ends_with(')');
+        let cleaned: String = value
+            .chars()
+            .filter(|c| !matches!(c, '$' | ',' | '(' | ')'))
+            .collect();
+        if cleaned.is_empty() || cleaned == "--" {
+            return Ok(Decimal::ZERO);
+        }
+        let amount = Decimal::from_str(&cleaned)
+            .map_err(|_| self.error(column, &format!("invalid amount '{value}'")))?;
+        Ok(if negative { -amount } else { amount })
+    }
+}
+
+#[cfg(test)]
+mod tests_trade_history {
+    use super::*;
+    use crate::model::lifecycle::PositionStatus;
+    use crate::model::types::Side;
+    use chrono::TimeZone;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    const TASTYTRADE: &str = "\
+Date,Type,Sub Type,Action,Symbol,Instrument Type,Description,Value,Quantity,Average Price,Commissions,Fees,Multiplier,Root Symbol,Underlying Symbol,Expiration Date,Strike Price,Call or Put,Order #,Currency
+2026-10-20T15:00:00+0000,Trade,Buy to Close,BUY_TO_CLOSE,SPY   261218P00440000,Equity Option,Bought 1 SPY 12/18/26 Put 440.00 @ 2.20,-220.00,1,-220.00,--,-0.14,100,SPY,SPY,12/18/26,440,PUT,1002,USD
+2026-10-05T13:00:00+0000,Money Movement,Balance Adjustment,,,,Regulatory fee adjustment,-0.02,0,0,,0.00,,,,,,,,USD
+2026-10-01T14:30:00+0000,Trade,Sell to Open,SELL_TO_OPEN,SPY   261218P00440000,Equity Option,Sold 2 SPY 12/18/26 Put 440.00 @ 5.20,1040.00,2,520.00,-2.00,-0.28,100,SPY,SPY,12/18/26,440,PUT,1001,USD
+2026-10-02T14:30:00+0000,Trade,Buy,BUY,SPY,Equity,Bought 100 SPY @ 445.00,\"-44,500.00\",100,-445.00,0.00,0.00,1,,,,,,1003,USD
+";
+
+    const THINKORSWIM: &str = "\
+Account Statement for 123456789 since 10/1/26 through 10/31/26
+
+Cash Balance
+DATE,TIME,TYPE,REF #,DESCRIPTION,Misc Fees,Commissions & Fees,AMOUNT,BALANCE
+10/1/26,00:00:00,BAL,,Cash balance at the start of business day 01.10 CST,,,,\"10,000.00\"
+10/1/26,10:30:00,TRD,=\"5001\",BOT +2 AAPL 100 (Weeklys) 20 NOV 26 150 CALL @3.40,-0.04,-1.30,-681.34,\"9,318.66\"
+10/15/26,12:00:00,TRD,=\"5002\",SOLD -1 AAPL 100 (Weeklys) 20 NOV 26 150 CALL @6.50,-0.02,-0.65,649.33,\"9,967.99\"
+,,,,TOTAL,-0.06,-1.95,-32.01
+
+Account Trade History
+,Exec Time,Spread,Side,Qty,Pos Effect,Symbol,Exp,Strike,Type,Price,Net Price,Order Type
+,10/1/26 10:30:00,SINGLE,BUY,+2,TO OPEN,AAPL,20 NOV 26,150,CALL,3.40,3.40,LMT
+";
+
+    #[test]
+    fn test_option_descriptions() {
+        let tasty: OptionDescription = "Sold 2 SPY 12/18/26 Put 440.00 @ 5.20".parse().unwrap();
+        assert_eq!(tasty.action, Action::Sell);
+        assert_eq!(tasty.quantity, Positive::TWO);
+        assert_eq!(tasty.underlying_symbol, "SPY");
+        assert_eq!(
+            tasty.expiration,
+            NaiveDate::from_ymd_opt(2026, 12, 18).unwrap()
+        );
+        assert_eq!(tasty.style, OptionStyle::Put);
+        assert_eq!(tasty.strike, pos_or_panic!(440.0));
+        assert_eq!(tasty.price, Some(pos_or_panic!(5.2)));
+        assert_eq!(tasty.occ_symbol().to_string(), "SPY   261218P00440000");
+
+        let tos: OptionDescription = "SOLD -1 SPXW 100 (Weeklys) 20 NOV 26 5800 PUT @12.5 CBOE"
+            .parse()
+            .unwrap();
+        assert_eq!(tos.action, Action::Sell);
+        assert_eq!(tos.quantity, Positive::ONE);
+        assert_eq!(tos.underlying_symbol, "SPXW");
+        assert_eq!(
+            tos.expiration,
+            NaiveDate::from_ymd_opt(2026, 11, 20).unwrap()
+        );
+        assert_eq!(tos.strike, pos_or_panic!(5800.0));
+        assert_eq!(tos.price, Some(pos_or_panic!(12.5)));
+
+        for invalid in [
+            "SOLD -1 VERTICAL SPY 100 20 DEC 26 440/435 PUT @1.20",
+            "Bought 100 SPY @ 445.00",
+            "Sold 2 SPY Put 440.00 @ 5.20",
+        ] {
+            assert!(invalid.parse::<OptionDescription>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_tastytrade_import() {
+        let importer =
+            TradeHistoryImporter::new(TradeHistoryFormat::Tastytrade, pos_or_panic!(0.2))
+                .underlying_price("SPY", pos_or_panic!(445.0));
+        let reports = importer.parse(TASTYTRADE.as_bytes()).unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].position_effect, Some(FillKind::Close));
+        assert_eq!(reports[1].commission, pos_or_panic!(2.28));
+
+        let portfolio = importer.import(TASTYTRADE.as_bytes()).unwrap();
+        assert_eq!(portfolio.positions.len(), 1);
+        let position = &portfolio.positions[0];
+        assert_eq!(position.option.side, Side::Short);
+        assert_eq!(position.option.quantity, Positive::ONE);
+        assert_eq!(position.open_fee, pos_or_panic!(1.14));
+        assert_eq!(position.close_fee, pos_or_panic!(0.14));
+        assert_eq!(position.realized_pnl, dec!(1.72));
+        assert_eq!(position.epic.as_deref(), Some("SPY   261218P00440000"));
+        assert_eq!(
+            position.date,
+            Utc.with_ymd_and_hms(2026, 10, 1, 14, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_thinkorswim_import() {
+        let portfolio =
+            TradeHistoryImporter::new(TradeHistoryFormat::ThinkOrSwim, pos_or_panic!(0.25))
+                .underlying_price("AAPL", pos_or_panic!(152.0))
+                .import(THINKORSWIM.as_bytes())
+                .unwrap();
+        assert_eq!(portfolio.positions.len(), 1);
+        let position = &portfolio.positions[0];
+        assert_eq!(position.option.side, Side::Long);
+        assert_eq!(position.option.quantity, Positive::ONE);
+        assert_eq!(position.open_fee, pos_or_panic!(0.67));
+        assert_eq!(position.status, PositionStatus::Open);
+        assert_eq!(position.fills.len(), 2);
+        assert_eq!(position.realized_pnl, dec!(1.76));
+        assert_eq!(
+            position.date,
+            Utc.with_ymd_and_hms(2026, 10, 1, 14, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let closes_only = TASTYTRADE
+            .lines()
+            .filter(|line| !line.contains("SELL_TO_OPEN"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let importer =
+            TradeHistoryImporter::new(TradeHistoryFormat::Tastytrade, pos_or_panic!(0.2))
+                .underlying_price("SPY", pos_or_panic!(445.0));
+        assert!(importer.import(closes_only.as_bytes()).is_err());
+
+        let without_price =
+            TradeHistoryImporter::new(TradeHistoryFormat::Tastytrade, pos_or_panic!(0.2));
+        assert!(without_price.import(TASTYTRADE.as_bytes()).is_err());
+    }
+}
@@ -11,16 +11,105 @@ pub use option_type::{
 };
 
 use crate::constants::ZERO;
-use crate::pricing::payoff::{Payoff, PayoffInfo, standard_payoff};
+use crate::pricing::payoff::{
+    Payoff, PayoffInfo, PayoffInputs, standard_payoff, standard_payoff_f64,
+};
+use crate::utils::calendar::{HolidayCalendar, MarketCalendar};
 use chrono::{DateTime, Utc};
+use expiration_date::ExpirationDate;
+use expiration_date::error::ExpirationDateError;
 use positive::Positive;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// How an option is settled when it is exercised or assigned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum SettlementType {
+    /// The underlying is delivered at the strike price, as with equity options.
+    #[default]
+    Physical,
+    /// The intrinsic value is paid in cash, as with index options.
+    Cash,
+}
+
+/// Day-count convention used to turn the days to expiration into years.
+///
+/// Pricing models annualize the time to expiration with it, and daily greeks
+/// such as theta are reported per day of the convention.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum DayCount {
+    /// Calendar days over a 365-day year.
+    #[default]
+    Act365Fixed,
+    /// Calendar days over a 360-day year.
+    Act360,
+    /// Trading days of the market calendar over a 252-day year.
+    Act252(MarketCalendar),
+}
+
+impl DayCount {
+    /// Number of days in a year under this convention.
+    pub fn days_per_year(&self) -> Decimal {
+        match self {
+            DayCount::Act365Fixed => Decimal::from(365),
+            DayCount::Act360 => Decimal::from(360),
+            DayCount::Act252(_) => Decimal::from(252),
+        }
+    }
+
+    /// Days left until `expiration`: calendar days, or trading days for
+    /// [`DayCount::Act252`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ExpirationDateError` if the days to expiration cannot be
+    /// resolved.
+    pub fn days_to_expiration(
+        &self,
+        expiration: &ExpirationDate,
+    ) -> Result<Positive, ExpirationDateError> {
+        match self {
+            DayCount::Act365Fixed | DayCount::Act360 => expiration.get_days(),
+            DayCount::Act252(calendar) => calendar.trading_days_to_expiry(expiration),
+        }
+    }
+
+    /// Time to `expiration` in years under this convention.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ExpirationDateError` if the days to expiration cannot be
+    /// resolved.
+    pub fn year_fraction(
+        &self,
+        expiration: &ExpirationDate,
+    ) -> Result<Positive, ExpirationDateError> {
+        let days = self.days_to_expiration(expiration)?;
+        Ok(Positive::new_decimal(days.to_dec() / self.days_per_year()).unwrap_or(Positive::ZERO))
+    }
+
+    /// Time from `from` to `to` in years under this convention, zero when
+    /// `to` is earlier.
+    pub fn year_fraction_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Positive {
+        let days = match self {
+            DayCount::Act365Fixed | DayCount::Act360 => {
+                Decimal::from((to - from).num_seconds().max(0)) / Decimal::from(86_400)
+            }
+            DayCount::Act252(calendar) => calendar.trading_time_between(from, to),
+        };
+        Positive::new_decimal(days / self.days_per_year()).unwrap_or(Positive::ZERO)
+    }
+}
 
-mod datetime_format {
+/// Serde helpers writing timestamps as RFC 3339 strings, so persisted
+/// records keep a stable, human-readable date format with the original
+/// sub-second precision.
+pub(crate) mod datetime_format {
     use super::*;
     use serde::{self, Deserialize, Deserializer, Serializer};
 
-    #[allow(dead_code)]
+    /// Writes `date` as an RFC 3339 string.
     pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -29,7 +118,7 @@ mod datetime_format {
         serializer.serialize_str(&s)
     }
 
-    #[allow(dead_code)]
+    /// Reads an RFC 3339 string with any offset and converts it to UTC.
     pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
     where
         D: Deserializer<'de>,
@@ -44,40 +133,44 @@ mod datetime_format {
 impl Payoff for OptionType {
     fn payoff(&self, info: &PayoffInfo) -> f64 {
         match self {
-            OptionType::European | OptionType::American => standard_payoff(info),
-            OptionType::Bermuda { .. } => standard_payoff(info),
-            OptionType::Asian { averaging_type } => calculate_asian_payoff(averaging_type, info),
+            // Vanilla payoffs are differences of decimal prices, computed
+            // exactly so that converting the result back to `Decimal` is lossless.
+            OptionType::European | OptionType::American | OptionType::Bermuda { .. } => {
+                standard_payoff(info)
+            }
+            _ => self.payoff_f64(&PayoffInputs::from(info)),
+        }
+    }
+
+    fn payoff_f64(&self, inputs: &PayoffInputs) -> f64 {
+        match self {
+            OptionType::European | OptionType::American => standard_payoff_f64(inputs),
+            OptionType::Bermuda { .. } => standard_payoff_f64(inputs),
+            OptionType::Asian { averaging_type } => calculate_asian_payoff(averaging_type, inputs),
             OptionType::Barrier {
                 barrier_type,
                 barrier_level,
                 rebate,
-            } => calculate_barrier_payoff(barrier_type, barrier_level, rebate, info),
-            OptionType::Binary { binary_type } => calculate_binary_payoff(binary_type, info),
+            } => calculate_barrier_payoff(barrier_type, barrier_level, rebate, inputs),
+            OptionType::Binary { binary_type } => calculate_binary_payoff(binary_type, inputs),
             OptionType::Lookback { lookback_type } => match lookback_type {
-                LookbackType::FixedStrike => standard_payoff(info),
-                LookbackType::FloatingStrike => calculate_floating_strike_payoff(info),
+                LookbackType::FixedStrike => standard_payoff_f64(inputs),
+                LookbackType::FloatingStrike => calculate_floating_strike_payoff(inputs),
             },
-            OptionType::Compound { underlying_option } => underlying_option.payoff(info),
-            OptionType::Chooser { .. } => (info.spot - info.strike)
-                .max(Positive::ZERO)
-                .max(
-                    Positive::new_decimal(
-                        (info.strike.to_dec() - info.spot.to_dec()).max(Decimal::ZERO),
-                    )
-                    .unwrap_or(Positive::ZERO),
-                )
-                .to_f64(),
-            OptionType::Cliquet { .. } => standard_payoff(info),
+            OptionType::Compound { underlying_option } => underlying_option.payoff_f64(inputs),
+            OptionType::Chooser { .. } => (inputs.spot - inputs.strike).abs(),
+            OptionType::Cliquet { .. } => standard_payoff_f64(inputs),
             OptionType::Rainbow { .. }
             | OptionType::Spread { .. }
-            | OptionType::Exchange { .. } => standard_payoff(info),
-            OptionType::Quanto { exchange_rate } => standard_payoff(info) * exchange_rate,
-            OptionType::Power { exponent } => match info.style {
-                OptionStyle::Call => (info.spot.to_f64().powf(*exponent) - info.strike).max(ZERO),
-                OptionStyle::Put => (info.strike - info.spot.to_f64().powf(*exponent))
-                    .max(Positive::ZERO)
-                    .to_f64(),
-            },
+            | OptionType::Exchange { .. } => standard_payoff_f64(inputs),
+            OptionType::Quanto { exchange_rate } => standard_payoff_f64(inputs) * exchange_rate,
+            OptionType::Power { exponent } => {
+                let powered = inputs.spot.powf(*exponent);
+                match inputs.style {
+                    OptionStyle::Call => (powered - inputs.strike).max(ZERO),
+                    OptionStyle::Put => (inputs.strike - powered).max(ZERO),
+                }
+            }
         }
     }
 }
@@ -88,7 +181,7 @@ impl Payoff for OptionType {
 /// - `averaging_type`: Specifies the method of averaging the spot prices. It can either be:
 ///   - `AsianAveragingType::Arithmetic`: Uses arithmetic mean for averaging.
 ///   - `AsianAveragingType::Geometric`: Uses geometric mean for averaging.
-/// - `info`: A reference to a `PayoffInfo` object containing the details about the option such as
+/// - `inputs`: A reference to a `PayoffInputs` object containing the details about the option such as
 ///   the spot prices, strike price, and option style (Call or Put).
 ///
 /// # Returns
@@ -107,23 +200,25 @@ impl Payoff for OptionType {
 ///   - For a `Put` option: The payoff is the maximum of `(strike - average)` or ZERO.
 ///
 /// # Assumptions:
-/// - The `spot_prices` and their length (`spot_prices_len()`) are correctly passed via the `PayoffInfo` object.
-/// - Constants `ZERO` and behavior for `Positive::ZERO.into()` are defined elsewhere in the code base.
-///
-fn calculate_asian_payoff(averaging_type: &AsianAveragingType, info: &PayoffInfo) -> f64 {
-    let average = match (&info.spot_prices, info.spot_prices_len()) {
-        (Some(spot_prices), Some(len)) if len > 0 => match averaging_type {
-            AsianAveragingType::Arithmetic => spot_prices.iter().sum::<f64>() / len as f64,
+/// - The `spot_prices` and their length are correctly passed via the `PayoffInputs` object.
+/// - The constant `ZERO` is defined elsewhere in the code base.
+///
+fn calculate_asian_payoff(averaging_type: &AsianAveragingType, inputs: &PayoffInputs) -> f64 {
+    let average = match inputs.spot_prices {
+        Some(spot_prices) if !spot_prices.is_empty() => match averaging_type {
+            AsianAveragingType::Arithmetic => {
+                spot_prices.iter().sum::<f64>() / spot_prices.len() as f64
+            }
             AsianAveragingType::Geometric => {
                 let product = spot_prices.iter().fold(1.0, |acc, &x| acc * x);
-                product.powf(1.0 / len as f64)
+                product.powf(1.0 / spot_prices.len() as f64)
             }
         },
         _ => return ZERO,
     };
-    match info.style {
-        OptionStyle::Call => (average - info.strike).max(ZERO),
-        OptionStyle::Put => (info.strike - average).max(Positive::ZERO).into(),
+    match inputs.style {
+        OptionStyle::Call => (average - inputs.strike).max(ZERO),
+        OptionStyle::Put => (inputs.strike - average).max(ZERO),
     }
 }
 
@@ -137,7 +232,7 @@ fn calculate_asian_payoff(averaging_type: &AsianAveragingType, info: &PayoffInfo
 ///     - `BarrierType::UpAndOut`: Payoff is only valid if the spot price does not rise above the barrier level.
 ///     - `BarrierType::DownAndOut`: Payoff is only valid if the spot price does not fall below the barrier level.
 /// * `barrier_level` - A reference to the barrier level price, which serves as the activation or deactivation threshold for the payoff.
-/// * `info` - Contains information required to calculate the payoff, including the spot price and additional data for standard payoff calculations.
+/// * `inputs` - Contains information required to calculate the payoff, including the spot price and additional data for standard payoff calculations.
 ///
 /// # Returns
 ///
@@ -152,28 +247,28 @@ fn calculate_asian_payoff(averaging_type: &AsianAveragingType, info: &PayoffInfo
 /// # Assumptions
 ///
 /// * It is assumed that the `standard_payoff` function is defined elsewhere and provides the base payoff calculation.
-/// * The `PayoffInfo` struct and the `BarrierType` enum are pre-defined and accessible in the same context.
+/// * The `PayoffInputs` struct and the `BarrierType` enum are pre-defined and accessible in the same context.
 ///
 /// # Errors
 ///
-/// This function does not explicitly handle errors. Ensure that the inputs are valid for the `barrier_type`, `barrier_level`, and `info` parameters.
+/// This function does not explicitly handle errors. Ensure that the inputs are valid for the `barrier_type`, `barrier_level`, and `inputs` parameters.
 fn calculate_barrier_payoff(
     barrier_type: &BarrierType,
     barrier_level: &f64,
     rebate: &Option<f64>,
-    info: &PayoffInfo,
+    inputs: &PayoffInputs,
 ) -> f64 {
     let barrier_condition = match barrier_type {
         BarrierType::UpAndIn | BarrierType::UpAndOut => {
             // Use spot_max if available, otherwise just current spot
-            info.spot_max.unwrap_or(info.spot.to_f64()) >= *barrier_level
+            inputs.spot_max.unwrap_or(inputs.spot) >= *barrier_level
         }
         BarrierType::DownAndIn | BarrierType::DownAndOut => {
             // Use spot_min if available, otherwise just current spot
-            info.spot_min.unwrap_or(info.spot.to_f64()) <= *barrier_level
+            inputs.spot_min.unwrap_or(inputs.spot) <= *barrier_level
         }
     };
-    let std_payoff = standard_payoff(info);
+    let std_payoff = standard_payoff_f64(inputs);
     match barrier_type {
         BarrierType::UpAndIn | BarrierType::DownAndIn => {
             if barrier_condition {
@@ -201,7 +296,7 @@ fn calculate_barrier_payoff(
 ///   - `AssetOrNothing`: Pays the current spot price of the asset if the option expires in-the-money; otherwise, pays 0.0.
 ///   - `Gap`: Pays the absolute difference between the spot price and the strike price (if in-the-money); otherwise, pays 0.0.
 ///
-/// - `info`: A reference to a `PayoffInfo` struct containing the following fields:
+/// - `inputs`: A reference to a `PayoffInputs` struct containing the following fields:
 ///   - `spot`: The current price of the underlying asset.
 ///   - `strike`: The strike price of the option.
 ///   - `style`: An enum (`OptionStyle`) representing whether the option is a call (long) or put (short):
@@ -220,18 +315,17 @@ fn calculate_barrier_payoff(
 /// 2. Calculate the payoff based on the type of binary option:
 ///
 ///    - **CashOrNothing**: Returns `1.0` if the option is in-the-money; otherwise, returns `0.0`.
-///    - **AssetOrNothing**: Returns the `spot` price (converted into `f64`) if the option is in-the-money; otherwise, returns `0.0`.
-///    - **Gap**: Returns the absolute difference between the `spot` and `strike` prices (converted into `f64`) if the option is in-the-money; otherwise, returns `0.0`.
+///    - **AssetOrNothing**: Returns the `spot` price if the option is in-the-money; otherwise, returns `0.0`.
+///    - **Gap**: Returns the absolute difference between the `spot` and `strike` prices if the option is in-the-money; otherwise, returns `0.0`.
 ///
 /// # Notes
 ///
-/// - The `to_f64` method is assumed to be implemented for the type of `spot` and `strike` to ensure compatibility with the calculations.
-/// - The definition and behavior of `BinaryType`, `PayoffInfo`, and `OptionStyle` are external to this function.
+/// - The definition and behavior of `BinaryType`, `PayoffInputs`, and `OptionStyle` are external to this function.
 ///
-fn calculate_binary_payoff(binary_type: &BinaryType, info: &PayoffInfo) -> f64 {
-    let is_in_the_money = match info.style {
-        OptionStyle::Call => info.spot > info.strike,
-        OptionStyle::Put => info.spot < info.strike,
+fn calculate_binary_payoff(binary_type: &BinaryType, inputs: &PayoffInputs) -> f64 {
+    let is_in_the_money = match inputs.style {
+        OptionStyle::Call => inputs.spot > inputs.strike,
+        OptionStyle::Put => inputs.spot < inputs.strike,
     };
     match binary_type {
         BinaryType::CashOrNothing => {
@@ -243,7 +337,7 @@ fn calculate_binary_payoff(binary_type: &BinaryType, info: &PayoffInfo) -> f64 {
         }
         BinaryType::AssetOrNothing => {
             if is_in_the_money {
-                info.spot.to_f64()
+                inputs.spot
             } else {
                 0.0
             }
@@ -252,7 +346,7 @@ fn calculate_binary_payoff(binary_type: &BinaryType, info: &PayoffInfo) -> f64 {
             if is_in_the_money {
                 // For Gap options, the payoff is proportional to how far above/below the strike price
                 // the underlying asset is at expiration
-                (info.spot.to_f64() - info.strike.to_f64()).abs()
+                (inputs.spot - inputs.strike).abs()
             } else {
                 0.0
             }
@@ -263,7 +357,7 @@ fn calculate_binary_payoff(binary_type: &BinaryType, info: &PayoffInfo) -> f64 {
 /// Calculates the payoff for a floating strike option based on the provided option information.
 ///
 /// # Parameters
-/// - `info`: A reference to a `PayoffInfo` struct that contains all necessary information for
+/// - `inputs`: A reference to a `PayoffInputs` struct that contains all necessary information for
 ///   calculating the payoff. The struct includes details such as the option style (call or put),
 ///   the spot value, and the minimum or maximum spot observed (as applicable).
 ///
@@ -272,31 +366,31 @@ fn calculate_binary_payoff(binary_type: &BinaryType, info: &PayoffInfo) -> f64 {
 ///
 /// # Logic
 /// 1. Determines the "extremum" based on the option style:
-///    - For a call option (`OptionStyle::Call`), the extremum is the minimum spot value (`info.spot_min`).
-///    - For a put option (`OptionStyle::Put`), the extremum is the maximum spot value (`info.spot_max`).
-/// 2. Calculates the payoff based on the difference between the spot price (`info.spot.to_f64()`)
+///    - For a call option (`OptionStyle::Call`), the extremum is the minimum spot value (`inputs.spot_min`).
+///    - For a put option (`OptionStyle::Put`), the extremum is the maximum spot value (`inputs.spot_max`).
+/// 2. Calculates the payoff based on the difference between the spot price (`inputs.spot`)
 ///    and the extremum:
 ///    - For a call option, the payoff is `spot - extremum` (or `spot` if `extremum` is unavailable).
 ///    - For a put option, the payoff is `extremum - spot` (or `-spot` if `extremum` is unavailable).
 ///
 /// # Assumptions
-/// - `info.to_f64()` correctly converts the spot value to a floating-point number (`f64`).
-/// - `info.spot_min` and `info.spot_max` are `Option<f64>` values that might be `None`, in which case
+/// - `inputs.spot` is the final spot value as a floating-point number (`f64`).
+/// - `inputs.spot_min` and `inputs.spot_max` are `Option<f64>` values that might be `None`, in which case
 ///   the fallback value (`ZERO`) is used in the payoff calculation.
 ///
 /// # Notes
-/// - Ensure that the `info.spot.to_f64()` implementation and the extremum values (`spot_min`, `spot_max`)
+/// - Ensure that the `inputs.spot` value and the extremum values (`spot_min`, `spot_max`)
 ///   are compatible with your application's floating-point requirements.
 /// - The function handles missing extremum values gracefully using a default value of `ZERO`.
 ///
-fn calculate_floating_strike_payoff(info: &PayoffInfo) -> f64 {
-    let extremum = match info.style {
-        OptionStyle::Call => info.spot_min,
-        OptionStyle::Put => info.spot_max,
+fn calculate_floating_strike_payoff(inputs: &PayoffInputs) -> f64 {
+    let extremum = match inputs.style {
+        OptionStyle::Call => inputs.spot_min,
+        OptionStyle::Put => inputs.spot_max,
     };
-    match info.style {
-        OptionStyle::Call => info.spot.to_f64() - extremum.unwrap_or(ZERO),
-        OptionStyle::Put => extremum.unwrap_or(ZERO) - info.spot.to_f64(),
+    match inputs.style {
+        OptionStyle::Call => inputs.spot - extremum.unwrap_or(ZERO),
+        OptionStyle::Put => extremum.unwrap_or(ZERO) - inputs.spot,
     }
 }
 
@@ -436,7 +530,10 @@ mod tests_calculate_floating_strike_payoff {
             spot_min: Some(80.0),
             spot_max: None,
         };
-        assert_eq!(calculate_floating_strike_payoff(&info), 20.0);
+        assert_eq!(
+            calculate_floating_strike_payoff(&PayoffInputs::from(&info)),
+            20.0
+        );
     }
 
     #[test]
@@ -450,7 +547,10 @@ mod tests_calculate_floating_strike_payoff {
             spot_min: None,
             spot_max: None,
         };
-        assert_eq!(calculate_floating_strike_payoff(&info), 100.0);
+        assert_eq!(
+            calculate_floating_strike_payoff(&PayoffInputs::from(&info)),
+            100.0
+        );
     }
 
     #[test]
@@ -464,7 +564,10 @@ mod tests_calculate_floating_strike_payoff {
             spot_min: None,
             spot_max: Some(120.0),
         };
-        assert_eq!(calculate_floating_strike_payoff(&info), 20.0);
+        assert_eq!(
+            calculate_floating_strike_payoff(&PayoffInputs::from(&info)),
+            20.0
+        );
     }
 
     #[test]
@@ -478,7 +581,10 @@ mod tests_calculate_floating_strike_payoff {
             spot_min: None,
             spot_max: None,
         };
-        assert_eq!(calculate_floating_strike_payoff(&info), -100.0);
+        assert_eq!(
+            calculate_floating_strike_payoff(&PayoffInputs::from(&info)),
+            -100.0
+        );
     }
 
     #[test]
@@ -492,7 +598,10 @@ mod tests_calculate_floating_strike_payoff {
             spot_min: Some(100.0),
             spot_max: None,
         };
-        assert_eq!(calculate_floating_strike_payoff(&info), 0.0);
+        assert_eq!(
+            calculate_floating_strike_payoff(&PayoffInputs::from(&info)),
+            0.0
+        );
     }
 
     #[test]
@@ -506,7 +615,10 @@ mod tests_calculate_floating_strike_payoff {
             spot_min: None,
             spot_max: Some(100.0),
         };
-        assert_eq!(calculate_floating_strike_payoff(&info), 0.0);
+        assert_eq!(
+            calculate_floating_strike_payoff(&PayoffInputs::from(&info)),
+            0.0
+        );
     }
 }
 
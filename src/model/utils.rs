@@ -4,8 +4,8 @@
    Date: 21/8/24
 ******************************************************************************/
 use crate::error::ChainError;
-use crate::model::Position;
-use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
+use crate::model::{Currency, Position, PositionStatus};
 use crate::{ExpirationDate, Options};
 use chrono::{NaiveDateTime, TimeZone, Utc};
 use positive::{Positive, pos_or_panic};
@@ -155,6 +155,8 @@ pub fn create_sample_position(
             option_style,
             dividend_yield: pos_or_panic!(0.01),
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         },
         premium: pos_or_panic!(5.0),
         date: Utc::now(),
@@ -162,6 +164,10 @@ pub fn create_sample_position(
         close_fee: pos_or_panic!(0.5),
         epic: Some("Epic123".to_string()),
         extra_fields: None,
+        status: PositionStatus::Open,
+        fills: Vec::new(),
+        realized_pnl: Decimal::ZERO,
+        currency: Currency::default(),
     }
 }
 
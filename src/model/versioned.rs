@@ -0,0 +1,313 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Versioned JSON
+//!
+//! Persistence format for [`Options`], [`Position`] and [`Strategy`] that
+//! survives crate upgrades in both directions:
+//!
+//! * every document carries a `schema_version` field, so older documents can
+//!   be migrated when the layout changes,
+//! * fields this version of the crate does not know about, at any depth of
+//!   the document, are kept in [`Versioned::extra_fields`] and written back on
+//!   serialization, so a document produced by a newer crate loses nothing
+//!   when it is read and saved again by an older one.
+//!
+//! Documents without `schema_version`, written before versioning existed, are
+//! read as version 0.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use optionstratlib::model::{Options, VersionedJson};
+//! use optionstratlib::{ExpirationDate, OptionStyle, OptionType, Side};
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal::Decimal;
+//!
+//! let option = Options::new(
+//!     OptionType::European,
+//!     Side::Long,
+//!     "AAPL".to_string(),
+//!     Positive::HUNDRED,
+//!     ExpirationDate::Days(pos_or_panic!(30.0)),
+//!     pos_or_panic!(0.2),
+//!     Positive::ONE,
+//!     Positive::HUNDRED,
+//!     Decimal::ZERO,
+//!     OptionStyle::Call,
+//!     Positive::ZERO,
+//!     None,
+//! );
+//! let json = option.to_versioned_json().unwrap();
+//! let restored = Options::from_versioned_json(&json).unwrap();
+//! assert_eq!(restored.data, option);
+//! ```
+
+use crate::model::option::Options;
+use crate::model::position::Position;
+use crate::strategies::base::Strategy;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// Version of the JSON layout written by this version of the crate.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Name of the field holding the schema version of a document.
+pub const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// A value together with the schema version it was read with and the fields
+/// of its document this version of the crate does not know about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Versioned<T> {
+    /// Schema version of the document the value was read from, or
+    /// [`SCHEMA_VERSION`] for values created in memory.
+    pub schema_version: u32,
+    /// The deserialized value.
+    pub data: T,
+    /// Unknown fields, laid out as in the original document: an object with
+    /// the unknown keys of each level, and of the nested objects and arrays
+    /// that contain some.
+    pub extra_fields: Map<String, Value>,
+}
+
+impl<T> Versioned<T> {
+    /// Wraps a value created in memory, with the current schema version.
+    pub fn new(data: T) -> Self {
+        Versioned {
+            schema_version: SCHEMA_VERSION,
+            data,
+            extra_fields: Map::new(),
+        }
+    }
+
+    /// Returns `true` if the document was written by a newer version of the crate.
+    pub fn is_from_newer_version(&self) -> bool {
+        self.schema_version > SCHEMA_VERSION
+    }
+}
+
+impl<T: Serialize> Serialize for Versioned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut document = serde_json::to_value(&self.data).map_err(S::Error::custom)?;
+        let Value::Object(fields) = &mut document else {
+            return Err(S::Error::custom(
+                "versioned values must serialize to objects",
+            ));
+        };
+        merge_unknown(fields, &self.extra_fields);
+        fields.insert(
+            SCHEMA_VERSION_FIELD.to_string(),
+            Value::from(self.schema_version.max(SCHEMA_VERSION)),
+        );
+        document.serialize(serializer)
+    }
+}
+
+impl<'de, T: Serialize + DeserializeOwned> Deserialize<'de> for Versioned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut document = Value::deserialize(deserializer)?;
+        let Value::Object(fields) = &mut document else {
+            return Err(D::Error::custom("versioned documents must be objects"));
+        };
+        let schema_version = match fields.remove(SCHEMA_VERSION_FIELD) {
+            None => 0,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| D::Error::custom("schema_version must be an unsigned integer"))?,
+        };
+
+        let data: T = serde_json::from_value(document.clone()).map_err(D::Error::custom)?;
+        let known = serde_json::to_value(&data).map_err(D::Error::custom)?;
+        let extra_fields = match unknown_fields(&document, &known) {
+            Some(Value::Object(extra_fields)) => extra_fields,
+            _ => Map::new(),
+        };
+
+        Ok(Versioned {
+            schema_version,
+            data,
+            extra_fields,
+        })
+    }
+}
+
+/// Parts of `document` that are absent from `known`, or `None` if there are none.
+fn unknown_fields(document: &Value, known: &Value) -> Option<Value> {
+    match (document, known) {
+        (Value::Object(document), Value::Object(known)) => {
+            let mut unknown = Map::new();
+            for (key, value) in document {
+                match known.get(key) {
+                    None => {
+                        unknown.insert(key.clone(), value.clone());
+                    }
+                    Some(known_value) => {
+                        if let Some(nested) = unknown_fields(value, known_value) {
+                            unknown.insert(key.clone(), nested);
+                        }
+                    }
+                }
+            }
+            (!unknown.is_empty()).then_some(Value::Object(unknown))
+        }
+        (Value::Array(document), Value::Array(known)) => {
+            let unknown: Vec<Value> = document
+                .iter()
+                .zip(known)
+                .map(|(value, known_value)| {
+                    unknown_fields(value, known_value).unwrap_or(Value::Null)
+                })
+                .collect();
+            unknown
+                .iter()
+                .any(|value| !value.is_null())
+                .then_some(Value::Array(unknown))
+        }
+        _ => None,
+    }
+}
+
+/// Writes the unknown fields back into a serialized document.
+fn merge_unknown(fields: &mut Map<String, Value>, unknown: &Map<String, Value>) {
+    for (key, value) in unknown {
+        match (fields.get_mut(key), value) {
+            (None, _) => {
+                fields.insert(key.clone(), value.clone());
+            }
+            (Some(Value::Object(nested)), Value::Object(unknown)) => merge_unknown(nested, unknown),
+            (Some(Value::Array(items)), Value::Array(unknown)) => {
+                for (item, unknown) in items.iter_mut().zip(unknown) {
+                    if let (Value::Object(item), Value::Object(unknown)) = (item, unknown) {
+                        merge_unknown(item, unknown);
+                    }
+                }
+            }
+            // Known fields always take the value of the in-memory data.
+            _ => {}
+        }
+    }
+}
+
+/// Reading and writing a type as a versioned JSON document.
+pub trait VersionedJson: Serialize + DeserializeOwned + Clone {
+    /// Serializes the value with the current schema version.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `serde_json::Error` if the value cannot be serialized.
+    fn to_versioned_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&Versioned::new(self.clone()))
+    }
+
+    /// Reads a versioned document, keeping the fields it does not know about.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `serde_json::Error` if the document is not valid JSON or lacks
+    /// a field required by this version of the crate.
+    fn from_versioned_json(json: &str) -> Result<Versioned<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl VersionedJson for Options {}
+
+impl VersionedJson for Position {}
+
+impl VersionedJson for Strategy {}
+
+#[cfg(test)]
+mod tests_versioned {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::strategies::base::StrategyType;
+    use chrono::Utc;
+    use positive::{Positive, pos_or_panic};
+    use rust_decimal::Decimal;
+
+    fn position() -> Position {
+        let option = Options::new(
+            OptionType::European,
+            Side::Short,
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            OptionStyle::Put,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            pos_or_panic!(2.5),
+            Utc::now(),
+            Positive::ONE,
+            Positive::ONE,
+            Some("EPIC".to_string()),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_strategy_roundtrip() {
+        let mut strategy = Strategy::new(
+            "Short put".to_string(),
+            StrategyType::ShortPut,
+            "A single short put".to_string(),
+        );
+        strategy.legs.push(position());
+        strategy.break_even_points.push(pos_or_panic!(97.5));
+
+        let json = strategy.to_versioned_json().unwrap();
+        let document: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(document[SCHEMA_VERSION_FIELD], Value::from(SCHEMA_VERSION));
+
+        let restored = Strategy::from_versioned_json(&json).unwrap();
+        assert_eq!(restored.schema_version, SCHEMA_VERSION);
+        assert!(restored.extra_fields.is_empty());
+        assert!(restored.data == strategy);
+    }
+
+    #[test]
+    fn test_unknown_fields_survive_roundtrip() {
+        let mut document = serde_json::to_value(Versioned::new(position())).unwrap();
+        document[SCHEMA_VERSION_FIELD] = Value::from(SCHEMA_VERSION + 1);
+        document["tags"] = Value::from(vec!["income"]);
+        document["option"]["contract_multiplier"] = Value::from(100);
+
+        let restored = Position::from_versioned_json(&document.to_string()).unwrap();
+        assert!(restored.is_from_newer_version());
+        assert_eq!(restored.data.premium, pos_or_panic!(2.5));
+        assert_eq!(restored.extra_fields["tags"], Value::from(vec!["income"]));
+        assert_eq!(
+            restored.extra_fields["option"]["contract_multiplier"],
+            Value::from(100)
+        );
+
+        let written = serde_json::to_value(&restored).unwrap();
+        assert_eq!(written["tags"], document["tags"]);
+        assert_eq!(written["option"]["contract_multiplier"], Value::from(100));
+        assert_eq!(
+            written[SCHEMA_VERSION_FIELD],
+            Value::from(SCHEMA_VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn test_unversioned_document_is_version_zero() {
+        let json = serde_json::to_string(&position().option).unwrap();
+        let restored = Options::from_versioned_json(&json).unwrap();
+        assert_eq!(restored.schema_version, 0);
+        assert_eq!(restored.data, position().option);
+    }
+}
@@ -0,0 +1,290 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+use crate::error::OhlcvError;
+use crate::utils::{OhlcvCandle, TimeFrame};
+use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+const SECONDS_PER_MINUTE: i64 = 60;
+const SECONDS_PER_HOUR: i64 = 3_600;
+const SECONDS_PER_DAY: i64 = 86_400;
+/// Seconds of a regular trading day, 6.5 hours.
+const TRADING_SECONDS_PER_DAY: i64 = 23_400;
+
+/// Length of the bars of a price history.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema,
+)]
+pub struct BarInterval {
+    seconds: i64,
+}
+
+impl BarInterval {
+    /// One minute bars.
+    pub const MINUTE: BarInterval = BarInterval {
+        seconds: SECONDS_PER_MINUTE,
+    };
+    /// One hour bars.
+    pub const HOUR: BarInterval = BarInterval {
+        seconds: SECONDS_PER_HOUR,
+    };
+    /// Daily bars.
+    pub const DAY: BarInterval = BarInterval {
+        seconds: SECONDS_PER_DAY,
+    };
+
+    /// Bars of `minutes` minutes, at least one.
+    pub fn minutes(minutes: u32) -> Self {
+        BarInterval {
+            seconds: i64::from(minutes.max(1)) * SECONDS_PER_MINUTE,
+        }
+    }
+
+    /// Bars of `hours` hours, at least one.
+    pub fn hours(hours: u32) -> Self {
+        BarInterval {
+            seconds: i64::from(hours.max(1)) * SECONDS_PER_HOUR,
+        }
+    }
+
+    /// Bars of `days` days, at least one.
+    pub fn days(days: u32) -> Self {
+        BarInterval {
+            seconds: i64::from(days.max(1)) * SECONDS_PER_DAY,
+        }
+    }
+
+    /// Length of a bar in seconds.
+    pub fn seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    /// Length of a bar.
+    pub fn duration(&self) -> Duration {
+        Duration::seconds(self.seconds)
+    }
+
+    /// Start of the bar containing `timestamp`.
+    pub fn floor(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let seconds = timestamp.timestamp();
+        let start = seconds - seconds.rem_euclid(self.seconds);
+        Utc.timestamp_opt(start, 0).single().unwrap_or(timestamp)
+    }
+
+    /// Whether bars of `self` can be aggregated into bars of `other`.
+    pub fn divides(&self, other: &BarInterval) -> bool {
+        other.seconds % self.seconds == 0
+    }
+
+    /// Time frame used to annualize the volatility of returns between bars,
+    /// assuming 252 trading days of 6.5 hours.
+    pub fn time_frame(&self) -> TimeFrame {
+        match self.seconds {
+            SECONDS_PER_MINUTE => TimeFrame::Minute,
+            SECONDS_PER_HOUR => TimeFrame::Hour,
+            SECONDS_PER_DAY => TimeFrame::Day,
+            seconds if seconds < SECONDS_PER_DAY => {
+                let per_day = Decimal::from(TRADING_SECONDS_PER_DAY) / Decimal::from(seconds);
+                TimeFrame::Custom(TimeFrame::Day.periods_per_year() * per_day.max(Decimal::ONE))
+            }
+            seconds => TimeFrame::Custom(
+                TimeFrame::Day.periods_per_year() * Decimal::from(SECONDS_PER_DAY)
+                    / Decimal::from(seconds),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for BarInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.seconds {
+            s if s % SECONDS_PER_DAY == 0 => write!(f, "{}d", s / SECONDS_PER_DAY),
+            s if s % SECONDS_PER_HOUR == 0 => write!(f, "{}h", s / SECONDS_PER_HOUR),
+            s if s % SECONDS_PER_MINUTE == 0 => write!(f, "{}m", s / SECONDS_PER_MINUTE),
+            s => write!(f, "{s}s"),
+        }
+    }
+}
+
+/// Open, high, low, close and volume of the underlying over one bar.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct OhlcBar {
+    /// Start of the bar.
+    pub timestamp: DateTime<Utc>,
+    /// First price of the bar.
+    pub open: Positive,
+    /// Highest price of the bar.
+    pub high: Positive,
+    /// Lowest price of the bar.
+    pub low: Positive,
+    /// Last price of the bar.
+    pub close: Positive,
+    /// Volume traded during the bar.
+    pub volume: Positive,
+}
+
+impl OhlcBar {
+    /// Creates a bar.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OhlcvError::InvalidParameter` if the open or the close is
+    /// outside the range between the low and the high.
+    pub fn new(
+        timestamp: DateTime<Utc>,
+        open: Positive,
+        high: Positive,
+        low: Positive,
+        close: Positive,
+        volume: Positive,
+    ) -> Result<Self, OhlcvError> {
+        if low > high || open < low || open > high || close < low || close > high {
+            return Err(OhlcvError::InvalidParameter {
+                reason: format!(
+                    "Bar at {timestamp} has open {open} and close {close} outside low {low} and high {high}"
+                ),
+            });
+        }
+        Ok(OhlcBar {
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        })
+    }
+
+    /// Bar with a single price, such as a trade or a filled gap.
+    pub fn flat(timestamp: DateTime<Utc>, price: Positive, volume: Positive) -> Self {
+        OhlcBar {
+            timestamp,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume,
+        }
+    }
+
+    /// Difference between the high and the low.
+    pub fn range(&self) -> Positive {
+        self.high.saturating_sub(&self.low)
+    }
+
+    /// Average of the high, low and close.
+    pub fn typical_price(&self) -> Positive {
+        (self.high + self.low + self.close) / Decimal::from(3)
+    }
+
+    /// Extends the bar with a later one: the high and low widen, the close
+    /// and volume are taken from and added to `later`.
+    pub fn merge(&mut self, later: &OhlcBar) {
+        self.high = self.high.max(later.high);
+        self.low = self.low.min(later.low);
+        self.close = later.close;
+        self.volume += later.volume;
+    }
+}
+
+impl TryFrom<&OhlcvCandle> for OhlcBar {
+    type Error = OhlcvError;
+
+    fn try_from(candle: &OhlcvCandle) -> Result<Self, Self::Error> {
+        let time = NaiveTime::parse_from_str(&candle.time, "%H:%M:%S").map_err(|e| {
+            OhlcvError::DateParseError {
+                reason: format!("Invalid candle time {}: {e}", candle.time),
+            }
+        })?;
+        let price = |value: Decimal| {
+            Positive::new_decimal(value).map_err(|e| OhlcvError::DecimalParseError {
+                reason: format!("Invalid candle price {value}: {e}"),
+            })
+        };
+        OhlcBar::new(
+            Utc.from_utc_datetime(&candle.date.and_time(time)),
+            price(candle.open)?,
+            price(candle.high)?,
+            price(candle.low)?,
+            price(candle.close)?,
+            Positive::new_decimal(Decimal::from(candle.volume)).unwrap_or(Positive::ZERO),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests_bar {
+    use super::*;
+    use chrono::NaiveDate;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_interval_buckets() {
+        let timestamp = Utc.with_ymd_and_hms(2030, 1, 2, 14, 37, 12).unwrap();
+        assert_eq!(
+            BarInterval::MINUTE.floor(timestamp),
+            Utc.with_ymd_and_hms(2030, 1, 2, 14, 37, 0).unwrap()
+        );
+        assert_eq!(
+            BarInterval::minutes(15).floor(timestamp),
+            Utc.with_ymd_and_hms(2030, 1, 2, 14, 30, 0).unwrap()
+        );
+        assert_eq!(
+            BarInterval::DAY.floor(timestamp),
+            Utc.with_ymd_and_hms(2030, 1, 2, 0, 0, 0).unwrap()
+        );
+        assert!(BarInterval::MINUTE.divides(&BarInterval::HOUR));
+        assert!(!BarInterval::HOUR.divides(&BarInterval::minutes(90)));
+        assert_eq!(BarInterval::hours(4).to_string(), "4h");
+        assert_eq!(
+            BarInterval::minutes(30).time_frame().periods_per_year(),
+            pos_or_panic!(252.0 * 13.0)
+        );
+    }
+
+    #[test]
+    fn test_bar_from_candle() {
+        let candle = OhlcvCandle {
+            date: NaiveDate::from_ymd_opt(2030, 1, 2).unwrap(),
+            time: "14:30:00".to_string(),
+            open: dec!(100),
+            high: dec!(101),
+            low: dec!(99),
+            close: dec!(100.5),
+            volume: 1_000,
+        };
+        let bar = OhlcBar::try_from(&candle).unwrap();
+        assert_eq!(
+            bar.timestamp,
+            Utc.with_ymd_and_hms(2030, 1, 2, 14, 30, 0).unwrap()
+        );
+        assert_eq!(bar.range(), Positive::TWO);
+        assert_eq!(bar.typical_price().to_dec(), dec!(300.5) / dec!(3));
+
+        let mut merged = bar;
+        merged.merge(&OhlcBar::flat(
+            bar.timestamp,
+            pos_or_panic!(98.0),
+            Positive::TEN,
+        ));
+        assert_eq!(merged.low, pos_or_panic!(98.0));
+        assert_eq!(merged.close, pos_or_panic!(98.0));
+        assert_eq!(merged.open, bar.open);
+        assert_eq!(merged.volume, pos_or_panic!(1_010.0));
+
+        let inverted = OhlcvCandle {
+            high: dec!(98),
+            ..candle
+        };
+        assert!(OhlcBar::try_from(&inverted).is_err());
+    }
+}
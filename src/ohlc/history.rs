@@ -0,0 +1,346 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+use crate::error::{OhlcvError, VolatilityError};
+use crate::ohlc::bar::{BarInterval, OhlcBar};
+use crate::utils::OhlcvCandle;
+use crate::volatility::{annualized_volatility, historical_volatility};
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Bars of the price of one symbol, in time order.
+///
+/// Every bar is keyed by the start of its interval. Appending data for an
+/// interval already stored extends its bar, so ticks or shorter bars can be
+/// appended directly; data older than the last bar is rejected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceHistory {
+    /// Symbol of the underlying.
+    pub symbol: String,
+    /// Length of the bars.
+    pub interval: BarInterval,
+    bars: BTreeMap<DateTime<Utc>, OhlcBar>,
+}
+
+impl PriceHistory {
+    /// Creates an empty history of `symbol` with bars of `interval`.
+    pub fn new(symbol: &str, interval: BarInterval) -> Self {
+        PriceHistory {
+            symbol: symbol.to_string(),
+            interval,
+            bars: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a history from candles read from a file or a provider, in any
+    /// order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OhlcvError` if a candle is not a valid bar.
+    pub fn from_candles(
+        symbol: &str,
+        interval: BarInterval,
+        candles: &[OhlcvCandle],
+    ) -> Result<Self, OhlcvError> {
+        let mut bars = candles
+            .iter()
+            .map(OhlcBar::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        bars.sort_by_key(|bar| bar.timestamp);
+        let mut history = Self::new(symbol, interval);
+        for bar in &bars {
+            history.append(bar)?;
+        }
+        Ok(history)
+    }
+
+    /// Appends `bar` to the bar of its interval, which is created if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OhlcvError::InvalidParameter` if `bar` starts before the
+    /// interval of the last bar.
+    pub fn append(&mut self, bar: &OhlcBar) -> Result<(), OhlcvError> {
+        let start = self.interval.floor(bar.timestamp);
+        if let Some((last, _)) = self.bars.last_key_value()
+            && start < *last
+        {
+            return Err(OhlcvError::InvalidParameter {
+                reason: format!(
+                    "Bar at {} of {} is older than the last bar at {last}",
+                    bar.timestamp, self.symbol
+                ),
+            });
+        }
+        self.bars
+            .entry(start)
+            .and_modify(|stored| stored.merge(bar))
+            .or_insert(OhlcBar {
+                timestamp: start,
+                ..*bar
+            });
+        Ok(())
+    }
+
+    /// Appends a trade of `volume` at `price`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OhlcvError::InvalidParameter` if the trade is older than the
+    /// interval of the last bar.
+    pub fn append_tick(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        price: Positive,
+        volume: Positive,
+    ) -> Result<(), OhlcvError> {
+        self.append(&OhlcBar::flat(timestamp, price, volume))
+    }
+
+    /// Number of bars.
+    pub fn len(&self) -> usize {
+        self.bars.len()
+    }
+
+    /// Whether the history has no bars.
+    pub fn is_empty(&self) -> bool {
+        self.bars.is_empty()
+    }
+
+    /// Bars in time order.
+    pub fn bars(&self) -> impl Iterator<Item = &OhlcBar> {
+        self.bars.values()
+    }
+
+    /// First bar.
+    pub fn first(&self) -> Option<&OhlcBar> {
+        self.bars.values().next()
+    }
+
+    /// Last bar.
+    pub fn last(&self) -> Option<&OhlcBar> {
+        self.bars.values().next_back()
+    }
+
+    /// Bar whose interval contains `timestamp`.
+    pub fn get(&self, timestamp: DateTime<Utc>) -> Option<&OhlcBar> {
+        self.bars.get(&self.interval.floor(timestamp))
+    }
+
+    /// Bars starting from `from` and before `to`.
+    pub fn range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> impl Iterator<Item = &OhlcBar> {
+        let to = to.max(from);
+        self.bars.range(from..to).map(|(_, bar)| bar)
+    }
+
+    /// Close of the last bar completed by `timestamp`, the price known at
+    /// that time without looking ahead.
+    pub fn close_at(&self, timestamp: DateTime<Utc>) -> Option<Positive> {
+        self.bars
+            .range(..=timestamp - self.interval.duration())
+            .next_back()
+            .map(|(_, bar)| bar.close)
+    }
+
+    /// Aggregates the bars into bars of a longer `interval`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OhlcvError::InvalidParameter` if `interval` is not a multiple
+    /// of the interval of the history.
+    pub fn resample(&self, interval: BarInterval) -> Result<PriceHistory, OhlcvError> {
+        if !self.interval.divides(&interval) {
+            return Err(OhlcvError::InvalidParameter {
+                reason: format!("Cannot resample {} bars to {interval}", self.interval),
+            });
+        }
+        let mut resampled = PriceHistory::new(&self.symbol, interval);
+        for bar in self.bars.values() {
+            resampled.append(bar)?;
+        }
+        Ok(resampled)
+    }
+
+    /// Missing intervals between consecutive bars, as the start of the first
+    /// missing bar and the start of the next stored one. Market closures
+    /// show as gaps too.
+    pub fn gaps(&self) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let step = self.interval.duration();
+        self.bars
+            .keys()
+            .zip(self.bars.keys().skip(1))
+            .filter(|(previous, next)| **next - **previous > step)
+            .map(|(previous, next)| (*previous + step, *next))
+            .collect()
+    }
+
+    /// Fills the gaps of at most `max_bars` missing bars with flat bars at
+    /// the previous close and no volume, leaving longer gaps such as market
+    /// closures. Returns the number of bars added.
+    pub fn fill_gaps(&mut self, max_bars: usize) -> usize {
+        let step = self.interval.duration();
+        let mut filled = Vec::new();
+        for (start, end) in self.gaps() {
+            let missing = (end - start).num_seconds() / self.interval.seconds();
+            if missing > max_bars as i64 {
+                continue;
+            }
+            let Some((_, previous)) = self.bars.range(..start).next_back() else {
+                continue;
+            };
+            let mut timestamp = start;
+            while timestamp < end {
+                filled.push(OhlcBar::flat(timestamp, previous.close, Positive::ZERO));
+                timestamp += step;
+            }
+        }
+        let count = filled.len();
+        for bar in filled {
+            self.bars.insert(bar.timestamp, bar);
+        }
+        count
+    }
+
+    /// Closes of the bars in time order.
+    pub fn closes(&self) -> Vec<Positive> {
+        self.bars.values().map(|bar| bar.close).collect()
+    }
+
+    /// Log returns between the closes of consecutive bars.
+    pub fn log_returns(&self) -> Vec<Decimal> {
+        let closes = self.closes();
+        closes
+            .windows(2)
+            .filter_map(|pair| (pair[1].to_dec() / pair[0].to_dec()).checked_ln())
+            .collect()
+    }
+
+    /// Annualized historical volatility of the log returns over every window
+    /// of `window` returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if the volatility cannot be computed.
+    pub fn historical_volatility(&self, window: usize) -> Result<Vec<Positive>, VolatilityError> {
+        let returns = self.log_returns();
+        if window == 0 || returns.len() < window {
+            return Ok(Vec::new());
+        }
+        historical_volatility(&returns, window)?
+            .into_iter()
+            .map(|volatility| annualized_volatility(volatility, self.interval.time_frame()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests_history {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use positive::pos_or_panic;
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2030, 1, 2, 14, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_append_lookup_and_resample() {
+        let mut minutes = PriceHistory::new("XYZ", BarInterval::MINUTE);
+        for day in 0..2 {
+            for minute in 0..120 {
+                let timestamp = start() + Duration::days(day) + Duration::minutes(minute);
+                let price = pos_or_panic!(100.0 + (day * 120 + minute) as f64 * 0.01);
+                minutes
+                    .append_tick(timestamp, price, Positive::ONE)
+                    .unwrap();
+                // A second trade in the same minute extends its bar.
+                minutes
+                    .append_tick(
+                        timestamp + Duration::seconds(30),
+                        price + Positive::ONE,
+                        Positive::ONE,
+                    )
+                    .unwrap();
+            }
+        }
+        assert_eq!(minutes.len(), 240);
+        let bar = minutes.get(start() + Duration::seconds(45)).unwrap();
+        assert_eq!(bar.high, pos_or_panic!(101.0));
+        assert_eq!(bar.volume, Positive::TWO);
+        assert!(
+            minutes
+                .append_tick(start(), Positive::HUNDRED, Positive::ONE)
+                .is_err()
+        );
+
+        // The first minute is only known once it has completed.
+        assert_eq!(minutes.close_at(start() + Duration::seconds(59)), None);
+        assert_eq!(
+            minutes.close_at(start() + Duration::minutes(1)),
+            Some(pos_or_panic!(101.0))
+        );
+        assert_eq!(
+            minutes
+                .range(start(), start() + Duration::minutes(10))
+                .count(),
+            10
+        );
+
+        let hourly = minutes.resample(BarInterval::HOUR).unwrap();
+        assert_eq!(hourly.len(), 4);
+        let first = hourly.first().unwrap();
+        assert_eq!(first.open, Positive::HUNDRED);
+        assert_eq!(first.volume, pos_or_panic!(120.0));
+        assert_eq!(first.close, pos_or_panic!(101.59));
+
+        let daily = hourly.resample(BarInterval::DAY).unwrap();
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily.last().unwrap().close, minutes.last().unwrap().close);
+        assert!(daily.resample(BarInterval::HOUR).is_err());
+        assert!(hourly.resample(BarInterval::minutes(90)).is_err());
+    }
+
+    #[test]
+    fn test_gaps_and_volatility() {
+        let mut history = PriceHistory::new("XYZ", BarInterval::DAY);
+        let closes = [100.0, 101.0, 99.5, 102.0, 101.0, 103.0];
+        for (day, close) in [0, 1, 2, 5, 6, 20].into_iter().zip(closes) {
+            history
+                .append_tick(
+                    start() + Duration::days(day),
+                    pos_or_panic!(close),
+                    Positive::TEN,
+                )
+                .unwrap();
+        }
+        let gaps = history.gaps();
+        assert_eq!(gaps.len(), 2);
+        assert_eq!(
+            gaps[0].0,
+            BarInterval::DAY.floor(start() + Duration::days(3))
+        );
+
+        // The weekend is filled, the two week closure is not.
+        assert_eq!(history.fill_gaps(3), 2);
+        assert_eq!(history.len(), 8);
+        let filled = history.get(start() + Duration::days(4)).unwrap();
+        assert_eq!(filled.close, pos_or_panic!(99.5));
+        assert_eq!(filled.volume, Positive::ZERO);
+        assert_eq!(history.gaps().len(), 1);
+
+        let returns = history.log_returns();
+        assert_eq!(returns.len(), 7);
+        assert_eq!(returns[2], Decimal::ZERO);
+        let volatility = history.historical_volatility(5).unwrap();
+        assert_eq!(volatility.len(), 3);
+        assert!(volatility.iter().all(|v| *v > Positive::ZERO));
+        assert!(history.historical_volatility(10).unwrap().is_empty());
+    }
+}
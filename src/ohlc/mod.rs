@@ -0,0 +1,43 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # OHLCV Price History
+//!
+//! In-memory time series of the underlying price, stored as OHLCV bars.
+//!
+//! * [`OhlcBar`] - open, high, low, close and volume of one interval, which
+//!   can be built from the [`OhlcvCandle`](crate::utils::OhlcvCandle)s read
+//!   from files or served by a
+//!   [`MarketDataProvider`](crate::chains::MarketDataProvider).
+//! * [`BarInterval`] - length of the bars, with buckets aligned to the Unix
+//!   epoch, so daily bars start at midnight UTC.
+//! * [`PriceHistory`] - the bars of one symbol in time order, with append
+//!   and lookup APIs, resampling to longer intervals (1m → 1h → 1d), gap
+//!   detection and forward filling, and the log returns and historical
+//!   volatility consumed by the volatility estimators and backtests.
+//!
+//! ```rust
+//! use chrono::{TimeZone, Utc};
+//! use optionstratlib::ohlc::{BarInterval, PriceHistory};
+//! use positive::pos_or_panic;
+//!
+//! let start = Utc.with_ymd_and_hms(2030, 1, 2, 14, 0, 0).unwrap();
+//! let mut history = PriceHistory::new("XYZ", BarInterval::MINUTE);
+//! for minute in 0..120 {
+//!     let price = pos_or_panic!(100.0 + minute as f64 * 0.01);
+//!     history
+//!         .append_tick(start + chrono::Duration::minutes(minute), price, pos_or_panic!(10.0))
+//!         .unwrap();
+//! }
+//! let hourly = history.resample(BarInterval::HOUR).unwrap();
+//! assert_eq!(hourly.len(), 2);
+//! ```
+
+mod bar;
+mod history;
+
+pub use bar::{BarInterval, OhlcBar};
+pub use history::PriceHistory;
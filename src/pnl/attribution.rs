@@ -0,0 +1,299 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # P&L Attribution
+//!
+//! Explains the change in value of option legs between two
+//! [`MarketSnapshot`]s with a Taylor expansion around the first one:
+//!
+//! ```text
+//! ΔV ≈ Δ·dS + ½·Γ·dS² + Θ·dt + ν·dσ + ρ·dr
+//! ```
+//!
+//! Every leg is repriced with Black-Scholes at both snapshots. The part of the
+//! actual change that the Greeks do not explain is reported as the residual,
+//! which grows with the size of the market move and captures higher-order
+//! effects such as vanna and volga.
+
+use crate::ExpirationDate;
+use crate::error::OptionsError;
+use crate::greeks::{Greeks, NetGreeks};
+use crate::model::option::Options;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::iter::Sum;
+use std::ops::Add;
+use utoipa::ToSchema;
+
+/// Market state at which option legs are valued.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MarketSnapshot {
+    /// Price of the underlying asset.
+    pub underlying_price: Positive,
+    /// Implied volatility applied to every leg, or `None` to keep the
+    /// volatility of each leg.
+    pub implied_volatility: Option<Positive>,
+    /// Annualized risk-free interest rate.
+    pub risk_free_rate: Decimal,
+    /// Days elapsed since the current state of the legs, shortening their
+    /// time to expiration.
+    pub days_elapsed: Positive,
+}
+
+impl MarketSnapshot {
+    /// Creates a new market snapshot.
+    pub fn new(
+        underlying_price: Positive,
+        implied_volatility: Option<Positive>,
+        risk_free_rate: Decimal,
+        days_elapsed: Positive,
+    ) -> Self {
+        Self {
+            underlying_price,
+            implied_volatility,
+            risk_free_rate,
+            days_elapsed,
+        }
+    }
+
+    /// Snapshot of the current market data of `option`.
+    pub fn from_option(option: &Options) -> Self {
+        Self::new(
+            option.underlying_price,
+            Some(option.implied_volatility),
+            option.risk_free_rate,
+            Positive::ZERO,
+        )
+    }
+
+    /// Copy of `option` with the market data of the snapshot.
+    fn apply(&self, option: &Options) -> Result<Options, OptionsError> {
+        let mut option = option.clone();
+        option.underlying_price = self.underlying_price;
+        option.risk_free_rate = self.risk_free_rate;
+        if let Some(implied_volatility) = self.implied_volatility {
+            option.implied_volatility = implied_volatility;
+        }
+        let days = option.expiration_date.get_days()?;
+        option.expiration_date = ExpirationDate::Days(days.saturating_sub(&self.days_elapsed));
+        Ok(option)
+    }
+}
+
+/// Decomposition of a change in value into Greek contributions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PnLAttribution {
+    /// P&L explained by the change in the underlying price.
+    pub delta: Decimal,
+    /// P&L explained by the convexity of the underlying price move.
+    pub gamma: Decimal,
+    /// P&L explained by the passage of time.
+    pub theta: Decimal,
+    /// P&L explained by the change in implied volatility.
+    pub vega: Decimal,
+    /// P&L explained by the change in the risk-free rate.
+    pub rho: Decimal,
+    /// P&L not explained by the Greeks.
+    pub residual: Decimal,
+    /// Actual change in value.
+    pub total: Decimal,
+}
+
+impl PnLAttribution {
+    /// Sum of the Greek contributions, excluding the residual.
+    pub fn explained(&self) -> Decimal {
+        self.delta + self.gamma + self.theta + self.vega + self.rho
+    }
+}
+
+impl Add for PnLAttribution {
+    type Output = PnLAttribution;
+
+    fn add(self, other: PnLAttribution) -> PnLAttribution {
+        PnLAttribution {
+            delta: self.delta + other.delta,
+            gamma: self.gamma + other.gamma,
+            theta: self.theta + other.theta,
+            vega: self.vega + other.vega,
+            rho: self.rho + other.rho,
+            residual: self.residual + other.residual,
+            total: self.total + other.total,
+        }
+    }
+}
+
+impl Sum for PnLAttribution {
+    fn sum<I: Iterator<Item = PnLAttribution>>(iter: I) -> Self {
+        iter.fold(PnLAttribution::default(), Add::add)
+    }
+}
+
+/// P&L attribution of every leg of a position or strategy and its net total.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PnLExplain {
+    /// Attribution of each leg, in the order of the legs.
+    pub legs: Vec<PnLAttribution>,
+    /// Sum of the attributions of the legs.
+    pub net: PnLAttribution,
+}
+
+/// Explains the change in value of `option` between the `from` and `to`
+/// market snapshots.
+///
+/// Greeks are taken at `from`. Like the Greeks of the crate, vega and rho are
+/// per percentage point and theta is per day. The result is scaled by the
+/// quantity of the option and signed by its side.
+///
+/// # Errors
+///
+/// Returns an `OptionsError` if the option cannot be priced or its Greeks
+/// cannot be computed at `from`.
+pub fn explain_pnl(
+    option: &Options,
+    from: &MarketSnapshot,
+    to: &MarketSnapshot,
+) -> Result<PnLAttribution, OptionsError> {
+    let start = from.apply(option)?;
+    let end = to.apply(option)?;
+    let quantity = option.quantity.to_dec();
+    let total =
+        (end.calculate_price_black_scholes()? - start.calculate_price_black_scholes()?) * quantity;
+
+    let greeks = NetGreeks::of_option(&start)?;
+    let price_move = end.underlying_price.to_dec() - start.underlying_price.to_dec();
+    let days = to.days_elapsed.to_dec() - from.days_elapsed.to_dec();
+    let volatility_move = end.implied_volatility.to_dec() - start.implied_volatility.to_dec();
+    let rate_move = end.risk_free_rate - start.risk_free_rate;
+
+    let delta = greeks.delta * price_move;
+    let gamma = greeks.gamma * price_move * price_move / Decimal::TWO;
+    let theta = greeks.theta * days;
+    let vega = greeks.vega * volatility_move * Decimal::ONE_HUNDRED;
+    let rho = greeks.rho * rate_move * Decimal::ONE_HUNDRED;
+    let explained = delta + gamma + theta + vega + rho;
+
+    Ok(PnLAttribution {
+        delta,
+        gamma,
+        theta,
+        vega,
+        rho,
+        residual: total - explained,
+        total,
+    })
+}
+
+/// Explains the change in value of every leg of `legs` between the `from` and
+/// `to` market snapshots, and nets the result.
+///
+/// # Errors
+///
+/// Returns an `OptionsError` if the legs cannot be retrieved or a leg cannot
+/// be explained.
+pub fn explain_pnl_legs<G: Greeks + ?Sized>(
+    legs: &G,
+    from: &MarketSnapshot,
+    to: &MarketSnapshot,
+) -> Result<PnLExplain, OptionsError> {
+    let legs = legs
+        .get_options()?
+        .into_iter()
+        .map(|option| explain_pnl(option, from, to))
+        .collect::<Result<Vec<_>, _>>()?;
+    let net = legs.iter().copied().sum();
+    Ok(PnLExplain { legs, net })
+}
+
+#[cfg(test)]
+mod tests_attribution {
+    use super::*;
+    use crate::model::Portfolio;
+    use crate::model::position::Position;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use chrono::Utc;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn option(style: OptionStyle, side: Side) -> Options {
+        Options::new(
+            OptionType::European,
+            side,
+            "AAPL".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(60.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(2.0),
+            Positive::HUNDRED,
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_explain_reconciles() {
+        let option = option(OptionStyle::Call, Side::Long);
+        let from = MarketSnapshot::from_option(&option);
+        let to = MarketSnapshot::new(
+            pos_or_panic!(102.0),
+            Some(pos_or_panic!(0.22)),
+            dec!(0.05),
+            Positive::ONE,
+        );
+        let explain = explain_pnl(&option, &from, &to).unwrap();
+
+        assert!(explain.delta > Decimal::ZERO);
+        assert!(explain.gamma > Decimal::ZERO);
+        assert!(explain.theta < Decimal::ZERO);
+        assert!(explain.vega > Decimal::ZERO);
+        assert_eq!(explain.rho, Decimal::ZERO);
+        assert_eq!(explain.explained() + explain.residual, explain.total);
+        assert!(explain.residual.abs() < explain.total.abs() / dec!(10));
+    }
+
+    #[test]
+    fn test_short_leg_mirrors_long_leg() {
+        let long = option(OptionStyle::Put, Side::Long);
+        let short = option(OptionStyle::Put, Side::Short);
+        let from = MarketSnapshot::from_option(&long);
+        let to = MarketSnapshot::new(pos_or_panic!(97.0), None, dec!(0.06), pos_or_panic!(2.0));
+
+        let long = explain_pnl(&long, &from, &to).unwrap();
+        let short = explain_pnl(&short, &from, &to).unwrap();
+        assert_eq!(long.delta, -short.delta);
+        assert_eq!(long.gamma, -short.gamma);
+        assert_eq!(long.theta, -short.theta);
+        assert_eq!(long.vega, Decimal::ZERO);
+        assert_eq!(long.rho, -short.rho);
+        assert_eq!(long.total, -short.total);
+    }
+
+    #[test]
+    fn test_explain_legs_nets() {
+        let mut portfolio = Portfolio::new("Straddle".to_string());
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            portfolio.add_position(Position::new(
+                option(style, Side::Long),
+                pos_or_panic!(4.0),
+                Utc::now(),
+                Positive::ZERO,
+                Positive::ZERO,
+                None,
+                None,
+            ));
+        }
+        let from = MarketSnapshot::from_option(&portfolio.positions[0].option);
+        let to = MarketSnapshot::new(pos_or_panic!(110.0), None, dec!(0.05), pos_or_panic!(5.0));
+        let explain = explain_pnl_legs(&portfolio, &from, &to).unwrap();
+
+        assert_eq!(explain.legs.len(), 2);
+        assert_eq!(explain.net, explain.legs[0] + explain.legs[1]);
+        assert!(explain.net.gamma > Decimal::ZERO);
+        assert!(explain.net.total > Decimal::ZERO);
+    }
+}
@@ -14,6 +14,7 @@ use positive::pos_or_panic;
 //!
 //! * `PnL` - Structure representing profit and loss information
 //! * `PnLCalculator` - Trait for implementing PnL calculation logic
+//! * `explain_pnl` - Greeks-based attribution of P&L between two market snapshots
 //!
 //! ## Key Features
 //!
@@ -107,12 +108,14 @@ use positive::pos_or_panic;
 /// * [`model`] - Core data structures for financial analysis and PnL modeling
 pub mod model;
 
+mod attribution;
 mod metrics;
 mod traits;
 mod transaction;
 /// * [`utils`] - Utility functions for data manipulation and calculations
 pub mod utils;
 
+pub use attribution::{MarketSnapshot, PnLAttribution, PnLExplain, explain_pnl, explain_pnl_legs};
 pub use metrics::{
     PnLMetrics, PnLMetricsDocument, PnLMetricsStep, create_pnl_metrics_document, load_pnl_metrics,
     save_pnl_metrics, save_pnl_metrics_with_document,
@@ -25,7 +25,7 @@
 // Core model types
 pub use crate::model::{
     BasicAxisTypes, ExpirationDate, Options, Position, Trade,
-    types::{Action, OptionStyle, OptionType, Side},
+    types::{Action, DayCount, OptionStyle, OptionType, SettlementType, Side},
 };
 pub use crate::strategies::{
     StrategyConstructor,
@@ -38,6 +38,7 @@ pub use crate::strategies::{
     bear_put_spread::BearPutSpread,
     bull_call_spread::BullCallSpread,
     bull_put_spread::BullPutSpread,
+    calendar_spread::CalendarSpread,
     call_butterfly::CallButterfly,
     collar::Collar,
     covered_call::CoveredCall,
@@ -46,6 +47,7 @@ pub use crate::strategies::{
         AdjustmentAction, AdjustmentConfig, AdjustmentError, AdjustmentOptimizer, AdjustmentPlan,
         AdjustmentTarget, DeltaNeutrality, PortfolioGreeks,
     },
+    diagonal_spread::DiagonalSpread,
     iron_butterfly::IronButterfly,
     iron_condor::IronCondor,
     long_butterfly_spread::LongButterflySpread,
@@ -53,6 +55,7 @@ pub use crate::strategies::{
     long_put::LongPut,
     long_straddle::LongStraddle,
     long_strangle::LongStrangle,
+    payoff_curve::PayoffCurvable,
     poor_mans_covered_call::PoorMansCoveredCall,
     probabilities::ProbabilityAnalysis,
     protective_put::ProtectivePut,
@@ -62,6 +65,7 @@ pub use crate::strategies::{
     short_straddle::ShortStraddle,
     short_strangle::ShortStrangle,
     utils::FindOptimalSide,
+    validation::Validate,
 };
 
 // Greeks calculations
@@ -17,6 +17,19 @@
 //! The BAW model provides a fast analytical approximation for American options with
 //! O(1) complexity, making it suitable for real-time pricing applications.
 //!
+//! ### Early-Exercise Boundary
+//!
+//! [`early_exercise_boundary`] computes the critical underlying price at which
+//! immediate exercise becomes optimal, as a function of the time left until
+//! expiration, on a binomial lattice.
+//!
+//! ### Discrete Dividends
+//!
+//! [`american_discrete_dividends`] prices American options on an underlying
+//! paying known cash dividends, such as the ex-dividend dates of an
+//! [`EventTimeline`], with an escrowed-dividend lattice that checks early
+//! exercise just before every ex-date.
+//!
 //! ## Usage Example
 //!
 //! ```rust
@@ -41,12 +54,19 @@
 //! - Barone-Adesi, G., & Whaley, R. E. (1987). "Efficient Analytic Approximation
 //!   of American Option Values". Journal of Finance, 42(2), 301-320.
 
+use crate::curves::{Curve, Point2D};
+use crate::d2f;
 use crate::error::PricingError;
 use crate::greeks::big_n;
-use crate::model::types::OptionStyle;
+use crate::model::events::EventTimeline;
+use crate::model::types::{DayCount, OptionStyle};
+use chrono::{DateTime, Utc};
 use positive::Positive;
 use rust_decimal::{Decimal, MathematicalOps};
 use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use utoipa::ToSchema;
 
 /// Maximum iterations for Newton-Raphson method to find critical price.
 const MAX_ITERATIONS: usize = 100;
@@ -378,6 +398,298 @@ fn find_critical_price_put(
     Ok(s_star.max(dec!(0.01)).min(strike))
 }
 
+/// Point of the early-exercise boundary of an American option.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExerciseBoundaryPoint {
+    /// Time left until expiration, in years.
+    pub time_to_expiry: Positive,
+    /// Underlying price beyond which immediate exercise is optimal: at or
+    /// above it for calls, at or below it for puts.
+    pub critical_price: Positive,
+}
+
+/// Early-exercise boundary of an American option as a function of the time
+/// left until expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExerciseBoundary {
+    /// Style of the option.
+    pub option_style: OptionStyle,
+    /// Strike price of the option.
+    pub strike: Positive,
+    /// Points of the boundary, sorted by increasing time to expiration.
+    /// Times at which early exercise is never optimal have no point.
+    pub points: Vec<ExerciseBoundaryPoint>,
+}
+
+impl ExerciseBoundary {
+    /// Critical price with `time_to_expiry` left, interpolated linearly
+    /// between the points of the boundary, or `None` outside of them.
+    pub fn critical_price_at(&self, time_to_expiry: Positive) -> Option<Positive> {
+        let index = self
+            .points
+            .partition_point(|point| point.time_to_expiry < time_to_expiry);
+        let upper = self.points.get(index)?;
+        if upper.time_to_expiry == time_to_expiry {
+            return Some(upper.critical_price);
+        }
+        let lower = self.points.get(index.checked_sub(1)?)?;
+        let weight = (time_to_expiry - lower.time_to_expiry).to_dec()
+            / (upper.time_to_expiry - lower.time_to_expiry).to_dec();
+        let price = lower.critical_price.to_dec()
+            + (upper.critical_price.to_dec() - lower.critical_price.to_dec()) * weight;
+        Positive::new_decimal(price).ok()
+    }
+
+    /// Whether immediate exercise is optimal at `spot` with `time_to_expiry`
+    /// left.
+    pub fn is_exercise_optimal(&self, spot: Positive, time_to_expiry: Positive) -> bool {
+        match self.critical_price_at(time_to_expiry) {
+            Some(critical) => match self.option_style {
+                OptionStyle::Call => spot >= critical,
+                OptionStyle::Put => spot <= critical,
+            },
+            None => false,
+        }
+    }
+
+    /// Boundary as a curve of critical price (y) against time to expiration (x).
+    pub fn to_curve(&self) -> Curve {
+        Curve::new(
+            self.points
+                .iter()
+                .map(|point| Point2D::new(point.time_to_expiry, point.critical_price))
+                .collect::<BTreeSet<_>>(),
+        )
+    }
+}
+
+/// Computes the early-exercise boundary of an American option with a
+/// Cox-Ross-Rubinstein lattice.
+///
+/// The lattice is centred on the strike, since the boundary does not depend on
+/// the current underlying price, and starts enough steps before
+/// `time_to_expiry` to span four standard deviations around the strike at
+/// every reported time. At every step the critical price is the node closest
+/// to the continuation region among those where exercising is worth more than
+/// holding, so the resolution of the curve improves with `steps`.
+///
+/// # Parameters
+///
+/// * `strike` - Strike price of the option
+/// * `time_to_expiry` - Time to expiration in years
+/// * `risk_free_rate` - Annualized risk-free interest rate
+/// * `dividend_yield` - Annualized dividend yield
+/// * `volatility` - Annualized volatility of the underlying
+/// * `option_style` - Whether the option is a Call or Put
+/// * `steps` - Number of time steps of the lattice
+///
+/// # Returns
+///
+/// * `Result<ExerciseBoundary, PricingError>` - The boundary; calls on assets
+///   without dividends have no points, since early exercise is never optimal.
+pub fn early_exercise_boundary(
+    strike: Positive,
+    time_to_expiry: Positive,
+    risk_free_rate: Decimal,
+    dividend_yield: Positive,
+    volatility: Positive,
+    option_style: &OptionStyle,
+    steps: usize,
+) -> Result<ExerciseBoundary, PricingError> {
+    if steps == 0 || time_to_expiry == Positive::ZERO || volatility == Positive::ZERO {
+        return Err(PricingError::method_error(
+            "early_exercise_boundary",
+            "steps, time to expiry and volatility must be positive",
+        ));
+    }
+    let k = strike.to_f64();
+    let r = d2f!(risk_free_rate);
+    let q = dividend_yield.to_f64();
+    let dt = time_to_expiry.to_f64() / steps as f64;
+    let u = (volatility.to_f64() * dt.sqrt()).exp();
+    let d = 1.0 / u;
+    let p = (((r - q) * dt).exp() - d) / (u - d);
+    if !(0.0..=1.0).contains(&p) {
+        return Err(PricingError::method_error(
+            "early_exercise_boundary",
+            "risk-neutral probability out of range, increase the number of steps",
+        ));
+    }
+    let discount = (-r * dt).exp();
+    let intrinsic = |spot: f64| match option_style {
+        OptionStyle::Call => (spot - k).max(0.0),
+        OptionStyle::Put => (k - spot).max(0.0),
+    };
+    // Extra steps before the first reported time widen the lattice so that
+    // the boundary is covered by nodes from the first reported time onwards.
+    let lead = (4.0 * (steps as f64).sqrt()).ceil() as usize;
+    let total = steps + lead;
+    let spot_at = |step: usize, i: usize| k * u.powi(2 * i as i32 - step as i32);
+
+    let mut values: Vec<f64> = (0..=total).map(|i| intrinsic(spot_at(total, i))).collect();
+    let mut points = Vec::new();
+    for step in (0..total).rev() {
+        let mut critical: Option<f64> = None;
+        for i in 0..=step {
+            let spot = spot_at(step, i);
+            let hold = discount * (p * values[i + 1] + (1.0 - p) * values[i]);
+            let exercise = intrinsic(spot);
+            if exercise > 0.0 && exercise > hold + f64::EPSILON * k {
+                critical = Some(match (option_style, critical) {
+                    (OptionStyle::Call, Some(current)) => current.min(spot),
+                    (OptionStyle::Put, Some(current)) => current.max(spot),
+                    (_, None) => spot,
+                });
+            }
+            values[i] = hold.max(exercise);
+        }
+        if let Some(critical) = critical
+            && step >= lead
+        {
+            points.push(ExerciseBoundaryPoint {
+                time_to_expiry: Positive::new(dt * (total - step) as f64)?,
+                critical_price: Positive::new(critical)?,
+            });
+        }
+    }
+    Ok(ExerciseBoundary {
+        option_style: *option_style,
+        strike,
+        points,
+    })
+}
+
+/// Cash dividend paid at a known time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DiscreteDividend {
+    /// Time until the ex-dividend date, in years.
+    pub time: Positive,
+    /// Dividend per share.
+    pub amount: Positive,
+}
+
+impl DiscreteDividend {
+    /// Creates a dividend going ex in `time` years.
+    pub fn new(time: Positive, amount: Positive) -> Self {
+        DiscreteDividend { time, amount }
+    }
+
+    /// Dividends of `symbol` in `timeline` going ex after `as_of` and up to
+    /// `expiration`, timed in years from `as_of` under `day_count`.
+    pub fn from_schedule(
+        timeline: &EventTimeline,
+        symbol: &str,
+        as_of: DateTime<Utc>,
+        expiration: DateTime<Utc>,
+        day_count: &DayCount,
+    ) -> Vec<Self> {
+        timeline
+            .dividends(symbol, as_of, expiration)
+            .map(|(ex_date, amount)| {
+                DiscreteDividend::new(day_count.year_fraction_between(as_of, ex_date), amount)
+            })
+            .collect()
+    }
+}
+
+/// Prices an American option on an underlying paying discrete cash dividends
+/// with a Cox-Ross-Rubinstein lattice.
+///
+/// The escrowed-dividend model is used: the lattice follows the underlying
+/// less the present value of the dividends before expiration, and the
+/// present value of the dividends still to go ex is added back at every node
+/// to get the price exercise is compared against. A dividend going ex at a
+/// node is still included there, so a call can be exercised just before the
+/// ex-date to capture it.
+///
+/// # Parameters
+///
+/// * `spot` - Current price of the underlying asset
+/// * `strike` - Strike price of the option
+/// * `time_to_expiry` - Time to expiration in years
+/// * `risk_free_rate` - Annualized risk-free interest rate
+/// * `volatility` - Annualized volatility of the underlying less dividends
+/// * `option_style` - Whether the option is a Call or Put
+/// * `dividends` - Cash dividends; those after expiration are ignored
+/// * `steps` - Number of time steps of the lattice
+///
+/// # Returns
+///
+/// * `Result<Decimal, PricingError>` - The option price
+#[allow(clippy::too_many_arguments)]
+pub fn american_discrete_dividends(
+    spot: Positive,
+    strike: Positive,
+    time_to_expiry: Positive,
+    risk_free_rate: Decimal,
+    volatility: Positive,
+    option_style: &OptionStyle,
+    dividends: &[DiscreteDividend],
+    steps: usize,
+) -> Result<Decimal, PricingError> {
+    let k = strike.to_f64();
+    let intrinsic = |price: f64| match option_style {
+        OptionStyle::Call => (price - k).max(0.0),
+        OptionStyle::Put => (k - price).max(0.0),
+    };
+    if time_to_expiry == Positive::ZERO {
+        return Ok(Decimal::from_f64_retain(intrinsic(spot.to_f64())).unwrap_or_default());
+    }
+    if steps == 0 || volatility == Positive::ZERO {
+        return Err(PricingError::method_error(
+            "american_discrete_dividends",
+            "steps and volatility must be positive",
+        ));
+    }
+    let t = time_to_expiry.to_f64();
+    let r = d2f!(risk_free_rate);
+    let dividends: Vec<(f64, f64)> = dividends
+        .iter()
+        .filter(|dividend| dividend.time <= time_to_expiry)
+        .map(|dividend| (dividend.time.to_f64(), dividend.amount.to_f64()))
+        .collect();
+    // Present value at `time` of the dividends going ex from `time` onwards.
+    let escrow = |time: f64| -> f64 {
+        dividends
+            .iter()
+            .filter(|(ex, _)| *ex >= time)
+            .map(|(ex, amount)| amount * (-r * (ex - time)).exp())
+            .sum()
+    };
+    let risky = spot.to_f64() - escrow(0.0);
+    if risky <= 0.0 {
+        return Err(PricingError::method_error(
+            "american_discrete_dividends",
+            "dividends are worth more than the underlying",
+        ));
+    }
+
+    let dt = t / steps as f64;
+    let u = (volatility.to_f64() * dt.sqrt()).exp();
+    let d = 1.0 / u;
+    let p = ((r * dt).exp() - d) / (u - d);
+    if !(0.0..=1.0).contains(&p) {
+        return Err(PricingError::method_error(
+            "american_discrete_dividends",
+            "risk-neutral probability out of range, increase the number of steps",
+        ));
+    }
+    let discount = (-r * dt).exp();
+    let node = |step: usize, i: usize| risky * u.powi(2 * i as i32 - step as i32);
+
+    let mut values: Vec<f64> = (0..=steps)
+        .map(|i| intrinsic(node(steps, i) + escrow(t)))
+        .collect();
+    for step in (0..steps).rev() {
+        let carried = escrow(dt * step as f64);
+        for i in 0..=step {
+            let hold = discount * (p * values[i + 1] + (1.0 - p) * values[i]);
+            values[i] = hold.max(intrinsic(node(step, i) + carried));
+        }
+    }
+    Ok(Decimal::from_f64_retain(values[0]).unwrap_or_default())
+}
+
 #[cfg(test)]
 mod tests_american_pricing {
     use super::*;
@@ -558,4 +870,149 @@ mod tests_american_pricing {
         // Should be positive (time value)
         assert!(price.to_f64().unwrap() > 0.0);
     }
+
+    #[test]
+    fn test_put_exercise_boundary() {
+        let boundary = early_exercise_boundary(
+            Positive::HUNDRED,
+            Positive::ONE,
+            dec!(0.05),
+            Positive::ZERO,
+            pos_or_panic!(0.2),
+            &OptionStyle::Put,
+            200,
+        )
+        .unwrap();
+
+        assert!(!boundary.points.is_empty());
+        for point in &boundary.points {
+            assert!(point.critical_price < Positive::HUNDRED);
+        }
+        // The boundary falls as the time to expiration grows.
+        let near = boundary.points.first().unwrap();
+        let far = boundary.points.last().unwrap();
+        assert!(near.time_to_expiry < far.time_to_expiry);
+        assert!(near.critical_price > far.critical_price);
+        assert_eq!(boundary.points.len(), 200);
+        assert_relative_eq!(far.time_to_expiry.to_f64(), 1.0, epsilon = 1e-9);
+        // Around 80 with one year left for these parameters.
+        assert_relative_eq!(far.critical_price.to_f64(), 80.0, epsilon = 3.0);
+
+        assert!(boundary.is_exercise_optimal(pos_or_panic!(60.0), pos_or_panic!(0.5)));
+        assert!(!boundary.is_exercise_optimal(pos_or_panic!(95.0), pos_or_panic!(0.5)));
+        assert_eq!(boundary.to_curve().points.len(), boundary.points.len());
+    }
+
+    #[test]
+    fn test_call_exercise_boundary() {
+        let no_dividend = early_exercise_boundary(
+            Positive::HUNDRED,
+            Positive::ONE,
+            dec!(0.05),
+            Positive::ZERO,
+            pos_or_panic!(0.2),
+            &OptionStyle::Call,
+            100,
+        )
+        .unwrap();
+        assert!(no_dividend.points.is_empty());
+        assert_eq!(no_dividend.critical_price_at(pos_or_panic!(0.5)), None);
+
+        let dividend = early_exercise_boundary(
+            Positive::HUNDRED,
+            Positive::ONE,
+            dec!(0.05),
+            pos_or_panic!(0.08),
+            pos_or_panic!(0.2),
+            &OptionStyle::Call,
+            100,
+        )
+        .unwrap();
+        assert!(!dividend.points.is_empty());
+        for point in &dividend.points {
+            assert!(point.critical_price > Positive::HUNDRED);
+        }
+        let critical = dividend.critical_price_at(pos_or_panic!(0.5)).unwrap();
+        assert!(dividend.is_exercise_optimal(critical, pos_or_panic!(0.5)));
+    }
+
+    #[test]
+    fn test_discrete_dividends() {
+        let price = |spot: f64, style: &OptionStyle, dividends: &[DiscreteDividend]| {
+            american_discrete_dividends(
+                pos_or_panic!(spot),
+                Positive::HUNDRED,
+                pos_or_panic!(0.25),
+                dec!(0.05),
+                pos_or_panic!(0.2),
+                style,
+                dividends,
+                400,
+            )
+            .unwrap()
+            .to_f64()
+            .unwrap()
+        };
+        // Without dividends an American call is worth the European one.
+        assert_relative_eq!(price(100.0, &OptionStyle::Call, &[]), 4.615, epsilon = 0.02);
+
+        // A dividend before expiration makes exercising a deep call early
+        // worth at least its intrinsic value, where holding it would drop.
+        let dividend = [DiscreteDividend::new(
+            pos_or_panic!(0.2),
+            pos_or_panic!(5.0),
+        )];
+        let call = price(120.0, &OptionStyle::Call, &dividend);
+        assert!(call >= 20.0 - 1e-9);
+        assert!(call < price(120.0, &OptionStyle::Call, &[]));
+        assert!(price(100.0, &OptionStyle::Put, &dividend) > price(100.0, &OptionStyle::Put, &[]));
+
+        // Dividends after expiration are ignored.
+        let late = [DiscreteDividend::new(Positive::ONE, pos_or_panic!(5.0))];
+        assert_eq!(
+            price(100.0, &OptionStyle::Call, &late),
+            price(100.0, &OptionStyle::Call, &[])
+        );
+        assert!(
+            american_discrete_dividends(
+                Positive::TEN,
+                Positive::HUNDRED,
+                Positive::ONE,
+                dec!(0.05),
+                pos_or_panic!(0.2),
+                &OptionStyle::Call,
+                &[DiscreteDividend::new(pos_or_panic!(0.5), Positive::TWENTY)],
+                100,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_dividends_from_schedule() {
+        use crate::model::events::MarketEvent;
+        let now = Utc::now();
+        let timeline = EventTimeline::new()
+            .with_event(MarketEvent::ex_dividend(
+                "XYZ",
+                now + chrono::Duration::days(73),
+                Positive::ONE,
+            ))
+            .with_event(MarketEvent::ex_dividend(
+                "XYZ",
+                now + chrono::Duration::days(400),
+                Positive::ONE,
+            ));
+        let dividends = DiscreteDividend::from_schedule(
+            &timeline,
+            "XYZ",
+            now,
+            now + chrono::Duration::days(365),
+            &DayCount::Act365Fixed,
+        );
+        assert_eq!(
+            dividends,
+            vec![DiscreteDividend::new(pos_or_panic!(0.2), Positive::ONE)]
+        );
+    }
 }
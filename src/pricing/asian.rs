@@ -75,8 +75,7 @@ fn geometric_asian_price(option: &Options) -> Result<Decimal, PricingError> {
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .time_to_expiration()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -109,13 +108,13 @@ fn geometric_asian_price(option: &Options) -> Result<Decimal, PricingError> {
 
     let price = match option.option_style {
         OptionStyle::Call => {
-            let n_d1 = big_n(d1_val).unwrap_or(Decimal::ZERO);
-            let n_d2 = big_n(d2_val).unwrap_or(Decimal::ZERO);
+            let n_d1 = big_n(d1_val)?;
+            let n_d2 = big_n(d2_val)?;
             s.to_dec() * ((b_adj - r) * t).exp() * n_d1 - k.to_dec() * discount * n_d2
         }
         OptionStyle::Put => {
-            let n_neg_d1 = big_n(-d1_val).unwrap_or(Decimal::ZERO);
-            let n_neg_d2 = big_n(-d2_val).unwrap_or(Decimal::ZERO);
+            let n_neg_d1 = big_n(-d1_val)?;
+            let n_neg_d2 = big_n(-d2_val)?;
             k.to_dec() * discount * n_neg_d2 - s.to_dec() * ((b_adj - r) * t).exp() * n_neg_d1
         }
     };
@@ -136,8 +135,7 @@ fn arithmetic_asian_price(option: &Options) -> Result<Decimal, PricingError> {
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .time_to_expiration()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -199,13 +197,13 @@ fn arithmetic_asian_price(option: &Options) -> Result<Decimal, PricingError> {
 
     let price = match option.option_style {
         OptionStyle::Call => {
-            let n_d1 = big_n(d1_val).unwrap_or(Decimal::ZERO);
-            let n_d2 = big_n(d2_val).unwrap_or(Decimal::ZERO);
+            let n_d1 = big_n(d1_val)?;
+            let n_d2 = big_n(d2_val)?;
             discount * (f_adj * n_d1 - k.to_dec() * n_d2)
         }
         OptionStyle::Put => {
-            let n_neg_d1 = big_n(-d1_val).unwrap_or(Decimal::ZERO);
-            let n_neg_d2 = big_n(-d2_val).unwrap_or(Decimal::ZERO);
+            let n_neg_d1 = big_n(-d1_val)?;
+            let n_neg_d2 = big_n(-d2_val)?;
             discount * (k.to_dec() * n_neg_d2 - f_adj * n_neg_d1)
         }
     };
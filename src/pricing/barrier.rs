@@ -6,9 +6,9 @@
 
 use crate::Options;
 use crate::error::PricingError;
+use crate::f2d;
 use crate::greeks::big_n;
 use crate::model::types::{BarrierType, OptionStyle, OptionType};
-use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::{Decimal, MathematicalOps};
 use rust_decimal_macros::dec;
 
@@ -22,8 +22,8 @@ pub fn barrier_black_scholes(option: &Options) -> Result<Decimal, PricingError>
             rebate,
         } => (
             barrier_type,
-            Decimal::from_f64(*barrier_level).unwrap(),
-            Decimal::from_f64(rebate.unwrap_or(0.0)).unwrap(),
+            f2d!(*barrier_level),
+            f2d!(rebate.unwrap_or(0.0)),
         ),
         _ => {
             return Err(PricingError::unsupported_option_type(
@@ -209,7 +209,9 @@ pub fn barrier_black_scholes(option: &Options) -> Result<Decimal, PricingError>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::types::{BarrierType, OptionStyle, OptionType, Side};
+    use crate::model::types::{
+        BarrierType, DayCount, OptionStyle, OptionType, SettlementType, Side,
+    };
     use crate::{ExpirationDate, Options};
     use positive::pos_or_panic;
     use rust_decimal_macros::dec;
@@ -232,6 +234,8 @@ mod tests {
             option_style: style,
             dividend_yield: pos_or_panic!(0.04),
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
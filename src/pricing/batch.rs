@@ -0,0 +1,798 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Batch Black-Scholes
+//!
+//! Vectorized Black-Scholes pricing of many European options sharing the same
+//! underlying price, expiry, risk-free rate and dividend yield, as in a full
+//! option chain re-priced on every tick.
+//!
+//! Inputs and outputs are columnar `f64` slices. The kernel works on fixed
+//! blocks of [`BATCH_LANES`] options with branch-free polynomial versions of
+//! `exp`, `ln` and the normal CDF, so the compiler emits packed SIMD
+//! instructions (SSE2 by default, AVX2 with `-C target-cpu=native`) on stable
+//! Rust without `unsafe` code. Discount factors, `ln(S)` and `√T` are computed
+//! once per batch; each option only costs one logarithm, two `erfc`
+//! evaluations and a few multiplications. The kernel is accurate to about
+//! `1e-14` relative error and agrees with [`black_scholes`] within the
+//! precision of its `Decimal` normal CDF.
+//!
+//! [`black_scholes`]: crate::pricing::black_scholes
+
+use crate::Options;
+use crate::error::PricingError;
+use crate::model::types::{DayCount, OptionStyle, OptionType, Side};
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Number of options priced together by one iteration of the batch kernel.
+pub const BATCH_LANES: usize = 4;
+
+type Lane = [f64; BATCH_LANES];
+
+const METHOD: &str = "Batch Black-Scholes";
+
+/// Black-Scholes pricer for batches of European options sharing their
+/// underlying price, time to expiry, risk-free rate and dividend yield.
+///
+/// Theta is expressed per day of the [`DayCount`] of the pricer, ACT/365
+/// unless set with [`BatchBlackScholes::with_day_count`].
+///
+/// # Example
+///
+/// ```rust
+/// use optionstratlib::OptionStyle;
+/// use optionstratlib::pricing::BatchBlackScholes;
+/// use positive::pos_or_panic;
+/// use rust_decimal_macros::dec;
+///
+/// let pricer = BatchBlackScholes::new(
+///     pos_or_panic!(100.0),
+///     pos_or_panic!(0.25),
+///     dec!(0.05),
+///     pos_or_panic!(0.01),
+/// )?;
+/// let strikes = [90.0, 95.0, 100.0, 105.0, 110.0];
+/// let volatilities = [0.24, 0.22, 0.20, 0.19, 0.18];
+/// let calls = pricer.price(&strikes, &volatilities, OptionStyle::Call)?;
+/// assert_eq!(calls.len(), 5);
+/// # Ok::<(), optionstratlib::error::PricingError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchBlackScholes {
+    spot: f64,
+    time_to_expiry: f64,
+    risk_free_rate: f64,
+    dividend_yield: f64,
+    ln_spot: f64,
+    sqrt_time: f64,
+    carry: f64,
+    spot_discount: f64,
+    strike_discount: f64,
+    days_per_year: f64,
+}
+
+impl BatchBlackScholes {
+    /// Creates a pricer for options on `spot` expiring in `time_to_expiry`
+    /// years.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the spot or the time to expiry is zero or
+    /// the rate is not representable as `f64`.
+    pub fn new(
+        spot: Positive,
+        time_to_expiry: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+    ) -> Result<Self, PricingError> {
+        if spot == Positive::ZERO {
+            return Err(PricingError::method_error(
+                METHOD,
+                "underlying price must be positive",
+            ));
+        }
+        if time_to_expiry == Positive::ZERO {
+            return Err(PricingError::method_error(
+                METHOD,
+                "time to expiry must be positive",
+            ));
+        }
+        let rate = risk_free_rate.to_f64().ok_or_else(|| {
+            PricingError::method_error(METHOD, "risk-free rate is not representable as f64")
+        })?;
+        let spot = spot.to_f64();
+        let time = time_to_expiry.to_f64();
+        let dividend = dividend_yield.to_f64();
+        Ok(Self {
+            spot,
+            time_to_expiry: time,
+            risk_free_rate: rate,
+            dividend_yield: dividend,
+            ln_spot: spot.ln(),
+            sqrt_time: time.sqrt(),
+            carry: (rate - dividend) * time,
+            spot_discount: spot * (-dividend * time).exp(),
+            strike_discount: (-rate * time).exp(),
+            days_per_year: day_count_days(&DayCount::default()),
+        })
+    }
+
+    /// Sets the day count theta is expressed with. `time_to_expiry` should
+    /// have been measured with the same convention.
+    pub fn with_day_count(mut self, day_count: &DayCount) -> Self {
+        self.days_per_year = day_count_days(day_count);
+        self
+    }
+
+    /// Creates a pricer from the shared market parameters and day count of
+    /// `option`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the option has expired or its parameters
+    /// are invalid for [`BatchBlackScholes::new`].
+    pub fn from_option(option: &Options) -> Result<Self, PricingError> {
+        Self::new(
+            option.underlying_price,
+            option.time_to_expiration()?,
+            option.risk_free_rate,
+            option.dividend_yield,
+        )
+        .map(|pricer| pricer.with_day_count(&option.day_count))
+    }
+
+    /// Underlying price shared by the batch.
+    pub fn spot(&self) -> f64 {
+        self.spot
+    }
+
+    /// Time to expiry in years shared by the batch.
+    pub fn time_to_expiry(&self) -> f64 {
+        self.time_to_expiry
+    }
+
+    /// Prices one option of `style` per strike and volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the slices have different lengths, a strike
+    /// is not strictly positive or a volatility is negative or not finite.
+    pub fn price(
+        &self,
+        strikes: &[f64],
+        volatilities: &[f64],
+        style: OptionStyle,
+    ) -> Result<Vec<f64>, PricingError> {
+        let mut prices = vec![0.0; strikes.len()];
+        self.price_into(strikes, volatilities, style, &mut prices)?;
+        Ok(prices)
+    }
+
+    /// Prices one option of `style` per strike and volatility into `prices`,
+    /// without allocating.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the slices have different lengths, a strike
+    /// is not strictly positive or a volatility is negative or not finite.
+    pub fn price_into(
+        &self,
+        strikes: &[f64],
+        volatilities: &[f64],
+        style: OptionStyle,
+        prices: &mut [f64],
+    ) -> Result<(), PricingError> {
+        validate_inputs(strikes, volatilities, prices.len())?;
+        self.for_each_lane(strikes, volatilities, |offset, len, calls, puts| {
+            let source = match style {
+                OptionStyle::Call => calls,
+                OptionStyle::Put => puts,
+            };
+            prices[offset..offset + len].copy_from_slice(&source[..len]);
+        });
+        Ok(())
+    }
+
+    /// Prices a call and a put per strike and volatility, sharing `d1`, `d2`
+    /// and the normal CDF evaluations between both.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the slices have different lengths, a strike
+    /// is not strictly positive or a volatility is negative or not finite.
+    pub fn price_calls_and_puts(
+        &self,
+        strikes: &[f64],
+        volatilities: &[f64],
+    ) -> Result<(Vec<f64>, Vec<f64>), PricingError> {
+        validate_inputs(strikes, volatilities, strikes.len())?;
+        let mut call_prices = vec![0.0; strikes.len()];
+        let mut put_prices = vec![0.0; strikes.len()];
+        self.for_each_lane(strikes, volatilities, |offset, len, calls, puts| {
+            call_prices[offset..offset + len].copy_from_slice(&calls[..len]);
+            put_prices[offset..offset + len].copy_from_slice(&puts[..len]);
+        });
+        Ok((call_prices, put_prices))
+    }
+
+    /// Prices European `options` sharing the market parameters of the
+    /// pricer, with the sign of their side like [`black_scholes`].
+    ///
+    /// [`black_scholes`]: crate::pricing::black_scholes
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if an option is not European or its
+    /// underlying price, expiry, rate or dividend yield differ from the
+    /// pricer's.
+    pub fn price_options(&self, options: &[Options]) -> Result<Vec<Decimal>, PricingError> {
+        let mut strikes = Vec::with_capacity(options.len());
+        let mut volatilities = Vec::with_capacity(options.len());
+        for option in options {
+            if option.option_type != OptionType::European {
+                return Err(PricingError::unsupported_option_type(
+                    &option.option_type.to_string(),
+                    METHOD,
+                ));
+            }
+            if !self.shares_market(option)? {
+                return Err(PricingError::method_error(
+                    METHOD,
+                    "options must share underlying price, expiry, rate and dividend yield",
+                ));
+            }
+            strikes.push(option.strike_price.to_f64());
+            volatilities.push(option.implied_volatility.to_f64());
+        }
+
+        let (calls, puts) = self.price_calls_and_puts(&strikes, &volatilities)?;
+        options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let price = match option.option_style {
+                    OptionStyle::Call => calls[i],
+                    OptionStyle::Put => puts[i],
+                };
+                let price = Decimal::from_f64(price).ok_or_else(|| {
+                    PricingError::method_error(METHOD, "price is not representable as Decimal")
+                })?;
+                Ok(match option.side {
+                    Side::Long => price,
+                    Side::Short => -price,
+                })
+            })
+            .collect()
+    }
+
+    fn shares_market(&self, option: &Options) -> Result<bool, PricingError> {
+        const TOLERANCE: f64 = 1e-12;
+        let close = |a: f64, b: f64| (a - b).abs() <= TOLERANCE * a.abs().max(1.0);
+        Ok(close(option.underlying_price.to_f64(), self.spot)
+            && close(option.time_to_expiration()?.to_f64(), self.time_to_expiry)
+            && close(
+                option.risk_free_rate.to_f64().unwrap_or(f64::NAN),
+                self.risk_free_rate,
+            )
+            && close(option.dividend_yield.to_f64(), self.dividend_yield))
+    }
+
+    /// Runs the kernel over blocks of [`BATCH_LANES`] options, padding the
+    /// last block, and hands `(offset, len, calls, puts)` to `sink`.
+    fn for_each_lane<F>(&self, strikes: &[f64], volatilities: &[f64], mut sink: F)
+    where
+        F: FnMut(usize, usize, &Lane, &Lane),
+    {
+        let mut offset = 0;
+        for (strike, volatility) in strikes
+            .chunks_exact(BATCH_LANES)
+            .zip(volatilities.chunks_exact(BATCH_LANES))
+        {
+            let (calls, puts) = self.kernel(to_lane(strike, 1.0), to_lane(volatility, 1.0));
+            sink(offset, BATCH_LANES, &calls, &puts);
+            offset += BATCH_LANES;
+        }
+        let len = strikes.len() - offset;
+        if len > 0 {
+            let (calls, puts) = self.kernel(
+                to_lane(&strikes[offset..], self.spot),
+                to_lane(&volatilities[offset..], 1.0),
+            );
+            sink(offset, len, &calls, &puts);
+        }
+    }
+
+    /// Computes the Greeks of a call and a put per strike and volatility,
+    /// sharing `d1`, `d2`, the normal CDF and density evaluations and the
+    /// discount factors.
+    ///
+    /// Greeks are per unit of a long position and follow the conventions of
+    /// [`crate::greeks::Greeks`]: vega and rho per 1% change, theta per day of
+    /// the day count of the pricer. Options with zero volatility have zero
+    /// gamma and vega.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the slices have different lengths, a strike
+    /// is not strictly positive or a volatility is negative or not finite.
+    pub fn greeks(
+        &self,
+        strikes: &[f64],
+        volatilities: &[f64],
+    ) -> Result<BatchGreeks, PricingError> {
+        validate_inputs(strikes, volatilities, strikes.len())?;
+        let mut greeks = BatchGreeks::with_len(strikes.len());
+        let mut offset = 0;
+        while offset < strikes.len() {
+            let len = BATCH_LANES.min(strikes.len() - offset);
+            let lane = self.greeks_kernel(
+                to_lane(&strikes[offset..offset + len], self.spot),
+                to_lane(&volatilities[offset..offset + len], 1.0),
+            );
+            greeks.store(offset, len, &lane);
+            offset += len;
+        }
+        Ok(greeks)
+    }
+
+    #[inline(always)]
+    fn d_values(&self, strikes: Lane, volatilities: Lane) -> (Lane, Lane, Lane) {
+        let ln_strikes = lane_ln(strikes);
+        let mut d1 = [0.0; BATCH_LANES];
+        let mut d2 = [0.0; BATCH_LANES];
+        let mut vol_sqrt_time = [0.0; BATCH_LANES];
+        for i in 0..BATCH_LANES {
+            // A zero volatility collapses d1 and d2 to ±∞, the intrinsic
+            // value of the forward, or to 0 exactly at the money.
+            vol_sqrt_time[i] = (volatilities[i] * self.sqrt_time).max(f64::MIN_POSITIVE);
+            d1[i] = (self.ln_spot - ln_strikes[i] + self.carry) / vol_sqrt_time[i]
+                + 0.5 * vol_sqrt_time[i];
+            d2[i] = d1[i] - vol_sqrt_time[i];
+        }
+        (d1, d2, vol_sqrt_time)
+    }
+
+    #[inline(always)]
+    fn kernel(&self, strikes: Lane, volatilities: Lane) -> (Lane, Lane) {
+        let (d1, d2, _) = self.d_values(strikes, volatilities);
+        let (n_d1, n_minus_d1) = lane_normal_cdf(d1);
+        let (n_d2, n_minus_d2) = lane_normal_cdf(d2);
+
+        let mut calls = [0.0; BATCH_LANES];
+        let mut puts = [0.0; BATCH_LANES];
+        for i in 0..BATCH_LANES {
+            let strike_discounted = strikes[i] * self.strike_discount;
+            calls[i] = (self.spot_discount * n_d1[i] - strike_discounted * n_d2[i]).max(0.0);
+            puts[i] =
+                (strike_discounted * n_minus_d2[i] - self.spot_discount * n_minus_d1[i]).max(0.0);
+        }
+        (calls, puts)
+    }
+
+    #[inline(always)]
+    fn greeks_kernel(&self, strikes: Lane, volatilities: Lane) -> [Lane; 8] {
+        const FRAC_1_SQRT_2PI: f64 = 0.398_942_280_401_432_7;
+        let (d1, d2, vol_sqrt_time) = self.d_values(strikes, volatilities);
+        let (n_d1, n_minus_d1) = lane_normal_cdf(d1);
+        let (n_d2, n_minus_d2) = lane_normal_cdf(d2);
+        let mut half_d1_squared = [0.0; BATCH_LANES];
+        for i in 0..BATCH_LANES {
+            half_d1_squared[i] = -0.5 * d1[i] * d1[i];
+        }
+        let density = lane_exp(half_d1_squared);
+
+        let dividend_discount = self.spot_discount / self.spot;
+        let mut lanes = [[0.0; BATCH_LANES]; 8];
+        for i in 0..BATCH_LANES {
+            let has_volatility = volatilities[i] > 0.0;
+            let pdf = FRAC_1_SQRT_2PI * density[i];
+            let strike_discounted = strikes[i] * self.strike_discount;
+            let decay = -self.spot_discount * pdf * vol_sqrt_time[i] / (2.0 * self.time_to_expiry);
+            let call_carry = self.dividend_yield * self.spot_discount * n_d1[i]
+                - self.risk_free_rate * strike_discounted * n_d2[i];
+            let put_carry = self.risk_free_rate * strike_discounted * n_minus_d2[i]
+                - self.dividend_yield * self.spot_discount * n_minus_d1[i];
+
+            lanes[0][i] = dividend_discount * n_d1[i];
+            lanes[1][i] = -dividend_discount * n_minus_d1[i];
+            lanes[2][i] = select(
+                has_volatility,
+                self.spot_discount * pdf / (self.spot * self.spot * vol_sqrt_time[i]),
+                0.0,
+            );
+            lanes[3][i] = select(
+                has_volatility,
+                self.spot_discount * pdf * self.sqrt_time / 100.0,
+                0.0,
+            );
+            lanes[4][i] = (select(has_volatility, decay, 0.0) + call_carry) / self.days_per_year;
+            lanes[5][i] = (select(has_volatility, decay, 0.0) + put_carry) / self.days_per_year;
+            lanes[6][i] = strike_discounted * self.time_to_expiry * n_d2[i] / 100.0;
+            lanes[7][i] = -strike_discounted * self.time_to_expiry * n_minus_d2[i] / 100.0;
+        }
+        lanes
+    }
+}
+
+/// Call and put Greeks of a batch, one entry per input strike.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchGreeks {
+    /// Call deltas.
+    pub call_delta: Vec<f64>,
+    /// Put deltas.
+    pub put_delta: Vec<f64>,
+    /// Gammas, equal for calls and puts.
+    pub gamma: Vec<f64>,
+    /// Vegas per 1% volatility change, equal for calls and puts.
+    pub vega: Vec<f64>,
+    /// Call thetas per day.
+    pub call_theta: Vec<f64>,
+    /// Put thetas per day.
+    pub put_theta: Vec<f64>,
+    /// Call rhos per 1% rate change.
+    pub call_rho: Vec<f64>,
+    /// Put rhos per 1% rate change.
+    pub put_rho: Vec<f64>,
+}
+
+impl BatchGreeks {
+    fn with_len(len: usize) -> Self {
+        Self {
+            call_delta: vec![0.0; len],
+            put_delta: vec![0.0; len],
+            gamma: vec![0.0; len],
+            vega: vec![0.0; len],
+            call_theta: vec![0.0; len],
+            put_theta: vec![0.0; len],
+            call_rho: vec![0.0; len],
+            put_rho: vec![0.0; len],
+        }
+    }
+
+    /// Number of strikes in the batch.
+    pub fn len(&self) -> usize {
+        self.gamma.len()
+    }
+
+    /// Returns `true` if the batch has no strikes.
+    pub fn is_empty(&self) -> bool {
+        self.gamma.is_empty()
+    }
+
+    fn store(&mut self, offset: usize, len: usize, lanes: &[Lane; 8]) {
+        let columns = [
+            &mut self.call_delta,
+            &mut self.put_delta,
+            &mut self.gamma,
+            &mut self.vega,
+            &mut self.call_theta,
+            &mut self.put_theta,
+            &mut self.call_rho,
+            &mut self.put_rho,
+        ];
+        for (column, lane) in columns.into_iter().zip(lanes) {
+            column[offset..offset + len].copy_from_slice(&lane[..len]);
+        }
+    }
+}
+
+fn day_count_days(day_count: &DayCount) -> f64 {
+    day_count.days_per_year().to_f64().unwrap_or(f64::NAN)
+}
+
+fn validate_inputs(
+    strikes: &[f64],
+    volatilities: &[f64],
+    output_len: usize,
+) -> Result<(), PricingError> {
+    if strikes.len() != volatilities.len() || strikes.len() != output_len {
+        return Err(PricingError::method_error(
+            METHOD,
+            "strikes, volatilities and output must have the same length",
+        ));
+    }
+    if !strikes.iter().all(|x| x.is_finite() && *x > 0.0) {
+        return Err(PricingError::method_error(
+            METHOD,
+            "strikes must be positive and finite",
+        ));
+    }
+    if !volatilities.iter().all(|x| x.is_finite() && *x >= 0.0) {
+        return Err(PricingError::method_error(
+            METHOD,
+            "volatilities must be non-negative and finite",
+        ));
+    }
+    Ok(())
+}
+
+#[inline(always)]
+fn to_lane(values: &[f64], padding: f64) -> Lane {
+    let mut lane = [padding; BATCH_LANES];
+    lane[..values.len()].copy_from_slice(values);
+    lane
+}
+
+#[inline(always)]
+fn select(condition: bool, if_true: f64, if_false: f64) -> f64 {
+    if condition { if_true } else { if_false }
+}
+
+/// `exp` by Cody-Waite range reduction and a degree-13 Taylor polynomial.
+#[inline(always)]
+fn lane_exp(x: Lane) -> Lane {
+    const LN2_HI: f64 = 6.931_471_803_691_238e-1;
+    const LN2_LO: f64 = 1.908_214_929_270_587_7e-10;
+    let mut result = [0.0; BATCH_LANES];
+    for i in 0..BATCH_LANES {
+        let x = x[i].clamp(-708.0, 709.0);
+        let n = (x * std::f64::consts::LOG2_E).round();
+        let r = x - n * LN2_HI - n * LN2_LO;
+        let mut p = 1.0 / 6_227_020_800.0;
+        for k in (1..13).rev() {
+            p = p * r + 1.0 / FACTORIALS[k];
+        }
+        p = p * r + 1.0;
+        let scale = f64::from_bits(((n as i64 + 1023) as u64) << 52);
+        result[i] = p * scale;
+    }
+    result
+}
+
+const FACTORIALS: [f64; 13] = [
+    1.0,
+    1.0,
+    2.0,
+    6.0,
+    24.0,
+    120.0,
+    720.0,
+    5_040.0,
+    40_320.0,
+    362_880.0,
+    3_628_800.0,
+    39_916_800.0,
+    479_001_600.0,
+];
+
+/// `ln` of positive normal numbers from the exponent bits and an `atanh`
+/// series on the mantissa reduced to `[√½, √2)`.
+#[inline(always)]
+fn lane_ln(x: Lane) -> Lane {
+    const MANTISSA_MASK: u64 = 0x000F_FFFF_FFFF_FFFF;
+    const ONE_BITS: u64 = 0x3FF0_0000_0000_0000;
+    let mut result = [0.0; BATCH_LANES];
+    for i in 0..BATCH_LANES {
+        let bits = x[i].to_bits();
+        let exponent = ((bits >> 52) & 0x7FF) as i64 - 1023;
+        let mantissa = f64::from_bits((bits & MANTISSA_MASK) | ONE_BITS);
+        let high = mantissa > std::f64::consts::SQRT_2;
+        let mantissa = select(high, 0.5 * mantissa, mantissa);
+        let exponent = exponent as f64 + select(high, 1.0, 0.0);
+
+        let s = (mantissa - 1.0) / (mantissa + 1.0);
+        let s2 = s * s;
+        let mut series = 1.0 / 21.0;
+        for k in (0..10).rev() {
+            series = series * s2 + 1.0 / (2 * k + 1) as f64;
+        }
+        result[i] = exponent * std::f64::consts::LN_2 + 2.0 * s * series;
+    }
+    result
+}
+
+/// Chebyshev coefficients of `erfc` on `[0, ∞)` (Press et al., Numerical
+/// Recipes, 3rd ed., §6.2.2).
+const ERFC_COEFFICIENTS: [f64; 28] = [
+    -1.302_653_719_781_709_4,
+    6.419_697_923_564_902e-1,
+    1.947_647_320_418_583_6e-2,
+    -9.561_514_786_808_63e-3,
+    -9.465_953_444_820_36e-4,
+    3.668_394_978_527_61e-4,
+    4.252_332_480_690_7e-5,
+    -2.027_857_811_253_4e-5,
+    -1.624_290_004_647e-6,
+    1.303_655_835_580e-6,
+    1.562_644_172_2e-8,
+    -8.523_809_591_5e-8,
+    6.529_054_439e-9,
+    5.059_343_495e-9,
+    -9.913_641_56e-10,
+    -2.273_651_22e-10,
+    9.646_791_1e-11,
+    2.394_038e-12,
+    -6.886_027e-12,
+    8.944_87e-13,
+    3.130_92e-13,
+    -1.127_08e-13,
+    3.81e-16,
+    7.106e-15,
+    -1.523e-15,
+    -9.4e-17,
+    1.21e-16,
+    -2.8e-17,
+];
+
+/// Returns `(N(x), N(-x))`, each computed from `erfc(|x| / √2)` so the
+/// smaller tail keeps full relative precision.
+#[inline(always)]
+fn lane_normal_cdf(x: Lane) -> (Lane, Lane) {
+    let mut exponent = [0.0; BATCH_LANES];
+    let mut factor = [0.0; BATCH_LANES];
+    for i in 0..BATCH_LANES {
+        let z = x[i].abs() * std::f64::consts::FRAC_1_SQRT_2;
+        let t = 2.0 / (2.0 + z);
+        let ty = 4.0 * t - 2.0;
+        let mut d = 0.0;
+        let mut dd = 0.0;
+        for coefficient in ERFC_COEFFICIENTS[1..].iter().rev() {
+            let previous = d;
+            d = ty * d - dd + coefficient;
+            dd = previous;
+        }
+        exponent[i] = -z * z + 0.5 * (ERFC_COEFFICIENTS[0] + ty * d) - dd;
+        factor[i] = t;
+    }
+    let exp = lane_exp(exponent);
+
+    let mut upper = [0.0; BATCH_LANES];
+    let mut lower = [0.0; BATCH_LANES];
+    for i in 0..BATCH_LANES {
+        let small_tail = 0.5 * factor[i] * exp[i];
+        let large_tail = 1.0 - small_tail;
+        let positive = x[i] >= 0.0;
+        upper[i] = select(positive, large_tail, small_tail);
+        lower[i] = select(positive, small_tail, large_tail);
+    }
+    (upper, lower)
+}
+
+#[cfg(test)]
+mod tests_batch {
+    use super::*;
+    use crate::model::types::{DayCount, SettlementType};
+    use crate::pricing::black_scholes;
+    use expiration_date::ExpirationDate;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn option(strike: f64, volatility: f64, style: OptionStyle, side: Side) -> Options {
+        Options {
+            option_type: OptionType::European,
+            side,
+            underlying_symbol: "TEST".to_string(),
+            strike_price: Positive::new(strike).unwrap(),
+            expiration_date: ExpirationDate::Days(pos_or_panic!(45.0)),
+            implied_volatility: Positive::new(volatility).unwrap(),
+            quantity: Positive::ONE,
+            underlying_price: Positive::HUNDRED,
+            risk_free_rate: dec!(0.04),
+            option_style: style,
+            dividend_yield: pos_or_panic!(0.01),
+            exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
+        }
+    }
+
+    #[test]
+    fn test_lane_math_matches_std() {
+        let xs = [-30.0, -0.7, 1e-3, 12.5];
+        let exp = lane_exp(xs);
+        for (x, e) in xs.iter().zip(exp) {
+            assert!((e - x.exp()).abs() <= 1e-14 * x.exp());
+        }
+        let ys = [1e-6, 0.9, 1.5, 12_345.678];
+        let ln = lane_ln(ys);
+        for (y, l) in ys.iter().zip(ln) {
+            assert!((l - y.ln()).abs() <= 1e-14 * y.ln().abs().max(1.0));
+        }
+        // Reference values computed with 30-digit arithmetic.
+        let expected = [
+            6.220_960_574_271_784e-16,
+            9.680_048_458_561_033e-2,
+            0.5,
+            9.965_330_261_969_593e-1,
+        ];
+        let (upper, lower) = lane_normal_cdf([-8.0, -1.3, 0.0, 2.7]);
+        for ((u, l), e) in upper.iter().zip(lower).zip(expected) {
+            assert!((u / e - 1.0).abs() <= 1e-14);
+            assert!((l - (1.0 - e)).abs() <= 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_batch_matches_black_scholes() {
+        let reference = option(100.0, 0.2, OptionStyle::Call, Side::Long);
+        let pricer = BatchBlackScholes::from_option(&reference).unwrap();
+        let strikes: Vec<f64> = (0..23).map(|i| 60.0 + 4.0 * i as f64).collect();
+        let volatilities: Vec<f64> = (0..23).map(|i| 0.12 + 0.01 * i as f64).collect();
+        let (calls, puts) = pricer
+            .price_calls_and_puts(&strikes, &volatilities)
+            .unwrap();
+
+        for (i, (strike, volatility)) in strikes.iter().zip(&volatilities).enumerate() {
+            for (style, batch) in [(OptionStyle::Call, calls[i]), (OptionStyle::Put, puts[i])] {
+                let expected = black_scholes(&option(*strike, *volatility, style, Side::Long))
+                    .unwrap()
+                    .to_f64()
+                    .unwrap();
+                assert!(
+                    (batch - expected).abs() < 1e-8,
+                    "{style:?} {strike}: {batch} vs {expected}"
+                );
+            }
+        }
+        assert_eq!(
+            pricer
+                .price(&strikes, &volatilities, OptionStyle::Put)
+                .unwrap(),
+            puts
+        );
+    }
+
+    #[test]
+    fn test_batch_greeks_match_greeks_equations() {
+        use crate::greeks::{delta, gamma, rho, theta, vega};
+        let mut reference = option(100.0, 0.2, OptionStyle::Call, Side::Long);
+        reference.dividend_yield = Positive::ZERO;
+        let pricer = BatchBlackScholes::from_option(&reference).unwrap();
+        let strikes = [80.0, 95.0, 100.0, 104.0, 130.0];
+        let volatilities = [0.35, 0.24, 0.2, 0.19, 0.3];
+        let greeks = pricer.greeks(&strikes, &volatilities).unwrap();
+        assert_eq!(greeks.len(), 5);
+
+        for (i, (strike, volatility)) in strikes.iter().zip(volatilities).enumerate() {
+            let mut call = option(*strike, volatility, OptionStyle::Call, Side::Long);
+            call.dividend_yield = Positive::ZERO;
+            let mut put = call.clone();
+            put.option_style = OptionStyle::Put;
+            let close =
+                |batch: f64, expected: Decimal| (batch - expected.to_f64().unwrap()).abs() < 1e-8;
+            assert!(close(greeks.call_delta[i], delta(&call).unwrap()));
+            assert!(close(greeks.put_delta[i], delta(&put).unwrap()));
+            assert!(close(greeks.gamma[i], gamma(&call).unwrap()));
+            assert!(close(greeks.vega[i], vega(&call).unwrap()));
+            assert!(close(greeks.call_theta[i], theta(&call).unwrap()));
+            assert!(close(greeks.put_theta[i], theta(&put).unwrap()));
+            assert!(close(greeks.call_rho[i], rho(&call).unwrap()));
+            assert!(close(greeks.put_rho[i], rho(&put).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_price_options_applies_side_and_validates() {
+        let options = vec![
+            option(95.0, 0.25, OptionStyle::Put, Side::Long),
+            option(105.0, 0.18, OptionStyle::Call, Side::Short),
+        ];
+        let pricer = BatchBlackScholes::from_option(&options[0]).unwrap();
+        let prices = pricer.price_options(&options).unwrap();
+        for (price, option) in prices.iter().zip(&options) {
+            let expected = black_scholes(option).unwrap();
+            assert!((price - expected).abs() < dec!(1e-8));
+        }
+        assert!(prices[1] < Decimal::ZERO);
+
+        let mut other_spot = options[1].clone();
+        other_spot.underlying_price = pos_or_panic!(101.0);
+        assert!(pricer.price_options(&[other_spot]).is_err());
+        assert!(
+            pricer
+                .price(&[100.0], &[0.2, 0.3], OptionStyle::Call)
+                .is_err()
+        );
+        assert!(pricer.price(&[100.0], &[-0.1], OptionStyle::Call).is_err());
+
+        let intrinsic = pricer.price(&[80.0], &[0.0], OptionStyle::Call).unwrap()[0];
+        let forward = 100.0 * (-0.01 * pricer.time_to_expiry()).exp()
+            - 80.0 * (-0.04 * pricer.time_to_expiry()).exp();
+        assert!((intrinsic - forward).abs() < 1e-12);
+    }
+}
@@ -77,8 +77,7 @@ fn cash_or_nothing_price(option: &Options, payout: Decimal) -> Result<Decimal, P
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .time_to_expiration()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -110,11 +109,11 @@ fn cash_or_nothing_price(option: &Options, payout: Decimal) -> Result<Decimal, P
 
     let price = match option.option_style {
         OptionStyle::Call => {
-            let n_d2 = big_n(d2_val).unwrap_or(Decimal::ZERO);
+            let n_d2 = big_n(d2_val)?;
             payout * discount * n_d2
         }
         OptionStyle::Put => {
-            let n_neg_d2 = big_n(-d2_val).unwrap_or(Decimal::ZERO);
+            let n_neg_d2 = big_n(-d2_val)?;
             payout * discount * n_neg_d2
         }
     };
@@ -137,8 +136,7 @@ fn asset_or_nothing_price(option: &Options) -> Result<Decimal, PricingError> {
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .time_to_expiration()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -168,11 +166,11 @@ fn asset_or_nothing_price(option: &Options) -> Result<Decimal, PricingError> {
 
     let price = match option.option_style {
         OptionStyle::Call => {
-            let n_d1 = big_n(d1_val).unwrap_or(Decimal::ZERO);
+            let n_d1 = big_n(d1_val)?;
             s.to_dec() * dividend_discount * n_d1
         }
         OptionStyle::Put => {
-            let n_neg_d1 = big_n(-d1_val).unwrap_or(Decimal::ZERO);
+            let n_neg_d1 = big_n(-d1_val)?;
             s.to_dec() * dividend_discount * n_neg_d1
         }
     };
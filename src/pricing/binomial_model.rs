@@ -1,6 +1,6 @@
 use crate::error::PricingError;
 use crate::model::types::{OptionStyle, OptionType, Side};
-use crate::pricing::payoff::{Payoff, PayoffInfo};
+use crate::pricing::payoff::{Payoff, PayoffInputs};
 use crate::pricing::utils::*;
 use crate::{d2f, f2d};
 use positive::{Positive, pos_or_panic};
@@ -89,18 +89,15 @@ pub struct BinomialPricingParams<'a> {
 /// - This model assumes that the underlying asset follows a multiplicative binomial process.
 /// - For American options, this model accounts for the possibility of early exercise.
 pub fn price_binomial(params: BinomialPricingParams) -> Result<Decimal, PricingError> {
-    let mut info = PayoffInfo {
-        spot: params.asset,
-        strike: params.strike,
-        style: *params.option_style,
-        side: *params.side,
-        spot_prices: None,
-        spot_min: None,
-        spot_max: None,
-    };
+    let mut inputs = PayoffInputs::new(
+        params.asset.to_f64(),
+        params.strike.to_f64(),
+        *params.option_style,
+        *params.side,
+    );
 
     if params.expiry == Decimal::ZERO {
-        let intrinsic_value = f2d!(params.option_type.payoff(&info));
+        let intrinsic_value = f2d!(params.option_type.payoff_f64(&inputs));
         return Ok(intrinsic_value);
     }
     if params.volatility == Decimal::ZERO {
@@ -113,9 +110,9 @@ pub fn price_binomial(params: BinomialPricingParams) -> Result<Decimal, PricingE
     let p = calculate_probability(params.int_rate, dt, d, u)?;
     let discount_factor = calculate_discount_factor(params.int_rate, dt)?;
 
-    let mut prices: Vec<Decimal> = (0..=params.no_steps)
-        .map(|i| calculate_option_price(params.clone(), u, d, i).unwrap())
-        .collect();
+    let mut prices = (0..=params.no_steps)
+        .map(|i| calculate_option_price(params.clone(), u, d, i))
+        .collect::<Result<Vec<Decimal>, _>>()?;
 
     for step in (0..params.no_steps).rev() {
         for i in 0..=step {
@@ -123,8 +120,8 @@ pub fn price_binomial(params: BinomialPricingParams) -> Result<Decimal, PricingE
             match params.option_type {
                 OptionType::American => {
                     let spot = params.asset * u.powi(i as i64) * d.powi((step - i) as i64);
-                    info.spot = spot;
-                    let intrinsic_value = f2d!(params.option_type.payoff(&info));
+                    inputs.spot = spot.to_f64();
+                    let intrinsic_value = f2d!(params.option_type.payoff_f64(&inputs));
                     prices[i] = option_value.max(intrinsic_value);
                 }
                 OptionType::Bermuda { exercise_dates } => {
@@ -137,8 +134,8 @@ pub fn price_binomial(params: BinomialPricingParams) -> Result<Decimal, PricingE
                     });
                     if is_exercise_date {
                         let spot = params.asset * u.powi(i as i64) * d.powi((step - i) as i64);
-                        info.spot = spot;
-                        let intrinsic_value = f2d!(params.option_type.payoff(&info));
+                        inputs.spot = spot.to_f64();
+                        let intrinsic_value = f2d!(params.option_type.payoff_f64(&inputs));
                         prices[i] = option_value.max(intrinsic_value);
                     } else {
                         prices[i] = option_value;
@@ -206,15 +203,12 @@ pub fn price_binomial(params: BinomialPricingParams) -> Result<Decimal, PricingE
 /// let (asset_tree, option_tree) = generate_binomial_tree(&params).unwrap();
 /// ```
 pub fn generate_binomial_tree(params: &BinomialPricingParams) -> BinomialTreeResult {
-    let mut info = PayoffInfo {
-        spot: params.asset,
-        strike: params.strike,
-        style: *params.option_style,
-        side: *params.side,
-        spot_prices: None,
-        spot_min: None,
-        spot_max: None,
-    };
+    let mut inputs = PayoffInputs::new(
+        params.asset.to_f64(),
+        params.strike.to_f64(),
+        *params.option_style,
+        *params.side,
+    );
 
     let dt = (params.expiry / f2d!(params.no_steps as f64)).to_dec();
     let up_factor = calculate_up_factor(params.volatility, dt)?;
@@ -237,8 +231,8 @@ pub fn generate_binomial_tree(params: &BinomialPricingParams) -> BinomialTreeRes
         .enumerate()
         .take(params.no_steps + 1)
     {
-        info.spot = Positive::new_decimal(*node_val)?;
-        option_tree[params.no_steps][node] = f2d!(params.option_type.payoff(&info));
+        inputs.spot = d2f!(*node_val);
+        option_tree[params.no_steps][node] = f2d!(params.option_type.payoff_f64(&inputs));
     }
 
     for step in (0..params.no_steps).rev() {
@@ -254,8 +248,8 @@ pub fn generate_binomial_tree(params: &BinomialPricingParams) -> BinomialTreeRes
                     if (step == 0) & (node_idx == 0) {
                         *node_val = node_value;
                     } else {
-                        info.spot = Positive::new_decimal(asset_tree[step][node_idx])?;
-                        let intrinsic_value = params.option_type.payoff(&info);
+                        inputs.spot = d2f!(asset_tree[step][node_idx]);
+                        let intrinsic_value = params.option_type.payoff_f64(&inputs);
                         let dec_node_val = d2f!(node_value);
                         *node_val = f2d!(intrinsic_value.max(dec_node_val));
                     }
@@ -269,8 +263,8 @@ pub fn generate_binomial_tree(params: &BinomialPricingParams) -> BinomialTreeRes
                         (time_at_step - t_dec).abs() < dt / Decimal::TWO
                     });
                     if is_exercise_date && !((step == 0) & (node_idx == 0)) {
-                        info.spot = Positive::new_decimal(asset_tree[step][node_idx])?;
-                        let intrinsic_value = params.option_type.payoff(&info);
+                        inputs.spot = d2f!(asset_tree[step][node_idx]);
+                        let intrinsic_value = params.option_type.payoff_f64(&inputs);
                         let dec_node_val = d2f!(node_value);
                         *node_val = f2d!(intrinsic_value.max(dec_node_val));
                     } else {
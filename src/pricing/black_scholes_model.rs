@@ -5,9 +5,11 @@
 ******************************************************************************/
 use crate::Options;
 use crate::error::PricingError;
-use crate::greeks::{big_n, calculate_d_values};
+use crate::greeks::{DRate, Intermediates, intermediates};
 use crate::model::types::{OptionStyle, OptionType, Side};
-use rust_decimal::{Decimal, MathematicalOps};
+use crate::pricing::numeric::Numeric;
+use positive::Positive;
+use rust_decimal::Decimal;
 use tracing::trace;
 
 /// Computes the price of an option using the Black-Scholes model.
@@ -58,9 +60,9 @@ use tracing::trace;
 /// - `d2 = d1 - σ * √T`
 ///
 pub fn black_scholes(option: &Options) -> Result<Decimal, PricingError> {
-    let (d1, d2, expiry_time) = calculate_d1_d2_and_time(option)?;
+    let values = intermediates(option, option.time_to_expiration()?, DRate::CostOfCarry)?;
     match option.option_type {
-        OptionType::European => calculate_european_option_price(option, d1, d2, expiry_time),
+        OptionType::European => calculate_european_option_price(option, &values),
         OptionType::American => Err(PricingError::unsupported_option_type(
             "American",
             "Black-Scholes",
@@ -84,6 +86,96 @@ pub fn black_scholes(option: &Options) -> Result<Decimal, PricingError> {
     }
 }
 
+/// Computes the Black-Scholes price of an option on the numeric backend `T`.
+///
+/// European options are priced entirely in `T`: the inputs are converted
+/// once and `d1`, `d2`, the discount factors and the price are computed with
+/// the backend arithmetic, so `f64` trades the exactness of [`black_scholes`]
+/// for speed. Other option types are priced by [`black_scholes`] and the
+/// result converted to `T`.
+///
+/// The price is signed by the side of the option, as in [`black_scholes`].
+///
+/// # Errors
+///
+/// Returns a `PricingError` if the option has expired, has a zero price,
+/// strike or volatility, or a backend operation overflows.
+///
+/// # Examples
+///
+/// ```rust
+/// use optionstratlib::pricing::{black_scholes, black_scholes_as};
+/// use optionstratlib::{ExpirationDate, OptionStyle, OptionType, Options, Side};
+/// use positive::{Positive, pos_or_panic};
+/// use rust_decimal::Decimal;
+/// use rust_decimal_macros::dec;
+///
+/// let option = Options::new(
+///     OptionType::European,
+///     Side::Long,
+///     "AAPL".to_string(),
+///     Positive::HUNDRED,
+///     ExpirationDate::Days(pos_or_panic!(30.0)),
+///     pos_or_panic!(0.2),
+///     Positive::ONE,
+///     pos_or_panic!(105.0),
+///     dec!(0.05),
+///     OptionStyle::Call,
+///     pos_or_panic!(0.01),
+///     None,
+/// );
+/// let fast: f64 = black_scholes_as(&option)?;
+/// let exact: Decimal = black_scholes_as(&option)?;
+/// assert!((exact - black_scholes(&option)?).abs() < dec!(1e-10));
+/// assert!((fast - exact.to_string().parse::<f64>().unwrap()).abs() < 1e-9);
+/// # Ok::<(), optionstratlib::error::PricingError>(())
+/// ```
+pub fn black_scholes_as<T: Numeric>(option: &Options) -> Result<T, PricingError> {
+    if !matches!(option.option_type, OptionType::European) {
+        return T::try_from_decimal(black_scholes(option)?);
+    }
+    if option.underlying_price == Positive::ZERO || option.strike_price == Positive::ZERO {
+        return Err(PricingError::method_error(
+            "Black-Scholes",
+            "underlying and strike prices must be positive",
+        ));
+    }
+
+    let t = T::from_positive(option.time_to_expiration()?);
+    let spot = T::from_positive(option.underlying_price);
+    let strike = T::from_positive(option.strike_price);
+    let rate = T::try_from_decimal(option.risk_free_rate)?;
+    let dividend = T::from_positive(option.dividend_yield);
+    let sigma = T::from_positive(option.implied_volatility);
+
+    let vol_sqrt_t = sigma * t.try_sqrt()?;
+    if vol_sqrt_t <= T::ZERO {
+        return Err(PricingError::method_error(
+            "Black-Scholes",
+            "volatility and time to expiry must be positive",
+        ));
+    }
+    let half = T::ONE / (T::ONE + T::ONE);
+    let d1 =
+        ((spot / strike).try_ln()? + (rate - dividend + half * sigma * sigma) * t) / vol_sqrt_t;
+    let d2 = d1 - vol_sqrt_t;
+    let spot_discounted = spot * (-(dividend * t)).try_exp()?;
+    let strike_discounted = strike * (-(rate * t)).try_exp()?;
+
+    let price = match option.option_style {
+        OptionStyle::Call => {
+            spot_discounted * d1.normal_cdf()? - strike_discounted * d2.normal_cdf()?
+        }
+        OptionStyle::Put => {
+            strike_discounted * (-d2).normal_cdf()? - spot_discounted * (-d1).normal_cdf()?
+        }
+    };
+    Ok(match option.side {
+        Side::Long => price,
+        Side::Short => -price,
+    })
+}
+
 /// Calculates the price of a European option.
 ///
 /// This function calculates the price of a European option based on the given parameters.
@@ -92,9 +184,7 @@ pub fn black_scholes(option: &Options) -> Result<Decimal, PricingError> {
 /// # Arguments
 ///
 /// * `option` - A reference to an `Options` struct that contains the options details (e.g., side, strike price, etc.).
-/// * `d1` - The d1 parameter used in the Black-Scholes model for pricing options.
-/// * `d2` - The d2 parameter used in the Black-Scholes model for pricing options.
-/// * `expiry_time` - The time remaining until the option's expiry, expressed in years.
+/// * `values` - The cached `d1`, `d2`, normal CDF values and discount factors of the option.
 ///
 /// # Returns
 ///
@@ -103,13 +193,11 @@ pub fn black_scholes(option: &Options) -> Result<Decimal, PricingError> {
 /// Note: This example uses placeholder values and the `Options` and `Side` structs should be defined accordingly in your codebase.
 fn calculate_european_option_price(
     option: &Options,
-    d1: Decimal,
-    d2: Decimal,
-    expiry_time: Decimal,
+    values: &Intermediates,
 ) -> Result<Decimal, PricingError> {
     match option.side {
-        Side::Long => calculate_long_position(option, d1, d2, expiry_time),
-        Side::Short => Ok(-calculate_long_position(option, d1, d2, expiry_time)?),
+        Side::Long => calculate_long_position(option, values),
+        Side::Short => Ok(-calculate_long_position(option, values)?),
     }
 }
 
@@ -118,9 +206,7 @@ fn calculate_european_option_price(
 /// # Arguments
 ///
 /// * `option` - A reference to an `Options` struct which contains the details of the option.
-/// * `d1` - A floating-point value representing the first parameter (typically related to the Black-Scholes model).
-/// * `d2` - A floating-point value representing the second parameter (typically related to the Black-Scholes model).
-/// * `expiry_time` - A floating-point value representing the time to expiry of the option.
+/// * `values` - The cached `d1`, `d2`, normal CDF values and discount factors of the option.
 ///
 /// # Returns
 ///
@@ -129,68 +215,35 @@ fn calculate_european_option_price(
 /// The function matches on the style of the option (Call or Put) and calls the respective price calculation function.
 fn calculate_long_position(
     option: &Options,
-    d1: Decimal,
-    d2: Decimal,
-    expiry_time: Decimal,
+    values: &Intermediates,
 ) -> Result<Decimal, PricingError> {
     match option.option_style {
-        OptionStyle::Call => calculate_call_option_price(option, d1, d2, expiry_time),
-        OptionStyle::Put => calculate_put_option_price(option, d1, d2, expiry_time),
+        OptionStyle::Call => calculate_call_option_price(option, values),
+        OptionStyle::Put => calculate_put_option_price(option, values),
     }
 }
 
-/// Calculates the d1 and d2 values along with the time to expiry for an option.
-///
-/// # Parameters:
-/// - `option`: A reference to an instance of `Options` that contains the option details.
-/// - `time_to_expiry`: An optional `f64` value representing the already known time to expiry.
-///   If not provided, it will be calculated based on the current date and the option's expiration date.
-///
-/// # Returns:
-/// A tuple containing:
-/// - `d1`: The first value computed based on the option's details and time to expiry.
-/// - `d2`: The second value computed based on the option's details and time to expiry.
-/// - `time_to_expiry`: The calculated or given time to expiry in years.
-///
-fn calculate_d1_d2_and_time(option: &Options) -> Result<(Decimal, Decimal, Decimal), PricingError> {
-    let calculated_time_to_expiry: Decimal = option.time_to_expiration()?.to_dec();
-    let (d1, d2) = calculate_d_values(option)?;
-    Ok((d1, d2, calculated_time_to_expiry))
-}
-
 /// Calculates the price of a call option using the Black-Scholes formula.
 ///
 /// # Parameters
 /// - `option`: A reference to an `Options` struct containing the details of the option.
-/// - `d1`: The d1 parameter calculated from the Black-Scholes model.
-/// - `d2`: The d2 parameter calculated from the Black-Scholes model.
-/// - `t`: The time to expiration in years.
+/// - `values`: The cached `d1`, `d2`, normal CDF values and discount factors of the option.
 ///
 /// # Returns
 /// The price of the call option.
 ///
 fn calculate_call_option_price(
     option: &Options,
-    d1: Decimal,
-    d2: Decimal,
-    t: Decimal,
+    values: &Intermediates,
 ) -> Result<Decimal, PricingError> {
-    let big_n_d1 = big_n(d1)?;
-    let big_n_d2 = big_n(d2)?;
-
     // e^(−qT) * S * N(d1) − e^(−rT) * K * N(d2)
-    let s_discounted =
-        option.underlying_price.to_dec() * (-option.dividend_yield.to_dec() * t).exp();
-    let k_discounted = (-option.risk_free_rate * t).exp() * option.strike_price.to_dec();
+    let s_discounted = option.underlying_price.to_dec() * values.dividend_discount;
+    let k_discounted = values.rate_discount * option.strike_price.to_dec();
 
-    let result = s_discounted * big_n_d1 - k_discounted * big_n_d2;
+    let result = s_discounted * values.big_n_d1 - k_discounted * values.big_n_d2;
     trace!(
         "Call Option Price: {} - {} * {} * {} = {}",
-        option.underlying_price,
-        option.strike_price,
-        (-option.risk_free_rate * t).exp(),
-        big_n_d2,
-        result
+        option.underlying_price, option.strike_price, values.rate_discount, values.big_n_d2, result
     );
     Ok(result)
 }
@@ -201,9 +254,7 @@ fn calculate_call_option_price(
 ///
 /// - `option`: A reference to an `Options` struct which contains the details of the option such
 ///   as strike price, risk-free rate, and underlying asset price.
-/// - `d1`: A `f64` value representing the `d1` parameter used in the Black-Scholes formula.
-/// - `d2`: A `f64` value representing the `d2` parameter used in the Black-Scholes formula.
-/// - `t`: A `f64` value representing the time to maturity, in years.
+/// - `values`: The cached `d1`, `d2`, normal CDF values and discount factors of the option.
 ///
 /// # Returns
 ///
@@ -226,21 +277,14 @@ fn calculate_call_option_price(
 ///
 fn calculate_put_option_price(
     option: &Options,
-    d1: Decimal,
-    d2: Decimal,
-    t: Decimal,
+    values: &Intermediates,
 ) -> Result<Decimal, PricingError> {
-    // N(–d1) and N(–d2)
-    let big_n_neg_d1 = big_n(-d1)?;
-    let big_n_neg_d2 = big_n(-d2)?;
-
     // Discount factors
-    let s_discounted =
-        option.underlying_price.to_dec() * (-option.dividend_yield.to_dec() * t).exp(); // e^(−qT)·S
-    let k_discounted = option.strike_price.to_dec() * (-option.risk_free_rate * t).exp(); // e^(−rT)·K
+    let s_discounted = option.underlying_price.to_dec() * values.dividend_discount; // e^(−qT)·S
+    let k_discounted = option.strike_price.to_dec() * values.rate_discount; // e^(−rT)·K
 
     // P = K e^(−rT) N(−d2) − S e^(−qT) N(−d1)
-    let result = k_discounted * big_n_neg_d2 - s_discounted * big_n_neg_d1;
+    let result = k_discounted * values.big_n_minus_d2 - s_discounted * values.big_n_minus_d1;
 
     Ok(result)
 }
@@ -302,8 +346,8 @@ pub trait BlackScholes {
 #[cfg(test)]
 mod tests_black_scholes {
     use super::*;
-    use crate::greeks::{d1, d2};
-    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::greeks::{big_n, d1, d2};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
     use crate::{ExpirationDate, Options, assert_decimal_eq};
     use positive::constants::DAYS_IN_A_YEAR;
     use positive::{Positive, assert_pos_relative_eq, pos_or_panic};
@@ -323,6 +367,8 @@ mod tests_black_scholes {
             quantity: Positive::ONE,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
@@ -341,6 +387,8 @@ mod tests_black_scholes {
             dividend_yield: Positive::ZERO,
 
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
@@ -358,6 +406,8 @@ mod tests_black_scholes {
             quantity: Positive::ZERO,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
@@ -947,6 +997,7 @@ mod tests_black_scholes_bis {
     use crate::model::types::{OptionStyle, Side};
     use crate::{ExpirationDate, assert_decimal_eq};
     use positive::{Positive, pos_or_panic};
+    use rust_decimal::MathematicalOps;
     use rust_decimal_macros::dec;
 
     fn create_base_option(side: Side, style: OptionStyle) -> Options {
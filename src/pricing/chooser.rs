@@ -24,6 +24,7 @@
 //! - y2 = y1 - σ√t
 
 use crate::Options;
+use crate::d2f;
 use crate::error::PricingError;
 use crate::greeks::{big_n, d1, d2};
 use crate::model::types::OptionType;
@@ -61,12 +62,17 @@ fn simple_chooser_price(option: &Options, choice_date_days: f64) -> Result<Decim
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t_big = option
-        .expiration_date
-        .get_years()
+        .time_to_expiration()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
-    // Convert choice_date from days to years
-    let t_choice = Positive::new(choice_date_days / 365.0).unwrap_or(Positive::ZERO);
+    // Convert choice_date from days to years with the day count of the expiration
+    let days_per_year = d2f!(option.day_count.days_per_year());
+    let t_choice = Positive::new(choice_date_days / days_per_year).map_err(|_| {
+        PricingError::method_error(
+            "Chooser",
+            "choice date must be a non-negative number of days",
+        )
+    })?;
 
     // Validation: choice date must be before expiration
     if t_choice >= t_big {
@@ -93,8 +99,7 @@ fn simple_chooser_price(option: &Options, choice_date_days: f64) -> Result<Decim
     let b = r - q;
     let t_big_dec = t_big.to_dec();
     let t_choice_dec = t_choice.to_dec();
-    let _sqrt_t_big = t_big_dec.sqrt().unwrap_or(Decimal::ZERO);
-    let sqrt_t_choice = t_choice_dec.sqrt().unwrap_or(dec!(0.001));
+    let sqrt_t_choice = t_choice.sqrt().to_dec();
 
     // Standard BS d-values for the final expiration T
     let d1_val = d1(s, k, b, t_big, sigma)
@@ -110,10 +115,10 @@ fn simple_chooser_price(option: &Options, choice_date_days: f64) -> Result<Decim
     let y2 = y1 - sigma.to_dec() * sqrt_t_choice;
 
     // Get cumulative normal values
-    let n_d1 = big_n(d1_val).unwrap_or(Decimal::ZERO);
-    let n_d2 = big_n(d2_val).unwrap_or(Decimal::ZERO);
-    let n_neg_y1 = big_n(-y1).unwrap_or(Decimal::ZERO);
-    let n_neg_y2 = big_n(-y2).unwrap_or(Decimal::ZERO);
+    let n_d1 = big_n(d1_val)?;
+    let n_d2 = big_n(d2_val)?;
+    let n_neg_y1 = big_n(-y1)?;
+    let n_neg_y2 = big_n(-y2)?;
 
     // Discount factors
     let dividend_discount_t = (-q * t_big_dec).exp();
@@ -141,8 +146,7 @@ fn price_at_choice_equals_expiry(option: &Options) -> Result<Decimal, PricingErr
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .time_to_expiration()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -158,10 +162,10 @@ fn price_at_choice_equals_expiry(option: &Options) -> Result<Decimal, PricingErr
     let d2_val = d2(s, k, b, t, sigma)
         .map_err(|e: crate::error::GreeksError| PricingError::other(&e.to_string()))?;
 
-    let n_d1 = big_n(d1_val).unwrap_or(Decimal::ZERO);
-    let n_d2 = big_n(d2_val).unwrap_or(Decimal::ZERO);
-    let n_neg_d1 = big_n(-d1_val).unwrap_or(Decimal::ZERO);
-    let n_neg_d2 = big_n(-d2_val).unwrap_or(Decimal::ZERO);
+    let n_d1 = big_n(d1_val)?;
+    let n_d2 = big_n(d2_val)?;
+    let n_neg_d1 = big_n(-d1_val)?;
+    let n_neg_d2 = big_n(-d2_val)?;
 
     let dividend_discount = (-q * t).exp();
     let discount = (-r * t).exp();
@@ -224,6 +228,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_choice_date_uses_day_count() {
+        // 180 of 360 days is the same time to expiration as 182.5 of 365.
+        let mut act360 = create_chooser_option(45.0 * 360.0 / 365.0);
+        act360.expiration_date = ExpirationDate::Days(pos_or_panic!(180.0));
+        act360.day_count = crate::model::types::DayCount::Act360;
+        let act365 = create_chooser_option(45.0);
+        assert_decimal_eq!(
+            chooser_black_scholes(&act360).unwrap(),
+            chooser_black_scholes(&act365).unwrap(),
+            dec!(1e-8)
+        );
+    }
+
     #[test]
     fn test_chooser_more_valuable_than_call() {
         let chooser = create_chooser_option(45.0);
@@ -300,6 +318,12 @@ mod tests {
         assert!(price > Decimal::ZERO, "Late choice date price: {}", price);
     }
 
+    #[test]
+    fn test_negative_choice_date_is_an_error() {
+        let option = create_chooser_option(-5.0);
+        assert!(chooser_black_scholes(&option).is_err());
+    }
+
     #[test]
     fn test_choice_at_expiry() {
         // Choice at expiration = straddle
@@ -22,7 +22,9 @@
 //! assuming S=1 at the start of each period effectively.
 
 use crate::Options;
+use crate::d2f;
 use crate::error::PricingError;
+use crate::f2d;
 use crate::greeks::big_n;
 use crate::model::types::OptionType;
 use num_traits::Inv;
@@ -65,15 +67,15 @@ fn price_cliquet(option: &Options, reset_dates: &[f64]) -> Result<Decimal, Prici
 
     // Total expiration in years
     let t_total = option
-        .expiration_date
-        .get_years()
+        .time_to_expiration()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
-    // Convert reset dates from days to years
+    // Convert reset dates from days to years with the day count of the expiration
     let t_total_f = t_total.to_f64();
+    let days_per_year = d2f!(option.day_count.days_per_year());
     let mut reset_times_years = vec![0.0]; // Start at t=0
     for &d in &dates {
-        let t = d / 365.0;
+        let t = d / days_per_year;
         if t > 0.0 && t < t_total_f {
             reset_times_years.push(t);
         }
@@ -123,8 +125,8 @@ fn price_period(
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility.to_dec();
 
-    let dt_dec = Decimal::from_f64(dt).unwrap();
-    let t_start_dec = Decimal::from_f64(t_start).unwrap();
+    let dt_dec = f2d!(dt);
+    let t_start_dec = f2d!(t_start);
 
     // S_0 * e^(-q * t_start) is the present value of the expected S_{t_prev}
     let s_prev_pv = s0 * (-q * t_start_dec).exp();
@@ -231,16 +233,13 @@ mod tests {
                 rainbow_second_asset_price: None,
                 rainbow_second_asset_volatility: None,
                 rainbow_second_asset_dividend: None,
-                rainbow_correlation: None,
                 spread_second_asset_volatility: None,
                 spread_second_asset_dividend: None,
-                spread_correlation: None,
                 quanto_fx_volatility: None,
-                quanto_fx_correlation: None,
                 quanto_foreign_rate: None,
                 exchange_second_asset_volatility: None,
                 exchange_second_asset_dividend: None,
-                exchange_correlation: None,
+                correlation: None,
             }),
         )
     }
@@ -72,8 +72,8 @@ pub fn exchange_black_scholes(option: &Options) -> Result<Decimal, PricingError>
         .unwrap_or(positive::Positive::ZERO);
 
     let rho = params
-        .exchange_correlation
-        .ok_or_else(|| PricingError::other("Missing exchange_correlation"))?;
+        .second_asset_correlation()
+        .ok_or_else(|| PricingError::other("Missing correlation"))?;
 
     if rho < dec!(-1.0) || rho > dec!(1.0) {
         return Err(PricingError::other("Correlation must be between -1 and 1"));
@@ -83,7 +83,7 @@ pub fn exchange_black_scholes(option: &Options) -> Result<Decimal, PricingError>
     let s2 = second_asset_price;
     let q1 = Decimal::from(option.dividend_yield);
     let sigma1 = Decimal::from(option.implied_volatility);
-    let t = Decimal::from(option.expiration_date.get_years()?);
+    let t = Decimal::from(option.time_to_expiration()?);
 
     let price = margrabe_formula(
         s1,
@@ -164,6 +164,7 @@ fn apply_side(price: Decimal, option: &Options) -> Decimal {
 mod tests {
     use super::*;
     use crate::ExpirationDate;
+    use crate::model::CorrelationMatrix;
     use crate::model::option::ExoticParams;
     use crate::model::types::OptionStyle;
     use positive::{Positive, pos_or_panic};
@@ -195,16 +196,13 @@ mod tests {
                 rainbow_second_asset_price: None,
                 rainbow_second_asset_volatility: None,
                 rainbow_second_asset_dividend: None,
-                rainbow_correlation: None,
                 spread_second_asset_volatility: None,
                 spread_second_asset_dividend: None,
-                spread_correlation: None,
                 quanto_fx_volatility: None,
-                quanto_fx_correlation: None,
                 quanto_foreign_rate: None,
                 exchange_second_asset_volatility: Some(pos_or_panic!(0.25)),
                 exchange_second_asset_dividend: Some(pos_or_panic!(0.01)),
-                exchange_correlation: Some(dec!(0.5)),
+                correlation: Some(CorrelationMatrix::pair("AAA", "BBB", dec!(0.5)).unwrap()),
             }),
         )
     }
@@ -239,12 +237,12 @@ mod tests {
     fn test_exchange_correlation_impact() {
         let mut low_corr = create_exchange_option();
         if let Some(ref mut params) = low_corr.exotic_params {
-            params.exchange_correlation = Some(dec!(0.0));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "BBB", dec!(0.0)).unwrap());
         }
 
         let mut high_corr = create_exchange_option();
         if let Some(ref mut params) = high_corr.exotic_params {
-            params.exchange_correlation = Some(dec!(0.9));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "BBB", dec!(0.9)).unwrap());
         }
 
         let low_price = exchange_black_scholes(&low_corr).unwrap();
@@ -260,7 +258,7 @@ mod tests {
     fn test_exchange_invalid_correlation() {
         let mut option = create_exchange_option();
         if let Some(ref mut params) = option.exotic_params {
-            params.exchange_correlation = Some(dec!(1.5));
+            params.correlation = Some(CorrelationMatrix::unchecked_pair("AAA", "BBB", dec!(1.5)));
         }
 
         let result = exchange_black_scholes(&option);
@@ -339,7 +337,7 @@ mod tests {
     fn test_exchange_negative_correlation() {
         let mut option = create_exchange_option();
         if let Some(ref mut params) = option.exotic_params {
-            params.exchange_correlation = Some(dec!(-0.5));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "BBB", dec!(-0.5)).unwrap());
         }
 
         let price = exchange_black_scholes(&option).unwrap();
@@ -368,7 +366,7 @@ mod tests {
     fn test_exchange_perfect_correlation() {
         let mut option = create_exchange_option();
         if let Some(ref mut params) = option.exotic_params {
-            params.exchange_correlation = Some(dec!(1.0));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "BBB", dec!(1.0)).unwrap());
         }
 
         let price = exchange_black_scholes(&option).unwrap();
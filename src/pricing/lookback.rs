@@ -68,8 +68,7 @@ fn floating_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .time_to_expiration()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -91,7 +90,7 @@ fn floating_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
     let b = r - q; // cost of carry
     let sigma_sq = sigma * sigma;
     let t_dec = t.to_dec();
-    let sqrt_t = t_dec.sqrt().unwrap_or(Decimal::ZERO);
+    let sqrt_t = t.sqrt().to_dec();
 
     // For a new floating strike lookback (S_min = S_max = S):
     // Use Goldman-Sosin-Gatto formulas
@@ -105,8 +104,8 @@ fn floating_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
             if b.abs() < dec!(1e-10) {
                 // Special case when b ≈ 0 (ATM forward)
                 let a1 = sigma.to_dec() * sqrt_t / dec!(2);
-                let n_a1 = big_n(a1).unwrap_or(Decimal::ZERO);
-                let n_neg_a1 = big_n(-a1).unwrap_or(Decimal::ZERO);
+                let n_a1 = big_n(a1)?;
+                let n_neg_a1 = big_n(-a1)?;
 
                 // Simplified formula for b = 0
                 s.to_dec() * (dec!(2) * n_a1 - dec!(1))
@@ -121,9 +120,9 @@ fn floating_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
                 let a1 = ((b + sigma_sq / dec!(2)) * t_dec) / (sigma.to_dec() * sqrt_t);
                 let a2 = a1 - sigma.to_dec() * sqrt_t;
 
-                let n_a1 = big_n(a1).unwrap_or(Decimal::ZERO);
-                let n_a2 = big_n(a2).unwrap_or(Decimal::ZERO);
-                let n_neg_a1 = big_n(-a1).unwrap_or(Decimal::ZERO);
+                let n_a1 = big_n(a1)?;
+                let n_a2 = big_n(a2)?;
+                let n_neg_a1 = big_n(-a1)?;
 
                 let dividend_discount = (-q * t).exp();
                 let discount = (-r * t).exp();
@@ -144,7 +143,7 @@ fn floating_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
 
             if b.abs() < dec!(1e-10) {
                 let a1 = sigma.to_dec() * sqrt_t / dec!(2);
-                let n_neg_a1 = big_n(-a1).unwrap_or(Decimal::ZERO);
+                let n_neg_a1 = big_n(-a1)?;
 
                 // Simplified for b = 0
                 s.to_dec() * (dec!(1) - dec!(2) * n_neg_a1)
@@ -153,10 +152,10 @@ fn floating_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
                 let a1 = ((b + sigma_sq / dec!(2)) * t_dec) / (sigma.to_dec() * sqrt_t);
                 let a2 = a1 - sigma.to_dec() * sqrt_t;
 
-                let n_neg_a1 = big_n(-a1).unwrap_or(Decimal::ZERO);
-                let n_neg_a2 = big_n(-a2).unwrap_or(Decimal::ZERO);
-                let n_a1 = big_n(a1).unwrap_or(Decimal::ZERO);
-                let n_a2 = big_n(a2).unwrap_or(Decimal::ZERO);
+                let n_neg_a1 = big_n(-a1)?;
+                let n_neg_a2 = big_n(-a2)?;
+                let n_a1 = big_n(a1)?;
+                let n_a2 = big_n(a2)?;
 
                 let dividend_discount = (-q * t).exp();
                 let discount = (-r * t).exp();
@@ -190,8 +189,7 @@ fn fixed_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .time_to_expiration()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -216,7 +214,7 @@ fn fixed_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
     let b = r - q;
     let sigma_sq = sigma * sigma;
     let t_dec = t.to_dec();
-    let sqrt_t = t_dec.sqrt().unwrap_or(Decimal::ZERO);
+    let sqrt_t = t.sqrt().to_dec();
 
     // For fixed strike lookback, we use a combination of standard BS
     // plus lookback premium
@@ -235,8 +233,8 @@ fn fixed_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
             // Fixed strike lookback call: pays max(S_max - K, 0)
             // For a new contract: similar to standard call + lookback premium
 
-            let n_d1 = big_n(d1_val).unwrap_or(Decimal::ZERO);
-            let n_d2 = big_n(d2_val).unwrap_or(Decimal::ZERO);
+            let n_d1 = big_n(d1_val)?;
+            let n_d2 = big_n(d2_val)?;
 
             // Standard BS call
             let bs_call = s.to_dec() * dividend_discount * n_d1 - k.to_dec() * discount * n_d2;
@@ -257,8 +255,8 @@ fn fixed_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
         OptionStyle::Put => {
             // Fixed strike lookback put: pays max(K - S_min, 0)
 
-            let n_neg_d1 = big_n(-d1_val).unwrap_or(Decimal::ZERO);
-            let n_neg_d2 = big_n(-d2_val).unwrap_or(Decimal::ZERO);
+            let n_neg_d1 = big_n(-d1_val)?;
+            let n_neg_d2 = big_n(-d2_val)?;
 
             // Standard BS put
             let bs_put =
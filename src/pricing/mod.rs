@@ -20,7 +20,8 @@
 //! ### Monte Carlo Simulations (`monte_carlo`)
 //! Provides Monte Carlo simulation capabilities for option pricing. This module
 //! supports simulation of stock price paths and uses statistical methods to estimate
-//! option values under various stochastic processes.
+//! option values under various stochastic processes. Path-based estimates report
+//! their standard error and support antithetic and control variates.
 //!
 //! ### Telegraph Process (`telegraph`)
 //! Implements the Telegraph process, a two-state stochastic process for modeling price movements.
@@ -64,7 +65,7 @@
 //! use rust_decimal_macros::dec;
 //! use optionstratlib::pricing::telegraph::{TelegraphProcess, telegraph};
 //! use optionstratlib::{ExpirationDate, Options};
-//! use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+//! use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 //! use positive::Positive;
 //! use positive::pos_or_panic;
 //!
@@ -85,6 +86,8 @@
 //!             option_style: OptionStyle::Call,
 //!             dividend_yield: pos_or_panic!(0.01),
 //!             exotic_params: None,
+//!             settlement_type: SettlementType::Physical,
+//!             day_count: DayCount::Act365Fixed,
 //!         };
 //! let price = telegraph(&option, 1000, Some(dec!(0.5)), Some(dec!(0.3)));
 //! ```
@@ -94,7 +97,7 @@
 //! ```rust
 //! use rust_decimal_macros::dec;
 //! use optionstratlib::{ExpirationDate, Options};
-//! use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+//! use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 //! use positive::Positive;
 //! use positive::pos_or_panic;
 //! use optionstratlib::pricing::{
@@ -115,6 +118,8 @@
 //!             option_style: OptionStyle::Call,
 //!             dividend_yield: pos_or_panic!(0.01),
 //!             exotic_params: None,
+//!             settlement_type: SettlementType::Physical,
+//!             day_count: DayCount::Act365Fixed,
 //!         };
 //! // Compare prices across different models
 //! let bs_price = black_scholes(&option);
@@ -196,6 +201,20 @@ pub mod exchange;
 /// Power option pricing (non-linear payoffs).
 pub mod power;
 
+/// Numeric backends for pricing engines.
+///
+/// Defines the [`Numeric`] trait, implemented for `f64` and `Decimal`, which
+/// lets engines run on floating point for speed or on decimals for exact
+/// reporting.
+pub mod numeric;
+
+/// Vectorized Black-Scholes pricing of option batches sharing expiry and rate.
+///
+/// Prices thousands of European options with a common underlying price,
+/// expiry, rate and dividend yield in one call, using a branch-free kernel
+/// that the compiler turns into packed SIMD instructions.
+pub mod batch;
+
 /// Black-Scholes model for option pricing and analysis.
 ///
 /// This module implements the Black-Scholes-Merton model for European option pricing
@@ -263,7 +282,7 @@ pub(crate) mod utils;
 /// use optionstratlib::pricing::{PricingEngine, Priceable};
 /// use optionstratlib::{Options, ExpirationDate};
 /// use positive::{Positive, pos_or_panic};
-/// use optionstratlib::model::types::{OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use rust_decimal_macros::dec;
 /// let option = Options {
 ///     option_type: OptionType::European,
@@ -278,6 +297,8 @@ pub(crate) mod utils;
 ///     option_style: OptionStyle::Call,
 ///     dividend_yield: pos_or_panic!(0.01),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 ///
 /// let engine = PricingEngine::ClosedFormBS;
@@ -286,23 +307,31 @@ pub(crate) mod utils;
 /// ```
 pub mod unified;
 
-pub use american::barone_adesi_whaley;
+pub use american::{
+    DiscreteDividend, ExerciseBoundary, ExerciseBoundaryPoint, american_discrete_dividends,
+    barone_adesi_whaley, early_exercise_boundary,
+};
 pub use asian::asian_black_scholes;
 pub use barrier::barrier_black_scholes;
+pub use batch::{BATCH_LANES, BatchBlackScholes, BatchGreeks};
 pub use binary::binary_black_scholes;
 pub use binomial_model::{BinomialPricingParams, generate_binomial_tree, price_binomial};
-pub use black_scholes_model::{BlackScholes, black_scholes};
+pub use black_scholes_model::{BlackScholes, black_scholes, black_scholes_as};
 pub use chooser::chooser_black_scholes;
 pub use cliquet::cliquet_black_scholes;
 pub use compound::compound_black_scholes;
 pub use exchange::exchange_black_scholes;
 pub use lookback::lookback_black_scholes;
-pub use monte_carlo::monte_carlo_option_pricing;
-pub use payoff::{Payoff, PayoffInfo, Profit};
+pub use monte_carlo::{
+    MonteCarloConfig, MonteCarloEstimate, asian_monte_carlo, monte_carlo_option_pricing,
+    price_option_monte_carlo_as, price_paths_monte_carlo, price_paths_with_control,
+};
+pub use numeric::Numeric;
+pub use payoff::{Payoff, PayoffInfo, PayoffInputs, Profit};
 pub use power::power_black_scholes;
 pub use quanto::quanto_black_scholes;
 pub use rainbow::rainbow_black_scholes;
 pub use spread::spread_black_scholes;
 pub use telegraph::{TelegraphProcess, telegraph};
-pub use unified::{Priceable, PricingEngine, price_option};
+pub use unified::{Priceable, PricingEngine, price_option, price_option_as};
 pub use utils::{probability_keep_under_strike, simulate_returns};
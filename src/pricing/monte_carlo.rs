@@ -1,10 +1,17 @@
 use crate::Options;
 use crate::error::PricingError;
-use crate::f2d;
+use crate::model::types::{AsianAveragingType, OptionStyle, OptionType, Side};
+use crate::pricing::numeric::Numeric;
+use crate::pricing::payoff::{Payoff, PayoffInputs};
 use crate::pricing::utils::wiener_increment;
-use num_traits::{FromPrimitive, ToPrimitive};
+use crate::simulation::{PathMatrix, PathSimulator, TimeGrid};
+use crate::{d2f, f2d};
+use num_traits::ToPrimitive;
 use positive::Positive;
 use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal};
+use utoipa::ToSchema;
 
 /// This function performs Monte Carlo simulation to price an option.
 ///
@@ -37,7 +44,7 @@ pub fn monte_carlo_option_pricing(
     steps: usize,       // Number of time steps
     simulations: usize, // Number of Monte Carlo simulations
 ) -> Result<Decimal, PricingError> {
-    let dt = option.expiration_date.get_years()? / steps as f64;
+    let dt = option.time_to_expiration()? / steps as f64;
     let mut payoff_sum = 0.0;
 
     for _ in 0..simulations {
@@ -47,15 +54,12 @@ pub fn monte_carlo_option_pricing(
             st *= Decimal::ONE + option.risk_free_rate * dt + option.implied_volatility * w;
         }
         // Calculate the payoff for a call option
-        let payoff: f64 = (st - option.strike_price)
-            .max(Decimal::ZERO)
-            .to_f64()
-            .unwrap();
+        let payoff: f64 = d2f!((st - option.strike_price).max(Decimal::ZERO));
         payoff_sum += payoff;
     }
     // Average value of the payoffs discounted to present value
     let average_payoff = (payoff_sum / simulations as f64)
-        * (-option.risk_free_rate.to_f64().unwrap() * option.expiration_date.get_years()?).exp();
+        * (-d2f!(option.risk_free_rate) * option.time_to_expiration()?).exp();
     Ok(f2d!(average_payoff))
 }
 
@@ -80,15 +84,16 @@ pub fn monte_carlo_option_pricing(
 /// 2. Calculates the effective discount factor based on the risk-free rate, dividend yield, and
 ///    time to expiration. This factor is used to discount future payoffs to their present value.
 /// 3. For each simulated final price in the `final_prices` slice:
-///    - Compute the payoff using the `option.payoff_at_price` method.
+///    - Compute the payoff in `f64` using [`Payoff::payoff_f64`].
 ///    - Accumulate the total payoff across all simulations.
 /// 4. Compute the average payoff by dividing the total payoff by the number of simulations.
 ///    The average payoff is then discounted using the calculated discount factor.
 /// 5. Return the discounted average payoff as the estimated option price.
 ///
+/// This is [`price_option_monte_carlo_as`] on the `f64` backend.
+///
 /// # Errors
 /// - Returns an error if there are any issues while calculating the time to expiration (e.g., invalid dates).
-/// - Panics if the `option.payoff_at_price` method fails unexpectedly during payoff calculations.
 ///
 /// This function assumes that the `Options` struct and `Positive` type
 /// are implemented elsewhere in the codebase and provide necessary functionality (e.g., payoff calculation).
@@ -103,31 +108,503 @@ pub fn price_option_monte_carlo(
         return Ok(Positive::ZERO);
     }
 
+    let avg_payoff = price_option_monte_carlo_as::<f64>(option, final_prices)?;
+    Ok(Positive::new_decimal(f2d!(avg_payoff).abs())?)
+}
+
+/// Estimates the price of an option from simulated final prices on the
+/// numeric backend `T`.
+///
+/// The discounted average of the payoffs over `final_prices`, computed in
+/// `T`: with `f64` every step stays in floating point, while with `Decimal`
+/// the vanilla payoffs, their sum and the discounting are exact. Exotic
+/// payoffs are evaluated with [`Payoff::payoff_f64`] and converted to `T`.
+///
+/// The result is signed by the side of the option and scaled by its
+/// quantity. An empty `final_prices` slice prices to zero.
+///
+/// # Errors
+///
+/// Returns a `PricingError` if the time to expiration cannot be computed or
+/// a payoff cannot be represented in `T`.
+pub fn price_option_monte_carlo_as<T: Numeric>(
+    option: &Options,
+    final_prices: &[Positive],
+) -> Result<T, PricingError> {
+    if final_prices.is_empty() {
+        return Ok(T::ZERO);
+    }
+
     // Calculate total discount factor (risk-free rate adjusted for dividends)
-    let effective_rate = option.risk_free_rate - option.dividend_yield;
-    let discount_factor = (-effective_rate * option.expiration_date.get_years()?).exp();
+    let t = T::from_positive(option.time_to_expiration()?);
+    let effective_rate =
+        T::try_from_decimal(option.risk_free_rate)? - T::from_positive(option.dividend_yield);
+    let discount_factor = (-(effective_rate * t)).try_exp()?;
+
+    let strike = T::from_positive(option.strike_price);
+    let strike_f64 = option.strike_price.to_f64();
+    let mut total_payoff = T::ZERO;
+    for final_price in final_prices {
+        let payoff = match option.option_type {
+            OptionType::European | OptionType::American | OptionType::Bermuda { .. } => {
+                let spot = T::from_positive(*final_price);
+                let intrinsic = match option.option_style {
+                    OptionStyle::Call => spot - strike,
+                    OptionStyle::Put => strike - spot,
+                };
+                match option.side {
+                    Side::Long => intrinsic.positive_part(),
+                    Side::Short => -intrinsic.positive_part(),
+                }
+            }
+            _ => {
+                let inputs = PayoffInputs::new(
+                    final_price.to_f64(),
+                    strike_f64,
+                    option.option_style,
+                    option.side,
+                );
+                T::try_from_f64(option.option_type.payoff_f64(&inputs))?
+            }
+        };
+        total_payoff = total_payoff + payoff;
+    }
+
+    // Average payoff discounted to present value
+    let simulations = T::try_from_f64(final_prices.len() as f64)?;
+    Ok(discount_factor * T::from_positive(option.quantity) * total_payoff / simulations)
+}
+
+/// Monte Carlo price together with the standard error of the estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MonteCarloEstimate {
+    /// Estimated price.
+    pub price: Decimal,
+    /// Standard error of the estimated price.
+    pub standard_error: Decimal,
+    /// Number of independent samples. An antithetic pair counts as one sample.
+    pub samples: usize,
+}
+
+impl MonteCarloEstimate {
+    /// Confidence interval `price ± z * standard_error`, for instance
+    /// `z = 1.96` for a 95% interval.
+    pub fn confidence_interval(&self, z: Decimal) -> (Decimal, Decimal) {
+        let half_width = z * self.standard_error;
+        (self.price - half_width, self.price + half_width)
+    }
+
+    /// Variance reduction relative to `baseline`: the ratio of the squared
+    /// standard errors, or how many times more samples the baseline would
+    /// need to reach the accuracy of this estimate. `None` if this estimate
+    /// has no error.
+    pub fn variance_reduction(&self, baseline: &MonteCarloEstimate) -> Option<Decimal> {
+        if self.standard_error.is_zero() {
+            return None;
+        }
+        Some((baseline.standard_error / self.standard_error).powi(2))
+    }
+}
+
+/// Settings of the path-based Monte Carlo pricers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MonteCarloConfig {
+    /// Number of simulated paths.
+    pub paths: usize,
+    /// Number of equally spaced observation dates until expiration.
+    pub steps: usize,
+    /// Optional seed for reproducible prices.
+    pub seed: Option<u64>,
+    /// Whether paths are generated in antithetic pairs.
+    pub antithetic: bool,
+    /// Whether a control variate with a closed-form price is used.
+    pub control_variate: bool,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        Self {
+            paths: 10_000,
+            steps: 252,
+            seed: None,
+            antithetic: false,
+            control_variate: false,
+        }
+    }
+}
+
+impl MonteCarloConfig {
+    /// Sets the number of paths.
+    pub fn with_paths(mut self, paths: usize) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Sets the number of observation dates.
+    pub fn with_steps(mut self, steps: usize) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Sets the seed of the random number generator.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Enables or disables antithetic sampling.
+    pub fn with_antithetic(mut self, antithetic: bool) -> Self {
+        self.antithetic = antithetic;
+        self
+    }
+
+    /// Enables or disables the control variate.
+    pub fn with_control_variate(mut self, control_variate: bool) -> Self {
+        self.control_variate = control_variate;
+        self
+    }
+}
+
+/// Evaluates `function` on every path, averaging antithetic pairs so that
+/// the returned samples are independent.
+fn path_samples<F>(paths: &PathMatrix, function: F) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let values: Vec<f64> = paths.iter().map(function).collect();
+    if paths.is_antithetic() {
+        values
+            .chunks(2)
+            .map(|pair| pair.iter().sum::<f64>() / pair.len() as f64)
+            .collect()
+    } else {
+        values
+    }
+}
+
+fn sample_mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+fn estimate(samples: &[f64]) -> Result<MonteCarloEstimate, PricingError> {
+    if samples.is_empty() {
+        return Err(PricingError::simulation_error("No simulated paths"));
+    }
+    let mean = sample_mean(samples);
+    let variance =
+        samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (samples.len() - 1).max(1) as f64;
+    let standard_error = (variance / samples.len() as f64).sqrt();
+    Ok(MonteCarloEstimate {
+        price: f2d!(mean),
+        standard_error: f2d!(standard_error),
+        samples: samples.len(),
+    })
+}
+
+/// Prices a path-dependent payoff as the discounted average of `payoff` over
+/// `paths`, reporting the standard error of the estimate.
+///
+/// Antithetic pairs of [`PathMatrix::is_antithetic`] matrices are averaged
+/// before the error is computed, so the reported error reflects the gain of
+/// antithetic sampling.
+///
+/// # Errors
+///
+/// Returns a `PricingError` if `paths` is empty.
+pub fn price_paths_monte_carlo<P>(
+    paths: &PathMatrix,
+    discount_factor: f64,
+    payoff: P,
+) -> Result<MonteCarloEstimate, PricingError>
+where
+    P: Fn(&[f64]) -> f64,
+{
+    let samples = path_samples(paths, |path| discount_factor * payoff(path));
+    estimate(&samples)
+}
+
+/// Prices a path-dependent payoff with a control variate: a second payoff
+/// evaluated on the same paths whose price `control_price` is known in closed
+/// form. The discounted payoff is corrected by `beta * (control - control_price)`,
+/// with the variance-minimising `beta` estimated from the paths.
+///
+/// # Errors
+///
+/// Returns a `PricingError` if `paths` is empty.
+pub fn price_paths_with_control<P, C>(
+    paths: &PathMatrix,
+    discount_factor: f64,
+    payoff: P,
+    control: C,
+    control_price: f64,
+) -> Result<MonteCarloEstimate, PricingError>
+where
+    P: Fn(&[f64]) -> f64,
+    C: Fn(&[f64]) -> f64,
+{
+    let targets = path_samples(paths, |path| discount_factor * payoff(path));
+    let controls = path_samples(paths, |path| discount_factor * control(path));
+    if targets.is_empty() {
+        return Err(PricingError::simulation_error("No simulated paths"));
+    }
 
-    // Calculate payoff for each final price and sum them
-    let total_payoff: Decimal = final_prices
+    let target_mean = sample_mean(&targets);
+    let control_mean = sample_mean(&controls);
+    let (covariance, variance) = targets.iter().zip(&controls).fold(
+        (0.0, 0.0),
+        |(covariance, variance), (target, control)| {
+            let deviation = control - control_mean;
+            (
+                covariance + (target - target_mean) * deviation,
+                variance + deviation * deviation,
+            )
+        },
+    );
+    let beta = if variance > 0.0 {
+        covariance / variance
+    } else {
+        0.0
+    };
+    let adjusted: Vec<f64> = targets
         .iter()
-        .map(|&final_price| {
-            option
-                .payoff_at_price(&final_price)
-                .unwrap_or(Decimal::ZERO)
-        })
+        .zip(&controls)
+        .map(|(target, control)| target - beta * (control - control_price))
+        .collect();
+    estimate(&adjusted)
+}
+
+/// Prices an Asian option by Monte Carlo on `config.steps` equally spaced
+/// fixing dates, under geometric Brownian motion.
+///
+/// With [`MonteCarloConfig::control_variate`] enabled, the geometric average
+/// option on the same fixings, whose price is known in closed form, is used
+/// as control. It is highly correlated with the arithmetic average option and
+/// typically shrinks the standard error by an order of magnitude or more.
+///
+/// Short positions return a negative price.
+///
+/// # Errors
+///
+/// Returns a `PricingError` if the option is not an Asian option, the
+/// expiration is invalid or the simulation parameters are inconsistent.
+pub fn asian_monte_carlo(
+    option: &Options,
+    config: &MonteCarloConfig,
+) -> Result<MonteCarloEstimate, PricingError> {
+    let OptionType::Asian { averaging_type } = &option.option_type else {
+        return Err(PricingError::unsupported_option_type(
+            &format!("{:?}", option.option_type),
+            "Asian Monte Carlo",
+        ));
+    };
+    let averaging_type = *averaging_type;
+    let t = option.time_to_expiration()?;
+    let sign = match option.side {
+        Side::Long => Decimal::ONE,
+        Side::Short => Decimal::NEGATIVE_ONE,
+    };
+    let strike = option.strike_price.to_f64();
+    let style = option.option_style;
+    let intrinsic = move |average: f64| match style {
+        OptionStyle::Call => (average - strike).max(0.0),
+        OptionStyle::Put => (strike - average).max(0.0),
+    };
+
+    if t == Positive::ZERO {
+        return Ok(MonteCarloEstimate {
+            price: sign * f2d!(intrinsic(option.underlying_price.to_f64())),
+            standard_error: Decimal::ZERO,
+            samples: 0,
+        });
+    }
+
+    let grid = TimeGrid::uniform(t, config.steps)
+        .map_err(|e| PricingError::simulation_error(&e.to_string()))?;
+    let mut simulator = PathSimulator::new(
+        option.underlying_price,
+        option.implied_volatility,
+        option.risk_free_rate,
+        option.dividend_yield,
+    )
+    .with_paths(config.paths)
+    .with_antithetic(config.antithetic);
+    if let Some(seed) = config.seed {
+        simulator = simulator.with_seed(seed);
+    }
+    let paths = simulator
+        .simulate(&grid)
+        .map_err(|e| PricingError::simulation_error(&e.to_string()))?;
+
+    let fixings = config.steps as f64;
+    let arithmetic = |path: &[f64]| intrinsic(path[1..].iter().sum::<f64>() / fixings);
+    let geometric =
+        |path: &[f64]| intrinsic((path[1..].iter().map(|s| s.ln()).sum::<f64>() / fixings).exp());
+    let discount_factor = (-option.risk_free_rate.to_f64().unwrap_or(0.0) * t.to_f64()).exp();
+
+    let mut result = match (averaging_type, config.control_variate) {
+        (AsianAveragingType::Arithmetic, false) => {
+            price_paths_monte_carlo(&paths, discount_factor, arithmetic)?
+        }
+        (AsianAveragingType::Geometric, false) => {
+            price_paths_monte_carlo(&paths, discount_factor, geometric)?
+        }
+        (averaging_type, true) => {
+            let control_price = discrete_geometric_asian(option, &grid)?;
+            match averaging_type {
+                AsianAveragingType::Arithmetic => price_paths_with_control(
+                    &paths,
+                    discount_factor,
+                    arithmetic,
+                    geometric,
+                    control_price,
+                )?,
+                AsianAveragingType::Geometric => price_paths_with_control(
+                    &paths,
+                    discount_factor,
+                    geometric,
+                    geometric,
+                    control_price,
+                )?,
+            }
+        }
+    };
+    result.price *= sign;
+    Ok(result)
+}
+
+/// Closed-form price of a long geometric average Asian option with discrete
+/// fixings on the dates of `grid` after time zero.
+///
+/// The log of the geometric average is normal with mean
+/// `ln S + (r - q - sigma^2 / 2) * mean(t_i)` and variance
+/// `sigma^2 / n^2 * sum_ij min(t_i, t_j)`.
+fn discrete_geometric_asian(option: &Options, grid: &TimeGrid) -> Result<f64, PricingError> {
+    let fixings = &grid.times()[1..];
+    let n = fixings.len() as f64;
+    let spot = option.underlying_price.to_f64();
+    let strike = option.strike_price.to_f64();
+    let rate = option.risk_free_rate.to_f64().unwrap_or(0.0);
+    let sigma = option.implied_volatility.to_f64();
+    let carry = rate - option.dividend_yield.to_f64();
+
+    let mean_time = fixings.iter().sum::<f64>() / n;
+    // For sorted fixings, sum_ij min(t_i, t_j) = sum_i t_i * (2 * (n - i) - 1).
+    let overlap: f64 = fixings
+        .iter()
+        .enumerate()
+        .map(|(i, t)| t * (2.0 * (n - i as f64) - 1.0))
         .sum();
+    let mean = spot.ln() + (carry - 0.5 * sigma * sigma) * mean_time;
+    let variance = sigma * sigma * overlap / (n * n);
+    let discount = (-rate * grid.horizon()).exp();
+    let forward = (mean + 0.5 * variance).exp();
 
-    // Average payoff discounted to present value
-    let avg_payoff =
-        discount_factor * (total_payoff / Decimal::from_usize(num_simulations).unwrap());
-    Ok(Positive(avg_payoff.abs()))
+    if variance <= 0.0 {
+        let value = match option.option_style {
+            OptionStyle::Call => (forward - strike).max(0.0),
+            OptionStyle::Put => (strike - forward).max(0.0),
+        };
+        return Ok(discount * value);
+    }
+
+    let normal = Normal::standard();
+    let deviation = variance.sqrt();
+    let d1 = (mean - strike.ln() + variance) / deviation;
+    let d2 = d1 - deviation;
+    let value = match option.option_style {
+        OptionStyle::Call => forward * normal.cdf(d1) - strike * normal.cdf(d2),
+        OptionStyle::Put => strike * normal.cdf(-d2) - forward * normal.cdf(-d1),
+    };
+    if !value.is_finite() {
+        return Err(PricingError::method_error(
+            "discrete_geometric_asian",
+            "Non-finite geometric Asian price",
+        ));
+    }
+    Ok(discount * value)
+}
+
+#[cfg(test)]
+mod tests_variance_reduction {
+    use super::*;
+    use crate::ExpirationDate;
+    use num_traits::FromPrimitive;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn asian_option(averaging_type: AsianAveragingType) -> Options {
+        Options::new(
+            OptionType::Asian { averaging_type },
+            Side::Long,
+            "XYZ".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(365.0)),
+            pos_or_panic!(0.3),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_geometric_control_matches_closed_form() {
+        let option = asian_option(AsianAveragingType::Geometric);
+        let config = MonteCarloConfig::default()
+            .with_paths(20_000)
+            .with_steps(12)
+            .with_seed(11);
+        let plain = asian_monte_carlo(&option, &config).unwrap();
+        let grid = TimeGrid::uniform(Positive::ONE, 12).unwrap();
+        let closed_form =
+            Decimal::from_f64(discrete_geometric_asian(&option, &grid).unwrap()).unwrap();
+
+        let (low, high) = plain.confidence_interval(dec!(3));
+        assert!(low < closed_form && closed_form < high);
+
+        // The control is the payoff itself, so the estimate is exact.
+        let controlled = asian_monte_carlo(&option, &config.with_control_variate(true)).unwrap();
+        assert!((controlled.price - closed_form).abs() < dec!(1e-6));
+        assert!(controlled.standard_error < dec!(1e-6));
+    }
+
+    #[test]
+    fn test_variance_reduction_on_arithmetic_asian() {
+        let option = asian_option(AsianAveragingType::Arithmetic);
+        let config = MonteCarloConfig::default()
+            .with_paths(10_000)
+            .with_steps(12)
+            .with_seed(5);
+        let plain = asian_monte_carlo(&option, &config).unwrap();
+        let antithetic = asian_monte_carlo(&option, &config.with_antithetic(true)).unwrap();
+        let controlled = asian_monte_carlo(&option, &config.with_control_variate(true)).unwrap();
+
+        assert_eq!(antithetic.samples, 5_000);
+        assert!(antithetic.variance_reduction(&plain).unwrap() > Decimal::ONE);
+        assert!(controlled.variance_reduction(&plain).unwrap() > dec!(20));
+        assert!((controlled.price - plain.price).abs() < dec!(4) * plain.standard_error);
+
+        let mut short = option.clone();
+        short.side = Side::Short;
+        let short_price = asian_monte_carlo(&short, &config.with_control_variate(true)).unwrap();
+        assert_eq!(short_price.price, -controlled.price);
+    }
+
+    #[test]
+    fn test_rejects_non_asian_options() {
+        let mut option = asian_option(AsianAveragingType::Arithmetic);
+        option.option_type = OptionType::European;
+        assert!(asian_monte_carlo(&option, &MonteCarloConfig::default()).is_err());
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::constants::ZERO;
-    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
     use crate::{ExpirationDate, assert_decimal_eq, f2du};
     use positive::constants::DAYS_IN_A_YEAR;
     use positive::{Positive, pos_or_panic};
@@ -148,6 +625,8 @@ mod tests {
             option_style: OptionStyle::Call,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
@@ -270,6 +749,31 @@ mod tests_price_option_monte_carlo {
         );
     }
 
+    #[test]
+    fn test_decimal_backend_sums_payoffs_exactly() {
+        let mut option = create_sample_option(
+            OptionStyle::Put,
+            Side::Short,
+            Positive::HUNDRED,
+            pos_or_panic!(3.0),
+            Positive::HUNDRED,
+            pos_or_panic!(0.2),
+        );
+        option.risk_free_rate = Decimal::ZERO;
+        option.dividend_yield = Positive::ZERO;
+        let prices = vec![
+            pos_or_panic!(99.9),
+            pos_or_panic!(99.8),
+            pos_or_panic!(120.0),
+        ];
+
+        let exact: Decimal = price_option_monte_carlo_as(&option, &prices).unwrap();
+        let fast: f64 = price_option_monte_carlo_as(&option, &prices).unwrap();
+        // Short put payoffs -0.1 and -0.2 on three contracts, averaged.
+        assert_eq!(exact, dec!(-0.3));
+        assert!((fast + 0.3).abs() < 1e-12);
+    }
+
     #[test]
     fn test_simulation() {
         struct TestWalker;
@@ -0,0 +1,289 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Numeric Backends
+//!
+//! The [`Numeric`] trait abstracts the scalar type a pricing engine computes
+//! with, so the same engine can run on `f64` for speed in simulations or on
+//! [`Decimal`] for exact reporting.
+//!
+//! Engines generic over [`Numeric`] convert the `Positive` and `Decimal`
+//! fields of an option into the backend type once, at the start of an
+//! evaluation, and never convert back until the result is returned:
+//!
+//! - `f64` uses hardware floating point and the normal CDF of `statrs`.
+//! - [`Decimal`] uses 96-bit fixed point arithmetic, so sums of payoffs and
+//!   discounting are free of binary rounding. The normal CDF is evaluated in
+//!   `f64`, as in [`crate::greeks::big_n`].
+//!
+//! See [`crate::pricing::price_option_as`] for the generic entry point.
+
+use crate::error::PricingError;
+use num_traits::{FromPrimitive, ToPrimitive};
+use positive::Positive;
+use rust_decimal::{Decimal, MathematicalOps};
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::fmt::{Debug, Display};
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Scalar type a pricing engine computes with.
+///
+/// Implemented for `f64` and [`Decimal`]. Operations that can fail for one of
+/// the backends, such as `ln` of a non-positive value or an overflowing
+/// `exp`, return a `PricingError` on both.
+pub trait Numeric:
+    Copy
+    + Debug
+    + Display
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Sum
+    + Send
+    + Sync
+{
+    /// Name of the backend, used in error messages.
+    const NAME: &'static str;
+
+    /// Zero.
+    const ZERO: Self;
+
+    /// One.
+    const ONE: Self;
+
+    /// Converts a `Positive` value.
+    fn from_positive(value: Positive) -> Self;
+
+    /// Converts a `Decimal` value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be represented by the backend.
+    fn try_from_decimal(value: Decimal) -> Result<Self, PricingError>;
+
+    /// Converts an `f64` value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not finite or cannot be represented
+    /// by the backend.
+    fn try_from_f64(value: f64) -> Result<Self, PricingError>;
+
+    /// Converts the value to `f64`.
+    fn as_f64(self) -> f64;
+
+    /// Converts the value to `Decimal`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not finite or out of range.
+    fn try_to_decimal(self) -> Result<Decimal, PricingError>;
+
+    /// Returns the absolute value.
+    fn magnitude(self) -> Self;
+
+    /// Returns `e^self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the result overflows the backend.
+    fn try_exp(self) -> Result<Self, PricingError>;
+
+    /// Returns the natural logarithm.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not strictly positive.
+    fn try_ln(self) -> Result<Self, PricingError>;
+
+    /// Returns the square root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is negative.
+    fn try_sqrt(self) -> Result<Self, PricingError>;
+
+    /// Returns the standard normal cumulative distribution function at `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be evaluated.
+    fn normal_cdf(self) -> Result<Self, PricingError>;
+
+    /// Returns `max(self, 0)`.
+    fn positive_part(self) -> Self {
+        if self > Self::ZERO { self } else { Self::ZERO }
+    }
+}
+
+fn operation_error(backend: &str, operation: &str, value: impl Display) -> PricingError {
+    PricingError::method_error(backend, &format!("{operation} is undefined for {value}"))
+}
+
+fn standard_normal() -> Normal {
+    Normal::new(0.0, 1.0).expect("standard normal parameters are valid")
+}
+
+impl Numeric for f64 {
+    const NAME: &'static str = "f64";
+
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn from_positive(value: Positive) -> Self {
+        value.to_f64()
+    }
+
+    fn try_from_decimal(value: Decimal) -> Result<Self, PricingError> {
+        ToPrimitive::to_f64(&value).ok_or_else(|| operation_error(Self::NAME, "conversion", value))
+    }
+
+    fn try_from_f64(value: f64) -> Result<Self, PricingError> {
+        if value.is_finite() {
+            Ok(value)
+        } else {
+            Err(operation_error(Self::NAME, "conversion", value))
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        self
+    }
+
+    fn try_to_decimal(self) -> Result<Decimal, PricingError> {
+        <Decimal as FromPrimitive>::from_f64(self)
+            .ok_or_else(|| operation_error(Self::NAME, "conversion", self))
+    }
+
+    fn magnitude(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn try_exp(self) -> Result<Self, PricingError> {
+        let result = f64::exp(self);
+        if result.is_finite() {
+            Ok(result)
+        } else {
+            Err(operation_error(Self::NAME, "exp", self))
+        }
+    }
+
+    fn try_ln(self) -> Result<Self, PricingError> {
+        if self > 0.0 {
+            Ok(f64::ln(self))
+        } else {
+            Err(operation_error(Self::NAME, "ln", self))
+        }
+    }
+
+    fn try_sqrt(self) -> Result<Self, PricingError> {
+        if self >= 0.0 {
+            Ok(f64::sqrt(self))
+        } else {
+            Err(operation_error(Self::NAME, "sqrt", self))
+        }
+    }
+
+    fn normal_cdf(self) -> Result<Self, PricingError> {
+        Ok(standard_normal().cdf(self))
+    }
+}
+
+impl Numeric for Decimal {
+    const NAME: &'static str = "Decimal";
+
+    const ZERO: Self = Decimal::ZERO;
+    const ONE: Self = Decimal::ONE;
+
+    fn from_positive(value: Positive) -> Self {
+        value.to_dec()
+    }
+
+    fn try_from_decimal(value: Decimal) -> Result<Self, PricingError> {
+        Ok(value)
+    }
+
+    fn try_from_f64(value: f64) -> Result<Self, PricingError> {
+        <Decimal as FromPrimitive>::from_f64(value)
+            .ok_or_else(|| operation_error(Self::NAME, "conversion", value))
+    }
+
+    fn as_f64(self) -> f64 {
+        ToPrimitive::to_f64(&self).unwrap_or(0.0)
+    }
+
+    fn try_to_decimal(self) -> Result<Decimal, PricingError> {
+        Ok(self)
+    }
+
+    fn magnitude(self) -> Self {
+        Decimal::abs(&self)
+    }
+
+    fn try_exp(self) -> Result<Self, PricingError> {
+        self.checked_exp()
+            .ok_or_else(|| operation_error(Self::NAME, "exp", self))
+    }
+
+    fn try_ln(self) -> Result<Self, PricingError> {
+        if self > Decimal::ZERO {
+            Ok(MathematicalOps::ln(&self))
+        } else {
+            Err(operation_error(Self::NAME, "ln", self))
+        }
+    }
+
+    fn try_sqrt(self) -> Result<Self, PricingError> {
+        MathematicalOps::sqrt(&self).ok_or_else(|| operation_error(Self::NAME, "sqrt", self))
+    }
+
+    fn normal_cdf(self) -> Result<Self, PricingError> {
+        let x = ToPrimitive::to_f64(&self)
+            .ok_or_else(|| operation_error(Self::NAME, "normal_cdf", self))?;
+        Self::try_from_f64(standard_normal().cdf(x))
+    }
+}
+
+#[cfg(test)]
+mod tests_numeric {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn exp_ln_round_trip<T: Numeric>(x: T) -> T {
+        x.try_ln().unwrap().try_exp().unwrap()
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let x = exp_ln_round_trip(2.5_f64);
+        let y = exp_ln_round_trip(dec!(2.5));
+        assert!((x - 2.5).abs() < 1e-12);
+        assert!((y - dec!(2.5)).abs() < dec!(1e-12));
+
+        let cdf_f64 = 0.3_f64.normal_cdf().unwrap();
+        let cdf_dec = dec!(0.3).normal_cdf().unwrap();
+        assert!((cdf_f64 - cdf_dec.as_f64()).abs() < 1e-15);
+        assert_eq!(Decimal::from_positive(Positive::HUNDRED), dec!(100));
+        assert_eq!(dec!(-2).positive_part(), Decimal::ZERO);
+        assert_eq!(2.0_f64.positive_part(), 2.0);
+    }
+
+    #[test]
+    fn test_invalid_operations_are_errors() {
+        assert!(Numeric::try_ln(0.0_f64).is_err());
+        assert!(Numeric::try_ln(dec!(-1)).is_err());
+        assert!(Numeric::try_sqrt(-1.0_f64).is_err());
+        assert!(Numeric::try_sqrt(dec!(-1)).is_err());
+        assert!(Numeric::try_exp(1000.0_f64).is_err());
+        assert!(Numeric::try_exp(dec!(1000)).is_err());
+        assert!(f64::try_from_f64(f64::NAN).is_err());
+        assert!(Decimal::try_from_f64(f64::INFINITY).is_err());
+    }
+}
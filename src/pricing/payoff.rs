@@ -52,6 +52,110 @@ pub trait Payoff {
     ///
     /// Returns the calculated payoff value as a `f64`.
     fn payoff(&self, info: &PayoffInfo) -> f64;
+
+    /// Calculates the payoff value from plain `f64` inputs.
+    ///
+    /// This is the hot-path entry point used by Monte Carlo and lattice
+    /// pricers, which evaluate payoffs millions of times: the whole
+    /// evaluation stays in `f64`, with no `Positive` validation or `Decimal`
+    /// arithmetic. The default implementation builds a [`PayoffInfo`] and
+    /// calls [`Payoff::payoff`], so implementors only need to override it to
+    /// avoid that conversion.
+    ///
+    /// # Parameters
+    ///
+    /// * `inputs` - The payoff parameters as `f64` values.
+    ///
+    /// # Returns
+    ///
+    /// Returns the calculated payoff value as a `f64`.
+    fn payoff_f64(&self, inputs: &PayoffInputs) -> f64 {
+        let info = PayoffInfo {
+            spot: Positive::new(inputs.spot).unwrap_or(Positive::ZERO),
+            strike: Positive::new(inputs.strike).unwrap_or(Positive::ZERO),
+            style: inputs.style,
+            side: inputs.side,
+            spot_prices: inputs.spot_prices.map(<[f64]>::to_vec),
+            spot_min: inputs.spot_min,
+            spot_max: inputs.spot_max,
+        };
+        self.payoff(&info)
+    }
+}
+
+/// Payoff parameters as plain `f64` values.
+///
+/// The `f64` counterpart of [`PayoffInfo`], evaluated by
+/// [`Payoff::payoff_f64`]. Path observations are borrowed, so building the
+/// inputs for a simulated path does not allocate.
+///
+/// # Example
+///
+/// ```
+/// use optionstratlib::pricing::{Payoff, PayoffInputs};
+/// use optionstratlib::model::types::{OptionStyle, OptionType, Side};
+///
+/// let inputs = PayoffInputs::new(110.0, 100.0, OptionStyle::Call, Side::Long);
+/// assert_eq!(OptionType::European.payoff_f64(&inputs), 10.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PayoffInputs<'a> {
+    /// Price of the underlying asset at evaluation.
+    pub spot: f64,
+    /// Strike price of the option.
+    pub strike: f64,
+    /// Whether the option is a Call or a Put.
+    pub style: OptionStyle,
+    /// Whether the position is Long or Short.
+    pub side: Side,
+    /// Observed spot prices, used by Asian options.
+    pub spot_prices: Option<&'a [f64]>,
+    /// Minimum observed spot price, used by Lookback and Barrier options.
+    pub spot_min: Option<f64>,
+    /// Maximum observed spot price, used by Lookback and Barrier options.
+    pub spot_max: Option<f64>,
+}
+
+impl<'a> PayoffInputs<'a> {
+    /// Creates inputs for a payoff that depends only on the final spot price.
+    pub fn new(spot: f64, strike: f64, style: OptionStyle, side: Side) -> Self {
+        PayoffInputs {
+            spot,
+            strike,
+            style,
+            side,
+            spot_prices: None,
+            spot_min: None,
+            spot_max: None,
+        }
+    }
+
+    /// Sets the observed spot prices of a path-dependent payoff.
+    pub fn with_spot_prices(mut self, spot_prices: &'a [f64]) -> Self {
+        self.spot_prices = Some(spot_prices);
+        self
+    }
+
+    /// Sets the minimum and maximum observed spot prices.
+    pub fn with_extremes(mut self, spot_min: f64, spot_max: f64) -> Self {
+        self.spot_min = Some(spot_min);
+        self.spot_max = Some(spot_max);
+        self
+    }
+}
+
+impl<'a> From<&'a PayoffInfo> for PayoffInputs<'a> {
+    fn from(info: &'a PayoffInfo) -> Self {
+        PayoffInputs {
+            spot: info.spot.to_f64(),
+            strike: info.strike.to_f64(),
+            style: info.style,
+            side: info.side,
+            spot_prices: info.spot_prices.as_deref(),
+            spot_min: info.spot_min,
+            spot_max: info.spot_max,
+        }
+    }
 }
 /// `PayoffInfo` is a struct that holds information about an option's payoff calculation parameters.
 ///
@@ -178,6 +282,23 @@ pub(crate) fn standard_payoff(info: &PayoffInfo) -> f64 {
     }
 }
 
+/// Calculates the standard payoff of an option from `f64` inputs.
+///
+/// The `f64` counterpart of [`standard_payoff`], used on the hot path where
+/// the inputs are already floating point and exact decimal differences are
+/// not needed.
+pub(crate) fn standard_payoff_f64(inputs: &PayoffInputs) -> f64 {
+    let payoff = match inputs.style {
+        OptionStyle::Call => (inputs.spot - inputs.strike).max(0.0),
+        OptionStyle::Put => (inputs.strike - inputs.spot).max(0.0),
+    };
+
+    match inputs.side {
+        Side::Long => payoff,
+        Side::Short => -payoff,
+    }
+}
+
 /// Defines the profit calculation behavior for financial instruments.
 ///
 /// This trait is used to calculate and visualize profit values at different price points
@@ -321,3 +442,72 @@ mod tests_standard_payoff {
         assert_eq!(option_type.payoff(&info), 0.0);
     }
 }
+
+#[cfg(test)]
+mod tests_payoff_inputs {
+    use super::*;
+    use crate::model::types::{AsianAveragingType, BarrierType, OptionType};
+    use positive::pos_or_panic;
+
+    struct FixedPayoff;
+
+    impl Payoff for FixedPayoff {
+        fn payoff(&self, info: &PayoffInfo) -> f64 {
+            info.spot.to_f64() + info.spot_prices_len().unwrap_or(0) as f64
+        }
+    }
+
+    #[test]
+    fn test_f64_path_matches_payoff_info_path() {
+        let option_types = [
+            OptionType::European,
+            OptionType::Chooser { choice_date: 30.0 },
+            OptionType::Power { exponent: 1.5 },
+            OptionType::Quanto { exchange_rate: 1.2 },
+            OptionType::Barrier {
+                barrier_type: BarrierType::UpAndOut,
+                barrier_level: 120.0,
+                rebate: None,
+            },
+            OptionType::Asian {
+                averaging_type: AsianAveragingType::Geometric,
+            },
+        ];
+        let spot_prices = vec![95.0, 104.0, 112.0];
+        for option_type in option_types {
+            for style in [OptionStyle::Call, OptionStyle::Put] {
+                let info = PayoffInfo {
+                    spot: pos_or_panic!(112.0),
+                    strike: Positive::HUNDRED,
+                    style,
+                    side: Side::Long,
+                    spot_prices: Some(spot_prices.clone()),
+                    spot_min: Some(95.0),
+                    spot_max: Some(112.0),
+                };
+                let inputs = PayoffInputs::new(112.0, 100.0, style, Side::Long)
+                    .with_spot_prices(&spot_prices)
+                    .with_extremes(95.0, 112.0);
+                assert_eq!(PayoffInputs::from(&info), inputs);
+                assert_eq!(option_type.payoff(&info), option_type.payoff_f64(&inputs));
+            }
+        }
+    }
+
+    #[test]
+    fn test_chooser_payoff_stays_in_f64() {
+        let chooser = OptionType::Chooser { choice_date: 30.0 };
+        let call = PayoffInputs::new(110.5, 100.0, OptionStyle::Call, Side::Long);
+        let put = PayoffInputs::new(89.5, 100.0, OptionStyle::Call, Side::Long);
+        assert_eq!(chooser.payoff_f64(&call), 10.5);
+        assert_eq!(chooser.payoff_f64(&put), 10.5);
+    }
+
+    #[test]
+    fn test_default_payoff_f64_delegates_to_payoff() {
+        let spot_prices = [1.0, 2.0];
+        let inputs = PayoffInputs::new(50.0, 40.0, OptionStyle::Call, Side::Long)
+            .with_spot_prices(&spot_prices);
+        assert_eq!(FixedPayoff.payoff_f64(&inputs), 52.0);
+    }
+}
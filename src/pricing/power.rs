@@ -66,7 +66,7 @@ pub fn power_black_scholes(option: &Options) -> Result<Decimal, PricingError> {
     let r = option.risk_free_rate;
     let q = Decimal::from(option.dividend_yield);
     let sigma = Decimal::from(option.implied_volatility);
-    let t = Decimal::from(option.expiration_date.get_years()?);
+    let t = Decimal::from(option.time_to_expiration()?);
 
     let price = power_price(s, k, r, q, sigma, t, n, &option.option_style)?;
 
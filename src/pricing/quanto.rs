@@ -69,8 +69,8 @@ pub fn quanto_black_scholes(option: &Options) -> Result<Decimal, PricingError> {
         .ok_or_else(|| PricingError::other("Missing quanto_fx_volatility"))?;
 
     let rho = params
-        .quanto_fx_correlation
-        .ok_or_else(|| PricingError::other("Missing quanto_fx_correlation"))?;
+        .second_asset_correlation()
+        .ok_or_else(|| PricingError::other("Missing correlation"))?;
 
     if rho < dec!(-1.0) || rho > dec!(1.0) {
         return Err(PricingError::other("Correlation must be between -1 and 1"));
@@ -81,7 +81,7 @@ pub fn quanto_black_scholes(option: &Options) -> Result<Decimal, PricingError> {
     let r_d = option.risk_free_rate;
     let q = Decimal::from(option.dividend_yield);
     let sigma_s = Decimal::from(option.implied_volatility);
-    let t = Decimal::from(option.expiration_date.get_years()?);
+    let t = Decimal::from(option.time_to_expiration()?);
 
     if t <= dec!(0.0) {
         let intrinsic = match option.option_style {
@@ -167,6 +167,7 @@ fn apply_side(price: Decimal, option: &Options) -> Decimal {
 mod tests {
     use super::*;
     use crate::ExpirationDate;
+    use crate::model::CorrelationMatrix;
     use crate::model::option::ExoticParams;
     use positive::{Positive, pos_or_panic};
     use rust_decimal_macros::dec;
@@ -197,16 +198,13 @@ mod tests {
                 rainbow_second_asset_price: None,
                 rainbow_second_asset_volatility: None,
                 rainbow_second_asset_dividend: None,
-                rainbow_correlation: None,
                 spread_second_asset_volatility: None,
                 spread_second_asset_dividend: None,
-                spread_correlation: None,
                 quanto_fx_volatility: Some(pos_or_panic!(0.1)),
-                quanto_fx_correlation: Some(dec!(0.3)),
                 quanto_foreign_rate: Some(dec!(0.03)),
                 exchange_second_asset_volatility: None,
                 exchange_second_asset_dividend: None,
-                exchange_correlation: None,
+                correlation: Some(CorrelationMatrix::pair("AAA", "FX", dec!(0.3)).unwrap()),
             }),
         )
     }
@@ -237,7 +235,7 @@ mod tests {
     fn test_quanto_zero_correlation() {
         let mut option = create_quanto_option(OptionStyle::Call);
         if let Some(ref mut params) = option.exotic_params {
-            params.quanto_fx_correlation = Some(dec!(0.0));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "FX", dec!(0.0)).unwrap());
         }
 
         let price = quanto_black_scholes(&option).unwrap();
@@ -251,12 +249,12 @@ mod tests {
     fn test_quanto_positive_correlation_reduces_call() {
         let mut low_corr = create_quanto_option(OptionStyle::Call);
         if let Some(ref mut params) = low_corr.exotic_params {
-            params.quanto_fx_correlation = Some(dec!(0.0));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "FX", dec!(0.0)).unwrap());
         }
 
         let mut high_corr = create_quanto_option(OptionStyle::Call);
         if let Some(ref mut params) = high_corr.exotic_params {
-            params.quanto_fx_correlation = Some(dec!(0.8));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "FX", dec!(0.8)).unwrap());
         }
 
         let low_price = quanto_black_scholes(&low_corr).unwrap();
@@ -272,12 +270,12 @@ mod tests {
     fn test_quanto_negative_correlation_increases_call() {
         let mut zero_corr = create_quanto_option(OptionStyle::Call);
         if let Some(ref mut params) = zero_corr.exotic_params {
-            params.quanto_fx_correlation = Some(dec!(0.0));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "FX", dec!(0.0)).unwrap());
         }
 
         let mut neg_corr = create_quanto_option(OptionStyle::Call);
         if let Some(ref mut params) = neg_corr.exotic_params {
-            params.quanto_fx_correlation = Some(dec!(-0.5));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "FX", dec!(-0.5)).unwrap());
         }
 
         let zero_price = quanto_black_scholes(&zero_corr).unwrap();
@@ -293,7 +291,7 @@ mod tests {
     fn test_quanto_invalid_correlation() {
         let mut option = create_quanto_option(OptionStyle::Call);
         if let Some(ref mut params) = option.exotic_params {
-            params.quanto_fx_correlation = Some(dec!(1.5));
+            params.correlation = Some(CorrelationMatrix::unchecked_pair("AAA", "FX", dec!(1.5)));
         }
 
         let result = quanto_black_scholes(&option);
@@ -108,7 +108,7 @@ fn price_two_asset_rainbow(
         .unwrap_or(option.dividend_yield)
         .to_dec();
 
-    let rho = params.rainbow_correlation.unwrap_or(dec!(0.5));
+    let rho = params.second_asset_correlation().unwrap_or(dec!(0.5));
 
     if rho < dec!(-1.0) || rho > dec!(1.0) {
         return Err(PricingError::other(
@@ -118,8 +118,7 @@ fn price_two_asset_rainbow(
 
     let r = option.risk_free_rate;
     let t = option
-        .expiration_date
-        .get_years()
+        .time_to_expiration()
         .map_err(|e| PricingError::other(&e.to_string()))?
         .to_dec();
 
@@ -423,6 +422,7 @@ fn apply_side(price: Decimal, option: &Options) -> Decimal {
 mod tests {
     use super::*;
     use crate::ExpirationDate;
+    use crate::model::CorrelationMatrix;
     use crate::model::option::ExoticParams;
     use positive::{Positive, pos_or_panic};
     use rust_decimal_macros::dec;
@@ -454,16 +454,13 @@ mod tests {
                 rainbow_second_asset_price: Some(pos_or_panic!(100.0)),
                 rainbow_second_asset_volatility: Some(pos_or_panic!(0.25)),
                 rainbow_second_asset_dividend: Some(Positive::ZERO),
-                rainbow_correlation: Some(dec!(0.5)),
                 spread_second_asset_volatility: None,
                 spread_second_asset_dividend: None,
-                spread_correlation: None,
                 quanto_fx_volatility: None,
-                quanto_fx_correlation: None,
                 quanto_foreign_rate: None,
                 exchange_second_asset_volatility: None,
                 exchange_second_asset_dividend: None,
-                exchange_correlation: None,
+                correlation: Some(CorrelationMatrix::pair("AAA", "BBB", dec!(0.5)).unwrap()),
             }),
         )
     }
@@ -522,12 +519,12 @@ mod tests {
     fn test_rainbow_correlation_impact() {
         let mut low_corr = create_rainbow_option(RainbowType::BestOf, OptionStyle::Call);
         if let Some(ref mut params) = low_corr.exotic_params {
-            params.rainbow_correlation = Some(dec!(0.0));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "BBB", dec!(0.0)).unwrap());
         }
 
         let mut high_corr = create_rainbow_option(RainbowType::BestOf, OptionStyle::Call);
         if let Some(ref mut params) = high_corr.exotic_params {
-            params.rainbow_correlation = Some(dec!(0.9));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "BBB", dec!(0.9)).unwrap());
         }
 
         let low_price = rainbow_black_scholes(&low_corr).unwrap();
@@ -543,7 +540,7 @@ mod tests {
     fn test_rainbow_invalid_correlation() {
         let mut option = create_rainbow_option(RainbowType::BestOf, OptionStyle::Call);
         if let Some(ref mut params) = option.exotic_params {
-            params.rainbow_correlation = Some(dec!(1.5));
+            params.correlation = Some(CorrelationMatrix::unchecked_pair("AAA", "BBB", dec!(1.5)));
         }
 
         let result = rainbow_black_scholes(&option);
@@ -68,8 +68,8 @@ pub fn spread_black_scholes(option: &Options) -> Result<Decimal, PricingError> {
         .unwrap_or(positive::Positive::ZERO);
 
     let rho = params
-        .spread_correlation
-        .ok_or_else(|| PricingError::other("Missing spread_correlation"))?;
+        .second_asset_correlation()
+        .ok_or_else(|| PricingError::other("Missing correlation"))?;
 
     if rho < dec!(-1.0) || rho > dec!(1.0) {
         return Err(PricingError::other("Correlation must be between -1 and 1"));
@@ -81,7 +81,7 @@ pub fn spread_black_scholes(option: &Options) -> Result<Decimal, PricingError> {
     let r = option.risk_free_rate;
     let q1 = Decimal::from(option.dividend_yield);
     let sigma1 = Decimal::from(option.implied_volatility);
-    let t = Decimal::from(option.expiration_date.get_years()?);
+    let t = Decimal::from(option.time_to_expiration()?);
 
     let price = if k.abs() < dec!(0.0001) {
         margrabe_formula(
@@ -252,6 +252,7 @@ fn apply_side(price: Decimal, option: &Options) -> Decimal {
 mod tests {
     use super::*;
     use crate::ExpirationDate;
+    use crate::model::CorrelationMatrix;
     use crate::model::option::ExoticParams;
     use positive::{Positive, pos_or_panic};
     use rust_decimal_macros::dec;
@@ -282,16 +283,13 @@ mod tests {
                 rainbow_second_asset_price: None,
                 rainbow_second_asset_volatility: None,
                 rainbow_second_asset_dividend: None,
-                rainbow_correlation: None,
                 spread_second_asset_volatility: Some(pos_or_panic!(0.25)),
                 spread_second_asset_dividend: Some(Positive::ZERO),
-                spread_correlation: Some(dec!(0.5)),
                 quanto_fx_volatility: None,
-                quanto_fx_correlation: None,
                 quanto_foreign_rate: None,
                 exchange_second_asset_volatility: None,
                 exchange_second_asset_dividend: None,
-                exchange_correlation: None,
+                correlation: Some(CorrelationMatrix::pair("AAA", "BBB", dec!(0.5)).unwrap()),
             }),
         )
     }
@@ -342,12 +340,12 @@ mod tests {
     fn test_spread_correlation_impact() {
         let mut low_corr = create_spread_option(pos_or_panic!(5.0), OptionStyle::Call);
         if let Some(ref mut params) = low_corr.exotic_params {
-            params.spread_correlation = Some(dec!(0.0));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "BBB", dec!(0.0)).unwrap());
         }
 
         let mut high_corr = create_spread_option(pos_or_panic!(5.0), OptionStyle::Call);
         if let Some(ref mut params) = high_corr.exotic_params {
-            params.spread_correlation = Some(dec!(0.9));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "BBB", dec!(0.9)).unwrap());
         }
 
         let low_price = spread_black_scholes(&low_corr).unwrap();
@@ -363,7 +361,7 @@ mod tests {
     fn test_spread_invalid_correlation() {
         let mut option = create_spread_option(pos_or_panic!(5.0), OptionStyle::Call);
         if let Some(ref mut params) = option.exotic_params {
-            params.spread_correlation = Some(dec!(1.5));
+            params.correlation = Some(CorrelationMatrix::unchecked_pair("AAA", "BBB", dec!(1.5)));
         }
 
         let result = spread_black_scholes(&option);
@@ -462,7 +460,7 @@ mod tests {
     fn test_spread_negative_correlation() {
         let mut option = create_spread_option(pos_or_panic!(5.0), OptionStyle::Call);
         if let Some(ref mut params) = option.exotic_params {
-            params.spread_correlation = Some(dec!(-0.5));
+            params.correlation = Some(CorrelationMatrix::pair("AAA", "BBB", dec!(-0.5)).unwrap());
         }
 
         let price = spread_black_scholes(&option).unwrap();
@@ -80,8 +80,8 @@ use crate::Options;
 use crate::error::PricingError;
 use crate::error::decimal::DecimalError;
 use crate::prelude::simulate_returns;
+use crate::simulation::rng::uniform;
 use num_traits::{FromPrimitive, ToPrimitive};
-use rand::random;
 use rust_decimal::{Decimal, MathematicalOps};
 use rust_decimal_macros::dec;
 
@@ -127,7 +127,7 @@ impl TelegraphProcess {
     ///
     /// A new TelegraphProcess with a randomly chosen initial state.
     pub fn new(lambda_up: Decimal, lambda_down: Decimal) -> Self {
-        let initial_state = if random::<f64>() < 0.5 { 1 } else { -1 };
+        let initial_state = if uniform() < 0.5 { 1 } else { -1 };
         TelegraphProcess {
             lambda_up,
             lambda_down,
@@ -157,7 +157,7 @@ impl TelegraphProcess {
             Decimal::ONE - lambda_dt.exp()
         };
 
-        if random::<f64>() < probability.to_f64().unwrap() {
+        if uniform() < probability.to_f64().unwrap() {
             self.current_state *= -1;
         }
 
@@ -330,7 +330,7 @@ pub fn telegraph(
         let volatility: Decimal =
             option.implied_volatility.to_dec() * Decimal::from_f64(state as f64).unwrap();
 
-        let rh = Decimal::from_f64(dt.sqrt().unwrap().to_f64().unwrap() * random::<f64>()).unwrap();
+        let rh = Decimal::from_f64(dt.sqrt().unwrap().to_f64().unwrap() * uniform()).unwrap();
         let lhs = drift * dt + volatility;
 
         let update = (lhs * rh).exp();
@@ -347,7 +347,7 @@ mod tests_telegraph_process_basis {
     use super::*;
     use positive::{Positive, pos_or_panic};
 
-    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
     use rust_decimal_macros::dec;
 
     #[test]
@@ -408,6 +408,8 @@ mod tests_telegraph_process_basis {
             expiration_date: Default::default(),
             quantity: Positive::ONE,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
 
         let _price = telegraph(&option, 1000, Some(dec!(0.7)), Some(dec!(0.5)));
@@ -421,7 +423,7 @@ mod tests_telegraph_process_extended {
     use super::*;
     use positive::{Positive, pos_or_panic};
 
-    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 
     use rust_decimal_macros::dec;
 
@@ -440,6 +442,8 @@ mod tests_telegraph_process_extended {
             expiration_date: Default::default(),
             quantity: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
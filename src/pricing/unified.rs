@@ -1,6 +1,8 @@
 use crate::Options;
 use crate::error::{PricingError, PricingResult};
-use crate::pricing::black_scholes_model::black_scholes;
+use crate::pricing::black_scholes_model::{black_scholes, black_scholes_as};
+use crate::pricing::monte_carlo::price_option_monte_carlo_as;
+use crate::pricing::numeric::Numeric;
 use crate::simulation::simulator::Simulator;
 use positive::Positive;
 
@@ -47,7 +49,7 @@ pub enum PricingEngine {
 /// use optionstratlib::pricing::{PricingEngine, price_option};
 /// use positive::{Positive, pos_or_panic};
 /// use optionstratlib::{ExpirationDate, Options};
-/// use optionstratlib::model::types::{OptionStyle, OptionType, Side};
+/// use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 /// use rust_decimal_macros::dec;
 ///
 /// let option = Options {
@@ -63,6 +65,8 @@ pub enum PricingEngine {
 ///     option_style: OptionStyle::Call,
 ///     dividend_yield: pos_or_panic!(0.01),
 ///     exotic_params: None,
+///     settlement_type: SettlementType::Physical,
+///     day_count: DayCount::Act365Fixed,
 /// };
 /// let engine = PricingEngine::ClosedFormBS;
 /// let price = price_option(&option, &engine)?;
@@ -83,6 +87,62 @@ pub fn price_option(option: &Options, engine: &PricingEngine) -> PricingResult<P
     }
 }
 
+/// Prices an option using the specified pricing engine on the numeric
+/// backend `T`.
+///
+/// The generic counterpart of [`price_option`]: choose `f64` for speed when
+/// pricing inside simulations or sweeps, and `Decimal` for exact reporting.
+/// The closed-form engine runs [`black_scholes_as`] and the Monte Carlo
+/// engine averages the simulated final prices with
+/// [`price_option_monte_carlo_as`], both entirely in `T`.
+///
+/// As with [`price_option`], the absolute value of the price is returned.
+///
+/// # Errors
+///
+/// Returns a `PricingError` if the selected engine fails.
+///
+/// # Examples
+///
+/// ```rust
+/// use optionstratlib::pricing::{PricingEngine, price_option_as};
+/// use optionstratlib::{ExpirationDate, OptionStyle, OptionType, Options, Side};
+/// use positive::{Positive, pos_or_panic};
+/// use rust_decimal::Decimal;
+/// use rust_decimal_macros::dec;
+///
+/// let option = Options::new(
+///     OptionType::European,
+///     Side::Long,
+///     "AAPL".to_string(),
+///     Positive::HUNDRED,
+///     ExpirationDate::Days(pos_or_panic!(30.0)),
+///     pos_or_panic!(0.2),
+///     Positive::ONE,
+///     pos_or_panic!(105.0),
+///     dec!(0.05),
+///     OptionStyle::Call,
+///     pos_or_panic!(0.01),
+///     None,
+/// );
+/// let engine = PricingEngine::ClosedFormBS;
+/// let fast = price_option_as::<f64>(&option, &engine)?;
+/// let exact = price_option_as::<Decimal>(&option, &engine)?;
+/// assert!((fast - exact.to_string().parse::<f64>().unwrap()).abs() < 1e-9);
+/// # Ok::<(), optionstratlib::error::PricingError>(())
+/// ```
+pub fn price_option_as<T: Numeric>(option: &Options, engine: &PricingEngine) -> PricingResult<T> {
+    let price = match engine {
+        PricingEngine::ClosedFormBS => black_scholes_as::<T>(option)
+            .map_err(|e| PricingError::method_error("Black-Scholes", &e.to_string()))?,
+        PricingEngine::MonteCarlo { simulator } => {
+            price_option_monte_carlo_as::<T>(option, &simulator.get_last_positive_values())
+                .map_err(|e| PricingError::simulation_error(&e.to_string()))?
+        }
+    };
+    Ok(price.magnitude())
+}
+
 /// Trait for types that can be priced using a pricing engine.
 ///
 /// This trait provides a unified interface for pricing financial instruments.
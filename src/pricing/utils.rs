@@ -6,16 +6,17 @@
 use crate::Options;
 
 use crate::error::decimal::DecimalError;
+use crate::f2du;
 use crate::greeks::{big_n, d2};
 use crate::model::types::Side;
 use crate::pricing::binomial_model::BinomialPricingParams;
 use crate::pricing::constants::{CLAMP_MAX, CLAMP_MIN};
-use crate::pricing::payoff::{Payoff, PayoffInfo};
+use crate::pricing::payoff::{Payoff, PayoffInputs};
+use crate::simulation::rng::{seeded_rng, standard_normal};
 use crate::utils::random_decimal;
 use num_traits::FromPrimitive;
 use positive::Positive;
 use rand::Rng;
-use rand_distr::{Distribution, Normal};
 use rust_decimal::{Decimal, MathematicalOps};
 use rust_decimal_macros::dec;
 
@@ -72,7 +73,7 @@ pub fn simulate_returns(
     }
 
     let mut returns = Vec::with_capacity(length);
-    let mut rng = rand::rng();
+    let mut rng = seeded_rng(None);
 
     // Generate pairs of normally distributed random numbers using Box-Muller transform
     for _ in 0..length.div_ceil(2) {
@@ -242,16 +243,13 @@ pub(crate) fn calculate_option_price(
     d: Decimal,
     i: usize,
 ) -> Result<Decimal, DecimalError> {
-    let info = PayoffInfo {
-        spot: params.asset * u.powu(i as u64) * d.powi((params.no_steps - i) as i64),
-        strike: params.strike,
-        style: *params.option_style,
-        side: *params.side,
-        spot_prices: None,
-        spot_min: None,
-        spot_max: None,
-    };
-    let payoff = Decimal::from_f64(params.option_type.payoff(&info)).unwrap();
+    let inputs = PayoffInputs::new(
+        (params.asset * u.powu(i as u64) * d.powi((params.no_steps - i) as i64)).to_f64(),
+        params.strike.to_f64(),
+        *params.option_style,
+        *params.side,
+    );
+    let payoff = f2du!(params.option_type.payoff_f64(&inputs))?;
 
     Ok(payoff)
 }
@@ -276,17 +274,14 @@ pub(crate) fn calculate_option_price(
 pub(crate) fn calculate_discounted_payoff(
     params: BinomialPricingParams,
 ) -> Result<Decimal, DecimalError> {
-    let info = PayoffInfo {
-        spot: params.asset * (params.int_rate * params.expiry).exp(),
-        strike: params.strike,
-        style: *params.option_style,
-        side: *params.side,
-        spot_prices: None,
-        spot_min: None,
-        spot_max: None,
-    };
-
-    let payoff = Decimal::from_f64(params.option_type.payoff(&info)).unwrap();
+    let inputs = PayoffInputs::new(
+        (params.asset * (params.int_rate * params.expiry).exp()).to_f64(),
+        params.strike.to_f64(),
+        *params.option_style,
+        *params.side,
+    );
+
+    let payoff = f2du!(params.option_type.payoff_f64(&inputs))?;
     let discounted_payoff = (-params.int_rate * params.expiry).exp() * payoff;
     match params.side {
         Side::Long => Ok(discounted_payoff),
@@ -315,10 +310,7 @@ pub(crate) fn calculate_discounted_payoff(
 /// highly unlikely with valid inputs.
 ///
 pub(crate) fn wiener_increment(dt: Decimal) -> Result<Decimal, DecimalError> {
-    let normal = Normal::new(0.0, 1.0).unwrap();
-    let mut rng = rand::rng();
-
-    let sample = Decimal::from_f64(normal.sample(&mut rng)).unwrap();
+    let sample = Decimal::from_f64(standard_normal()).unwrap_or(Decimal::ZERO);
 
     Ok(sample * dt.sqrt().unwrap())
 }
@@ -346,7 +338,7 @@ pub fn probability_keep_under_strike(
             option.underlying_price,
             strike_price,
             option.risk_free_rate,
-            option.expiration_date.get_years().unwrap(),
+            option.time_to_expiration().unwrap(),
             option.implied_volatility,
         )
         .unwrap(),
@@ -566,7 +558,7 @@ mod tests_probability_keep_under_strike {
     use super::*;
     use positive::{Positive, pos_or_panic, spos};
 
-    use crate::model::types::{OptionStyle, OptionType};
+    use crate::model::types::{DayCount, OptionStyle, OptionType, SettlementType};
     use crate::{ExpirationDate, assert_decimal_eq};
     use positive::constants::DAYS_IN_A_YEAR;
     use rust_decimal_macros::dec;
@@ -587,6 +579,8 @@ mod tests_probability_keep_under_strike {
             underlying_symbol: "".to_string(),
             quantity: Positive::ONE,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
         let strike = spos!(100.0);
         let probability = probability_keep_under_strike(option, strike).unwrap();
@@ -609,6 +603,8 @@ mod tests_probability_keep_under_strike {
             underlying_symbol: "".to_string(),
             quantity: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
         let strike = None;
         let probability = probability_keep_under_strike(option, strike).unwrap();
@@ -634,6 +630,8 @@ mod tests_probability_keep_under_strike {
             underlying_symbol: "".to_string(),
             quantity: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
         let strike = None;
         let _ = probability_keep_under_strike(option, strike);
@@ -654,6 +652,8 @@ mod tests_probability_keep_under_strike {
             underlying_symbol: "".to_string(),
             quantity: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
         let strike = None;
         let probability = probability_keep_under_strike(option, strike).unwrap();
@@ -678,6 +678,8 @@ mod tests_probability_keep_under_strike {
             underlying_symbol: "".to_string(),
             quantity: Positive::ZERO,
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         };
         let strike = None;
         let probability = probability_keep_under_strike(option, strike).unwrap();
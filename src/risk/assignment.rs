@@ -0,0 +1,362 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Early-Assignment Risk
+//!
+//! Flags the short option legs whose holders have a reason to exercise before
+//! expiration:
+//!
+//! * in-the-money calls ahead of an ex-dividend date, when the dividend is
+//!   larger than the extrinsic value the holder gives up by exercising;
+//! * deep in-the-money puts, when the interest earned on the strike until
+//!   expiration (the cost of carry) is larger than the extrinsic value.
+//!
+//! The extrinsic value is the Black-Scholes value of holding the option to
+//! expiration less its intrinsic value. Listed equity options are modelled as
+//! European in this crate, so both European and American legs are analysed;
+//! exotic legs are skipped.
+//!
+//! Every flagged leg carries a probability of early assignment, which grows as
+//! the early-exercise benefit overtakes the extrinsic value, and a severity,
+//! the value transferred to the holder if the leg is assigned.
+//!
+//! Dividends can be added one by one or taken from the ex-dividend events of
+//! an [`EventTimeline`] with
+//! [`EarlyAssignmentAnalysis::with_dividend_schedule`].
+
+use crate::error::OptionsError;
+use crate::model::events::{EventTimeline, MarketEventKind};
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, OptionType};
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Upcoming dividend of an underlying.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DividendEvent {
+    /// Symbol of the underlying paying the dividend.
+    pub underlying_symbol: String,
+    /// Days until the ex-dividend date.
+    pub days_to_ex_date: Positive,
+    /// Dividend per share.
+    pub amount: Positive,
+}
+
+impl DividendEvent {
+    /// Creates a new dividend event.
+    pub fn new(underlying_symbol: &str, days_to_ex_date: Positive, amount: Positive) -> Self {
+        Self {
+            underlying_symbol: underlying_symbol.to_string(),
+            days_to_ex_date,
+            amount,
+        }
+    }
+}
+
+/// Reason a holder may exercise a leg early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum AssignmentTrigger {
+    /// An in-the-money call ahead of an ex-dividend date.
+    Dividend,
+    /// A deep in-the-money put whose strike earns more interest than its
+    /// extrinsic value.
+    CostOfCarry,
+}
+
+/// Early-assignment risk of a short leg.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AssignmentRisk {
+    /// Index of the leg in the analysed positions.
+    pub leg: usize,
+    /// Symbol of the underlying.
+    pub underlying_symbol: String,
+    /// Style of the option.
+    pub option_style: OptionStyle,
+    /// Strike price of the option.
+    pub strike_price: Positive,
+    /// Reason for the early exercise.
+    pub trigger: AssignmentTrigger,
+    /// Extrinsic value per unit given up by exercising.
+    pub extrinsic_value: Decimal,
+    /// Benefit per unit of exercising early: the dividend or the cost of carry.
+    pub exercise_benefit: Decimal,
+    /// Probability of early assignment, between 0 and 1.
+    pub probability: Decimal,
+    /// Value transferred to the holder if the leg is assigned, scaled by quantity.
+    pub severity: Decimal,
+}
+
+impl AssignmentRisk {
+    /// Expected cost of early assignment: probability times severity.
+    pub fn score(&self) -> Decimal {
+        self.probability * self.severity
+    }
+}
+
+/// Early-assignment analysis of short option legs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct EarlyAssignmentAnalysis {
+    /// Upcoming dividends of the underlyings.
+    pub dividends: Vec<DividendEvent>,
+}
+
+impl EarlyAssignmentAnalysis {
+    /// Creates an analysis without dividends.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an upcoming dividend.
+    pub fn with_dividend(mut self, dividend: DividendEvent) -> Self {
+        self.dividends.push(dividend);
+        self
+    }
+
+    /// Adds the dividends of the ex-dividend events of `timeline` after
+    /// `as_of`. Events without a symbol or an amount are skipped.
+    pub fn with_dividend_schedule(
+        mut self,
+        timeline: &EventTimeline,
+        as_of: DateTime<Utc>,
+    ) -> Self {
+        for event in timeline.events() {
+            if event.kind != MarketEventKind::ExDividend || event.timestamp <= as_of {
+                continue;
+            }
+            if let (Some(symbol), Some(amount)) = (&event.symbol, event.amount) {
+                let seconds = Decimal::from((event.timestamp - as_of).num_seconds());
+                let days = Positive::new_decimal(seconds / Decimal::from(86_400))
+                    .unwrap_or(Positive::ZERO);
+                self.dividends
+                    .push(DividendEvent::new(symbol, days, amount));
+            }
+        }
+        self
+    }
+
+    /// Analyses the short legs of `positions` and returns the flagged ones,
+    /// highest score first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if a leg cannot be priced or its expiration
+    /// cannot be resolved.
+    pub fn analyze(&self, positions: &[&Position]) -> Result<Vec<AssignmentRisk>, OptionsError> {
+        let mut risks = Vec::new();
+        for (leg, position) in positions.iter().enumerate() {
+            if let Some(risk) = self.analyze_leg(leg, position)? {
+                risks.push(risk);
+            }
+        }
+        risks.sort_by_key(|risk| std::cmp::Reverse(risk.score()));
+        Ok(risks)
+    }
+
+    fn analyze_leg(
+        &self,
+        leg: usize,
+        position: &Position,
+    ) -> Result<Option<AssignmentRisk>, OptionsError> {
+        let option = &position.option;
+        if !option.is_short()
+            || !option.is_in_the_money()
+            || !matches!(
+                option.option_type,
+                OptionType::European | OptionType::American
+            )
+        {
+            return Ok(None);
+        }
+
+        let mut european = option.clone();
+        european.option_type = OptionType::European;
+        let spot = option.underlying_price.to_dec();
+        let strike = option.strike_price.to_dec();
+        let intrinsic = match option.option_style {
+            OptionStyle::Call => spot - strike,
+            OptionStyle::Put => strike - spot,
+        };
+        let extrinsic =
+            (european.calculate_price_black_scholes()?.abs() - intrinsic).max(Decimal::ZERO);
+
+        let (trigger, benefit) = match option.option_style {
+            OptionStyle::Call => {
+                let days_to_expiration = option.expiration_date.get_days()?;
+                let dividend = self
+                    .dividends
+                    .iter()
+                    .filter(|dividend| {
+                        dividend.underlying_symbol == option.underlying_symbol
+                            && dividend.days_to_ex_date <= days_to_expiration
+                    })
+                    .map(|dividend| dividend.amount.to_dec())
+                    .max();
+                match dividend {
+                    Some(dividend) => (AssignmentTrigger::Dividend, dividend),
+                    None => return Ok(None),
+                }
+            }
+            OptionStyle::Put => {
+                let years = option.expiration_date.get_years()?.to_dec();
+                let carry = strike * option.risk_free_rate * years;
+                (AssignmentTrigger::CostOfCarry, carry)
+            }
+        };
+        if benefit <= extrinsic {
+            return Ok(None);
+        }
+
+        Ok(Some(AssignmentRisk {
+            leg,
+            underlying_symbol: option.underlying_symbol.clone(),
+            option_style: option.option_style,
+            strike_price: option.strike_price,
+            trigger,
+            extrinsic_value: extrinsic,
+            exercise_benefit: benefit,
+            probability: Decimal::ONE - extrinsic / benefit,
+            severity: (benefit - extrinsic) * option.quantity.to_dec(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests_assignment {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::events::MarketEvent;
+    use crate::model::option::Options;
+    use crate::model::types::Side;
+    use chrono::Utc;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn position(style: OptionStyle, side: Side, strike: Positive, days: Positive) -> Position {
+        let option = Options::new(
+            OptionType::American,
+            side,
+            "XYZ".to_string(),
+            strike,
+            ExpirationDate::Days(days),
+            pos_or_panic!(0.2),
+            Positive::HUNDRED,
+            Positive::HUNDRED,
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            Positive::ONE,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_call_ahead_of_dividend() {
+        let call = position(
+            OptionStyle::Call,
+            Side::Short,
+            pos_or_panic!(80.0),
+            pos_or_panic!(30.0),
+        );
+        let analysis = EarlyAssignmentAnalysis::new().with_dividend(DividendEvent::new(
+            "XYZ",
+            pos_or_panic!(5.0),
+            pos_or_panic!(1.5),
+        ));
+        let risks = analysis.analyze(&[&call]).unwrap();
+
+        assert_eq!(risks.len(), 1);
+        let risk = &risks[0];
+        assert_eq!(risk.trigger, AssignmentTrigger::Dividend);
+        assert_eq!(risk.exercise_benefit, dec!(1.5));
+        assert!(risk.extrinsic_value < dec!(1.5));
+        assert!(risk.probability > Decimal::ZERO && risk.probability <= Decimal::ONE);
+        assert_eq!(
+            risk.severity,
+            (dec!(1.5) - risk.extrinsic_value) * dec!(100)
+        );
+
+        // A dividend after expiration, a small dividend or a long leg are not flagged.
+        let late = EarlyAssignmentAnalysis::new().with_dividend(DividendEvent::new(
+            "XYZ",
+            pos_or_panic!(45.0),
+            pos_or_panic!(1.5),
+        ));
+        assert!(late.analyze(&[&call]).unwrap().is_empty());
+        let small = EarlyAssignmentAnalysis::new().with_dividend(DividendEvent::new(
+            "XYZ",
+            pos_or_panic!(5.0),
+            pos_or_panic!(0.01),
+        ));
+        assert!(small.analyze(&[&call]).unwrap().is_empty());
+        let long = position(
+            OptionStyle::Call,
+            Side::Long,
+            pos_or_panic!(80.0),
+            pos_or_panic!(30.0),
+        );
+        assert!(analysis.analyze(&[&long]).unwrap().is_empty());
+
+        let now = Utc::now();
+        let timeline = EventTimeline::new()
+            .with_event(MarketEvent::ex_dividend(
+                "XYZ",
+                now + chrono::Duration::days(5),
+                pos_or_panic!(1.5),
+            ))
+            .with_event(MarketEvent::earnings(
+                "XYZ",
+                now + chrono::Duration::days(6),
+            ));
+        let scheduled = EarlyAssignmentAnalysis::new().with_dividend_schedule(&timeline, now);
+        assert_eq!(
+            scheduled.dividends,
+            vec![DividendEvent::new(
+                "XYZ",
+                pos_or_panic!(5.0),
+                pos_or_panic!(1.5)
+            )]
+        );
+        assert_eq!(scheduled.analyze(&[&call]).unwrap(), risks);
+    }
+
+    #[test]
+    fn test_deep_itm_put_cost_of_carry() {
+        let deep = position(
+            OptionStyle::Put,
+            Side::Short,
+            pos_or_panic!(150.0),
+            pos_or_panic!(180.0),
+        );
+        let at_the_money = position(
+            OptionStyle::Put,
+            Side::Short,
+            Positive::HUNDRED,
+            pos_or_panic!(180.0),
+        );
+        let risks = EarlyAssignmentAnalysis::new()
+            .analyze(&[&at_the_money, &deep])
+            .unwrap();
+
+        assert_eq!(risks.len(), 1);
+        let risk = &risks[0];
+        assert_eq!(risk.leg, 1);
+        assert_eq!(risk.trigger, AssignmentTrigger::CostOfCarry);
+        assert_eq!(risk.extrinsic_value, Decimal::ZERO);
+        assert_eq!(risk.probability, Decimal::ONE);
+        assert_eq!(risk.score(), risk.severity);
+    }
+}
@@ -0,0 +1,245 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Beta-Weighted Delta
+//!
+//! Options on several underlyings are usually hedged with a single index.
+//! Beta-weighting expresses the delta of every underlying as the number of
+//! index units that move the same amount of money for a given index move:
+//!
+//! ```text
+//! weighted delta = delta × beta × underlying price / index price
+//! ```
+//!
+//! The weighted deltas add up to the delta of the book in index terms, and
+//! the opposite amount of the index neutralises the market exposure of the
+//! book.
+
+use crate::error::OptionsError;
+use crate::greeks::delta;
+use crate::model::leg::SpotPosition;
+use crate::model::position::Position;
+use crate::model::types::Side;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+/// Delta of the positions on one underlying, in index terms.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BetaWeightedDelta {
+    /// Underlying symbol.
+    pub underlying_symbol: String,
+    /// Current price of the underlying.
+    pub underlying_price: Positive,
+    /// Beta of the underlying against the index.
+    pub beta: Decimal,
+    /// Net delta of the positions, in units of the underlying.
+    pub delta: Decimal,
+    /// Net delta of the positions, in units of the index.
+    pub weighted_delta: Decimal,
+}
+
+/// Beta-weighted delta of a book.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BetaWeightedReport {
+    /// Index the deltas are expressed against.
+    pub index_symbol: String,
+    /// Price of the index.
+    pub index_price: Positive,
+    /// Weighted delta of every underlying, in alphabetical order.
+    pub underlyings: Vec<BetaWeightedDelta>,
+    /// Delta of the book in units of the index.
+    pub total_delta: Decimal,
+}
+
+impl BetaWeightedReport {
+    /// Notional of the index with the same market exposure as the book.
+    pub fn dollar_delta(&self) -> Decimal {
+        self.total_delta * self.index_price.to_dec()
+    }
+
+    /// Units of the index to buy (positive) or sell (negative) to make the
+    /// book delta neutral.
+    pub fn hedge_quantity(&self) -> Decimal {
+        -self.total_delta
+    }
+}
+
+/// Betas of the underlyings against an index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BetaWeighting {
+    /// Index the deltas are expressed against.
+    pub index_symbol: String,
+    /// Price of the index.
+    pub index_price: Positive,
+    /// Beta of each underlying. The index itself has a beta of one.
+    pub betas: BTreeMap<String, Decimal>,
+}
+
+impl BetaWeighting {
+    /// Creates a weighting against `index_symbol` trading at `index_price`.
+    pub fn new(index_symbol: &str, index_price: Positive) -> Self {
+        BetaWeighting {
+            index_symbol: index_symbol.to_string(),
+            index_price,
+            betas: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the beta of an underlying.
+    pub fn with_beta(mut self, symbol: &str, beta: Decimal) -> Self {
+        self.betas.insert(symbol.to_string(), beta);
+        self
+    }
+
+    /// Beta of `symbol`, one for the index itself.
+    pub fn beta(&self, symbol: &str) -> Option<Decimal> {
+        self.betas
+            .get(symbol)
+            .copied()
+            .or_else(|| (symbol == self.index_symbol).then_some(Decimal::ONE))
+    }
+
+    /// Beta-weighted delta of the open positions and stock. Stock is valued
+    /// at the underlying price of the options on the same symbol, or at its
+    /// cost basis when there are none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if the index price is zero, an underlying
+    /// has no beta, or the delta of a position cannot be computed.
+    pub fn calculate(
+        &self,
+        positions: &[&Position],
+        stock: &[&SpotPosition],
+    ) -> Result<BetaWeightedReport, OptionsError> {
+        if self.index_price == Positive::ZERO {
+            return Err(OptionsError::OtherError {
+                reason: format!("Price of index {} must be positive", self.index_symbol),
+            });
+        }
+
+        let mut deltas: BTreeMap<&str, (Positive, Decimal)> = BTreeMap::new();
+        for position in positions.iter().filter(|position| position.is_open()) {
+            let option = &position.option;
+            let entry = deltas
+                .entry(option.underlying_symbol.as_str())
+                .or_insert((option.underlying_price, Decimal::ZERO));
+            entry.1 += delta(option)?;
+        }
+        for spot in stock {
+            let entry = deltas
+                .entry(spot.symbol.as_str())
+                .or_insert((spot.cost_basis, Decimal::ZERO));
+            match spot.side {
+                Side::Long => entry.1 += spot.quantity.to_dec(),
+                Side::Short => entry.1 -= spot.quantity.to_dec(),
+            }
+        }
+
+        let underlyings = deltas
+            .into_iter()
+            .map(|(symbol, (price, delta))| {
+                let beta = self.beta(symbol).ok_or_else(|| OptionsError::OtherError {
+                    reason: format!("No beta for {symbol} against {}", self.index_symbol),
+                })?;
+                Ok(BetaWeightedDelta {
+                    underlying_symbol: symbol.to_string(),
+                    underlying_price: price,
+                    beta,
+                    delta,
+                    weighted_delta: delta * beta * price.to_dec() / self.index_price.to_dec(),
+                })
+            })
+            .collect::<Result<Vec<_>, OptionsError>>()?;
+
+        Ok(BetaWeightedReport {
+            index_symbol: self.index_symbol.clone(),
+            index_price: self.index_price,
+            total_delta: underlyings
+                .iter()
+                .map(|underlying| underlying.weighted_delta)
+                .sum(),
+            underlyings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests_beta {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::Options;
+    use crate::model::types::{OptionStyle, OptionType};
+    use chrono::Utc;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn position(symbol: &str, side: Side, price: Positive) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            symbol.to_string(),
+            price,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            price,
+            Decimal::ZERO,
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            Positive::ONE,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_stock_weighted_by_beta_and_price() {
+        let shares = SpotPosition::long("AAA".to_string(), Positive::HUNDRED, pos_or_panic!(50.0));
+        let index = SpotPosition::short("SPX".to_string(), Positive::TEN, pos_or_panic!(500.0));
+        let weighting = BetaWeighting::new("SPX", pos_or_panic!(500.0)).with_beta("AAA", dec!(1.5));
+        let report = weighting.calculate(&[], &[&shares, &index]).unwrap();
+
+        // 100 shares at 50 with beta 1.5 move like 15 units of a 500 index.
+        assert_eq!(report.underlyings[0].weighted_delta, dec!(15));
+        assert_eq!(report.underlyings[1].beta, Decimal::ONE);
+        assert_eq!(report.underlyings[1].weighted_delta, dec!(-10));
+        assert_eq!(report.total_delta, dec!(5));
+        assert_eq!(report.hedge_quantity(), dec!(-5));
+        assert_eq!(report.dollar_delta(), dec!(2500));
+    }
+
+    #[test]
+    fn test_options_and_missing_beta() {
+        let long = position("AAA", Side::Long, Positive::HUNDRED);
+        let short = position("BBB", Side::Short, pos_or_panic!(200.0));
+        let weighting = BetaWeighting::new("SPX", pos_or_panic!(400.0))
+            .with_beta("AAA", dec!(2))
+            .with_beta("BBB", dec!(0.5));
+        let report = weighting.calculate(&[&long, &short], &[]).unwrap();
+
+        let aaa = &report.underlyings[0];
+        assert!(aaa.delta > Decimal::ZERO);
+        assert_eq!(
+            aaa.weighted_delta,
+            aaa.delta * dec!(2) * dec!(100) / dec!(400)
+        );
+        assert!(report.underlyings[1].weighted_delta < Decimal::ZERO);
+
+        let missing = BetaWeighting::new("SPX", pos_or_panic!(400.0));
+        assert!(missing.calculate(&[&long], &[]).is_err());
+    }
+}
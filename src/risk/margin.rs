@@ -0,0 +1,647 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Reg-T Margin
+//!
+//! Initial and maintenance requirements for option positions under the
+//! strategy-based rules of Regulation T, following the CBOE margin manual:
+//!
+//! * long options are paid in full and carry no maintenance requirement,
+//! * naked short options require the option proceeds plus 20% of the
+//!   underlying value less the out-of-the-money amount, with a floor of 10%
+//!   of the underlying value for calls and of the strike for puts,
+//! * credit spreads require the difference between the strikes and debit
+//!   spreads the net debit, as long as the long leg does not expire before the
+//!   short one,
+//! * short straddles and strangles require the larger naked requirement plus
+//!   the proceeds of the other side, and iron condors the larger of their two
+//!   credit spreads,
+//! * short calls covered by long stock, and short puts covered by short
+//!   stock, add nothing to the requirement of the stock.
+//!
+//! [`RegTMargin::calculate`] pairs the legs of each underlying into the
+//! cheapest of these treatments in that order: covered, spread, straddle and
+//! finally naked. [`MarginReport::buying_power_effect`] applies the option
+//! proceeds to the requirement. Option premiums are the premiums of the
+//! positions and quantities are units of the underlying, like everywhere else
+//! in the crate.
+
+use crate::model::leg::SpotPosition;
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, Side};
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::iter::Sum;
+use std::ops::Add;
+use utoipa::ToSchema;
+
+/// Rates of the Reg-T margin rules.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RegTRules {
+    /// Fraction of the underlying value required for naked options (0.20).
+    pub naked_underlying_rate: Decimal,
+    /// Minimum fraction of the underlying value, or of the strike for puts,
+    /// required for naked options (0.10).
+    pub naked_minimum_rate: Decimal,
+    /// Initial requirement of stock as a fraction of its value (0.50).
+    pub stock_initial_rate: Decimal,
+    /// Maintenance requirement of long stock as a fraction of its value (0.25).
+    pub stock_maintenance_rate: Decimal,
+    /// Maintenance requirement of short stock as a fraction of its value (0.30).
+    pub short_stock_maintenance_rate: Decimal,
+}
+
+impl Default for RegTRules {
+    fn default() -> Self {
+        RegTRules {
+            naked_underlying_rate: dec!(0.20),
+            naked_minimum_rate: dec!(0.10),
+            stock_initial_rate: dec!(0.50),
+            stock_maintenance_rate: dec!(0.25),
+            short_stock_maintenance_rate: dec!(0.30),
+        }
+    }
+}
+
+/// Initial and maintenance margin requirement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MarginRequirement {
+    /// Requirement to open the position.
+    pub initial: Decimal,
+    /// Requirement to keep the position open.
+    pub maintenance: Decimal,
+}
+
+impl MarginRequirement {
+    /// Creates a new margin requirement.
+    pub fn new(initial: Decimal, maintenance: Decimal) -> Self {
+        MarginRequirement {
+            initial,
+            maintenance,
+        }
+    }
+
+    /// Requirement of `quantity` units at this requirement per unit.
+    fn scale(self, quantity: Decimal) -> Self {
+        MarginRequirement::new(self.initial * quantity, self.maintenance * quantity)
+    }
+}
+
+impl Add for MarginRequirement {
+    type Output = MarginRequirement;
+
+    fn add(self, other: MarginRequirement) -> MarginRequirement {
+        MarginRequirement::new(
+            self.initial + other.initial,
+            self.maintenance + other.maintenance,
+        )
+    }
+}
+
+impl Sum for MarginRequirement {
+    fn sum<I: Iterator<Item = MarginRequirement>>(iter: I) -> Self {
+        iter.fold(MarginRequirement::default(), Add::add)
+    }
+}
+
+/// Rule applied to a group of legs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum MarginTreatment {
+    /// Long option paid in full.
+    Long,
+    /// Uncovered short option.
+    Naked,
+    /// Short option covered by a long option of the same style.
+    Spread,
+    /// Short call paired with a short put.
+    Straddle,
+    /// Short call spread paired with a short put spread, charged for the
+    /// greater of the two.
+    IronCondor,
+    /// Short option covered by stock.
+    Covered,
+    /// Stock position.
+    Stock,
+}
+
+/// Requirement of a group of legs treated together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MarginComponent {
+    /// Rule applied to the legs.
+    pub treatment: MarginTreatment,
+    /// Underlying of the legs.
+    pub underlying_symbol: String,
+    /// Units of the underlying covered by the group.
+    pub quantity: Positive,
+    /// Requirement of the group.
+    pub requirement: MarginRequirement,
+    /// Option proceeds of the group applied to its requirement.
+    pub credit: Decimal,
+}
+
+/// Breakdown of the margin requirement of a set of positions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MarginReport {
+    /// Requirement of every group of legs.
+    pub components: Vec<MarginComponent>,
+    /// Total requirement.
+    pub total: MarginRequirement,
+}
+
+impl MarginReport {
+    /// Buying power consumed when opening the positions: the initial
+    /// requirement less the option proceeds applied to it.
+    pub fn buying_power_effect(&self) -> Decimal {
+        let credit: Decimal = self
+            .components
+            .iter()
+            .map(|component| component.credit)
+            .sum();
+        (self.total.initial - credit).max(Decimal::ZERO)
+    }
+}
+
+/// Reg-T margin calculator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RegTMargin {
+    /// Rates of the rules.
+    pub rules: RegTRules,
+}
+
+impl RegTMargin {
+    /// Creates a calculator with the given rates.
+    pub fn new(rules: RegTRules) -> Self {
+        RegTMargin { rules }
+    }
+
+    /// Requirement of a long option, paid in full.
+    pub fn long_option(&self, position: &Position) -> MarginRequirement {
+        self.long_unit(position)
+            .scale(position.option.quantity.to_dec())
+    }
+
+    /// Requirement of an uncovered short option.
+    pub fn naked_option(&self, position: &Position) -> MarginRequirement {
+        self.naked_unit(position)
+            .scale(position.option.quantity.to_dec())
+    }
+
+    /// Requirement of a vertical or calendar spread, for the smaller quantity
+    /// of the two legs, or `None` if `long` does not cover `short`.
+    pub fn spread(&self, short: &Position, long: &Position) -> Option<MarginRequirement> {
+        let quantity = short.option.quantity.min(long.option.quantity).to_dec();
+        self.spread_unit(short, long)
+            .map(|requirement| requirement.scale(quantity))
+    }
+
+    /// Requirement of a short straddle or strangle, for the smaller quantity
+    /// of the two legs.
+    pub fn straddle(&self, call: &Position, put: &Position) -> MarginRequirement {
+        let quantity = call.option.quantity.min(put.option.quantity).to_dec();
+        self.straddle_unit(call, put).scale(quantity)
+    }
+
+    /// Requirement of a stock position valued at `price`.
+    pub fn stock(&self, stock: &SpotPosition, price: Positive) -> MarginRequirement {
+        let value = stock.quantity.to_dec() * price.to_dec();
+        let maintenance_rate = match stock.side {
+            Side::Long => self.rules.stock_maintenance_rate,
+            Side::Short => self.rules.short_stock_maintenance_rate,
+        };
+        MarginRequirement::new(
+            value * self.rules.stock_initial_rate,
+            value * maintenance_rate,
+        )
+    }
+
+    /// Requirement of a set of open positions and stock, pairing the legs of
+    /// each underlying into covered positions, spreads, straddles and naked
+    /// options. Stock is valued at the underlying price of the options on the
+    /// same symbol, or at its cost basis when there are none.
+    pub fn calculate(&self, positions: &[&Position], stock: &[&SpotPosition]) -> MarginReport {
+        let mut symbols: Vec<&str> = positions
+            .iter()
+            .filter(|position| position.is_open())
+            .map(|position| position.option.underlying_symbol.as_str())
+            .chain(stock.iter().map(|stock| stock.symbol.as_str()))
+            .collect();
+        symbols.sort_unstable();
+        symbols.dedup();
+
+        let mut components = Vec::new();
+        for symbol in symbols {
+            let legs: Vec<&Position> = positions
+                .iter()
+                .copied()
+                .filter(|position| {
+                    position.is_open() && position.option.underlying_symbol == symbol
+                })
+                .collect();
+            let stock: Vec<&SpotPosition> = stock
+                .iter()
+                .copied()
+                .filter(|stock| stock.symbol == symbol)
+                .collect();
+            self.calculate_underlying(symbol, &legs, &stock, &mut components);
+        }
+
+        let total = components
+            .iter()
+            .map(|component| component.requirement)
+            .sum();
+        MarginReport { components, total }
+    }
+
+    /// Pairs the legs of a single underlying.
+    fn calculate_underlying(
+        &self,
+        symbol: &str,
+        positions: &[&Position],
+        stock: &[&SpotPosition],
+        components: &mut Vec<MarginComponent>,
+    ) {
+        let mut legs: Vec<(&Position, Decimal)> = positions
+            .iter()
+            .map(|position| (*position, position.option.quantity.to_dec()))
+            .collect();
+        let mut push = |treatment, quantity: Decimal, requirement: MarginRequirement, credit| {
+            components.push(MarginComponent {
+                treatment,
+                underlying_symbol: symbol.to_string(),
+                quantity: Positive::new_decimal(quantity).unwrap_or(Positive::ZERO),
+                requirement: requirement.scale(quantity),
+                credit: credit * quantity,
+            });
+        };
+
+        // Stock, and the short options it covers.
+        let price = positions
+            .first()
+            .map(|position| position.option.underlying_price);
+        for shares in stock {
+            let requirement = self.stock(shares, price.unwrap_or(shares.cost_basis));
+            let quantity = shares.quantity.to_dec();
+            if quantity > Decimal::ZERO {
+                push(
+                    MarginTreatment::Stock,
+                    quantity,
+                    MarginRequirement::new(
+                        requirement.initial / quantity,
+                        requirement.maintenance / quantity,
+                    ),
+                    Decimal::ZERO,
+                );
+            }
+            let covered_style = match shares.side {
+                Side::Long => OptionStyle::Call,
+                Side::Short => OptionStyle::Put,
+            };
+            let mut available = quantity;
+            for (position, remaining) in legs.iter_mut() {
+                if available <= Decimal::ZERO {
+                    break;
+                }
+                if position.option.side == Side::Short
+                    && position.option.option_style == covered_style
+                    && *remaining > Decimal::ZERO
+                {
+                    let quantity = available.min(*remaining);
+                    push(
+                        MarginTreatment::Covered,
+                        quantity,
+                        MarginRequirement::default(),
+                        position.premium.to_dec(),
+                    );
+                    *remaining -= quantity;
+                    available -= quantity;
+                }
+            }
+        }
+
+        // Spreads, choosing for each short leg the cheapest long leg.
+        let mut spreads: Vec<(OptionStyle, Decimal, MarginRequirement, Decimal)> = Vec::new();
+        for short in 0..legs.len() {
+            if legs[short].0.option.side != Side::Short {
+                continue;
+            }
+            while legs[short].1 > Decimal::ZERO {
+                let best = (0..legs.len())
+                    .filter(|&long| {
+                        legs[long].0.option.side == Side::Long && legs[long].1 > Decimal::ZERO
+                    })
+                    .filter_map(|long| {
+                        self.spread_unit(legs[short].0, legs[long].0)
+                            .map(|requirement| (long, requirement))
+                    })
+                    .min_by(|a, b| a.1.initial.cmp(&b.1.initial));
+                let Some((long, requirement)) = best else {
+                    break;
+                };
+                let quantity = legs[short].1.min(legs[long].1);
+                let credit = if requirement.maintenance > Decimal::ZERO {
+                    (legs[short].0.premium.to_dec() - legs[long].0.premium.to_dec())
+                        .max(Decimal::ZERO)
+                } else {
+                    Decimal::ZERO
+                };
+                spreads.push((
+                    legs[short].0.option.option_style,
+                    quantity,
+                    requirement,
+                    credit,
+                ));
+                legs[short].1 -= quantity;
+                legs[long].1 -= quantity;
+            }
+        }
+
+        // Credit spreads on both sides can only lose on one of them.
+        for call in 0..spreads.len() {
+            for put in 0..spreads.len() {
+                let (call_style, call_quantity, call_requirement, call_credit) = spreads[call];
+                let (put_style, put_quantity, put_requirement, put_credit) = spreads[put];
+                if call_style != OptionStyle::Call
+                    || put_style != OptionStyle::Put
+                    || call_requirement.maintenance <= Decimal::ZERO
+                    || put_requirement.maintenance <= Decimal::ZERO
+                    || call_quantity <= Decimal::ZERO
+                    || put_quantity <= Decimal::ZERO
+                {
+                    continue;
+                }
+                let quantity = call_quantity.min(put_quantity);
+                let requirement = if call_requirement.initial >= put_requirement.initial {
+                    call_requirement
+                } else {
+                    put_requirement
+                };
+                push(
+                    MarginTreatment::IronCondor,
+                    quantity,
+                    requirement,
+                    call_credit + put_credit,
+                );
+                spreads[call].1 -= quantity;
+                spreads[put].1 -= quantity;
+            }
+        }
+        for (_, quantity, requirement, credit) in spreads {
+            if quantity > Decimal::ZERO {
+                push(MarginTreatment::Spread, quantity, requirement, credit);
+            }
+        }
+
+        // Short calls against short puts.
+        for call in 0..legs.len() {
+            let (call_position, _) = legs[call];
+            if call_position.option.side != Side::Short
+                || call_position.option.option_style != OptionStyle::Call
+            {
+                continue;
+            }
+            for put in 0..legs.len() {
+                let (put_position, _) = legs[put];
+                if put_position.option.side != Side::Short
+                    || put_position.option.option_style != OptionStyle::Put
+                    || legs[put].1 <= Decimal::ZERO
+                    || legs[call].1 <= Decimal::ZERO
+                {
+                    continue;
+                }
+                let quantity = legs[call].1.min(legs[put].1);
+                push(
+                    MarginTreatment::Straddle,
+                    quantity,
+                    self.straddle_unit(call_position, put_position),
+                    call_position.premium.to_dec() + put_position.premium.to_dec(),
+                );
+                legs[call].1 -= quantity;
+                legs[put].1 -= quantity;
+            }
+        }
+
+        // Whatever is left stands alone.
+        for (position, remaining) in legs {
+            if remaining <= Decimal::ZERO {
+                continue;
+            }
+            match position.option.side {
+                Side::Long => push(
+                    MarginTreatment::Long,
+                    remaining,
+                    self.long_unit(position),
+                    Decimal::ZERO,
+                ),
+                Side::Short => push(
+                    MarginTreatment::Naked,
+                    remaining,
+                    self.naked_unit(position),
+                    position.premium.to_dec(),
+                ),
+            }
+        }
+    }
+
+    /// Requirement per unit of a long option.
+    fn long_unit(&self, position: &Position) -> MarginRequirement {
+        MarginRequirement::new(position.premium.to_dec(), Decimal::ZERO)
+    }
+
+    /// Requirement per unit of a naked short option.
+    fn naked_unit(&self, position: &Position) -> MarginRequirement {
+        let option = &position.option;
+        let underlying = option.underlying_price.to_dec();
+        let strike = option.strike_price.to_dec();
+        let (out_of_the_money, floor_base) = match option.option_style {
+            OptionStyle::Call => ((strike - underlying).max(Decimal::ZERO), underlying),
+            OptionStyle::Put => ((underlying - strike).max(Decimal::ZERO), strike),
+        };
+        let charge = (self.rules.naked_underlying_rate * underlying - out_of_the_money)
+            .max(self.rules.naked_minimum_rate * floor_base);
+        let requirement = position.premium.to_dec() + charge;
+        MarginRequirement::new(requirement, requirement)
+    }
+
+    /// Requirement per unit of a spread, or `None` if the legs do not form
+    /// one.
+    fn spread_unit(&self, short: &Position, long: &Position) -> Option<MarginRequirement> {
+        let (short_option, long_option) = (&short.option, &long.option);
+        if short_option.side != Side::Short
+            || long_option.side != Side::Long
+            || short_option.option_style != long_option.option_style
+            || short_option.underlying_symbol != long_option.underlying_symbol
+        {
+            return None;
+        }
+        let short_days = short_option.expiration_date.get_days().ok()?;
+        let long_days = long_option.expiration_date.get_days().ok()?;
+        if long_days < short_days {
+            return None;
+        }
+
+        let width = match short_option.option_style {
+            OptionStyle::Call => {
+                long_option.strike_price.to_dec() - short_option.strike_price.to_dec()
+            }
+            OptionStyle::Put => {
+                short_option.strike_price.to_dec() - long_option.strike_price.to_dec()
+            }
+        };
+        if width > Decimal::ZERO {
+            Some(MarginRequirement::new(width, width))
+        } else {
+            let debit = (long.premium.to_dec() - short.premium.to_dec()).max(Decimal::ZERO);
+            Some(MarginRequirement::new(debit, Decimal::ZERO))
+        }
+    }
+
+    /// Requirement per unit of a short straddle or strangle.
+    fn straddle_unit(&self, call: &Position, put: &Position) -> MarginRequirement {
+        let call_requirement = self.naked_unit(call).initial;
+        let put_requirement = self.naked_unit(put).initial;
+        let requirement = if call_requirement >= put_requirement {
+            call_requirement + put.premium.to_dec()
+        } else {
+            put_requirement + call.premium.to_dec()
+        };
+        MarginRequirement::new(requirement, requirement)
+    }
+}
+
+#[cfg(test)]
+mod tests_margin {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::Options;
+    use crate::model::types::OptionType;
+    use chrono::Utc;
+    use positive::pos_or_panic;
+
+    fn position(style: OptionStyle, side: Side, strike: f64, premium: f64, days: f64) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            "AAPL".to_string(),
+            Positive::new(strike).unwrap(),
+            ExpirationDate::Days(Positive::new(days).unwrap()),
+            pos_or_panic!(0.2),
+            Positive::HUNDRED,
+            Positive::HUNDRED,
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            Positive::new(premium).unwrap(),
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_naked_options() {
+        let margin = RegTMargin::default();
+        // 2 + 20% of 100 - 5 out of the money = 17 per unit.
+        let call = position(OptionStyle::Call, Side::Short, 105.0, 2.0, 30.0);
+        assert_eq!(margin.naked_option(&call).initial, dec!(1700));
+        // Deep out of the money put falls back to 10% of the strike.
+        let put = position(OptionStyle::Put, Side::Short, 70.0, 0.5, 30.0);
+        assert_eq!(margin.naked_option(&put).maintenance, dec!(750));
+
+        let long = position(OptionStyle::Call, Side::Long, 100.0, 3.0, 30.0);
+        assert_eq!(
+            margin.long_option(&long),
+            MarginRequirement::new(dec!(300), Decimal::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_spreads() {
+        let margin = RegTMargin::default();
+        let short_put = position(OptionStyle::Put, Side::Short, 100.0, 4.0, 30.0);
+        let long_put = position(OptionStyle::Put, Side::Long, 95.0, 2.0, 30.0);
+        assert_eq!(
+            margin.spread(&short_put, &long_put),
+            Some(MarginRequirement::new(dec!(500), dec!(500)))
+        );
+
+        let short_call = position(OptionStyle::Call, Side::Short, 105.0, 1.0, 30.0);
+        let long_call = position(OptionStyle::Call, Side::Long, 100.0, 3.0, 30.0);
+        assert_eq!(
+            margin.spread(&short_call, &long_call),
+            Some(MarginRequirement::new(dec!(200), Decimal::ZERO))
+        );
+
+        let near_long = position(OptionStyle::Call, Side::Long, 110.0, 1.0, 10.0);
+        assert_eq!(margin.spread(&short_call, &near_long), None);
+        assert_eq!(margin.spread(&short_call, &long_put), None);
+    }
+
+    #[test]
+    fn test_calculate_pairs_legs() {
+        let margin = RegTMargin::default();
+        let iron_condor = [
+            position(OptionStyle::Put, Side::Long, 90.0, 1.0, 30.0),
+            position(OptionStyle::Put, Side::Short, 95.0, 2.0, 30.0),
+            position(OptionStyle::Call, Side::Short, 105.0, 2.0, 30.0),
+            position(OptionStyle::Call, Side::Long, 110.0, 1.0, 30.0),
+        ];
+        let legs: Vec<&Position> = iron_condor.iter().collect();
+        let report = margin.calculate(&legs, &[]);
+        assert_eq!(report.components.len(), 1);
+        assert_eq!(report.components[0].treatment, MarginTreatment::IronCondor);
+        assert_eq!(report.total, MarginRequirement::new(dec!(500), dec!(500)));
+        // Width of 5 less the net credit of 2.
+        assert_eq!(report.buying_power_effect(), dec!(300));
+
+        let strangle = [
+            position(OptionStyle::Call, Side::Short, 105.0, 2.0, 30.0),
+            position(OptionStyle::Put, Side::Short, 95.0, 1.5, 30.0),
+        ];
+        let legs: Vec<&Position> = strangle.iter().collect();
+        let report = margin.calculate(&legs, &[]);
+        assert_eq!(report.components[0].treatment, MarginTreatment::Straddle);
+        // Call: 2 + 20 - 5 = 17, plus the put premium of 1.5.
+        assert_eq!(report.total.initial, dec!(1850));
+        assert_eq!(report.buying_power_effect(), dec!(1500));
+    }
+
+    #[test]
+    fn test_covered_call() {
+        let margin = RegTMargin::default();
+        let call = [position(OptionStyle::Call, Side::Short, 105.0, 2.0, 30.0)];
+        let legs: Vec<&Position> = call.iter().collect();
+        let shares =
+            SpotPosition::long("AAPL".to_string(), pos_or_panic!(60.0), pos_or_panic!(95.0));
+        let report = margin.calculate(&legs, &[&shares]);
+
+        let treatments: Vec<MarginTreatment> = report
+            .components
+            .iter()
+            .map(|component| component.treatment)
+            .collect();
+        assert_eq!(
+            treatments,
+            vec![
+                MarginTreatment::Stock,
+                MarginTreatment::Covered,
+                MarginTreatment::Naked
+            ]
+        );
+        // Stock: 60 shares at 100, plus 40 naked calls at 17.
+        assert_eq!(report.total.initial, dec!(3000) + dec!(680));
+        assert_eq!(report.total.maintenance, dec!(1500) + dec!(680));
+        // Call proceeds of 2 on all 100 units are applied.
+        assert_eq!(report.buying_power_effect(), dec!(3480));
+    }
+}
@@ -50,8 +50,10 @@ use positive::pos_or_panic;
 //! use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
 //! use positive::Positive;
 //! use optionstratlib::model::position::Position;
+//! use optionstratlib::model::{Currency, PositionStatus};
 //! use positive::pos_or_panic;
 //! use chrono::Utc;
+//! use rust_decimal::Decimal;
 //! use rust_decimal_macros::dec;
 //! use optionstratlib::risk::SPANMargin;
 //!
@@ -79,6 +81,10 @@ use positive::pos_or_panic;
 //!     close_fee: pos_or_panic!(0.5),
 //!     epic: None,
 //!     extra_fields: None,
+//!     status: PositionStatus::Open,
+//!     fills: Vec::new(),
+//!     realized_pnl: Decimal::ZERO,
+//!     currency: Currency::default(),
 //! };
 //!
 //! // Create SPAN calculator
@@ -99,8 +105,9 @@ use positive::pos_or_panic;
 //! use rust_decimal::Decimal;
 //! use rust_decimal_macros::dec;
 //! use optionstratlib::{ExpirationDate, Options};
-//! use optionstratlib::model::types::{ OptionStyle, OptionType, Side};
+//! use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
 //! use optionstratlib::model::position::Position;
+//! use optionstratlib::model::{Currency, PositionStatus};
 //! use positive::Positive;
 //! use positive::pos_or_panic;
 //! use optionstratlib::risk::SPANMargin;
@@ -118,6 +125,8 @@ use positive::pos_or_panic;
 //!             option_style: OptionStyle::Call,
 //!             dividend_yield: pos_or_panic!(0.01),
 //!             exotic_params: None,
+//!             settlement_type: SettlementType::Physical,
+//!             day_count: DayCount::Act365Fixed,
 //!         };
 //! // Create multiple positions
 //! let positions = vec![
@@ -129,6 +138,10 @@ use positive::pos_or_panic;
 //!         close_fee: pos_or_panic!(0.5),
 //!         epic: None,
 //!         extra_fields: None,
+//!         status: PositionStatus::Open,
+//!         fills: Vec::new(),
+//!         realized_pnl: Decimal::ZERO,
+//!         currency: Currency::default(),
 //!     },
 //!     Position {
 //!         option,
@@ -138,6 +151,10 @@ use positive::pos_or_panic;
 //!         close_fee: pos_or_panic!(0.5),
 //!         epic: None,
 //!         extra_fields: None,
+//!         status: PositionStatus::Open,
+//!         fills: Vec::new(),
+//!         realized_pnl: Decimal::ZERO,
+//!         currency: Currency::default(),
 //!     },
 //! ];
 //!
@@ -180,8 +197,30 @@ use positive::pos_or_panic;
 //! - Short option minimum is always enforced for short positions
 //! - Results are conservative estimates of potential losses
 
+mod assignment;
+mod beta;
+mod margin;
 mod model;
+mod pin;
+mod pretrade;
+mod scenario_margin;
 mod span;
+mod stress;
+mod var;
 
+pub use assignment::{AssignmentRisk, AssignmentTrigger, DividendEvent, EarlyAssignmentAnalysis};
+pub use beta::{BetaWeightedDelta, BetaWeightedReport, BetaWeighting};
+pub use margin::{
+    MarginComponent, MarginReport, MarginRequirement, MarginTreatment, RegTMargin, RegTRules,
+};
 pub use model::{RiskCategory, RiskMetricsSimulation};
+pub use pin::{PinRisk, PinRiskCheck};
+pub use pretrade::{
+    AccountRules, PreTradeCheck, PreTradeDecision, PreTradeReport, PreTradeRule, RuleCheck,
+};
+pub use scenario_margin::{
+    GroupMargin, InterCommodityOffset, ScenarioGrid, ScenarioMargin, ScenarioMarginReport,
+};
 pub use span::SPANMargin;
+pub use stress::{PositionStress, StressReport, StressScenario, StressTest, VolatilityDynamics};
+pub use var::{PositionTailRisk, UnderlyingVar, ValueAtRisk, VarMethod, VarReport};
@@ -0,0 +1,226 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Pin Risk
+//!
+//! Close to expiration, a short option whose strike is near the underlying
+//! price may or may not be assigned, and the outcome is only known after the
+//! close. An assigned leg turns into a stock position overnight, so the delta
+//! of the book can jump by the full quantity of the leg.
+//!
+//! [`PinRiskCheck`] flags the short legs expiring within a window of days
+//! whose strike lies within a band around the underlying price, and reports
+//! the delta and notional of the stock position that assignment would create.
+
+use crate::error::OptionsError;
+use crate::greeks::delta;
+use crate::model::position::Position;
+use crate::model::types::OptionStyle;
+use positive::{Positive, pos_or_panic};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Pin risk of a short leg near expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PinRisk {
+    /// Index of the leg in the analysed positions.
+    pub leg: usize,
+    /// Symbol of the underlying.
+    pub underlying_symbol: String,
+    /// Style of the option.
+    pub option_style: OptionStyle,
+    /// Strike price of the option.
+    pub strike_price: Positive,
+    /// Price of the underlying.
+    pub underlying_price: Positive,
+    /// Distance between the strike and the underlying price, as a fraction of
+    /// the underlying price.
+    pub distance: Decimal,
+    /// Days left until expiration.
+    pub days_to_expiration: Positive,
+    /// Current delta of the leg.
+    pub current_delta: Decimal,
+    /// Delta of the stock position received if the leg is assigned.
+    pub delta_if_assigned: Decimal,
+    /// Value of the stock position received if the leg is assigned.
+    pub notional_if_assigned: Decimal,
+}
+
+impl PinRisk {
+    /// Change in delta from the current delta of the leg to the delta after
+    /// assignment.
+    pub fn delta_swing(&self) -> Decimal {
+        self.delta_if_assigned - self.current_delta
+    }
+}
+
+/// Detects short legs pinned near their strike close to expiration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PinRiskCheck {
+    /// Largest distance between strike and underlying price, as a fraction of
+    /// the underlying price (0.01 = 1%).
+    pub band: Positive,
+    /// Legs expiring within this number of days are checked.
+    pub days_to_expiration: Positive,
+}
+
+impl Default for PinRiskCheck {
+    fn default() -> Self {
+        Self {
+            band: pos_or_panic!(0.01),
+            days_to_expiration: Positive::ONE,
+        }
+    }
+}
+
+impl PinRiskCheck {
+    /// Creates a check with the given band and window of days.
+    pub fn new(band: Positive, days_to_expiration: Positive) -> Self {
+        Self {
+            band,
+            days_to_expiration,
+        }
+    }
+
+    /// Returns the short legs of `positions` at risk of pinning, largest
+    /// notional first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if the expiration or the delta of a leg
+    /// cannot be computed.
+    pub fn check(&self, positions: &[&Position]) -> Result<Vec<PinRisk>, OptionsError> {
+        let mut risks = Vec::new();
+        for (leg, position) in positions.iter().enumerate() {
+            let option = &position.option;
+            if !option.is_short() {
+                continue;
+            }
+            let days_to_expiration = option.expiration_date.get_days()?;
+            if days_to_expiration > self.days_to_expiration {
+                continue;
+            }
+            let spot = option.underlying_price.to_dec();
+            let distance = (option.strike_price.to_dec() - spot).abs() / spot;
+            if distance > self.band.to_dec() {
+                continue;
+            }
+
+            let quantity = option.quantity.to_dec();
+            // Assigned short calls deliver shares, assigned short puts receive them.
+            let delta_if_assigned = match option.option_style {
+                OptionStyle::Call => -quantity,
+                OptionStyle::Put => quantity,
+            };
+            risks.push(PinRisk {
+                leg,
+                underlying_symbol: option.underlying_symbol.clone(),
+                option_style: option.option_style,
+                strike_price: option.strike_price,
+                underlying_price: option.underlying_price,
+                distance,
+                days_to_expiration,
+                current_delta: delta(option)?,
+                delta_if_assigned,
+                notional_if_assigned: quantity * option.strike_price.to_dec(),
+            });
+        }
+        risks.sort_by_key(|risk| std::cmp::Reverse(risk.notional_if_assigned));
+        Ok(risks)
+    }
+}
+
+#[cfg(test)]
+mod tests_pin {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::option::Options;
+    use crate::model::types::{OptionType, Side};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn position(style: OptionStyle, side: Side, strike: Positive, days: Positive) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            "XYZ".to_string(),
+            strike,
+            ExpirationDate::Days(days),
+            pos_or_panic!(0.2),
+            Positive::HUNDRED,
+            Positive::HUNDRED,
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            Positive::ONE,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_flags_short_strikes_near_spot() {
+        let call = position(
+            OptionStyle::Call,
+            Side::Short,
+            pos_or_panic!(100.5),
+            pos_or_panic!(0.5),
+        );
+        let put = position(
+            OptionStyle::Put,
+            Side::Short,
+            pos_or_panic!(99.5),
+            pos_or_panic!(0.5),
+        );
+        let risks = PinRiskCheck::default().check(&[&call, &put]).unwrap();
+
+        assert_eq!(risks.len(), 2);
+        let call_risk = &risks[0];
+        assert_eq!(call_risk.leg, 0);
+        assert_eq!(call_risk.distance, dec!(0.005));
+        assert_eq!(call_risk.delta_if_assigned, dec!(-100));
+        assert_eq!(call_risk.notional_if_assigned, dec!(10050));
+        assert!(call_risk.current_delta < Decimal::ZERO);
+        assert!(call_risk.delta_swing() < Decimal::ZERO);
+        assert_eq!(risks[1].delta_if_assigned, dec!(100));
+        assert!(risks[1].delta_swing() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_ignores_far_long_and_distant_legs() {
+        let far = position(
+            OptionStyle::Call,
+            Side::Short,
+            pos_or_panic!(105.0),
+            pos_or_panic!(0.5),
+        );
+        let long = position(
+            OptionStyle::Call,
+            Side::Long,
+            Positive::HUNDRED,
+            pos_or_panic!(0.5),
+        );
+        let distant = position(
+            OptionStyle::Put,
+            Side::Short,
+            Positive::HUNDRED,
+            pos_or_panic!(30.0),
+        );
+        let check = PinRiskCheck::default();
+        assert!(check.check(&[&far, &long, &distant]).unwrap().is_empty());
+
+        let wide = PinRiskCheck::new(pos_or_panic!(0.05), pos_or_panic!(30.0));
+        assert_eq!(wide.check(&[&far, &long, &distant]).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,481 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Pre-Trade Risk Checks
+//!
+//! Validates a proposed strategy against a set of account rules before it is
+//! sent to the market. Every configured rule is evaluated and reported, so an
+//! order workflow can show all the reasons for a rejection at once instead of
+//! stopping at the first failure.
+//!
+//! Supported rules:
+//!
+//! * maximum loss of the strategy,
+//! * margin usage as a fraction of account equity,
+//! * absolute limits on net delta, gamma, vega and theta,
+//! * restricted underlyings,
+//! * a ban on undefined-risk strategies.
+
+use crate::error::StrategyError;
+use crate::greeks::Greeks;
+use crate::strategies::base::Strategies;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use utoipa::ToSchema;
+
+/// Account level rules a proposed strategy must satisfy.
+///
+/// Every limit is optional; unset limits are not evaluated. The default rules
+/// evaluate nothing and allow undefined-risk strategies.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountRules {
+    /// Largest acceptable maximum loss of the strategy.
+    pub max_loss: Option<Positive>,
+    /// Net liquidation value of the account, used for margin usage.
+    pub account_equity: Option<Positive>,
+    /// Margin already committed by open positions.
+    pub margin_in_use: Positive,
+    /// Largest acceptable fraction of equity committed to margin after the trade (0.5 = 50%).
+    pub max_margin_usage: Option<Positive>,
+    /// Largest acceptable absolute net delta.
+    pub max_abs_delta: Option<Decimal>,
+    /// Largest acceptable absolute net gamma.
+    pub max_abs_gamma: Option<Decimal>,
+    /// Largest acceptable absolute net vega.
+    pub max_abs_vega: Option<Decimal>,
+    /// Largest acceptable absolute net theta.
+    pub max_abs_theta: Option<Decimal>,
+    /// Underlyings that may not be traded.
+    pub restricted_underlyings: BTreeSet<String>,
+    /// Whether strategies with unlimited maximum loss may be opened. Set to
+    /// `false` to ban them.
+    pub allow_undefined_risk: bool,
+}
+
+impl Default for AccountRules {
+    fn default() -> Self {
+        AccountRules {
+            max_loss: None,
+            account_equity: None,
+            margin_in_use: Positive::ZERO,
+            max_margin_usage: None,
+            max_abs_delta: None,
+            max_abs_gamma: None,
+            max_abs_vega: None,
+            max_abs_theta: None,
+            restricted_underlyings: BTreeSet::new(),
+            allow_undefined_risk: true,
+        }
+    }
+}
+
+/// Identifies the rule a check result refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum PreTradeRule {
+    /// Maximum loss limit.
+    MaxLoss,
+    /// Margin usage limit.
+    MarginUsage,
+    /// Net delta limit.
+    DeltaLimit,
+    /// Net gamma limit.
+    GammaLimit,
+    /// Net vega limit.
+    VegaLimit,
+    /// Net theta limit.
+    ThetaLimit,
+    /// Restricted underlying list.
+    RestrictedUnderlying,
+    /// Undefined-risk ban.
+    UndefinedRisk,
+}
+
+impl fmt::Display for PreTradeRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PreTradeRule::MaxLoss => "max loss",
+            PreTradeRule::MarginUsage => "margin usage",
+            PreTradeRule::DeltaLimit => "delta limit",
+            PreTradeRule::GammaLimit => "gamma limit",
+            PreTradeRule::VegaLimit => "vega limit",
+            PreTradeRule::ThetaLimit => "theta limit",
+            PreTradeRule::RestrictedUnderlying => "restricted underlying",
+            PreTradeRule::UndefinedRisk => "undefined risk",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Outcome of evaluating a single rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RuleCheck {
+    /// Rule that was evaluated.
+    pub rule: PreTradeRule,
+    /// Whether the proposed strategy satisfies the rule.
+    pub passed: bool,
+    /// Value observed on the proposed strategy, when the rule is numeric.
+    pub observed: Option<Decimal>,
+    /// Configured limit, when the rule is numeric.
+    pub limit: Option<Decimal>,
+    /// Human readable explanation.
+    pub message: String,
+}
+
+impl RuleCheck {
+    fn numeric(rule: PreTradeRule, observed: Decimal, limit: Decimal) -> Self {
+        let passed = observed <= limit;
+        let message = if passed {
+            format!("{rule} {observed} within limit {limit}")
+        } else {
+            format!("{rule} {observed} exceeds limit {limit}")
+        };
+        RuleCheck {
+            rule,
+            passed,
+            observed: Some(observed),
+            limit: Some(limit),
+            message,
+        }
+    }
+
+    fn flag(rule: PreTradeRule, passed: bool, message: String) -> Self {
+        RuleCheck {
+            rule,
+            passed,
+            observed: None,
+            limit: None,
+            message,
+        }
+    }
+}
+
+/// Overall decision of a pre-trade check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum PreTradeDecision {
+    /// Every configured rule passed.
+    Approved,
+    /// At least one rule failed.
+    Rejected,
+}
+
+/// Structured result of a pre-trade check.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PreTradeReport {
+    /// Overall decision.
+    pub decision: PreTradeDecision,
+    /// Result of every evaluated rule, in evaluation order.
+    pub checks: Vec<RuleCheck>,
+}
+
+impl PreTradeReport {
+    /// Returns `true` if the strategy was approved.
+    pub fn is_approved(&self) -> bool {
+        self.decision == PreTradeDecision::Approved
+    }
+
+    /// Returns the failed rules.
+    pub fn rejections(&self) -> Vec<&RuleCheck> {
+        self.checks.iter().filter(|c| !c.passed).collect()
+    }
+}
+
+/// Pre-trade risk check service validating proposed strategies against [`AccountRules`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct PreTradeCheck {
+    /// Rules every proposed strategy is checked against.
+    pub rules: AccountRules,
+}
+
+impl PreTradeCheck {
+    /// Creates a check service for the given account rules.
+    pub fn new(rules: AccountRules) -> Self {
+        PreTradeCheck { rules }
+    }
+
+    /// Evaluates every configured rule against a proposed strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The strategy that would be opened.
+    /// * `margin_requirement` - Margin the strategy would commit, required only
+    ///   when a margin usage limit is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if the positions or Greeks of the strategy
+    /// cannot be computed, or if a margin limit is configured without the data
+    /// needed to evaluate it.
+    pub fn check<S>(
+        &self,
+        strategy: &S,
+        margin_requirement: Option<Positive>,
+    ) -> Result<PreTradeReport, StrategyError>
+    where
+        S: Strategies + Greeks,
+    {
+        let rules = &self.rules;
+        let mut checks = Vec::new();
+
+        if !rules.restricted_underlyings.is_empty() {
+            let restricted: BTreeSet<&str> = strategy
+                .get_positions()?
+                .iter()
+                .map(|p| p.option.underlying_symbol.as_str())
+                .filter(|s| rules.restricted_underlyings.contains(*s))
+                .collect();
+            let check = if restricted.is_empty() {
+                RuleCheck::flag(
+                    PreTradeRule::RestrictedUnderlying,
+                    true,
+                    "No restricted underlyings".to_string(),
+                )
+            } else {
+                let names: Vec<&str> = restricted.into_iter().collect();
+                RuleCheck::flag(
+                    PreTradeRule::RestrictedUnderlying,
+                    false,
+                    format!("Restricted underlyings: {}", names.join(", ")),
+                )
+            };
+            checks.push(check);
+        }
+
+        if !rules.allow_undefined_risk || rules.max_loss.is_some() {
+            let max_loss = strategy.get_max_loss().ok();
+            let undefined = max_loss.is_none_or(|loss| loss == Positive::INFINITY);
+            if !rules.allow_undefined_risk {
+                checks.push(RuleCheck::flag(
+                    PreTradeRule::UndefinedRisk,
+                    !undefined,
+                    if undefined {
+                        "Strategy has undefined maximum loss".to_string()
+                    } else {
+                        "Strategy has defined maximum loss".to_string()
+                    },
+                ));
+            }
+            if let Some(limit) = rules.max_loss {
+                let check = match max_loss {
+                    Some(loss) if !undefined => {
+                        RuleCheck::numeric(PreTradeRule::MaxLoss, loss.to_dec(), limit.to_dec())
+                    }
+                    _ => RuleCheck {
+                        rule: PreTradeRule::MaxLoss,
+                        passed: false,
+                        observed: None,
+                        limit: Some(limit.to_dec()),
+                        message: "Maximum loss is unlimited or cannot be determined".to_string(),
+                    },
+                };
+                checks.push(check);
+            }
+        }
+
+        if let Some(max_usage) = rules.max_margin_usage {
+            let equity = rules.account_equity.ok_or_else(|| {
+                StrategyError::operation_not_supported(
+                    "margin usage check without account equity",
+                    "PreTradeCheck",
+                )
+            })?;
+            let margin = margin_requirement.ok_or_else(|| {
+                StrategyError::operation_not_supported(
+                    "margin usage check without margin requirement",
+                    "PreTradeCheck",
+                )
+            })?;
+            if equity == Positive::ZERO {
+                checks.push(RuleCheck::flag(
+                    PreTradeRule::MarginUsage,
+                    false,
+                    "Account equity is zero".to_string(),
+                ));
+            } else {
+                let usage = (rules.margin_in_use + margin).to_dec() / equity.to_dec();
+                checks.push(RuleCheck::numeric(
+                    PreTradeRule::MarginUsage,
+                    usage,
+                    max_usage.to_dec(),
+                ));
+            }
+        }
+
+        let greek_limits = [
+            (PreTradeRule::DeltaLimit, rules.max_abs_delta),
+            (PreTradeRule::GammaLimit, rules.max_abs_gamma),
+            (PreTradeRule::VegaLimit, rules.max_abs_vega),
+            (PreTradeRule::ThetaLimit, rules.max_abs_theta),
+        ];
+        if greek_limits.iter().any(|(_, limit)| limit.is_some()) {
+            let greeks = strategy.net_greeks()?;
+            for (rule, limit) in greek_limits {
+                let Some(limit) = limit else { continue };
+                let observed = match rule {
+                    PreTradeRule::DeltaLimit => greeks.delta,
+                    PreTradeRule::GammaLimit => greeks.gamma,
+                    PreTradeRule::VegaLimit => greeks.vega,
+                    _ => greeks.theta,
+                };
+                checks.push(RuleCheck::numeric(rule, observed.abs(), limit.abs()));
+            }
+        }
+
+        let decision = if checks.iter().all(|c| c.passed) {
+            PreTradeDecision::Approved
+        } else {
+            PreTradeDecision::Rejected
+        };
+        Ok(PreTradeReport { decision, checks })
+    }
+}
+
+#[cfg(test)]
+mod tests_pretrade {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::strategies::bull_call_spread::BullCallSpread;
+    use crate::strategies::short_strangle::ShortStrangle;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn spread() -> BullCallSpread {
+        BullCallSpread::new(
+            "SPY".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(7.0),
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn strangle() -> ShortStrangle {
+        ShortStrangle::new(
+            "TSLA".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(110.0),
+            pos_or_panic!(90.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.3),
+            pos_or_panic!(0.3),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.0),
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_no_rules_approves() {
+        let report = PreTradeCheck::default().check(&strangle(), None).unwrap();
+        assert!(report.is_approved());
+        assert!(report.checks.is_empty());
+    }
+
+    #[test]
+    fn test_undefined_risk_ban() {
+        let check = PreTradeCheck::new(AccountRules {
+            allow_undefined_risk: false,
+            ..Default::default()
+        });
+        let report = check.check(&strangle(), None).unwrap();
+        assert!(!report.is_approved());
+        assert_eq!(report.rejections()[0].rule, PreTradeRule::UndefinedRisk);
+
+        let report = check.check(&spread(), None).unwrap();
+        assert!(report.is_approved());
+    }
+
+    #[test]
+    fn test_max_loss_and_restricted() {
+        let mut rules = AccountRules {
+            max_loss: Some(pos_or_panic!(1.0)),
+            ..Default::default()
+        };
+        rules.restricted_underlyings.insert("SPY".to_string());
+        let report = PreTradeCheck::new(rules).check(&spread(), None).unwrap();
+        let failed: Vec<PreTradeRule> = report.rejections().iter().map(|c| c.rule).collect();
+        assert_eq!(
+            failed,
+            vec![PreTradeRule::RestrictedUnderlying, PreTradeRule::MaxLoss]
+        );
+        let max_loss = report
+            .checks
+            .iter()
+            .find(|c| c.rule == PreTradeRule::MaxLoss)
+            .unwrap();
+        assert_eq!(max_loss.observed, Some(dec!(5)));
+    }
+
+    #[test]
+    fn test_margin_usage() {
+        let rules = AccountRules {
+            account_equity: Some(pos_or_panic!(10_000.0)),
+            margin_in_use: pos_or_panic!(4_000.0),
+            max_margin_usage: Some(pos_or_panic!(0.5)),
+            ..Default::default()
+        };
+        let check = PreTradeCheck::new(rules);
+        assert!(
+            check
+                .check(&spread(), Some(pos_or_panic!(500.0)))
+                .unwrap()
+                .is_approved()
+        );
+        let report = check
+            .check(&spread(), Some(pos_or_panic!(2_000.0)))
+            .unwrap();
+        assert_eq!(report.rejections()[0].observed, Some(dec!(0.6)));
+        assert!(check.check(&spread(), None).is_err());
+    }
+
+    #[test]
+    fn test_greek_limits() {
+        let rules = AccountRules {
+            max_abs_delta: Some(dec!(0.01)),
+            max_abs_vega: Some(dec!(1000)),
+            ..Default::default()
+        };
+        let report = PreTradeCheck::new(rules).check(&spread(), None).unwrap();
+        assert_eq!(report.checks.len(), 2);
+        assert_eq!(report.decision, PreTradeDecision::Rejected);
+        assert_eq!(report.rejections()[0].rule, PreTradeRule::DeltaLimit);
+    }
+
+    #[test]
+    fn test_greek_limits_use_net_greeks() {
+        let strategy = spread();
+        let gross = strategy.greeks().unwrap().vega;
+        let net = strategy.net_greeks().unwrap().vega.abs();
+        assert!(net < gross);
+
+        // The short call offsets the vega of the long call.
+        let rules = AccountRules {
+            max_abs_vega: Some((net + gross) / Decimal::TWO),
+            ..Default::default()
+        };
+        let report = PreTradeCheck::new(rules).check(&strategy, None).unwrap();
+        assert!(report.is_approved());
+        assert_eq!(report.checks[0].observed, Some(net));
+    }
+}
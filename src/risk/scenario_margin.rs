@@ -0,0 +1,442 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Scenario-Based Portfolio Margin
+//!
+//! A SPAN-like margin engine for portfolios of options on several
+//! underlyings. Every underlying is a product group, and its positions are
+//! repriced with Black-Scholes over a [`ScenarioGrid`] of relative price and
+//! volatility shocks. The scenario P&L of the positions forms the risk array
+//! of the group; its worst loss is the scan risk.
+//!
+//! Offsets between positions are recognised in two steps:
+//!
+//! * **Inter-month**: the scan risk of the whole group is never larger than
+//!   the sum of the scan risks of each expiration month on its own. A
+//!   configurable fraction of that difference is credited, the rest is
+//!   charged back as the inter-month spread charge.
+//! * **Inter-commodity**: configured pairs of correlated underlyings whose
+//!   groups move in opposite directions receive a credit equal to a fraction
+//!   of the smaller of the two group requirements.
+//!
+//! The requirement of a group is never lower than the short option minimum,
+//! a fraction of the underlying value of its short options.
+
+use crate::error::PricingError;
+use crate::model::position::Position;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+/// Relative price and volatility shocks over which positions are stressed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ScenarioGrid {
+    /// Relative moves of the underlying price (-0.1 = 10% down).
+    pub price_shocks: Vec<Decimal>,
+    /// Relative moves of the implied volatility (0.25 = 25% higher).
+    pub volatility_shocks: Vec<Decimal>,
+}
+
+impl ScenarioGrid {
+    /// Creates a grid from explicit shocks.
+    pub fn new(price_shocks: Vec<Decimal>, volatility_shocks: Vec<Decimal>) -> Self {
+        ScenarioGrid {
+            price_shocks,
+            volatility_shocks,
+        }
+    }
+
+    /// Grid of `2 * price_steps + 1` price shocks evenly spread over
+    /// `±price_range`, combined with a volatility move down, none and up by
+    /// `volatility_range`.
+    pub fn uniform(price_range: Decimal, price_steps: usize, volatility_range: Decimal) -> Self {
+        let steps = Decimal::from(price_steps.max(1));
+        let price_shocks = (-(price_steps.max(1) as i64)..=price_steps.max(1) as i64)
+            .map(|step| price_range * Decimal::from(step) / steps)
+            .collect();
+        ScenarioGrid::new(
+            price_shocks,
+            vec![-volatility_range, Decimal::ZERO, volatility_range],
+        )
+    }
+
+    /// Every combination of price and volatility shock.
+    fn scenarios(&self) -> impl Iterator<Item = (Decimal, Decimal)> + '_ {
+        self.price_shocks.iter().flat_map(move |&price| {
+            self.volatility_shocks
+                .iter()
+                .map(move |&volatility| (price, volatility))
+        })
+    }
+}
+
+impl Default for ScenarioGrid {
+    /// Price moves of up to 15% in thirds, with volatility 25% lower and higher,
+    /// as in the standard SPAN risk array.
+    fn default() -> Self {
+        ScenarioGrid::uniform(dec!(0.15), 3, dec!(0.25))
+    }
+}
+
+/// Credit between two correlated underlyings held in opposite directions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct InterCommodityOffset {
+    /// First underlying symbol.
+    pub first: String,
+    /// Second underlying symbol.
+    pub second: String,
+    /// Fraction of the smaller group requirement credited (0.5 = 50%).
+    pub credit_rate: Decimal,
+}
+
+impl InterCommodityOffset {
+    /// Creates a new inter-commodity offset.
+    pub fn new(first: &str, second: &str, credit_rate: Decimal) -> Self {
+        InterCommodityOffset {
+            first: first.to_string(),
+            second: second.to_string(),
+            credit_rate,
+        }
+    }
+}
+
+/// Margin of the positions on one underlying.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct GroupMargin {
+    /// Underlying of the group.
+    pub underlying_symbol: String,
+    /// P&L of the group in every scenario of the grid, in grid order.
+    pub risk_array: Vec<Decimal>,
+    /// Worst loss of the group as a whole.
+    pub scan_risk: Decimal,
+    /// Part of the diversification between expiration months charged back.
+    pub intermonth_charge: Decimal,
+    /// Floor for groups with short options.
+    pub short_option_minimum: Decimal,
+    /// Requirement of the group before inter-commodity credits.
+    pub requirement: Decimal,
+}
+
+/// Margin of a portfolio under the scenario engine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ScenarioMarginReport {
+    /// Requirement of each underlying, in alphabetical order.
+    pub groups: Vec<GroupMargin>,
+    /// Credit for offsetting correlated underlyings.
+    pub inter_commodity_credit: Decimal,
+    /// Total requirement.
+    pub total: Decimal,
+}
+
+/// Scenario-based portfolio margin engine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ScenarioMargin {
+    /// Shocks applied to every underlying.
+    pub grid: ScenarioGrid,
+    /// Fraction of the inter-month diversification credited (1 = full offset).
+    pub intermonth_offset: Decimal,
+    /// Credits between correlated underlyings.
+    pub inter_commodity_offsets: Vec<InterCommodityOffset>,
+    /// Fraction of the underlying value of short options required at least.
+    pub short_option_minimum: Decimal,
+}
+
+impl Default for ScenarioMargin {
+    fn default() -> Self {
+        ScenarioMargin {
+            grid: ScenarioGrid::default(),
+            intermonth_offset: dec!(0.75),
+            inter_commodity_offsets: Vec::new(),
+            short_option_minimum: dec!(0.10),
+        }
+    }
+}
+
+impl ScenarioMargin {
+    /// Creates an engine with the given grid and the default offsets.
+    pub fn new(grid: ScenarioGrid) -> Self {
+        ScenarioMargin {
+            grid,
+            ..ScenarioMargin::default()
+        }
+    }
+
+    /// Sets the fraction of the inter-month diversification credited.
+    pub fn with_intermonth_offset(mut self, intermonth_offset: Decimal) -> Self {
+        self.intermonth_offset = intermonth_offset;
+        self
+    }
+
+    /// Adds a credit between two correlated underlyings.
+    pub fn with_inter_commodity_offset(mut self, offset: InterCommodityOffset) -> Self {
+        self.inter_commodity_offsets.push(offset);
+        self
+    }
+
+    /// Sets the short option minimum as a fraction of the underlying value.
+    pub fn with_short_option_minimum(mut self, short_option_minimum: Decimal) -> Self {
+        self.short_option_minimum = short_option_minimum;
+        self
+    }
+
+    /// Margin of the open positions.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if a position cannot be priced with
+    /// Black-Scholes or its expiration date is invalid.
+    pub fn calculate(&self, positions: &[&Position]) -> Result<ScenarioMarginReport, PricingError> {
+        let mut by_symbol: BTreeMap<&str, Vec<&Position>> = BTreeMap::new();
+        for position in positions.iter().filter(|position| position.is_open()) {
+            by_symbol
+                .entry(position.option.underlying_symbol.as_str())
+                .or_default()
+                .push(position);
+        }
+
+        let groups = by_symbol
+            .into_iter()
+            .map(|(symbol, positions)| self.group_margin(symbol, &positions))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let inter_commodity_credit = self
+            .inter_commodity_offsets
+            .iter()
+            .filter_map(|offset| {
+                let first = groups
+                    .iter()
+                    .find(|group| group.underlying_symbol == offset.first)?;
+                let second = groups
+                    .iter()
+                    .find(|group| group.underlying_symbol == offset.second)?;
+                let opposite = self.direction(first) * self.direction(second) < Decimal::ZERO;
+                opposite.then(|| offset.credit_rate * first.requirement.min(second.requirement))
+            })
+            .sum();
+
+        let gross: Decimal = groups.iter().map(|group| group.requirement).sum();
+        Ok(ScenarioMarginReport {
+            groups,
+            inter_commodity_credit,
+            total: (gross - inter_commodity_credit).max(Decimal::ZERO),
+        })
+    }
+
+    /// Margin of the positions on one underlying.
+    fn group_margin(
+        &self,
+        symbol: &str,
+        positions: &[&Position],
+    ) -> Result<GroupMargin, PricingError> {
+        let mut risk_array = vec![Decimal::ZERO; self.grid.scenarios().count()];
+        let mut months: BTreeMap<String, Vec<Decimal>> = BTreeMap::new();
+        for position in positions {
+            let month = position
+                .option
+                .expiration_date
+                .get_date()?
+                .format("%Y-%m")
+                .to_string();
+            let month_array = months
+                .entry(month)
+                .or_insert_with(|| vec![Decimal::ZERO; risk_array.len()]);
+            for (index, pnl) in self.position_risk_array(position)?.into_iter().enumerate() {
+                risk_array[index] += pnl;
+                month_array[index] += pnl;
+            }
+        }
+
+        let scan_risk = worst_loss(&risk_array);
+        let months_scan_risk: Decimal = months.values().map(|array| worst_loss(array)).sum();
+        let intermonth_charge =
+            (months_scan_risk - scan_risk) * (Decimal::ONE - self.intermonth_offset);
+        let short_option_minimum = positions
+            .iter()
+            .filter(|position| position.option.is_short())
+            .map(|position| {
+                self.short_option_minimum
+                    * position.option.underlying_price.to_dec()
+                    * position.option.quantity.to_dec()
+            })
+            .sum();
+
+        Ok(GroupMargin {
+            underlying_symbol: symbol.to_string(),
+            risk_array,
+            scan_risk,
+            intermonth_charge,
+            short_option_minimum,
+            requirement: (scan_risk + intermonth_charge).max(short_option_minimum),
+        })
+    }
+
+    /// P&L of a position in every scenario of the grid.
+    fn position_risk_array(&self, position: &Position) -> Result<Vec<Decimal>, PricingError> {
+        let option = &position.option;
+        let quantity = option.quantity.to_dec();
+        let current_price = option.calculate_price_black_scholes()?;
+        self.grid
+            .scenarios()
+            .map(|(price_shock, volatility_shock)| {
+                let mut scenario = option.clone();
+                scenario.underlying_price = shocked(option.underlying_price, price_shock);
+                scenario.implied_volatility = shocked(option.implied_volatility, volatility_shock);
+                Ok((scenario.calculate_price_black_scholes()? - current_price) * quantity)
+            })
+            .collect()
+    }
+
+    /// Sign of the exposure of a group to the underlying price: the P&L of
+    /// the largest up move less that of the largest down move, at unchanged
+    /// volatility.
+    fn direction(&self, group: &GroupMargin) -> Decimal {
+        let scenarios: Vec<(Decimal, Decimal)> = self.grid.scenarios().collect();
+        let pnl_at = |target: Option<Decimal>| {
+            target.and_then(|target| {
+                scenarios
+                    .iter()
+                    .position(|&(price, volatility)| price == target && volatility == Decimal::ZERO)
+                    .map(|index| group.risk_array[index])
+            })
+        };
+        let up = pnl_at(self.grid.price_shocks.iter().copied().max());
+        let down = pnl_at(self.grid.price_shocks.iter().copied().min());
+        match (up, down) {
+            (Some(up), Some(down)) if up > down => Decimal::ONE,
+            (Some(up), Some(down)) if up < down => Decimal::NEGATIVE_ONE,
+            _ => Decimal::ZERO,
+        }
+    }
+}
+
+/// Largest loss of a risk array as a non-negative amount.
+fn worst_loss(risk_array: &[Decimal]) -> Decimal {
+    risk_array
+        .iter()
+        .map(|pnl| -*pnl)
+        .fold(Decimal::ZERO, Decimal::max)
+}
+
+/// `value` moved by the relative `shock`, floored at zero.
+fn shocked(value: Positive, shock: Decimal) -> Positive {
+    Positive::new_decimal(value.to_dec() * (Decimal::ONE + shock)).unwrap_or(Positive::ZERO)
+}
+
+#[cfg(test)]
+mod tests_scenario_margin {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::Options;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use chrono::{Duration, Utc};
+    use positive::pos_or_panic;
+
+    fn position(symbol: &str, style: OptionStyle, side: Side, strike: f64, days: i64) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            symbol.to_string(),
+            Positive::new(strike).unwrap(),
+            ExpirationDate::DateTime(Utc::now() + Duration::days(days)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            Positive::ONE,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_uniform_grid() {
+        let grid = ScenarioGrid::uniform(dec!(0.15), 3, dec!(0.25));
+        assert_eq!(
+            grid.price_shocks,
+            vec![
+                dec!(-0.15),
+                dec!(-0.10),
+                dec!(-0.05),
+                Decimal::ZERO,
+                dec!(0.05),
+                dec!(0.10),
+                dec!(0.15)
+            ]
+        );
+        assert_eq!(grid.scenarios().count(), 21);
+    }
+
+    #[test]
+    fn test_hedged_group_needs_less_margin() {
+        let engine = ScenarioMargin::default().with_short_option_minimum(Decimal::ZERO);
+        let naked = position("AAA", OptionStyle::Call, Side::Short, 100.0, 30);
+        let hedge = position("AAA", OptionStyle::Call, Side::Long, 105.0, 30);
+
+        let naked_report = engine.calculate(&[&naked]).unwrap();
+        let spread_report = engine.calculate(&[&naked, &hedge]).unwrap();
+        assert!(naked_report.total > Decimal::ZERO);
+        assert!(spread_report.total < naked_report.total);
+        assert_eq!(spread_report.groups[0].intermonth_charge, Decimal::ZERO);
+        assert_eq!(spread_report.groups[0].risk_array.len(), 21);
+    }
+
+    #[test]
+    fn test_intermonth_offset() {
+        let near = position("AAA", OptionStyle::Call, Side::Short, 100.0, 20);
+        let far = position("AAA", OptionStyle::Call, Side::Long, 100.0, 80);
+
+        let full = ScenarioMargin::default().with_intermonth_offset(Decimal::ONE);
+        let none = ScenarioMargin::default().with_intermonth_offset(Decimal::ZERO);
+        let full = full.calculate(&[&near, &far]).unwrap();
+        let none = none.calculate(&[&near, &far]).unwrap();
+
+        assert_eq!(full.groups[0].intermonth_charge, Decimal::ZERO);
+        assert!(none.groups[0].intermonth_charge > Decimal::ZERO);
+        assert_eq!(full.groups[0].scan_risk, none.groups[0].scan_risk);
+    }
+
+    #[test]
+    fn test_inter_commodity_credit_and_minimum() {
+        let long = position("AAA", OptionStyle::Call, Side::Long, 100.0, 30);
+        let short = position("BBB", OptionStyle::Call, Side::Short, 100.0, 30);
+        let engine = ScenarioMargin::default()
+            .with_inter_commodity_offset(InterCommodityOffset::new("AAA", "BBB", dec!(0.5)));
+        let report = engine.calculate(&[&long, &short]).unwrap();
+
+        let smaller = report.groups[0]
+            .requirement
+            .min(report.groups[1].requirement);
+        assert_eq!(report.inter_commodity_credit, smaller / Decimal::TWO);
+        assert_eq!(
+            report.total,
+            report.groups[0].requirement + report.groups[1].requirement
+                - report.inter_commodity_credit
+        );
+        // Short option minimum: 10% of 100 for one unit.
+        assert_eq!(report.groups[1].short_option_minimum, dec!(10));
+        assert!(report.groups[1].requirement >= dec!(10));
+
+        let same_direction = engine
+            .calculate(&[
+                &long,
+                &position("BBB", OptionStyle::Call, Side::Long, 100.0, 30),
+            ])
+            .unwrap();
+        assert_eq!(same_direction.inter_commodity_credit, Decimal::ZERO);
+    }
+}
@@ -269,6 +269,7 @@ mod tests_span {
     use super::*;
     use crate::model::types::{OptionStyle, Side};
     use crate::model::utils::create_sample_option;
+    use crate::model::{Currency, PositionStatus};
 
     use chrono::Utc;
     use positive::pos_or_panic;
@@ -294,6 +295,10 @@ mod tests_span {
             close_fee: pos_or_panic!(0.5),
             epic: Some("Epic123".to_string()),
             extra_fields: None,
+            status: PositionStatus::Open,
+            fills: Vec::new(),
+            realized_pnl: Decimal::ZERO,
+            currency: Currency::default(),
         };
 
         let span = SPANMargin::new(
@@ -0,0 +1,460 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Stress Testing
+//!
+//! Revalues a book of option positions and stock under user-defined
+//! [`StressScenario`]s. A scenario combines three shocks applied to every
+//! underlying at once:
+//!
+//! * a relative move of the underlying price (-0.1 = 10% down),
+//! * a shift of the implied volatility in volatility points (0.05 = +5 vol),
+//! * a parallel shift of the risk-free rate (0.01 = +100 basis points).
+//!
+//! How implied volatility reacts to the spot move is set by the
+//! [`VolatilityDynamics`]. Under sticky strike every option keeps its own
+//! volatility. Under sticky delta the smile moves with the underlying, so an
+//! option takes the volatility the smile gives today to the strike with the
+//! same moneyness. Without a smile for an underlying both behave the same.
+//!
+//! The result is a revaluation grid: the P&L of every position and of the
+//! whole book in every scenario.
+
+use crate::constants::MIN_VOLATILITY;
+use crate::curves::Curve;
+use crate::error::OptionsError;
+use crate::geometrics::LinearInterpolation;
+use crate::model::leg::SpotPosition;
+use crate::model::position::Position;
+use crate::model::types::Side;
+use crate::volatility::VolatilitySmile;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+/// Reaction of implied volatility to a move of the underlying price.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum VolatilityDynamics {
+    /// The volatility of each strike is unchanged.
+    #[default]
+    StickyStrike,
+    /// The smile moves with the underlying, keeping the volatility of each
+    /// moneyness unchanged.
+    StickyDelta,
+}
+
+/// Market shocks applied together to every underlying.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StressScenario {
+    /// Name of the scenario.
+    pub name: String,
+    /// Relative move of the underlying price (-0.1 = 10% down).
+    pub spot_shock: Decimal,
+    /// Shift of the implied volatility in volatility points (0.05 = +5 vol).
+    pub volatility_shift: Decimal,
+    /// Parallel shift of the risk-free rate (0.01 = +100 basis points).
+    pub rate_shift: Decimal,
+}
+
+impl StressScenario {
+    /// Creates a scenario from its shocks.
+    pub fn new(
+        name: &str,
+        spot_shock: Decimal,
+        volatility_shift: Decimal,
+        rate_shift: Decimal,
+    ) -> Self {
+        StressScenario {
+            name: name.to_string(),
+            spot_shock,
+            volatility_shift,
+            rate_shift,
+        }
+    }
+
+    /// Scenario named after its shocks, such as `spot -10% / vol +5 / rate +0bp`.
+    pub fn from_shocks(
+        spot_shock: Decimal,
+        volatility_shift: Decimal,
+        rate_shift: Decimal,
+    ) -> Self {
+        let name = format!(
+            "spot {:+}% / vol {:+} / rate {:+}bp",
+            (spot_shock * Decimal::ONE_HUNDRED).normalize(),
+            (volatility_shift * Decimal::ONE_HUNDRED).normalize(),
+            (rate_shift * dec!(10000)).normalize()
+        );
+        StressScenario::new(&name, spot_shock, volatility_shift, rate_shift)
+    }
+}
+
+/// Revaluation of one option position or stock holding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PositionStress {
+    /// Underlying symbol.
+    pub underlying_symbol: String,
+    /// Index of the holding in the option positions, or in the stock when
+    /// `is_stock` is set.
+    pub index: usize,
+    /// Whether the holding is stock rather than an option position.
+    pub is_stock: bool,
+    /// Current value of the holding.
+    pub value: Decimal,
+    /// P&L of the holding in every scenario, in scenario order.
+    pub pnl: Vec<Decimal>,
+}
+
+/// Revaluation grid of a book under a set of scenarios.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StressReport {
+    /// Scenarios of the grid.
+    pub scenarios: Vec<StressScenario>,
+    /// Every open option position, in input order, followed by every stock
+    /// holding.
+    pub positions: Vec<PositionStress>,
+    /// P&L of the whole book in every scenario, in scenario order.
+    pub book_pnl: Vec<Decimal>,
+}
+
+impl StressReport {
+    /// Scenario with the largest loss of the book, with its P&L.
+    pub fn worst_scenario(&self) -> Option<(&StressScenario, Decimal)> {
+        self.scenarios
+            .iter()
+            .zip(self.book_pnl.iter().copied())
+            .min_by_key(|(_, pnl)| *pnl)
+    }
+
+    /// P&L of the positions on each underlying in every scenario.
+    pub fn pnl_by_underlying(&self) -> BTreeMap<String, Vec<Decimal>> {
+        let mut groups: BTreeMap<String, Vec<Decimal>> = BTreeMap::new();
+        for position in &self.positions {
+            let group = groups
+                .entry(position.underlying_symbol.clone())
+                .or_insert_with(|| vec![Decimal::ZERO; self.scenarios.len()]);
+            for (total, pnl) in group.iter_mut().zip(&position.pnl) {
+                *total += *pnl;
+            }
+        }
+        groups
+    }
+}
+
+/// Stress-test engine.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StressTest {
+    /// Scenarios applied to the book.
+    pub scenarios: Vec<StressScenario>,
+    /// Reaction of implied volatility to the spot move.
+    pub dynamics: VolatilityDynamics,
+    /// Volatility smile of each underlying, by strike.
+    pub smiles: BTreeMap<String, Curve>,
+}
+
+impl Default for StressTest {
+    /// Spot moves of ±5, 10 and 20% combined with volatility shifts of ±5
+    /// and 10 points, at unchanged rates, under sticky strike.
+    fn default() -> Self {
+        StressTest::grid(
+            &[
+                dec!(-0.20),
+                dec!(-0.10),
+                dec!(-0.05),
+                Decimal::ZERO,
+                dec!(0.05),
+                dec!(0.10),
+                dec!(0.20),
+            ],
+            &[
+                dec!(-0.10),
+                dec!(-0.05),
+                Decimal::ZERO,
+                dec!(0.05),
+                dec!(0.10),
+            ],
+            &[Decimal::ZERO],
+        )
+    }
+}
+
+impl StressTest {
+    /// Creates an engine from explicit scenarios, under sticky strike.
+    pub fn new(scenarios: Vec<StressScenario>) -> Self {
+        StressTest {
+            scenarios,
+            dynamics: VolatilityDynamics::default(),
+            smiles: BTreeMap::new(),
+        }
+    }
+
+    /// Every combination of the spot, volatility and rate shocks.
+    pub fn grid(
+        spot_shocks: &[Decimal],
+        volatility_shifts: &[Decimal],
+        rate_shifts: &[Decimal],
+    ) -> Self {
+        let mut scenarios = Vec::new();
+        for &spot in spot_shocks {
+            for &volatility in volatility_shifts {
+                for &rate in rate_shifts {
+                    scenarios.push(StressScenario::from_shocks(spot, volatility, rate));
+                }
+            }
+        }
+        StressTest::new(scenarios)
+    }
+
+    /// Adds a scenario.
+    pub fn with_scenario(mut self, scenario: StressScenario) -> Self {
+        self.scenarios.push(scenario);
+        self
+    }
+
+    /// Sets the reaction of implied volatility to the spot move.
+    pub fn with_dynamics(mut self, dynamics: VolatilityDynamics) -> Self {
+        self.dynamics = dynamics;
+        self
+    }
+
+    /// Sets the volatility smile of an underlying, used under sticky delta.
+    pub fn with_smile(mut self, symbol: &str, smile: &impl VolatilitySmile) -> Self {
+        self.smiles.insert(symbol.to_string(), smile.smile());
+        self
+    }
+
+    /// Revalues the open positions and stock in every scenario. Stock is
+    /// valued at the underlying price of the options on the same symbol, or
+    /// at its cost basis when there are none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if a position cannot be priced with
+    /// Black-Scholes.
+    pub fn run(
+        &self,
+        positions: &[&Position],
+        stock: &[&SpotPosition],
+    ) -> Result<StressReport, OptionsError> {
+        let mut results = Vec::new();
+        for (index, position) in positions.iter().enumerate() {
+            if !position.is_open() {
+                continue;
+            }
+            let option = &position.option;
+            let quantity = option.quantity.to_dec();
+            let value = option.calculate_price_black_scholes()? * quantity;
+            let pnl = self
+                .scenarios
+                .iter()
+                .map(|scenario| {
+                    let mut shocked = option.clone();
+                    shocked.underlying_price = shocked_price(option.underlying_price, scenario);
+                    shocked.implied_volatility = self.shocked_volatility(
+                        &option.underlying_symbol,
+                        option.strike_price,
+                        option.implied_volatility,
+                        scenario,
+                    );
+                    shocked.risk_free_rate += scenario.rate_shift;
+                    Ok(shocked.calculate_price_black_scholes()? * quantity - value)
+                })
+                .collect::<Result<Vec<_>, OptionsError>>()?;
+            results.push(PositionStress {
+                underlying_symbol: option.underlying_symbol.clone(),
+                index,
+                is_stock: false,
+                value,
+                pnl,
+            });
+        }
+
+        for (index, spot) in stock.iter().enumerate() {
+            let price = positions
+                .iter()
+                .find(|position| position.option.underlying_symbol == spot.symbol)
+                .map(|position| position.option.underlying_price)
+                .unwrap_or(spot.cost_basis);
+            let sign = match spot.side {
+                Side::Long => Decimal::ONE,
+                Side::Short => Decimal::NEGATIVE_ONE,
+            };
+            let value = sign * price.to_dec() * spot.quantity.to_dec();
+            results.push(PositionStress {
+                underlying_symbol: spot.symbol.clone(),
+                index,
+                is_stock: true,
+                value,
+                pnl: self
+                    .scenarios
+                    .iter()
+                    .map(|scenario| value * scenario.spot_shock)
+                    .collect(),
+            });
+        }
+
+        let book_pnl = (0..self.scenarios.len())
+            .map(|scenario| results.iter().map(|result| result.pnl[scenario]).sum())
+            .collect();
+        Ok(StressReport {
+            scenarios: self.scenarios.clone(),
+            positions: results,
+            book_pnl,
+        })
+    }
+
+    /// Implied volatility of a strike after the shocks of `scenario`.
+    fn shocked_volatility(
+        &self,
+        symbol: &str,
+        strike: Positive,
+        volatility: Positive,
+        scenario: &StressScenario,
+    ) -> Positive {
+        let smile_shift = match (self.dynamics, self.smiles.get(symbol)) {
+            (VolatilityDynamics::StickyDelta, Some(smile)) => {
+                // The strike now has the moneyness of `strike / (1 + shock)` today.
+                let equivalent = strike.to_dec() / (Decimal::ONE + scenario.spot_shock);
+                match (
+                    smile_at(smile, equivalent),
+                    smile_at(smile, strike.to_dec()),
+                ) {
+                    (Some(moved), Some(current)) => moved - current,
+                    _ => Decimal::ZERO,
+                }
+            }
+            _ => Decimal::ZERO,
+        };
+        let shocked = volatility.to_dec() + scenario.volatility_shift + smile_shift;
+        Positive::new_decimal(shocked.max(MIN_VOLATILITY.to_dec())).unwrap_or(MIN_VOLATILITY)
+    }
+}
+
+/// Underlying price after the spot shock of `scenario`, floored at zero.
+fn shocked_price(price: Positive, scenario: &StressScenario) -> Positive {
+    Positive::new_decimal(price.to_dec() * (Decimal::ONE + scenario.spot_shock))
+        .unwrap_or(Positive::ZERO)
+}
+
+/// Volatility of the smile at `strike`, flat beyond its strikes.
+fn smile_at(smile: &Curve, strike: Decimal) -> Option<Decimal> {
+    let first = smile.points.first()?;
+    let last = smile.points.last()?;
+    if strike <= first.x {
+        return Some(first.y);
+    }
+    if strike >= last.x {
+        return Some(last.y);
+    }
+    smile.linear_interpolate(strike).ok().map(|point| point.y)
+}
+
+#[cfg(test)]
+mod tests_stress {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::curves::Point2D;
+    use crate::model::Options;
+    use crate::model::types::{OptionStyle, OptionType};
+    use chrono::Utc;
+    use positive::pos_or_panic;
+    use std::collections::BTreeSet;
+
+    fn position(style: OptionStyle, side: Side, strike: f64) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            "AAA".to_string(),
+            Positive::new(strike).unwrap(),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            Positive::ONE,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    struct SkewedSmile;
+
+    impl VolatilitySmile for SkewedSmile {
+        fn smile(&self) -> Curve {
+            Curve::new(BTreeSet::from([
+                Point2D::new(dec!(80), dec!(0.30)),
+                Point2D::new(dec!(100), dec!(0.20)),
+                Point2D::new(dec!(120), dec!(0.15)),
+            ]))
+        }
+    }
+
+    #[test]
+    fn test_default_grid() {
+        let engine = StressTest::default();
+        assert_eq!(engine.scenarios.len(), 35);
+        assert_eq!(engine.scenarios[0].name, "spot -20% / vol -10 / rate +0bp");
+    }
+
+    #[test]
+    fn test_revaluation_grid() {
+        let call = position(OptionStyle::Call, Side::Long, 100.0);
+        let put = position(OptionStyle::Put, Side::Short, 95.0);
+        let shares = SpotPosition::long("AAA".to_string(), pos_or_panic!(2.0), pos_or_panic!(90.0));
+        let engine = StressTest::new(vec![
+            StressScenario::from_shocks(Decimal::ZERO, Decimal::ZERO, Decimal::ZERO),
+            StressScenario::from_shocks(dec!(-0.10), dec!(0.05), Decimal::ZERO),
+            StressScenario::from_shocks(dec!(0.10), Decimal::ZERO, dec!(0.01)),
+        ]);
+        let report = engine.run(&[&call, &put], &[&shares]).unwrap();
+
+        assert_eq!(report.positions.len(), 3);
+        assert!(report.positions.iter().all(|p| p.pnl[0] == Decimal::ZERO));
+        // Shares are valued at the underlying price of the options.
+        assert_eq!(report.positions[2].value, dec!(200));
+        assert_eq!(report.positions[2].pnl[1], dec!(-20));
+        assert!(report.positions[0].pnl[2] > Decimal::ZERO);
+        assert!(report.positions[1].pnl[1] < Decimal::ZERO);
+
+        let (worst, pnl) = report.worst_scenario().unwrap();
+        assert_eq!(worst.spot_shock, dec!(-0.10));
+        assert_eq!(pnl, report.book_pnl[1]);
+        assert_eq!(report.pnl_by_underlying()["AAA"], report.book_pnl);
+    }
+
+    #[test]
+    fn test_sticky_delta_moves_smile() {
+        let put = position(OptionStyle::Put, Side::Long, 100.0);
+        let crash = StressScenario::from_shocks(dec!(-0.10), Decimal::ZERO, Decimal::ZERO);
+        let sticky_strike = StressTest::new(vec![crash.clone()]).with_smile("AAA", &SkewedSmile);
+        let sticky_delta = sticky_strike
+            .clone()
+            .with_dynamics(VolatilityDynamics::StickyDelta);
+
+        // After a 10% drop, the 100 strike has the moneyness of 111.1 today,
+        // where the smile is lower, so the put gains less under sticky delta.
+        let strike_pnl = sticky_strike.run(&[&put], &[]).unwrap().book_pnl[0];
+        let delta_pnl = sticky_delta.run(&[&put], &[]).unwrap().book_pnl[0];
+        assert!(delta_pnl < strike_pnl);
+
+        let volatility =
+            sticky_delta.shocked_volatility("AAA", Positive::HUNDRED, pos_or_panic!(0.2), &crash);
+        assert_eq!(volatility.to_dec().round_dp(4), dec!(0.1722));
+        let flat = StressTest::new(vec![crash]).with_dynamics(VolatilityDynamics::StickyDelta);
+        assert_eq!(flat.run(&[&put], &[]).unwrap().book_pnl[0], strike_pnl);
+    }
+}
@@ -0,0 +1,817 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Value-at-Risk
+//!
+//! Loss that the open positions of a portfolio are not expected to exceed
+//! over a horizon of trading days at a given confidence level. Two methods
+//! are available:
+//!
+//! * **Delta-gamma parametric**: the P&L of every underlying is approximated
+//!   with its delta and gamma, `ΔV ≈ Δ·dS + ½·Γ·dS²`, and the resulting
+//!   distribution is replaced by a normal with the same mean and variance.
+//! * **Monte Carlo full revaluation**: underlying prices are simulated as
+//!   driftless lognormal moves and every position is repriced with
+//!   Black-Scholes at the end of the horizon, so the non-linearity of the
+//!   options and their time decay are captured exactly.
+//!
+//! Both methods also report the expected shortfall (CVaR), the average loss
+//! beyond the Value-at-Risk. Short options lose far more in the tail than
+//! at the quantile, which the Value-at-Risk alone does not show. Value-at-Risk
+//! and expected shortfall are given for the whole set of positions, for each
+//! underlying and for each position on its own.
+//!
+//! The volatility of an underlying is the implied volatility of its option
+//! closest to the money, unless set explicitly. Underlyings move together as
+//! given by a [`CorrelationMatrix`]; those missing from it, or all of them
+//! when none is set, move independently of each other.
+
+use crate::ExpirationDate;
+use crate::constants::TRADING_DAYS;
+use crate::error::OptionsError;
+use crate::model::correlation::CorrelationMatrix;
+use crate::model::leg::SpotPosition;
+use crate::model::option::Options;
+use crate::model::position::Position;
+use crate::model::types::Side;
+use crate::simulation::seeded_rng;
+use positive::Positive;
+use positive::constants::DAYS_IN_A_YEAR;
+use rand_distr::{Distribution, StandardNormal};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{Continuous, ContinuousCDF, Normal};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+/// Method used to estimate the Value-at-Risk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum VarMethod {
+    /// Normal approximation of the delta-gamma expansion of the P&L.
+    DeltaGamma,
+    /// Full revaluation of the positions over simulated prices.
+    MonteCarlo,
+}
+
+/// Risk of the positions on one underlying, taken on their own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct UnderlyingVar {
+    /// Underlying symbol.
+    pub underlying_symbol: String,
+    /// Current price of the underlying.
+    pub underlying_price: Positive,
+    /// Annualized volatility of the underlying.
+    pub volatility: Positive,
+    /// Net delta of the positions, in currency per unit move of the underlying.
+    pub delta: Decimal,
+    /// Net gamma of the positions.
+    pub gamma: Decimal,
+    /// Value-at-Risk of the positions on this underlying alone.
+    pub var: Decimal,
+    /// Expected shortfall of the positions on this underlying alone.
+    pub expected_shortfall: Decimal,
+}
+
+/// Tail risk of a single option position or stock holding, taken on its own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PositionTailRisk {
+    /// Underlying symbol.
+    pub underlying_symbol: String,
+    /// Index of the holding in the option positions, or in the stock when
+    /// `is_stock` is set.
+    pub index: usize,
+    /// Whether the holding is stock rather than an option position.
+    pub is_stock: bool,
+    /// Value-at-Risk of the holding.
+    pub var: Decimal,
+    /// Expected shortfall of the holding.
+    pub expected_shortfall: Decimal,
+}
+
+/// Value-at-Risk of a set of positions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct VarReport {
+    /// Method of the estimate.
+    pub method: VarMethod,
+    /// Confidence level (0.99 = 99%).
+    pub confidence: Decimal,
+    /// Horizon in trading days.
+    pub horizon_days: Positive,
+    /// Expected P&L over the horizon.
+    pub expected_pnl: Decimal,
+    /// Standard deviation of the P&L over the horizon.
+    pub standard_deviation: Decimal,
+    /// Value-at-Risk, as a non-negative loss.
+    pub var: Decimal,
+    /// Expected shortfall: average loss beyond the Value-at-Risk, as a
+    /// non-negative amount.
+    pub expected_shortfall: Decimal,
+    /// Standalone risk of every underlying, in alphabetical order.
+    pub underlyings: Vec<UnderlyingVar>,
+    /// Standalone tail risk of every open option position, in input order,
+    /// followed by every stock holding.
+    pub positions: Vec<PositionTailRisk>,
+}
+
+impl VarReport {
+    /// Sum of the standalone expected shortfalls of the holdings less the
+    /// expected shortfall of the whole: the tail risk removed by netting the
+    /// holdings against each other.
+    pub fn diversification_benefit(&self) -> Decimal {
+        self.positions
+            .iter()
+            .map(|position| position.expected_shortfall)
+            .sum::<Decimal>()
+            - self.expected_shortfall
+    }
+}
+
+/// Value-at-Risk calculator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ValueAtRisk {
+    /// Confidence level (0.99 = 99%).
+    pub confidence: Decimal,
+    /// Horizon in trading days.
+    pub horizon_days: Positive,
+    /// Number of Monte Carlo scenarios.
+    pub simulations: usize,
+    /// Seed of the Monte Carlo simulation, `None` for a random one.
+    pub seed: Option<u64>,
+    /// Annualized volatility of underlyings overriding their implied volatility.
+    pub volatilities: BTreeMap<String, Positive>,
+    /// Correlation between the returns of the underlyings, `None` when they
+    /// are independent.
+    pub correlations: Option<CorrelationMatrix>,
+}
+
+impl Default for ValueAtRisk {
+    /// One-day 99% Value-at-Risk over 10,000 scenarios.
+    fn default() -> Self {
+        ValueAtRisk {
+            confidence: dec!(0.99),
+            horizon_days: Positive::ONE,
+            simulations: 10_000,
+            seed: None,
+            volatilities: BTreeMap::new(),
+            correlations: None,
+        }
+    }
+}
+
+impl ValueAtRisk {
+    /// Creates a calculator for the given confidence level and horizon.
+    pub fn new(confidence: Decimal, horizon_days: Positive) -> Self {
+        ValueAtRisk {
+            confidence,
+            horizon_days,
+            ..ValueAtRisk::default()
+        }
+    }
+
+    /// One-day Value-at-Risk at the given confidence level.
+    pub fn one_day(confidence: Decimal) -> Self {
+        ValueAtRisk::new(confidence, Positive::ONE)
+    }
+
+    /// Ten-day Value-at-Risk at the given confidence level.
+    pub fn ten_day(confidence: Decimal) -> Self {
+        ValueAtRisk::new(confidence, Positive::TEN)
+    }
+
+    /// Sets the number of Monte Carlo scenarios.
+    pub fn with_simulations(mut self, simulations: usize) -> Self {
+        self.simulations = simulations;
+        self
+    }
+
+    /// Seeds the Monte Carlo simulation, making it reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the annualized volatility of an underlying.
+    pub fn with_volatility(mut self, symbol: &str, volatility: Positive) -> Self {
+        self.volatilities.insert(symbol.to_string(), volatility);
+        self
+    }
+
+    /// Sets the correlation between the returns of the underlyings.
+    pub fn with_correlations(mut self, correlations: CorrelationMatrix) -> Self {
+        self.correlations = Some(correlations);
+        self
+    }
+
+    /// Delta-gamma parametric Value-at-Risk of the open positions and stock.
+    /// The expected shortfall is that of the fitted normal distribution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if the confidence level is not strictly
+    /// between 0 and 1, a position cannot be priced with Black-Scholes, or
+    /// the volatility of a stock-only underlying is unknown.
+    pub fn parametric(
+        &self,
+        positions: &[&Position],
+        stock: &[&SpotPosition],
+    ) -> Result<VarReport, OptionsError> {
+        let z = self.quantile()?;
+        let groups = self.groups(positions, stock)?;
+        let tail = self.tail_factor(z);
+        let correlations = self.correlations(&groups);
+        let mut expected_pnl = Decimal::ZERO;
+        let mut sensitivities = Vec::with_capacity(groups.len());
+        let mut underlyings = Vec::with_capacity(groups.len());
+        let mut holdings = Vec::new();
+        for group in &groups {
+            let move_variance =
+                (group.price.to_dec() * group.volatility.to_dec()).powi(2) * self.horizon_years();
+            let (delta, gamma) = delta_gamma(group.price, |price| group.value_at(price))?;
+            let (mean, group_variance) = delta_gamma_moments(delta, gamma, move_variance);
+            expected_pnl += mean;
+            sensitivities.push((delta, gamma, move_variance));
+            underlyings.push(UnderlyingVar {
+                underlying_symbol: group.symbol.to_string(),
+                underlying_price: group.price,
+                volatility: group.volatility,
+                delta,
+                gamma,
+                var: (z * sqrt(group_variance) - mean).max(Decimal::ZERO),
+                expected_shortfall: (tail * sqrt(group_variance) - mean).max(Decimal::ZERO),
+            });
+            for (key, holding) in &group.holdings {
+                let (delta, gamma) =
+                    delta_gamma(group.price, |price| holding.value_at(price, Positive::ZERO))?;
+                let (mean, variance) = delta_gamma_moments(delta, gamma, move_variance);
+                holdings.push(PositionTailRisk {
+                    underlying_symbol: group.symbol.to_string(),
+                    index: key.1,
+                    is_stock: key.0,
+                    var: (z * sqrt(variance) - mean).max(Decimal::ZERO),
+                    expected_shortfall: (tail * sqrt(variance) - mean).max(Decimal::ZERO),
+                });
+            }
+        }
+        // Var(Σ Δ·dS + ½·Γ·dS²) for jointly normal moves, whose squares
+        // have covariance 2·ρ²·s_i²·s_j².
+        let mut variance = Decimal::ZERO;
+        for (row, (delta_i, gamma_i, variance_i)) in sensitivities.iter().enumerate() {
+            for (column, (delta_j, gamma_j, variance_j)) in sensitivities.iter().enumerate() {
+                let covariance = if row == column {
+                    *variance_i
+                } else {
+                    correlations.at(row, column).unwrap_or(Decimal::ZERO)
+                        * sqrt(*variance_i * *variance_j)
+                };
+                variance += delta_i * delta_j * covariance
+                    + gamma_i * gamma_j * covariance.powi(2) / Decimal::TWO;
+            }
+        }
+        let standard_deviation = sqrt(variance);
+        Ok(VarReport {
+            method: VarMethod::DeltaGamma,
+            confidence: self.confidence,
+            horizon_days: self.horizon_days,
+            expected_pnl,
+            standard_deviation,
+            var: (z * standard_deviation - expected_pnl).max(Decimal::ZERO),
+            expected_shortfall: (tail * standard_deviation - expected_pnl).max(Decimal::ZERO),
+            underlyings,
+            positions: sorted_holdings(holdings),
+        })
+    }
+
+    /// Monte Carlo Value-at-Risk of the open positions and stock, repricing
+    /// every position at the end of the horizon. The expected shortfall is
+    /// the average of the simulated losses at or beyond the Value-at-Risk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OptionsError` if the confidence level is not strictly
+    /// between 0 and 1, no scenario is requested, a position cannot be priced
+    /// with Black-Scholes, or the volatility of a stock-only underlying is
+    /// unknown.
+    pub fn monte_carlo(
+        &self,
+        positions: &[&Position],
+        stock: &[&SpotPosition],
+    ) -> Result<VarReport, OptionsError> {
+        self.quantile()?;
+        if self.simulations == 0 {
+            return Err(OptionsError::OtherError {
+                reason: "Monte Carlo VaR requires at least one scenario".to_string(),
+            });
+        }
+        let groups = self.groups(positions, stock)?;
+        let horizon = self.horizon_years().to_f64().unwrap_or(0.0);
+        let days_elapsed = self.calendar_days();
+        let correlations = self.correlations(&groups);
+        let mut rng = seeded_rng(self.seed);
+        let current_values = groups
+            .iter()
+            .map(|group| {
+                group
+                    .holdings
+                    .iter()
+                    .map(|(_, holding)| holding.value_at(group.price, Positive::ZERO))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut holding_pnls: Vec<Vec<Vec<Decimal>>> = groups
+            .iter()
+            .map(|group| vec![Vec::with_capacity(self.simulations); group.holdings.len()])
+            .collect();
+        for _ in 0..self.simulations {
+            let independent: Vec<f64> = groups
+                .iter()
+                .map(|_| StandardNormal.sample(&mut rng))
+                .collect();
+            let draws = correlations.correlate(&independent);
+            for (index, (group, z)) in groups.iter().zip(draws).enumerate() {
+                let sigma = group.volatility.to_f64();
+                let growth = (sigma * horizon.sqrt() * z - sigma * sigma * horizon / 2.0).exp();
+                let price = Decimal::from_f64(group.price.to_f64() * growth)
+                    .and_then(|price| Positive::new_decimal(price).ok())
+                    .unwrap_or(Positive::ZERO);
+                for (slot, (_, holding)) in group.holdings.iter().enumerate() {
+                    let pnl = holding.value_at(price, days_elapsed)? - current_values[index][slot];
+                    holding_pnls[index][slot].push(pnl);
+                }
+            }
+        }
+
+        let group_pnls: Vec<Vec<Decimal>> = holding_pnls
+            .iter()
+            .map(|pnls| sum_scenarios(pnls, self.simulations))
+            .collect();
+        let total = sum_scenarios(&group_pnls, self.simulations);
+        let mut underlyings = Vec::with_capacity(groups.len());
+        let mut holdings = Vec::new();
+        for ((group, pnls), per_holding) in groups.iter().zip(&group_pnls).zip(&holding_pnls) {
+            let (delta, gamma) = delta_gamma(group.price, |price| group.value_at(price))?;
+            let (var, expected_shortfall) = self.tail_losses(pnls);
+            underlyings.push(UnderlyingVar {
+                underlying_symbol: group.symbol.to_string(),
+                underlying_price: group.price,
+                volatility: group.volatility,
+                delta,
+                gamma,
+                var,
+                expected_shortfall,
+            });
+            for ((key, _), pnls) in group.holdings.iter().zip(per_holding) {
+                let (var, expected_shortfall) = self.tail_losses(pnls);
+                holdings.push(PositionTailRisk {
+                    underlying_symbol: group.symbol.to_string(),
+                    index: key.1,
+                    is_stock: key.0,
+                    var,
+                    expected_shortfall,
+                });
+            }
+        }
+        let (expected_pnl, standard_deviation) = mean_and_deviation(&total);
+        let (var, expected_shortfall) = self.tail_losses(&total);
+        Ok(VarReport {
+            method: VarMethod::MonteCarlo,
+            confidence: self.confidence,
+            horizon_days: self.horizon_days,
+            expected_pnl,
+            standard_deviation,
+            var,
+            expected_shortfall,
+            underlyings,
+            positions: sorted_holdings(holdings),
+        })
+    }
+
+    /// Correlation matrix of the underlyings of `groups`, in their order.
+    fn correlations(&self, groups: &[UnderlyingGroup]) -> CorrelationMatrix {
+        let symbols: Vec<&str> = groups.iter().map(|group| group.symbol).collect();
+        match &self.correlations {
+            Some(correlations) => correlations.subset(&symbols),
+            None => CorrelationMatrix::identity(&symbols),
+        }
+    }
+
+    /// Horizon as a fraction of a trading year.
+    fn horizon_years(&self) -> Decimal {
+        self.horizon_days.to_dec() / TRADING_DAYS.to_dec()
+    }
+
+    /// Calendar days elapsed over the horizon, by which options are aged.
+    fn calendar_days(&self) -> Positive {
+        self.horizon_days * DAYS_IN_A_YEAR / TRADING_DAYS
+    }
+
+    /// Standard normal quantile of the confidence level.
+    fn quantile(&self) -> Result<Decimal, OptionsError> {
+        if self.confidence <= Decimal::ZERO || self.confidence >= Decimal::ONE {
+            return Err(OptionsError::OtherError {
+                reason: format!(
+                    "Confidence level {} must be between 0 and 1",
+                    self.confidence
+                ),
+            });
+        }
+        let confidence = self.confidence.to_f64().unwrap_or(0.0);
+        Ok(Decimal::from_f64(Normal::standard().inverse_cdf(confidence)).unwrap_or(Decimal::ZERO))
+    }
+
+    /// Expected shortfall of a standard normal at the confidence level,
+    /// `φ(z) / (1 - confidence)`.
+    fn tail_factor(&self, z: Decimal) -> Decimal {
+        let density = Normal::standard().pdf(z.to_f64().unwrap_or(0.0));
+        Decimal::from_f64(density).unwrap_or(Decimal::ZERO) / (Decimal::ONE - self.confidence)
+    }
+
+    /// Value-at-Risk and expected shortfall of a simulated P&L: the loss
+    /// exceeded in a fraction `1 - confidence` of the scenarios, and the
+    /// average loss of the scenarios at or beyond it.
+    fn tail_losses(&self, pnls: &[Decimal]) -> (Decimal, Decimal) {
+        if pnls.is_empty() {
+            return (Decimal::ZERO, Decimal::ZERO);
+        }
+        let mut sorted = pnls.to_vec();
+        sorted.sort();
+        let tail = ((Decimal::ONE - self.confidence) * Decimal::from(sorted.len()))
+            .floor()
+            .to_usize()
+            .unwrap_or(0)
+            .min(sorted.len() - 1);
+        let tail_mean = sorted[..=tail].iter().sum::<Decimal>() / Decimal::from(tail + 1);
+        (
+            (-sorted[tail]).max(Decimal::ZERO),
+            (-tail_mean).max(Decimal::ZERO),
+        )
+    }
+
+    /// Open positions and stock grouped by underlying, in alphabetical order.
+    fn groups<'a>(
+        &self,
+        positions: &[&'a Position],
+        stock: &[&'a SpotPosition],
+    ) -> Result<Vec<UnderlyingGroup<'a>>, OptionsError> {
+        let mut holdings: BTreeMap<&str, Vec<(HoldingKey, Holding<'a>)>> = BTreeMap::new();
+        for (index, position) in positions.iter().enumerate() {
+            if position.is_open() {
+                holdings
+                    .entry(position.option.underlying_symbol.as_str())
+                    .or_default()
+                    .push(((false, index), Holding::Option(&position.option)));
+            }
+        }
+        for (index, spot) in stock.iter().enumerate() {
+            holdings
+                .entry(spot.symbol.as_str())
+                .or_default()
+                .push(((true, index), Holding::Shares(spot)));
+        }
+
+        holdings
+            .into_iter()
+            .map(|(symbol, holdings)| {
+                let nearest = holdings
+                    .iter()
+                    .filter_map(|(_, holding)| match holding {
+                        Holding::Option(option) => Some(*option),
+                        Holding::Shares(_) => None,
+                    })
+                    .min_by_key(|option| {
+                        (option.strike_price.to_dec() - option.underlying_price.to_dec()).abs()
+                    });
+                let price = nearest
+                    .map(|option| option.underlying_price)
+                    .or_else(|| {
+                        holdings.iter().find_map(|(_, holding)| match holding {
+                            Holding::Shares(spot) => Some(spot.cost_basis),
+                            Holding::Option(_) => None,
+                        })
+                    })
+                    .unwrap_or(Positive::ZERO);
+                let volatility = self
+                    .volatilities
+                    .get(symbol)
+                    .copied()
+                    .or_else(|| nearest.map(|option| option.implied_volatility))
+                    .ok_or_else(|| OptionsError::OtherError {
+                        reason: format!("No volatility for {symbol}"),
+                    })?;
+                Ok(UnderlyingGroup {
+                    symbol,
+                    price,
+                    volatility,
+                    holdings,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Whether a holding is stock, and its index among the option positions or
+/// the stock.
+type HoldingKey = (bool, usize);
+
+/// Option position or stock holding on an underlying.
+enum Holding<'a> {
+    Option(&'a Options),
+    Shares(&'a SpotPosition),
+}
+
+impl Holding<'_> {
+    /// Value of the holding with the underlying at `price`, `days_elapsed`
+    /// calendar days from now. Options expiring within the horizon are worth
+    /// their payoff.
+    fn value_at(&self, price: Positive, days_elapsed: Positive) -> Result<Decimal, OptionsError> {
+        match self {
+            Holding::Option(option) => {
+                let mut scenario = (*option).clone();
+                scenario.underlying_price = price;
+                if days_elapsed > Positive::ZERO {
+                    let days = scenario.expiration_date.get_days()?;
+                    scenario.expiration_date =
+                        ExpirationDate::Days(days.saturating_sub(&days_elapsed));
+                }
+                let unit_value = if scenario.expiration_date.get_days()? == Positive::ZERO {
+                    scenario.payoff()?
+                } else {
+                    scenario.calculate_price_black_scholes()?
+                };
+                Ok(unit_value * option.quantity.to_dec())
+            }
+            Holding::Shares(spot) => {
+                let value = price.to_dec() * spot.quantity.to_dec();
+                Ok(match spot.side {
+                    Side::Long => value,
+                    Side::Short => -value,
+                })
+            }
+        }
+    }
+}
+
+/// Options and stock on one underlying.
+struct UnderlyingGroup<'a> {
+    symbol: &'a str,
+    price: Positive,
+    volatility: Positive,
+    holdings: Vec<(HoldingKey, Holding<'a>)>,
+}
+
+impl UnderlyingGroup<'_> {
+    /// Current value of the group with the underlying at `price`.
+    fn value_at(&self, price: Positive) -> Result<Decimal, OptionsError> {
+        self.holdings
+            .iter()
+            .map(|(_, holding)| holding.value_at(price, Positive::ZERO))
+            .sum()
+    }
+}
+
+/// Delta and gamma of `value` around `price` by central differences.
+fn delta_gamma(
+    price: Positive,
+    value: impl Fn(Positive) -> Result<Decimal, OptionsError>,
+) -> Result<(Decimal, Decimal), OptionsError> {
+    let bump = price * dec!(0.01);
+    if bump == Positive::ZERO {
+        return Ok((Decimal::ZERO, Decimal::ZERO));
+    }
+    let up = value(price + bump)?;
+    let down = value(price - bump)?;
+    let current = value(price)?;
+    let bump = bump.to_dec();
+    Ok((
+        (up - down) / (Decimal::TWO * bump),
+        (up - Decimal::TWO * current + down) / bump.powi(2),
+    ))
+}
+
+/// Mean and variance of `Δ·dS + ½·Γ·dS²` for a normal move `dS` with
+/// variance `move_variance`.
+fn delta_gamma_moments(
+    delta: Decimal,
+    gamma: Decimal,
+    move_variance: Decimal,
+) -> (Decimal, Decimal) {
+    (
+        gamma * move_variance / Decimal::TWO,
+        delta.powi(2) * move_variance + gamma.powi(2) * move_variance.powi(2) / Decimal::TWO,
+    )
+}
+
+/// Holdings with the option positions first, then the stock, each in input
+/// order.
+fn sorted_holdings(mut holdings: Vec<PositionTailRisk>) -> Vec<PositionTailRisk> {
+    holdings.sort_by_key(|holding| (holding.is_stock, holding.index));
+    holdings
+}
+
+/// Scenario-by-scenario sum of several simulated P&L series.
+fn sum_scenarios(series: &[Vec<Decimal>], simulations: usize) -> Vec<Decimal> {
+    (0..simulations)
+        .map(|scenario| series.iter().map(|pnls| pnls[scenario]).sum())
+        .collect()
+}
+
+/// Mean and standard deviation of a sample.
+fn mean_and_deviation(values: &[Decimal]) -> (Decimal, Decimal) {
+    if values.is_empty() {
+        return (Decimal::ZERO, Decimal::ZERO);
+    }
+    let count = Decimal::from(values.len());
+    let mean = values.iter().sum::<Decimal>() / count;
+    let variance = values
+        .iter()
+        .map(|value| (*value - mean).powi(2))
+        .sum::<Decimal>()
+        / count;
+    (mean, sqrt(variance))
+}
+
+fn sqrt(value: Decimal) -> Decimal {
+    value.max(Decimal::ZERO).sqrt().unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests_var {
+    use super::*;
+    use crate::model::types::{OptionStyle, OptionType};
+    use chrono::Utc;
+    use positive::pos_or_panic;
+
+    fn position(symbol: &str, style: OptionStyle, side: Side, strike: f64) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            symbol.to_string(),
+            Positive::new(strike).unwrap(),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            Positive::ONE,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_parametric_stock_matches_normal_var() {
+        let shares = SpotPosition::long("AAA".to_string(), Positive::HUNDRED, Positive::HUNDRED);
+        let var = ValueAtRisk::one_day(dec!(0.99)).with_volatility("AAA", pos_or_panic!(0.2));
+        let report = var.parametric(&[], &[&shares]).unwrap();
+
+        // 100 shares at 100 with 20% volatility: 2.326 * 10,000 * 0.2 / sqrt(252).
+        let expected = dec!(2.326348) * dec!(10000) * dec!(0.2) / dec!(15.874508);
+        assert!((report.var - expected).abs() < dec!(0.1));
+        assert_eq!(report.expected_pnl, Decimal::ZERO);
+        assert_eq!(report.underlyings[0].delta.round_dp(6), dec!(100));
+
+        // Normal expected shortfall: φ(2.326) / 0.01 = 2.665 standard deviations.
+        let expected = dec!(2.665214) * dec!(10000) * dec!(0.2) / dec!(15.874508);
+        assert!((report.expected_shortfall - expected).abs() < dec!(0.1));
+        assert_eq!(report.positions.len(), 1);
+        assert!(report.positions[0].is_stock);
+        assert_eq!(report.positions[0].var, report.var);
+    }
+
+    #[test]
+    fn test_ten_day_var_is_larger() {
+        let short = position("AAA", OptionStyle::Put, Side::Short, 100.0);
+        let one_day = ValueAtRisk::one_day(dec!(0.95))
+            .parametric(&[&short], &[])
+            .unwrap();
+        let ten_day = ValueAtRisk::ten_day(dec!(0.95))
+            .parametric(&[&short], &[])
+            .unwrap();
+        assert!(one_day.var > Decimal::ZERO);
+        assert!(ten_day.var > one_day.var * dec!(2.5));
+        assert!(one_day.underlyings[0].gamma < Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_monte_carlo_close_to_parametric_and_reproducible() {
+        let call = position("AAA", OptionStyle::Call, Side::Long, 100.0);
+        let var = ValueAtRisk::one_day(dec!(0.99))
+            .with_simulations(2_000)
+            .with_seed(7);
+        let parametric = var.parametric(&[&call], &[]).unwrap();
+        let simulated = var.monte_carlo(&[&call], &[]).unwrap();
+
+        assert_eq!(simulated.method, VarMethod::MonteCarlo);
+        assert!((simulated.var - parametric.var).abs() < parametric.var * dec!(0.25));
+        assert_eq!(simulated, var.monte_carlo(&[&call], &[]).unwrap());
+    }
+
+    #[test]
+    fn test_hedged_positions_reduce_var() {
+        let call = position("AAA", OptionStyle::Call, Side::Long, 100.0);
+        let shares = SpotPosition::short("AAA".to_string(), pos_or_panic!(0.5), Positive::HUNDRED);
+        let var = ValueAtRisk::one_day(dec!(0.99))
+            .with_simulations(1_000)
+            .with_seed(1);
+        let naked = var.monte_carlo(&[&call], &[]).unwrap();
+        let hedged = var.monte_carlo(&[&call], &[&shares]).unwrap();
+        assert!(hedged.var < naked.var);
+    }
+
+    #[test]
+    fn test_expected_shortfall_per_position() {
+        let call = position("AAA", OptionStyle::Call, Side::Long, 100.0);
+        let closed = {
+            let mut closed = position("AAA", OptionStyle::Put, Side::Long, 100.0);
+            closed.close_all(Positive::ONE, Utc::now()).unwrap();
+            closed
+        };
+        let put = position("BBB", OptionStyle::Put, Side::Short, 95.0);
+        let shares = SpotPosition::short("AAA".to_string(), pos_or_panic!(0.5), Positive::HUNDRED);
+        let var = ValueAtRisk::one_day(dec!(0.95))
+            .with_simulations(1_000)
+            .with_seed(11);
+        let report = var
+            .monte_carlo(&[&call, &closed, &put], &[&shares])
+            .unwrap();
+
+        assert!(report.expected_shortfall >= report.var);
+        let keys: Vec<(bool, usize)> = report
+            .positions
+            .iter()
+            .map(|position| (position.is_stock, position.index))
+            .collect();
+        assert_eq!(keys, vec![(false, 0), (false, 2), (true, 0)]);
+        for position in &report.positions {
+            assert!(position.expected_shortfall >= position.var);
+        }
+        // The short shares hedge the long call.
+        assert!(report.diversification_benefit() > Decimal::ZERO);
+        assert_eq!(report.underlyings[1].underlying_symbol, "BBB");
+        assert_eq!(
+            report.underlyings[1].expected_shortfall,
+            report.positions[1].expected_shortfall
+        );
+
+        let parametric = var.parametric(&[&call, &closed, &put], &[&shares]).unwrap();
+        assert_eq!(parametric.positions.len(), 3);
+        assert!(parametric.expected_shortfall > parametric.var);
+    }
+
+    #[test]
+    fn test_correlated_underlyings() {
+        let long = SpotPosition::long("AAA".to_string(), Positive::HUNDRED, Positive::HUNDRED);
+        let short = SpotPosition::short("BBB".to_string(), Positive::HUNDRED, Positive::HUNDRED);
+        let var = ValueAtRisk::one_day(dec!(0.99))
+            .with_volatility("AAA", pos_or_panic!(0.2))
+            .with_volatility("BBB", pos_or_panic!(0.2))
+            .with_simulations(2_000)
+            .with_seed(3);
+        let independent = var.parametric(&[], &[&long, &short]).unwrap();
+        let correlated = var
+            .clone()
+            .with_correlations(CorrelationMatrix::pair("AAA", "BBB", dec!(0.9)).unwrap());
+
+        // A long and a short leg of identical size: σ² = 2·(1 - ρ)·s².
+        let hedged = correlated.parametric(&[], &[&long, &short]).unwrap();
+        let ratio = hedged.standard_deviation / independent.standard_deviation;
+        assert!((ratio - dec!(0.1).sqrt().unwrap()).abs() < dec!(0.0001));
+        assert_eq!(hedged.underlyings, independent.underlyings);
+
+        let simulated = correlated.monte_carlo(&[], &[&long, &short]).unwrap();
+        let unhedged = var.monte_carlo(&[], &[&long, &short]).unwrap();
+        assert!(simulated.var < unhedged.var * dec!(0.5));
+        assert!((simulated.var - hedged.var).abs() < hedged.var * dec!(0.2));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let call = position("AAA", OptionStyle::Call, Side::Long, 100.0);
+        let shares = SpotPosition::long("BBB".to_string(), Positive::ONE, Positive::HUNDRED);
+        assert!(
+            ValueAtRisk::one_day(Decimal::ONE)
+                .parametric(&[&call], &[])
+                .is_err()
+        );
+        assert!(
+            ValueAtRisk::default()
+                .with_simulations(0)
+                .monte_carlo(&[&call], &[])
+                .is_err()
+        );
+        assert!(ValueAtRisk::default().parametric(&[], &[&shares]).is_err());
+    }
+}
@@ -0,0 +1,422 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # P&L Distribution at Expiry
+//!
+//! Simulates terminal prices of the underlying with a [`PathSimulator`] and
+//! evaluates the P&L of every leg of a strategy at its intrinsic value, giving
+//! the full distribution of outcomes if the strategy is held to expiration.
+//!
+//! [`simulate_pnl_distribution`] summarises the distribution with a histogram,
+//! its mean and standard deviation, a set of percentiles, the probability of
+//! profit (POP), and the value at risk and expected shortfall of the left tail.
+//!
+//! Every leg is settled on the nearest expiration of the strategy, so the
+//! analysis is meant for strategies whose legs share an expiration date.
+
+use crate::error::SimulationError;
+use crate::model::Position;
+use crate::simulation::paths::{PathSimulator, ProcessModel, TimeGrid};
+use crate::strategies::base::Positionable;
+use num_traits::ToPrimitive;
+use positive::{Positive, pos_or_panic};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Percentile levels reported by [`PnLDistribution::percentiles`].
+const PERCENTILE_LEVELS: [Decimal; 7] = [
+    dec!(0.01),
+    dec!(0.05),
+    dec!(0.25),
+    dec!(0.5),
+    dec!(0.75),
+    dec!(0.95),
+    dec!(0.99),
+];
+
+/// Parameters of a P&L distribution simulation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PnLDistributionParams {
+    /// Annualized volatility of the underlying.
+    pub volatility: Positive,
+    /// Annualized drift of the underlying. Zero by default.
+    pub drift: Decimal,
+    /// Dynamics of the underlying.
+    pub process: ProcessModel,
+    /// Number of simulated terminal prices.
+    pub simulations: usize,
+    /// Number of buckets of the histogram.
+    pub buckets: usize,
+    /// Confidence level of the value at risk and expected shortfall.
+    pub confidence_level: Positive,
+    /// Optional seed for reproducible simulations.
+    pub seed: Option<u64>,
+}
+
+impl PnLDistributionParams {
+    /// Creates parameters with no drift, 10,000 geometric Brownian motion
+    /// simulations, 50 histogram buckets and a 95% confidence level.
+    pub fn new(volatility: Positive) -> Self {
+        Self {
+            volatility,
+            drift: Decimal::ZERO,
+            process: ProcessModel::GeometricBrownian,
+            simulations: 10_000,
+            buckets: 50,
+            confidence_level: pos_or_panic!(0.95),
+            seed: None,
+        }
+    }
+
+    /// Sets the annualized drift of the underlying.
+    pub fn with_drift(mut self, drift: Decimal) -> Self {
+        self.drift = drift;
+        self
+    }
+
+    /// Sets the dynamics of the underlying.
+    pub fn with_process(mut self, process: ProcessModel) -> Self {
+        self.process = process;
+        self
+    }
+
+    /// Sets the number of simulations.
+    pub fn with_simulations(mut self, simulations: usize) -> Self {
+        self.simulations = simulations;
+        self
+    }
+
+    /// Sets the number of histogram buckets.
+    pub fn with_buckets(mut self, buckets: usize) -> Self {
+        self.buckets = buckets;
+        self
+    }
+
+    /// Sets the confidence level of the tail measures.
+    pub fn with_confidence_level(mut self, confidence_level: Positive) -> Self {
+        self.confidence_level = confidence_level;
+        self
+    }
+
+    /// Sets the seed of the random number generator.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn validate(&self) -> Result<(), SimulationError> {
+        if self.buckets == 0 {
+            return Err(SimulationError::invalid_parameters(
+                "At least one histogram bucket is required",
+            ));
+        }
+        if self.confidence_level >= Positive::ONE || self.confidence_level == Positive::ZERO {
+            return Err(SimulationError::invalid_parameters(
+                "Confidence level must be between 0 and 1",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Bucket of a P&L histogram, covering `[lower, upper)`; the last bucket also
+/// includes its upper bound.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PnLBucket {
+    /// Lower bound of the bucket.
+    pub lower: Decimal,
+    /// Upper bound of the bucket.
+    pub upper: Decimal,
+    /// Number of simulations in the bucket.
+    pub count: usize,
+    /// Fraction of simulations in the bucket.
+    pub probability: Decimal,
+}
+
+/// P&L at a given percentile of the distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PnLPercentile {
+    /// Percentile level, between 0 and 1.
+    pub level: Decimal,
+    /// P&L at that level.
+    pub pnl: Decimal,
+}
+
+/// Simulated distribution of the P&L of a strategy at expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PnLDistribution {
+    /// Days from now to the expiration the strategy is settled on.
+    pub days_to_expiration: Positive,
+    /// Mean P&L.
+    pub mean: Decimal,
+    /// Standard deviation of the P&L.
+    pub std_dev: Decimal,
+    /// Worst simulated P&L.
+    pub min: Decimal,
+    /// Best simulated P&L.
+    pub max: Decimal,
+    /// Probability of a strictly positive P&L.
+    pub probability_of_profit: Decimal,
+    /// Confidence level of the tail measures.
+    pub confidence_level: Positive,
+    /// P&L at the `1 - confidence_level` quantile. Negative values are losses.
+    pub value_at_risk: Decimal,
+    /// Mean P&L of the simulations at or below the value at risk.
+    pub expected_shortfall: Decimal,
+    /// P&L at the 1st, 5th, 25th, 50th, 75th, 95th and 99th percentiles.
+    pub percentiles: Vec<PnLPercentile>,
+    /// Histogram of the simulated P&L.
+    pub histogram: Vec<PnLBucket>,
+    /// Every simulated P&L, sorted from worst to best.
+    pub pnls: Vec<Decimal>,
+}
+
+impl PnLDistribution {
+    /// P&L at percentile `level`, between 0 and 1, using the nearest rank.
+    pub fn percentile(&self, level: Decimal) -> Decimal {
+        nearest_rank(&self.pnls, level)
+    }
+}
+
+/// Simulates the P&L distribution of a strategy held to expiration.
+///
+/// # Errors
+///
+/// Returns a `SimulationError` if the parameters are invalid, the strategy does
+/// not expose its positions or a P&L cannot be computed.
+pub fn simulate_pnl_distribution<S: Positionable>(
+    strategy: &S,
+    params: &PnLDistributionParams,
+) -> Result<PnLDistribution, SimulationError> {
+    let positions: Vec<Position> = strategy.get_positions()?.into_iter().cloned().collect();
+    simulate_positions_pnl_distribution(&positions, params)
+}
+
+/// Simulates the P&L distribution of a set of positions held to expiration.
+///
+/// See [`simulate_pnl_distribution`].
+///
+/// # Errors
+///
+/// Returns a `SimulationError` if the parameters are invalid or a P&L cannot be computed.
+pub fn simulate_positions_pnl_distribution(
+    positions: &[Position],
+    params: &PnLDistributionParams,
+) -> Result<PnLDistribution, SimulationError> {
+    params.validate()?;
+    let Some(first) = positions.first() else {
+        return Err(SimulationError::invalid_parameters(
+            "No positions to simulate",
+        ));
+    };
+
+    let mut days_to_expiration = first.option.expiration_date.get_days()?;
+    let mut horizon = first.option.time_to_expiration()?;
+    for position in &positions[1..] {
+        let days = position.option.expiration_date.get_days()?;
+        if days < days_to_expiration {
+            days_to_expiration = days;
+            horizon = position.option.time_to_expiration()?;
+        }
+    }
+
+    let mut simulator = PathSimulator::new(
+        first.option.underlying_price,
+        params.volatility,
+        params.drift,
+        Positive::ZERO,
+    )
+    .with_paths(params.simulations)
+    .with_process(params.process);
+    if let Some(seed) = params.seed {
+        simulator = simulator.with_seed(seed);
+    }
+    let terminal = if horizon == Positive::ZERO {
+        vec![first.option.underlying_price.to_f64(); params.simulations.max(1)]
+    } else {
+        simulator
+            .simulate(&TimeGrid::uniform(horizon, 1)?)?
+            .terminal()
+    };
+
+    let mut pnls = Vec::with_capacity(terminal.len());
+    for price in terminal {
+        let price = Positive::new(price)?;
+        pnls.push(
+            positions
+                .iter()
+                .map(|p| p.pnl_at_expiration(&Some(&price)))
+                .sum::<Result<Decimal, _>>()?,
+        );
+    }
+    pnls.sort();
+
+    let count = Decimal::from(pnls.len());
+    let mean = pnls.iter().sum::<Decimal>() / count;
+    let variance = pnls
+        .iter()
+        .map(|p| (p - mean) * (p - mean))
+        .sum::<Decimal>()
+        / count;
+    let std_dev = Positive::new_decimal(variance)
+        .map(|v| v.sqrt().to_dec())
+        .unwrap_or(Decimal::ZERO);
+    let profitable = pnls.iter().filter(|p| **p > Decimal::ZERO).count();
+
+    let tail_level = Decimal::ONE - params.confidence_level.to_dec();
+    let tail = (tail_level * count).floor().max(Decimal::ONE);
+    let tail_len = tail.to_usize().unwrap_or(1).min(pnls.len());
+    let value_at_risk = pnls[tail_len - 1];
+    let expected_shortfall = pnls[..tail_len].iter().sum::<Decimal>() / Decimal::from(tail_len);
+
+    Ok(PnLDistribution {
+        days_to_expiration,
+        mean,
+        std_dev,
+        min: pnls[0],
+        max: pnls[pnls.len() - 1],
+        probability_of_profit: Decimal::from(profitable) / count,
+        confidence_level: params.confidence_level,
+        value_at_risk,
+        expected_shortfall,
+        percentiles: PERCENTILE_LEVELS
+            .iter()
+            .map(|&level| PnLPercentile {
+                level,
+                pnl: nearest_rank(&pnls, level),
+            })
+            .collect(),
+        histogram: histogram(&pnls, params.buckets),
+        pnls,
+    })
+}
+
+/// Nearest-rank percentile of sorted values.
+pub(super) fn nearest_rank(sorted: &[Decimal], level: Decimal) -> Decimal {
+    if sorted.is_empty() {
+        return Decimal::ZERO;
+    }
+    let rank = (level.clamp(Decimal::ZERO, Decimal::ONE) * Decimal::from(sorted.len())).ceil();
+    let index = rank.to_usize().unwrap_or(1).max(1) - 1;
+    sorted[index.min(sorted.len() - 1)]
+}
+
+/// Equal-width histogram of sorted values.
+fn histogram(sorted: &[Decimal], buckets: usize) -> Vec<PnLBucket> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let count = Decimal::from(sorted.len());
+    if min == max {
+        return vec![PnLBucket {
+            lower: min,
+            upper: max,
+            count: sorted.len(),
+            probability: Decimal::ONE,
+        }];
+    }
+
+    let width = (max - min) / Decimal::from(buckets);
+    let mut counts = vec![0usize; buckets];
+    for value in sorted {
+        let index = ((value - min) / width).floor();
+        let index = index.to_usize().unwrap_or(0).min(buckets - 1);
+        counts[index] += 1;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, bucket_count)| PnLBucket {
+            lower: min + width * Decimal::from(i),
+            upper: if i == buckets - 1 {
+                max
+            } else {
+                min + width * Decimal::from(i + 1)
+            },
+            count: bucket_count,
+            probability: Decimal::from(bucket_count) / count,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_distribution {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::option::Options;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use chrono::Utc;
+
+    fn short_put(premium: Positive) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            Side::Short,
+            "XYZ".to_string(),
+            pos_or_panic!(95.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            OptionStyle::Put,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            premium,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_short_put_distribution() {
+        let params = PnLDistributionParams::new(pos_or_panic!(0.2))
+            .with_simulations(5_000)
+            .with_buckets(10)
+            .with_seed(42);
+        let distribution =
+            simulate_positions_pnl_distribution(&[short_put(Positive::TWO)], &params).unwrap();
+
+        // A short put keeps at most its premium.
+        assert_eq!(distribution.max, dec!(2));
+        assert!(distribution.min < Decimal::ZERO);
+        assert!(distribution.probability_of_profit > dec!(0.7));
+        assert!(distribution.probability_of_profit < Decimal::ONE);
+        assert!(distribution.expected_shortfall <= distribution.value_at_risk);
+        assert!(distribution.value_at_risk < Decimal::ZERO);
+        assert!(distribution.std_dev > Decimal::ZERO);
+
+        assert_eq!(distribution.pnls.len(), 5_000);
+        assert_eq!(distribution.percentiles.len(), 7);
+        assert_eq!(distribution.percentile(dec!(0.5)), dec!(2));
+        assert!(distribution.percentile(dec!(0.01)) <= distribution.percentile(dec!(0.25)));
+
+        assert_eq!(distribution.histogram.len(), 10);
+        assert_eq!(
+            distribution
+                .histogram
+                .iter()
+                .map(|b| b.count)
+                .sum::<usize>(),
+            5_000
+        );
+        assert_eq!(distribution.histogram[9].upper, dec!(2));
+    }
+
+    #[test]
+    fn test_rejects_invalid_parameters() {
+        let params = PnLDistributionParams::new(pos_or_panic!(0.2));
+        assert!(simulate_positions_pnl_distribution(&[], &params).is_err());
+
+        let params = params.with_confidence_level(Positive::ONE);
+        assert!(simulate_positions_pnl_distribution(&[short_put(Positive::ONE)], &params).is_err());
+    }
+}
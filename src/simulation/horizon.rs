@@ -0,0 +1,553 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Pre-Expiry P&L
+//!
+//! Simulates price paths of the underlying up to the nearest expiration of a
+//! strategy and revalues every leg with a [`PricingEngine`] at each date of
+//! the path, giving the mark-to-model P&L of the strategy through time.
+//!
+//! [`simulate_pnl_paths`] summarises the paths as a fan chart, the mean and
+//! percentiles of the P&L at every date, and, when a profit target is set, the
+//! probability of reaching it at any date before or at expiration together
+//! with the average number of days needed.
+//!
+//! Legs expiring on or before a date are settled at their intrinsic value;
+//! later legs are marked with the time they have left and their current
+//! implied volatility.
+//!
+//! Scheduled events of the underlying set with [`PnLPathParams::with_events`]
+//! are stepped through with the revaluation dates, each fan point listing the
+//! events since the previous one. With an [`EarningsVolModel`], legs
+//! spanning an earnings release of the timeline are marked with the event
+//! bump in their implied volatility until the release and crushed after it.
+//! The simulated underlying drops by the dividend of every ex-dividend event
+//! of the timeline from its ex-date on.
+
+use crate::ExpirationDate;
+use crate::error::SimulationError;
+use crate::model::Position;
+use crate::model::events::{EventTimeline, MarketEvent, MarketEventKind};
+use crate::model::types::Side;
+use crate::pricing::{PricingEngine, price_option};
+use crate::simulation::distribution::{PnLPercentile, nearest_rank};
+use crate::simulation::paths::{PathSimulator, ProcessModel, TimeGrid};
+use crate::strategies::base::Positionable;
+use crate::volatility::EarningsVolModel;
+use chrono::{DateTime, Duration, Utc};
+use positive::Positive;
+use positive::constants::DAYS_IN_A_YEAR;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Percentile levels of the fan chart.
+const FAN_LEVELS: [Decimal; 5] = [dec!(0.05), dec!(0.25), dec!(0.5), dec!(0.75), dec!(0.95)];
+
+/// Parameters of a mark-to-model P&L simulation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PnLPathParams {
+    /// Annualized volatility of the underlying.
+    pub volatility: Positive,
+    /// Annualized drift of the underlying. Zero by default.
+    pub drift: Decimal,
+    /// Dynamics of the underlying.
+    pub process: ProcessModel,
+    /// Number of simulated paths.
+    pub paths: usize,
+    /// Number of equally spaced revaluation dates until expiration.
+    pub steps: usize,
+    /// Optional P&L at which the strategy would be closed.
+    pub profit_target: Option<Decimal>,
+    /// Optional seed for reproducible simulations.
+    pub seed: Option<u64>,
+    /// Scheduled market events, dated from the start of the simulation.
+    #[serde(default)]
+    pub events: EventTimeline,
+    /// Optional earnings volatility model applied to the events.
+    #[serde(default)]
+    pub earnings: Option<EarningsVolModel>,
+}
+
+impl PnLPathParams {
+    /// Creates parameters with no drift and 1,000 geometric Brownian motion
+    /// paths revalued on 20 dates.
+    pub fn new(volatility: Positive) -> Self {
+        Self {
+            volatility,
+            drift: Decimal::ZERO,
+            process: ProcessModel::GeometricBrownian,
+            paths: 1_000,
+            steps: 20,
+            profit_target: None,
+            seed: None,
+            events: EventTimeline::new(),
+            earnings: None,
+        }
+    }
+
+    /// Sets the annualized drift of the underlying.
+    pub fn with_drift(mut self, drift: Decimal) -> Self {
+        self.drift = drift;
+        self
+    }
+
+    /// Sets the dynamics of the underlying.
+    pub fn with_process(mut self, process: ProcessModel) -> Self {
+        self.process = process;
+        self
+    }
+
+    /// Sets the number of paths.
+    pub fn with_paths(mut self, paths: usize) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Sets the number of revaluation dates.
+    pub fn with_steps(mut self, steps: usize) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Sets the profit target.
+    pub fn with_profit_target(mut self, profit_target: Decimal) -> Self {
+        self.profit_target = Some(profit_target);
+        self
+    }
+
+    /// Sets the seed of the random number generator.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the scheduled market events.
+    pub fn with_events(mut self, events: EventTimeline) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Marks legs spanning the earnings releases of the events with `model`.
+    pub fn with_earnings_model(mut self, model: EarningsVolModel) -> Self {
+        self.earnings = Some(model);
+        self
+    }
+}
+
+/// Distribution of the P&L at one date of the simulation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PnLFanPoint {
+    /// Days elapsed since the start of the simulation.
+    pub elapsed_days: Positive,
+    /// Mean P&L across paths.
+    pub mean: Decimal,
+    /// P&L at the 5th, 25th, 50th, 75th and 95th percentiles.
+    pub percentiles: Vec<PnLPercentile>,
+    /// Events of the underlying since the previous revaluation date.
+    #[serde(default)]
+    pub events: Vec<MarketEvent>,
+}
+
+/// Result of a mark-to-model P&L simulation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PnLPathSimulation {
+    /// Days from now to the nearest expiration of the strategy.
+    pub days_to_expiration: Positive,
+    /// Fan chart of the P&L, one point per revaluation date.
+    pub fan: Vec<PnLFanPoint>,
+    /// Profit target of the simulation, if any.
+    pub profit_target: Option<Decimal>,
+    /// Fraction of paths reaching the profit target at some date.
+    pub target_probability: Option<Decimal>,
+    /// Mean number of days needed by the paths that reach the profit target.
+    pub mean_days_to_target: Option<Positive>,
+    /// P&L of every path at every revaluation date.
+    pub pnl_paths: Vec<Vec<Decimal>>,
+}
+
+/// Simulates the mark-to-model P&L of a strategy along price paths until its
+/// nearest expiration.
+///
+/// # Errors
+///
+/// Returns a `SimulationError` if the parameters are invalid, the strategy does
+/// not expose its positions or a leg cannot be priced.
+pub fn simulate_pnl_paths<S: Positionable>(
+    strategy: &S,
+    params: &PnLPathParams,
+    engine: &PricingEngine,
+) -> Result<PnLPathSimulation, SimulationError> {
+    let positions: Vec<Position> = strategy.get_positions()?.into_iter().cloned().collect();
+    simulate_positions_pnl_paths(&positions, params, engine)
+}
+
+/// Simulates the mark-to-model P&L of a set of positions along price paths.
+///
+/// See [`simulate_pnl_paths`].
+///
+/// # Errors
+///
+/// Returns a `SimulationError` if the parameters are invalid or a leg cannot be priced.
+pub fn simulate_positions_pnl_paths(
+    positions: &[Position],
+    params: &PnLPathParams,
+    engine: &PricingEngine,
+) -> Result<PnLPathSimulation, SimulationError> {
+    let Some(first) = positions.first() else {
+        return Err(SimulationError::invalid_parameters(
+            "No positions to simulate",
+        ));
+    };
+
+    let mut leg_days = Vec::with_capacity(positions.len());
+    for position in positions {
+        leg_days.push(position.option.expiration_date.get_days()?);
+    }
+    let nearest = (0..positions.len())
+        .min_by_key(|&i| leg_days[i])
+        .unwrap_or_default();
+    let days_to_expiration = leg_days[nearest];
+    let horizon = positions[nearest].option.time_to_expiration()?;
+    if horizon == Positive::ZERO {
+        return Err(SimulationError::invalid_parameters(
+            "The strategy has already expired",
+        ));
+    }
+
+    let mut simulator = PathSimulator::new(
+        first.option.underlying_price,
+        params.volatility,
+        params.drift,
+        Positive::ZERO,
+    )
+    .with_paths(params.paths)
+    .with_process(params.process);
+    if let Some(seed) = params.seed {
+        simulator = simulator.with_seed(seed);
+    }
+    let grid = TimeGrid::uniform(horizon, params.steps)?;
+    let matrix = simulator.simulate(&grid)?;
+
+    let steps = Decimal::from(params.steps);
+    let elapsed: Vec<Positive> = (0..=params.steps)
+        .map(|step| days_to_expiration * Decimal::from(step) / steps)
+        .collect();
+    let start = Utc::now();
+    let dates: Vec<DateTime<Utc>> = elapsed.iter().map(|days| start + duration(*days)).collect();
+    let symbol = &first.option.underlying_symbol;
+    // Dividends gone ex by every date, taken off the simulated prices.
+    let paid: Vec<Decimal> = dates
+        .iter()
+        .map(|date| {
+            params
+                .events
+                .dividends(symbol, start, *date)
+                .map(|(_, amount)| amount.to_dec())
+                .sum()
+        })
+        .collect();
+
+    // Implied volatility of every leg at every date.
+    let volatilities: Vec<Vec<Positive>> = positions
+        .iter()
+        .zip(&leg_days)
+        .map(|(position, days)| {
+            let implied = position.option.implied_volatility;
+            let Some(model) = &params.earnings else {
+                return vec![implied; dates.len()];
+            };
+            let expiration = start + duration(*days);
+            let events =
+                params
+                    .events
+                    .count_between(symbol, MarketEventKind::Earnings, start, expiration);
+            dates
+                .iter()
+                .zip(&elapsed)
+                .map(|(date, elapsed_days)| {
+                    model.roll_forward(
+                        implied,
+                        *days / DAYS_IN_A_YEAR,
+                        events,
+                        days.saturating_sub(elapsed_days) / DAYS_IN_A_YEAR,
+                        params.events.count_between(
+                            symbol,
+                            MarketEventKind::Earnings,
+                            *date,
+                            expiration,
+                        ),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut pnl_paths = Vec::with_capacity(matrix.num_paths());
+    for path in matrix.iter() {
+        let mut pnls = Vec::with_capacity(path.len());
+        for (point, (price, elapsed_days)) in path.iter().zip(&elapsed).enumerate() {
+            let price = Positive::new_decimal(
+                (Positive::new(*price)?.to_dec() - paid[point]).max(Decimal::ZERO),
+            )?;
+            let mut pnl = Decimal::ZERO;
+            for ((position, days), volatility) in positions.iter().zip(&leg_days).zip(&volatilities)
+            {
+                pnl += mark(
+                    position,
+                    price,
+                    volatility[point],
+                    *days,
+                    *elapsed_days,
+                    engine,
+                )?;
+            }
+            pnls.push(pnl);
+        }
+        pnl_paths.push(pnls);
+    }
+
+    let count = Decimal::from(pnl_paths.len());
+    let fan = elapsed
+        .iter()
+        .zip(params.events.clock(symbol, dates))
+        .enumerate()
+        .map(|(point, (elapsed_days, tick))| {
+            let mut values: Vec<Decimal> = pnl_paths.iter().map(|p| p[point]).collect();
+            values.sort();
+            PnLFanPoint {
+                elapsed_days: *elapsed_days,
+                mean: values.iter().sum::<Decimal>() / count,
+                percentiles: FAN_LEVELS
+                    .iter()
+                    .map(|&level| PnLPercentile {
+                        level,
+                        pnl: nearest_rank(&values, level),
+                    })
+                    .collect(),
+                events: tick.events.into_iter().cloned().collect(),
+            }
+        })
+        .collect();
+
+    let (target_probability, mean_days_to_target) = match params.profit_target {
+        Some(target) => {
+            let hits: Vec<Positive> = pnl_paths
+                .iter()
+                .filter_map(|pnls| pnls.iter().position(|pnl| *pnl >= target))
+                .map(|point| elapsed[point])
+                .collect();
+            let mean_days = (!hits.is_empty())
+                .then(|| hits.iter().copied().sum::<Positive>() / Decimal::from(hits.len()));
+            (Some(Decimal::from(hits.len()) / count), mean_days)
+        }
+        None => (None, None),
+    };
+
+    Ok(PnLPathSimulation {
+        days_to_expiration,
+        fan,
+        profit_target: params.profit_target,
+        target_probability,
+        mean_days_to_target,
+        pnl_paths,
+    })
+}
+
+/// Time span of `days` days.
+fn duration(days: Positive) -> Duration {
+    let seconds = (days.to_dec() * dec!(86400)).to_i64().unwrap_or_default();
+    Duration::seconds(seconds)
+}
+
+/// P&L of `position` after `elapsed_days`, with the underlying at `price`
+/// and the leg at `volatility`.
+fn mark(
+    position: &Position,
+    price: Positive,
+    volatility: Positive,
+    days_to_expiration: Positive,
+    elapsed_days: Positive,
+    engine: &PricingEngine,
+) -> Result<Decimal, SimulationError> {
+    if days_to_expiration <= elapsed_days {
+        return Ok(position.pnl_at_expiration(&Some(&price))?);
+    }
+    let mut marked = position.option.clone();
+    marked.underlying_price = price;
+    marked.implied_volatility = volatility;
+    marked.expiration_date = ExpirationDate::Days(days_to_expiration - elapsed_days);
+    let value = price_option(&marked, engine)?.to_dec() * marked.quantity.to_dec();
+    let value = match marked.side {
+        Side::Long => value,
+        Side::Short => -value,
+    };
+    Ok(value - position.total_cost()?.to_dec() + position.premium_received()?.to_dec())
+}
+
+#[cfg(test)]
+mod tests_horizon {
+    use super::*;
+    use crate::model::events::MarketEventKind;
+    use crate::model::option::Options;
+    use crate::model::types::{OptionStyle, OptionType};
+    use positive::pos_or_panic;
+
+    fn long_call() -> Position {
+        let option = Options::new(
+            OptionType::European,
+            Side::Long,
+            "XYZ".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        );
+        let premium =
+            Positive::new_decimal(option.calculate_price_black_scholes().unwrap()).unwrap();
+        Position::new(
+            option,
+            premium,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_fan_chart_starts_flat_and_widens() {
+        let params = PnLPathParams::new(pos_or_panic!(0.2))
+            .with_paths(200)
+            .with_steps(5)
+            .with_profit_target(dec!(2))
+            .with_seed(42);
+        let simulation =
+            simulate_positions_pnl_paths(&[long_call()], &params, &PricingEngine::ClosedFormBS)
+                .unwrap();
+
+        assert_eq!(simulation.days_to_expiration, pos_or_panic!(30.0));
+        assert_eq!(simulation.fan.len(), 6);
+        assert_eq!(simulation.pnl_paths.len(), 200);
+
+        // Paid at model value, so every path starts at zero P&L.
+        let start = &simulation.fan[0];
+        assert_eq!(start.elapsed_days, Positive::ZERO);
+        assert!(start.mean.abs() < dec!(1e-9));
+        let spread = |point: &PnLFanPoint| point.percentiles[4].pnl - point.percentiles[0].pnl;
+        assert!(spread(&simulation.fan[5]) > spread(&simulation.fan[1]));
+        // A long call loses at most its premium.
+        assert!(simulation.fan[5].percentiles[0].pnl >= -dec!(2.3));
+
+        let probability = simulation.target_probability.unwrap();
+        assert!(probability > Decimal::ZERO && probability < Decimal::ONE);
+        let days = simulation.mean_days_to_target.unwrap();
+        assert!(days > Positive::ZERO && days <= pos_or_panic!(30.0));
+    }
+
+    #[test]
+    fn test_events_are_placed_on_revaluation_dates() {
+        let now = Utc::now();
+        let events = EventTimeline::new()
+            .with_event(MarketEvent::earnings("XYZ", now + Duration::days(10)))
+            .with_event(MarketEvent::earnings("ABC", now + Duration::days(10)))
+            .with_event(MarketEvent::fomc(now + Duration::days(20)));
+        let params = PnLPathParams::new(pos_or_panic!(0.2))
+            .with_paths(20)
+            .with_steps(5)
+            .with_seed(7)
+            .with_events(events);
+        let simulation =
+            simulate_positions_pnl_paths(&[long_call()], &params, &PricingEngine::ClosedFormBS)
+                .unwrap();
+
+        // Revaluation every 6 days: the earnings fall on day 12, FOMC on day 24.
+        let kinds: Vec<Vec<MarketEventKind>> = simulation
+            .fan
+            .iter()
+            .map(|point| point.events.iter().map(|event| event.kind).collect())
+            .collect();
+        assert!(kinds[0].is_empty() && kinds[1].is_empty());
+        assert_eq!(kinds[2], vec![MarketEventKind::Earnings]);
+        assert_eq!(kinds[4], vec![MarketEventKind::Fomc]);
+    }
+
+    #[test]
+    fn test_earnings_model_crushes_volatility_after_the_release() {
+        let model = EarningsVolModel::new(pos_or_panic!(0.2), pos_or_panic!(0.03));
+        let mut position = long_call();
+        position.option.implied_volatility =
+            model.implied_volatility(pos_or_panic!(30.0) / DAYS_IN_A_YEAR, 1);
+        position.premium =
+            Positive::new_decimal(position.option.calculate_price_black_scholes().unwrap())
+                .unwrap();
+        let events = EventTimeline::new().with_event(MarketEvent::earnings(
+            "XYZ",
+            Utc::now() + Duration::days(10),
+        ));
+        let params = PnLPathParams::new(pos_or_panic!(0.2))
+            .with_paths(50)
+            .with_steps(5)
+            .with_seed(11)
+            .with_events(events);
+        let engine = PricingEngine::ClosedFormBS;
+
+        let flat = simulate_positions_pnl_paths(&[position.clone()], &params, &engine).unwrap();
+        let crushed =
+            simulate_positions_pnl_paths(&[position], &params.with_earnings_model(model), &engine)
+                .unwrap();
+
+        // Same paths: the bump grows until day 10 and is gone by day 12.
+        assert_eq!(crushed.fan[0].mean, flat.fan[0].mean);
+        assert!(crushed.fan[1].mean > flat.fan[1].mean);
+        assert!(crushed.fan[2].mean < flat.fan[2].mean);
+        assert_eq!(crushed.fan[5].mean, flat.fan[5].mean);
+    }
+
+    #[test]
+    fn test_ex_dividend_drops_the_underlying() {
+        let params = PnLPathParams::new(pos_or_panic!(0.2))
+            .with_paths(50)
+            .with_steps(5)
+            .with_seed(3);
+        let engine = PricingEngine::ClosedFormBS;
+        let events = EventTimeline::new().with_event(MarketEvent::ex_dividend(
+            "XYZ",
+            Utc::now() + Duration::days(10),
+            pos_or_panic!(2.0),
+        ));
+
+        let plain = simulate_positions_pnl_paths(&[long_call()], &params, &engine).unwrap();
+        let paying =
+            simulate_positions_pnl_paths(&[long_call()], &params.with_events(events), &engine)
+                .unwrap();
+
+        // Same paths: the call loses value once the dividend has gone ex.
+        assert_eq!(paying.fan[1].mean, plain.fan[1].mean);
+        assert!(paying.fan[2].mean < plain.fan[2].mean);
+        assert!(paying.fan[5].mean < plain.fan[5].mean);
+    }
+
+    #[test]
+    fn test_rejects_expired_strategies() {
+        let mut position = long_call();
+        position.option.expiration_date = ExpirationDate::Days(Positive::ZERO);
+        let params = PnLPathParams::new(pos_or_panic!(0.2));
+        assert!(
+            simulate_positions_pnl_paths(&[position], &params, &PricingEngine::ClosedFormBS)
+                .is_err()
+        );
+        assert!(simulate_positions_pnl_paths(&[], &params, &PricingEngine::ClosedFormBS).is_err());
+    }
+}
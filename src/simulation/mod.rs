@@ -130,8 +130,44 @@ mod params;
 pub mod exit;
 mod stats;
 
+/// Module simulating the settlement of strategies held into expiration.
+///
+/// Differentiates cash-settled index options (AM or PM settlement prints) from
+/// physically settled equity options, where post-close assignment decisions
+/// create pin risk.
+mod settlement;
+
+/// Module simulating GBM, Merton jump and Heston price paths on arbitrary
+/// time grids.
+///
+/// Produces a compact path matrix shared by the path-dependent pricers.
+pub mod paths;
+
+/// Seeding policy and independent random streams of the simulations.
+pub mod rng;
+
+/// Module simulating the P&L distribution of strategies held to expiration.
+mod distribution;
+
+/// Module simulating the mark-to-model P&L of strategies along price paths
+/// before expiration.
+mod horizon;
+
+pub use distribution::{
+    PnLBucket, PnLDistribution, PnLDistributionParams, PnLPercentile, simulate_pnl_distribution,
+    simulate_positions_pnl_distribution,
+};
 pub use exit::{ExitPolicy, check_exit_policy};
+pub use horizon::{
+    PnLFanPoint, PnLPathParams, PnLPathSimulation, simulate_pnl_paths, simulate_positions_pnl_paths,
+};
 pub use model::WalkType;
 pub use params::WalkParams;
+pub use paths::{HestonScheme, PathMatrix, PathSimulator, ProcessModel, TimeGrid};
+pub use rng::{base_seed, is_seeded, seeded_rng, stream_rng, stream_seed, with_seed};
+pub use settlement::{
+    SettlementOutcome, SettlementParams, SettlementSimulationResult, simulate_positions_settlement,
+    simulate_settlement,
+};
 pub use stats::SimulationStats;
 pub use traits::{Simulate, WalkTypeAble};
@@ -0,0 +1,797 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Price Paths
+//!
+//! Simulates price paths of an underlying on an arbitrary [`TimeGrid`] and
+//! stores them in a [`PathMatrix`], the common input of path-dependent pricers
+//! such as Asian, lookback or barrier options.
+//!
+//! Paths are simulated under the risk-neutral measure, with drift equal to
+//! the risk-free rate minus the dividend yield, following one of the dynamics
+//! of [`ProcessModel`]:
+//!
+//! * **Geometric Brownian motion** - sampled exactly on each interval:
+//!
+//! ```text
+//! S(t + dt) = S(t) * exp((r - q - sigma^2 / 2) * dt + sigma * sqrt(dt) * Z)
+//! ```
+//!
+//! * **Merton jump diffusion** - lognormal jumps arriving as a Poisson
+//!   process, with the drift compensated so that discounted prices remain
+//!   martingales. Also sampled exactly on each interval.
+//! * **Heston** - mean-reverting stochastic variance correlated with the
+//!   price, discretised with the full truncation Euler scheme or the
+//!   quadratic-exponential (QE) scheme of Andersen. Grid intervals longer
+//!   than a trading day are split into daily sub-steps.
+//!
+//! Every path draws from its own random stream derived from the seed, so a
+//! path only depends on the seed and its index. With the `parallel` feature
+//! the streams are generated on the rayon thread pool and the result is the
+//! same as a single-threaded run.
+//!
+//! With antithetic sampling enabled, paths are generated in pairs: the second
+//! path of each pair replays the shocks of the first one with opposite signs,
+//! which cancels much of the sampling noise of monotonic payoffs.
+
+use crate::error::SimulationError;
+use crate::simulation::rng::{base_seed, stream_rng};
+use crate::utils::parallel::map_ordered;
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rand::RngExt;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, Poisson, StandardNormal};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal};
+use utoipa::ToSchema;
+
+/// Longest step, in years, of the Heston discretisation.
+const HESTON_MAX_STEP: f64 = 1.0 / 252.0;
+
+/// Discretisation scheme of the Heston variance process.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum HestonScheme {
+    /// Euler scheme using the positive part of the variance in the drift and
+    /// diffusion terms.
+    #[default]
+    FullTruncation,
+    /// Quadratic-exponential scheme of Andersen (2008), matching the first
+    /// two moments of the variance over each step.
+    QuadraticExponential,
+}
+
+/// Dynamics of the simulated underlying.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum ProcessModel {
+    /// Geometric Brownian motion with constant volatility.
+    #[default]
+    GeometricBrownian,
+    /// Merton jump diffusion: geometric Brownian motion plus lognormal jumps.
+    MertonJump {
+        /// Expected number of jumps per year.
+        intensity: Positive,
+        /// Mean of the log jump size.
+        jump_mean: Decimal,
+        /// Standard deviation of the log jump size.
+        jump_volatility: Positive,
+    },
+    /// Heston stochastic volatility. The initial variance is the square of
+    /// the simulator volatility.
+    Heston {
+        /// Mean reversion speed of the variance.
+        kappa: Positive,
+        /// Long-term variance.
+        theta: Positive,
+        /// Volatility of the variance.
+        xi: Positive,
+        /// Correlation between price and variance shocks, between -1 and 1.
+        rho: Decimal,
+        /// Discretisation scheme of the variance.
+        scheme: HestonScheme,
+    },
+}
+
+/// Observation times of a simulation, in years from the start.
+///
+/// The grid always starts at time zero and is strictly increasing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TimeGrid {
+    times: Vec<f64>,
+}
+
+impl TimeGrid {
+    /// Creates a grid from observation times in years. Time zero is added if
+    /// missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SimulationError` if a time is negative or not finite, or if
+    /// the times are not strictly increasing.
+    pub fn new(times: Vec<f64>) -> Result<Self, SimulationError> {
+        let mut grid = Vec::with_capacity(times.len() + 1);
+        if times.first() != Some(&0.0) {
+            grid.push(0.0);
+        }
+        grid.extend(times);
+        if grid.iter().any(|time| !time.is_finite() || *time < 0.0) {
+            return Err(SimulationError::invalid_parameters(
+                "Grid times must be finite and non-negative",
+            ));
+        }
+        if grid.windows(2).any(|pair| pair[1] <= pair[0]) {
+            return Err(SimulationError::invalid_parameters(
+                "Grid times must be strictly increasing",
+            ));
+        }
+        Ok(Self { times: grid })
+    }
+
+    /// Creates a grid of `steps` equal intervals up to `horizon` years.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SimulationError` if `steps` or `horizon` is zero.
+    pub fn uniform(horizon: Positive, steps: usize) -> Result<Self, SimulationError> {
+        if steps == 0 || horizon == Positive::ZERO {
+            return Err(SimulationError::invalid_parameters(
+                "A uniform grid needs at least one step and a positive horizon",
+            ));
+        }
+        let dt = horizon.to_f64() / steps as f64;
+        Self::new((0..=steps).map(|step| step as f64 * dt).collect())
+    }
+
+    /// Observation times in years, starting at zero.
+    pub fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Number of steps between observation times.
+    pub fn steps(&self) -> usize {
+        self.times.len() - 1
+    }
+
+    /// Last observation time in years.
+    pub fn horizon(&self) -> f64 {
+        self.times[self.times.len() - 1]
+    }
+}
+
+/// Simulated paths stored row by row: one row per path, one column per
+/// observation time of the grid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PathMatrix {
+    grid: TimeGrid,
+    paths: usize,
+    antithetic: bool,
+    values: Vec<f64>,
+}
+
+impl PathMatrix {
+    /// Observation grid of the paths.
+    pub fn grid(&self) -> &TimeGrid {
+        &self.grid
+    }
+
+    /// Number of simulated paths.
+    pub fn num_paths(&self) -> usize {
+        self.paths
+    }
+
+    /// Whether consecutive paths form antithetic pairs: paths `2i` and
+    /// `2i + 1` are driven by opposite shocks.
+    pub fn is_antithetic(&self) -> bool {
+        self.antithetic
+    }
+
+    /// Number of observations per path, including the starting price.
+    pub fn num_points(&self) -> usize {
+        self.grid.times.len()
+    }
+
+    /// Prices of path `index`, one per observation time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not lower than [`PathMatrix::num_paths`].
+    pub fn path(&self, index: usize) -> &[f64] {
+        let width = self.num_points();
+        &self.values[index * width..(index + 1) * width]
+    }
+
+    /// Iterates over the paths.
+    pub fn iter(&self) -> impl Iterator<Item = &[f64]> {
+        self.values.chunks(self.num_points())
+    }
+
+    /// Prices of every path at observation `point`.
+    pub fn column(&self, point: usize) -> Vec<f64> {
+        self.iter().map(|path| path[point]).collect()
+    }
+
+    /// Prices of every path at the end of the grid.
+    pub fn terminal(&self) -> Vec<f64> {
+        self.column(self.num_points() - 1)
+    }
+
+    /// Fraction of the paths that reach `level` at some observation: rising
+    /// to it when it is above the starting price, falling to it otherwise.
+    pub fn touch_frequency(&self, level: f64) -> f64 {
+        let touched = self
+            .iter()
+            .filter(|path| {
+                if level >= path[0] {
+                    path.iter().any(|price| *price >= level)
+                } else {
+                    path.iter().any(|price| *price <= level)
+                }
+            })
+            .count();
+        touched as f64 / self.paths as f64
+    }
+
+    /// Average price across paths at every observation time.
+    pub fn mean_path(&self) -> Vec<f64> {
+        let mut mean = vec![0.0; self.num_points()];
+        for path in self.iter() {
+            for (total, price) in mean.iter_mut().zip(path) {
+                *total += price;
+            }
+        }
+        mean.iter().map(|total| total / self.paths as f64).collect()
+    }
+}
+
+/// Simulator of risk-neutral price paths.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PathSimulator {
+    /// Price of the underlying at time zero.
+    pub spot: Positive,
+    /// Annualized volatility of the underlying.
+    pub volatility: Positive,
+    /// Annualized risk-free rate.
+    pub risk_free_rate: Decimal,
+    /// Annualized dividend yield of the underlying.
+    pub dividend_yield: Positive,
+    /// Number of paths to simulate.
+    pub paths: usize,
+    /// Optional seed for reproducible simulations.
+    pub seed: Option<u64>,
+    /// Dynamics of the underlying.
+    #[serde(default)]
+    pub process: ProcessModel,
+    /// Whether paths are generated in antithetic pairs.
+    #[serde(default)]
+    pub antithetic: bool,
+}
+
+impl PathSimulator {
+    /// Creates a simulator of 10,000 geometric Brownian motion paths.
+    pub fn new(
+        spot: Positive,
+        volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+    ) -> Self {
+        Self {
+            spot,
+            volatility,
+            risk_free_rate,
+            dividend_yield,
+            paths: 10_000,
+            seed: None,
+            process: ProcessModel::GeometricBrownian,
+            antithetic: false,
+        }
+    }
+
+    /// Enables or disables antithetic sampling.
+    pub fn with_antithetic(mut self, antithetic: bool) -> Self {
+        self.antithetic = antithetic;
+        self
+    }
+
+    /// Sets the dynamics of the underlying.
+    pub fn with_process(mut self, process: ProcessModel) -> Self {
+        self.process = process;
+        self
+    }
+
+    /// Sets the number of paths.
+    pub fn with_paths(mut self, paths: usize) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Sets the seed of the random number generator.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Risk-neutral drift: the risk-free rate minus the dividend yield.
+    pub fn drift(&self) -> f64 {
+        self.risk_free_rate.to_f64().unwrap_or(0.0) - self.dividend_yield.to_f64()
+    }
+
+    /// Simulates the paths on `grid`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SimulationError` if no path is requested, the spot price is
+    /// zero or the parameters of the process are invalid.
+    pub fn simulate(&self, grid: &TimeGrid) -> Result<PathMatrix, SimulationError> {
+        if self.paths == 0 {
+            return Err(SimulationError::invalid_parameters(
+                "At least one path is required",
+            ));
+        }
+        if self.spot == Positive::ZERO {
+            return Err(SimulationError::invalid_parameters(
+                "Spot price must be greater than zero",
+            ));
+        }
+        if let ProcessModel::Heston {
+            kappa,
+            xi,
+            rho,
+            scheme,
+            ..
+        } = self.process
+        {
+            if rho.abs() > Decimal::ONE {
+                return Err(SimulationError::invalid_parameters(
+                    "Heston correlation must be between -1 and 1",
+                ));
+            }
+            if scheme == HestonScheme::QuadraticExponential
+                && (kappa == Positive::ZERO || xi == Positive::ZERO)
+            {
+                return Err(SimulationError::invalid_parameters(
+                    "The QE scheme needs a positive kappa and xi",
+                ));
+            }
+        }
+
+        let seed = base_seed(self.seed);
+        let (streams, per_stream) = if self.antithetic {
+            (self.paths.div_ceil(2), 2)
+        } else {
+            (self.paths, 1)
+        };
+        // Streams are independent, so they can be generated in any order and
+        // are concatenated in stream order.
+        let chunks = map_ordered((0..streams).collect(), |stream| {
+            let count = per_stream.min(self.paths - stream * per_stream);
+            self.simulate_stream(grid, seed, stream as u64, count)
+        });
+        let mut values = Vec::with_capacity(self.paths * grid.times.len());
+        for chunk in chunks {
+            values.extend(chunk?);
+        }
+        Ok(PathMatrix {
+            grid: grid.clone(),
+            paths: self.paths,
+            antithetic: self.antithetic,
+            values,
+        })
+    }
+
+    /// Simulates the `count` paths of `stream`: one path, or an antithetic
+    /// pair.
+    fn simulate_stream(
+        &self,
+        grid: &TimeGrid,
+        seed: u64,
+        stream: u64,
+        count: usize,
+    ) -> Result<Vec<f64>, SimulationError> {
+        let mut shocks = Shocks::new(seed);
+        let mut values = Vec::with_capacity(count * grid.times.len());
+        for index in 0..count {
+            shocks.begin(stream, index == 1);
+            values.push(self.spot.to_f64());
+            match self.process {
+                ProcessModel::GeometricBrownian => self.gbm_path(grid, &mut shocks, &mut values),
+                ProcessModel::MertonJump {
+                    intensity,
+                    jump_mean,
+                    jump_volatility,
+                } => self.merton_path(
+                    grid,
+                    intensity.to_f64(),
+                    jump_mean.to_f64().unwrap_or(0.0),
+                    jump_volatility.to_f64(),
+                    &mut shocks,
+                    &mut values,
+                )?,
+                ProcessModel::Heston {
+                    kappa,
+                    theta,
+                    xi,
+                    rho,
+                    scheme,
+                } => self.heston_path(
+                    grid,
+                    HestonParams {
+                        kappa: kappa.to_f64(),
+                        theta: theta.to_f64(),
+                        xi: xi.to_f64(),
+                        rho: rho.to_f64().unwrap_or(0.0),
+                        scheme,
+                    },
+                    &mut shocks,
+                    &mut values,
+                ),
+            }
+        }
+        Ok(values)
+    }
+
+    fn gbm_path(&self, grid: &TimeGrid, shocks: &mut Shocks, values: &mut Vec<f64>) {
+        let sigma = self.volatility.to_f64();
+        let drift = self.drift() - 0.5 * sigma * sigma;
+        let mut price = self.spot.to_f64();
+        for pair in grid.times.windows(2) {
+            let dt = pair[1] - pair[0];
+            let z = shocks.normal();
+            price *= (drift * dt + sigma * dt.sqrt() * z).exp();
+            values.push(price);
+        }
+    }
+
+    fn merton_path(
+        &self,
+        grid: &TimeGrid,
+        intensity: f64,
+        jump_mean: f64,
+        jump_volatility: f64,
+        shocks: &mut Shocks,
+        values: &mut Vec<f64>,
+    ) -> Result<(), SimulationError> {
+        let sigma = self.volatility.to_f64();
+        // Compensates the expected jump so the discounted price stays a martingale.
+        let compensator = intensity * ((jump_mean + 0.5 * jump_volatility.powi(2)).exp() - 1.0);
+        let drift = self.drift() - compensator - 0.5 * sigma * sigma;
+        let mut price = self.spot.to_f64();
+        for pair in grid.times.windows(2) {
+            let dt = pair[1] - pair[0];
+            let z = shocks.normal();
+            let mut log_return = drift * dt + sigma * dt.sqrt() * z;
+            if intensity > 0.0 {
+                let jumps = shocks.poisson(intensity * dt)?;
+                if jumps > 0.0 {
+                    let z = shocks.normal();
+                    log_return += jumps * jump_mean + jumps.sqrt() * jump_volatility * z;
+                }
+            }
+            price *= log_return.exp();
+            values.push(price);
+        }
+        Ok(())
+    }
+
+    fn heston_path(
+        &self,
+        grid: &TimeGrid,
+        params: HestonParams,
+        shocks: &mut Shocks,
+        values: &mut Vec<f64>,
+    ) {
+        let drift = self.drift();
+        let mut log_price = self.spot.to_f64().ln();
+        let mut variance = self.volatility.to_f64().powi(2);
+        for pair in grid.times.windows(2) {
+            let interval = pair[1] - pair[0];
+            let substeps = (interval / HESTON_MAX_STEP).ceil().max(1.0);
+            let dt = interval / substeps;
+            for _ in 0..substeps as usize {
+                let (step, next) = match params.scheme {
+                    HestonScheme::FullTruncation => params.full_truncation(variance, dt, shocks),
+                    HestonScheme::QuadraticExponential => {
+                        params.quadratic_exponential(variance, dt, shocks)
+                    }
+                };
+                log_price += drift * dt + step;
+                variance = next;
+            }
+            values.push(log_price.exp());
+        }
+    }
+}
+
+/// Random draws of the simulation. Each path, or antithetic pair, draws from
+/// its own stream of the base seed. Records the draws of each path so that,
+/// when mirroring, the next path replays them as its antithetic twin: normal
+/// shocks change sign, uniforms `u` become `1 - u` and jump counts repeat.
+struct Shocks {
+    seed: u64,
+    rng: StdRng,
+    draws: Vec<f64>,
+    cursor: usize,
+    mirror: bool,
+}
+
+impl Shocks {
+    fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: stream_rng(seed, 0),
+            draws: Vec::new(),
+            cursor: 0,
+            mirror: false,
+        }
+    }
+
+    /// Starts a new path on `stream`, mirroring the previous path if `mirror`
+    /// is set.
+    fn begin(&mut self, stream: u64, mirror: bool) {
+        self.mirror = mirror;
+        self.cursor = 0;
+        if !mirror {
+            self.rng = stream_rng(self.seed, stream);
+            self.draws.clear();
+        }
+    }
+
+    fn replay(&mut self) -> f64 {
+        let draw = self.draws[self.cursor];
+        self.cursor += 1;
+        draw
+    }
+
+    fn record(&mut self, draw: f64) -> f64 {
+        self.draws.push(draw);
+        draw
+    }
+
+    fn normal(&mut self) -> f64 {
+        if self.mirror {
+            -self.replay()
+        } else {
+            let z: f64 = StandardNormal.sample(&mut self.rng);
+            self.record(z)
+        }
+    }
+
+    fn uniform(&mut self) -> f64 {
+        if self.mirror {
+            1.0 - self.replay()
+        } else {
+            let u = self.rng.random::<f64>();
+            self.record(u)
+        }
+    }
+
+    fn poisson(&mut self, lambda: f64) -> Result<f64, SimulationError> {
+        if self.mirror {
+            return Ok(self.replay());
+        }
+        let jumps: f64 = Poisson::new(lambda)
+            .map_err(|e| SimulationError::invalid_parameters(&e.to_string()))?
+            .sample(&mut self.rng);
+        Ok(self.record(jumps))
+    }
+}
+
+/// Heston parameters converted for the simulation loop.
+#[derive(Debug, Clone, Copy)]
+struct HestonParams {
+    kappa: f64,
+    theta: f64,
+    xi: f64,
+    rho: f64,
+    scheme: HestonScheme,
+}
+
+impl HestonParams {
+    /// Full truncation Euler step. Returns the log price increment, without
+    /// the risk-neutral drift, and the next variance.
+    fn full_truncation(&self, variance: f64, dt: f64, shocks: &mut Shocks) -> (f64, f64) {
+        let z1 = shocks.normal();
+        let z2 = shocks.normal();
+        let z_variance = self.rho * z1 + (1.0 - self.rho * self.rho).sqrt() * z2;
+        let positive = variance.max(0.0);
+        let step = -0.5 * positive * dt + (positive * dt).sqrt() * z1;
+        let next = variance
+            + self.kappa * (self.theta - positive) * dt
+            + self.xi * (positive * dt).sqrt() * z_variance;
+        (step, next)
+    }
+
+    /// Quadratic-exponential step with central discretisation of the
+    /// integrated variance. Returns the log price increment, without the
+    /// risk-neutral drift, and the next variance.
+    fn quadratic_exponential(&self, variance: f64, dt: f64, shocks: &mut Shocks) -> (f64, f64) {
+        let decay = (-self.kappa * dt).exp();
+        let xi2 = self.xi * self.xi;
+        let mean = self.theta + (variance - self.theta) * decay;
+        let var = variance * xi2 * decay * (1.0 - decay) / self.kappa
+            + self.theta * xi2 * (1.0 - decay).powi(2) / (2.0 * self.kappa);
+        let psi = var / (mean * mean);
+
+        // A single uniform drives both branches, so antithetic twins mirror it.
+        let u = shocks.uniform();
+        let next = if psi <= 1.5 {
+            let inverse = 2.0 / psi;
+            let b2 = inverse - 1.0 + (inverse * (inverse - 1.0)).sqrt();
+            let a = mean / (1.0 + b2);
+            let z = Normal::standard().inverse_cdf(u.clamp(f64::EPSILON, 1.0 - f64::EPSILON));
+            a * (b2.sqrt() + z).powi(2)
+        } else {
+            let p = (psi - 1.0) / (psi + 1.0);
+            let beta = (1.0 - p) / mean;
+            if u <= p {
+                0.0
+            } else {
+                ((1.0 - p) / (1.0 - u)).ln() / beta
+            }
+        };
+
+        let k0 = -self.rho * self.kappa * self.theta * dt / self.xi;
+        let k1 = 0.5 * dt * (self.kappa * self.rho / self.xi - 0.5) - self.rho / self.xi;
+        let k2 = 0.5 * dt * (self.kappa * self.rho / self.xi - 0.5) + self.rho / self.xi;
+        let k3 = 0.5 * dt * (1.0 - self.rho * self.rho);
+        let z = shocks.normal();
+        let step = k0 + k1 * variance + k2 * next + (k3 * (variance + next)).sqrt() * z;
+        (step, next)
+    }
+}
+
+#[cfg(test)]
+mod tests_paths {
+    use super::*;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_time_grid() {
+        let grid = TimeGrid::new(vec![0.25, 0.5, 1.0]).unwrap();
+        assert_eq!(grid.times(), &[0.0, 0.25, 0.5, 1.0]);
+        assert_eq!(grid.steps(), 3);
+        assert_eq!(grid.horizon(), 1.0);
+        assert!(TimeGrid::new(vec![0.5, 0.25]).is_err());
+        assert!(TimeGrid::new(vec![-0.1]).is_err());
+
+        let uniform = TimeGrid::uniform(Positive::ONE, 4).unwrap();
+        assert_eq!(uniform.times(), &[0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert!(TimeGrid::uniform(Positive::ONE, 0).is_err());
+    }
+
+    #[test]
+    fn test_gbm_paths_match_risk_neutral_moments() {
+        let simulator = PathSimulator::new(
+            Positive::HUNDRED,
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            pos_or_panic!(0.02),
+        )
+        .with_paths(20_000)
+        .with_seed(42);
+        let grid = TimeGrid::new(vec![0.1, 0.5, 1.0]).unwrap();
+        let matrix = simulator.simulate(&grid).unwrap();
+
+        assert_eq!(matrix.num_paths(), 20_000);
+        assert_eq!(matrix.num_points(), 4);
+        assert!(matrix.iter().all(|path| path[0] == 100.0));
+        assert_eq!(matrix.path(3).len(), 4);
+
+        // E[S(T)] = S * exp((r - q) * T).
+        let mean = matrix.mean_path();
+        for (time, mean) in grid.times().iter().zip(&mean) {
+            let expected = 100.0 * (0.03 * time).exp();
+            assert!((mean - expected).abs() / expected < 0.005);
+        }
+        assert_eq!(matrix.terminal().len(), 20_000);
+
+        let again = simulator.simulate(&grid).unwrap();
+        assert_eq!(matrix, again);
+        // Paths come from independent streams, so fewer paths give a prefix.
+        let fewer = simulator.clone().with_paths(10).simulate(&grid).unwrap();
+        assert_eq!(fewer.path(9), matrix.path(9));
+    }
+
+    fn assert_martingale(process: ProcessModel) {
+        let simulator = PathSimulator::new(
+            Positive::HUNDRED,
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            pos_or_panic!(0.02),
+        )
+        .with_paths(10_000)
+        .with_seed(7)
+        .with_process(process);
+        let grid = TimeGrid::uniform(pos_or_panic!(0.5), 4).unwrap();
+        let matrix = simulator.simulate(&grid).unwrap();
+
+        let expected = 100.0 * (0.03_f64 * 0.5).exp();
+        let terminal = matrix.terminal();
+        let mean = terminal.iter().sum::<f64>() / terminal.len() as f64;
+        assert!(
+            (mean - expected).abs() / expected < 0.01,
+            "{process:?}: mean {mean} vs {expected}"
+        );
+        assert!(
+            terminal
+                .iter()
+                .all(|price| price.is_finite() && *price > 0.0)
+        );
+    }
+
+    #[test]
+    fn test_merton_paths_are_compensated() {
+        assert_martingale(ProcessModel::MertonJump {
+            intensity: Positive::ONE,
+            jump_mean: dec!(-0.1),
+            jump_volatility: pos_or_panic!(0.15),
+        });
+    }
+
+    #[test]
+    fn test_heston_schemes_are_martingales() {
+        for scheme in [
+            HestonScheme::FullTruncation,
+            HestonScheme::QuadraticExponential,
+        ] {
+            assert_martingale(ProcessModel::Heston {
+                kappa: Positive::TWO,
+                theta: pos_or_panic!(0.04),
+                xi: pos_or_panic!(0.5),
+                rho: dec!(-0.7),
+                scheme,
+            });
+        }
+    }
+
+    #[test]
+    fn test_antithetic_pairs_mirror_shocks() {
+        let simulator = PathSimulator::new(
+            Positive::HUNDRED,
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+        )
+        .with_paths(4)
+        .with_seed(3)
+        .with_antithetic(true);
+        let grid = TimeGrid::uniform(Positive::ONE, 2).unwrap();
+        let matrix = simulator.simulate(&grid).unwrap();
+        assert!(matrix.is_antithetic());
+
+        // Log returns of the twins are symmetric around the drift -sigma^2 / 2 * t.
+        for pair in 0..2 {
+            let first = matrix.path(2 * pair);
+            let second = matrix.path(2 * pair + 1);
+            for point in 1..3 {
+                let drift = -0.02 * grid.times()[point];
+                let up = (first[point] / 100.0).ln() - drift;
+                let down = (second[point] / 100.0).ln() - drift;
+                assert!((up + down).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_heston_rejects_invalid_correlation() {
+        let simulator = PathSimulator::new(
+            Positive::HUNDRED,
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+        )
+        .with_process(ProcessModel::Heston {
+            kappa: Positive::TWO,
+            theta: pos_or_panic!(0.04),
+            xi: pos_or_panic!(0.5),
+            rho: dec!(1.5),
+            scheme: HestonScheme::FullTruncation,
+        });
+        let grid = TimeGrid::uniform(Positive::ONE, 1).unwrap();
+        assert!(simulator.simulate(&grid).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Simulation Random Numbers
+//!
+//! Seeding policy shared by every simulation of the library:
+//!
+//! * **Explicit seeds** - APIs taking an `Option<u64>` seed build their
+//!   generator with [`seeded_rng`]. The same seed always produces the same
+//!   simulation.
+//! * **Seeded scopes** - APIs without a seed parameter, such as the random
+//!   walk generators or [`monte_carlo_option_pricing`](crate::pricing::monte_carlo_option_pricing),
+//!   draw from the generator installed by [`with_seed`] on the current thread.
+//!   Unseeded calls made inside the scope become reproducible as well.
+//! * **Independent streams** - multi-path simulations derive one generator per
+//!   path with [`stream_rng`], so a path only depends on the seed and its own
+//!   index, not on how many paths were generated before it.
+//!
+//! Outside a seeded scope and without a seed, generators are seeded from the
+//! operating system and simulations differ from run to run.
+
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+use rand_distr::{Distribution, StandardNormal};
+use std::cell::RefCell;
+
+thread_local! {
+    static SCOPED_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous scoped generator when a [`with_seed`] scope ends,
+/// including by panic.
+struct ScopeGuard(Option<StdRng>);
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        SCOPED_RNG.with(|rng| *rng.borrow_mut() = previous);
+    }
+}
+
+/// Runs `f` with a generator seeded with `seed` installed on the current
+/// thread, making every simulation without an explicit seed reproducible.
+///
+/// Scopes can be nested; the previous generator is restored when `f` returns.
+pub fn with_seed<T>(seed: u64, f: impl FnOnce() -> T) -> T {
+    let previous = SCOPED_RNG.with(|rng| rng.replace(Some(StdRng::seed_from_u64(seed))));
+    let _guard = ScopeGuard(previous);
+    f()
+}
+
+/// Returns `true` inside a [`with_seed`] scope.
+pub fn is_seeded() -> bool {
+    SCOPED_RNG.with(|rng| rng.borrow().is_some())
+}
+
+/// Creates a generator from `seed`, or, without a seed, from the scoped
+/// generator if any and from the operating system otherwise.
+pub fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => SCOPED_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+            Some(scoped) => StdRng::seed_from_u64(scoped.random()),
+            None => StdRng::from_rng(&mut rand::rng()),
+        }),
+    }
+}
+
+/// Derives the seed of stream `stream` from a base `seed`.
+///
+/// Uses two rounds of SplitMix64, so neighbouring streams get unrelated seeds.
+pub fn stream_seed(seed: u64, stream: u64) -> u64 {
+    splitmix64(splitmix64(seed).wrapping_add(stream))
+}
+
+/// Creates the generator of stream `stream` of a simulation seeded with `seed`.
+pub fn stream_rng(seed: u64, stream: u64) -> StdRng {
+    StdRng::seed_from_u64(stream_seed(seed, stream))
+}
+
+/// Draws a base seed for the streams of a simulation: `seed` itself if set,
+/// otherwise a draw of [`seeded_rng`].
+pub fn base_seed(seed: Option<u64>) -> u64 {
+    seed.unwrap_or_else(|| seeded_rng(None).random())
+}
+
+/// Standard normal draw from the scoped generator, or the thread generator
+/// outside a seeded scope.
+pub(crate) fn standard_normal() -> f64 {
+    SCOPED_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+        Some(scoped) => StandardNormal.sample(scoped),
+        None => StandardNormal.sample(&mut rand::rng()),
+    })
+}
+
+/// Uniform draw in `[0, 1)` from the scoped generator, or the thread generator
+/// outside a seeded scope.
+pub(crate) fn uniform() -> f64 {
+    SCOPED_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+        Some(scoped) => scoped.random::<f64>(),
+        None => rand::rng().random::<f64>(),
+    })
+}
+
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests_rng {
+    use super::*;
+
+    #[test]
+    fn test_seeded_scopes_are_reproducible() {
+        let draw = || (0..5).map(|_| standard_normal()).collect::<Vec<f64>>();
+        let first = with_seed(7, draw);
+        let second = with_seed(7, draw);
+        assert_eq!(first, second);
+        assert_ne!(first, with_seed(8, draw));
+
+        assert!(!is_seeded());
+        let nested = with_seed(1, || {
+            let outer = uniform();
+            let inner = with_seed(2, uniform);
+            assert!(is_seeded());
+            (outer, inner)
+        });
+        assert_eq!(nested.1, with_seed(2, uniform));
+        assert!(!is_seeded());
+    }
+
+    #[test]
+    fn test_streams_are_independent_of_order() {
+        assert_eq!(stream_seed(42, 3), stream_seed(42, 3));
+        assert_ne!(stream_seed(42, 3), stream_seed(42, 4));
+        assert_ne!(stream_seed(42, 3), stream_seed(43, 3));
+
+        let mut a = stream_rng(42, 3);
+        let mut b = stream_rng(42, 3);
+        assert_eq!(a.random::<u64>(), b.random::<u64>());
+        assert_eq!(
+            with_seed(5, || seeded_rng(None).random::<u64>()),
+            with_seed(5, || seeded_rng(None).random::<u64>())
+        );
+        assert_eq!(base_seed(Some(9)), 9);
+    }
+}
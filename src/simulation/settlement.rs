@@ -0,0 +1,518 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Settlement Simulation
+//!
+//! Simulates what happens to a strategy that is held into expiration, starting
+//! from the close of the session before the settlement print.
+//!
+//! The settlement mechanics depend on when and how the contract settles:
+//!
+//! * **AM-settled options** settle on a special opening quotation (SET/SOQ
+//!   style) computed from the opening prints of expiration day. The settlement
+//!   value is only exposed to the overnight gap from the prior close.
+//! * **PM-settled options** settle on the closing print of expiration day, so
+//!   they carry a full session of risk.
+//! * **Cash-settled options** pay their intrinsic value at the settlement print.
+//! * **Physically settled options** deliver shares. Long legs in the
+//!   money at the close are auto-exercised, but short legs are assigned based on
+//!   the holder's decision after the close, which can differ from the closing
+//!   moneyness. Strikes pinned near the close therefore leave an uncertain share
+//!   position that is marked at the post-close price.
+//!
+//! [`simulate_settlement`] samples the settlement print distribution relative to
+//! the prior close and reports the resulting P&L distribution, the pin
+//! probability and the expected residual share position.
+
+use crate::error::SimulationError;
+use crate::model::Position;
+use crate::model::types::{OptionStyle, SettlementType, Side};
+use crate::simulation::rng::seeded_rng;
+use crate::strategies::base::Positionable;
+use crate::strategies::delta_neutral::DEFAULT_OPTION_MULTIPLIER;
+use crate::utils::calendar::SettlementTiming;
+use num_traits::{FromPrimitive, ToPrimitive};
+use positive::{Positive, pos_or_panic};
+use rand_distr::{Distribution, StandardNormal};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Number of trading sessions per year used to scale the annualized volatility.
+const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// Parameters of a settlement simulation.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SettlementParams {
+    /// Whether the legs settle in cash or by delivery of the underlying.
+    pub settlement_type: SettlementType,
+    /// Whether the legs settle on the opening or the closing print of expiration day.
+    pub timing: SettlementTiming,
+    /// Units of the underlying delivered per contract on physical settlement.
+    pub contract_multiplier: Positive,
+    /// Closing price of the session before the settlement print.
+    pub prior_close: Positive,
+    /// Annualized volatility of the underlying.
+    pub volatility: Positive,
+    /// Share of one session's variance accrued between the prior close and an AM print.
+    pub am_variance_fraction: Positive,
+    /// Share of one session's variance accrued after the close, before the exercise cutoff.
+    pub after_hours_variance_fraction: Positive,
+    /// Distance from a short strike within which the settlement is considered pinned.
+    pub pin_band: Positive,
+    /// Number of simulated settlement prints.
+    pub simulations: usize,
+    /// Optional seed for reproducible simulations.
+    pub seed: Option<u64>,
+}
+
+impl SettlementParams {
+    /// Creates parameters with a contract multiplier of 100, the default
+    /// variance split (35% of a session overnight, 10% after hours), a pin band
+    /// of 0.5% of the prior close and 10,000 simulations.
+    pub fn new(
+        settlement_type: SettlementType,
+        timing: SettlementTiming,
+        prior_close: Positive,
+        volatility: Positive,
+    ) -> Self {
+        SettlementParams {
+            settlement_type,
+            timing,
+            contract_multiplier: DEFAULT_OPTION_MULTIPLIER,
+            prior_close,
+            volatility,
+            am_variance_fraction: pos_or_panic!(0.35),
+            after_hours_variance_fraction: pos_or_panic!(0.1),
+            pin_band: prior_close * pos_or_panic!(0.005),
+            simulations: 10_000,
+            seed: None,
+        }
+    }
+
+    /// Returns `true` when the legs settle in cash.
+    pub fn is_cash(&self) -> bool {
+        self.settlement_type == SettlementType::Cash
+    }
+
+    fn validate(&self) -> Result<(), SimulationError> {
+        if self.simulations == 0 {
+            return Err(SimulationError::invalid_parameters(
+                "At least one settlement simulation is required",
+            ));
+        }
+        if self.prior_close == Positive::ZERO {
+            return Err(SimulationError::invalid_parameters(
+                "Prior close must be greater than zero",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Standard deviation of the log move between the prior close and the settlement print.
+    fn settlement_sigma(&self) -> f64 {
+        let fraction = match self.timing {
+            SettlementTiming::Am => self.am_variance_fraction.to_f64(),
+            SettlementTiming::Pm => 1.0,
+        };
+        session_sigma(self.volatility.to_f64(), fraction)
+    }
+
+    /// Standard deviation of the log move between the close and the exercise cutoff.
+    fn after_hours_sigma(&self) -> f64 {
+        session_sigma(
+            self.volatility.to_f64(),
+            self.after_hours_variance_fraction.to_f64(),
+        )
+    }
+}
+
+fn session_sigma(annual_volatility: f64, fraction: f64) -> f64 {
+    annual_volatility * (fraction / TRADING_DAYS_PER_YEAR).sqrt()
+}
+
+/// One simulated settlement.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SettlementOutcome {
+    /// Settlement print of the underlying.
+    pub settlement_price: Positive,
+    /// Price used by holders to decide on exercise; equals the settlement print for cash settlement.
+    pub decision_price: Positive,
+    /// Strategy P&L after settlement, including premiums and fees.
+    pub pnl: Decimal,
+    /// Net shares left after exercise and assignment, i.e. contracts times the
+    /// contract multiplier (always zero for cash settlement).
+    pub residual_shares: Decimal,
+    /// Number of short legs whose strike was within the pin band of the settlement print.
+    pub pinned_legs: usize,
+    /// Number of short legs whose assignment differs from their moneyness at the settlement print.
+    pub assignment_surprises: usize,
+}
+
+/// Aggregated result of a settlement simulation.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SettlementSimulationResult {
+    /// Whether the simulated legs settled in cash or by delivery.
+    pub settlement_type: SettlementType,
+    /// Whether the simulated legs settled on the opening or the closing print.
+    pub timing: SettlementTiming,
+    /// Closing price the simulation started from.
+    pub prior_close: Positive,
+    /// P&L the strategy would realize if it settled exactly at the prior close.
+    pub pnl_at_prior_close: Decimal,
+    /// Mean of the simulated settlement prints.
+    pub mean_settlement: Decimal,
+    /// Standard deviation of the settlement print relative to the prior close.
+    pub settlement_std_dev: Decimal,
+    /// Mean simulated P&L.
+    pub expected_pnl: Decimal,
+    /// Standard deviation of the simulated P&L.
+    pub pnl_std_dev: Decimal,
+    /// Fraction of simulations with at least one pinned short leg.
+    pub pin_probability: Decimal,
+    /// Fraction of simulations ending with a non-zero share position.
+    pub residual_shares_probability: Decimal,
+    /// Mean absolute residual share position, in shares.
+    pub expected_abs_residual_shares: Decimal,
+    /// Every simulated settlement.
+    pub outcomes: Vec<SettlementOutcome>,
+}
+
+/// Simulates the settlement of a strategy held into expiration.
+///
+/// # Arguments
+///
+/// * `strategy` - Strategy whose positions are held into expiration.
+/// * `params` - Settlement mechanics and distribution parameters.
+///
+/// # Errors
+///
+/// Returns a `SimulationError` if the parameters are invalid, the strategy does
+/// not expose its positions or a P&L cannot be computed.
+pub fn simulate_settlement<S: Positionable>(
+    strategy: &S,
+    params: &SettlementParams,
+) -> Result<SettlementSimulationResult, SimulationError> {
+    let positions: Vec<Position> = strategy.get_positions()?.into_iter().cloned().collect();
+    simulate_positions_settlement(&positions, params)
+}
+
+/// Simulates the settlement of a set of positions held into expiration.
+///
+/// See [`simulate_settlement`].
+///
+/// # Errors
+///
+/// Returns a `SimulationError` if the parameters are invalid or a P&L cannot be computed.
+pub fn simulate_positions_settlement(
+    positions: &[Position],
+    params: &SettlementParams,
+) -> Result<SettlementSimulationResult, SimulationError> {
+    params.validate()?;
+    if positions.is_empty() {
+        return Err(SimulationError::invalid_parameters(
+            "No positions to settle",
+        ));
+    }
+
+    let mut rng = seeded_rng(params.seed);
+    let close = params.prior_close.to_f64();
+    let settlement_sigma = params.settlement_sigma();
+    let after_hours_sigma = params.after_hours_sigma();
+
+    let mut outcomes = Vec::with_capacity(params.simulations);
+    for _ in 0..params.simulations {
+        let z: f64 = StandardNormal.sample(&mut rng);
+        let settlement = lognormal_move(close, settlement_sigma, z);
+        let decision = if params.is_cash() {
+            settlement
+        } else {
+            let z: f64 = StandardNormal.sample(&mut rng);
+            lognormal_move(settlement, after_hours_sigma, z)
+        };
+        outcomes.push(settle(
+            positions,
+            params,
+            Positive::new(settlement)?,
+            Positive::new(decision)?,
+        )?);
+    }
+
+    let pnl_at_prior_close = positions
+        .iter()
+        .map(|p| p.pnl_at_expiration(&Some(&params.prior_close)))
+        .sum::<Result<Decimal, _>>()?;
+    let count = Decimal::from(outcomes.len());
+    let settlements: Vec<Decimal> = outcomes
+        .iter()
+        .map(|o| o.settlement_price.to_dec())
+        .collect();
+    let pnls: Vec<Decimal> = outcomes.iter().map(|o| o.pnl).collect();
+    let pinned = outcomes.iter().filter(|o| o.pinned_legs > 0).count();
+    let with_shares = outcomes
+        .iter()
+        .filter(|o| !o.residual_shares.is_zero())
+        .count();
+    let abs_shares: Decimal = outcomes.iter().map(|o| o.residual_shares.abs()).sum();
+
+    Ok(SettlementSimulationResult {
+        settlement_type: params.settlement_type,
+        timing: params.timing,
+        prior_close: params.prior_close,
+        pnl_at_prior_close,
+        mean_settlement: mean(&settlements),
+        settlement_std_dev: std_dev_around(&settlements, params.prior_close.to_dec()),
+        expected_pnl: mean(&pnls),
+        pnl_std_dev: std_dev_around(&pnls, mean(&pnls)),
+        pin_probability: Decimal::from(pinned) / count,
+        residual_shares_probability: Decimal::from(with_shares) / count,
+        expected_abs_residual_shares: abs_shares / count,
+        outcomes,
+    })
+}
+
+/// Martingale log-normal move of `price` with standard deviation `sigma`.
+fn lognormal_move(price: f64, sigma: f64, z: f64) -> f64 {
+    price * (sigma * z - 0.5 * sigma * sigma).exp()
+}
+
+/// Settles every position for one pair of settlement and decision prices.
+fn settle(
+    positions: &[Position],
+    params: &SettlementParams,
+    settlement: Positive,
+    decision: Positive,
+) -> Result<SettlementOutcome, SimulationError> {
+    let mut pnl = Decimal::ZERO;
+    let mut residual_shares = Decimal::ZERO;
+    let mut pinned_legs = 0;
+    let mut assignment_surprises = 0;
+
+    for position in positions {
+        let option = &position.option;
+        let strike = option.strike_price;
+        let quantity = option.quantity.to_dec();
+        let is_short = option.side == Side::Short;
+        if is_short && (settlement.to_dec() - strike.to_dec()).abs() <= params.pin_band.to_dec() {
+            pinned_legs += 1;
+        }
+
+        let value = if params.is_cash() {
+            option.intrinsic_value(settlement)?
+        } else {
+            // Long legs are auto-exercised on the settlement print, short legs
+            // are assigned on the holder's post-close decision.
+            let exercise_price = if is_short { decision } else { settlement };
+            let exercised = in_the_money(option.option_style, strike, exercise_price);
+            if is_short && exercised != in_the_money(option.option_style, strike, settlement) {
+                assignment_surprises += 1;
+            }
+            if exercised {
+                let (value, delivered) = match option.option_style {
+                    OptionStyle::Call => (decision.to_dec() - strike.to_dec(), quantity),
+                    OptionStyle::Put => (strike.to_dec() - decision.to_dec(), -quantity),
+                };
+                let sign = if is_short {
+                    Decimal::NEGATIVE_ONE
+                } else {
+                    Decimal::ONE
+                };
+                residual_shares += sign * delivered * params.contract_multiplier.to_dec();
+                sign * value * quantity
+            } else {
+                Decimal::ZERO
+            }
+        };
+        pnl += value - position.total_cost()?.to_dec() + position.premium_received()?.to_dec();
+    }
+
+    Ok(SettlementOutcome {
+        settlement_price: settlement,
+        decision_price: decision,
+        pnl,
+        residual_shares,
+        pinned_legs,
+        assignment_surprises,
+    })
+}
+
+fn in_the_money(style: OptionStyle, strike: Positive, price: Positive) -> bool {
+    match style {
+        OptionStyle::Call => price > strike,
+        OptionStyle::Put => price < strike,
+    }
+}
+
+fn mean(values: &[Decimal]) -> Decimal {
+    if values.is_empty() {
+        return Decimal::ZERO;
+    }
+    values.iter().sum::<Decimal>() / Decimal::from(values.len())
+}
+
+fn std_dev_around(values: &[Decimal], center: Decimal) -> Decimal {
+    if values.is_empty() {
+        return Decimal::ZERO;
+    }
+    let variance = values
+        .iter()
+        .map(|v| ((*v - center) * (*v - center)).to_f64().unwrap_or(0.0))
+        .sum::<f64>()
+        / values.len() as f64;
+    Decimal::from_f64(variance.sqrt()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests_settlement {
+    use super::*;
+    use crate::model::utils::create_sample_position;
+
+    fn short_straddle(strike: Positive) -> Vec<Position> {
+        vec![
+            create_sample_position(
+                OptionStyle::Call,
+                Side::Short,
+                Positive::HUNDRED,
+                Positive::ONE,
+                strike,
+                pos_or_panic!(0.2),
+            ),
+            create_sample_position(
+                OptionStyle::Put,
+                Side::Short,
+                Positive::HUNDRED,
+                Positive::ONE,
+                strike,
+                pos_or_panic!(0.2),
+            ),
+        ]
+    }
+
+    fn params(settlement_type: SettlementType, timing: SettlementTiming) -> SettlementParams {
+        let mut params = SettlementParams::new(
+            settlement_type,
+            timing,
+            Positive::HUNDRED,
+            pos_or_panic!(0.3),
+        );
+        params.simulations = 2_000;
+        params.seed = Some(42);
+        params
+    }
+
+    #[test]
+    fn test_am_settlement_is_narrower_than_pm() {
+        let positions = short_straddle(Positive::HUNDRED);
+        let am = simulate_positions_settlement(
+            &positions,
+            &params(SettlementType::Cash, SettlementTiming::Am),
+        )
+        .unwrap();
+        let pm = simulate_positions_settlement(
+            &positions,
+            &params(SettlementType::Cash, SettlementTiming::Pm),
+        )
+        .unwrap();
+        assert!(am.settlement_std_dev < pm.settlement_std_dev);
+        assert!(am.pin_probability > pm.pin_probability);
+        assert_eq!(am.expected_abs_residual_shares, Decimal::ZERO);
+        assert_eq!(am.outcomes.len(), 2_000);
+    }
+
+    #[test]
+    fn test_cash_settlement_matches_expiration_pnl() {
+        let positions = short_straddle(Positive::HUNDRED);
+        let result = simulate_positions_settlement(
+            &positions,
+            &params(SettlementType::Cash, SettlementTiming::Pm),
+        )
+        .unwrap();
+        for outcome in result.outcomes.iter().take(20) {
+            let expected: Decimal = positions
+                .iter()
+                .map(|p| {
+                    p.pnl_at_expiration(&Some(&outcome.settlement_price))
+                        .unwrap()
+                })
+                .sum();
+            assert!((outcome.pnl - expected).abs() < Decimal::new(1, 6));
+            assert_eq!(outcome.decision_price, outcome.settlement_price);
+        }
+    }
+
+    #[test]
+    fn test_physical_pin_leaves_residual_shares() {
+        let positions = short_straddle(Positive::HUNDRED);
+        let result = simulate_positions_settlement(
+            &positions,
+            &params(SettlementType::Physical, SettlementTiming::Pm),
+        )
+        .unwrap();
+        assert!(result.residual_shares_probability > Decimal::ZERO);
+        assert!(result.outcomes.iter().any(|o| o.assignment_surprises > 0));
+        // One contract delivers a full lot of shares.
+        assert!(
+            result
+                .outcomes
+                .iter()
+                .all(|o| (o.residual_shares % Decimal::ONE_HUNDRED).is_zero())
+        );
+        assert!(
+            result
+                .outcomes
+                .iter()
+                .any(|o| o.residual_shares.abs() == Decimal::ONE_HUNDRED)
+        );
+    }
+
+    #[test]
+    fn test_physical_far_otm_has_no_residual() {
+        let positions = vec![create_sample_position(
+            OptionStyle::Call,
+            Side::Short,
+            Positive::HUNDRED,
+            Positive::ONE,
+            pos_or_panic!(200.0),
+            pos_or_panic!(0.2),
+        )];
+        let result = simulate_positions_settlement(
+            &positions,
+            &params(SettlementType::Physical, SettlementTiming::Pm),
+        )
+        .unwrap();
+        assert_eq!(result.residual_shares_probability, Decimal::ZERO);
+        assert_eq!(result.pin_probability, Decimal::ZERO);
+        assert_eq!(result.expected_pnl, result.pnl_at_prior_close);
+    }
+
+    #[test]
+    fn test_seed_is_reproducible() {
+        let positions = short_straddle(Positive::HUNDRED);
+        let a = simulate_positions_settlement(
+            &positions,
+            &params(SettlementType::Physical, SettlementTiming::Pm),
+        )
+        .unwrap();
+        let b = simulate_positions_settlement(
+            &positions,
+            &params(SettlementType::Physical, SettlementTiming::Pm),
+        )
+        .unwrap();
+        assert_eq!(a.expected_pnl, b.expected_pnl);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let positions = short_straddle(Positive::HUNDRED);
+        let mut p = params(SettlementType::Cash, SettlementTiming::Am);
+        p.simulations = 0;
+        assert!(simulate_positions_settlement(&positions, &p).is_err());
+        assert!(
+            simulate_positions_settlement(&[], &params(SettlementType::Cash, SettlementTiming::Am))
+                .is_err()
+        );
+    }
+}
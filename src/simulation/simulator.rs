@@ -4,6 +4,7 @@ use crate::pricing::Profit;
 use crate::pricing::monte_carlo::price_option_monte_carlo;
 use crate::simulation::WalkParams;
 use crate::simulation::randomwalk::RandomWalk;
+use crate::simulation::rng::{stream_seed, with_seed};
 use crate::simulation::steps::Step;
 use crate::strategies::base::BasicAble;
 use crate::utils::Len;
@@ -85,6 +86,40 @@ where
         }
     }
 
+    /// Creates a reproducible simulator: random walk `i` is generated inside a
+    /// [`with_seed`](crate::simulation::rng::with_seed) scope on its own stream
+    /// of `seed`, so each walk only depends on the seed and its index.
+    ///
+    /// # Parameters
+    ///
+    /// * `title` - A descriptive title for the random walks
+    /// * `size` - Number of random walks
+    /// * `params` - Parameters that define the properties of the random walks
+    /// * `generator` - A function that generates the steps of a random walk
+    /// * `seed` - Base seed of the simulation
+    pub fn new_seeded<F>(
+        title: String,
+        size: usize,
+        params: &WalkParams<X, Y>,
+        generator: F,
+        seed: u64,
+    ) -> Self
+    where
+        F: Fn(&WalkParams<X, Y>) -> Vec<Step<X, Y>> + Clone,
+    {
+        let random_walks = (0..size)
+            .map(|i| {
+                with_seed(stream_seed(seed, i as u64), || {
+                    RandomWalk::new(format!("{title}_{i}"), params, &generator)
+                })
+            })
+            .collect();
+        Self {
+            title,
+            random_walks,
+        }
+    }
+
     /// Returns the title of the random walk.
     ///
     /// # Returns
@@ -527,6 +562,58 @@ mod tests {
         assert!(!simulator.is_empty());
     }
 
+    fn gbm_generator(params: &WalkParams<Positive, Positive>) -> Vec<Step<Positive, Positive>> {
+        params
+            .walker
+            .geometric_brownian(params)
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| Step {
+                x: params.init_step.x,
+                y: Ystep::new(i as i32, value),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_seeded_simulator_is_reproducible() {
+        let walk_params = WalkParams {
+            size: 10,
+            init_step: Step {
+                x: Xstep::new(
+                    Positive::ONE,
+                    TimeFrame::Day,
+                    ExpirationDate::Days(pos_or_panic!(30.0)),
+                ),
+                y: Ystep::new(0, Positive::HUNDRED),
+            },
+            walk_type: WalkType::GeometricBrownian {
+                dt: pos_or_panic!(0.004),
+                drift: dec!(0.0),
+                volatility: pos_or_panic!(0.2),
+            },
+            walker: Box::new(TestWalker),
+        };
+
+        let run = |size: usize, seed: u64| {
+            Simulator::new_seeded(
+                "Seeded".to_string(),
+                size,
+                &walk_params,
+                gbm_generator,
+                seed,
+            )
+            .get_last_positive_values()
+        };
+        let first = run(4, 42);
+        assert_eq!(first, run(4, 42));
+        assert_ne!(first, run(4, 43));
+        // Every walk has its own stream, so fewer walks give a prefix.
+        assert_eq!(run(2, 42), first[..2].to_vec());
+        assert_ne!(first[0], first[1]);
+    }
+
     // Test title methods
     #[test]
     fn test_simulator_title_methods() {
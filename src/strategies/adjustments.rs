@@ -0,0 +1,524 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Strategy Adjustments
+//!
+//! Rule-based defense of open strategies. An [`AdjustmentEngine`] holds a list
+//! of [`AdjustmentRule`]s, each pairing an [`AdjustmentTrigger`] with a
+//! [`DefenseAction`], such as "when the delta of a short strike exceeds 0.35,
+//! roll the untested side". The engine re-prices the legs of a strategy with
+//! the current [`MarketSnapshot`] and emits a [`SuggestedAdjustment`] for every
+//! action whose trigger fires. Nothing is modified: suggestions carry the
+//! priced [`Roll`] or closing price so the caller decides what to execute.
+//!
+//! A short leg is *tested* when the underlying moves against it. Triggers that
+//! look at the legs report which short legs are tested; time-based triggers
+//! consider every short leg tested. The *untested* side is made of the short
+//! legs of the other option style, e.g. the short puts of an iron condor whose
+//! short call is tested.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! let engine = AdjustmentEngine::new().with_rule(AdjustmentRule::new(
+//!     "defend short strike",
+//!     AdjustmentTrigger::ShortDeltaAbove(dec!(0.35)),
+//!     DefenseAction::RollUntested { toward_money: dec!(5), expiration: None },
+//! ));
+//! let suggestions = engine.evaluate(&iron_condor, &MarketSnapshot::new(pos_or_panic!(108.0)))?;
+//! ```
+
+use crate::error::strategies::StrategyError;
+use crate::greeks::delta;
+use crate::model::ExpirationDate;
+use crate::model::Position;
+use crate::model::roll::{Roll, RollTarget, StrikeAdjustment};
+use crate::model::types::{OptionStyle, Side};
+use crate::strategies::base::Strategies;
+use chrono::Utc;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Current market data used to evaluate a strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MarketSnapshot {
+    /// Current price of the underlying.
+    pub underlying_price: Positive,
+    /// Current implied volatility, or `None` to keep the volatility of each leg.
+    pub implied_volatility: Option<Positive>,
+}
+
+impl MarketSnapshot {
+    /// Creates a snapshot with the given underlying price.
+    pub fn new(underlying_price: Positive) -> Self {
+        MarketSnapshot {
+            underlying_price,
+            implied_volatility: None,
+        }
+    }
+
+    /// Sets the implied volatility used to re-price every leg.
+    pub fn with_implied_volatility(mut self, implied_volatility: Positive) -> Self {
+        self.implied_volatility = Some(implied_volatility);
+        self
+    }
+
+    /// Returns a copy of the position priced with this market data.
+    fn apply(&self, position: &Position) -> Position {
+        let mut position = position.clone();
+        position.option.underlying_price = self.underlying_price;
+        if let Some(implied_volatility) = self.implied_volatility {
+            position.option.implied_volatility = implied_volatility;
+        }
+        position
+    }
+}
+
+/// Condition that activates an adjustment rule.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AdjustmentTrigger {
+    /// The absolute delta of one contract of a short leg exceeds the threshold.
+    ShortDeltaAbove(Decimal),
+    /// The underlying trades through the strike of a short leg.
+    ShortStrikeBreached,
+    /// The underlying is within the given percentage of the strike of a short
+    /// leg, or beyond it.
+    WithinPercentOfShortStrike(Positive),
+    /// A leg expires in fewer days than the threshold.
+    DaysToExpirationBelow(Positive),
+}
+
+/// Action suggested when a rule is triggered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DefenseAction {
+    /// Rolls the short legs of the untested side closer to the underlying.
+    RollUntested {
+        /// Strike distance moved toward the underlying.
+        toward_money: Decimal,
+        /// New expiration, or `None` to keep the current one.
+        expiration: Option<ExpirationDate>,
+    },
+    /// Rolls the tested short legs further from the underlying.
+    RollTested {
+        /// Strike distance moved away from the underlying.
+        away_from_money: Decimal,
+        /// New expiration, or `None` to keep the current one.
+        expiration: Option<ExpirationDate>,
+    },
+    /// Closes the tested short legs.
+    CloseTested,
+    /// Closes every leg of the strategy.
+    CloseAll,
+}
+
+/// A named trigger and the action taken when it fires.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdjustmentRule {
+    /// Name reported with the suggestions of the rule.
+    pub name: String,
+    /// Condition that activates the rule.
+    pub trigger: AdjustmentTrigger,
+    /// Action suggested when the rule is active.
+    pub action: DefenseAction,
+}
+
+impl AdjustmentRule {
+    /// Creates a rule.
+    pub fn new(name: &str, trigger: AdjustmentTrigger, action: DefenseAction) -> Self {
+        AdjustmentRule {
+            name: name.to_string(),
+            trigger,
+            action,
+        }
+    }
+}
+
+/// Concrete operation suggested for one leg.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SuggestedAction {
+    /// Roll the leg, priced with the current market data.
+    Roll(Box<Roll>),
+    /// Close the leg at the given theoretical price.
+    Close {
+        /// Theoretical price of one contract of the leg.
+        close_price: Positive,
+    },
+}
+
+/// Adjustment emitted by the engine for one leg of the strategy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuggestedAdjustment {
+    /// Name of the rule that produced the suggestion.
+    pub rule: String,
+    /// Index of the leg in the positions of the strategy.
+    pub leg_index: usize,
+    /// Why the rule was triggered.
+    pub reason: String,
+    /// Operation to perform on the leg.
+    pub action: SuggestedAction,
+}
+
+/// Evaluates adjustment rules against strategies.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AdjustmentEngine {
+    /// Registered rules, evaluated in order.
+    pub rules: Vec<AdjustmentRule>,
+}
+
+impl AdjustmentEngine {
+    /// Creates an engine without rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a rule.
+    pub fn add_rule(&mut self, rule: AdjustmentRule) {
+        self.rules.push(rule);
+    }
+
+    /// Registers a rule and returns the engine.
+    pub fn with_rule(mut self, rule: AdjustmentRule) -> Self {
+        self.add_rule(rule);
+        self
+    }
+
+    /// Evaluates every rule against the strategy at the given market data.
+    ///
+    /// # Returns
+    ///
+    /// The suggestions of the triggered rules, in the order of the rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if a leg cannot be priced or rolled.
+    pub fn evaluate<S: Strategies + ?Sized>(
+        &self,
+        strategy: &S,
+        market: &MarketSnapshot,
+    ) -> Result<Vec<SuggestedAdjustment>, StrategyError> {
+        let legs: Vec<Position> = strategy
+            .get_positions()?
+            .into_iter()
+            .map(|position| market.apply(position))
+            .collect();
+
+        let mut suggestions = Vec::new();
+        for rule in &self.rules {
+            let tested = tested_legs(&rule.trigger, &legs, market)?;
+            if tested.is_empty() {
+                continue;
+            }
+            let reason = |index: usize| match &rule.trigger {
+                AdjustmentTrigger::DaysToExpirationBelow(days) => {
+                    format!("a leg expires in fewer than {days} days")
+                }
+                trigger => format!(
+                    "short {:?} at {} triggered {:?}",
+                    legs[index].option.option_style, legs[index].option.strike_price, trigger
+                ),
+            };
+
+            match &rule.action {
+                DefenseAction::RollUntested {
+                    toward_money,
+                    expiration,
+                } => {
+                    for index in untested_legs(&tested, &legs) {
+                        let shift = toward_underlying(&legs[index], market, *toward_money);
+                        suggestions.push(SuggestedAdjustment {
+                            rule: rule.name.clone(),
+                            leg_index: index,
+                            reason: reason(tested[0]),
+                            action: roll(&legs[index], shift, expiration)?,
+                        });
+                    }
+                }
+                DefenseAction::RollTested {
+                    away_from_money,
+                    expiration,
+                } => {
+                    for &index in &tested {
+                        let shift = -toward_underlying(&legs[index], market, *away_from_money);
+                        suggestions.push(SuggestedAdjustment {
+                            rule: rule.name.clone(),
+                            leg_index: index,
+                            reason: reason(index),
+                            action: roll(&legs[index], shift, expiration)?,
+                        });
+                    }
+                }
+                DefenseAction::CloseTested => {
+                    for &index in &tested {
+                        suggestions.push(SuggestedAdjustment {
+                            rule: rule.name.clone(),
+                            leg_index: index,
+                            reason: reason(index),
+                            action: close(&legs[index])?,
+                        });
+                    }
+                }
+                DefenseAction::CloseAll => {
+                    for (index, leg) in legs.iter().enumerate() {
+                        suggestions.push(SuggestedAdjustment {
+                            rule: rule.name.clone(),
+                            leg_index: index,
+                            reason: reason(tested[0]),
+                            action: close(leg)?,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(suggestions)
+    }
+}
+
+/// Indices of the short legs tested according to the trigger.
+fn tested_legs(
+    trigger: &AdjustmentTrigger,
+    legs: &[Position],
+    market: &MarketSnapshot,
+) -> Result<Vec<usize>, StrategyError> {
+    let price = market.underlying_price.to_dec();
+    let mut tested = Vec::new();
+    for (index, leg) in legs.iter().enumerate() {
+        if leg.option.side != Side::Short {
+            continue;
+        }
+        let strike = leg.option.strike_price.to_dec();
+        let is_tested = match trigger {
+            AdjustmentTrigger::ShortDeltaAbove(threshold) => {
+                let delta_per_contract = delta(&leg.option)? / leg.option.quantity.to_dec();
+                delta_per_contract.abs() > *threshold
+            }
+            AdjustmentTrigger::ShortStrikeBreached => match leg.option.option_style {
+                OptionStyle::Call => price > strike,
+                OptionStyle::Put => price < strike,
+            },
+            AdjustmentTrigger::WithinPercentOfShortStrike(percent) => {
+                let distance = strike * percent.to_dec() / Decimal::ONE_HUNDRED;
+                match leg.option.option_style {
+                    OptionStyle::Call => price >= strike - distance,
+                    OptionStyle::Put => price <= strike + distance,
+                }
+            }
+            AdjustmentTrigger::DaysToExpirationBelow(threshold) => {
+                let days = leg.option.expiration_date.get_days().map_err(|e| {
+                    StrategyError::invalid_parameters("evaluate_adjustments", &e.to_string())
+                })?;
+                days < *threshold
+            }
+        };
+        if is_tested {
+            tested.push(index);
+        }
+    }
+    if matches!(trigger, AdjustmentTrigger::DaysToExpirationBelow(_)) && !tested.is_empty() {
+        tested = short_legs(legs).collect();
+    }
+    Ok(tested)
+}
+
+fn short_legs(legs: &[Position]) -> impl Iterator<Item = usize> + '_ {
+    legs.iter()
+        .enumerate()
+        .filter(|(_, leg)| leg.option.side == Side::Short)
+        .map(|(index, _)| index)
+}
+
+/// Short legs of the other option style than the tested legs.
+fn untested_legs(tested: &[usize], legs: &[Position]) -> Vec<usize> {
+    short_legs(legs)
+        .filter(|index| !tested.contains(index))
+        .filter(|index| {
+            tested
+                .iter()
+                .all(|t| legs[*t].option.option_style != legs[*index].option.option_style)
+        })
+        .collect()
+}
+
+/// Signed strike shift that moves the leg `amount` closer to the underlying.
+fn toward_underlying(leg: &Position, market: &MarketSnapshot, amount: Decimal) -> Decimal {
+    if leg.option.strike_price > market.underlying_price {
+        -amount
+    } else {
+        amount
+    }
+}
+
+fn roll(
+    leg: &Position,
+    shift: Decimal,
+    expiration: &Option<ExpirationDate>,
+) -> Result<SuggestedAction, StrategyError> {
+    let target = RollTarget {
+        strike: StrikeAdjustment::Shift(shift),
+        expiration: *expiration,
+    };
+    let mut replacement = leg.option.clone();
+    replacement.strike_price = target.strike_for(leg.option.strike_price)?;
+    if let Some(expiration) = target.expiration {
+        replacement.expiration_date = expiration;
+    }
+    let close_price = leg.option.calculate_price_black_scholes()?.abs();
+    let open_price = replacement.calculate_price_black_scholes()?.abs();
+    let roll = leg.roll(
+        &target,
+        Positive::new_decimal(close_price)?,
+        Positive::new_decimal(open_price)?,
+        Utc::now(),
+    )?;
+    Ok(SuggestedAction::Roll(Box::new(roll)))
+}
+
+fn close(leg: &Position) -> Result<SuggestedAction, StrategyError> {
+    let close_price = leg.option.calculate_price_black_scholes()?.abs();
+    Ok(SuggestedAction::Close {
+        close_price: Positive::new_decimal(close_price)?,
+    })
+}
+
+#[cfg(test)]
+mod tests_adjustments {
+    use super::*;
+    use crate::model::roll::RollKind;
+    use crate::strategies::IronCondor;
+    use crate::strategies::base::Positionable;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn iron_condor() -> IronCondor {
+        IronCondor::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(110.0),
+            pos_or_panic!(90.0),
+            pos_or_panic!(120.0),
+            pos_or_panic!(80.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.0),
+            pos_or_panic!(2.0),
+            pos_or_panic!(0.5),
+            pos_or_panic!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn leg_index(strategy: &IronCondor, side: Side, style: OptionStyle) -> usize {
+        strategy
+            .get_positions()
+            .unwrap()
+            .iter()
+            .position(|p| p.option.side == side && p.option.option_style == style)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_roll_untested_put_when_short_call_delta_rises() {
+        let strategy = iron_condor();
+        let engine = AdjustmentEngine::new().with_rule(AdjustmentRule::new(
+            "defend calls",
+            AdjustmentTrigger::ShortDeltaAbove(dec!(0.35)),
+            DefenseAction::RollUntested {
+                toward_money: dec!(5),
+                expiration: None,
+            },
+        ));
+
+        let calm = engine
+            .evaluate(&strategy, &MarketSnapshot::new(Positive::HUNDRED))
+            .unwrap();
+        assert!(calm.is_empty());
+
+        let suggestions = engine
+            .evaluate(&strategy, &MarketSnapshot::new(pos_or_panic!(108.0)))
+            .unwrap();
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.rule, "defend calls");
+        assert_eq!(
+            suggestion.leg_index,
+            leg_index(&strategy, Side::Short, OptionStyle::Put)
+        );
+        let SuggestedAction::Roll(roll) = &suggestion.action else {
+            panic!("expected a roll");
+        };
+        assert_eq!(roll.kind, RollKind::Up);
+        assert_eq!(roll.replacement.option.strike_price, pos_or_panic!(95.0));
+        assert_eq!(
+            roll.replacement.option.underlying_price,
+            pos_or_panic!(108.0)
+        );
+        // Rolling a short put closer to the money collects more premium.
+        assert!(roll.is_credit());
+    }
+
+    #[test]
+    fn test_close_tested_on_breach() {
+        let strategy = iron_condor();
+        let engine = AdjustmentEngine::new().with_rule(AdjustmentRule::new(
+            "stop",
+            AdjustmentTrigger::ShortStrikeBreached,
+            DefenseAction::CloseTested,
+        ));
+        let suggestions = engine
+            .evaluate(
+                &strategy,
+                &MarketSnapshot::new(pos_or_panic!(85.0))
+                    .with_implied_volatility(pos_or_panic!(0.3)),
+            )
+            .unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(
+            suggestions[0].leg_index,
+            leg_index(&strategy, Side::Short, OptionStyle::Put)
+        );
+        let SuggestedAction::Close { close_price } = suggestions[0].action else {
+            panic!("expected a close");
+        };
+        assert!(close_price > pos_or_panic!(5.0));
+    }
+
+    #[test]
+    fn test_roll_tested_and_time_rules() {
+        let strategy = iron_condor();
+        let engine = AdjustmentEngine::new()
+            .with_rule(AdjustmentRule::new(
+                "roll away",
+                AdjustmentTrigger::WithinPercentOfShortStrike(pos_or_panic!(5.0)),
+                DefenseAction::RollTested {
+                    away_from_money: dec!(5),
+                    expiration: Some(ExpirationDate::Days(pos_or_panic!(60.0))),
+                },
+            ))
+            .with_rule(AdjustmentRule::new(
+                "expiry",
+                AdjustmentTrigger::DaysToExpirationBelow(pos_or_panic!(45.0)),
+                DefenseAction::CloseAll,
+            ));
+        let suggestions = engine
+            .evaluate(&strategy, &MarketSnapshot::new(pos_or_panic!(106.0)))
+            .unwrap();
+
+        assert_eq!(suggestions[0].rule, "roll away");
+        let SuggestedAction::Roll(roll) = &suggestions[0].action else {
+            panic!("expected a roll");
+        };
+        assert_eq!(roll.kind, RollKind::OutAndUp);
+        assert_eq!(roll.replacement.option.strike_price, pos_or_panic!(115.0));
+
+        let closes: Vec<_> = suggestions.iter().filter(|s| s.rule == "expiry").collect();
+        assert_eq!(closes.len(), 4);
+        assert!(closes[0].reason.contains("45"));
+    }
+}
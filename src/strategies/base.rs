@@ -4,21 +4,29 @@ use crate::error::strategies::BreakEvenErrorKind;
 use crate::{
     ExpirationDate, Options,
     chains::{StrategyLegs, chain::OptionChain, utils::OptionDataGroup},
-    error::{OperationErrorKind, position::PositionError, strategies::StrategyError},
+    error::{OperationErrorKind, PricingError, position::PositionError, strategies::StrategyError},
     greeks::Greeks,
     model::{
-        Trade,
+        QuotePricing, Trade,
         position::Position,
+        roll::{Roll, RollTarget},
         types::{Action, OptionBasicType, OptionStyle, OptionType, Side},
     },
     pnl::PnLCalculator,
     pricing::payoff::Profit,
+    risk::RegTMargin,
     strategies::{
         StrategyConstructor,
         delta_neutral::DeltaNeutrality,
+        optimizer::{
+            ExpectedValueObjective, OptimizationConstraints, StrikeCandidate,
+            top_by_expected_value, top_by_expected_value_with_constraints,
+            top_by_return_on_capital,
+        },
         probabilities::core::ProbabilityAnalysis,
         utils::{FindOptimalSide, OptimizationCriteria, calculate_price_range},
     },
+    utils::calendar::{ExpiryCycle, HolidayCalendar, next_expiration},
     visualization::Graph,
 };
 use positive::Positive;
@@ -156,6 +164,10 @@ pub enum StrategyType {
     PoorMansCoveredCall,
     /// Call Butterfly strategy.
     CallButterfly,
+    /// Calendar Spread strategy.
+    CalendarSpread,
+    /// Diagonal Spread strategy.
+    DiagonalSpread,
     /// Custom strategy.
     Custom,
 }
@@ -186,6 +198,8 @@ impl FromStr for StrategyType {
             "ShortPut" => Ok(StrategyType::ShortPut),
             "PoorMansCoveredCall" => Ok(StrategyType::PoorMansCoveredCall),
             "CallButterfly" => Ok(StrategyType::CallButterfly),
+            "CalendarSpread" => Ok(StrategyType::CalendarSpread),
+            "DiagonalSpread" => Ok(StrategyType::DiagonalSpread),
             "Custom" => Ok(StrategyType::Custom),
             _ => Err(()),
         }
@@ -240,6 +254,7 @@ impl fmt::Display for StrategyType {
 /// This structure serves as the foundation for strategy analysis, visualization,
 /// and trading execution within the options trading framework.
 ///
+#[derive(Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct Strategy {
     /// The name of the strategy, which identifies it among other strategies.
     pub name: String,
@@ -346,6 +361,16 @@ impl Strategy {
     }
 }
 
+impl Profit for Strategy {
+    /// Sums the P&L at expiration of every leg at `price`.
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        self.legs
+            .iter()
+            .map(|leg| leg.calculate_profit_at(price))
+            .sum()
+    }
+}
+
 /// A trait that defines basic operations and attributes for managing options-related strategies.
 ///
 /// This trait provides methods to retrieve various properties and mappings
@@ -884,6 +909,74 @@ pub trait Strategies: Validable + Positionable + BreakEvenable + BasicAble {
         }
     }
 
+    /// Calculates the buying power consumed by opening the strategy under the
+    /// default Reg-T rules: the margin requirement of its legs less the
+    /// premium received that can be applied to it.
+    ///
+    /// # Returns
+    /// * `Ok(Positive)` - The buying power effect.
+    /// * `Err(StrategyError)` - If there is an error retrieving the positions.
+    fn buying_power_effect(&self) -> Result<Positive, StrategyError> {
+        self.buying_power_effect_with(&RegTMargin::default())
+    }
+
+    /// Calculates the buying power consumed by opening the strategy under the
+    /// rules of the given Reg-T calculator.
+    ///
+    /// # Returns
+    /// * `Ok(Positive)` - The buying power effect.
+    /// * `Err(StrategyError)` - If there is an error retrieving the positions.
+    fn buying_power_effect_with(&self, margin: &RegTMargin) -> Result<Positive, StrategyError> {
+        let positions = self.get_positions()?;
+        let report = margin.calculate(&positions, &[]);
+        Ok(Positive::new_decimal(report.buying_power_effect())?)
+    }
+
+    /// Returns the earliest expiration among the legs of the strategy.
+    ///
+    /// For strategies whose legs share an expiration this is simply that
+    /// expiration; for calendar and diagonal spreads it is the near leg's.
+    ///
+    /// # Returns
+    /// * `Ok(ExpirationDate)` - The nearest expiration.
+    /// * `Err(StrategyError)` - If the strategy has no positions or a date cannot be resolved.
+    fn get_nearest_expiration(&self) -> Result<ExpirationDate, StrategyError> {
+        let mut nearest: Option<(Positive, ExpirationDate)> = None;
+        for position in self.get_positions()? {
+            let expiration = position.option.expiration_date;
+            let days = expiration.get_days().map_err(|e| {
+                StrategyError::operation_not_supported(&e.to_string(), "expiration")
+            })?;
+            if nearest.is_none_or(|(nearest_days, _)| days < nearest_days) {
+                nearest = Some((days, expiration));
+            }
+        }
+        nearest.map(|(_, expiration)| expiration).ok_or_else(|| {
+            StrategyError::operation_not_supported("nearest_expiration", "empty strategy")
+        })
+    }
+
+    /// Calculates the profit or loss of the strategy at its nearest expiration.
+    ///
+    /// Legs expiring at the horizon settle at intrinsic value, while legs
+    /// expiring later are marked with Black-Scholes using their remaining time.
+    /// When every leg shares one expiration this equals the profit at expiration.
+    ///
+    /// # Parameters
+    /// * `price` - The underlying price at the nearest expiration.
+    ///
+    /// # Returns
+    /// * `Ok(Decimal)` - The profit or loss.
+    /// * `Err(StrategyError)` - If any leg cannot be valued.
+    fn calculate_profit_at_horizon(&self, price: &Positive) -> Result<Decimal, StrategyError> {
+        let horizon = self.get_nearest_expiration()?;
+        let mut profit = Decimal::ZERO;
+        for position in self.get_positions()? {
+            profit += position.pnl_at_horizon(price, &horizon)?;
+        }
+        Ok(profit)
+    }
+
     /// Calculates the total fees for the strategy by summing the fees of all positions.
     ///
     /// # Returns
@@ -908,6 +1001,60 @@ pub trait Strategies: Validable + Positionable + BreakEvenable + BasicAble {
         Ok(fee)
     }
 
+    /// Calculates the net premium of opening the strategy at the quotes of `chain`.
+    ///
+    /// Each leg is priced with `pricing` (mid, natural or mid plus slippage)
+    /// instead of the stored `premium`. Positive values are net credits,
+    /// negative values net debits. Quantities and opening fees are included.
+    ///
+    /// # Returns
+    /// * `Ok(Decimal)` - The net premium at the chosen pricing.
+    /// * `Err(StrategyError)` - If positions cannot be retrieved or a leg is not quoted in `chain`.
+    fn get_net_premium_with_pricing(
+        &self,
+        chain: &OptionChain,
+        pricing: QuotePricing,
+    ) -> Result<Decimal, StrategyError> {
+        let mut net = Decimal::ZERO;
+        for position in self.get_positions()? {
+            let price = quoted_leg_price(chain, position, pricing, true)?;
+            let amount = price.to_dec() * position.option.quantity.to_dec();
+            net += match position.option.side {
+                Side::Long => -amount,
+                Side::Short => amount,
+            };
+            net -= position.open_fee.to_dec() * position.option.quantity.to_dec();
+        }
+        Ok(net)
+    }
+
+    /// Calculates the net cost of closing the strategy at the quotes of `chain`.
+    ///
+    /// Long legs are sold and short legs bought back, each priced with
+    /// `pricing`. Positive values are net amounts paid, negative values net
+    /// amounts received. Quantities and closing fees are included.
+    ///
+    /// # Returns
+    /// * `Ok(Decimal)` - The net closing cost at the chosen pricing.
+    /// * `Err(StrategyError)` - If positions cannot be retrieved or a leg is not quoted in `chain`.
+    fn get_close_cost_with_pricing(
+        &self,
+        chain: &OptionChain,
+        pricing: QuotePricing,
+    ) -> Result<Decimal, StrategyError> {
+        let mut net = Decimal::ZERO;
+        for position in self.get_positions()? {
+            let price = quoted_leg_price(chain, position, pricing, false)?;
+            let amount = price.to_dec() * position.option.quantity.to_dec();
+            net += match position.option.side {
+                Side::Long => -amount,
+                Side::Short => amount,
+            };
+            net += position.close_fee.to_dec() * position.option.quantity.to_dec();
+        }
+        Ok(net)
+    }
+
     /// Calculates the profit area for the strategy. The default implementation returns an error
     /// indicating that the operation is not supported.
     ///
@@ -1103,6 +1250,88 @@ pub trait Strategies: Validable + Positionable + BreakEvenable + BasicAble {
     fn roll_out(&mut self, _position: &Position) -> Result<HashMap<Action, Trade>, StrategyError> {
         unimplemented!("roll_out is not implemented for this strategy")
     }
+
+    /// Rolls every leg of the strategy to the same target at theoretical prices.
+    ///
+    /// Each leg is closed and reopened at its Black-Scholes value, so the
+    /// result estimates the net credit or debit of rolling the whole strategy
+    /// before quotes are available. The strategy itself is not modified.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - New strike and/or expiration applied to every leg
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Roll>)` - One roll per leg, with its linked trades and replacement leg
+    /// * `Err(StrategyError)` - If a leg cannot be priced or rolled
+    fn roll_legs(&self, target: &RollTarget) -> Result<Vec<Roll>, StrategyError> {
+        let now = chrono::Utc::now();
+        self.get_positions()?
+            .into_iter()
+            .map(|position| {
+                let mut replacement = position.option.clone();
+                replacement.strike_price = target.strike_for(position.option.strike_price)?;
+                if let Some(expiration) = target.expiration {
+                    replacement.expiration_date = expiration;
+                }
+                let close_price = position.option.calculate_price_black_scholes()?.abs();
+                let open_price = replacement.calculate_price_black_scholes()?.abs();
+                Ok(position.roll(
+                    target,
+                    Positive::new_decimal(close_price)?,
+                    Positive::new_decimal(open_price)?,
+                    now,
+                )?)
+            })
+            .collect()
+    }
+
+    /// Next expiration of `cycle` with at least `min_days` calendar days left,
+    /// at the close of the expiration date.
+    ///
+    /// # Arguments
+    ///
+    /// * `cycle` - Expiration cycle to roll into, such as monthly
+    /// * `min_days` - Minimum calendar days to expiration of the target
+    /// * `calendar` - Holiday calendar and trading session of the exchange
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if no expiration of the cycle is listed.
+    fn roll_target_expiration(
+        &self,
+        cycle: ExpiryCycle,
+        min_days: u32,
+        calendar: &dyn HolidayCalendar,
+    ) -> Result<ExpirationDate, StrategyError> {
+        let today = chrono::Utc::now().date_naive();
+        next_expiration(today, cycle, min_days, calendar)
+            .map(|expiration| calendar.expiration_at_close(expiration.date))
+            .ok_or_else(|| {
+                StrategyError::invalid_parameters(
+                    "roll_target_expiration",
+                    &format!("no {cycle:?} expiration with {min_days} days or more"),
+                )
+            })
+    }
+
+    /// Rolls every leg of the strategy out to the next expiration of `cycle`
+    /// with at least `min_days` left, keeping the strikes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Roll>)` - One roll per leg, with the replacement legs at the new expiration
+    /// * `Err(StrategyError)` - If no target expiration is listed or a leg cannot be rolled
+    fn roll_to_next_cycle(
+        &self,
+        cycle: ExpiryCycle,
+        min_days: u32,
+        calendar: &dyn HolidayCalendar,
+    ) -> Result<Vec<Roll>, StrategyError> {
+        let expiration = self.roll_target_expiration(cycle, min_days, calendar)?;
+        self.roll_legs(&RollTarget::out(expiration))
+    }
 }
 
 /// Trait for strategies that can calculate and update break-even points.
@@ -1232,6 +1461,90 @@ pub trait Optimizable: Validable + Strategies {
         panic!("Find optimal is not applicable for this strategy");
     }
 
+    /// Returns the `top_n` strike combinations with the highest expected value.
+    ///
+    /// Combinations are enumerated with [`Optimizable::filter_combinations`] and
+    /// built with [`Optimizable::create_strategy`]; the strategy itself is left
+    /// unchanged. See [`top_by_expected_value`](crate::strategies::optimizer::top_by_expected_value).
+    ///
+    /// # Arguments
+    /// * `option_chain` - A reference to the `OptionChain` containing option data.
+    /// * `side` - A `FindOptimalSide` value specifying the filtering strategy.
+    /// * `objective` - The terminal distribution used to compute the expected value.
+    /// * `top_n` - The maximum number of candidates returned.
+    fn top_by_expected_value(
+        &self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        objective: &ExpectedValueObjective,
+        top_n: usize,
+    ) -> Vec<StrikeCandidate<Self::Strategy>>
+    where
+        Self: Sized,
+        Self::Strategy: Profit + Send,
+    {
+        top_by_expected_value(self, option_chain, side, objective, top_n)
+    }
+
+    /// Returns the `top_n` strike combinations with the highest expected value
+    /// among those satisfying the constraints.
+    ///
+    /// See [`top_by_expected_value_with_constraints`](crate::strategies::optimizer::top_by_expected_value_with_constraints).
+    ///
+    /// # Arguments
+    /// * `option_chain` - A reference to the `OptionChain` containing option data.
+    /// * `side` - A `FindOptimalSide` value specifying the filtering strategy.
+    /// * `objective` - The terminal distribution used to compute the expected value.
+    /// * `constraints` - Limits on maximum loss, probability of profit and margin.
+    /// * `top_n` - The maximum number of candidates returned.
+    fn top_by_expected_value_with_constraints(
+        &self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        objective: &ExpectedValueObjective,
+        constraints: &OptimizationConstraints,
+        top_n: usize,
+    ) -> Vec<StrikeCandidate<Self::Strategy>>
+    where
+        Self: Sized,
+        Self::Strategy: Profit + Send,
+    {
+        top_by_expected_value_with_constraints(
+            self,
+            option_chain,
+            side,
+            objective,
+            constraints,
+            top_n,
+        )
+    }
+
+    /// Returns the `top_n` strike combinations with the highest return on
+    /// capital among those satisfying the constraints.
+    ///
+    /// See [`top_by_return_on_capital`](crate::strategies::optimizer::top_by_return_on_capital).
+    ///
+    /// # Arguments
+    /// * `option_chain` - A reference to the `OptionChain` containing option data.
+    /// * `side` - A `FindOptimalSide` value specifying the filtering strategy.
+    /// * `objective` - The terminal distribution used to compute the expected value.
+    /// * `constraints` - Limits on maximum loss, probability of profit and margin.
+    /// * `top_n` - The maximum number of candidates returned.
+    fn top_by_return_on_capital(
+        &self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        objective: &ExpectedValueObjective,
+        constraints: &OptimizationConstraints,
+        top_n: usize,
+    ) -> Vec<StrikeCandidate<Self::Strategy>>
+    where
+        Self: Sized,
+        Self::Strategy: Profit + Send,
+    {
+        top_by_return_on_capital(self, option_chain, side, objective, constraints, top_n)
+    }
+
     /// Checks if a long option is valid based on the given criteria.
     ///
     /// # Arguments
@@ -1480,6 +1793,36 @@ pub trait Positionable {
     }
 }
 
+/// Entry (`entry = true`) or exit price of a position's leg at the quotes of `chain`.
+fn quoted_leg_price(
+    chain: &OptionChain,
+    position: &Position,
+    pricing: QuotePricing,
+    entry: bool,
+) -> Result<Positive, StrategyError> {
+    let option = &position.option;
+    let not_quoted = |reason: String| {
+        StrategyError::OperationError(OperationErrorKind::InvalidParameters {
+            operation: "quoted_leg_price".to_string(),
+            reason,
+        })
+    };
+    let option_data = chain
+        .get_optiondata_with_strike(&option.strike_price)
+        .map_err(|e| not_quoted(e.to_string()))?;
+    let price = if entry {
+        option_data.get_entry_price(option.side, option.option_style, pricing)
+    } else {
+        option_data.get_exit_price(option.side, option.option_style, pricing)
+    };
+    price.ok_or_else(|| {
+        not_quoted(format!(
+            "No bid/ask for {:?} strike {}",
+            option.option_style, option.strike_price
+        ))
+    })
+}
+
 #[cfg(test)]
 mod tests_strategies_extended {
     use super::*;
@@ -2316,4 +2659,134 @@ mod tests_strategy_net_operations {
         let result = strategy.get_fees().unwrap();
         assert!(result > Positive::ZERO);
     }
+
+    fn quoted_chain() -> OptionChain {
+        let mut chain = OptionChain::new(
+            "AAPL",
+            Positive::HUNDRED,
+            "2030-01-18".to_string(),
+            None,
+            None,
+        );
+        chain.add_option(
+            Positive::HUNDRED,
+            Some(pos_or_panic!(4.0)),
+            Some(pos_or_panic!(4.4)),
+            Some(pos_or_panic!(3.0)),
+            Some(pos_or_panic!(3.2)),
+            pos_or_panic!(0.2),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        chain
+    }
+
+    fn quoted_strategy() -> TestStrategy {
+        let mut strategy = TestStrategy::new();
+        for (style, side) in [
+            (OptionStyle::Call, Side::Long),
+            (OptionStyle::Put, Side::Short),
+        ] {
+            let position = Position::new(
+                create_sample_option_simplest(style, side),
+                Positive::ONE,
+                Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+                Positive::ZERO,
+                pos_or_panic!(0.1),
+                None,
+                None,
+            );
+            strategy.add_position(&position).unwrap();
+        }
+        strategy
+    }
+
+    #[test]
+    fn test_net_premium_with_pricing() {
+        let strategy = quoted_strategy();
+        let chain = quoted_chain();
+        let mid = strategy
+            .get_net_premium_with_pricing(&chain, QuotePricing::Mid)
+            .unwrap();
+        let natural = strategy
+            .get_net_premium_with_pricing(&chain, QuotePricing::Natural)
+            .unwrap();
+        let slipped = strategy
+            .get_net_premium_with_pricing(
+                &chain,
+                QuotePricing::Slippage {
+                    fraction: Decimal::new(25, 2),
+                },
+            )
+            .unwrap();
+        // Buy call at 4.2 mid / 4.4 ask, sell put at 3.1 mid / 3.0 bid.
+        assert_eq!(mid, Decimal::new(-11, 1));
+        assert_eq!(natural, Decimal::new(-14, 1));
+        assert!(slipped < mid && slipped > natural);
+    }
+
+    #[test]
+    fn test_close_cost_with_pricing() {
+        let strategy = quoted_strategy();
+        let chain = quoted_chain();
+        // Sell call at 4.0 bid, buy back put at 3.2 ask, plus 0.1 closing fee per leg.
+        let natural = strategy
+            .get_close_cost_with_pricing(&chain, QuotePricing::Natural)
+            .unwrap();
+        assert_eq!(natural, Decimal::new(-6, 1));
+    }
+
+    #[test]
+    fn test_pricing_requires_quotes() {
+        let strategy = quoted_strategy();
+        let empty = OptionChain::new(
+            "AAPL",
+            Positive::HUNDRED,
+            "2030-01-18".to_string(),
+            None,
+            None,
+        );
+        assert!(
+            strategy
+                .get_net_premium_with_pricing(&empty, QuotePricing::Mid)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_roll_to_next_cycle() {
+        let mut strategy = TestStrategy::new();
+        let option = create_sample_option_simplest(OptionStyle::Call, Side::Short);
+        let position = Position::new(
+            option,
+            Positive::ONE,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        );
+        strategy.add_position(&position).unwrap();
+
+        let calendar = crate::utils::calendar::MarketCalendar::Nyse;
+        let target = strategy
+            .roll_target_expiration(ExpiryCycle::Monthly, 30, &calendar)
+            .unwrap();
+        let days = target.get_days().unwrap();
+        assert!(days >= pos_or_panic!(29.0) && days <= pos_or_panic!(65.0));
+
+        let rolls = strategy
+            .roll_to_next_cycle(ExpiryCycle::Monthly, 30, &calendar)
+            .unwrap();
+        assert_eq!(rolls.len(), 1);
+        assert_eq!(rolls[0].replacement.option.expiration_date, target);
+        assert_eq!(
+            rolls[0].replacement.option.strike_price,
+            position.option.strike_price
+        );
+    }
 }
@@ -0,0 +1,406 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Break-Even Solver
+//!
+//! Locates the break-even prices of any type implementing [`Profit`] by
+//! root-finding on its payoff instead of deriving them by hand for every
+//! strategy.
+//!
+//! The payoff is evaluated on a grid that always contains the strikes of the
+//! legs, so every kink of a piecewise-linear payoff falls on a grid point.
+//! Each sign change between consecutive grid points brackets a break-even
+//! price, which is then refined with Brent's method to the configured
+//! precision.
+
+use crate::error::PricingError;
+use crate::pricing::payoff::Profit;
+use num_traits::{FromPrimitive, ToPrimitive};
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// Default number of grid intervals scanned for sign changes.
+pub const DEFAULT_BREAK_EVEN_SCAN_STEPS: usize = 2000;
+
+/// Default number of Brent iterations used to refine each break-even price.
+pub const DEFAULT_BREAK_EVEN_MAX_ITERATIONS: usize = 100;
+
+/// Default precision of the break-even prices.
+pub const DEFAULT_BREAK_EVEN_TOLERANCE: Decimal = dec!(0.000001);
+
+/// Finds a root of `f` in the bracket `[low, high]` with Brent's method.
+///
+/// Combines bisection, the secant method and inverse quadratic
+/// interpolation, so it converges superlinearly on smooth functions while
+/// keeping the guaranteed convergence of bisection. The bracket must contain
+/// a sign change; if it does not, the end point closest to zero is returned.
+///
+/// # Parameters
+///
+/// * `f` - Fallible function whose root is searched
+/// * `low`, `high` - Bracket of the root
+/// * `tolerance` - Width of the final bracket
+/// * `max_iterations` - Maximum number of iterations
+///
+/// # Returns
+///
+/// The best estimate of the root after convergence or after `max_iterations`.
+///
+/// # Errors
+///
+/// Propagates the first error returned by `f`.
+pub fn brent<E, F>(
+    mut f: F,
+    low: f64,
+    high: f64,
+    tolerance: f64,
+    max_iterations: usize,
+) -> Result<f64, E>
+where
+    F: FnMut(f64) -> Result<f64, E>,
+{
+    let (mut a, mut b) = (low, high);
+    let (mut fa, mut fb) = (f(a)?, f(b)?);
+    if fa == 0.0 {
+        return Ok(a);
+    }
+    if fb == 0.0 {
+        return Ok(b);
+    }
+    if fa.signum() == fb.signum() {
+        return Ok(if fa.abs() < fb.abs() { a } else { b });
+    }
+
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+    for _ in 0..max_iterations {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tol = 2.0 * f64::EPSILON * b.abs() + tolerance / 2.0;
+        let m = (c - b) / 2.0;
+        if m.abs() <= tol || fb == 0.0 {
+            return Ok(b);
+        }
+
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            // Secant step when only two points are distinct, inverse
+            // quadratic interpolation otherwise.
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        fb = f(b)?;
+    }
+    Ok(b)
+}
+
+/// Locates the break-even prices of a payoff with a grid scan and Brent's method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreakEvenSolver {
+    /// Precision of the break-even prices.
+    pub tolerance: Decimal,
+    /// Maximum number of Brent iterations for each break-even price.
+    pub max_iterations: usize,
+    /// Number of grid intervals scanned for sign changes of the payoff.
+    pub scan_steps: usize,
+}
+
+impl Default for BreakEvenSolver {
+    fn default() -> Self {
+        BreakEvenSolver {
+            tolerance: DEFAULT_BREAK_EVEN_TOLERANCE,
+            max_iterations: DEFAULT_BREAK_EVEN_MAX_ITERATIONS,
+            scan_steps: DEFAULT_BREAK_EVEN_SCAN_STEPS,
+        }
+    }
+}
+
+impl BreakEvenSolver {
+    /// Creates a solver with the default precision and grid.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the precision of the break-even prices.
+    pub fn with_tolerance(mut self, tolerance: Decimal) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of Brent iterations for each break-even price.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the number of grid intervals scanned for sign changes.
+    pub fn with_scan_steps(mut self, scan_steps: usize) -> Self {
+        self.scan_steps = scan_steps.max(1);
+        self
+    }
+
+    /// Refines the break-even price of `payoff` inside the bracket `[low, high]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the payoff cannot be evaluated.
+    pub fn refine<P: Profit + ?Sized>(
+        &self,
+        payoff: &P,
+        low: Positive,
+        high: Positive,
+    ) -> Result<Positive, PricingError> {
+        let root = brent(
+            |price| -> Result<f64, PricingError> {
+                let price = Positive::new(price.max(0.0))?;
+                Ok(payoff.calculate_profit_at(&price)?.to_f64().unwrap_or(0.0))
+            },
+            low.to_f64(),
+            high.to_f64(),
+            self.tolerance.to_f64().unwrap_or(0.0),
+            self.max_iterations,
+        )?;
+        let root = Decimal::from_f64(root.max(0.0)).unwrap_or(Decimal::ZERO);
+        Ok(Positive::new_decimal(
+            root.round_dp(self.tolerance.scale()),
+        )?)
+    }
+
+    /// Finds every break-even price of `payoff` in `[low, high]`.
+    ///
+    /// The grid of `scan_steps` intervals is completed with the `knots`
+    /// inside the range, typically the strikes of the legs. A grid point where
+    /// the payoff is exactly zero between a gain and a loss is a break-even
+    /// price; a payoff that only touches zero is not.
+    ///
+    /// # Returns
+    ///
+    /// The break-even prices in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the payoff cannot be evaluated.
+    pub fn solve<P: Profit + ?Sized>(
+        &self,
+        payoff: &P,
+        low: Positive,
+        high: Positive,
+        knots: &[Positive],
+    ) -> Result<Vec<Positive>, PricingError> {
+        if high <= low {
+            return Ok(Vec::new());
+        }
+        let step = (high - low) / self.scan_steps as f64;
+        let mut prices: Vec<Positive> = (0..self.scan_steps)
+            .map(|i| low + step * i as f64)
+            .collect();
+        prices.push(high);
+        prices.extend(knots.iter().copied().filter(|k| *k > low && *k < high));
+        prices.sort();
+        prices.dedup();
+
+        let mut roots = Vec::new();
+        // Last grid point with a non-zero payoff, and whether it was a gain.
+        let mut last_signed: Option<(Positive, bool)> = None;
+        for price in prices {
+            let profit = payoff.calculate_profit_at(&price)?;
+            if profit.is_zero() {
+                continue;
+            }
+            let is_profit = profit > Decimal::ZERO;
+            if let Some((previous, was_profit)) = last_signed
+                && was_profit != is_profit
+            {
+                roots.push(self.refine(payoff, previous, price)?);
+            }
+            last_signed = Some((price, is_profit));
+        }
+        roots.dedup();
+        Ok(roots)
+    }
+
+    /// Finds every break-even price of `payoff` above `low`, assuming the
+    /// payoff is linear above `high`.
+    ///
+    /// The range `[low, high]` is searched with [`BreakEvenSolver::solve`];
+    /// beyond `high` the payoff of a combination of option legs at expiration
+    /// is linear in the price, so a crossing there is solved on the slope of
+    /// the last grid interval.
+    ///
+    /// # Returns
+    ///
+    /// The break-even prices in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the payoff cannot be evaluated.
+    pub fn solve_with_linear_tail<P: Profit + ?Sized>(
+        &self,
+        payoff: &P,
+        low: Positive,
+        high: Positive,
+        knots: &[Positive],
+    ) -> Result<Vec<Positive>, PricingError> {
+        let mut roots = self.solve(payoff, low, high, knots)?;
+        if high <= low {
+            return Ok(roots);
+        }
+        let step = (high - low) / self.scan_steps as f64;
+        let last_profit = payoff.calculate_profit_at(&high)?;
+        let slope = (last_profit - payoff.calculate_profit_at(&(high - step))?) / step.to_dec();
+        if !slope.is_zero() && !last_profit.is_zero() {
+            let root = high.to_dec() - last_profit / slope;
+            if root > high.to_dec() {
+                roots.push(Positive::new_decimal(root)?);
+            }
+        }
+        Ok(roots)
+    }
+}
+
+#[cfg(test)]
+mod tests_break_even {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::strategies::{LongCall, ShortStrangle};
+    use positive::pos_or_panic;
+    use std::convert::Infallible;
+
+    #[test]
+    fn test_brent_smooth_function() {
+        let root = brent(|x| Ok::<f64, Infallible>(x * x - 2.0), 0.0, 2.0, 1e-12, 100).unwrap();
+        assert!((root - 2f64.sqrt()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_brent_without_sign_change() {
+        let root = brent(|x| Ok::<f64, Infallible>(x + 1.0), 0.0, 2.0, 1e-9, 100).unwrap();
+        assert_eq!(root, 0.0);
+    }
+
+    #[test]
+    fn test_long_call_break_even() {
+        let strategy = LongCall::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos_or_panic!(5.25),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let roots = BreakEvenSolver::new()
+            .solve(
+                &strategy,
+                Positive::ZERO,
+                pos_or_panic!(200.0),
+                &[Positive::HUNDRED],
+            )
+            .unwrap();
+        assert_eq!(roots, vec![pos_or_panic!(105.25)]);
+    }
+
+    #[test]
+    fn test_short_strangle_break_evens_with_precision() {
+        let strategy = ShortStrangle::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(110.0),
+            pos_or_panic!(90.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.5),
+            pos_or_panic!(1.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let solver = BreakEvenSolver::new()
+            .with_tolerance(dec!(0.01))
+            .with_scan_steps(7);
+        let roots = solver
+            .solve(
+                &strategy,
+                Positive::ZERO,
+                pos_or_panic!(200.0),
+                &[pos_or_panic!(90.0), pos_or_panic!(110.0)],
+            )
+            .unwrap();
+        assert_eq!(roots, vec![pos_or_panic!(86.0), pos_or_panic!(114.0)]);
+    }
+
+    #[test]
+    fn test_no_break_even() {
+        let strategy = LongCall::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos_or_panic!(5.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let roots = BreakEvenSolver::new()
+            .solve(&strategy, Positive::ZERO, pos_or_panic!(100.0), &[])
+            .unwrap();
+        assert!(roots.is_empty());
+    }
+}
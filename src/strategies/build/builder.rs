@@ -0,0 +1,374 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Strategy Builder
+//!
+//! [`StrategyBuilder`] assembles a [`Strategy`] one leg at a time and derives
+//! everything that depends on the legs when it is built:
+//!
+//! - the [`StrategyType`], inferred from the sides, styles, strikes and
+//!   expirations of the legs unless it was set explicitly,
+//! - the maximum profit and maximum loss at expiration, `None` when unbounded,
+//! - the break-even prices at expiration.
+//!
+//! ```rust
+//! use optionstratlib::model::Position;
+//! use optionstratlib::model::utils::create_sample_option;
+//! use optionstratlib::strategies::StrategyBuilder;
+//! use optionstratlib::strategies::base::StrategyType;
+//! use optionstratlib::{OptionStyle, Side};
+//! use positive::{Positive, pos_or_panic};
+//!
+//! let leg = |side, strike: f64, premium: f64| {
+//!     let option = create_sample_option(
+//!         OptionStyle::Call,
+//!         side,
+//!         Positive::HUNDRED,
+//!         Positive::ONE,
+//!         pos_or_panic!(strike),
+//!         pos_or_panic!(0.2),
+//!     );
+//!     Position::builder()
+//!         .option(option)
+//!         .premium(pos_or_panic!(premium))
+//!         .build()
+//!         .unwrap()
+//! };
+//!
+//! let strategy = StrategyBuilder::new()
+//!     .leg(leg(Side::Long, 95.0, 7.0))
+//!     .leg(leg(Side::Short, 105.0, 3.0))
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(strategy.kind, StrategyType::BullCallSpread);
+//! assert_eq!(strategy.max_profit, Some(6.0));
+//! assert_eq!(strategy.max_loss, Some(4.0));
+//! assert_eq!(strategy.break_even_points, vec![pos_or_panic!(99.0)]);
+//! ```
+
+use crate::error::strategies::StrategyError;
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, Side};
+use crate::strategies::base::{Strategy, StrategyType};
+use crate::strategies::break_even::BreakEvenSolver;
+use crate::strategies::payoff_extremes::{PayoffExtreme, legs_payoff_extremes};
+use crate::strategies::validation::validate_legs;
+use num_traits::ToPrimitive;
+use positive::{Positive, pos_or_panic};
+use rust_decimal::Decimal;
+
+/// Strategy types [`infer_strategy_type`] can recognize, in the order they are tried.
+const INFERABLE_TYPES: [StrategyType; 19] = [
+    StrategyType::LongCall,
+    StrategyType::ShortCall,
+    StrategyType::LongPut,
+    StrategyType::ShortPut,
+    StrategyType::BullCallSpread,
+    StrategyType::BearCallSpread,
+    StrategyType::BullPutSpread,
+    StrategyType::BearPutSpread,
+    StrategyType::LongStraddle,
+    StrategyType::ShortStraddle,
+    StrategyType::LongStrangle,
+    StrategyType::ShortStrangle,
+    StrategyType::LongButterflySpread,
+    StrategyType::ShortButterflySpread,
+    StrategyType::CallButterfly,
+    StrategyType::IronButterfly,
+    StrategyType::IronCondor,
+    StrategyType::CalendarSpread,
+    StrategyType::DiagonalSpread,
+];
+
+/// Sorted strikes of the legs with the given side and style.
+fn strikes(positions: &[&Position], side: Side, style: OptionStyle) -> Vec<Positive> {
+    let mut strikes: Vec<Positive> = positions
+        .iter()
+        .filter(|p| p.option.side == side && p.option.option_style == style)
+        .map(|p| p.option.strike_price)
+        .collect();
+    strikes.sort();
+    strikes
+}
+
+/// Checks the strike layout of legs whose sides and styles already match `kind`.
+fn matches_strikes(kind: &StrategyType, positions: &[&Position]) -> bool {
+    use OptionStyle::{Call, Put};
+    use Side::{Long, Short};
+    let s = |side, style| strikes(positions, side, style);
+    match kind {
+        StrategyType::BullCallSpread => s(Long, Call) < s(Short, Call),
+        StrategyType::BearCallSpread => s(Short, Call) < s(Long, Call),
+        StrategyType::BullPutSpread => s(Long, Put) < s(Short, Put),
+        StrategyType::BearPutSpread => s(Short, Put) < s(Long, Put),
+        StrategyType::LongStraddle => s(Long, Call) == s(Long, Put),
+        StrategyType::ShortStraddle => s(Short, Call) == s(Short, Put),
+        StrategyType::LongStrangle => s(Long, Call) > s(Long, Put),
+        StrategyType::ShortStrangle => s(Short, Call) > s(Short, Put),
+        StrategyType::LongButterflySpread => {
+            let (wings, body) = (s(Long, Call), s(Short, Call));
+            wings[0] < body[0] && body[0] < wings[1]
+        }
+        StrategyType::ShortButterflySpread => {
+            let (wings, body) = (s(Short, Call), s(Long, Call));
+            wings[0] < body[0] && body[0] < wings[1]
+        }
+        StrategyType::CallButterfly => s(Long, Call)[0] < s(Short, Call)[0],
+        StrategyType::IronButterfly | StrategyType::IronCondor => {
+            let (long_put, short_put) = (s(Long, Put)[0], s(Short, Put)[0]);
+            let (short_call, long_call) = (s(Short, Call)[0], s(Long, Call)[0]);
+            let body = if *kind == StrategyType::IronButterfly {
+                short_put == short_call
+            } else {
+                short_put < short_call
+            };
+            long_put < short_put && body && short_call < long_call
+        }
+        StrategyType::CalendarSpread => positions
+            .iter()
+            .all(|p| p.option.strike_price == positions[0].option.strike_price),
+        StrategyType::DiagonalSpread => positions
+            .iter()
+            .any(|p| p.option.strike_price != positions[0].option.strike_price),
+        _ => true,
+    }
+}
+
+/// Infers the strategy type of a set of legs.
+///
+/// A named strategy type is returned when the legs pass [`validate_legs`]
+/// for it and their strikes are laid out the way that strategy is built,
+/// for example a long call below a short call for a bull call spread.
+/// Anything else is [`StrategyType::Custom`]. Strategies that need a
+/// position in the underlying, such as covered calls, are never inferred.
+pub fn infer_strategy_type(positions: &[&Position]) -> StrategyType {
+    INFERABLE_TYPES
+        .into_iter()
+        .find(|kind| validate_legs(kind, positions).is_ok() && matches_strikes(kind, positions))
+        .unwrap_or(StrategyType::Custom)
+}
+
+/// Builder for [`Strategy`] that accumulates legs and derives the strategy
+/// type, the maximum profit and loss and the break-even prices on
+/// [`StrategyBuilder::build`].
+#[derive(Debug, Clone, Default)]
+pub struct StrategyBuilder {
+    name: Option<String>,
+    kind: Option<StrategyType>,
+    description: String,
+    legs: Vec<Position>,
+}
+
+impl StrategyBuilder {
+    /// Creates a builder without legs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the strategy. Defaults to the name of its type.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the strategy type instead of inferring it. The legs are then
+    /// validated against it.
+    pub fn kind(mut self, kind: StrategyType) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Sets the description of the strategy.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Adds one leg.
+    pub fn leg(mut self, leg: Position) -> Self {
+        self.legs.push(leg);
+        self
+    }
+
+    /// Adds several legs.
+    pub fn legs(mut self, legs: impl IntoIterator<Item = Position>) -> Self {
+        self.legs.extend(legs);
+        self
+    }
+
+    /// Validates the legs and builds the strategy with its derived metrics.
+    ///
+    /// Break-even prices are rounded to two decimals.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError::ValidationError` if there are no legs or
+    /// they do not fit the strategy type, or an error raised while evaluating
+    /// the payoff.
+    pub fn build(self) -> Result<Strategy, StrategyError> {
+        let positions: Vec<&Position> = self.legs.iter().collect();
+        let kind = match self.kind {
+            Some(kind) => kind,
+            None => infer_strategy_type(&positions),
+        };
+        validate_legs(&kind, &positions)?;
+
+        let name = self.name.unwrap_or_else(|| kind.to_string());
+        let mut strategy = Strategy::new(name, kind, self.description);
+        strategy.legs = self.legs;
+
+        let positions: Vec<&Position> = strategy.legs.iter().collect();
+        let extremes = legs_payoff_extremes(&strategy, &positions)?;
+        let strikes: Vec<Positive> = positions.iter().map(|p| p.option.strike_price).collect();
+        let upper = positions
+            .iter()
+            .map(|p| p.option.strike_price.max(p.option.underlying_price))
+            .max()
+            .unwrap_or(Positive::ZERO)
+            * pos_or_panic!(1.5);
+        let mut break_even_points: Vec<Positive> = BreakEvenSolver::new()
+            .solve_with_linear_tail(&strategy, Positive::ZERO, upper, &strikes)?
+            .into_iter()
+            .map(|point| point.round_to(2))
+            .collect();
+        break_even_points.dedup();
+
+        let amount = |extreme: PayoffExtreme, sign: Decimal| match extreme {
+            PayoffExtreme::Bounded { pnl, .. } => (pnl * sign).max(Decimal::ZERO).to_f64(),
+            PayoffExtreme::Unbounded => None,
+        };
+        strategy.max_profit = amount(extremes.max_profit, Decimal::ONE);
+        strategy.max_loss = amount(extremes.max_loss, Decimal::NEGATIVE_ONE);
+        strategy.break_even_points = break_even_points;
+        Ok(strategy)
+    }
+}
+
+#[cfg(test)]
+mod tests_strategy_builder {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::error::strategies::ValidationErrorKind;
+    use crate::model::utils::create_sample_option;
+
+    fn leg(style: OptionStyle, side: Side, strike: f64, premium: f64) -> Position {
+        let option = create_sample_option(
+            style,
+            side,
+            Positive::HUNDRED,
+            Positive::ONE,
+            pos_or_panic!(strike),
+            pos_or_panic!(0.2),
+        );
+        Position::builder()
+            .option(option)
+            .premium(pos_or_panic!(premium))
+            .build()
+            .unwrap()
+    }
+
+    fn kind_of(legs: &[Position]) -> StrategyType {
+        infer_strategy_type(&legs.iter().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_infers_vertical_and_volatility_strategies() {
+        use OptionStyle::{Call, Put};
+        use Side::{Long, Short};
+        assert_eq!(
+            kind_of(&[leg(Put, Short, 95.0, 2.0)]),
+            StrategyType::ShortPut
+        );
+        assert_eq!(
+            kind_of(&[leg(Call, Short, 95.0, 7.0), leg(Call, Long, 105.0, 3.0)]),
+            StrategyType::BearCallSpread
+        );
+        assert_eq!(
+            kind_of(&[leg(Put, Long, 95.0, 2.0), leg(Put, Short, 105.0, 6.0)]),
+            StrategyType::BullPutSpread
+        );
+        assert_eq!(
+            kind_of(&[leg(Call, Long, 100.0, 4.0), leg(Put, Long, 100.0, 4.0)]),
+            StrategyType::LongStraddle
+        );
+        assert_eq!(
+            kind_of(&[leg(Call, Short, 110.0, 1.0), leg(Put, Short, 90.0, 1.0)]),
+            StrategyType::ShortStrangle
+        );
+        assert_eq!(
+            kind_of(&[
+                leg(Put, Long, 85.0, 0.5),
+                leg(Put, Short, 95.0, 2.0),
+                leg(Call, Short, 105.0, 2.0),
+                leg(Call, Long, 115.0, 0.5),
+            ]),
+            StrategyType::IronCondor
+        );
+        assert_eq!(
+            kind_of(&[
+                leg(Call, Long, 110.0, 1.0),
+                leg(Put, Long, 90.0, 1.0),
+                leg(Put, Long, 80.0, 0.5)
+            ]),
+            StrategyType::Custom
+        );
+    }
+
+    #[test]
+    fn test_infers_time_spreads() {
+        let near = leg(OptionStyle::Call, Side::Short, 100.0, 3.0);
+        let mut far = leg(OptionStyle::Call, Side::Long, 100.0, 5.0);
+        far.option.expiration_date = ExpirationDate::Days(pos_or_panic!(60.0));
+        assert_eq!(
+            kind_of(&[near.clone(), far.clone()]),
+            StrategyType::CalendarSpread
+        );
+        far.option.strike_price = pos_or_panic!(95.0);
+        assert_eq!(kind_of(&[near, far]), StrategyType::DiagonalSpread);
+    }
+
+    #[test]
+    fn test_unbounded_metrics_are_none() {
+        let strategy = StrategyBuilder::new()
+            .name("Strangle")
+            .leg(leg(OptionStyle::Call, Side::Short, 110.0, 2.0))
+            .leg(leg(OptionStyle::Put, Side::Short, 90.0, 3.0))
+            .build()
+            .unwrap();
+        assert_eq!(strategy.name, "Strangle");
+        assert_eq!(strategy.kind, StrategyType::ShortStrangle);
+        assert_eq!(strategy.max_profit, Some(5.0));
+        assert_eq!(strategy.max_loss, None);
+
+        let json = serde_json::to_string(&strategy).unwrap();
+        let restored: Strategy = serde_json::from_str(&json).unwrap();
+        assert!(restored == strategy);
+        assert_eq!(
+            strategy.break_even_points,
+            vec![pos_or_panic!(85.0), pos_or_panic!(115.0)]
+        );
+    }
+
+    #[test]
+    fn test_explicit_kind_is_validated() {
+        let result = StrategyBuilder::new()
+            .kind(StrategyType::BullCallSpread)
+            .leg(leg(OptionStyle::Put, Side::Long, 95.0, 2.0))
+            .leg(leg(OptionStyle::Call, Side::Short, 105.0, 3.0))
+            .build();
+        assert!(matches!(
+            result,
+            Err(StrategyError::ValidationError(
+                ValidationErrorKind::IncompatibleLeg { .. }
+            ))
+        ));
+        assert!(matches!(
+            StrategyBuilder::new().build(),
+            Err(StrategyError::ValidationError(ValidationErrorKind::NoLegs))
+        ));
+    }
+}
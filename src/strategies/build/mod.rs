@@ -4,5 +4,6 @@
    Date: 16/2/25
 ******************************************************************************/
 
+pub(crate) mod builder;
 pub(crate) mod model;
 pub(crate) mod traits;
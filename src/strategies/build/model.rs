@@ -9,9 +9,10 @@ use crate::model::Position;
 use crate::strategies::base::StrategyType;
 use crate::strategies::custom::CustomStrategy;
 use crate::strategies::{
-    BearCallSpread, BearPutSpread, BullCallSpread, BullPutSpread, CallButterfly, IronButterfly,
-    IronCondor, LongButterflySpread, LongStraddle, LongStrangle, PoorMansCoveredCall,
-    ShortButterflySpread, ShortStraddle, ShortStrangle, Strategable, StrategyConstructor,
+    BearCallSpread, BearPutSpread, BullCallSpread, BullPutSpread, CalendarSpread, CallButterfly,
+    DiagonalSpread, IronButterfly, IronCondor, LongButterflySpread, LongStraddle, LongStrangle,
+    PoorMansCoveredCall, ShortButterflySpread, ShortStraddle, ShortStrangle, Strategable,
+    StrategyConstructor, validate_legs,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -67,6 +68,8 @@ impl StrategyRequest {
     ///
     /// This method acts as a factory that constructs the appropriate strategy object
     /// by delegating to the corresponding strategy implementation's `get_strategy` method.
+    /// The positions are first checked with [`validate_legs`], so malformed requests are
+    /// rejected before any strategy is built.
     ///
     /// # Returns
     /// * `Ok(Box<dyn Strategable>)` - A boxed trait object implementing the `Strategable`
@@ -75,9 +78,12 @@ impl StrategyRequest {
     ///   Returns `StrategyError::NotImplemented` for strategies that are not yet implemented.
     ///
     /// # Errors
-    /// This method can return errors from the underlying strategy constructors or
-    /// `StrategyError::NotImplemented` for strategies that are defined but not yet implemented.
+    /// This method can return `StrategyError::ValidationError` for inconsistent legs, errors
+    /// from the underlying strategy constructors, or `StrategyError::NotImplemented` for
+    /// strategies that are defined but not yet implemented.
     pub fn get_strategy(&self) -> Result<Box<dyn Strategable>, StrategyError> {
+        let positions: Vec<&Position> = self.positions.iter().collect();
+        validate_legs(&self.strategy_type, &positions)?;
         match self.strategy_type {
             StrategyType::BullCallSpread => {
                 Ok(Box::new(BullCallSpread::get_strategy(&self.positions)?))
@@ -126,6 +132,12 @@ impl StrategyRequest {
             StrategyType::CallButterfly => {
                 Ok(Box::new(CallButterfly::get_strategy(&self.positions)?))
             }
+            StrategyType::CalendarSpread => {
+                Ok(Box::new(CalendarSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::DiagonalSpread => {
+                Ok(Box::new(DiagonalSpread::get_strategy(&self.positions)?))
+            }
             StrategyType::Custom => Ok(Box::new(CustomStrategy::get_strategy(&self.positions)?)),
         }
     }
@@ -0,0 +1,820 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Calendar Spread Strategy
+//!
+//! A calendar spread (also called a time or horizontal spread) sells a
+//! near-term option and buys a longer-dated option of the same style and
+//! strike. The trade profits from the faster time decay of the near leg and
+//! is usually opened for a net debit.
+//!
+//! ## Key Characteristics
+//!
+//! - The legs expire on different dates, so the strategy is analysed at the
+//!   near leg's expiration: the near leg settles at intrinsic value while the
+//!   far leg is still marked with Black-Scholes using its remaining time.
+//! - Maximum profit is reached when the underlying sits at the strike at the
+//!   near expiration.
+//! - Maximum loss is approximately the net debit paid, reached when the
+//!   underlying moves far away from the strike in either direction.
+//!
+//! ## Profit/Loss Profile at the Near Expiration
+//!
+//! ```text
+//! Profit ^
+//!        |          /\
+//!        |         /  \
+//!        |--------/----\--------> Underlying Price
+//!        |      /        \
+//!        |_____/          \_____ <- Max loss (≈ net debit)
+//!        |
+//! ```
+//!
+//! ## Example
+//!
+//! ```rust
+//! use optionstratlib::strategies::base::BreakEvenable;
+//! use optionstratlib::strategies::calendar_spread::CalendarSpread;
+//! use optionstratlib::model::ExpirationDate;
+//! use optionstratlib::OptionStyle;
+//! use positive::{pos_or_panic, Positive};
+//! use rust_decimal_macros::dec;
+//!
+//! let calendar = CalendarSpread::new(
+//!     "AAPL".to_string(),
+//!     pos_or_panic!(150.0),                      // underlying price
+//!     pos_or_panic!(150.0),                      // strike
+//!     OptionStyle::Call,
+//!     ExpirationDate::Days(pos_or_panic!(30.0)), // near expiration
+//!     ExpirationDate::Days(pos_or_panic!(90.0)), // far expiration
+//!     pos_or_panic!(0.25),                       // near implied volatility
+//!     pos_or_panic!(0.23),                       // far implied volatility
+//!     dec!(0.05),                                // risk-free rate
+//!     Positive::ZERO,                            // dividend yield
+//!     Positive::ONE,                             // quantity
+//!     pos_or_panic!(4.50),                       // near premium received
+//!     pos_or_panic!(7.80),                       // far premium paid
+//!     Positive::ZERO,                            // near open fee
+//!     Positive::ZERO,                            // near close fee
+//!     Positive::ZERO,                            // far open fee
+//!     Positive::ZERO,                            // far close fee
+//! );
+//! assert_eq!(calendar.get_break_even_points().unwrap().len(), 2);
+//! ```
+
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use crate::Options;
+use crate::error::OperationErrorKind;
+use crate::error::position::PositionValidationErrorKind;
+use crate::error::probability::ProbabilityError;
+use crate::error::{GreeksError, PositionError, PricingError, StrategyError};
+use crate::greeks::Greeks;
+use crate::model::ExpirationDate;
+use crate::model::ProfitLossRange;
+use crate::model::position::Position;
+use crate::model::types::{OptionBasicType, OptionStyle, OptionType, Side};
+use crate::pnl::PnLCalculator;
+use crate::pricing::payoff::Profit;
+use crate::strategies::delta_neutral::DeltaNeutrality;
+use crate::strategies::probabilities::core::ProbabilityAnalysis;
+use crate::strategies::probabilities::utils::VolatilityAdjustment;
+use crate::strategies::shared::TimeSpreadStrategy;
+use crate::strategies::{BasicAble, Strategies, StrategyConstructor};
+use chrono::Utc;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+use utoipa::ToSchema;
+
+/// Default description for the Calendar Spread strategy.
+pub const CALENDAR_SPREAD_DESCRIPTION: &str = "A calendar spread sells a near-term option and \
+    buys a longer-dated option of the same style and strike. It profits from the faster time \
+    decay of the near leg and reaches its maximum profit when the underlying is at the strike \
+    when the near leg expires. The maximum loss is approximately the net debit paid.";
+
+/// Represents a Calendar Spread options strategy.
+///
+/// The strategy is valued at the near leg's expiration: the near leg settles
+/// at intrinsic value and the far leg is marked with Black-Scholes using the
+/// time it has left, which produces the characteristic tent-shaped P&L.
+///
+/// # Structure
+///
+/// - **Near Leg**: Short option expiring first
+/// - **Far Leg**: Long option of the same style and strike expiring later
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CalendarSpread {
+    /// The name of the strategy.
+    pub name: String,
+
+    /// The type of strategy (StrategyType::CalendarSpread).
+    pub kind: StrategyType,
+
+    /// A textual description of this strategy instance.
+    pub description: String,
+
+    /// The price points at which the strategy breaks even at the near expiration.
+    pub break_even_points: Vec<Positive>,
+
+    /// The short option expiring first.
+    pub near_leg: Position,
+
+    /// The long option expiring last.
+    pub far_leg: Position,
+}
+
+impl CalendarSpread {
+    /// Creates a new Calendar Spread strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset
+    /// * `underlying_price` - The current market price of the underlying asset
+    /// * `strike` - The strike price shared by both legs
+    /// * `option_style` - Whether both legs are calls or puts
+    /// * `near_expiration` - The expiration date of the short leg
+    /// * `far_expiration` - The expiration date of the long leg
+    /// * `near_implied_volatility` - The implied volatility of the short leg
+    /// * `far_implied_volatility` - The implied volatility of the long leg
+    /// * `risk_free_rate` - The risk-free interest rate
+    /// * `dividend_yield` - The dividend yield of the underlying asset
+    /// * `quantity` - The number of contracts of each leg
+    /// * `premium_near` - The premium received for selling the near leg
+    /// * `premium_far` - The premium paid for buying the far leg
+    /// * `open_fee_near` - Fee to open the near leg
+    /// * `close_fee_near` - Fee to close the near leg
+    /// * `open_fee_far` - Fee to open the far leg
+    /// * `close_fee_far` - Fee to close the far leg
+    ///
+    /// # Returns
+    ///
+    /// A fully configured `CalendarSpread` strategy instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if break-even point calculation fails.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        strike: Positive,
+        option_style: OptionStyle,
+        near_expiration: ExpirationDate,
+        far_expiration: ExpirationDate,
+        near_implied_volatility: Positive,
+        far_implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_near: Positive,
+        premium_far: Positive,
+        open_fee_near: Positive,
+        close_fee_near: Positive,
+        open_fee_far: Positive,
+        close_fee_far: Positive,
+    ) -> Self {
+        let near_option = Options::new(
+            OptionType::European,
+            Side::Short,
+            underlying_symbol.clone(),
+            strike,
+            near_expiration,
+            near_implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let near_leg = Position::new(
+            near_option,
+            premium_near,
+            Utc::now(),
+            open_fee_near,
+            close_fee_near,
+            None,
+            None,
+        );
+
+        let far_option = Options::new(
+            OptionType::European,
+            Side::Long,
+            underlying_symbol,
+            strike,
+            far_expiration,
+            far_implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let far_leg = Position::new(
+            far_option,
+            premium_far,
+            Utc::now(),
+            open_fee_far,
+            close_fee_far,
+            None,
+            None,
+        );
+
+        let mut strategy = CalendarSpread {
+            name: "Calendar Spread".to_string(),
+            kind: StrategyType::CalendarSpread,
+            description: CALENDAR_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_leg,
+            far_leg,
+        };
+
+        strategy.validate();
+        strategy
+            .update_break_even_points()
+            .expect("Failed to calculate break-even points");
+
+        strategy
+    }
+
+    /// Returns the strike price shared by both legs.
+    #[must_use]
+    pub fn strike(&self) -> Positive {
+        self.near_leg.option.strike_price
+    }
+
+    /// Returns the option style shared by both legs.
+    #[must_use]
+    pub fn option_style(&self) -> OptionStyle {
+        self.near_leg.option.option_style
+    }
+
+    /// Calculates the net debit paid to open the spread, excluding fees.
+    ///
+    /// Net Debit = Far Premium Paid - Near Premium Received
+    #[must_use]
+    pub fn net_debit(&self) -> Decimal {
+        let paid = self.far_leg.premium * self.far_leg.option.quantity;
+        let received = self.near_leg.premium * self.near_leg.option.quantity;
+        paid.to_dec() - received.to_dec()
+    }
+
+    /// Price range scanned when searching for break-evens and extremes.
+    fn scan_range(&self) -> (Positive, Positive) {
+        let strike = self.strike();
+        (strike * Decimal::new(5, 1), strike * Decimal::new(15, 1))
+    }
+}
+
+impl TimeSpreadStrategy for CalendarSpread {
+    fn near_leg(&self) -> &Position {
+        &self.near_leg
+    }
+
+    fn far_leg(&self) -> &Position {
+        &self.far_leg
+    }
+}
+
+impl Validable for CalendarSpread {
+    fn validate(&self) -> bool {
+        if !self.near_leg.validate() || !self.far_leg.validate() {
+            debug!("Invalid: Calendar spread legs are not valid");
+            return false;
+        }
+
+        if self.near_leg.option.side != Side::Short || self.far_leg.option.side != Side::Long {
+            debug!("Invalid: Calendar spread must sell the near leg and buy the far leg");
+            return false;
+        }
+
+        if self.near_leg.option.option_style != self.far_leg.option.option_style {
+            debug!("Invalid: Both legs must share the same option style");
+            return false;
+        }
+
+        if self.near_leg.option.strike_price != self.far_leg.option.strike_price {
+            debug!("Invalid: Both legs must share the same strike");
+            return false;
+        }
+
+        match (
+            self.near_leg.option.expiration_date.get_days(),
+            self.far_leg.option.expiration_date.get_days(),
+        ) {
+            (Ok(near), Ok(far)) if near < far => true,
+            _ => {
+                debug!("Invalid: The near leg must expire before the far leg");
+                false
+            }
+        }
+    }
+}
+
+impl BreakEvenable for CalendarSpread {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        let (lower, upper) = self.scan_range();
+        self.break_even_points = self.near_expiration_break_evens(lower, upper)?;
+        Ok(())
+    }
+}
+
+impl Positionable for CalendarSpread {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match position.option.side {
+            Side::Short => self.near_leg = position.clone(),
+            Side::Long => self.far_leg = position.clone(),
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(vec![&self.near_leg, &self.far_leg])
+    }
+
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        if *option_style != self.option_style() || *strike != self.strike() {
+            return Err(PositionError::invalid_position(
+                "Position not found in Calendar Spread",
+            ));
+        }
+        match side {
+            Side::Short => Ok(vec![&mut self.near_leg]),
+            Side::Long => Ok(vec![&mut self.far_leg]),
+        }
+    }
+
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+
+        if position.option.option_style != self.option_style()
+            || position.option.strike_price != self.strike()
+        {
+            return Err(PositionError::invalid_position(
+                "Position does not match existing calendar spread positions",
+            ));
+        }
+
+        self.add_position(position)
+    }
+}
+
+impl StrategyConstructor for CalendarSpread {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        if vec_positions.len() != 2 {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Calendar Spread get_strategy".to_string(),
+                    reason: "Must have exactly 2 options".to_string(),
+                },
+            ));
+        }
+
+        let near_leg = vec_positions
+            .iter()
+            .find(|position| position.option.side == Side::Short);
+        let far_leg = vec_positions
+            .iter()
+            .find(|position| position.option.side == Side::Long);
+        let (Some(near_leg), Some(far_leg)) = (near_leg, far_leg) else {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Calendar Spread get_strategy".to_string(),
+                    reason: "Calendar Spread requires a short near leg and a long far leg"
+                        .to_string(),
+                },
+            ));
+        };
+
+        let mut strategy = CalendarSpread {
+            name: "Calendar Spread".to_string(),
+            kind: StrategyType::CalendarSpread,
+            description: CALENDAR_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_leg: near_leg.clone(),
+            far_leg: far_leg.clone(),
+        };
+
+        if !strategy.validate() {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Calendar Spread get_strategy".to_string(),
+                    reason: "Legs must share style and strike and the short leg must expire first"
+                        .to_string(),
+                },
+            ));
+        }
+
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl Strategable for CalendarSpread {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for CalendarSpread {
+    fn get_title(&self) -> String {
+        format!(
+            "Calendar Spread Strategy:\n\t{}\n\t{}",
+            self.near_leg.get_title(),
+            self.far_leg.get_title()
+        )
+    }
+
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            })
+            .collect()
+    }
+
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.implied_volatility,
+                )
+            })
+            .collect()
+    }
+
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.quantity,
+                )
+            })
+            .collect()
+    }
+
+    fn one_option(&self) -> &Options {
+        self.near_leg.one_option()
+    }
+
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.near_leg.one_option_mut()
+    }
+
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        for leg in [&mut self.near_leg, &mut self.far_leg] {
+            leg.option.underlying_price = *price;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        self.update_break_even_points()
+    }
+
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        for leg in [&mut self.near_leg, &mut self.far_leg] {
+            leg.option.implied_volatility = *volatility;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        self.update_break_even_points()
+    }
+}
+
+impl Strategies for CalendarSpread {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        let (lower, upper) = self.scan_range();
+        let (_, max_profit) = self.near_expiration_extremes(lower, upper)?;
+        if max_profit <= Decimal::ZERO {
+            return Err(StrategyError::ProfitLossError(
+                crate::error::strategies::ProfitLossErrorKind::MaxProfitError {
+                    reason: "Calendar spread has no profitable price at the near expiration"
+                        .to_string(),
+                },
+            ));
+        }
+        Ok(Positive::new_decimal(max_profit)?)
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        let (lower, upper) = self.scan_range();
+        let (min_profit, _) = self.near_expiration_extremes(lower, upper)?;
+        Ok(Positive::new_decimal(-min_profit.min(Decimal::ZERO))?)
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        let (lower, upper) = match self.break_even_points.as_slice() {
+            [lower, upper] => (*lower, *upper),
+            _ => return Ok(Decimal::ZERO),
+        };
+        let max_profit = self.get_max_profit().unwrap_or(Positive::ZERO);
+        Ok((upper - lower).to_dec() * max_profit.to_dec() / Decimal::TWO)
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        match (self.get_max_profit(), self.get_max_loss()) {
+            (Ok(profit), Ok(loss)) if loss > Positive::ZERO => {
+                Ok(profit.to_dec() / loss.to_dec() * Decimal::ONE_HUNDRED)
+            }
+            _ => Ok(Decimal::ZERO),
+        }
+    }
+}
+
+impl Profit for CalendarSpread {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        self.profit_at_near_expiration(price)
+    }
+}
+
+impl Greeks for CalendarSpread {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.near_leg.option, &self.far_leg.option])
+    }
+}
+
+impl PnLCalculator for CalendarSpread {
+    fn calculate_pnl(
+        &self,
+        underlying_price: &Positive,
+        _expiration_date: ExpirationDate,
+        _implied_volatility: &Positive,
+    ) -> Result<crate::pnl::utils::PnL, PricingError> {
+        self.calculate_pnl_at_expiration(underlying_price)
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<crate::pnl::utils::PnL, PricingError> {
+        Ok(crate::pnl::utils::PnL {
+            realized: None,
+            unrealized: Some(self.calculate_profit_at(underlying_price)?),
+            initial_costs: self.far_leg.premium * self.far_leg.option.quantity,
+            initial_income: self.near_leg.premium * self.near_leg.option.quantity,
+            date_time: Utc::now(),
+        })
+    }
+}
+
+impl DeltaNeutrality for CalendarSpread {}
+
+impl Optimizable for CalendarSpread {
+    type Strategy = CalendarSpread;
+}
+
+impl ProbabilityAnalysis for CalendarSpread {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let (lower, upper) = match self.break_even_points.as_slice() {
+            [lower, upper] => (*lower, *upper),
+            _ => return Err(ProbabilityError::from("Expected two break-even points")),
+        };
+
+        let option = &self.far_leg.option;
+        let mut profit_range = ProfitLossRange::new(Some(lower), Some(upper), Positive::ZERO)?;
+        profit_range.calculate_probability(
+            &option.underlying_price,
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &self.near_leg.option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![profit_range])
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let (lower, upper) = match self.break_even_points.as_slice() {
+            [lower, upper] => (*lower, *upper),
+            _ => return Err(ProbabilityError::from("Expected two break-even points")),
+        };
+
+        let option = &self.far_leg.option;
+        let mut ranges = vec![
+            ProfitLossRange::new(None, Some(lower), Positive::ZERO)?,
+            ProfitLossRange::new(Some(upper), None, Positive::ZERO)?,
+        ];
+        for range in ranges.iter_mut() {
+            range.calculate_probability(
+                &option.underlying_price,
+                Some(VolatilityAdjustment {
+                    base_volatility: option.implied_volatility,
+                    std_dev_adjustment: Positive::ZERO,
+                }),
+                None,
+                &self.near_leg.option.expiration_date,
+                Some(option.risk_free_rate),
+            )?;
+        }
+
+        Ok(ranges)
+    }
+}
+
+impl std::fmt::Display for CalendarSpread {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Calendar Spread: Short {} {} @ {} ({}) + Long {} {} @ {} ({})",
+            self.near_leg.option.option_style,
+            self.near_leg.option.strike_price,
+            self.near_leg.premium,
+            self.near_leg.option.expiration_date,
+            self.far_leg.option.option_style,
+            self.far_leg.option.strike_price,
+            self.far_leg.premium,
+            self.far_leg.option.expiration_date
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn create_test_calendar() -> CalendarSpread {
+        CalendarSpread::new(
+            "AAPL".to_string(),
+            Positive::HUNDRED,
+            Positive::HUNDRED,
+            OptionStyle::Call,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            ExpirationDate::Days(pos_or_panic!(90.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.3),
+            pos_or_panic!(4.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_calendar_creation() {
+        let calendar = create_test_calendar();
+        assert_eq!(calendar.kind, StrategyType::CalendarSpread);
+        assert_eq!(calendar.near_leg.option.side, Side::Short);
+        assert_eq!(calendar.far_leg.option.side, Side::Long);
+        assert_eq!(calendar.strike(), Positive::HUNDRED);
+        assert_eq!(calendar.net_debit(), dec!(1.7));
+        assert!(calendar.validate());
+    }
+
+    #[test]
+    fn test_calendar_invalid_expirations() {
+        let mut calendar = create_test_calendar();
+        calendar.far_leg.option.expiration_date = ExpirationDate::Days(pos_or_panic!(10.0));
+        assert!(!calendar.validate());
+    }
+
+    #[test]
+    fn test_tent_shaped_profit() {
+        let calendar = create_test_calendar();
+        let at_strike = calendar.calculate_profit_at(&Positive::HUNDRED).unwrap();
+        let below = calendar.calculate_profit_at(&pos_or_panic!(80.0)).unwrap();
+        let above = calendar.calculate_profit_at(&pos_or_panic!(120.0)).unwrap();
+        assert!(at_strike > Decimal::ZERO);
+        assert!(at_strike > below);
+        assert!(at_strike > above);
+        assert!(below < Decimal::ZERO);
+        assert!(above < Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_break_even_points() {
+        let calendar = create_test_calendar();
+        let break_evens = calendar.get_break_even_points().unwrap();
+        assert_eq!(break_evens.len(), 2);
+        assert!(break_evens[0] < Positive::HUNDRED);
+        assert!(break_evens[1] > Positive::HUNDRED);
+        for break_even in break_evens {
+            let profit = calendar.calculate_profit_at(break_even).unwrap();
+            assert!(profit.abs() < dec!(0.05));
+        }
+    }
+
+    #[test]
+    fn test_max_profit_and_loss() {
+        let calendar = create_test_calendar();
+        let max_profit = calendar.get_max_profit().unwrap();
+        let at_strike = calendar.calculate_profit_at(&Positive::HUNDRED).unwrap();
+        assert_eq!(max_profit.to_dec(), at_strike);
+
+        let max_loss = calendar.get_max_loss().unwrap();
+        assert!(max_loss.to_dec() <= calendar.net_debit());
+        assert!(max_loss.to_dec() > calendar.net_debit() - dec!(0.1));
+        assert!(calendar.get_profit_ratio().unwrap() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_nearest_expiration() {
+        let calendar = create_test_calendar();
+        let nearest = calendar.get_nearest_expiration().unwrap();
+        assert_eq!(nearest.get_days().unwrap(), pos_or_panic!(30.0));
+        let profit = calendar
+            .calculate_profit_at_horizon(&Positive::HUNDRED)
+            .unwrap();
+        assert_eq!(
+            profit,
+            calendar.calculate_profit_at(&Positive::HUNDRED).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_far_leg_marked_at_horizon() {
+        let calendar = create_test_calendar();
+        let horizon = calendar.near_leg.option.expiration_date;
+        let near = calendar
+            .near_leg
+            .pnl_at_horizon(&Positive::HUNDRED, &horizon)
+            .unwrap();
+        let far = calendar
+            .far_leg
+            .pnl_at_horizon(&Positive::HUNDRED, &horizon)
+            .unwrap();
+        assert_eq!(near, dec!(2.3));
+        // A 60 day at-the-money call keeps time value beyond intrinsic.
+        assert!(far > dec!(-4.0) + dec!(2.0));
+    }
+
+    #[test]
+    fn test_get_strategy() {
+        let calendar = create_test_calendar();
+        let positions = vec![calendar.far_leg.clone(), calendar.near_leg.clone()];
+        let rebuilt = CalendarSpread::get_strategy(&positions).unwrap();
+        assert_eq!(rebuilt.near_leg.option.side, Side::Short);
+        assert_eq!(rebuilt.break_even_points, calendar.break_even_points);
+
+        let swapped = vec![calendar.near_leg.clone(), calendar.near_leg.clone()];
+        assert!(CalendarSpread::get_strategy(&swapped).is_err());
+    }
+
+    #[test]
+    fn test_probability_ranges() {
+        let calendar = create_test_calendar();
+        assert_eq!(calendar.get_profit_ranges().unwrap().len(), 1);
+        assert_eq!(calendar.get_loss_ranges().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_display() {
+        let calendar = create_test_calendar();
+        assert!(format!("{calendar}").contains("Calendar Spread"));
+        assert!(calendar.get_title().contains("Calendar Spread"));
+    }
+}
@@ -77,14 +77,16 @@ use crate::greeks::Greeks;
 use crate::model::ExpirationDate;
 use crate::model::ProfitLossRange;
 use crate::model::leg::traits::LegAble;
-use crate::model::leg::{Leg, SpotPosition};
+use crate::model::leg::{Leg, SHARES_PER_CONTRACT, SpotPosition, StockLeg};
 use crate::model::position::Position;
 use crate::model::types::{OptionBasicType, OptionStyle, OptionType, Side};
 use crate::pnl::PnLCalculator;
 use crate::pricing::payoff::Profit;
+use crate::risk::RegTMargin;
 use crate::strategies::delta_neutral::DeltaNeutrality;
 use crate::strategies::probabilities::core::ProbabilityAnalysis;
 use crate::strategies::probabilities::utils::VolatilityAdjustment;
+use crate::strategies::shared::StockOptionStrategy;
 use crate::strategies::{BasicAble, Strategies};
 use chrono::Utc;
 use positive::Positive;
@@ -168,10 +170,10 @@ impl Collar {
     /// * `premium_short_call` - The premium received for selling the call
     /// * `spot_open_fee` - Fee to open the spot position
     /// * `spot_close_fee` - Fee to close the spot position
-    /// * `put_open_fee` - Fee to open the put position
-    /// * `put_close_fee` - Fee to close the put position
-    /// * `call_open_fee` - Fee to open the call position
-    /// * `call_close_fee` - Fee to close the call position
+    /// * `put_open_fee` - Fee per contract to open the put position
+    /// * `put_close_fee` - Fee per contract to close the put position
+    /// * `call_open_fee` - Fee per contract to open the call position
+    /// * `call_close_fee` - Fee per contract to close the call position
     ///
     /// # Returns
     ///
@@ -220,7 +222,7 @@ impl Collar {
             put_strike,
             expiration,
             implied_volatility,
-            quantity / Positive::HUNDRED, // Convert shares to contracts
+            quantity, // Option quantity is expressed in shares
             underlying_price,
             risk_free_rate,
             OptionStyle::Put,
@@ -232,8 +234,8 @@ impl Collar {
             long_put_option,
             premium_long_put,
             Utc::now(),
-            put_open_fee,
-            put_close_fee,
+            put_open_fee / SHARES_PER_CONTRACT,
+            put_close_fee / SHARES_PER_CONTRACT,
             None,
             None,
         );
@@ -246,7 +248,7 @@ impl Collar {
             call_strike,
             expiration,
             implied_volatility,
-            quantity / Positive::HUNDRED, // Convert shares to contracts
+            quantity, // Option quantity is expressed in shares
             underlying_price,
             risk_free_rate,
             OptionStyle::Call,
@@ -258,8 +260,8 @@ impl Collar {
             short_call_option,
             premium_short_call,
             Utc::now(),
-            call_open_fee,
-            call_close_fee,
+            call_open_fee / SHARES_PER_CONTRACT,
+            call_close_fee / SHARES_PER_CONTRACT,
             None,
             None,
         );
@@ -414,11 +416,7 @@ impl Collar {
 
     /// Calculates total fees for all positions.
     fn total_fees(&self) -> Positive {
-        self.spot_leg.fees()
-            + self.long_put.open_fee
-            + self.long_put.close_fee
-            + self.short_call.open_fee
-            + self.short_call.close_fee
+        self.combined_fees()
     }
 
     /// Checks if the put is currently in-the-money.
@@ -488,14 +486,9 @@ impl BreakEvenable for Collar {
     fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
         self.break_even_points.clear();
 
-        // Break-even = Cost Basis - Net Premium per Share
-        let net_premium_per_share = self.net_premium() / self.spot_leg.quantity.to_dec();
-        let fees_per_share = self.total_fees().to_dec() / self.spot_leg.quantity.to_dec();
-
-        let break_even = self.spot_leg.cost_basis.to_dec() - net_premium_per_share + fees_per_share;
-
-        if let Ok(be) = Positive::new_decimal(break_even) {
-            self.break_even_points.push(be.round_to(2));
+        // Break-even = Cost Basis - Net Premium per Share + Fees per Share
+        if let Some(break_even) = self.stock_break_even()? {
+            self.break_even_points.push(break_even.round_to(2));
         }
 
         Ok(())
@@ -682,26 +675,26 @@ impl Strategies for Collar {
     fn get_max_loss(&self) -> Result<Positive, StrategyError> {
         self.max_loss_potential().map_err(StrategyError::from)
     }
+
+    fn buying_power_effect_with(&self, margin: &RegTMargin) -> Result<Positive, StrategyError> {
+        let report = self.stock_margin(margin);
+        Ok(Positive::new_decimal(report.buying_power_effect())?)
+    }
 }
 
 impl Profit for Collar {
     fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
-        // Spot P&L
-        let spot_pnl = self.spot_leg.pnl_at_price(*price);
-
-        // Put P&L at expiration
-        let put_pnl = self
-            .long_put
-            .pnl_at_expiration(&Some(price))
-            .unwrap_or(Decimal::ZERO);
+        self.stock_profit_at(price)
+    }
+}
 
-        // Call P&L at expiration
-        let call_pnl = self
-            .short_call
-            .pnl_at_expiration(&Some(price))
-            .unwrap_or(Decimal::ZERO);
+impl StockOptionStrategy for Collar {
+    fn stock_leg(&self) -> &StockLeg {
+        &self.spot_leg
+    }
 
-        Ok(spot_pnl + put_pnl + call_pnl)
+    fn option_legs(&self) -> Vec<&Position> {
+        vec![&self.long_put, &self.short_call]
     }
 }
 
@@ -1051,4 +1044,37 @@ mod tests {
 
         assert_eq!(positions.len(), 2);
     }
+
+    #[test]
+    fn test_collar_break_even_and_bounds() {
+        let collar = create_test_collar();
+        // 150 - (3.00 - 2.50) + (1 + 1 + 4 * 0.65) / 100
+        assert_eq!(collar.break_even_points[0], pos_or_panic!(149.55));
+
+        // (160 - 150) * 100 + 0.50 * 100 - 4.60
+        assert_eq!(collar.get_max_profit().unwrap(), pos_or_panic!(1045.4));
+        // (150 - 145) * 100 - 0.50 * 100 + 4.60
+        assert_eq!(collar.get_max_loss().unwrap(), pos_or_panic!(454.6));
+        assert_eq!(
+            collar.calculate_profit_at(&pos_or_panic!(100.0)).unwrap(),
+            dec!(-454.6)
+        );
+    }
+
+    #[test]
+    fn test_collar_assignment_outcome() {
+        let collar = create_test_collar();
+
+        let put_exercised = collar.assignment_outcome(&pos_or_panic!(120.0)).unwrap();
+        assert!(put_exercised.is_called_away());
+        assert_eq!(put_exercised.exercise_cash, dec!(14500));
+        assert_eq!(
+            put_exercised.pnl,
+            collar.calculate_profit_at(&pos_or_panic!(120.0)).unwrap()
+        );
+
+        let between = collar.assignment_outcome(&pos_or_panic!(150.0)).unwrap();
+        assert_eq!(between.shares_delivered, Positive::ZERO);
+        assert_eq!(between.remaining_shares, dec!(100));
+    }
 }
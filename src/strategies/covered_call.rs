@@ -59,14 +59,16 @@ use crate::greeks::Greeks;
 use crate::model::ExpirationDate;
 use crate::model::ProfitLossRange;
 use crate::model::leg::traits::LegAble;
-use crate::model::leg::{Leg, SpotPosition};
+use crate::model::leg::{Leg, SHARES_PER_CONTRACT, SpotPosition, StockLeg};
 use crate::model::position::Position;
 use crate::model::types::{OptionBasicType, OptionStyle, OptionType, Side};
 use crate::pnl::PnLCalculator;
 use crate::pricing::payoff::Profit;
+use crate::risk::RegTMargin;
 use crate::strategies::delta_neutral::DeltaNeutrality;
 use crate::strategies::probabilities::core::ProbabilityAnalysis;
 use crate::strategies::probabilities::utils::VolatilityAdjustment;
+use crate::strategies::shared::StockOptionStrategy;
 use crate::strategies::{BasicAble, Strategies};
 use chrono::Utc;
 use positive::Positive;
@@ -143,8 +145,8 @@ impl CoveredCall {
     /// * `premium_short_call` - The premium received for selling the call
     /// * `spot_open_fee` - Fee to open the spot position
     /// * `spot_close_fee` - Fee to close the spot position
-    /// * `call_open_fee` - Fee to open the call position
-    /// * `call_close_fee` - Fee to close the call position
+    /// * `call_open_fee` - Fee per contract to open the call position
+    /// * `call_close_fee` - Fee per contract to close the call position
     ///
     /// # Returns
     ///
@@ -185,7 +187,7 @@ impl CoveredCall {
             call_strike,
             expiration,
             implied_volatility,
-            quantity / Positive::HUNDRED, // Convert shares to contracts
+            quantity, // Option quantity is expressed in shares
             underlying_price,
             risk_free_rate,
             OptionStyle::Call,
@@ -197,8 +199,8 @@ impl CoveredCall {
             short_call_option,
             premium_short_call,
             Utc::now(),
-            call_open_fee,
-            call_close_fee,
+            call_open_fee / SHARES_PER_CONTRACT,
+            call_close_fee / SHARES_PER_CONTRACT,
             None,
             None,
         );
@@ -288,8 +290,7 @@ impl CoveredCall {
         let cost_basis = self.spot_leg.cost_basis;
         let quantity = self.spot_leg.quantity;
         let premium_received = self.short_call.premium * self.short_call.option.quantity;
-        let total_fees =
-            self.spot_leg.fees() + self.short_call.open_fee + self.short_call.close_fee;
+        let total_fees = self.combined_fees();
 
         if strike >= cost_basis {
             let capital_gain = (strike - cost_basis) * quantity;
@@ -313,8 +314,7 @@ impl CoveredCall {
         let cost_basis = self.spot_leg.cost_basis;
         let quantity = self.spot_leg.quantity;
         let premium_received = self.short_call.premium * self.short_call.option.quantity;
-        let total_fees =
-            self.spot_leg.fees() + self.short_call.open_fee + self.short_call.close_fee;
+        let total_fees = self.combined_fees();
 
         let total_investment = cost_basis * quantity;
         if total_investment + total_fees > premium_received {
@@ -379,18 +379,10 @@ impl BreakEvenable for CoveredCall {
     fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
         self.break_even_points.clear();
 
-        // Break-even = Cost Basis - Premium Received per Share
-        let premium_per_share =
-            self.short_call.premium * self.short_call.option.quantity / self.spot_leg.quantity;
-        let fees_per_share = self.spot_leg.fees() / self.spot_leg.quantity;
-
-        let break_even = if self.spot_leg.cost_basis > premium_per_share - fees_per_share {
-            self.spot_leg.cost_basis - premium_per_share + fees_per_share
-        } else {
-            Positive::ZERO
-        };
-
-        self.break_even_points.push(break_even.round_to(2));
+        // Break-even = Cost Basis - Premium Received per Share + Fees per Share
+        if let Some(break_even) = self.stock_break_even()? {
+            self.break_even_points.push(break_even.round_to(2));
+        }
         Ok(())
     }
 }
@@ -528,20 +520,26 @@ impl Strategies for CoveredCall {
     fn get_max_loss(&self) -> Result<Positive, StrategyError> {
         self.max_loss_potential().map_err(StrategyError::from)
     }
+
+    fn buying_power_effect_with(&self, margin: &RegTMargin) -> Result<Positive, StrategyError> {
+        let report = self.stock_margin(margin);
+        Ok(Positive::new_decimal(report.buying_power_effect())?)
+    }
 }
 
 impl Profit for CoveredCall {
     fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
-        // Spot P&L
-        let spot_pnl = self.spot_leg.pnl_at_price(*price);
+        self.stock_profit_at(price)
+    }
+}
 
-        // Option P&L at expiration
-        let option_pnl = self
-            .short_call
-            .pnl_at_expiration(&Some(price))
-            .unwrap_or(Decimal::ZERO);
+impl StockOptionStrategy for CoveredCall {
+    fn stock_leg(&self) -> &StockLeg {
+        &self.spot_leg
+    }
 
-        Ok(spot_pnl + option_pnl)
+    fn option_legs(&self) -> Vec<&Position> {
+        vec![&self.short_call]
     }
 }
 
@@ -829,4 +827,53 @@ mod tests {
         let cc = create_test_covered_call();
         assert_eq!(cc.quantity(), Positive::HUNDRED);
     }
+
+    #[test]
+    fn test_break_even_includes_full_premium_and_fees() {
+        let cc = create_test_covered_call();
+        // 150 - 3.50 + (1 + 1 + 0.65 + 0.65) / 100
+        assert_eq!(cc.break_even_points[0], pos_or_panic!(146.53));
+        let profit = cc.calculate_profit_at(&pos_or_panic!(146.533)).unwrap();
+        assert!(profit.abs() < dec!(0.001));
+    }
+
+    #[test]
+    fn test_max_profit_matches_profit_above_strike() {
+        let cc = create_test_covered_call();
+        let max_profit = cc.get_max_profit().unwrap();
+        // (155 - 150) * 100 + 3.50 * 100 - 3.30
+        assert_eq!(max_profit, pos_or_panic!(846.7));
+        let profit = cc.calculate_profit_at(&pos_or_panic!(170.0)).unwrap();
+        assert_eq!(profit, max_profit.to_dec());
+    }
+
+    #[test]
+    fn test_assignment_outcome() {
+        let cc = create_test_covered_call();
+
+        let assigned = cc.assignment_outcome(&pos_or_panic!(170.0)).unwrap();
+        assert!(assigned.is_called_away());
+        assert_eq!(assigned.shares_delivered, Positive::HUNDRED);
+        assert_eq!(assigned.exercise_cash, dec!(15500));
+        assert_eq!(assigned.remaining_shares, Decimal::ZERO);
+        assert_eq!(
+            assigned.pnl,
+            cc.calculate_profit_at(&pos_or_panic!(170.0)).unwrap()
+        );
+
+        let kept = cc.assignment_outcome(&pos_or_panic!(140.0)).unwrap();
+        assert!(!kept.is_called_away());
+        assert_eq!(kept.remaining_shares, dec!(100));
+        assert_eq!(
+            kept.pnl,
+            cc.calculate_profit_at(&pos_or_panic!(140.0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_buying_power_effect_counts_stock_leg() {
+        let cc = create_test_covered_call();
+        // Half of the stock value, less the call premium received.
+        assert_eq!(cc.buying_power_effect().unwrap(), pos_or_panic!(7150.0));
+    }
 }
@@ -24,7 +24,9 @@ use crate::{
     pricing::payoff::Profit,
     strategies::{
         BasicAble, DeltaAdjustment, Strategies, StrategyConstructor,
+        break_even::BreakEvenSolver,
         delta_neutral::DeltaNeutrality,
+        payoff_extremes::payoff_extremes,
         probabilities::{core::ProbabilityAnalysis, utils::VolatilityAdjustment},
         utils::{FindOptimalSide, OptimizationCriteria},
     },
@@ -266,6 +268,55 @@ impl CustomStrategy {
         }
     }
 
+    /// Price range scanned for break-even points.
+    ///
+    /// Starts at zero and extends half way beyond the highest strike or the
+    /// underlying price, whichever is larger.
+    fn break_even_search_range(&self) -> (Positive, Positive) {
+        let max_strike = self
+            .positions
+            .iter()
+            .map(|position| position.option.strike_price)
+            .max()
+            .unwrap_or(Positive::ZERO);
+        (
+            Positive::ZERO,
+            max_strike.max(self.underlying_price) * pos_or_panic!(1.5),
+        )
+    }
+
+    /// Finds the break-even points of the aggregate payoff at expiration.
+    ///
+    /// The payoff is scanned by a [`BreakEvenSolver`] on a grid that includes
+    /// every strike, and crossings beyond the scanned range are solved on the
+    /// linear tail of the payoff. Strategies that are always profitable or
+    /// always losing have no break-even points.
+    ///
+    /// # Returns
+    /// The break-even points in ascending order, rounded to two decimals.
+    ///
+    /// # Errors
+    /// Returns a `PricingError` if the payoff cannot be evaluated.
+    pub fn find_break_even_points(&self) -> Result<Vec<Positive>, PricingError> {
+        let (lower, upper) = self.break_even_search_range();
+        if upper == Positive::ZERO {
+            return Ok(Vec::new());
+        }
+        let solver = BreakEvenSolver::new();
+        let strikes: Vec<Positive> = self
+            .positions
+            .iter()
+            .map(|position| position.option.strike_price)
+            .collect();
+        let mut break_even_points: Vec<Positive> = solver
+            .solve_with_linear_tail(self, lower, upper, &strikes)?
+            .into_iter()
+            .map(|point| point.round_to(2))
+            .collect();
+        break_even_points.dedup();
+        Ok(break_even_points)
+    }
+
     pub(crate) fn get_profit_loss_zones(
         &self,
         break_even_points: &[Positive],
@@ -361,25 +412,7 @@ impl BreakEvenable for CustomStrategy {
     }
 
     fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
-        // Simple implementation - calculate break-even points by finding where profit = 0
-        self.break_even_points.clear();
-
-        // Get a reasonable price range
-        let min_price = self.underlying_price * pos_or_panic!(0.5);
-        let max_price = self.underlying_price * pos_or_panic!(1.5);
-        let step = pos_or_panic!(0.01);
-
-        let mut current_price = min_price;
-        while current_price <= max_price {
-            if let Ok(profit) = self.calculate_profit_at(&current_price)
-                && profit.abs() < rust_decimal::Decimal::new(1, 2)
-            {
-                // Close to zero
-                self.break_even_points.push(current_price);
-            }
-            current_price += step;
-        }
-
+        self.break_even_points = self.find_break_even_points()?;
         Ok(())
     }
 }
@@ -612,64 +645,14 @@ impl Strategies for CustomStrategy {
         if self.positions.is_empty() {
             return Ok(Positive::ZERO);
         }
-
-        let (min_price, max_price) = self.range_to_show()?;
-        let step = (max_price - min_price) / pos_or_panic!(50.0); // Use 50 steps max
-        let mut max_profit = Decimal::ZERO;
-        let mut current_price = min_price;
-
-        // Limit iterations to prevent infinite loops
-        let max_iterations = 100;
-        let mut iterations = 0;
-
-        while current_price <= max_price && iterations < max_iterations {
-            if let Ok(current_profit) = self.calculate_profit_at(&current_price)
-                && current_profit > max_profit
-            {
-                max_profit = current_profit;
-            }
-            current_price += step;
-            iterations += 1;
-        }
-
-        // If max_profit is still zero or negative, return zero
-        if max_profit <= Decimal::ZERO {
-            Ok(Positive::ZERO)
-        } else {
-            Ok(Positive::new_decimal(max_profit)?)
-        }
+        payoff_extremes(self)?.max_profit_amount()
     }
 
     fn get_max_loss(&self) -> Result<Positive, StrategyError> {
         if self.positions.is_empty() {
             return Ok(Positive::ZERO);
         }
-
-        let (min_price, max_price) = self.range_to_show()?;
-        let step = (max_price - min_price) / pos_or_panic!(50.0); // Use 50 steps max
-        let mut max_loss = Decimal::ZERO;
-        let mut current_price = min_price;
-
-        // Limit iterations to prevent infinite loops
-        let max_iterations = 100;
-        let mut iterations = 0;
-
-        while current_price <= max_price && iterations < max_iterations {
-            if let Ok(current_profit) = self.calculate_profit_at(&current_price)
-                && current_profit < max_loss
-            {
-                max_loss = current_profit;
-            }
-            current_price += step;
-            iterations += 1;
-        }
-
-        // Return absolute value of max loss
-        if max_loss >= Decimal::ZERO {
-            Ok(Positive::ZERO)
-        } else {
-            Ok(Positive::new_decimal(-max_loss)?)
-        }
+        payoff_extremes(self)?.max_loss_amount()
     }
 
     fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
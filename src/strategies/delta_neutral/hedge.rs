@@ -0,0 +1,212 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Delta Hedging Module
+//!
+//! Sizes a hedge in the underlying, or in futures on the underlying, that
+//! neutralizes the delta of an options position.
+//!
+//! Option Greeks in this crate are expressed per unit of underlying, so they
+//! are first converted to share-equivalents with the contract multiplier of
+//! the options. The hedge is rounded to whole shares or futures contracts and
+//! the delta left over by the rounding is reported, together with the gamma
+//! and vega the hedge cannot remove.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! let hedge = strategy.delta_hedge(&DeltaHedgeConfig::default())?;
+//! println!("{:?} {} shares", hedge.action, hedge.quantity);
+//! ```
+
+use crate::greeks::NetGreeks;
+use crate::model::types::Action;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Standard number of shares controlled by one equity option contract.
+pub const DEFAULT_OPTION_MULTIPLIER: Positive = Positive::HUNDRED;
+
+/// Instrument used to hedge the delta of a position.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum HedgeInstrument {
+    /// Shares of the underlying, each with a delta of one.
+    Shares,
+    /// Futures on the underlying, each worth `contract_size` units of it.
+    Futures {
+        /// Units of the underlying represented by one futures contract.
+        contract_size: Positive,
+    },
+}
+
+impl HedgeInstrument {
+    /// Share-equivalent delta of one unit of the instrument.
+    pub fn unit_delta(&self) -> Decimal {
+        match self {
+            HedgeInstrument::Shares => Decimal::ONE,
+            HedgeInstrument::Futures { contract_size } => contract_size.to_dec(),
+        }
+    }
+}
+
+/// Parameters of a delta hedge.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DeltaHedgeConfig {
+    /// Units of the underlying controlled by one option contract.
+    pub option_multiplier: Positive,
+    /// Instrument traded to hedge the delta.
+    pub instrument: HedgeInstrument,
+}
+
+impl Default for DeltaHedgeConfig {
+    fn default() -> Self {
+        DeltaHedgeConfig {
+            option_multiplier: DEFAULT_OPTION_MULTIPLIER,
+            instrument: HedgeInstrument::Shares,
+        }
+    }
+}
+
+impl DeltaHedgeConfig {
+    /// Creates a configuration for the given multiplier and instrument.
+    pub fn new(option_multiplier: Positive, instrument: HedgeInstrument) -> Self {
+        DeltaHedgeConfig {
+            option_multiplier,
+            instrument,
+        }
+    }
+}
+
+/// Trade that neutralizes the delta of a position and the exposure left after it.
+///
+/// Deltas, gammas and vegas are expressed in share-equivalents, i.e. already
+/// multiplied by the option contract multiplier.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DeltaHedge {
+    /// Instrument traded.
+    pub instrument: HedgeInstrument,
+    /// Whether the instrument is bought or sold.
+    pub action: Action,
+    /// Whole units of the instrument to trade.
+    pub quantity: Positive,
+    /// Signed number of units that would neutralize the delta exactly.
+    pub exact_quantity: Decimal,
+    /// Delta of the position before hedging.
+    pub position_delta: Decimal,
+    /// Delta left after trading `quantity` whole units.
+    pub residual_delta: Decimal,
+    /// Gamma of the hedged position; the hedge itself has no gamma.
+    pub residual_gamma: Decimal,
+    /// Vega of the hedged position; the hedge itself has no vega.
+    pub residual_vega: Decimal,
+}
+
+impl DeltaHedge {
+    /// Sizes the hedge for a position with the given net Greeks.
+    pub fn from_net_greeks(greeks: &NetGreeks, config: &DeltaHedgeConfig) -> Self {
+        let multiplier = config.option_multiplier.to_dec();
+        let position_delta = greeks.delta * multiplier;
+        let unit_delta = config.instrument.unit_delta();
+
+        let exact_quantity = -position_delta / unit_delta;
+        let rounded = exact_quantity.round();
+        let action = if rounded < Decimal::ZERO {
+            Action::Sell
+        } else {
+            Action::Buy
+        };
+
+        DeltaHedge {
+            instrument: config.instrument,
+            action,
+            quantity: Positive::new_decimal(rounded.abs()).unwrap_or(Positive::ZERO),
+            exact_quantity,
+            position_delta,
+            residual_delta: position_delta + rounded * unit_delta,
+            residual_gamma: greeks.gamma * multiplier,
+            residual_vega: greeks.vega * multiplier,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_delta_hedge {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::greeks::Greeks;
+    use crate::strategies::{DeltaNeutrality, LongCall};
+    use rust_decimal_macros::dec;
+
+    fn greeks(delta: Decimal) -> NetGreeks {
+        NetGreeks {
+            delta,
+            gamma: dec!(0.05),
+            theta: dec!(-0.02),
+            vega: dec!(0.12),
+            rho: dec!(0.04),
+        }
+    }
+
+    #[test]
+    fn test_long_delta_is_hedged_by_selling_shares() {
+        let hedge = DeltaHedge::from_net_greeks(&greeks(dec!(2.537)), &DeltaHedgeConfig::default());
+        assert_eq!(hedge.position_delta, dec!(253.7));
+        assert_eq!(hedge.exact_quantity, dec!(-253.7));
+        assert_eq!(hedge.action, Action::Sell);
+        assert_eq!(hedge.quantity, Positive::new(254.0).unwrap());
+        assert_eq!(hedge.residual_delta, dec!(-0.3));
+        assert_eq!(hedge.residual_gamma, dec!(5));
+        assert_eq!(hedge.residual_vega, dec!(12));
+    }
+
+    #[test]
+    fn test_short_delta_is_hedged_with_futures() {
+        let config = DeltaHedgeConfig::new(
+            Positive::new(50.0).unwrap(),
+            HedgeInstrument::Futures {
+                contract_size: Positive::new(50.0).unwrap(),
+            },
+        );
+        let hedge = DeltaHedge::from_net_greeks(&greeks(dec!(-3.4)), &config);
+        assert_eq!(hedge.position_delta, dec!(-170));
+        assert_eq!(hedge.exact_quantity, dec!(3.4));
+        assert_eq!(hedge.action, Action::Buy);
+        assert_eq!(hedge.quantity, Positive::new(3.0).unwrap());
+        assert_eq!(hedge.residual_delta, dec!(-20));
+    }
+
+    #[test]
+    fn test_neutral_position_needs_no_trade() {
+        let hedge = DeltaHedge::from_net_greeks(&greeks(dec!(0.001)), &DeltaHedgeConfig::default());
+        assert_eq!(hedge.quantity, Positive::ZERO);
+        assert_eq!(hedge.residual_delta, dec!(0.1));
+    }
+
+    #[test]
+    fn test_strategy_delta_hedge() {
+        let strategy = LongCall::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(Positive::new(30.0).unwrap()),
+            Positive::new(0.2).unwrap(),
+            Positive::TWO,
+            Positive::HUNDRED,
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::new(3.0).unwrap(),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let hedge = strategy.delta_hedge(&DeltaHedgeConfig::default()).unwrap();
+        let net = strategy.net_greeks().unwrap();
+        assert_eq!(hedge.action, Action::Sell);
+        assert_eq!(hedge.position_delta, net.delta * dec!(100));
+        assert!(hedge.residual_delta.abs() <= dec!(0.5));
+        assert!(hedge.residual_gamma > Decimal::ZERO);
+    }
+}
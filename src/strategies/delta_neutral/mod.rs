@@ -43,11 +43,13 @@
 //! - Portfolio-level Greeks aggregation and optimization.
 //!
 pub mod adjustment;
+pub mod hedge;
 mod model;
 pub mod optimizer;
 pub mod portfolio;
 
 pub use adjustment::{AdjustmentAction, AdjustmentConfig, AdjustmentError, AdjustmentPlan};
+pub use hedge::{DEFAULT_OPTION_MULTIPLIER, DeltaHedge, DeltaHedgeConfig, HedgeInstrument};
 pub use model::{
     DELTA_THRESHOLD, DeltaAdjustment, DeltaInfo, DeltaNeutralResponse, DeltaNeutrality,
     DeltaPositionInfo,
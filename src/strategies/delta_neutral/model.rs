@@ -4,6 +4,7 @@
    Date: 10/12/24
 ******************************************************************************/
 use super::adjustment::{AdjustmentConfig, AdjustmentPlan};
+use super::hedge::{DeltaHedge, DeltaHedgeConfig};
 use super::optimizer::AdjustmentOptimizer;
 use super::portfolio::{AdjustmentTarget, PortfolioGreeks};
 use crate::error::position::PositionValidationErrorKind;
@@ -1095,6 +1096,21 @@ pub trait DeltaNeutrality: Greeks + Positionable + Strategies {
         }
     }
 
+    /// Sizes the trade in the underlying or its futures that neutralizes the
+    /// delta of the strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Option contract multiplier and hedging instrument
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DeltaHedge)` - Units to trade and the residual delta, gamma and vega
+    /// * `Err(GreeksError)` - If the Greeks of the strategy cannot be calculated
+    fn delta_hedge(&self, config: &DeltaHedgeConfig) -> Result<DeltaHedge, GreeksError> {
+        Ok(DeltaHedge::from_net_greeks(&self.net_greeks()?, config))
+    }
+
     /// Returns the delta gap from a target value.
     ///
     /// # Arguments
@@ -1514,6 +1530,7 @@ mod tests_serialization {
 mod tests_generate_delta_adjustments {
     use super::*;
     use crate::ExpirationDate;
+    use crate::model::types::{DayCount, SettlementType};
     use crate::strategies::base::BreakEvenable;
     use crate::strategies::{BasicAble, Validable};
     use positive::pos_or_panic;
@@ -1546,6 +1563,8 @@ mod tests_generate_delta_adjustments {
             option_style,
             dividend_yield: pos_or_panic!(0.01),
             exotic_params: None,
+            settlement_type: SettlementType::Physical,
+            day_count: DayCount::Act365Fixed,
         }
     }
 
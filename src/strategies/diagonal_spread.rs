@@ -0,0 +1,898 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Diagonal Spread Strategy
+//!
+//! A diagonal spread sells a near-term option and buys a longer-dated option
+//! of the same style at a different strike. It combines the time decay edge of
+//! a calendar spread with the directional bias of a vertical spread.
+//!
+//! ## Key Characteristics
+//!
+//! - Like the calendar spread, it is analysed at the near leg's expiration:
+//!   the near leg settles at intrinsic value and the far leg is marked with
+//!   Black-Scholes using its remaining time.
+//! - The short leg can be sold again each time it expires. The roll metrics
+//!   measure how much of the far leg's cost each short sale recovers.
+//! - The maximum loss is estimated by scanning the P&L at the near expiration.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use optionstratlib::strategies::diagonal_spread::DiagonalSpread;
+//! use optionstratlib::model::ExpirationDate;
+//! use optionstratlib::OptionStyle;
+//! use positive::{pos_or_panic, Positive};
+//! use rust_decimal_macros::dec;
+//!
+//! let diagonal = DiagonalSpread::new(
+//!     "AAPL".to_string(),
+//!     pos_or_panic!(150.0),                      // underlying price
+//!     pos_or_panic!(155.0),                      // near strike
+//!     pos_or_panic!(145.0),                      // far strike
+//!     OptionStyle::Call,
+//!     ExpirationDate::Days(pos_or_panic!(30.0)), // near expiration
+//!     ExpirationDate::Days(pos_or_panic!(90.0)), // far expiration
+//!     pos_or_panic!(0.25),                       // near implied volatility
+//!     pos_or_panic!(0.23),                       // far implied volatility
+//!     dec!(0.05),                                // risk-free rate
+//!     Positive::ZERO,                            // dividend yield
+//!     Positive::ONE,                             // quantity
+//!     pos_or_panic!(2.60),                       // near premium received
+//!     pos_or_panic!(11.90),                      // far premium paid
+//!     Positive::ZERO,                            // near open fee
+//!     Positive::ZERO,                            // near close fee
+//!     Positive::ZERO,                            // far open fee
+//!     Positive::ZERO,                            // far close fee
+//! );
+//! let metrics = diagonal.roll_metrics().unwrap();
+//! assert_eq!(metrics.remaining_rolls, 2);
+//! ```
+
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use crate::Options;
+use crate::error::OperationErrorKind;
+use crate::error::position::PositionValidationErrorKind;
+use crate::error::probability::ProbabilityError;
+use crate::error::strategies::ProfitLossErrorKind;
+use crate::error::{GreeksError, PositionError, PricingError, StrategyError};
+use crate::greeks::Greeks;
+use crate::model::ExpirationDate;
+use crate::model::ProfitLossRange;
+use crate::model::position::Position;
+use crate::model::types::{OptionBasicType, OptionStyle, OptionType, Side};
+use crate::pnl::PnLCalculator;
+use crate::pricing::payoff::Profit;
+use crate::strategies::delta_neutral::DeltaNeutrality;
+use crate::strategies::probabilities::core::ProbabilityAnalysis;
+use crate::strategies::probabilities::utils::VolatilityAdjustment;
+use crate::strategies::shared::TimeSpreadStrategy;
+use crate::strategies::{BasicAble, Strategies, StrategyConstructor};
+use chrono::Utc;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+use utoipa::ToSchema;
+
+/// Default description for the Diagonal Spread strategy.
+pub const DIAGONAL_SPREAD_DESCRIPTION: &str = "A diagonal spread sells a near-term option and \
+    buys a longer-dated option of the same style at a different strike. It earns the faster time \
+    decay of the near leg while keeping a directional bias set by the distance between the \
+    strikes. The short leg can be rolled each time it expires to keep reducing the cost of the \
+    long leg.";
+
+/// Roll yield metrics of a diagonal spread.
+///
+/// The short leg of a diagonal can be sold again every time it expires until
+/// the far leg expires. These metrics measure how much of the far leg's cost
+/// each short sale recovers, assuming every roll collects the current near
+/// premium.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct DiagonalRollMetrics {
+    /// Days until the near leg expires.
+    pub near_days: Positive,
+    /// Days until the far leg expires.
+    pub far_days: Positive,
+    /// Premium received for the near leg, times quantity.
+    pub near_premium: Positive,
+    /// Premium paid for the far leg, times quantity.
+    pub far_premium: Positive,
+    /// Near premium divided by far premium.
+    pub roll_yield: Decimal,
+    /// Roll yield scaled to a 365 day year using the near leg's cycle.
+    pub annualized_roll_yield: Decimal,
+    /// Number of short sales needed to pay for the far leg.
+    pub cycles_to_recover_cost: Decimal,
+    /// Full near cycles left after the current one before the far leg expires.
+    pub remaining_rolls: usize,
+    /// Estimated maximum loss at the near expiration.
+    pub max_loss_at_near_expiration: Positive,
+}
+
+/// Represents a Diagonal Spread options strategy.
+///
+/// The strategy is valued at the near leg's expiration: the near leg settles
+/// at intrinsic value and the far leg is marked with Black-Scholes using the
+/// time it has left.
+///
+/// # Structure
+///
+/// - **Near Leg**: Short option expiring first
+/// - **Far Leg**: Long option of the same style expiring later at a different strike
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DiagonalSpread {
+    /// The name of the strategy.
+    pub name: String,
+
+    /// The type of strategy (StrategyType::DiagonalSpread).
+    pub kind: StrategyType,
+
+    /// A textual description of this strategy instance.
+    pub description: String,
+
+    /// The price points at which the strategy breaks even at the near expiration.
+    pub break_even_points: Vec<Positive>,
+
+    /// The short option expiring first.
+    pub near_leg: Position,
+
+    /// The long option expiring last.
+    pub far_leg: Position,
+}
+
+impl DiagonalSpread {
+    /// Creates a new Diagonal Spread strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset
+    /// * `underlying_price` - The current market price of the underlying asset
+    /// * `near_strike` - The strike price of the short leg
+    /// * `far_strike` - The strike price of the long leg
+    /// * `option_style` - Whether both legs are calls or puts
+    /// * `near_expiration` - The expiration date of the short leg
+    /// * `far_expiration` - The expiration date of the long leg
+    /// * `near_implied_volatility` - The implied volatility of the short leg
+    /// * `far_implied_volatility` - The implied volatility of the long leg
+    /// * `risk_free_rate` - The risk-free interest rate
+    /// * `dividend_yield` - The dividend yield of the underlying asset
+    /// * `quantity` - The number of contracts of each leg
+    /// * `premium_near` - The premium received for selling the near leg
+    /// * `premium_far` - The premium paid for buying the far leg
+    /// * `open_fee_near` - Fee to open the near leg
+    /// * `close_fee_near` - Fee to close the near leg
+    /// * `open_fee_far` - Fee to open the far leg
+    /// * `close_fee_far` - Fee to close the far leg
+    ///
+    /// # Returns
+    ///
+    /// A fully configured `DiagonalSpread` strategy instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if break-even point calculation fails.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        near_strike: Positive,
+        far_strike: Positive,
+        option_style: OptionStyle,
+        near_expiration: ExpirationDate,
+        far_expiration: ExpirationDate,
+        near_implied_volatility: Positive,
+        far_implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_near: Positive,
+        premium_far: Positive,
+        open_fee_near: Positive,
+        close_fee_near: Positive,
+        open_fee_far: Positive,
+        close_fee_far: Positive,
+    ) -> Self {
+        let near_option = Options::new(
+            OptionType::European,
+            Side::Short,
+            underlying_symbol.clone(),
+            near_strike,
+            near_expiration,
+            near_implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let near_leg = Position::new(
+            near_option,
+            premium_near,
+            Utc::now(),
+            open_fee_near,
+            close_fee_near,
+            None,
+            None,
+        );
+
+        let far_option = Options::new(
+            OptionType::European,
+            Side::Long,
+            underlying_symbol,
+            far_strike,
+            far_expiration,
+            far_implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let far_leg = Position::new(
+            far_option,
+            premium_far,
+            Utc::now(),
+            open_fee_far,
+            close_fee_far,
+            None,
+            None,
+        );
+
+        let mut strategy = DiagonalSpread {
+            name: "Diagonal Spread".to_string(),
+            kind: StrategyType::DiagonalSpread,
+            description: DIAGONAL_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_leg,
+            far_leg,
+        };
+
+        strategy.validate();
+        strategy
+            .update_break_even_points()
+            .expect("Failed to calculate break-even points");
+
+        strategy
+    }
+
+    /// Returns the strike price of the short leg.
+    #[must_use]
+    pub fn near_strike(&self) -> Positive {
+        self.near_leg.option.strike_price
+    }
+
+    /// Returns the strike price of the long leg.
+    #[must_use]
+    pub fn far_strike(&self) -> Positive {
+        self.far_leg.option.strike_price
+    }
+
+    /// Returns the option style shared by both legs.
+    #[must_use]
+    pub fn option_style(&self) -> OptionStyle {
+        self.near_leg.option.option_style
+    }
+
+    /// Calculates the net debit paid to open the spread, excluding fees.
+    ///
+    /// Net Debit = Far Premium Paid - Near Premium Received
+    #[must_use]
+    pub fn net_debit(&self) -> Decimal {
+        let paid = self.far_leg.premium * self.far_leg.option.quantity;
+        let received = self.near_leg.premium * self.near_leg.option.quantity;
+        paid.to_dec() - received.to_dec()
+    }
+
+    /// Estimates the maximum loss at the near leg's expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if the P&L cannot be computed.
+    pub fn max_loss_at_near_expiration(&self) -> Result<Positive, StrategyError> {
+        let (lower, upper) = self.scan_range();
+        let (min_profit, _) = self.near_expiration_extremes(lower, upper)?;
+        Ok(Positive::new_decimal(-min_profit.min(Decimal::ZERO))?)
+    }
+
+    /// Calculates the roll yield metrics of the spread.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if the expirations cannot be resolved, the far
+    /// leg has no premium or the maximum loss cannot be estimated.
+    pub fn roll_metrics(&self) -> Result<DiagonalRollMetrics, StrategyError> {
+        let near_days = self
+            .near_leg
+            .option
+            .expiration_date
+            .get_days()
+            .map_err(PricingError::from)?;
+        let far_days = self
+            .far_leg
+            .option
+            .expiration_date
+            .get_days()
+            .map_err(PricingError::from)?;
+        let near_premium = self.near_leg.premium * self.near_leg.option.quantity;
+        let far_premium = self.far_leg.premium * self.far_leg.option.quantity;
+        if far_premium == Positive::ZERO || near_days == Positive::ZERO {
+            return Err(StrategyError::operation_not_supported(
+                "roll_metrics",
+                "Diagonal Spread without far premium or near time",
+            ));
+        }
+
+        let roll_yield = near_premium.to_dec() / far_premium.to_dec();
+        let cycles_to_recover_cost = if near_premium > Positive::ZERO {
+            far_premium.to_dec() / near_premium.to_dec()
+        } else {
+            Decimal::MAX
+        };
+        let remaining_rolls = (self.days_between_expirations()? / near_days)
+            .floor()
+            .to_dec()
+            .try_into()
+            .unwrap_or(0);
+
+        Ok(DiagonalRollMetrics {
+            near_days,
+            far_days,
+            near_premium,
+            far_premium,
+            roll_yield,
+            annualized_roll_yield: roll_yield * Decimal::from(365) / near_days.to_dec(),
+            cycles_to_recover_cost,
+            remaining_rolls,
+            max_loss_at_near_expiration: self.max_loss_at_near_expiration()?,
+        })
+    }
+
+    /// Price range scanned when searching for break-evens and extremes.
+    fn scan_range(&self) -> (Positive, Positive) {
+        let lower = self.near_strike().min(self.far_strike());
+        let upper = self.near_strike().max(self.far_strike());
+        (lower * Decimal::new(5, 1), upper * Decimal::new(15, 1))
+    }
+}
+
+impl TimeSpreadStrategy for DiagonalSpread {
+    fn near_leg(&self) -> &Position {
+        &self.near_leg
+    }
+
+    fn far_leg(&self) -> &Position {
+        &self.far_leg
+    }
+}
+
+impl Validable for DiagonalSpread {
+    fn validate(&self) -> bool {
+        if !self.near_leg.validate() || !self.far_leg.validate() {
+            debug!("Invalid: Diagonal spread legs are not valid");
+            return false;
+        }
+
+        if self.near_leg.option.side != Side::Short || self.far_leg.option.side != Side::Long {
+            debug!("Invalid: Diagonal spread must sell the near leg and buy the far leg");
+            return false;
+        }
+
+        if self.near_leg.option.option_style != self.far_leg.option.option_style {
+            debug!("Invalid: Both legs must share the same option style");
+            return false;
+        }
+
+        if self.near_strike() == self.far_strike() {
+            debug!("Invalid: Diagonal spread legs must have different strikes");
+            return false;
+        }
+
+        match (
+            self.near_leg.option.expiration_date.get_days(),
+            self.far_leg.option.expiration_date.get_days(),
+        ) {
+            (Ok(near), Ok(far)) if near < far => true,
+            _ => {
+                debug!("Invalid: The near leg must expire before the far leg");
+                false
+            }
+        }
+    }
+}
+
+impl BreakEvenable for DiagonalSpread {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        let (lower, upper) = self.scan_range();
+        self.break_even_points = self.near_expiration_break_evens(lower, upper)?;
+        Ok(())
+    }
+}
+
+impl Positionable for DiagonalSpread {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match position.option.side {
+            Side::Short => self.near_leg = position.clone(),
+            Side::Long => self.far_leg = position.clone(),
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(vec![&self.near_leg, &self.far_leg])
+    }
+
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        match side {
+            Side::Short
+                if *option_style == self.option_style() && *strike == self.near_strike() =>
+            {
+                Ok(vec![&mut self.near_leg])
+            }
+            Side::Long if *option_style == self.option_style() && *strike == self.far_strike() => {
+                Ok(vec![&mut self.far_leg])
+            }
+            _ => Err(PositionError::invalid_position(
+                "Position not found in Diagonal Spread",
+            )),
+        }
+    }
+
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+
+        if position.option.option_style != self.option_style() {
+            return Err(PositionError::invalid_position(
+                "Position does not match existing diagonal spread positions",
+            ));
+        }
+
+        self.add_position(position)
+    }
+}
+
+impl StrategyConstructor for DiagonalSpread {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        if vec_positions.len() != 2 {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Diagonal Spread get_strategy".to_string(),
+                    reason: "Must have exactly 2 options".to_string(),
+                },
+            ));
+        }
+
+        let near_leg = vec_positions
+            .iter()
+            .find(|position| position.option.side == Side::Short);
+        let far_leg = vec_positions
+            .iter()
+            .find(|position| position.option.side == Side::Long);
+        let (Some(near_leg), Some(far_leg)) = (near_leg, far_leg) else {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Diagonal Spread get_strategy".to_string(),
+                    reason: "Diagonal Spread requires a short near leg and a long far leg"
+                        .to_string(),
+                },
+            ));
+        };
+
+        let mut strategy = DiagonalSpread {
+            name: "Diagonal Spread".to_string(),
+            kind: StrategyType::DiagonalSpread,
+            description: DIAGONAL_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_leg: near_leg.clone(),
+            far_leg: far_leg.clone(),
+        };
+
+        if !strategy.validate() {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Diagonal Spread get_strategy".to_string(),
+                    reason: "Legs must share style, have different strikes and the short leg \
+                        must expire first"
+                        .to_string(),
+                },
+            ));
+        }
+
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl Strategable for DiagonalSpread {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for DiagonalSpread {
+    fn get_title(&self) -> String {
+        format!(
+            "Diagonal Spread Strategy:\n\t{}\n\t{}",
+            self.near_leg.get_title(),
+            self.far_leg.get_title()
+        )
+    }
+
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            })
+            .collect()
+    }
+
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.implied_volatility,
+                )
+            })
+            .collect()
+    }
+
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.quantity,
+                )
+            })
+            .collect()
+    }
+
+    fn one_option(&self) -> &Options {
+        self.near_leg.one_option()
+    }
+
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.near_leg.one_option_mut()
+    }
+
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        for leg in [&mut self.near_leg, &mut self.far_leg] {
+            leg.option.underlying_price = *price;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        self.update_break_even_points()
+    }
+
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        for leg in [&mut self.near_leg, &mut self.far_leg] {
+            leg.option.implied_volatility = *volatility;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        self.update_break_even_points()
+    }
+}
+
+impl Strategies for DiagonalSpread {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        let (lower, upper) = self.scan_range();
+        let (_, max_profit) = self.near_expiration_extremes(lower, upper)?;
+        if max_profit <= Decimal::ZERO {
+            return Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxProfitError {
+                    reason: "Diagonal spread has no profitable price at the near expiration"
+                        .to_string(),
+                },
+            ));
+        }
+        Ok(Positive::new_decimal(max_profit)?)
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        self.max_loss_at_near_expiration()
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        let (lower, upper) = match self.break_even_points.as_slice() {
+            [lower, upper] => (*lower, *upper),
+            _ => return Ok(Decimal::ZERO),
+        };
+        let max_profit = self.get_max_profit().unwrap_or(Positive::ZERO);
+        Ok((upper - lower).to_dec() * max_profit.to_dec() / Decimal::TWO)
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        match (self.get_max_profit(), self.get_max_loss()) {
+            (Ok(profit), Ok(loss)) if loss > Positive::ZERO => {
+                Ok(profit.to_dec() / loss.to_dec() * Decimal::ONE_HUNDRED)
+            }
+            _ => Ok(Decimal::ZERO),
+        }
+    }
+}
+
+impl Profit for DiagonalSpread {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        self.profit_at_near_expiration(price)
+    }
+}
+
+impl Greeks for DiagonalSpread {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.near_leg.option, &self.far_leg.option])
+    }
+}
+
+impl PnLCalculator for DiagonalSpread {
+    fn calculate_pnl(
+        &self,
+        underlying_price: &Positive,
+        _expiration_date: ExpirationDate,
+        _implied_volatility: &Positive,
+    ) -> Result<crate::pnl::utils::PnL, PricingError> {
+        self.calculate_pnl_at_expiration(underlying_price)
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<crate::pnl::utils::PnL, PricingError> {
+        Ok(crate::pnl::utils::PnL {
+            realized: None,
+            unrealized: Some(self.calculate_profit_at(underlying_price)?),
+            initial_costs: self.far_leg.premium * self.far_leg.option.quantity,
+            initial_income: self.near_leg.premium * self.near_leg.option.quantity,
+            date_time: Utc::now(),
+        })
+    }
+}
+
+impl DeltaNeutrality for DiagonalSpread {}
+
+impl Optimizable for DiagonalSpread {
+    type Strategy = DiagonalSpread;
+}
+
+impl ProbabilityAnalysis for DiagonalSpread {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let (lower, upper) = match self.break_even_points.as_slice() {
+            [lower, upper] => (Some(*lower), Some(*upper)),
+            [single] if self.calculate_profit_at(&(*single * Decimal::TWO))? > Decimal::ZERO => {
+                (Some(*single), None)
+            }
+            [single] => (None, Some(*single)),
+            _ => return Err(ProbabilityError::from("No break-even point found")),
+        };
+
+        let option = &self.far_leg.option;
+        let mut profit_range = ProfitLossRange::new(lower, upper, Positive::ZERO)?;
+        profit_range.calculate_probability(
+            &option.underlying_price,
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &self.near_leg.option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![profit_range])
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let mut ranges = match self.break_even_points.as_slice() {
+            [lower, upper] => vec![
+                ProfitLossRange::new(None, Some(*lower), Positive::ZERO)?,
+                ProfitLossRange::new(Some(*upper), None, Positive::ZERO)?,
+            ],
+            [single] if self.calculate_profit_at(&(*single * Decimal::TWO))? > Decimal::ZERO => {
+                vec![ProfitLossRange::new(None, Some(*single), Positive::ZERO)?]
+            }
+            [single] => vec![ProfitLossRange::new(Some(*single), None, Positive::ZERO)?],
+            _ => return Err(ProbabilityError::from("No break-even point found")),
+        };
+
+        let option = &self.far_leg.option;
+        for range in ranges.iter_mut() {
+            range.calculate_probability(
+                &option.underlying_price,
+                Some(VolatilityAdjustment {
+                    base_volatility: option.implied_volatility,
+                    std_dev_adjustment: Positive::ZERO,
+                }),
+                None,
+                &self.near_leg.option.expiration_date,
+                Some(option.risk_free_rate),
+            )?;
+        }
+
+        Ok(ranges)
+    }
+}
+
+impl std::fmt::Display for DiagonalSpread {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Diagonal Spread: Short {} {} @ {} ({}) + Long {} {} @ {} ({})",
+            self.near_leg.option.option_style,
+            self.near_leg.option.strike_price,
+            self.near_leg.premium,
+            self.near_leg.option.expiration_date,
+            self.far_leg.option.option_style,
+            self.far_leg.option.strike_price,
+            self.far_leg.premium,
+            self.far_leg.option.expiration_date
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn create_test_diagonal() -> DiagonalSpread {
+        DiagonalSpread::new(
+            "AAPL".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(105.0),
+            pos_or_panic!(95.0),
+            OptionStyle::Call,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            ExpirationDate::Days(pos_or_panic!(120.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(0.9),
+            pos_or_panic!(7.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_diagonal_creation() {
+        let diagonal = create_test_diagonal();
+        assert_eq!(diagonal.kind, StrategyType::DiagonalSpread);
+        assert_eq!(diagonal.near_strike(), pos_or_panic!(105.0));
+        assert_eq!(diagonal.far_strike(), pos_or_panic!(95.0));
+        assert_eq!(diagonal.net_debit(), dec!(6.1));
+        assert!(diagonal.validate());
+    }
+
+    #[test]
+    fn test_diagonal_requires_different_strikes() {
+        let mut diagonal = create_test_diagonal();
+        diagonal.far_leg.option.strike_price = pos_or_panic!(105.0);
+        assert!(!diagonal.validate());
+    }
+
+    #[test]
+    fn test_profit_peaks_at_near_strike() {
+        let diagonal = create_test_diagonal();
+        let at_strike = diagonal.calculate_profit_at(&pos_or_panic!(105.0)).unwrap();
+        let below = diagonal.calculate_profit_at(&pos_or_panic!(80.0)).unwrap();
+        let above = diagonal.calculate_profit_at(&pos_or_panic!(130.0)).unwrap();
+        assert!(at_strike > Decimal::ZERO);
+        assert!(at_strike > below);
+        assert!(at_strike > above);
+        assert_eq!(diagonal.get_max_profit().unwrap().to_dec(), at_strike);
+    }
+
+    #[test]
+    fn test_break_even_points() {
+        let diagonal = create_test_diagonal();
+        let break_evens = diagonal.get_break_even_points().unwrap();
+        assert!(!break_evens.is_empty());
+        assert!(break_evens[0] < pos_or_panic!(105.0));
+        for break_even in break_evens {
+            let profit = diagonal.calculate_profit_at(break_even).unwrap();
+            assert!(profit.abs() < dec!(0.05));
+        }
+    }
+
+    #[test]
+    fn test_max_loss_at_near_expiration() {
+        let diagonal = create_test_diagonal();
+        let max_loss = diagonal.max_loss_at_near_expiration().unwrap();
+        // Far below both strikes the far call is nearly worthless.
+        assert!(max_loss.to_dec() <= diagonal.net_debit());
+        assert!(max_loss.to_dec() > diagonal.net_debit() - dec!(0.5));
+        assert_eq!(diagonal.get_max_loss().unwrap(), max_loss);
+    }
+
+    #[test]
+    fn test_roll_metrics() {
+        let diagonal = create_test_diagonal();
+        let metrics = diagonal.roll_metrics().unwrap();
+        assert_eq!(metrics.near_days, pos_or_panic!(30.0));
+        assert_eq!(metrics.far_days, pos_or_panic!(120.0));
+        assert_eq!(metrics.remaining_rolls, 3);
+        assert_eq!(metrics.roll_yield, dec!(0.9) / dec!(7.0));
+        assert_eq!(
+            metrics.annualized_roll_yield,
+            metrics.roll_yield * dec!(365) / dec!(30)
+        );
+        assert_eq!(metrics.cycles_to_recover_cost, dec!(7.0) / dec!(0.9));
+        assert_eq!(
+            metrics.max_loss_at_near_expiration,
+            diagonal.get_max_loss().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_strategy() {
+        let diagonal = create_test_diagonal();
+        let positions = vec![diagonal.far_leg.clone(), diagonal.near_leg.clone()];
+        let rebuilt = DiagonalSpread::get_strategy(&positions).unwrap();
+        assert_eq!(rebuilt.near_strike(), pos_or_panic!(105.0));
+        assert_eq!(rebuilt.break_even_points, diagonal.break_even_points);
+
+        let calendar = vec![diagonal.near_leg.clone(), {
+            let mut far = diagonal.far_leg.clone();
+            far.option.strike_price = pos_or_panic!(105.0);
+            far
+        }];
+        assert!(DiagonalSpread::get_strategy(&calendar).is_err());
+    }
+
+    #[test]
+    fn test_probability_ranges() {
+        let diagonal = create_test_diagonal();
+        assert_eq!(diagonal.get_profit_ranges().unwrap().len(), 1);
+        assert!(!diagonal.get_loss_ranges().unwrap().is_empty());
+    }
+}
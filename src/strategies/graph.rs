@@ -378,5 +378,7 @@ impl_graph_for_payoff_strategy!(
     crate::strategies::custom::CustomStrategy,
     crate::strategies::covered_call::CoveredCall,
     crate::strategies::collar::Collar,
+    crate::strategies::calendar_spread::CalendarSpread,
+    crate::strategies::diagonal_spread::DiagonalSpread,
     crate::strategies::protective_put::ProtectivePut
 );
@@ -22,7 +22,7 @@ use crate::{
         probability::ProbabilityError,
         strategies::{ProfitLossErrorKind, StrategyError},
     },
-    greeks::Greeks,
+    greeks::{Greeks, NetGreeks},
     model::{
         ProfitLossRange,
         position::Position,
@@ -33,6 +33,8 @@ use crate::{
     pricing::payoff::Profit,
     strategies::{
         BasicAble, Strategies, StrategyConstructor,
+        bear_call_spread::BearCallSpread,
+        bull_put_spread::BullPutSpread,
         delta_neutral::DeltaNeutrality,
         probabilities::{core::ProbabilityAnalysis, utils::VolatilityAdjustment},
         utils::{FindOptimalSide, OptimizationCriteria},
@@ -319,6 +321,129 @@ impl IronCondor {
             .expect("Unable to update break even points");
         strategy
     }
+
+    /// Assembles an Iron Condor from a short put spread and a short call spread.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if the spreads do not form a valid Iron Condor,
+    /// for example when their expirations differ or the short strikes overlap.
+    pub fn from_spreads(
+        put_spread: &BullPutSpread,
+        call_spread: &BearCallSpread,
+    ) -> Result<Self, StrategyError> {
+        let mut strategy = Self::get_strategy(&[
+            put_spread.long_put.clone(),
+            put_spread.short_put.clone(),
+            call_spread.short_call.clone(),
+            call_spread.long_call.clone(),
+        ])?;
+        strategy.description = IRON_CONDOR_DESCRIPTION.to_string();
+        Ok(strategy)
+    }
+
+    /// Width between the short put and the long put strikes.
+    pub fn put_wing_width(&self) -> Positive {
+        self.short_put.option.strike_price - self.long_put.option.strike_price
+    }
+
+    /// Width between the long call and the short call strikes.
+    pub fn call_wing_width(&self) -> Positive {
+        self.long_call.option.strike_price - self.short_call.option.strike_price
+    }
+
+    /// Describes whether the put and call wings have the same width.
+    pub fn wing_structure(&self) -> WingStructure {
+        let (put_width, call_width) = (self.put_wing_width(), self.call_wing_width());
+        if put_width == call_width {
+            WingStructure::Symmetric { width: put_width }
+        } else {
+            WingStructure::Skewed {
+                put_width,
+                call_width,
+            }
+        }
+    }
+
+    /// Checks the wings, accepting skewed wings only when `allow_skew` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if a wing has zero width, or if
+    /// the wings are skewed and `allow_skew` is `false`.
+    pub fn validate_wings(&self, allow_skew: bool) -> Result<WingStructure, StrategyError> {
+        let invalid = |reason: String| {
+            StrategyError::OperationError(OperationErrorKind::InvalidParameters {
+                operation: "Iron Condor validate_wings".to_string(),
+                reason,
+            })
+        };
+        if self.put_wing_width() == Positive::ZERO || self.call_wing_width() == Positive::ZERO {
+            return Err(invalid("Wings must have a positive width".to_string()));
+        }
+        let structure = self.wing_structure();
+        if let WingStructure::Skewed {
+            put_width,
+            call_width,
+        } = structure
+            && !allow_skew
+        {
+            return Err(invalid(format!(
+                "Wings are skewed: put width {put_width}, call width {call_width}"
+            )));
+        }
+        Ok(structure)
+    }
+
+    /// Collects the net credit, P&L bounds, break-evens, wings and Greeks of the condor.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if any of the metrics cannot be computed.
+    pub fn metrics(&self) -> Result<IronCondorMetrics, StrategyError> {
+        Ok(IronCondorMetrics {
+            net_credit: self.get_net_premium_received()?.to_dec(),
+            max_profit: self.get_max_profit()?,
+            max_loss: self.get_max_loss()?,
+            break_even_points: self.break_even_points.clone(),
+            wings: self.wing_structure(),
+            greeks: self.net_greeks()?,
+        })
+    }
+}
+
+/// Relative width of the put and call wings of an [`IronCondor`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum WingStructure {
+    /// Both wings have the same width.
+    Symmetric {
+        /// Width of each wing.
+        width: Positive,
+    },
+    /// The wings have different widths, skewing the risk to one side.
+    Skewed {
+        /// Width of the put wing.
+        put_width: Positive,
+        /// Width of the call wing.
+        call_width: Positive,
+    },
+}
+
+/// Summary of the metrics of an [`IronCondor`].
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct IronCondorMetrics {
+    /// Net credit received after fees.
+    pub net_credit: Decimal,
+    /// Maximum profit.
+    pub max_profit: Positive,
+    /// Maximum loss.
+    pub max_loss: Positive,
+    /// Lower and upper break-even points.
+    pub break_even_points: Vec<Positive>,
+    /// Wing structure.
+    pub wings: WingStructure,
+    /// Net Greeks of the four legs, signed by side.
+    pub greeks: NetGreeks,
 }
 
 impl StrategyConstructor for IronCondor {
@@ -3731,3 +3856,148 @@ mod tests_iron_condor_pnl {
         assert!(pnl.unrealized.unwrap() > dec!(0.0));
     }
 }
+
+#[cfg(test)]
+mod tests_iron_condor_wings {
+    use super::*;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn condor(long_put: f64, short_put: f64, short_call: f64, long_call: f64) -> IronCondor {
+        IronCondor::new(
+            "AAPL".to_string(),
+            pos_or_panic!(150.0),
+            pos_or_panic!(short_call),
+            pos_or_panic!(short_put),
+            pos_or_panic!(long_call),
+            pos_or_panic!(long_put),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.01),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.5),
+            Positive::ONE,
+            pos_or_panic!(0.5),
+            pos_or_panic!(0.3),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_symmetric_wings() {
+        let strategy = condor(140.0, 145.0, 155.0, 160.0);
+        assert_eq!(strategy.put_wing_width(), pos_or_panic!(5.0));
+        assert_eq!(strategy.call_wing_width(), pos_or_panic!(5.0));
+        assert_eq!(
+            strategy.validate_wings(false).unwrap(),
+            WingStructure::Symmetric {
+                width: pos_or_panic!(5.0)
+            }
+        );
+    }
+
+    #[test]
+    fn test_skewed_wings() {
+        let strategy = condor(140.0, 145.0, 155.0, 165.0);
+        assert!(strategy.validate_wings(false).is_err());
+        assert_eq!(
+            strategy.validate_wings(true).unwrap(),
+            WingStructure::Skewed {
+                put_width: pos_or_panic!(5.0),
+                call_width: pos_or_panic!(10.0)
+            }
+        );
+    }
+
+    #[test]
+    fn test_zero_width_wing() {
+        let strategy = condor(145.0, 145.0, 155.0, 160.0);
+        assert!(strategy.validate_wings(true).is_err());
+    }
+
+    #[test]
+    fn test_metrics() {
+        let strategy = condor(140.0, 145.0, 155.0, 160.0);
+        let metrics = strategy.metrics().unwrap();
+        assert_eq!(metrics.net_credit, dec!(1.7));
+        assert_eq!(metrics.max_profit, pos_or_panic!(1.7));
+        assert_eq!(metrics.max_loss, pos_or_panic!(3.3));
+        assert_eq!(
+            metrics.break_even_points,
+            vec![pos_or_panic!(143.3), pos_or_panic!(156.7)]
+        );
+        assert!(metrics.greeks.delta.abs() < dec!(0.5));
+        // A short condor is short gamma and vega and collects theta.
+        assert!(metrics.greeks.gamma < Decimal::ZERO);
+        assert!(metrics.greeks.vega < Decimal::ZERO);
+        assert!(metrics.greeks.theta > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_from_spreads() {
+        let expiration = ExpirationDate::Days(pos_or_panic!(30.0));
+        let put_spread = BullPutSpread::new(
+            "AAPL".to_string(),
+            pos_or_panic!(150.0),
+            pos_or_panic!(140.0),
+            pos_or_panic!(145.0),
+            expiration,
+            pos_or_panic!(0.2),
+            dec!(0.01),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(0.3),
+            Positive::ONE,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let call_spread = BearCallSpread::new(
+            "AAPL".to_string(),
+            pos_or_panic!(150.0),
+            pos_or_panic!(155.0),
+            pos_or_panic!(160.0),
+            expiration,
+            pos_or_panic!(0.2),
+            dec!(0.01),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.5),
+            pos_or_panic!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let strategy = IronCondor::from_spreads(&put_spread, &call_spread).unwrap();
+        assert_eq!(strategy.long_put.option.strike_price, pos_or_panic!(140.0));
+        assert_eq!(strategy.long_call.option.strike_price, pos_or_panic!(160.0));
+        assert_eq!(strategy.description, IRON_CONDOR_DESCRIPTION);
+        assert_eq!(
+            strategy.get_net_premium_received().unwrap(),
+            pos_or_panic!(1.7)
+        );
+
+        let overlapping = BearCallSpread::new(
+            "AAPL".to_string(),
+            pos_or_panic!(150.0),
+            pos_or_panic!(142.0),
+            pos_or_panic!(160.0),
+            expiration,
+            pos_or_panic!(0.2),
+            dec!(0.01),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.5),
+            pos_or_panic!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(IronCondor::from_spreads(&put_spread, &overlapping).is_err());
+    }
+}
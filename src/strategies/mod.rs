@@ -19,10 +19,12 @@
 //! - `bull_call_spread`: Implements the Bull Call Spread strategy.
 //! - `bull_put_spread`: Implements the Bull Put Spread strategy.
 //! - `butterfly_spread`: Implements the Butterfly Spread strategy.
+//! - `calendar_spread`: Implements the Calendar Spread strategy.
 //! - `call_butterfly`: Implements the Call Butterfly strategy.
 //! - `collar`: Implements the Collar strategy.
 //! - `covered_call`: Implements the Covered Call strategy.
 //! - `custom`: Provides utilities for creating custom strategies.
+//! - `diagonal_spread`: Implements the Diagonal Spread strategy.
 //! - `iron_butterfly`: Implements the Iron Butterfly strategy.
 //! - `iron_condor`: Implements the Iron Condor strategy.
 //! - `poor_mans_covered_call`: Implements the Poor Man's Covered Call strategy.
@@ -191,6 +193,8 @@
 //! strategies and their usage.
 //!
 
+/// Rule-based adjustment suggestions for open strategies
+pub mod adjustments;
 /// Options trading strategies module collection
 ///
 /// This module provides implementations of various options trading strategies and utility functions
@@ -200,12 +204,16 @@ pub mod base;
 pub mod bear_call_spread;
 /// Bear Put Spread strategy implementation  
 pub mod bear_put_spread;
+/// Break-even prices located by root-finding on the payoff
+pub mod break_even;
 /// Internal module for strategy building utilities
 mod build;
 /// Bull Call Spread strategy implementation
 pub mod bull_call_spread;
 /// Bull Put Spread strategy implementation
 pub mod bull_put_spread;
+/// Calendar Spread strategy implementation
+pub mod calendar_spread;
 /// Call Butterfly strategy implementation  
 pub mod call_butterfly;
 /// Collar strategy implementation
@@ -218,6 +226,8 @@ pub mod custom;
 pub mod default;
 /// Delta-neutral strategy implementation and utilities
 pub mod delta_neutral;
+/// Diagonal Spread strategy implementation
+pub mod diagonal_spread;
 
 /// The `graph` module provides functionality for creating, managing, and
 /// manipulating graph data structures. Common use cases include representing
@@ -254,12 +264,22 @@ pub mod long_straddle;
 pub mod long_strangle;
 /// Macros for options strategies
 pub mod macros;
+/// Expected value ranking of strike combinations from an option chain
+pub mod optimizer;
+/// Annotated sampling of the payoff curve of a strategy
+pub mod payoff_curve;
+/// Exact maximum profit and loss of piecewise-linear payoffs
+pub mod payoff_extremes;
+/// Profit and loss of a strategy over underlying prices and dates
+pub mod pnl_heatmap;
 /// Poor Man's Covered Call strategy implementation
 pub mod poor_mans_covered_call;
 /// Probability calculations for options strategies
 pub mod probabilities;
 /// Protective Put strategy implementation
 pub mod protective_put;
+/// Self-contained HTML reports of strategies
+pub mod report;
 /// Shared traits for strategy categories
 pub mod shared;
 /// Short Call strategy implementation
@@ -272,16 +292,31 @@ pub mod short_put;
 pub mod short_straddle;
 /// Short Strangle strategy implementation
 pub mod short_strangle;
+/// thinkorswim order strings for strategies
+pub mod tos;
 /// Utility functions for options calculations and analysis
 pub mod utils;
+/// Structured validation of the legs of a strategy
+pub mod validation;
+/// Expected move and Greeks analysis shared by straddles and strangles
+pub mod volatility_plays;
+/// What-if projection of a strategy to a price, date and volatility shift
+pub mod what_if;
 
+pub use adjustments::{
+    AdjustmentEngine, AdjustmentRule, AdjustmentTrigger, DefenseAction, MarketSnapshot,
+    SuggestedAction, SuggestedAdjustment,
+};
 pub use base::{BasicAble, Strategable, Strategies, StrategyBasics, Validable};
 pub use bear_call_spread::BearCallSpread;
 pub use bear_put_spread::BearPutSpread;
+pub use break_even::{BreakEvenSolver, brent};
+pub use build::builder::{StrategyBuilder, infer_strategy_type};
 pub use build::model::StrategyRequest;
 pub use build::traits::StrategyConstructor;
 pub use bull_call_spread::BullCallSpread;
 pub use bull_put_spread::BullPutSpread;
+pub use calendar_spread::CalendarSpread;
 pub use call_butterfly::CallButterfly;
 pub use collar::Collar;
 pub use covered_call::CoveredCall;
@@ -290,6 +325,7 @@ pub use delta_neutral::{
     AdjustmentTarget, DELTA_THRESHOLD, DeltaAdjustment, DeltaInfo, DeltaNeutrality,
     PortfolioGreeks,
 };
+pub use diagonal_spread::{DiagonalRollMetrics, DiagonalSpread};
 pub use iron_butterfly::IronButterfly;
 pub use iron_condor::IronCondor;
 pub use long_butterfly_spread::LongButterflySpread;
@@ -297,16 +333,28 @@ pub use long_call::LongCall;
 pub use long_put::LongPut;
 pub use long_straddle::LongStraddle;
 pub use long_strangle::LongStrangle;
+pub use optimizer::{
+    ExpectedValueObjective, OptimizationConstraints, StrikeCandidate, top_by_expected_value,
+    top_by_expected_value_with_constraints, top_by_return_on_capital,
+};
+pub use payoff_curve::{PayoffCurvable, PayoffCurve, PayoffPlateau, PayoffPoint, StrikeMarker};
+pub use payoff_extremes::{PayoffExtreme, PayoffExtremes, legs_payoff_extremes, payoff_extremes};
+pub use pnl_heatmap::{PnlHeatmap, PnlHeatmapable};
 pub use poor_mans_covered_call::PoorMansCoveredCall;
 pub use protective_put::ProtectivePut;
+pub use report::StrategyReport;
 pub use shared::{
     ButterflyStrategy, CondorStrategy, SpreadStrategy, StraddleStrategy, StrangleStrategy,
-    aggregate_fees, aggregate_premiums, calculate_profit_ratio, credit_spread_break_even,
-    debit_spread_break_even,
+    TimeSpreadStrategy, aggregate_fees, aggregate_premiums, calculate_profit_ratio,
+    credit_spread_break_even, debit_spread_break_even,
 };
 pub use short_butterfly_spread::ShortButterflySpread;
 pub use short_call::ShortCall;
 pub use short_put::ShortPut;
 pub use short_straddle::ShortStraddle;
 pub use short_strangle::ShortStrangle;
+pub use tos::{TosOrder, TosSpread};
 pub use utils::FindOptimalSide;
+pub use validation::{Validate, validate_legs};
+pub use volatility_plays::{ExpectedMoveComparison, VolatilityPlay, VolatilityPlayMetrics};
+pub use what_if::{WhatIf, WhatIfAnalysis};
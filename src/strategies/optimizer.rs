@@ -0,0 +1,632 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Strike Optimizer
+//!
+//! Ranks the strike combinations of a strategy template by their expected
+//! value at expiration. The template is any [`Optimizable`] strategy: its
+//! `filter_combinations` enumerates the candidate strikes and widths of a
+//! chain and its `create_strategy` builds each candidate.
+//!
+//! The expected value is taken over the terminal distribution described by
+//! an [`ExpectedValueObjective`], either a lognormal distribution or a set of
+//! simulated terminal prices. [`OptimizationConstraints`] restrict the search
+//! to combinations within a maximum loss, a minimum probability of profit
+//! and a maximum margin requirement.
+//!
+//! The margin requirement of a candidate is its buying-power effect under
+//! Reg-T rules, so candidates can also be ranked by return on capital, the
+//! expected value per unit of buying power consumed.
+//!
+//! With the `parallel` feature the candidates are evaluated on the rayon
+//! thread pool; the ranking is identical to a single-threaded run.
+
+use crate::chains::chain::OptionChain;
+use crate::error::chains::ChainError;
+use crate::error::strategies::StrategyError;
+use crate::pricing::payoff::Profit;
+use crate::strategies::base::{Optimizable, Strategies};
+use crate::strategies::utils::FindOptimalSide;
+use crate::utils::parallel::map_ordered;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use tracing::debug;
+
+/// Default number of price points used to integrate over a lognormal distribution.
+pub const DEFAULT_LOGNORMAL_STEPS: usize = 400;
+
+/// Number of standard deviations covered on each side of a lognormal distribution.
+const LOGNORMAL_STD_DEVS: f64 = 6.0;
+
+/// Terminal price distribution used to compute the expected value of a strategy.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpectedValueObjective {
+    /// Lognormal terminal prices from the underlying price of the strategy.
+    Lognormal {
+        /// Annualized volatility of the underlying.
+        volatility: Positive,
+        /// Annualized drift of the underlying.
+        drift: Decimal,
+        /// Number of price points used in the integration.
+        steps: usize,
+    },
+    /// Equally likely terminal prices, typically produced by a simulation.
+    Simulated(Vec<Positive>),
+}
+
+impl ExpectedValueObjective {
+    /// Creates a lognormal objective with the at-the-money implied volatility
+    /// of the chain and its risk-free rate as drift.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the chain has no at-the-money implied volatility.
+    pub fn lognormal_from_chain(chain: &OptionChain) -> Result<Self, ChainError> {
+        Ok(ExpectedValueObjective::Lognormal {
+            volatility: *chain.get_atm_implied_volatility()?,
+            drift: chain.risk_free_rate.unwrap_or(Decimal::ZERO),
+            steps: DEFAULT_LOGNORMAL_STEPS,
+        })
+    }
+
+    /// Returns the terminal prices and their probabilities for a strategy.
+    ///
+    /// The horizon of the lognormal distribution is the expiration of the
+    /// nearest leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if the strategy has no legs, the time to
+    /// expiration cannot be computed, or the distribution is empty.
+    pub fn distribution<S: Strategies>(
+        &self,
+        strategy: &S,
+    ) -> Result<Vec<(Positive, Decimal)>, StrategyError> {
+        match self {
+            ExpectedValueObjective::Simulated(prices) => {
+                if prices.is_empty() {
+                    return Err(StrategyError::invalid_parameters(
+                        "expected_value",
+                        "no simulated prices",
+                    ));
+                }
+                let weight = Decimal::ONE / Decimal::from(prices.len());
+                Ok(prices.iter().map(|price| (*price, weight)).collect())
+            }
+            ExpectedValueObjective::Lognormal {
+                volatility,
+                drift,
+                steps,
+            } => {
+                let mut years: Option<Positive> = None;
+                for position in strategy.get_positions()? {
+                    let leg_years = position.option.time_to_expiration()?;
+                    years = Some(years.map_or(leg_years, |y| y.min(leg_years)));
+                }
+                let years = years
+                    .ok_or_else(|| StrategyError::invalid_parameters("expected_value", "no legs"))?
+                    .to_f64();
+                let spot = strategy.get_underlying_price().to_f64();
+                let sigma = volatility.to_f64();
+                let mu = drift.to_f64().unwrap_or(0.0);
+                let std_dev = sigma * years.sqrt();
+                let mean = spot.ln() + (mu - sigma * sigma / 2.0) * years;
+
+                if std_dev == 0.0 || *steps < 2 {
+                    return Ok(vec![(Positive::new(mean.exp())?, Decimal::ONE)]);
+                }
+                let dz = 2.0 * LOGNORMAL_STD_DEVS / (*steps - 1) as f64;
+                let points: Vec<(f64, f64)> = (0..*steps)
+                    .map(|i| {
+                        let z = -LOGNORMAL_STD_DEVS + dz * i as f64;
+                        ((mean + std_dev * z).exp(), (-z * z / 2.0).exp())
+                    })
+                    .collect();
+                let total: f64 = points.iter().map(|(_, weight)| weight).sum();
+                points
+                    .into_iter()
+                    .map(|(price, weight)| {
+                        Ok((
+                            Positive::new(price)?,
+                            Decimal::from_f64(weight / total).unwrap_or(Decimal::ZERO),
+                        ))
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Limits a strike combination must satisfy to be kept by the optimizer.
+///
+/// Unset limits are not checked. The margin requirement is the buying-power
+/// effect of the strategy, falling back to its maximum loss when the margin
+/// cannot be computed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OptimizationConstraints {
+    /// Largest acceptable maximum loss.
+    pub max_loss: Option<Positive>,
+    /// Smallest acceptable probability of profit, between 0 and 1.
+    pub min_probability_of_profit: Option<Decimal>,
+    /// Largest acceptable margin requirement.
+    pub max_margin: Option<Positive>,
+}
+
+impl OptimizationConstraints {
+    /// Creates constraints without limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the largest acceptable maximum loss.
+    pub fn with_max_loss(mut self, max_loss: Positive) -> Self {
+        self.max_loss = Some(max_loss);
+        self
+    }
+
+    /// Sets the smallest acceptable probability of profit.
+    pub fn with_min_probability_of_profit(mut self, probability: Decimal) -> Self {
+        self.min_probability_of_profit = Some(probability);
+        self
+    }
+
+    /// Sets the largest acceptable margin requirement.
+    pub fn with_max_margin(mut self, max_margin: Positive) -> Self {
+        self.max_margin = Some(max_margin);
+        self
+    }
+
+    fn allows_risk(&self, max_loss: Positive, margin_requirement: Positive) -> bool {
+        self.max_loss.is_none_or(|limit| max_loss <= limit)
+            && self
+                .max_margin
+                .is_none_or(|limit| margin_requirement <= limit)
+    }
+}
+
+/// A strike combination of a strategy template with its metrics.
+#[derive(Debug, Clone)]
+pub struct StrikeCandidate<S> {
+    /// The strategy built from the combination.
+    pub strategy: S,
+    /// Strikes of the legs, in the order of the strategy positions.
+    pub strikes: Vec<Positive>,
+    /// Expected profit at expiration under the objective.
+    pub expected_value: Decimal,
+    /// Probability of a positive profit at expiration under the objective.
+    pub probability_of_profit: Decimal,
+    /// Maximum profit of the strategy.
+    pub max_profit: Positive,
+    /// Maximum loss of the strategy.
+    pub max_loss: Positive,
+    /// Buying-power effect of the strategy.
+    pub margin_requirement: Positive,
+    /// Expected value per unit of margin requirement, or zero without margin.
+    pub return_on_capital: Decimal,
+    /// Break-even points of the strategy.
+    pub break_even_points: Vec<Positive>,
+}
+
+impl<S: Strategies + Profit> StrikeCandidate<S> {
+    /// Computes the metrics of a strategy under the given objective.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if any of the metrics cannot be computed.
+    pub fn evaluate(
+        strategy: S,
+        objective: &ExpectedValueObjective,
+    ) -> Result<Self, StrategyError> {
+        Self::evaluate_constrained(strategy, objective, &OptimizationConstraints::default())?
+            .ok_or_else(|| StrategyError::invalid_parameters("evaluate", "candidate rejected"))
+    }
+
+    /// Computes the metrics of a strategy, returning `None` as soon as one of
+    /// the constraints is violated.
+    ///
+    /// The risk limits are checked before the expected value is integrated,
+    /// and the integration stops once the remaining probability mass can no
+    /// longer reach the minimum probability of profit.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if any of the metrics cannot be computed.
+    pub fn evaluate_constrained(
+        strategy: S,
+        objective: &ExpectedValueObjective,
+        constraints: &OptimizationConstraints,
+    ) -> Result<Option<Self>, StrategyError> {
+        let max_loss = strategy.get_max_loss()?;
+        let margin_requirement = strategy.buying_power_effect().unwrap_or(max_loss);
+        if !constraints.allows_risk(max_loss, margin_requirement) {
+            return Ok(None);
+        }
+
+        let min_probability = constraints
+            .min_probability_of_profit
+            .unwrap_or(Decimal::ZERO);
+        let mut remaining = Decimal::ONE;
+        let mut expected_value = Decimal::ZERO;
+        let mut probability_of_profit = Decimal::ZERO;
+        for (price, probability) in objective.distribution(&strategy)? {
+            let profit = strategy.calculate_profit_at(&price)?;
+            expected_value += profit * probability;
+            if profit > Decimal::ZERO {
+                probability_of_profit += probability;
+            }
+            remaining -= probability;
+            if probability_of_profit + remaining < min_probability {
+                return Ok(None);
+            }
+        }
+        if probability_of_profit < min_probability {
+            return Ok(None);
+        }
+
+        let strikes = strategy
+            .get_positions()?
+            .iter()
+            .map(|position| position.option.strike_price)
+            .collect();
+        let return_on_capital = if margin_requirement > Positive::ZERO {
+            expected_value / margin_requirement.to_dec()
+        } else {
+            Decimal::ZERO
+        };
+        Ok(Some(StrikeCandidate {
+            strikes,
+            expected_value,
+            probability_of_profit,
+            max_profit: strategy.get_max_profit()?,
+            max_loss,
+            margin_requirement,
+            return_on_capital,
+            break_even_points: strategy.get_break_even_points()?.clone(),
+            strategy,
+        }))
+    }
+}
+
+/// Searches the strike combinations of a strategy template in a chain and
+/// returns the `top_n` candidates with the highest expected value, best first.
+///
+/// Combinations whose metrics cannot be computed are skipped.
+///
+/// # Arguments
+///
+/// * `template` - Strategy whose combinations are searched.
+/// * `option_chain` - Chain providing strikes and prices.
+/// * `side` - Filter applied to the strikes of the combinations.
+/// * `objective` - Terminal distribution of the expected value.
+/// * `top_n` - Maximum number of candidates returned.
+pub fn top_by_expected_value<T>(
+    template: &T,
+    option_chain: &OptionChain,
+    side: FindOptimalSide,
+    objective: &ExpectedValueObjective,
+    top_n: usize,
+) -> Vec<StrikeCandidate<T::Strategy>>
+where
+    T: Optimizable,
+    T::Strategy: Profit + Send,
+{
+    top_by_expected_value_with_constraints(
+        template,
+        option_chain,
+        side,
+        objective,
+        &OptimizationConstraints::default(),
+        top_n,
+    )
+}
+
+/// Same as [`top_by_expected_value`], keeping only the combinations that
+/// satisfy the constraints.
+///
+/// Combinations are pruned as soon as a constraint fails, so the expected
+/// value is only integrated for combinations within the risk limits.
+///
+/// # Arguments
+///
+/// * `template` - Strategy whose combinations are searched.
+/// * `option_chain` - Chain providing strikes and prices.
+/// * `side` - Filter applied to the strikes of the combinations.
+/// * `objective` - Terminal distribution of the expected value.
+/// * `constraints` - Limits on maximum loss, probability of profit and margin.
+/// * `top_n` - Maximum number of candidates returned.
+pub fn top_by_expected_value_with_constraints<T>(
+    template: &T,
+    option_chain: &OptionChain,
+    side: FindOptimalSide,
+    objective: &ExpectedValueObjective,
+    constraints: &OptimizationConstraints,
+    top_n: usize,
+) -> Vec<StrikeCandidate<T::Strategy>>
+where
+    T: Optimizable,
+    T::Strategy: Profit + Send,
+{
+    let mut candidates =
+        evaluate_combinations(template, option_chain, side, objective, constraints);
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.expected_value));
+    candidates.truncate(top_n);
+    candidates
+}
+
+/// Same as [`top_by_expected_value_with_constraints`], ranking the candidates
+/// by return on capital instead of expected value, best first.
+///
+/// # Arguments
+///
+/// * `template` - Strategy whose combinations are searched.
+/// * `option_chain` - Chain providing strikes and prices.
+/// * `side` - Filter applied to the strikes of the combinations.
+/// * `objective` - Terminal distribution of the expected value.
+/// * `constraints` - Limits on maximum loss, probability of profit and margin.
+/// * `top_n` - Maximum number of candidates returned.
+pub fn top_by_return_on_capital<T>(
+    template: &T,
+    option_chain: &OptionChain,
+    side: FindOptimalSide,
+    objective: &ExpectedValueObjective,
+    constraints: &OptimizationConstraints,
+    top_n: usize,
+) -> Vec<StrikeCandidate<T::Strategy>>
+where
+    T: Optimizable,
+    T::Strategy: Profit + Send,
+{
+    let mut candidates =
+        evaluate_combinations(template, option_chain, side, objective, constraints);
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.return_on_capital));
+    candidates.truncate(top_n);
+    candidates
+}
+
+/// Evaluates every combination of the template that satisfies the constraints.
+fn evaluate_combinations<T>(
+    template: &T,
+    option_chain: &OptionChain,
+    side: FindOptimalSide,
+    objective: &ExpectedValueObjective,
+    constraints: &OptimizationConstraints,
+) -> Vec<StrikeCandidate<T::Strategy>>
+where
+    T: Optimizable,
+    T::Strategy: Profit + Send,
+{
+    // Candidates are built sequentially and evaluated with `map_ordered`,
+    // which keeps the enumeration order with or without the `parallel` feature.
+    let strategies: Vec<(String, T::Strategy)> = template
+        .filter_combinations(option_chain, side)
+        .filter_map(|group| group.to_strategy_legs())
+        .map(|legs| {
+            let strategy = template.create_strategy(option_chain, &legs);
+            (legs.to_string(), strategy)
+        })
+        .collect();
+    map_ordered(strategies, |(legs, strategy)| {
+        StrikeCandidate::evaluate_constrained(strategy, objective, constraints)
+            .inspect_err(|e| debug!("Skipping candidate {}: {}", legs, e))
+            .ok()
+            .flatten()
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests_strike_optimizer {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::model::ExpirationDate;
+    use crate::strategies::{BullCallSpread, IronCondor};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn chain() -> OptionChain {
+        let price_params = OptionDataPriceParams::new(
+            Some(Box::new(Positive::HUNDRED)),
+            Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+            Some(dec!(0.0)),
+            Some(Positive::ZERO),
+            Some("TEST".to_string()),
+        );
+        let params = OptionChainBuildParams::new(
+            "TEST".to_string(),
+            None,
+            8,
+            Some(pos_or_panic!(5.0)),
+            dec!(0.0),
+            dec!(0.0),
+            pos_or_panic!(0.02),
+            2,
+            price_params,
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    fn iron_condor() -> IronCondor {
+        IronCondor::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(105.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(110.0),
+            pos_or_panic!(90.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.0),
+            pos_or_panic!(2.0),
+            pos_or_panic!(1.0),
+            pos_or_panic!(1.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_lognormal_distribution_is_normalized() {
+        let objective = ExpectedValueObjective::Lognormal {
+            volatility: pos_or_panic!(0.2),
+            drift: Decimal::ZERO,
+            steps: DEFAULT_LOGNORMAL_STEPS,
+        };
+        let distribution = objective.distribution(&iron_condor()).unwrap();
+        let total: Decimal = distribution.iter().map(|(_, p)| *p).sum();
+        assert!((total - Decimal::ONE).abs() < dec!(0.0001));
+        // Under zero drift the expected terminal price is the current price.
+        let mean: Decimal = distribution.iter().map(|(s, p)| s.to_dec() * p).sum();
+        assert!((mean - dec!(100)).abs() < dec!(0.05));
+    }
+
+    #[test]
+    fn test_simulated_expected_value() {
+        let objective =
+            ExpectedValueObjective::Simulated(vec![Positive::HUNDRED, pos_or_panic!(120.0)]);
+        let candidate = StrikeCandidate::evaluate(iron_condor(), &objective).unwrap();
+        // Net credit of 2 at 100, loss of 5 - 2 = 3 at 120.
+        assert_eq!(candidate.expected_value, dec!(-0.5));
+        assert_eq!(candidate.probability_of_profit, dec!(0.5));
+        assert_eq!(candidate.strikes.len(), 4);
+
+        let empty = ExpectedValueObjective::Simulated(vec![]);
+        assert!(StrikeCandidate::evaluate(iron_condor(), &empty).is_err());
+    }
+
+    #[test]
+    fn test_top_by_expected_value() {
+        let chain = chain();
+        let objective = ExpectedValueObjective::lognormal_from_chain(&chain).unwrap();
+        let candidates =
+            top_by_expected_value(&iron_condor(), &chain, FindOptimalSide::All, &objective, 5);
+        assert!(!candidates.is_empty() && candidates.len() <= 5);
+        for pair in candidates.windows(2) {
+            assert!(pair[0].expected_value >= pair[1].expected_value);
+        }
+
+        let spread = BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(6.0),
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let best = spread.top_by_expected_value(&chain, FindOptimalSide::All, &objective, 1);
+        assert_eq!(best.len(), 1);
+    }
+
+    #[test]
+    fn test_evaluate_constrained() {
+        let objective =
+            ExpectedValueObjective::Simulated(vec![Positive::HUNDRED, pos_or_panic!(120.0)]);
+        let candidate = StrikeCandidate::evaluate_constrained(
+            iron_condor(),
+            &objective,
+            &OptimizationConstraints::new().with_max_loss(pos_or_panic!(3.0)),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(candidate.max_loss, pos_or_panic!(3.0));
+        // The buying-power effect of a defined-risk credit strategy is its
+        // maximum loss.
+        assert_eq!(candidate.margin_requirement, candidate.max_loss);
+        assert_eq!(candidate.return_on_capital, dec!(-0.5) / dec!(3));
+
+        let too_risky = OptimizationConstraints::new().with_max_loss(pos_or_panic!(2.0));
+        assert!(
+            StrikeCandidate::evaluate_constrained(iron_condor(), &objective, &too_risky)
+                .unwrap()
+                .is_none()
+        );
+        let low_margin = OptimizationConstraints::new().with_max_margin(Positive::ONE);
+        assert!(
+            StrikeCandidate::evaluate_constrained(iron_condor(), &objective, &low_margin)
+                .unwrap()
+                .is_none()
+        );
+        let high_pop = OptimizationConstraints::new().with_min_probability_of_profit(dec!(0.6));
+        assert!(
+            StrikeCandidate::evaluate_constrained(iron_condor(), &objective, &high_pop)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_top_by_expected_value_with_constraints() {
+        let chain = chain();
+        let objective = ExpectedValueObjective::lognormal_from_chain(&chain).unwrap();
+        let unconstrained = top_by_expected_value(
+            &iron_condor(),
+            &chain,
+            FindOptimalSide::All,
+            &objective,
+            1000,
+        );
+        let constraints = OptimizationConstraints::new()
+            .with_max_loss(pos_or_panic!(4.0))
+            .with_min_probability_of_profit(dec!(0.5));
+        let constrained = iron_condor().top_by_expected_value_with_constraints(
+            &chain,
+            FindOptimalSide::All,
+            &objective,
+            &constraints,
+            1000,
+        );
+        assert!(constrained.len() < unconstrained.len());
+        for candidate in &constrained {
+            assert!(candidate.max_loss <= pos_or_panic!(4.0));
+            assert!(candidate.probability_of_profit >= dec!(0.5));
+        }
+        let expected = unconstrained
+            .iter()
+            .filter(|c| c.max_loss <= pos_or_panic!(4.0) && c.probability_of_profit >= dec!(0.5))
+            .count();
+        assert_eq!(constrained.len(), expected);
+    }
+
+    #[test]
+    fn test_top_by_return_on_capital() {
+        let chain = chain();
+        let objective = ExpectedValueObjective::lognormal_from_chain(&chain).unwrap();
+        let candidates = top_by_return_on_capital(
+            &iron_condor(),
+            &chain,
+            FindOptimalSide::All,
+            &objective,
+            &OptimizationConstraints::default(),
+            10,
+        );
+        assert!(!candidates.is_empty());
+        for pair in candidates.windows(2) {
+            assert!(pair[0].return_on_capital >= pair[1].return_on_capital);
+        }
+        for candidate in &candidates {
+            assert!(candidate.margin_requirement > Positive::ZERO);
+            assert_eq!(
+                candidate.return_on_capital,
+                candidate.expected_value / candidate.margin_requirement.to_dec()
+            );
+        }
+    }
+}
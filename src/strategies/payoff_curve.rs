@@ -0,0 +1,304 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Payoff Curve
+//!
+//! Samples the P&L at expiration of a strategy over a price range and
+//! annotates the samples with everything a profit diagram needs:
+//!
+//! * the strike of every leg, with its side and style,
+//! * the break-even points of the strategy,
+//! * the ranges where the curve reaches its maximum profit or maximum loss.
+//!
+//! Strikes inside the range are always sampled so the kinks of the payoff
+//! are drawn exactly, whatever the number of steps.
+
+use crate::error::strategies::{PriceErrorKind, StrategyError};
+use crate::model::types::{OptionStyle, Side};
+use crate::pricing::payoff::Profit;
+use crate::strategies::base::Strategies;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Largest difference between two profits considered to be on the same plateau.
+const PLATEAU_TOLERANCE: Decimal = dec!(0.000001);
+
+/// A sampled point of the payoff curve.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PayoffPoint {
+    /// Price of the underlying at expiration.
+    pub price: Positive,
+    /// Profit or loss of the strategy at that price.
+    pub profit: Decimal,
+}
+
+/// Strike of one leg of the strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StrikeMarker {
+    /// Strike price of the leg.
+    pub strike: Positive,
+    /// Whether the leg is long or short.
+    pub side: Side,
+    /// Whether the leg is a call or a put.
+    pub style: OptionStyle,
+    /// Profit or loss of the strategy when the underlying expires at the strike.
+    pub profit: Decimal,
+}
+
+/// Range of prices where the payoff stays at its maximum profit or maximum loss.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PayoffPlateau {
+    /// Lowest sampled price of the plateau.
+    pub from: Positive,
+    /// Highest sampled price of the plateau.
+    pub to: Positive,
+    /// Profit or loss along the plateau.
+    pub profit: Decimal,
+    /// `false` when the plateau touches an edge of the range while the payoff
+    /// keeps moving in its direction, so the extreme lies outside the range.
+    pub bounded: bool,
+}
+
+/// Sampled payoff curve of a strategy with its annotations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayoffCurve {
+    /// Sampled points sorted by price.
+    pub points: Vec<PayoffPoint>,
+    /// Strikes of the legs inside the range.
+    pub strikes: Vec<StrikeMarker>,
+    /// Break-even points of the strategy inside the range.
+    pub break_even_points: Vec<Positive>,
+    /// Plateaus where the payoff reaches its highest value in the range.
+    pub max_profit: Vec<PayoffPlateau>,
+    /// Plateaus where the payoff reaches its lowest value in the range.
+    pub max_loss: Vec<PayoffPlateau>,
+}
+
+/// Samples the payoff curve of a strategy with annotations for rendering.
+pub trait PayoffCurvable: Strategies + Profit {
+    /// Samples the P&L at expiration on `steps` equal intervals of `range`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::PriceError` if the range is empty or `steps` is
+    /// zero, or any error raised while computing the P&L of the legs.
+    fn payoff_curve(
+        &self,
+        range: (Positive, Positive),
+        steps: usize,
+    ) -> Result<PayoffCurve, StrategyError> {
+        let (low, high) = range;
+        if low >= high || steps == 0 {
+            return Err(StrategyError::PriceError(
+                PriceErrorKind::InvalidPriceRange {
+                    start: low.to_f64(),
+                    end: high.to_f64(),
+                    reason: "the range must be increasing and sampled on at least one step"
+                        .to_string(),
+                },
+            ));
+        }
+
+        let positions = self.get_positions()?;
+        let step = (high - low) / steps as f64;
+        let mut prices: Vec<Positive> = (0..steps).map(|i| low + step * i as f64).collect();
+        prices.push(high);
+        prices.extend(
+            positions
+                .iter()
+                .map(|position| position.option.strike_price)
+                .filter(|strike| *strike > low && *strike < high),
+        );
+        prices.sort();
+        prices.dedup();
+
+        let points = prices
+            .into_iter()
+            .map(|price| {
+                Ok(PayoffPoint {
+                    price,
+                    profit: self.calculate_profit_at(&price)?,
+                })
+            })
+            .collect::<Result<Vec<_>, StrategyError>>()?;
+
+        let mut strikes = Vec::new();
+        for position in &positions {
+            let strike = position.option.strike_price;
+            if strike >= low && strike <= high {
+                strikes.push(StrikeMarker {
+                    strike,
+                    side: position.option.side,
+                    style: position.option.option_style,
+                    profit: self.calculate_profit_at(&strike)?,
+                });
+            }
+        }
+        strikes.sort_by_key(|marker| marker.strike);
+
+        let break_even_points = self
+            .get_break_even_points()
+            .map(|points| {
+                points
+                    .iter()
+                    .copied()
+                    .filter(|point| *point >= low && *point <= high)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let max_profit = plateaus(&points, true);
+        let max_loss = plateaus(&points, false);
+
+        Ok(PayoffCurve {
+            points,
+            strikes,
+            break_even_points,
+            max_profit,
+            max_loss,
+        })
+    }
+}
+
+impl<T: Strategies + Profit + ?Sized> PayoffCurvable for T {}
+
+/// Groups the consecutive points at the highest (or lowest) profit into plateaus.
+fn plateaus(points: &[PayoffPoint], highest: bool) -> Vec<PayoffPlateau> {
+    let extreme = points
+        .iter()
+        .map(|point| point.profit)
+        .reduce(|a, b| if highest == (b > a) { b } else { a });
+    let Some(extreme) = extreme else {
+        return Vec::new();
+    };
+    let at_extreme = |point: &PayoffPoint| (point.profit - extreme).abs() <= PLATEAU_TOLERANCE;
+
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < points.len() {
+        if !at_extreme(&points[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i + 1 < points.len() && at_extreme(&points[i + 1]) {
+            i += 1;
+        }
+        // An extreme at the edge of the range is only bounded if the payoff
+        // has already flattened out there.
+        let open_left = start == 0 && start == i;
+        let open_right = i == points.len() - 1 && start == i;
+        result.push(PayoffPlateau {
+            from: points[start].price,
+            to: points[i].price,
+            profit: extreme,
+            bounded: !(open_left || open_right),
+        });
+        i += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests_payoff_curve {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::strategies::{BullCallSpread, LongCall};
+    use positive::pos_or_panic;
+
+    fn bull_call_spread() -> BullCallSpread {
+        BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(7.0),
+            pos_or_panic!(3.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_bull_call_spread_annotations() {
+        let curve = bull_call_spread()
+            .payoff_curve((pos_or_panic!(80.0), pos_or_panic!(120.0)), 7)
+            .unwrap();
+
+        // Strikes are sampled even though they are not on the grid.
+        assert!(curve.points.iter().any(|p| p.price == pos_or_panic!(95.0)));
+        assert!(curve.points.iter().any(|p| p.price == pos_or_panic!(105.0)));
+        assert!(curve.points.windows(2).all(|w| w[0].price < w[1].price));
+
+        assert_eq!(curve.strikes.len(), 2);
+        assert_eq!(curve.strikes[0].side, Side::Long);
+        assert_eq!(curve.strikes[0].profit, dec!(-4));
+        assert_eq!(curve.strikes[1].side, Side::Short);
+        assert_eq!(curve.strikes[1].profit, dec!(6));
+
+        assert_eq!(curve.break_even_points, vec![pos_or_panic!(99.0)]);
+
+        assert_eq!(curve.max_profit.len(), 1);
+        assert_eq!(curve.max_profit[0].from, pos_or_panic!(105.0));
+        assert_eq!(curve.max_profit[0].to, pos_or_panic!(120.0));
+        assert_eq!(curve.max_profit[0].profit, dec!(6));
+        assert!(curve.max_profit[0].bounded);
+
+        assert_eq!(curve.max_loss[0].from, pos_or_panic!(80.0));
+        assert_eq!(curve.max_loss[0].to, pos_or_panic!(95.0));
+        assert_eq!(curve.max_loss[0].profit, dec!(-4));
+    }
+
+    #[test]
+    fn test_unbounded_profit_at_edge() {
+        let strategy = LongCall::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos_or_panic!(5.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let curve = strategy
+            .payoff_curve((pos_or_panic!(90.0), pos_or_panic!(130.0)), 40)
+            .unwrap();
+        assert_eq!(curve.points.len(), 41);
+        assert_eq!(curve.max_profit.len(), 1);
+        assert_eq!(curve.max_profit[0].from, pos_or_panic!(130.0));
+        assert!(!curve.max_profit[0].bounded);
+        assert!(curve.max_loss[0].bounded);
+        assert_eq!(curve.max_loss[0].to, Positive::HUNDRED);
+    }
+
+    #[test]
+    fn test_invalid_range() {
+        let strategy = bull_call_spread();
+        assert!(
+            strategy
+                .payoff_curve((pos_or_panic!(120.0), pos_or_panic!(80.0)), 10)
+                .is_err()
+        );
+        assert!(
+            strategy
+                .payoff_curve((pos_or_panic!(80.0), pos_or_panic!(120.0)), 0)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,284 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Payoff Extremes
+//!
+//! Derives the exact maximum profit and maximum loss of any combination of
+//! option legs from its piecewise-linear payoff at expiration.
+//!
+//! Between two consecutive strikes the payoff is linear, so its extremes on
+//! `[0, highest strike]` are reached at zero or at a strike. Above the highest
+//! strike only calls are in the money and the payoff moves with a constant
+//! slope equal to the net number of long calls: a positive slope means
+//! unlimited profit, a negative one unlimited loss. Below the lowest strike
+//! the price is bounded by zero, so puts can never make either side
+//! unbounded.
+
+use crate::error::strategies::StrategyError;
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, Side};
+use crate::pricing::payoff::Profit;
+use crate::strategies::base::Strategies;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Extreme value of the payoff on one side.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PayoffExtreme {
+    /// The extreme is reached at `price`, where the P&L equals `pnl`.
+    Bounded {
+        /// Underlying price at which the extreme is reached.
+        price: Positive,
+        /// P&L of the strategy at that price.
+        pnl: Decimal,
+    },
+    /// The payoff keeps growing (or falling) as the underlying rises.
+    Unbounded,
+}
+
+impl PayoffExtreme {
+    /// Returns `true` if the extreme is not finite.
+    pub fn is_unbounded(&self) -> bool {
+        matches!(self, PayoffExtreme::Unbounded)
+    }
+}
+
+/// Maximum profit and maximum loss of a strategy at expiration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PayoffExtremes {
+    /// Highest P&L of the strategy.
+    pub max_profit: PayoffExtreme,
+    /// Lowest P&L of the strategy.
+    pub max_loss: PayoffExtreme,
+    /// Change of the P&L per unit of the underlying above the highest strike.
+    pub slope_above_strikes: Decimal,
+}
+
+impl PayoffExtremes {
+    /// Maximum profit as a positive amount, `Positive::INFINITY` when unbounded
+    /// and zero when the strategy can never make money.
+    pub fn max_profit_amount(&self) -> Result<Positive, StrategyError> {
+        match self.max_profit {
+            PayoffExtreme::Unbounded => Ok(Positive::INFINITY),
+            PayoffExtreme::Bounded { pnl, .. } => {
+                Ok(Positive::new_decimal(pnl.max(Decimal::ZERO))?)
+            }
+        }
+    }
+
+    /// Maximum loss as a positive amount, `Positive::INFINITY` when unbounded
+    /// and zero when the strategy can never lose money.
+    pub fn max_loss_amount(&self) -> Result<Positive, StrategyError> {
+        match self.max_loss {
+            PayoffExtreme::Unbounded => Ok(Positive::INFINITY),
+            PayoffExtreme::Bounded { pnl, .. } => {
+                Ok(Positive::new_decimal((-pnl).max(Decimal::ZERO))?)
+            }
+        }
+    }
+}
+
+/// Computes the exact maximum profit and maximum loss of a strategy at expiration.
+///
+/// The P&L is evaluated at zero and at every strike, and the slope above the
+/// highest strike is derived from the quantities of the call legs.
+///
+/// # Errors
+///
+/// Returns a `StrategyError` if the strategy has no legs or its P&L cannot be
+/// computed.
+pub fn payoff_extremes<S: Strategies + Profit + ?Sized>(
+    strategy: &S,
+) -> Result<PayoffExtremes, StrategyError> {
+    let positions = strategy.get_positions()?;
+    legs_payoff_extremes(strategy, &positions)
+}
+
+/// Computes the maximum profit and maximum loss of `payoff`, the aggregate
+/// P&L at expiration of the option legs in `positions`.
+///
+/// # Errors
+///
+/// Returns a `StrategyError` if there are no legs or the P&L cannot be
+/// computed.
+pub fn legs_payoff_extremes<P: Profit + ?Sized>(
+    payoff: &P,
+    positions: &[&Position],
+) -> Result<PayoffExtremes, StrategyError> {
+    if positions.is_empty() {
+        return Err(StrategyError::operation_not_supported(
+            "payoff_extremes",
+            "strategy without legs",
+        ));
+    }
+
+    let slope_above_strikes: Decimal = positions
+        .iter()
+        .filter(|position| position.option.option_style == OptionStyle::Call)
+        .map(|position| match position.option.side {
+            Side::Long => position.option.quantity.to_dec(),
+            Side::Short => -position.option.quantity.to_dec(),
+        })
+        .sum();
+
+    let mut kinks: Vec<Positive> = positions
+        .iter()
+        .map(|position| position.option.strike_price)
+        .collect();
+    kinks.push(Positive::ZERO);
+    kinks.sort();
+    kinks.dedup();
+
+    let mut highest: Option<(Positive, Decimal)> = None;
+    let mut lowest: Option<(Positive, Decimal)> = None;
+    for price in kinks {
+        let pnl = payoff.calculate_profit_at(&price)?;
+        if highest.is_none_or(|(_, best)| pnl > best) {
+            highest = Some((price, pnl));
+        }
+        if lowest.is_none_or(|(_, worst)| pnl < worst) {
+            lowest = Some((price, pnl));
+        }
+    }
+
+    let bounded = |extreme: Option<(Positive, Decimal)>| {
+        extreme.map_or(PayoffExtreme::Unbounded, |(price, pnl)| {
+            PayoffExtreme::Bounded { price, pnl }
+        })
+    };
+    Ok(PayoffExtremes {
+        max_profit: if slope_above_strikes > Decimal::ZERO {
+            PayoffExtreme::Unbounded
+        } else {
+            bounded(highest)
+        },
+        max_loss: if slope_above_strikes < Decimal::ZERO {
+            PayoffExtreme::Unbounded
+        } else {
+            bounded(lowest)
+        },
+        slope_above_strikes,
+    })
+}
+
+#[cfg(test)]
+mod tests_payoff_extremes {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::strategies::{BullCallSpread, IronCondor, ShortPut, ShortStrangle};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_bull_call_spread_is_bounded() {
+        let strategy = BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos_or_panic!(2.0),
+            pos_or_panic!(7.0),
+            pos_or_panic!(3.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let extremes = payoff_extremes(&strategy).unwrap();
+        assert_eq!(extremes.slope_above_strikes, Decimal::ZERO);
+        assert_eq!(
+            extremes.max_profit,
+            PayoffExtreme::Bounded {
+                price: pos_or_panic!(105.0),
+                pnl: dec!(12),
+            }
+        );
+        assert_eq!(extremes.max_loss_amount().unwrap(), pos_or_panic!(8.0));
+    }
+
+    #[test]
+    fn test_short_strangle_unbounded_loss() {
+        let strategy = ShortStrangle::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(110.0),
+            pos_or_panic!(90.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.5),
+            pos_or_panic!(1.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let extremes = payoff_extremes(&strategy).unwrap();
+        assert!(extremes.max_loss.is_unbounded());
+        assert_eq!(extremes.max_loss_amount().unwrap(), Positive::INFINITY);
+        assert_eq!(extremes.max_profit_amount().unwrap(), pos_or_panic!(4.0));
+    }
+
+    #[test]
+    fn test_short_put_loss_at_zero() {
+        let strategy = ShortPut::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos_or_panic!(4.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let extremes = payoff_extremes(&strategy).unwrap();
+        assert_eq!(
+            extremes.max_loss,
+            PayoffExtreme::Bounded {
+                price: Positive::ZERO,
+                pnl: dec!(-96),
+            }
+        );
+        assert_eq!(extremes.max_profit_amount().unwrap(), pos_or_panic!(4.0));
+    }
+
+    #[test]
+    fn test_iron_condor_matches_wings() {
+        let strategy = IronCondor::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(105.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(110.0),
+            pos_or_panic!(90.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.0),
+            pos_or_panic!(2.0),
+            pos_or_panic!(0.5),
+            pos_or_panic!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let extremes = payoff_extremes(&strategy).unwrap();
+        assert_eq!(extremes.max_profit_amount().unwrap(), pos_or_panic!(3.0));
+        assert_eq!(extremes.max_loss_amount().unwrap(), pos_or_panic!(2.0));
+    }
+}
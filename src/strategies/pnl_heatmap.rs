@@ -0,0 +1,216 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # P&L Heat Map
+//!
+//! Computes the profit or loss of a strategy on a grid of underlying prices
+//! and calendar dates, from today to its last expiration. This is the data
+//! behind the heat map views of options analysis tools, where each cell
+//! shows what the strategy would be worth if the underlying traded at a
+//! price on a date.
+//!
+//! Every leg is marked to model: legs still alive on a date are priced with
+//! Black-Scholes at their current implied volatility and remaining time,
+//! and legs expired by then settle at intrinsic value. The last column is
+//! therefore the payoff at expiration.
+
+use crate::ExpirationDate;
+use crate::error::strategies::{PriceErrorKind, StrategyError};
+use crate::strategies::base::Strategies;
+use chrono::{Duration, NaiveDate, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Seconds in a day, used to turn fractional days into calendar dates.
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Profit or loss of a strategy over underlying prices and dates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PnlHeatmap {
+    /// Underlying prices of the columns, increasing.
+    pub prices: Vec<Positive>,
+    /// Calendar dates of the rows, from today to the last expiration.
+    pub dates: Vec<NaiveDate>,
+    /// Days from now of each row.
+    pub days: Vec<Positive>,
+    /// Profit or loss, indexed by row (date) then column (price).
+    pub pnl: Vec<Vec<Decimal>>,
+}
+
+impl PnlHeatmap {
+    /// Profit or loss on the date of row `date` at the price of column `price`.
+    pub fn get(&self, date: usize, price: usize) -> Option<Decimal> {
+        self.pnl.get(date).and_then(|row| row.get(price)).copied()
+    }
+
+    /// Lowest and highest profit of the grid, to scale the colors of a map.
+    pub fn bounds(&self) -> Option<(Decimal, Decimal)> {
+        self.pnl
+            .iter()
+            .flatten()
+            .fold(None, |bounds, &value| match bounds {
+                None => Some((value, value)),
+                Some((low, high)) => Some((low.min(value), high.max(value))),
+            })
+    }
+}
+
+/// Computes the P&L heat map of a strategy.
+pub trait PnlHeatmapable: Strategies {
+    /// Marks the strategy to model on `price_steps` equal intervals of
+    /// `price_range` and `date_steps` equal intervals between now and the
+    /// last expiration of its legs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::PriceError` if the range is empty or a number
+    /// of steps is zero, or any error raised while valuing the legs.
+    fn pnl_heatmap(
+        &self,
+        price_range: (Positive, Positive),
+        price_steps: usize,
+        date_steps: usize,
+    ) -> Result<PnlHeatmap, StrategyError> {
+        let (low, high) = price_range;
+        if low >= high || price_steps == 0 || date_steps == 0 {
+            return Err(StrategyError::PriceError(
+                PriceErrorKind::InvalidPriceRange {
+                    start: low.to_f64(),
+                    end: high.to_f64(),
+                    reason: "the range must be increasing and sampled on at least one step"
+                        .to_string(),
+                },
+            ));
+        }
+
+        let positions = self.get_positions()?;
+        let mut last = Positive::ZERO;
+        for position in &positions {
+            let days = position.option.expiration_date.get_days().map_err(|e| {
+                StrategyError::operation_not_supported(&e.to_string(), "expiration")
+            })?;
+            last = last.max(days);
+        }
+
+        let step = (high - low) / price_steps as f64;
+        let mut prices: Vec<Positive> = (0..price_steps).map(|i| low + step * i as f64).collect();
+        prices.push(high);
+
+        let now = Utc::now();
+        let mut dates = Vec::with_capacity(date_steps + 1);
+        let mut days = Vec::with_capacity(date_steps + 1);
+        let mut pnl = Vec::with_capacity(date_steps + 1);
+        for i in 0..=date_steps {
+            let horizon = last * i as f64 / date_steps as f64;
+            let offset = Duration::seconds((horizon.to_f64() * SECONDS_PER_DAY) as i64);
+            let expiration = ExpirationDate::Days(horizon);
+            let mut row = Vec::with_capacity(prices.len());
+            for price in &prices {
+                let mut profit = Decimal::ZERO;
+                for position in &positions {
+                    profit += position.pnl_at_horizon(price, &expiration)?;
+                }
+                row.push(profit);
+            }
+            dates.push((now + offset).date_naive());
+            days.push(horizon);
+            pnl.push(row);
+        }
+
+        Ok(PnlHeatmap {
+            prices,
+            dates,
+            days,
+            pnl,
+        })
+    }
+}
+
+impl<T: Strategies + ?Sized> PnlHeatmapable for T {}
+
+#[cfg(test)]
+mod tests_pnl_heatmap {
+    use super::*;
+    use crate::pricing::payoff::Profit;
+    use crate::strategies::BullCallSpread;
+    use positive::pos_or_panic;
+
+    fn bull_call_spread() -> BullCallSpread {
+        BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(7.0),
+            pos_or_panic!(3.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_grid_runs_from_today_to_expiration() {
+        let spread = bull_call_spread();
+        let map = spread
+            .pnl_heatmap((pos_or_panic!(80.0), pos_or_panic!(120.0)), 8, 6)
+            .unwrap();
+        assert_eq!(map.prices.len(), 9);
+        assert_eq!(map.dates.len(), 7);
+        assert_eq!(map.pnl.len(), 7);
+        assert!(map.pnl.iter().all(|row| row.len() == 9));
+        assert_eq!(map.days[0], Positive::ZERO);
+        assert_eq!(map.days[6], pos_or_panic!(30.0));
+        assert_eq!(map.dates[0], Utc::now().date_naive());
+        assert!(map.dates.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // The last row settles at expiration.
+        for (column, price) in map.prices.iter().enumerate() {
+            let payoff = spread.calculate_profit_at(price).unwrap();
+            assert_eq!(map.get(6, column), Some(payoff));
+        }
+        // A bull spread gains with the underlying on every date.
+        for row in &map.pnl {
+            assert!(row.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+        let (low, high) = map.bounds().unwrap();
+        assert_eq!(low, Decimal::from(-4));
+        assert_eq!(high, Decimal::from(6));
+    }
+
+    #[test]
+    fn test_time_decay_towards_payoff() {
+        let map = bull_call_spread()
+            .pnl_heatmap((pos_or_panic!(80.0), pos_or_panic!(120.0)), 4, 3)
+            .unwrap();
+        // Far in the money the spread converges up to its maximum profit.
+        let top: Vec<Decimal> = map.pnl.iter().map(|row| row[4]).collect();
+        assert!(top.windows(2).all(|pair| pair[0] <= pair[1]));
+        // Far out of the money it decays down to its maximum loss.
+        let bottom: Vec<Decimal> = map.pnl.iter().map(|row| row[0]).collect();
+        assert!(bottom.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        let spread = bull_call_spread();
+        let range = (pos_or_panic!(80.0), pos_or_panic!(120.0));
+        assert!(spread.pnl_heatmap(range, 0, 5).is_err());
+        assert!(spread.pnl_heatmap(range, 5, 0).is_err());
+        assert!(
+            spread
+                .pnl_heatmap((pos_or_panic!(120.0), pos_or_panic!(80.0)), 5, 5)
+                .is_err()
+        );
+    }
+}
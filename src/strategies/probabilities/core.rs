@@ -14,6 +14,11 @@ use crate::model::ProfitLossRange;
 use crate::pricing::payoff::Profit;
 use crate::strategies::base::Strategies;
 use crate::strategies::probabilities::analysis::StrategyProbabilityAnalysis;
+use crate::strategies::probabilities::expected_pnl::{ExpectedPnL, expected_pnl_at_expiry};
+use crate::strategies::probabilities::pop::{
+    TerminalDistribution, probability_of_profit_at_expiry,
+};
+use crate::strategies::probabilities::touch::{StrikeTouch, short_strike_touch_probabilities};
 use crate::strategies::probabilities::utils::{
     PriceTrend, VolatilityAdjustment, calculate_single_point_probability,
 };
@@ -229,6 +234,67 @@ pub trait ProbabilityAnalysis: Strategies + Profit {
         Ok(sum_of_probabilities)
     }
 
+    /// Calculate probability of profit from the payoff at expiration
+    ///
+    /// Unlike [`ProbabilityAnalysis::probability_of_profit`], which relies on the
+    /// profit ranges declared by the strategy, this method evaluates the P&L of
+    /// the legs directly, including premiums and fees, under the given
+    /// terminal distribution.
+    ///
+    /// # Parameters
+    ///
+    /// - `distribution`: Risk-neutral lognormal, simulated or user-supplied terminal distribution
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Positive, ProbabilityError>`: The probability of profit (between 0 and 1) or an error
+    fn probability_of_profit_at_expiry(
+        &self,
+        distribution: &TerminalDistribution,
+    ) -> Result<Positive, ProbabilityError> {
+        probability_of_profit_at_expiry(self, distribution)
+    }
+
+    /// Calculate the probability of touching each short strike before expiration
+    ///
+    /// The probability that the underlying trades at the strike of a short
+    /// leg at any time before the leg expires, which is roughly twice the
+    /// probability of it finishing beyond the strike.
+    ///
+    /// # Parameters
+    ///
+    /// - `volatility`: Annualized volatility of the underlying; the implied volatility of each leg when `None`
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Vec<StrikeTouch>, ProbabilityError>`: One entry per short leg or an error
+    fn short_strike_touch_probabilities(
+        &self,
+        volatility: Option<Positive>,
+    ) -> Result<Vec<StrikeTouch>, ProbabilityError> {
+        short_strike_touch_probabilities(self, volatility)
+    }
+
+    /// Calculates the expected P&L of the strategy at expiration
+    ///
+    /// Integrates the P&L of the legs, including premiums and fees, against
+    /// the terminal distribution and reports its mean, variance and profit
+    /// factor.
+    ///
+    /// # Parameters
+    ///
+    /// - `distribution`: Risk-neutral lognormal, simulated or user-supplied terminal distribution
+    ///
+    /// # Returns
+    ///
+    /// - `Result<ExpectedPnL, ProbabilityError>`: The P&L statistics or an error
+    fn expected_pnl_at_expiry(
+        &self,
+        distribution: &TerminalDistribution,
+    ) -> Result<ExpectedPnL, ProbabilityError> {
+        expected_pnl_at_expiry(self, distribution)
+    }
+
     /// Calculate probability of loss
     ///
     /// Calculates the probability that the option strategy will result in a loss at expiration.
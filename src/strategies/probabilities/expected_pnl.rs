@@ -0,0 +1,183 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Expected P&L
+//!
+//! Integrates the P&L of a strategy at expiration against a terminal price
+//! distribution. The distribution can be the risk-neutral lognormal implied by
+//! the legs, an empirical sample from a simulation or a user-supplied density.
+
+use crate::error::probability::ProbabilityError;
+use crate::pricing::payoff::Profit;
+use crate::strategies::base::Strategies;
+use crate::strategies::probabilities::pop::TerminalDistribution;
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+
+/// Moments of the P&L of a strategy at expiration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExpectedPnL {
+    /// Probability-weighted mean P&L.
+    pub expected_value: Decimal,
+    /// Variance of the P&L around its mean.
+    pub variance: Decimal,
+    /// Square root of the variance.
+    pub standard_deviation: Decimal,
+    /// Expected value of the profitable outcomes, `E[max(P&L, 0)]`.
+    pub expected_profit: Decimal,
+    /// Expected size of the losing outcomes, `E[max(-P&L, 0)]`.
+    pub expected_loss: Decimal,
+    /// Ratio of expected profit to expected loss, `None` if no outcome loses money.
+    pub profit_factor: Option<Decimal>,
+}
+
+/// Computes the expected P&L, its variance and the profit factor of a strategy
+/// at expiration under the given terminal distribution.
+///
+/// # Errors
+///
+/// Returns a `ProbabilityError` if the distribution cannot be discretized or
+/// the P&L of the strategy cannot be computed at a terminal price.
+pub fn expected_pnl_at_expiry<S: Strategies + Profit + ?Sized>(
+    strategy: &S,
+    distribution: &TerminalDistribution,
+) -> Result<ExpectedPnL, ProbabilityError> {
+    let outcomes = distribution
+        .weighted_prices(strategy)?
+        .into_iter()
+        .map(|(price, weight)| Ok((strategy.calculate_profit_at(&price)?, weight)))
+        .collect::<Result<Vec<(Decimal, Decimal)>, ProbabilityError>>()?;
+
+    let mut expected_value = Decimal::ZERO;
+    let mut expected_profit = Decimal::ZERO;
+    let mut expected_loss = Decimal::ZERO;
+    for (pnl, weight) in &outcomes {
+        expected_value += pnl * weight;
+        if *pnl > Decimal::ZERO {
+            expected_profit += pnl * weight;
+        } else {
+            expected_loss -= pnl * weight;
+        }
+    }
+    let variance: Decimal = outcomes
+        .iter()
+        .map(|(pnl, weight)| (pnl - expected_value).powi(2) * weight)
+        .sum();
+    let standard_deviation = variance.sqrt().unwrap_or(Decimal::ZERO);
+    let profit_factor = (expected_loss > Decimal::ZERO).then(|| expected_profit / expected_loss);
+
+    Ok(ExpectedPnL {
+        expected_value,
+        variance,
+        standard_deviation,
+        expected_profit,
+        expected_loss,
+        profit_factor,
+    })
+}
+
+#[cfg(test)]
+mod tests_expected_pnl {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::strategies::probabilities::ProbabilityAnalysis;
+    use crate::strategies::{BullCallSpread, LongCall};
+    use positive::{Positive, pos_or_panic};
+    use rust_decimal_macros::dec;
+
+    fn long_call() -> LongCall {
+        LongCall::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(365.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos_or_panic!(10.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_long_call_risk_neutral() {
+        // The expected payoff is the Black-Scholes price of the ATM call, 7.9656.
+        let result = long_call()
+            .expected_pnl_at_expiry(&TerminalDistribution::RiskNeutral { volatility: None })
+            .unwrap();
+        assert!((result.expected_value - dec!(-2.0344)).abs() < dec!(0.01));
+        assert!(
+            (result.expected_profit - result.expected_loss - result.expected_value).abs()
+                < dec!(0.0001)
+        );
+        assert!(result.standard_deviation > Decimal::ZERO);
+        assert!(result.profit_factor.unwrap() < Decimal::ONE);
+    }
+
+    #[test]
+    fn test_bull_call_spread_simulated() {
+        // Debit of 4 on a 95/105 spread: P&L of -4, 1, 6 and 6.
+        let strategy = BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(7.0),
+            pos_or_panic!(3.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let distribution = TerminalDistribution::Simulated(vec![
+            pos_or_panic!(90.0),
+            pos_or_panic!(100.0),
+            pos_or_panic!(110.0),
+            pos_or_panic!(120.0),
+        ]);
+        let result = expected_pnl_at_expiry(&strategy, &distribution).unwrap();
+        assert_eq!(result.expected_value, dec!(2.25));
+        assert_eq!(result.expected_profit, dec!(3.25));
+        assert_eq!(result.expected_loss, dec!(1));
+        assert_eq!(result.profit_factor, Some(dec!(3.25)));
+        assert_eq!(result.variance, dec!(17.1875));
+    }
+
+    #[test]
+    fn test_user_supplied_density() {
+        // Uniform density on [100, 120]: the payoff above the 110 break-even is linear.
+        let density = (0..=20)
+            .map(|i| (pos_or_panic!(100.0 + i as f64), Decimal::ONE))
+            .collect();
+        let result =
+            expected_pnl_at_expiry(&long_call(), &TerminalDistribution::Density(density)).unwrap();
+        assert!((result.expected_value - Decimal::ZERO).abs() < dec!(0.0001));
+        assert!((result.expected_profit - dec!(2.5)).abs() < dec!(0.0001));
+        assert_eq!(result.profit_factor.map(|f| f.round_dp(4)), Some(dec!(1)));
+    }
+
+    #[test]
+    fn test_no_losing_outcomes() {
+        let distribution = TerminalDistribution::Simulated(vec![pos_or_panic!(130.0)]);
+        let result = expected_pnl_at_expiry(&long_call(), &distribution).unwrap();
+        assert_eq!(result.expected_value, dec!(20));
+        assert_eq!(result.variance, Decimal::ZERO);
+        assert_eq!(result.profit_factor, None);
+    }
+
+    #[test]
+    fn test_empty_density_errors() {
+        let distribution = TerminalDistribution::Density(vec![]);
+        assert!(expected_pnl_at_expiry(&long_call(), &distribution).is_err());
+    }
+}
@@ -229,10 +229,19 @@ use positive::pos_or_panic;
 
 mod analysis;
 pub(crate) mod core;
+mod expected_pnl;
+mod pop;
+mod touch;
 pub(crate) mod utils;
 
 pub use analysis::StrategyProbabilityAnalysis;
 pub use core::ProbabilityAnalysis;
+pub use expected_pnl::{ExpectedPnL, expected_pnl_at_expiry};
+pub use pop::{TerminalDistribution, probability_of_profit_at_expiry};
+pub use touch::{
+    StrikeTouch, barrier_touch_probability, option_touch_probability, probability_of_touch,
+    short_strike_touch_probabilities,
+};
 pub use utils::{
     PriceTrend, VolatilityAdjustment, calculate_price_probability,
     calculate_single_point_probability,
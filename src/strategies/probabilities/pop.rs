@@ -0,0 +1,387 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Probability of Profit
+//!
+//! Computes the probability that a strategy ends with a positive P&L at
+//! expiration directly from its payoff, so it works for any combination of
+//! legs and includes the premiums and fees stored on each `Position`.
+//!
+//! Under the risk-neutral lognormal distribution the payoff is scanned for
+//! its break-even points, which are refined with a [`BreakEvenSolver`], and
+//! the probability of every profitable interval is computed in closed form. Simulated and user-supplied distributions are
+//! handled by adding up the probability of the profitable terminal prices.
+
+use crate::error::probability::ProbabilityError;
+use crate::error::strategies::StrategyError;
+use crate::greeks::big_n;
+use crate::pricing::payoff::Profit;
+use crate::strategies::base::Strategies;
+use crate::strategies::break_even::BreakEvenSolver;
+use num_traits::{FromPrimitive, ToPrimitive};
+use positive::Positive;
+use rust_decimal::Decimal;
+
+/// Number of grid intervals scanned for break-even points.
+const POP_SCAN_STEPS: usize = 2000;
+
+/// Number of standard deviations of the log price covered by the scan.
+const POP_STD_DEVS: f64 = 8.0;
+
+/// Number of price points used to discretize the risk-neutral distribution.
+const DISCRETIZATION_STEPS: usize = 1000;
+
+/// Terminal price distribution used to compute the probability of profit and
+/// the expected P&L of a strategy.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerminalDistribution {
+    /// Risk-neutral lognormal distribution at the expiration of the nearest
+    /// leg, drifting at the risk-free rate minus the dividend yield.
+    RiskNeutral {
+        /// Annualized volatility; the average implied volatility of the legs when `None`.
+        volatility: Option<Positive>,
+    },
+    /// Equally likely terminal prices from a real-world simulation.
+    Simulated(Vec<Positive>),
+    /// User-supplied density as `(price, density)` points sorted by price.
+    ///
+    /// The density is integrated with the trapezoidal rule and normalized, so
+    /// it does not need to integrate to one.
+    Density(Vec<(Positive, Decimal)>),
+}
+
+impl TerminalDistribution {
+    /// Discretizes the distribution into terminal prices and their probabilities.
+    ///
+    /// The risk-neutral distribution is sampled on `DISCRETIZATION_STEPS`
+    /// points covering eight standard deviations of the log price on each
+    /// side of its mean.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProbabilityError` if the strategy has no legs, a sample
+    /// price is not positive, or the distribution is empty or has no mass.
+    pub fn weighted_prices<S: Strategies + ?Sized>(
+        &self,
+        strategy: &S,
+    ) -> Result<Vec<(Positive, Decimal)>, ProbabilityError> {
+        match self {
+            TerminalDistribution::Simulated(prices) => {
+                if prices.is_empty() {
+                    return Err(ProbabilityError::StdError(
+                        "No simulated prices in the terminal distribution".to_string(),
+                    ));
+                }
+                let weight = Decimal::ONE / Decimal::from(prices.len());
+                Ok(prices.iter().map(|price| (*price, weight)).collect())
+            }
+            TerminalDistribution::Density(points) => {
+                let masses: Vec<Decimal> = (0..points.len())
+                    .map(|i| {
+                        let left = points[i.saturating_sub(1)].0.to_dec();
+                        let right = points[(i + 1).min(points.len() - 1)].0.to_dec();
+                        points[i].1.max(Decimal::ZERO) * (right - left) / Decimal::TWO
+                    })
+                    .collect();
+                let total: Decimal = masses.iter().sum();
+                if total <= Decimal::ZERO {
+                    return Err(ProbabilityError::StdError(
+                        "The terminal density has no probability mass".to_string(),
+                    ));
+                }
+                Ok(points
+                    .iter()
+                    .zip(masses)
+                    .map(|((price, _), mass)| (*price, mass / total))
+                    .collect())
+            }
+            TerminalDistribution::RiskNeutral { volatility } => {
+                let lognormal = LogNormalTerminal::new(strategy, *volatility)?;
+                if lognormal.std_dev == 0.0 {
+                    return Ok(vec![(
+                        Positive::new(lognormal.log_mean.exp())?,
+                        Decimal::ONE,
+                    )]);
+                }
+                let dz = 2.0 * POP_STD_DEVS / (DISCRETIZATION_STEPS - 1) as f64;
+                let points: Vec<(f64, f64)> = (0..DISCRETIZATION_STEPS)
+                    .map(|i| {
+                        let z = -POP_STD_DEVS + dz * i as f64;
+                        (
+                            (lognormal.log_mean + lognormal.std_dev * z).exp(),
+                            (-z * z / 2.0).exp(),
+                        )
+                    })
+                    .collect();
+                let total: f64 = points.iter().map(|(_, weight)| weight).sum();
+                points
+                    .into_iter()
+                    .map(|(price, weight)| {
+                        Ok((
+                            Positive::new(price)?,
+                            Decimal::from_f64(weight / total).unwrap_or(Decimal::ZERO),
+                        ))
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Risk-neutral lognormal distribution of the log price at the expiration of
+/// the nearest leg of a strategy.
+struct LogNormalTerminal {
+    log_mean: f64,
+    std_dev: f64,
+}
+
+impl LogNormalTerminal {
+    fn new<S: Strategies + ?Sized>(
+        strategy: &S,
+        volatility: Option<Positive>,
+    ) -> Result<Self, ProbabilityError> {
+        let positions = strategy.get_positions().map_err(StrategyError::from)?;
+        let first = positions
+            .first()
+            .ok_or_else(|| ProbabilityError::NoPositions("strategy has no legs".to_string()))?;
+        let mut years = first.option.expiration_date.get_years()?;
+        let mut total_volatility = Positive::ZERO;
+        for position in &positions {
+            years = years.min(position.option.expiration_date.get_years()?);
+            total_volatility += position.option.implied_volatility;
+        }
+        let sigma = volatility
+            .unwrap_or(total_volatility / positions.len() as f64)
+            .to_f64();
+        let rate = first.option.risk_free_rate.to_f64().unwrap_or(0.0)
+            - first.option.dividend_yield.to_f64();
+        let spot = strategy.get_underlying_price().to_f64();
+        let years = years.to_f64();
+        Ok(LogNormalTerminal {
+            log_mean: spot.ln() + (rate - sigma * sigma / 2.0) * years,
+            std_dev: sigma * years.sqrt(),
+        })
+    }
+}
+
+/// Calculates the probability that a strategy is profitable at expiration.
+///
+/// # Arguments
+///
+/// * `strategy` - The strategy whose expiration P&L is analysed.
+/// * `distribution` - The distribution of the underlying price at expiration.
+///
+/// # Returns
+///
+/// The probability of a strictly positive P&L, between 0 and 1.
+///
+/// # Errors
+///
+/// Returns a `ProbabilityError` if the strategy has no legs, its P&L cannot
+/// be computed or the simulated distribution is empty.
+pub fn probability_of_profit_at_expiry<S: Strategies + Profit + ?Sized>(
+    strategy: &S,
+    distribution: &TerminalDistribution,
+) -> Result<Positive, ProbabilityError> {
+    match distribution {
+        TerminalDistribution::RiskNeutral { volatility } => {
+            risk_neutral_probability_of_profit(strategy, *volatility)
+        }
+        _ => {
+            let mut probability = Decimal::ZERO;
+            for (price, weight) in distribution.weighted_prices(strategy)? {
+                if strategy.calculate_profit_at(&price)? > Decimal::ZERO {
+                    probability += weight;
+                }
+            }
+            Ok(Positive::new_decimal(
+                probability.clamp(Decimal::ZERO, Decimal::ONE),
+            )?)
+        }
+    }
+}
+
+fn risk_neutral_probability_of_profit<S: Strategies + Profit + ?Sized>(
+    strategy: &S,
+    volatility: Option<Positive>,
+) -> Result<Positive, ProbabilityError> {
+    let positions = strategy.get_positions().map_err(StrategyError::from)?;
+    let LogNormalTerminal { log_mean, std_dev } = LogNormalTerminal::new(strategy, volatility)?;
+
+    if std_dev == 0.0 {
+        let forward = Positive::new(log_mean.exp())?;
+        return Ok(if strategy.calculate_profit_at(&forward)? > Decimal::ZERO {
+            Positive::ONE
+        } else {
+            Positive::ZERO
+        });
+    }
+
+    let lower = (log_mean - POP_STD_DEVS * std_dev).exp();
+    let upper = (log_mean + POP_STD_DEVS * std_dev).exp();
+    let mut prices: Vec<f64> = (0..=POP_SCAN_STEPS)
+        .map(|i| lower + (upper - lower) * i as f64 / POP_SCAN_STEPS as f64)
+        .collect();
+    prices.extend(
+        positions
+            .iter()
+            .map(|position| position.option.strike_price.to_f64())
+            .filter(|strike| *strike > lower && *strike < upper),
+    );
+    prices.sort_by(f64::total_cmp);
+    prices.dedup();
+
+    let profit_at = |price: f64| -> Result<bool, ProbabilityError> {
+        Ok(strategy.calculate_profit_at(&Positive::new(price)?)? > Decimal::ZERO)
+    };
+    let cdf = |price: f64| -> Result<Decimal, ProbabilityError> {
+        let d = (price.ln() - log_mean) / std_dev;
+        Ok(big_n(Decimal::from_f64(d).unwrap_or(Decimal::ZERO))?)
+    };
+
+    // Walk the grid, closing a profitable interval at every break-even point.
+    let solver = BreakEvenSolver::new();
+    let mut probability = Decimal::ZERO;
+    let mut previous_price = prices[0];
+    let mut previous_profitable = profit_at(previous_price)?;
+    let mut interval_start = previous_profitable.then_some(Decimal::ZERO);
+    for &price in &prices[1..] {
+        let profitable = profit_at(price)?;
+        if profitable != previous_profitable {
+            let break_even = solver.refine(
+                strategy,
+                Positive::new(previous_price)?,
+                Positive::new(price)?,
+            )?;
+            let break_even = cdf(break_even.to_f64())?;
+            match interval_start.take() {
+                Some(start) => probability += break_even - start,
+                None => interval_start = Some(break_even),
+            }
+        }
+        previous_price = price;
+        previous_profitable = profitable;
+    }
+    if let Some(start) = interval_start {
+        probability += Decimal::ONE - start;
+    }
+    Ok(Positive::new_decimal(
+        probability.clamp(Decimal::ZERO, Decimal::ONE),
+    )?)
+}
+
+#[cfg(test)]
+mod tests_pop {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::strategies::probabilities::ProbabilityAnalysis;
+    use crate::strategies::{BullCallSpread, LongCall, ShortStrangle};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn long_call(premium: f64, fee: f64) -> LongCall {
+        LongCall::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(365.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos_or_panic!(premium),
+            pos_or_panic!(fee),
+            pos_or_panic!(fee),
+        )
+    }
+
+    #[test]
+    fn test_long_call_risk_neutral() {
+        // Profitable above 110: P(S_T > 110) = N(d2) with d2 = (ln(100/110) - 0.02) / 0.2
+        let strategy = long_call(10.0, 0.0);
+        let distribution = TerminalDistribution::RiskNeutral { volatility: None };
+        let pop = probability_of_profit_at_expiry(&strategy, &distribution).unwrap();
+        assert!((pop.to_dec() - dec!(0.2821)).abs() < dec!(0.001));
+
+        // Fees move the break-even up and lower the probability.
+        let with_fees = long_call(10.0, 2.5);
+        let pop_with_fees = probability_of_profit_at_expiry(&with_fees, &distribution).unwrap();
+        assert!(pop_with_fees < pop);
+        assert!((pop_with_fees.to_dec() - dec!(0.2122)).abs() < dec!(0.001));
+    }
+
+    #[test]
+    fn test_short_strangle_two_break_evens() {
+        let strategy = ShortStrangle::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(110.0),
+            pos_or_panic!(90.0),
+            ExpirationDate::Days(pos_or_panic!(365.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(5.0),
+            pos_or_panic!(5.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let pop = strategy
+            .probability_of_profit_at_expiry(&TerminalDistribution::RiskNeutral {
+                volatility: None,
+            })
+            .unwrap();
+        // Profitable between 80 and 120.
+        assert!((pop.to_dec() - dec!(0.6893)).abs() < dec!(0.001));
+    }
+
+    #[test]
+    fn test_simulated_and_bounds() {
+        let strategy = BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(6.0),
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let simulated = TerminalDistribution::Simulated(vec![
+            pos_or_panic!(90.0),
+            Positive::HUNDRED,
+            pos_or_panic!(110.0),
+            pos_or_panic!(120.0),
+        ]);
+        // Debit of 4, profitable above 99.
+        let pop = probability_of_profit_at_expiry(&strategy, &simulated).unwrap();
+        assert_eq!(pop, pos_or_panic!(0.75));
+        assert!(
+            probability_of_profit_at_expiry(&strategy, &TerminalDistribution::Simulated(vec![]))
+                .is_err()
+        );
+
+        let pop = probability_of_profit_at_expiry(
+            &strategy,
+            &TerminalDistribution::RiskNeutral {
+                volatility: Some(Positive::ZERO),
+            },
+        )
+        .unwrap();
+        assert_eq!(pop, Positive::ONE);
+    }
+}
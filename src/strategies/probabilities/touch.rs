@@ -0,0 +1,370 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Probability of Touch
+//!
+//! Probability that the underlying trades at a given level at any time before
+//! expiration, rather than only at expiration. Traders use it to judge how
+//! likely the short strikes of a strategy are to be tested, and it is the
+//! probability that the barrier of a barrier option is hit.
+//!
+//! Under geometric Brownian motion with risk-neutral drift, the log price
+//! `X_t = ν·t + σ·W_t` with `ν = r - q - σ²/2` reaches a level `b > 0` above
+//! its start before `T` with probability
+//!
+//! ```text
+//! P = N((-b + νT) / σ√T) + exp(2νb / σ²) · N((-b - νT) / σ√T)
+//! ```
+//!
+//! and levels below the current price are handled by symmetry. Paths are
+//! monitored continuously, so Monte Carlo estimates from discretely sampled
+//! paths come out slightly lower.
+
+use crate::error::probability::ProbabilityError;
+use crate::error::strategies::StrategyError;
+use crate::model::option::Options;
+use crate::model::types::{OptionStyle, OptionType};
+use crate::strategies::base::Strategies;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal};
+use utoipa::ToSchema;
+
+/// Probability of the underlying touching the strike of a short leg.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StrikeTouch {
+    /// Index of the leg in the positions of the strategy.
+    pub index: usize,
+    /// Strike of the leg.
+    pub strike: Positive,
+    /// Whether the leg is a call or a put.
+    pub option_style: OptionStyle,
+    /// Probability of the underlying touching the strike before expiration.
+    pub probability: Positive,
+}
+
+/// Calculates the probability that the underlying touches `level` before
+/// expiration under risk-neutral geometric Brownian motion.
+///
+/// # Arguments
+///
+/// * `spot` - Current price of the underlying.
+/// * `level` - Price level to touch, above or below the current price.
+/// * `volatility` - Annualized volatility of the underlying.
+/// * `years` - Time to expiration in years.
+/// * `risk_free_rate` - Annualized risk-free rate.
+/// * `dividend_yield` - Annualized dividend yield of the underlying.
+///
+/// # Returns
+///
+/// The probability of touching the level, between 0 and 1. A level equal to
+/// the current price has already been touched.
+///
+/// # Errors
+///
+/// Returns a `ProbabilityError` if the spot price or the level is zero.
+pub fn probability_of_touch(
+    spot: Positive,
+    level: Positive,
+    volatility: Positive,
+    years: Positive,
+    risk_free_rate: Decimal,
+    dividend_yield: Positive,
+) -> Result<Positive, ProbabilityError> {
+    if spot == Positive::ZERO || level == Positive::ZERO {
+        return Err(ProbabilityError::StdError(
+            "Spot price and level must be greater than zero".to_string(),
+        ));
+    }
+    if spot == level {
+        return Ok(Positive::ONE);
+    }
+
+    let sigma = volatility.to_f64();
+    let years = years.to_f64();
+    let carry = risk_free_rate.to_f64().unwrap_or(0.0) - dividend_yield.to_f64();
+    // Distance to the level in log price, and the drift towards it.
+    let up = level > spot;
+    let distance = (level.to_f64() / spot.to_f64()).ln().abs();
+    let drift = if up { 1.0 } else { -1.0 } * (carry - sigma * sigma / 2.0);
+
+    let probability = if years == 0.0 {
+        0.0
+    } else if sigma == 0.0 {
+        if drift * years >= distance { 1.0 } else { 0.0 }
+    } else {
+        let normal = Normal::standard();
+        let spread = sigma * years.sqrt();
+        normal.cdf((-distance + drift * years) / spread)
+            + (2.0 * drift * distance / (sigma * sigma)).exp()
+                * normal.cdf((-distance - drift * years) / spread)
+    };
+    Ok(Positive::new(probability.clamp(0.0, 1.0))?)
+}
+
+/// Calculates the probability that the underlying of `option` touches
+/// `level` before the option expires, with the volatility, rate and
+/// dividend yield of the option.
+///
+/// # Errors
+///
+/// Returns a `ProbabilityError` if the time to expiration cannot be computed
+/// or the underlying price or the level is zero.
+pub fn option_touch_probability(
+    option: &Options,
+    level: Positive,
+) -> Result<Positive, ProbabilityError> {
+    probability_of_touch(
+        option.underlying_price,
+        level,
+        option.implied_volatility,
+        option.time_to_expiration()?,
+        option.risk_free_rate,
+        option.dividend_yield,
+    )
+}
+
+/// Calculates the probability that the barrier of a barrier option is hit
+/// before expiration: the probability that a knock-in option is activated
+/// or a knock-out option is extinguished.
+///
+/// # Errors
+///
+/// Returns a `ProbabilityError` if the option is not a barrier option or its
+/// barrier level is not positive.
+pub fn barrier_touch_probability(option: &Options) -> Result<Positive, ProbabilityError> {
+    match &option.option_type {
+        OptionType::Barrier { barrier_level, .. } => {
+            option_touch_probability(option, Positive::new(*barrier_level)?)
+        }
+        _ => Err(ProbabilityError::StdError(
+            "Barrier touch probability requires OptionType::Barrier".to_string(),
+        )),
+    }
+}
+
+/// Calculates the probability of touching the strike of every short leg of a
+/// strategy before it expires.
+///
+/// # Arguments
+///
+/// * `strategy` - The strategy whose short strikes are assessed.
+/// * `volatility` - Annualized volatility of the underlying; the implied
+///   volatility of each leg when `None`.
+///
+/// # Returns
+///
+/// One entry per short leg, in the order of the positions of the strategy.
+///
+/// # Errors
+///
+/// Returns a `ProbabilityError` if the legs cannot be read or the time to
+/// expiration of a leg cannot be computed.
+pub fn short_strike_touch_probabilities<S: Strategies + ?Sized>(
+    strategy: &S,
+    volatility: Option<Positive>,
+) -> Result<Vec<StrikeTouch>, ProbabilityError> {
+    let positions = strategy.get_positions().map_err(StrategyError::from)?;
+    positions
+        .iter()
+        .enumerate()
+        .filter(|(_, position)| position.option.is_short())
+        .map(|(index, position)| {
+            let option = &position.option;
+            let probability = probability_of_touch(
+                option.underlying_price,
+                option.strike_price,
+                volatility.unwrap_or(option.implied_volatility),
+                option.time_to_expiration()?,
+                option.risk_free_rate,
+                option.dividend_yield,
+            )?;
+            Ok(StrikeTouch {
+                index,
+                strike: option.strike_price,
+                option_style: option.option_style,
+                probability,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_touch {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::types::{BarrierType, DayCount, Side};
+    use crate::simulation::{PathSimulator, TimeGrid};
+    use crate::strategies::ShortStrangle;
+    use crate::strategies::probabilities::ProbabilityAnalysis;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn touch(level: f64, rate: Decimal) -> Positive {
+        probability_of_touch(
+            Positive::HUNDRED,
+            Positive::new(level).unwrap(),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            rate,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_driftless_touch_is_twice_terminal() {
+        // With ν = 0 the reflection principle gives P(touch) = 2·P(S_T ≥ H),
+        // so r = σ²/2 = 0.02: 2·N(-ln(1.1) / 0.2) = 0.6337.
+        assert!((touch(110.0, dec!(0.02)).to_dec() - dec!(0.6337)).abs() < dec!(0.0005));
+        assert!(
+            (touch(1.0 / 1.1 * 100.0, dec!(0.02)).to_dec() - dec!(0.6337)).abs() < dec!(0.0005)
+        );
+        assert_eq!(touch(100.0, Decimal::ZERO), Positive::ONE);
+        // Farther levels are less likely to be touched.
+        assert!(touch(130.0, Decimal::ZERO) < touch(110.0, Decimal::ZERO));
+        assert!(touch(70.0, Decimal::ZERO) < touch(90.0, Decimal::ZERO));
+        // A positive drift makes levels above more likely than below.
+        assert!(touch(110.0, dec!(0.1)) > touch(100.0 / 1.1, dec!(0.1)));
+    }
+
+    #[test]
+    fn test_zero_time_and_volatility() {
+        let expired = probability_of_touch(
+            Positive::HUNDRED,
+            pos_or_panic!(105.0),
+            pos_or_panic!(0.2),
+            Positive::ZERO,
+            Decimal::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        assert_eq!(expired, Positive::ZERO);
+        let deterministic = |rate| {
+            probability_of_touch(
+                Positive::HUNDRED,
+                pos_or_panic!(105.0),
+                Positive::ZERO,
+                Positive::ONE,
+                rate,
+                Positive::ZERO,
+            )
+            .unwrap()
+        };
+        assert_eq!(deterministic(dec!(0.1)), Positive::ONE);
+        assert_eq!(deterministic(dec!(0.01)), Positive::ZERO);
+    }
+
+    #[test]
+    fn test_short_strikes() {
+        let strangle = ShortStrangle::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(110.0),
+            pos_or_panic!(90.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.0),
+            pos_or_panic!(1.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let touches = strangle.short_strike_touch_probabilities(None).unwrap();
+        assert_eq!(touches.len(), 2);
+        for touch in &touches {
+            assert!(touch.probability > Positive::ZERO && touch.probability < pos_or_panic!(0.5));
+        }
+        let wild = strangle
+            .short_strike_touch_probabilities(Some(pos_or_panic!(0.6)))
+            .unwrap();
+        assert!(wild[0].probability > touches[0].probability);
+    }
+
+    #[test]
+    fn test_barrier_matches_simulated_paths() {
+        let option = Options::new(
+            OptionType::Barrier {
+                barrier_type: BarrierType::UpAndOut,
+                barrier_level: 115.0,
+                rebate: None,
+            },
+            Side::Long,
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(182.5)),
+            pos_or_panic!(0.25),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.03),
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        );
+        let probability = barrier_touch_probability(&option).unwrap().to_f64();
+
+        let years = option.time_to_expiration().unwrap();
+        let paths = PathSimulator::new(
+            option.underlying_price,
+            option.implied_volatility,
+            option.risk_free_rate,
+            option.dividend_yield,
+        )
+        .with_paths(4_000)
+        .with_seed(42)
+        .simulate(&TimeGrid::uniform(years, 500).unwrap())
+        .unwrap();
+        let simulated = paths.touch_frequency(115.0);
+        // Discrete monitoring misses some crossings between observations.
+        assert!(simulated <= probability + 0.02);
+        assert!((simulated - probability).abs() < 0.04);
+
+        let european = Options {
+            option_type: OptionType::European,
+            ..option
+        };
+        assert!(barrier_touch_probability(&european).is_err());
+    }
+
+    #[test]
+    fn test_touch_uses_day_count() {
+        let option = Options::new(
+            OptionType::European,
+            Side::Short,
+            "TEST".to_string(),
+            pos_or_panic!(110.0),
+            ExpirationDate::Days(pos_or_panic!(90.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        );
+        let act365 = option_touch_probability(&option, option.strike_price).unwrap();
+        let act360 = option.clone().with_day_count(DayCount::Act360);
+        let expected = probability_of_touch(
+            act360.underlying_price,
+            act360.strike_price,
+            act360.implied_volatility,
+            pos_or_panic!(0.25),
+            Decimal::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        let probability = option_touch_probability(&act360, act360.strike_price).unwrap();
+        assert_eq!(probability, expected);
+        assert!(probability > act365);
+    }
+}
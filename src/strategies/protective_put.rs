@@ -20,14 +20,16 @@ use crate::greeks::Greeks;
 use crate::model::ExpirationDate;
 use crate::model::ProfitLossRange;
 use crate::model::leg::traits::LegAble;
-use crate::model::leg::{Leg, SpotPosition};
+use crate::model::leg::{Leg, SHARES_PER_CONTRACT, SpotPosition, StockLeg};
 use crate::model::position::Position;
 use crate::model::types::{OptionBasicType, OptionStyle, OptionType, Side};
 use crate::pnl::PnLCalculator;
 use crate::pricing::payoff::Profit;
+use crate::risk::RegTMargin;
 use crate::strategies::delta_neutral::DeltaNeutrality;
 use crate::strategies::probabilities::core::ProbabilityAnalysis;
 use crate::strategies::probabilities::utils::VolatilityAdjustment;
+use crate::strategies::shared::StockOptionStrategy;
 use crate::strategies::{BasicAble, Strategies};
 use chrono::Utc;
 use positive::Positive;
@@ -107,8 +109,8 @@ impl ProtectivePut {
             long_put_option,
             premium_long_put,
             Utc::now(),
-            put_open_fee / Positive::HUNDRED,
-            put_close_fee / Positive::HUNDRED,
+            put_open_fee / SHARES_PER_CONTRACT,
+            put_close_fee / SHARES_PER_CONTRACT,
             None,
             None,
         );
@@ -194,9 +196,7 @@ impl ProtectivePut {
     /// Calculates total fees for all positions.
     #[must_use]
     pub fn total_fees(&self) -> Positive {
-        self.spot_leg.open_fee
-            + self.spot_leg.close_fee
-            + (self.long_put.open_fee + self.long_put.close_fee) * self.long_put.option.quantity
+        self.combined_fees()
     }
 
     /// Returns the protection level as a percentage below current price.
@@ -251,13 +251,8 @@ impl BreakEvenable for ProtectivePut {
 
     fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
         self.break_even_points.clear();
-        let entry_price = self.spot_leg.cost_basis.to_dec();
-        let put_premium = self.long_put.premium.to_dec();
-        let quantity = self.spot_leg.quantity.to_dec();
-        let total_fees = self.total_fees();
-        let break_even = entry_price + put_premium + (total_fees.to_dec() / quantity);
-        if let Ok(be) = Positive::new_decimal(break_even) {
-            self.break_even_points.push(be.round_to(2));
+        if let Some(break_even) = self.stock_break_even()? {
+            self.break_even_points.push(break_even.round_to(2));
         }
         Ok(())
     }
@@ -385,16 +380,26 @@ impl Strategies for ProtectivePut {
     fn get_max_loss(&self) -> Result<Positive, StrategyError> {
         self.max_loss_potential().map_err(StrategyError::from)
     }
+
+    fn buying_power_effect_with(&self, margin: &RegTMargin) -> Result<Positive, StrategyError> {
+        let report = self.stock_margin(margin);
+        Ok(Positive::new_decimal(report.buying_power_effect())?)
+    }
 }
 
 impl Profit for ProtectivePut {
     fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
-        let spot_pnl = self.spot_leg.pnl_at_price(*price);
-        let put_pnl = self
-            .long_put
-            .pnl_at_expiration(&Some(price))
-            .unwrap_or(Decimal::ZERO);
-        Ok(spot_pnl + put_pnl)
+        self.stock_profit_at(price)
+    }
+}
+
+impl StockOptionStrategy for ProtectivePut {
+    fn stock_leg(&self) -> &StockLeg {
+        &self.spot_leg
+    }
+
+    fn option_legs(&self) -> Vec<&Position> {
+        vec![&self.long_put]
     }
 }
 
@@ -656,4 +661,17 @@ mod tests {
         assert_eq!(positions.len(), 1);
         assert_eq!(positions[0].option.option_style, OptionStyle::Put);
     }
+
+    #[test]
+    fn test_assignment_outcome() {
+        let pp = create_test_protective_put();
+        let exercised = pp.assignment_outcome(&pos_or_panic!(100.0)).unwrap();
+        assert!(exercised.is_called_away());
+        assert_eq!(exercised.exercise_cash, dec!(14500));
+        assert_eq!(exercised.pnl, -pp.get_max_loss().unwrap().to_dec());
+        assert_eq!(
+            exercised.pnl,
+            pp.calculate_profit_at(&pos_or_panic!(100.0)).unwrap()
+        );
+    }
 }
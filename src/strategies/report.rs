@@ -0,0 +1,543 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Strategy Report
+//!
+//! Writes the analysis of a strategy to a single HTML file, with its styles
+//! and payoff chart inline, so it can be opened in any browser or attached
+//! to a message without the library:
+//!
+//! * a summary: underlying, net premium, maximum profit and loss, fees and
+//!   break-even points,
+//! * the payoff at expiration and T+0, drawn as an inline SVG chart,
+//! * a table of the legs with their Greeks and the net Greeks,
+//! * the probability of profit and the expected P&L at expiration,
+//! * a scenario grid of the P&L over underlying prices and dates, from the
+//!   [`PnlHeatmapable`] heat map.
+//!
+//! ```rust,no_run
+//! use optionstratlib::ExpirationDate;
+//! use optionstratlib::strategies::{BullCallSpread, StrategyReport};
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal::Decimal;
+//!
+//! let spread = BullCallSpread::new(
+//!     "SPY".to_string(),
+//!     pos_or_panic!(445.0),
+//!     pos_or_panic!(440.0),
+//!     pos_or_panic!(450.0),
+//!     ExpirationDate::Days(pos_or_panic!(30.0)),
+//!     pos_or_panic!(0.2),
+//!     Decimal::ZERO,
+//!     Positive::ZERO,
+//!     Positive::ONE,
+//!     pos_or_panic!(12.5),
+//!     pos_or_panic!(7.4),
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//! );
+//! StrategyReport::new("SPY bull call spread")
+//!     .save(&spread, "report.html")
+//!     .unwrap();
+//! ```
+
+use crate::ExpirationDate;
+use crate::error::{OptionStratError, OptionStratResult};
+use crate::greeks::NetGreeks;
+use crate::pricing::payoff::Profit;
+use crate::strategies::base::Strategies;
+use crate::strategies::payoff_curve::{PayoffCurvable, PayoffCurve};
+use crate::strategies::pnl_heatmap::{PnlHeatmap, PnlHeatmapable};
+use crate::strategies::probabilities::{
+    TerminalDistribution, expected_pnl_at_expiry, probability_of_profit_at_expiry,
+};
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Number of intervals the payoff chart is sampled on by default.
+const DEFAULT_PAYOFF_STEPS: usize = 100;
+
+/// Number of price intervals of the scenario grid by default.
+const DEFAULT_SCENARIO_PRICES: usize = 8;
+
+/// Number of date intervals of the scenario grid by default.
+const DEFAULT_SCENARIO_DATES: usize = 4;
+
+/// Width of the payoff chart, in SVG units.
+const CHART_WIDTH: f64 = 800.0;
+
+/// Height of the payoff chart, in SVG units.
+const CHART_HEIGHT: f64 = 360.0;
+
+/// Space around the plotting area of the payoff chart, in SVG units.
+const CHART_MARGIN: f64 = 50.0;
+
+/// Styles of the report.
+const STYLE: &str = "body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;\
+margin:2em auto;max-width:960px;color:#222}h1{font-size:1.6em}h2{font-size:1.2em;\
+margin-top:2em;border-bottom:1px solid #ddd}table{border-collapse:collapse;margin:.5em 0}\
+th,td{padding:4px 10px;text-align:right;border-bottom:1px solid #eee}th{background:#f6f6f6}\
+td.label{text-align:left;color:#555}tr.total td{font-weight:bold;border-top:1px solid #999}\
+svg text{font-size:11px;fill:#555}";
+
+/// Builds the HTML report of a strategy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyReport {
+    /// Title of the report.
+    pub title: String,
+    /// Price range of the payoff chart and the scenario grid; the range the
+    /// strategy shows by default when `None`.
+    pub price_range: Option<(Positive, Positive)>,
+    /// Number of intervals the payoff chart is sampled on.
+    pub payoff_steps: usize,
+    /// Number of price intervals of the scenario grid.
+    pub scenario_prices: usize,
+    /// Number of date intervals of the scenario grid.
+    pub scenario_dates: usize,
+    /// Distribution of the underlying at expiration for the probability of
+    /// profit and the expected P&L.
+    pub distribution: TerminalDistribution,
+}
+
+impl StrategyReport {
+    /// Creates a report with default settings and a risk-neutral
+    /// distribution at the implied volatility of the legs.
+    pub fn new(title: impl Into<String>) -> Self {
+        StrategyReport {
+            title: title.into(),
+            price_range: None,
+            payoff_steps: DEFAULT_PAYOFF_STEPS,
+            scenario_prices: DEFAULT_SCENARIO_PRICES,
+            scenario_dates: DEFAULT_SCENARIO_DATES,
+            distribution: TerminalDistribution::RiskNeutral { volatility: None },
+        }
+    }
+
+    /// Sets the price range of the payoff chart and the scenario grid.
+    pub fn price_range(mut self, low: Positive, high: Positive) -> Self {
+        self.price_range = Some((low, high));
+        self
+    }
+
+    /// Sets the number of intervals the payoff chart is sampled on.
+    pub fn payoff_steps(mut self, steps: usize) -> Self {
+        self.payoff_steps = steps;
+        self
+    }
+
+    /// Sets the number of price and date intervals of the scenario grid.
+    pub fn scenario_grid(mut self, prices: usize, dates: usize) -> Self {
+        self.scenario_prices = prices;
+        self.scenario_dates = dates;
+        self
+    }
+
+    /// Sets the distribution of the underlying at expiration.
+    pub fn distribution(mut self, distribution: TerminalDistribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+
+    /// Renders the report of a strategy as an HTML document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payoff, the Greeks, the probabilities or the
+    /// scenario grid of the strategy cannot be computed.
+    pub fn render<S: Strategies + Profit + ?Sized>(
+        &self,
+        strategy: &S,
+    ) -> OptionStratResult<String> {
+        let range = match self.price_range {
+            Some(range) => range,
+            None => strategy.get_range_to_show()?,
+        };
+        let curve = strategy.payoff_curve(range, self.payoff_steps)?;
+        let heatmap = strategy.pnl_heatmap(range, self.scenario_prices, self.scenario_dates)?;
+
+        let mut html = String::new();
+        let title = escape(&self.title);
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+        );
+        self.summary(strategy, &mut html)?;
+        html.push_str("<h2>Payoff</h2>\n");
+        html.push_str(&payoff_svg(strategy, &curve)?);
+        greeks_table(strategy, &mut html)?;
+        self.probabilities(strategy, &mut html)?;
+        scenario_table(&heatmap, &mut html);
+        html.push_str("</body>\n</html>\n");
+        Ok(html)
+    }
+
+    /// Writes the report of a strategy to an HTML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report cannot be rendered or the file written.
+    pub fn save<S: Strategies + Profit + ?Sized>(
+        &self,
+        strategy: &S,
+        path: impl AsRef<Path>,
+    ) -> OptionStratResult<()> {
+        std::fs::write(path, self.render(strategy)?)?;
+        Ok(())
+    }
+
+    fn summary<S: Strategies + ?Sized>(
+        &self,
+        strategy: &S,
+        html: &mut String,
+    ) -> OptionStratResult<()> {
+        let option = strategy.one_option();
+        let net_cost = strategy.get_net_cost()?;
+        let premium = if net_cost > Decimal::ZERO {
+            format!("{net_cost:.2} debit")
+        } else {
+            format!("{:.2} credit", -net_cost)
+        };
+        let bound = |value: Result<Positive, _>| {
+            value.map_or_else(
+                |_| "n/a".to_string(),
+                |value: Positive| {
+                    if value.to_f64().is_finite() {
+                        value.round_to(2).to_string()
+                    } else {
+                        "unlimited".to_string()
+                    }
+                },
+            )
+        };
+        let break_even = strategy
+            .get_break_even_points()
+            .map(|points| {
+                points
+                    .iter()
+                    .map(|point| point.round_to(2).to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+        let rows = [
+            ("Underlying", escape(&option.underlying_symbol)),
+            (
+                "Underlying price",
+                strategy.get_underlying_price().to_string(),
+            ),
+            ("Net premium", premium),
+            ("Maximum profit", bound(strategy.get_max_profit())),
+            ("Maximum loss", bound(strategy.get_max_loss())),
+            ("Fees", strategy.get_fees()?.round_to(2).to_string()),
+            ("Break-even points", break_even),
+        ];
+        html.push_str("<h2>Summary</h2>\n<table>\n");
+        for (label, value) in rows {
+            let _ = writeln!(
+                html,
+                "<tr><td class=\"label\">{label}</td><td>{value}</td></tr>"
+            );
+        }
+        html.push_str("</table>\n");
+        Ok(())
+    }
+
+    fn probabilities<S: Strategies + Profit + ?Sized>(
+        &self,
+        strategy: &S,
+        html: &mut String,
+    ) -> OptionStratResult<()> {
+        let probability = probability_of_profit_at_expiry(strategy, &self.distribution)?;
+        let expected = expected_pnl_at_expiry(strategy, &self.distribution)?;
+        let profit_factor = expected
+            .profit_factor
+            .map_or_else(|| "n/a".to_string(), |factor| format!("{:.2}", factor));
+        let rows = [
+            (
+                "Probability of profit",
+                format!("{:.1}%", probability.to_f64() * 100.0),
+            ),
+            (
+                "Expected P&amp;L",
+                format!("{:.2}", expected.expected_value),
+            ),
+            (
+                "Standard deviation",
+                format!("{:.2}", expected.standard_deviation),
+            ),
+            (
+                "Expected profit",
+                format!("{:.2}", expected.expected_profit),
+            ),
+            ("Expected loss", format!("{:.2}", expected.expected_loss)),
+            ("Profit factor", profit_factor),
+        ];
+        html.push_str("<h2>Probabilities at expiration</h2>\n<table>\n");
+        for (label, value) in rows {
+            let _ = writeln!(
+                html,
+                "<tr><td class=\"label\">{label}</td><td>{value}</td></tr>"
+            );
+        }
+        html.push_str("</table>\n");
+        Ok(())
+    }
+}
+
+/// Writes the legs with their Greeks, signed by side and scaled by quantity.
+fn greeks_table<S: Strategies + ?Sized>(strategy: &S, html: &mut String) -> OptionStratResult<()> {
+    html.push_str(
+        "<h2>Legs and Greeks</h2>\n<table>\n<tr><th>Side</th><th>Style</th><th>Strike</th>\
+         <th>Expiration</th><th>Quantity</th><th>Premium</th><th>Delta</th><th>Gamma</th>\
+         <th>Theta</th><th>Vega</th></tr>\n",
+    );
+    let mut net = [Decimal::ZERO; 4];
+    for position in strategy.get_positions()? {
+        let option = &position.option;
+        let signed = NetGreeks::of_option(option)?;
+        let greeks = [signed.delta, signed.gamma, signed.theta, signed.vega];
+        let _ = write!(
+            html,
+            "<tr><td class=\"label\">{}</td><td class=\"label\">{}</td><td>{}</td><td>{}</td>\
+             <td>{}</td><td>{}</td>",
+            option.side,
+            option.option_style,
+            option.strike_price,
+            expiration(&option.expiration_date),
+            option.quantity,
+            position.premium.round_to(2),
+        );
+        for (total, greek) in net.iter_mut().zip(greeks) {
+            *total += greek;
+            let _ = write!(html, "<td>{greek:.4}</td>");
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("<tr class=\"total\"><td class=\"label\" colspan=\"6\">Net</td>");
+    for total in net {
+        let _ = write!(html, "<td>{total:.4}</td>");
+    }
+    html.push_str("</tr>\n</table>\n");
+    Ok(())
+}
+
+/// Writes the scenario grid, shading profits in green and losses in red.
+fn scenario_table(heatmap: &PnlHeatmap, html: &mut String) {
+    let scale = heatmap
+        .bounds()
+        .map_or(Decimal::ONE, |(low, high)| low.abs().max(high.abs()))
+        .max(Decimal::new(1, 2));
+    html.push_str("<h2>Scenarios</h2>\n<table>\n<tr><th>Date \\ Price</th>");
+    for price in &heatmap.prices {
+        let _ = write!(html, "<th>{}</th>", price.round_to(2));
+    }
+    html.push_str("</tr>\n");
+    for (date, row) in heatmap.dates.iter().zip(&heatmap.pnl) {
+        let _ = write!(html, "<tr><td class=\"label\">{date}</td>");
+        for value in row {
+            let alpha = (value.abs() / scale).to_f64().unwrap_or(0.0) * 0.6;
+            let color = if *value >= Decimal::ZERO {
+                "46,160,67"
+            } else {
+                "214,39,40"
+            };
+            let _ = write!(
+                html,
+                "<td style=\"background:rgba({color},{alpha:.2})\">{value:.2}</td>"
+            );
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+}
+
+/// Draws the payoff at expiration and T+0 as an inline SVG chart.
+fn payoff_svg<S: Strategies + ?Sized>(
+    strategy: &S,
+    curve: &PayoffCurve,
+) -> Result<String, OptionStratError> {
+    let positions = strategy.get_positions()?;
+    let today = ExpirationDate::Days(Positive::ZERO);
+    let mut expiry = Vec::with_capacity(curve.points.len());
+    let mut model = Vec::with_capacity(curve.points.len());
+    for point in &curve.points {
+        let mut marked = Decimal::ZERO;
+        for position in &positions {
+            marked += position.pnl_at_horizon(&point.price, &today)?;
+        }
+        expiry.push((point.price.to_f64(), point.profit.to_f64().unwrap_or(0.0)));
+        model.push((point.price.to_f64(), marked.to_f64().unwrap_or(0.0)));
+    }
+
+    let (Some(first), Some(last)) = (expiry.first(), expiry.last()) else {
+        return Ok(String::new());
+    };
+    let (x_low, x_high) = (first.0, last.0);
+    let (y_low, y_high) = expiry
+        .iter()
+        .chain(&model)
+        .fold((0.0_f64, 0.0_f64), |(low, high), point| {
+            (low.min(point.1), high.max(point.1))
+        });
+    let y_span = (y_high - y_low).max(f64::EPSILON);
+    let x = |value: f64| {
+        CHART_MARGIN + (value - x_low) / (x_high - x_low) * (CHART_WIDTH - 2.0 * CHART_MARGIN)
+    };
+    let y =
+        |value: f64| CHART_MARGIN + (y_high - value) / y_span * (CHART_HEIGHT - 2.0 * CHART_MARGIN);
+    let polyline = |points: &[(f64, f64)], color: &str, dash: &str| {
+        let points: Vec<String> = points
+            .iter()
+            .map(|(px, py)| format!("{:.1},{:.1}", x(*px), y(*py)))
+            .collect();
+        format!(
+            "<polyline fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"{dash} points=\"{}\"/>\n",
+            points.join(" ")
+        )
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {CHART_WIDTH} {CHART_HEIGHT}\" \
+         width=\"100%\">\n"
+    );
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{:.1}\" y1=\"{zero:.1}\" x2=\"{:.1}\" y2=\"{zero:.1}\" stroke=\"#999\"/>",
+        x(x_low),
+        x(x_high),
+        zero = y(0.0)
+    );
+    for (label, price) in curve
+        .strikes
+        .iter()
+        .map(|marker| ("K", marker.strike))
+        .chain(curve.break_even_points.iter().map(|point| ("BE", *point)))
+    {
+        let px = x(price.to_f64());
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{px:.1}\" y1=\"{top:.1}\" x2=\"{px:.1}\" y2=\"{bottom:.1}\" \
+             stroke=\"#ccc\" stroke-dasharray=\"4 3\"/><text x=\"{px:.1}\" y=\"{label_y:.1}\" \
+             text-anchor=\"middle\">{label} {}</text>",
+            price.round_to(2),
+            top = CHART_MARGIN,
+            bottom = CHART_HEIGHT - CHART_MARGIN,
+            label_y = CHART_MARGIN - 8.0,
+        );
+    }
+    svg.push_str(&polyline(&model, "#1f77b4", " stroke-dasharray=\"6 4\""));
+    svg.push_str(&polyline(&expiry, "#d62728", ""));
+    let axis_y = CHART_HEIGHT - CHART_MARGIN + 18.0;
+    let _ = writeln!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{axis_y:.1}\">{x_low:.2}</text>\
+         <text x=\"{:.1}\" y=\"{axis_y:.1}\" text-anchor=\"end\">{x_high:.2}</text>\
+         <text x=\"4\" y=\"{:.1}\">{y_high:.2}</text><text x=\"4\" y=\"{:.1}\">{y_low:.2}</text>\
+         <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">— at expiration   - - T+0</text>",
+        x(x_low),
+        x(x_high),
+        y(y_high) + 4.0,
+        y(y_low) + 4.0,
+        CHART_WIDTH - CHART_MARGIN,
+        CHART_HEIGHT - 8.0,
+    );
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// Expiration of a leg as a date, or in days when it has none.
+fn expiration(date: &ExpirationDate) -> String {
+    match date {
+        ExpirationDate::Days(days) => format!("{} days", days.round_to(1)),
+        ExpirationDate::DateTime(datetime) => datetime.format("%Y-%m-%d").to_string(),
+    }
+}
+
+/// Escapes text for HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests_report {
+    use super::*;
+    use crate::strategies::BullCallSpread;
+    use positive::pos_or_panic;
+
+    fn bull_call_spread() -> BullCallSpread {
+        BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(7.0),
+            pos_or_panic!(3.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_render_sections() {
+        let html = StrategyReport::new("Bull <call> spread")
+            .price_range(pos_or_panic!(80.0), pos_or_panic!(120.0))
+            .scenario_grid(4, 2)
+            .render(&bull_call_spread())
+            .unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>Bull &lt;call&gt; spread</h1>"));
+        for section in [
+            "Summary",
+            "Payoff",
+            "Legs and Greeks",
+            "Probabilities at expiration",
+            "Scenarios",
+        ] {
+            assert!(html.contains(&format!("<h2>{section}</h2>")), "{section}");
+        }
+        assert!(html.contains("<td class=\"label\">Maximum profit</td><td>6</td>"));
+        assert!(html.contains("<td class=\"label\">Maximum loss</td><td>4</td>"));
+        assert!(html.contains("<td class=\"label\">Net premium</td><td>4.00 debit</td>"));
+        assert_eq!(html.matches("<polyline").count(), 2);
+        // Two legs and the net row, plus the header.
+        let greeks =
+            &html[html.find("Legs and Greeks").unwrap()..html.find("Probabilities").unwrap()];
+        assert_eq!(greeks.matches("<tr").count(), 4);
+        // Three dates of five prices.
+        let scenarios = &html[html.find("<h2>Scenarios").unwrap()..];
+        assert_eq!(scenarios.matches("<tr><td class=\"label\">").count(), 3);
+        assert_eq!(scenarios.matches("background:rgba").count(), 15);
+        // Nothing is loaded from outside the file.
+        assert!(!html.contains("<script") && !html.contains("href="));
+    }
+
+    #[test]
+    fn test_save() {
+        let path = std::env::temp_dir().join("optionstratlib_report.html");
+        StrategyReport::new("Report")
+            .save(&bull_call_spread(), &path)
+            .unwrap();
+        let html = std::fs::read_to_string(&path).unwrap();
+        assert!(html.ends_with("</html>\n"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -15,16 +15,27 @@
 //! - **Butterfly strategies**: Three-strike strategies with wings and body
 //! - **Condor strategies**: Four-strike strategies
 //! - **Straddle/Strangle strategies**: Volatility-based strategies
+//! - **Time spread strategies**: Legs with different expirations
+//! - **Stock and option strategies**: Shares combined with options on them
 //!
 //! ## Usage
 //!
 //! Strategies implement these traits to gain access to common calculations
 //! and reduce boilerplate code.
 
+use crate::error::PricingError;
 use crate::error::strategies::StrategyError;
+use crate::model::leg::StockLeg;
+use crate::model::leg::traits::LegAble;
 use crate::model::position::Position;
+use crate::model::types::{OptionStyle, Side};
+use crate::pricing::payoff::Profit;
+use crate::risk::{MarginReport, RegTMargin};
+use crate::strategies::break_even::BreakEvenSolver;
 use positive::Positive;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Trait for vertical spread strategies (two legs with different strikes).
 ///
@@ -187,6 +198,296 @@ pub trait StrangleStrategy {
     fn is_long(&self) -> bool;
 }
 
+/// Trait for time spread strategies (legs with different expirations).
+///
+/// Time spreads sell an option expiring first and buy one expiring later, so
+/// they cannot be analysed at a single expiration. They are valued at the
+/// near leg's expiration instead: the near leg settles at intrinsic value and
+/// the far leg is marked with Black-Scholes using the time it has left.
+///
+/// # Examples
+///
+/// - Calendar Spread
+/// - Diagonal Spread
+pub trait TimeSpreadStrategy {
+    /// Returns the short position expiring first.
+    fn near_leg(&self) -> &Position;
+
+    /// Returns the long position expiring last.
+    fn far_leg(&self) -> &Position;
+
+    /// Returns the number of days between the near and far expirations.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if either expiration cannot be resolved.
+    fn days_between_expirations(&self) -> Result<Positive, PricingError> {
+        let near = self.near_leg().option.expiration_date.get_days()?;
+        let far = self.far_leg().option.expiration_date.get_days()?;
+        Ok(far.sub_or_zero(&near.to_dec()))
+    }
+
+    /// Calculates the profit or loss of both legs at the near expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if either leg cannot be valued.
+    fn profit_at_near_expiration(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        let horizon = &self.near_leg().option.expiration_date;
+        Ok(self.near_leg().pnl_at_horizon(price, horizon)?
+            + self.far_leg().pnl_at_horizon(price, horizon)?)
+    }
+
+    /// Evaluates the P&L at the near expiration over `[lower, upper]`, on the
+    /// grid a [`BreakEvenSolver`] scans.
+    ///
+    /// Both strikes inside the range are always included so the peak of the
+    /// P&L curve is hit exactly.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the P&L cannot be computed at any price.
+    fn scan_near_expiration(
+        &self,
+        lower: Positive,
+        upper: Positive,
+    ) -> Result<Vec<(Positive, Decimal)>, PricingError> {
+        let steps = BreakEvenSolver::default().scan_steps;
+        let step = (upper - lower) / steps as f64;
+        let mut prices: Vec<Positive> = (0..=steps).map(|i| lower + step * i as f64).collect();
+        for strike in [
+            self.near_leg().option.strike_price,
+            self.far_leg().option.strike_price,
+        ] {
+            if strike >= lower && strike <= upper {
+                prices.push(strike);
+            }
+        }
+        prices.sort();
+        prices.dedup();
+        prices
+            .into_iter()
+            .map(|price| Ok((price, self.profit_at_near_expiration(&price)?)))
+            .collect()
+    }
+
+    /// Returns the lowest and highest scanned P&L at the near expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the P&L cannot be computed at any price.
+    fn near_expiration_extremes(
+        &self,
+        lower: Positive,
+        upper: Positive,
+    ) -> Result<(Decimal, Decimal), PricingError> {
+        let profits = self.scan_near_expiration(lower, upper)?;
+        let min = profits.iter().map(|(_, profit)| *profit).min();
+        let max = profits.iter().map(|(_, profit)| *profit).max();
+        Ok((min.unwrap_or(Decimal::ZERO), max.unwrap_or(Decimal::ZERO)))
+    }
+
+    /// Finds the break-even points at the near expiration inside `[lower, upper]`
+    /// with a [`BreakEvenSolver`], using the strikes of both legs as knots.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the P&L cannot be computed at any price.
+    fn near_expiration_break_evens(
+        &self,
+        lower: Positive,
+        upper: Positive,
+    ) -> Result<Vec<Positive>, PricingError> {
+        let knots = [
+            self.near_leg().option.strike_price,
+            self.far_leg().option.strike_price,
+        ];
+        BreakEvenSolver::new().solve(&NearExpirationPayoff(self), lower, upper, &knots)
+    }
+}
+
+/// P&L of a time spread at its near expiration, as a payoff for the
+/// break-even solver.
+struct NearExpirationPayoff<'a, T: ?Sized>(&'a T);
+
+impl<T: TimeSpreadStrategy + ?Sized> Profit for NearExpirationPayoff<'_, T> {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        self.0.profit_at_near_expiration(price)
+    }
+}
+
+/// Result of settling a stock and option strategy at expiration.
+///
+/// In-the-money options are exercised or assigned against the stock leg:
+/// short calls and long puts deliver shares at their strike, long calls and
+/// short puts take shares in at their strike. Whatever is left of the stock
+/// position is marked at the settlement price.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AssignmentOutcome {
+    /// Underlying price at expiration.
+    pub settlement_price: Positive,
+    /// Shares delivered at a strike through assigned short calls or exercised long puts.
+    pub shares_delivered: Positive,
+    /// Shares received at a strike through exercised long calls or assigned short puts.
+    pub shares_received: Positive,
+    /// Net cash from exercises and assignments (deliveries minus receipts at strike).
+    pub exercise_cash: Decimal,
+    /// Signed shares left after settlement (negative for a short stock position).
+    pub remaining_shares: Decimal,
+    /// Total P&L including premiums and fees.
+    pub pnl: Decimal,
+}
+
+impl AssignmentOutcome {
+    /// Returns `true` if the whole long stock position was delivered.
+    pub fn is_called_away(&self) -> bool {
+        self.shares_delivered > Positive::ZERO && self.remaining_shares <= Decimal::ZERO
+    }
+}
+
+/// Trait for strategies combining a stock leg with options on the same shares.
+///
+/// Option quantities are expressed in shares, so the premiums, intrinsic
+/// values and fees of the option legs are directly comparable with the stock
+/// leg.
+///
+/// # Examples
+///
+/// - Covered Call
+/// - Protective Put
+/// - Collar
+pub trait StockOptionStrategy {
+    /// Returns the stock leg.
+    fn stock_leg(&self) -> &StockLeg;
+
+    /// Returns the option legs written on the stock.
+    fn option_legs(&self) -> Vec<&Position>;
+
+    /// Returns the fees of the stock leg and every option leg.
+    fn combined_fees(&self) -> Positive {
+        self.option_legs()
+            .iter()
+            .fold(self.stock_leg().fees(), |total, position| {
+                total + (position.open_fee + position.close_fee) * position.option.quantity
+            })
+    }
+
+    /// Returns the net option premium: premiums received minus premiums paid.
+    fn net_option_premium(&self) -> Decimal {
+        self.option_legs()
+            .iter()
+            .map(|position| {
+                let premium = (position.premium * position.option.quantity).to_dec();
+                match position.option.side {
+                    Side::Short => premium,
+                    Side::Long => -premium,
+                }
+            })
+            .sum()
+    }
+
+    /// Cost basis per share after premiums and fees.
+    ///
+    /// This is the break-even while every option is out of the money.
+    fn net_cost_basis(&self) -> Decimal {
+        let stock = self.stock_leg();
+        if stock.quantity == Positive::ZERO {
+            return stock.cost_basis.to_dec();
+        }
+        let adjustment =
+            (self.net_option_premium() - self.combined_fees().to_dec()) / stock.quantity.to_dec();
+        match stock.side {
+            Side::Long => stock.cost_basis.to_dec() - adjustment,
+            Side::Short => stock.cost_basis.to_dec() + adjustment,
+        }
+    }
+
+    /// Reg-T margin of the stock leg together with the option legs, so that
+    /// calls written against long stock count as covered.
+    fn stock_margin(&self, margin: &RegTMargin) -> MarginReport {
+        margin.calculate(&self.option_legs(), &[self.stock_leg()])
+    }
+
+    /// Calculates the P&L at expiration of the stock leg and every option leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if an option leg cannot be valued.
+    fn stock_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        let mut profit = self.stock_leg().pnl_at_price(*price);
+        for position in self.option_legs() {
+            profit += position.pnl_at_expiration(&Some(price))?;
+        }
+        Ok(profit)
+    }
+
+    /// Returns the break-even point if it lies where every option is out of the money.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the P&L cannot be computed.
+    fn stock_break_even(&self) -> Result<Option<Positive>, PricingError> {
+        let Ok(candidate) = Positive::new_decimal(self.net_cost_basis()) else {
+            return Ok(None);
+        };
+        let profit = self.stock_profit_at(&candidate)?;
+        Ok((profit.abs() < Decimal::new(1, 6)).then_some(candidate))
+    }
+
+    /// Settles the strategy at `price`, exercising and assigning in-the-money options.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PricingError` if the P&L cannot be computed.
+    fn assignment_outcome(&self, price: &Positive) -> Result<AssignmentOutcome, PricingError> {
+        let stock = self.stock_leg();
+        let stock_shares = match stock.side {
+            Side::Long => stock.quantity.to_dec(),
+            Side::Short => -stock.quantity.to_dec(),
+        };
+
+        let mut shares_delivered = Positive::ZERO;
+        let mut shares_received = Positive::ZERO;
+        let mut exercise_cash = Decimal::ZERO;
+        for position in self.option_legs() {
+            let option = &position.option;
+            let in_the_money = match option.option_style {
+                OptionStyle::Call => *price > option.strike_price,
+                OptionStyle::Put => *price < option.strike_price,
+            };
+            if !in_the_money {
+                continue;
+            }
+            let notional = (option.strike_price * option.quantity).to_dec();
+            match (option.option_style, option.side) {
+                (OptionStyle::Call, Side::Short) | (OptionStyle::Put, Side::Long) => {
+                    shares_delivered += option.quantity;
+                    exercise_cash += notional;
+                }
+                (OptionStyle::Call, Side::Long) | (OptionStyle::Put, Side::Short) => {
+                    shares_received += option.quantity;
+                    exercise_cash -= notional;
+                }
+            }
+        }
+
+        let remaining_shares = stock_shares - shares_delivered.to_dec() + shares_received.to_dec();
+        let pnl = exercise_cash + remaining_shares * price.to_dec()
+            - stock_shares * stock.cost_basis.to_dec()
+            + self.net_option_premium()
+            - self.combined_fees().to_dec();
+
+        Ok(AssignmentOutcome {
+            settlement_price: *price,
+            shares_delivered,
+            shares_received,
+            exercise_cash,
+            remaining_shares,
+            pnl,
+        })
+    }
+}
+
 /// Helper function to calculate break-even for a credit spread.
 ///
 /// # Arguments
@@ -0,0 +1,672 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # thinkorswim Order Strings
+//!
+//! Reads and writes the order strings of the thinkorswim platform, which
+//! traders commonly paste to share a trade:
+//!
+//! ```text
+//! SELL -1 IRON CONDOR SPY 100 (Weeklys) 18 DEC 26 460/465/430/425 CALL/PUT @1.20 LMT
+//! └side┘└qty┘└─spread──┘└sym┘└mult┘└series┘└expiry─┘└──strikes────┘└styles┘└price┘└type┘
+//! ```
+//!
+//! A [`TosOrder`] describes the legs of one of the [`TosSpread`] layouts for
+//! a single expiration. Buying a spread buys its first strike and sells the
+//! second, or buys the wings and sells the body of butterflies and condors;
+//! selling it takes the opposite side of every leg. Iron condors list the
+//! two strikes of the call spread, then those of the put spread.
+//!
+//! [`TosOrder::to_strategy`] builds the legs into a [`Strategy`], and
+//! [`TosOrder::from_strategy`] recognizes the layout of a strategy, writing
+//! credit spreads as sales. Calendars, diagonals, ratio spreads and orders
+//! with stock are not supported.
+//!
+//! ```rust
+//! use optionstratlib::strategies::TosOrder;
+//! use optionstratlib::strategies::base::StrategyType;
+//! use positive::pos_or_panic;
+//!
+//! let order: TosOrder = "BUY +2 VERTICAL SPY 100 18 DEC 26 440/450 CALL @3.10 LMT"
+//!     .parse()
+//!     .unwrap();
+//! let strategy = order.to_strategy(pos_or_panic!(445.0), pos_or_panic!(0.2)).unwrap();
+//! assert_eq!(strategy.kind, StrategyType::BullCallSpread);
+//!
+//! let rendered = TosOrder::from_strategy(&strategy).unwrap();
+//! assert!(rendered.to_string().starts_with("BUY +2 VERTICAL SPY 100 18 DEC 26 440/450 CALL @"));
+//! ```
+
+use crate::ExpirationDate;
+use crate::error::strategies::StrategyError;
+use crate::model::option::Options;
+use crate::model::position::Position;
+use crate::model::types::{Action, OptionStyle, OptionType, Side};
+use crate::strategies::StrategyBuilder;
+use crate::strategies::base::Strategy;
+use chrono::{NaiveDate, NaiveTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use std::fmt;
+use std::str::FromStr;
+
+/// Multiplier of standard equity and index option contracts.
+const STANDARD_MULTIPLIER: u32 = 100;
+
+/// Spread layouts of thinkorswim order strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TosSpread {
+    /// A single option.
+    Single,
+    /// Two strikes of the same style, one bought and one sold.
+    Vertical,
+    /// Three strikes of the same style, in a 1/-2/1 ratio.
+    Butterfly,
+    /// Four strikes of the same style, in a 1/-1/-1/1 ratio.
+    Condor,
+    /// A call vertical and a put vertical.
+    IronCondor,
+    /// A call and a put on the same strike.
+    Straddle,
+    /// A call and a put on different strikes.
+    Strangle,
+}
+
+impl TosSpread {
+    /// Keyword of the layout in an order string, empty for single options.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            TosSpread::Single => "",
+            TosSpread::Vertical => "VERTICAL",
+            TosSpread::Butterfly => "BUTTERFLY",
+            TosSpread::Condor => "CONDOR",
+            TosSpread::IronCondor => "IRON CONDOR",
+            TosSpread::Straddle => "STRADDLE",
+            TosSpread::Strangle => "STRANGLE",
+        }
+    }
+
+    /// Signed ratios of the legs when the spread is bought, with the index
+    /// of the style of each leg, in the order of the strikes.
+    fn bought_legs(&self) -> &'static [(i64, usize)] {
+        match self {
+            TosSpread::Single => &[(1, 0)],
+            TosSpread::Vertical => &[(1, 0), (-1, 0)],
+            TosSpread::Butterfly => &[(1, 0), (-2, 0), (1, 0)],
+            TosSpread::Condor => &[(1, 0), (-1, 0), (-1, 0), (1, 0)],
+            TosSpread::IronCondor => &[(1, 0), (-1, 0), (1, 1), (-1, 1)],
+            TosSpread::Straddle => &[(1, 0), (1, 1)],
+            TosSpread::Strangle => &[(1, 0), (1, 1)],
+        }
+    }
+
+    /// Number of styles of the order string.
+    fn styles(&self) -> usize {
+        match self {
+            TosSpread::IronCondor | TosSpread::Straddle | TosSpread::Strangle => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// A thinkorswim order string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TosOrder {
+    /// Whether the spread is bought or sold.
+    pub action: Action,
+    /// Number of spreads.
+    pub quantity: Positive,
+    /// Layout of the legs.
+    pub spread: TosSpread,
+    /// Symbol of the underlying.
+    pub symbol: String,
+    /// Contract multiplier.
+    pub multiplier: u32,
+    /// Series annotation, such as `(Weeklys)`.
+    pub series: Option<String>,
+    /// Expiration date of every leg.
+    pub expiration: NaiveDate,
+    /// Strikes, in the order of the layout.
+    pub strikes: Vec<Positive>,
+    /// Styles: one, or a call and a put for two-style layouts.
+    pub styles: Vec<OptionStyle>,
+    /// Net price per spread.
+    pub price: Option<Positive>,
+    /// Order type and time in force, such as `LMT` or `LMT GTC`.
+    pub order_type: Option<String>,
+}
+
+impl TosOrder {
+    /// Legs of the order: side, style, strike and number of contracts.
+    pub fn legs(&self) -> Vec<(Side, OptionStyle, Positive, Positive)> {
+        let strike_of = |leg: usize| match self.spread {
+            TosSpread::Straddle => self.strikes[0],
+            _ => self.strikes[leg],
+        };
+        self.spread
+            .bought_legs()
+            .iter()
+            .enumerate()
+            .map(|(leg, &(ratio, style))| {
+                let bought = (ratio > 0) == (self.action != Action::Sell);
+                let side = if bought { Side::Long } else { Side::Short };
+                let contracts = self.quantity * Decimal::from(ratio.abs());
+                (side, self.styles[style], strike_of(leg), contracts)
+            })
+            .collect()
+    }
+
+    /// Builds the order into a strategy, pricing every leg with
+    /// Black-Scholes at the given underlying price and implied volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a leg cannot be priced or the legs do not form a
+    /// valid strategy.
+    pub fn to_strategy(
+        &self,
+        underlying_price: Positive,
+        implied_volatility: Positive,
+    ) -> Result<Strategy, StrategyError> {
+        let expiration = NaiveTime::from_hms_opt(18, 30, 0)
+            .map(|time| self.expiration.and_time(time).and_utc())
+            .ok_or_else(|| StrategyError::invalid_parameters("to_strategy", "invalid time"))?;
+        let mut legs = Vec::new();
+        for (side, style, strike, quantity) in self.legs() {
+            let option = Options::new(
+                OptionType::European,
+                side,
+                self.symbol.clone(),
+                strike,
+                ExpirationDate::DateTime(expiration),
+                implied_volatility,
+                quantity,
+                underlying_price,
+                Decimal::ZERO,
+                style,
+                Positive::ZERO,
+                None,
+            );
+            let premium = Positive::new_decimal(option.calculate_price_black_scholes()?.abs())?;
+            legs.push(Position::new(
+                option,
+                premium,
+                Utc::now(),
+                Positive::ZERO,
+                Positive::ZERO,
+                None,
+                None,
+            ));
+        }
+        StrategyBuilder::new()
+            .description(self.to_string())
+            .legs(legs)
+            .build()
+    }
+
+    /// Writes the legs of a strategy as an order string, with the net
+    /// premium of the legs as price.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the legs span several underlyings or expirations,
+    /// their quantities are not whole multiples of the smallest one, or they
+    /// do not form one of the [`TosSpread`] layouts.
+    pub fn from_strategy(strategy: &Strategy) -> Result<Self, StrategyError> {
+        let unsupported = |reason: &str| StrategyError::invalid_parameters("from_strategy", reason);
+        let first = strategy
+            .legs
+            .first()
+            .ok_or_else(|| unsupported("the strategy has no legs"))?;
+        let symbol = first.option.underlying_symbol.clone();
+        let expiration = first
+            .option
+            .expiration_date
+            .get_date()
+            .map_err(|e| unsupported(&e.to_string()))?
+            .date_naive();
+        let unit = strategy
+            .legs
+            .iter()
+            .map(|leg| leg.option.quantity)
+            .min()
+            .unwrap_or(Positive::ZERO);
+        if unit == Positive::ZERO {
+            return Err(unsupported("a leg has no contracts"));
+        }
+
+        let mut legs = Vec::new();
+        let mut net = Decimal::ZERO;
+        for leg in &strategy.legs {
+            let option = &leg.option;
+            let same_expiration = option
+                .expiration_date
+                .get_date()
+                .is_ok_and(|date| date.date_naive() == expiration);
+            if option.underlying_symbol != symbol || !same_expiration {
+                return Err(unsupported("legs on several underlyings or expirations"));
+            }
+            let ratio = option.quantity.to_dec() / unit.to_dec();
+            if ratio.fract() != Decimal::ZERO {
+                return Err(unsupported("leg quantities are not whole ratios"));
+            }
+            let ratio = ratio.trunc().try_into().unwrap_or(0i64);
+            let signed = match option.side {
+                Side::Long => ratio,
+                Side::Short => -ratio,
+            };
+            net += leg.premium.to_dec() * Decimal::from(signed);
+            legs.push((signed, option.option_style, option.strike_price));
+        }
+        legs.sort_by_key(|&(_, style, strike)| (style == OptionStyle::Put, strike));
+
+        let (spread, bought, strikes, styles) = layout(&legs)
+            .ok_or_else(|| unsupported("the legs do not form a thinkorswim spread"))?;
+        Ok(TosOrder {
+            action: if bought { Action::Buy } else { Action::Sell },
+            quantity: unit,
+            spread,
+            symbol,
+            multiplier: STANDARD_MULTIPLIER,
+            series: None,
+            expiration,
+            strikes,
+            styles,
+            price: Positive::new_decimal(net.abs().round_dp(2)).ok(),
+            order_type: None,
+        })
+    }
+}
+
+/// Layout of legs sorted calls first, then by strike: the spread, whether
+/// it is bought, its strikes and its styles.
+fn layout(
+    legs: &[(i64, OptionStyle, Positive)],
+) -> Option<(TosSpread, bool, Vec<Positive>, Vec<OptionStyle>)> {
+    let signs: Vec<i64> = legs.iter().map(|leg| leg.0).collect();
+    let strikes = |order: &[usize]| order.iter().map(|&i| legs[i].2).collect::<Vec<_>>();
+    let same_style = legs.iter().all(|leg| leg.1 == legs[0].1);
+    // A vertical is a sale when the leg sold is the more expensive one.
+    let vertical = |long: usize, short: usize| {
+        let credit = match legs[long].1 {
+            OptionStyle::Call => legs[short].2 < legs[long].2,
+            OptionStyle::Put => legs[short].2 > legs[long].2,
+        };
+        if credit {
+            (false, vec![short, long])
+        } else {
+            (true, vec![long, short])
+        }
+    };
+    let pair = |a: usize, b: usize| match (signs[a], signs[b]) {
+        (1, -1) => Some((a, b)),
+        (-1, 1) => Some((b, a)),
+        _ => None,
+    };
+
+    match (legs.len(), same_style) {
+        (1, _) if signs[0].abs() == 1 => Some((
+            TosSpread::Single,
+            signs[0] > 0,
+            strikes(&[0]),
+            vec![legs[0].1],
+        )),
+        (2, true) => {
+            let (long, short) = pair(0, 1)?;
+            let (bought, order) = vertical(long, short);
+            Some((
+                TosSpread::Vertical,
+                bought,
+                strikes(&order),
+                vec![legs[0].1],
+            ))
+        }
+        (2, false) if signs[0] == signs[1] && signs[0].abs() == 1 => {
+            let spread = if legs[0].2 == legs[1].2 {
+                TosSpread::Straddle
+            } else {
+                TosSpread::Strangle
+            };
+            let order: &[usize] = match spread {
+                TosSpread::Straddle => &[0],
+                _ => &[0, 1],
+            };
+            Some((
+                spread,
+                signs[0] > 0,
+                strikes(order),
+                vec![OptionStyle::Call, OptionStyle::Put],
+            ))
+        }
+        (3, true) if signs == [1, -2, 1] || signs == [-1, 2, -1] => Some((
+            TosSpread::Butterfly,
+            signs[0] > 0,
+            strikes(&[0, 1, 2]),
+            vec![legs[0].1],
+        )),
+        (4, true) if signs == [1, -1, -1, 1] || signs == [-1, 1, 1, -1] => Some((
+            TosSpread::Condor,
+            signs[0] > 0,
+            strikes(&[0, 1, 2, 3]),
+            vec![legs[0].1],
+        )),
+        (4, false) if legs[1].1 == OptionStyle::Call && legs[2].1 == OptionStyle::Put => {
+            let (call_long, call_short) = pair(0, 1)?;
+            let (put_long, put_short) = pair(2, 3)?;
+            let (bought, _) = vertical(call_long, call_short);
+            let order = if bought {
+                [call_long, call_short, put_long, put_short]
+            } else {
+                [call_short, call_long, put_short, put_long]
+            };
+            Some((
+                TosSpread::IronCondor,
+                bought,
+                strikes(&order),
+                vec![OptionStyle::Call, OptionStyle::Put],
+            ))
+        }
+        _ => None,
+    }
+}
+
+impl FromStr for TosOrder {
+    type Err = StrategyError;
+
+    fn from_str(order: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| {
+            StrategyError::invalid_parameters(
+                "parse thinkorswim order",
+                &format!("'{order}': {reason}"),
+            )
+        };
+        let mut tokens = order.split_whitespace();
+        let mut next = |what: &str| {
+            tokens
+                .next()
+                .map(str::to_string)
+                .ok_or_else(|| invalid(&format!("no {what}")))
+        };
+
+        let action = match next("action")?.to_ascii_uppercase().as_str() {
+            "BUY" => Action::Buy,
+            "SELL" => Action::Sell,
+            _ => return Err(invalid("the order must start with BUY or SELL")),
+        };
+        let quantity = Decimal::from_str(next("quantity")?.trim_start_matches('+'))
+            .ok()
+            .and_then(|quantity| Positive::new_decimal(quantity.abs()).ok())
+            .filter(|quantity| *quantity > Positive::ZERO)
+            .ok_or_else(|| invalid("invalid quantity"))?;
+
+        let mut word = next("symbol")?;
+        let spread = match word.to_ascii_uppercase().as_str() {
+            "VERTICAL" => TosSpread::Vertical,
+            "BUTTERFLY" => TosSpread::Butterfly,
+            "CONDOR" => TosSpread::Condor,
+            "STRADDLE" => TosSpread::Straddle,
+            "STRANGLE" => TosSpread::Strangle,
+            "IRON" => match next("spread")?.to_ascii_uppercase().as_str() {
+                "CONDOR" => TosSpread::IronCondor,
+                other => return Err(invalid(&format!("IRON {other} orders are not supported"))),
+            },
+            "CALENDAR" | "DIAGONAL" | "DBL" | "DOUBLE" | "BACKRATIO" | "RATIO" | "COVERED"
+            | "COMBO" | "COLLAR" | "VERT" | "~BUTTERFLY" | "~CONDOR" | "~IRON" => {
+                return Err(invalid(&format!("{word} orders are not supported")));
+            }
+            _ => TosSpread::Single,
+        };
+        if spread != TosSpread::Single {
+            word = next("symbol")?;
+        }
+        let symbol = word;
+        let multiplier = next("multiplier")?
+            .parse()
+            .map_err(|_| invalid("invalid multiplier"))?;
+
+        let mut token = next("expiration")?;
+        let series = if token.starts_with('(') {
+            let mut series = vec![token.clone()];
+            while !token.ends_with(')') {
+                token = next("end of series")?;
+                series.push(token.clone());
+            }
+            token = next("expiration")?;
+            Some(series.join(" "))
+        } else {
+            None
+        };
+        let date = format!("{token} {} {}", next("expiration")?, next("expiration")?);
+        let expiration = NaiveDate::parse_from_str(&date, "%d %b %y").map_err(|_| {
+            invalid(&format!(
+                "invalid expiration '{date}', calendars are not supported"
+            ))
+        })?;
+
+        let strikes = next("strikes")?
+            .split('/')
+            .map(|strike| {
+                Decimal::from_str(strike)
+                    .ok()
+                    .and_then(|strike| Positive::new_decimal(strike).ok())
+                    .ok_or_else(|| invalid(&format!("invalid strike '{strike}'")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let styles = next("style")?
+            .to_ascii_uppercase()
+            .split('/')
+            .map(|style| match style {
+                "CALL" => Ok(OptionStyle::Call),
+                "PUT" => Ok(OptionStyle::Put),
+                other => Err(invalid(&format!("invalid style '{other}'"))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let expected_strikes = match spread {
+            TosSpread::Straddle => 1,
+            _ => spread.bought_legs().len(),
+        };
+        if strikes.len() != expected_strikes || styles.len() != spread.styles() {
+            return Err(invalid(&format!(
+                "a {:?} takes {expected_strikes} strikes and {} styles",
+                spread,
+                spread.styles()
+            )));
+        }
+
+        let mut price = None;
+        let mut order_type = Vec::new();
+        while let Ok(token) = next("order type") {
+            match token.strip_prefix('@') {
+                Some(value) if price.is_none() && order_type.is_empty() => {
+                    let value = match value {
+                        "" => next("price")?,
+                        value => value.to_string(),
+                    };
+                    let parsed = Decimal::from_str(&value)
+                        .ok()
+                        .and_then(|value| Positive::new_decimal(value.abs()).ok())
+                        .ok_or_else(|| invalid(&format!("invalid price '{value}'")))?;
+                    price = Some(parsed);
+                }
+                _ => order_type.push(token),
+            }
+        }
+
+        Ok(TosOrder {
+            action,
+            quantity,
+            spread,
+            symbol,
+            multiplier,
+            series,
+            expiration,
+            strikes,
+            styles,
+            price,
+            order_type: (!order_type.is_empty()).then(|| order_type.join(" ")),
+        })
+    }
+}
+
+impl fmt::Display for TosOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (action, sign) = match self.action {
+            Action::Sell => ("SELL", '-'),
+            _ => ("BUY", '+'),
+        };
+        write!(f, "{action} {sign}{} ", self.quantity)?;
+        if self.spread != TosSpread::Single {
+            write!(f, "{} ", self.spread.keyword())?;
+        }
+        write!(f, "{} {} ", self.symbol, self.multiplier)?;
+        if let Some(series) = &self.series {
+            write!(f, "{series} ")?;
+        }
+        let join = |values: Vec<String>| values.join("/");
+        write!(
+            f,
+            "{} {} {}",
+            self.expiration
+                .format("%d %b %y")
+                .to_string()
+                .to_uppercase(),
+            join(
+                self.strikes
+                    .iter()
+                    .map(|strike| strike.to_string())
+                    .collect()
+            ),
+            join(
+                self.styles
+                    .iter()
+                    .map(|style| match style {
+                        OptionStyle::Call => "CALL".to_string(),
+                        OptionStyle::Put => "PUT".to_string(),
+                    })
+                    .collect()
+            )
+        )?;
+        if let Some(price) = self.price {
+            write!(f, " @{:.2}", price.to_dec())?;
+        }
+        if let Some(order_type) = &self.order_type {
+            write!(f, " {order_type}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_tos {
+    use super::*;
+    use crate::strategies::base::StrategyType;
+    use positive::pos_or_panic;
+
+    fn strategy(order: &str) -> Strategy {
+        order
+            .parse::<TosOrder>()
+            .unwrap()
+            .to_strategy(pos_or_panic!(445.0), pos_or_panic!(0.2))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let text = "SELL -1 IRON CONDOR SPY 100 (Weeklys) 18 DEC 26 460/465/430/425 CALL/PUT @1.20 LMT GTC";
+        let order: TosOrder = text.parse().unwrap();
+        assert_eq!(order.action, Action::Sell);
+        assert_eq!(order.spread, TosSpread::IronCondor);
+        assert_eq!(order.symbol, "SPY");
+        assert_eq!(order.series.as_deref(), Some("(Weeklys)"));
+        assert_eq!(
+            order.expiration,
+            NaiveDate::from_ymd_opt(2026, 12, 18).unwrap()
+        );
+        assert_eq!(order.price, Some(pos_or_panic!(1.2)));
+        assert_eq!(order.order_type.as_deref(), Some("LMT GTC"));
+        assert_eq!(order.to_string(), text);
+
+        let legs = order.legs();
+        assert_eq!(
+            legs[0],
+            (
+                Side::Short,
+                OptionStyle::Call,
+                pos_or_panic!(460.0),
+                Positive::ONE
+            )
+        );
+        assert_eq!(
+            legs[3],
+            (
+                Side::Long,
+                OptionStyle::Put,
+                pos_or_panic!(425.0),
+                Positive::ONE
+            )
+        );
+
+        let single: TosOrder = "buy +3 SPY 100 18 DEC 26 452.5 PUT".parse().unwrap();
+        assert_eq!(single.spread, TosSpread::Single);
+        assert_eq!(single.to_string(), "BUY +3 SPY 100 18 DEC 26 452.5 PUT");
+    }
+
+    #[test]
+    fn test_strategies_roundtrip() {
+        for (text, kind) in [
+            (
+                "BUY +1 VERTICAL SPY 100 18 DEC 26 440/450 CALL",
+                StrategyType::BullCallSpread,
+            ),
+            (
+                "SELL -1 VERTICAL SPY 100 18 DEC 26 440/430 PUT",
+                StrategyType::BullPutSpread,
+            ),
+            (
+                "SELL -2 IRON CONDOR SPY 100 18 DEC 26 460/465/430/425 CALL/PUT",
+                StrategyType::IronCondor,
+            ),
+            (
+                "BUY +1 STRADDLE SPY 100 18 DEC 26 445 CALL/PUT",
+                StrategyType::LongStraddle,
+            ),
+            (
+                "SELL -1 STRANGLE SPY 100 18 DEC 26 460/430 CALL/PUT",
+                StrategyType::ShortStrangle,
+            ),
+            (
+                "BUY +1 BUTTERFLY SPY 100 18 DEC 26 435/445/455 CALL",
+                StrategyType::LongButterflySpread,
+            ),
+            (
+                "BUY +1 CONDOR SPY 100 18 DEC 26 430/440/450/460 PUT",
+                StrategyType::Custom,
+            ),
+            ("SELL -1 SPY 100 18 DEC 26 430 PUT", StrategyType::ShortPut),
+        ] {
+            let built = strategy(text);
+            assert_eq!(built.kind, kind, "{text}");
+            let rendered = TosOrder::from_strategy(&built).unwrap().to_string();
+            assert!(rendered.starts_with(text), "{rendered} != {text}");
+        }
+    }
+
+    #[test]
+    fn test_unsupported_orders() {
+        for text in [
+            "BUY +1 CALENDAR SPY 100 18 DEC 26/15 JAN 27 450 CALL",
+            "BUY +1 VERTICAL SPY 100 18 DEC 26 440 CALL",
+            "BUY +1 STRADDLE SPY 100 18 DEC 26 445 CALL",
+            "HOLD +1 SPY 100 18 DEC 26 445 CALL",
+        ] {
+            assert!(text.parse::<TosOrder>().is_err(), "{text}");
+        }
+
+        let mut ratio = strategy("BUY +1 VERTICAL SPY 100 18 DEC 26 440/450 CALL");
+        ratio.legs[1].option.quantity = Positive::TWO;
+        assert!(TosOrder::from_strategy(&ratio).is_err());
+    }
+}
@@ -0,0 +1,403 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Strategy Validation
+//!
+//! Checks that the legs of a strategy are consistent with each other and with
+//! the declared [`StrategyType`]:
+//!
+//! * every leg is written on the same underlying,
+//! * no leg has a zero quantity,
+//! * every expiration can be resolved and the expirations follow the
+//!   strategy type (a single expiration for vertical strategies, a short near
+//!   leg and a long far leg for time spreads),
+//! * the side and style of every leg belong to the strategy type.
+//!
+//! Failures are reported as [`ValidationErrorKind`] values wrapped in
+//! [`StrategyError::ValidationError`], identifying the offending leg.
+
+use crate::error::strategies::{StrategyError, ValidationErrorKind};
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, Side};
+use crate::strategies::Strategable;
+use crate::strategies::base::StrategyType;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// Largest difference, in days, between expirations considered equal.
+const EXPIRATION_TOLERANCE_DAYS: Decimal = dec!(0.0001);
+
+/// How the expirations of the legs of a strategy type relate to each other.
+enum ExpirationRule {
+    /// All legs share the same expiration.
+    Same,
+    /// The short leg expires strictly before the long leg.
+    ShortBeforeLong,
+    /// Any combination of expirations is accepted.
+    Any,
+}
+
+fn expiration_rule(kind: &StrategyType) -> ExpirationRule {
+    match kind {
+        StrategyType::CalendarSpread
+        | StrategyType::DiagonalSpread
+        | StrategyType::PoorMansCoveredCall => ExpirationRule::ShortBeforeLong,
+        StrategyType::Custom => ExpirationRule::Any,
+        _ => ExpirationRule::Same,
+    }
+}
+
+/// Returns the side and style of every leg required by the strategy type, or
+/// `None` if the strategy type accepts any combination of legs.
+///
+/// Time spreads can be built with calls or puts, so their style is taken from
+/// the first leg.
+fn expected_legs(
+    kind: &StrategyType,
+    first_style: OptionStyle,
+) -> Option<Vec<(Side, OptionStyle)>> {
+    use OptionStyle::{Call, Put};
+    use Side::{Long, Short};
+    let legs = match kind {
+        StrategyType::BullCallSpread | StrategyType::BearCallSpread => {
+            vec![(Long, Call), (Short, Call)]
+        }
+        StrategyType::BullPutSpread | StrategyType::BearPutSpread => {
+            vec![(Long, Put), (Short, Put)]
+        }
+        StrategyType::LongButterflySpread => vec![(Long, Call), (Short, Call), (Long, Call)],
+        StrategyType::ShortButterflySpread => vec![(Short, Call), (Long, Call), (Short, Call)],
+        StrategyType::CallButterfly => vec![(Long, Call), (Short, Call), (Short, Call)],
+        StrategyType::IronCondor | StrategyType::IronButterfly => {
+            vec![(Short, Call), (Short, Put), (Long, Call), (Long, Put)]
+        }
+        StrategyType::LongStraddle | StrategyType::LongStrangle => {
+            vec![(Long, Call), (Long, Put)]
+        }
+        StrategyType::ShortStraddle | StrategyType::ShortStrangle => {
+            vec![(Short, Call), (Short, Put)]
+        }
+        StrategyType::CoveredCall | StrategyType::ShortCall => vec![(Short, Call)],
+        StrategyType::ProtectivePut | StrategyType::LongPut => vec![(Long, Put)],
+        StrategyType::Collar => vec![(Long, Put), (Short, Call)],
+        StrategyType::LongCall => vec![(Long, Call)],
+        StrategyType::ShortPut => vec![(Short, Put)],
+        StrategyType::PoorMansCoveredCall => vec![(Long, Call), (Short, Call)],
+        StrategyType::CalendarSpread | StrategyType::DiagonalSpread => {
+            vec![(Short, first_style), (Long, first_style)]
+        }
+        StrategyType::Custom => return None,
+    };
+    Some(legs)
+}
+
+/// Validates a set of legs against the declared strategy type.
+///
+/// # Arguments
+///
+/// * `kind` - The strategy type the legs are meant to build.
+/// * `positions` - The legs of the strategy.
+///
+/// # Errors
+///
+/// Returns a `StrategyError::ValidationError` describing the first
+/// inconsistency found.
+pub fn validate_legs(kind: &StrategyType, positions: &[&Position]) -> Result<(), StrategyError> {
+    let first = positions
+        .first()
+        .ok_or(StrategyError::ValidationError(ValidationErrorKind::NoLegs))?;
+
+    let mut days = Vec::with_capacity(positions.len());
+    for (leg, position) in positions.iter().enumerate() {
+        let option = &position.option;
+        if option.underlying_symbol != first.option.underlying_symbol {
+            return Err(StrategyError::ValidationError(
+                ValidationErrorKind::MixedUnderlying {
+                    leg,
+                    expected: first.option.underlying_symbol.clone(),
+                    found: option.underlying_symbol.clone(),
+                },
+            ));
+        }
+        if option.quantity == Positive::ZERO {
+            return Err(StrategyError::ValidationError(
+                ValidationErrorKind::ZeroQuantity { leg },
+            ));
+        }
+        let leg_days = option.expiration_date.get_days().map_err(|e| {
+            StrategyError::ValidationError(ValidationErrorKind::InvalidExpiration {
+                leg,
+                reason: e.to_string(),
+            })
+        })?;
+        days.push(leg_days.to_dec());
+    }
+
+    if let Some(expected) = expected_legs(kind, first.option.option_style) {
+        if expected.len() != positions.len() {
+            return Err(StrategyError::ValidationError(
+                ValidationErrorKind::LegCount {
+                    strategy: kind.clone(),
+                    expected: expected.len(),
+                    found: positions.len(),
+                },
+            ));
+        }
+        let mut unmatched = expected;
+        for (leg, position) in positions.iter().enumerate() {
+            let side = position.option.side;
+            let style = position.option.option_style;
+            match unmatched
+                .iter()
+                .position(|&required| required == (side, style))
+            {
+                Some(index) => {
+                    unmatched.swap_remove(index);
+                }
+                None => {
+                    return Err(StrategyError::ValidationError(
+                        ValidationErrorKind::IncompatibleLeg {
+                            strategy: kind.clone(),
+                            leg,
+                            side,
+                            style,
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    match expiration_rule(kind) {
+        ExpirationRule::Same => {
+            if let Some(leg) = days
+                .iter()
+                .position(|d| (*d - days[0]).abs() > EXPIRATION_TOLERANCE_DAYS)
+            {
+                return Err(StrategyError::ValidationError(
+                    ValidationErrorKind::ExpirationMismatch {
+                        strategy: kind.clone(),
+                        reason: format!("leg {leg} expires on a different date than leg 0"),
+                    },
+                ));
+            }
+        }
+        ExpirationRule::ShortBeforeLong => {
+            let leg_days = |side: Side| {
+                positions
+                    .iter()
+                    .zip(&days)
+                    .find(|(position, _)| position.option.side == side)
+                    .map(|(_, d)| *d)
+            };
+            if let (Some(short_days), Some(long_days)) =
+                (leg_days(Side::Short), leg_days(Side::Long))
+                && short_days + EXPIRATION_TOLERANCE_DAYS >= long_days
+            {
+                return Err(StrategyError::ValidationError(
+                    ValidationErrorKind::ExpirationMismatch {
+                        strategy: kind.clone(),
+                        reason: "the short leg must expire before the long leg".to_string(),
+                    },
+                ));
+            }
+        }
+        ExpirationRule::Any => {}
+    }
+
+    Ok(())
+}
+
+/// Structured validation of the legs of a strategy.
+///
+/// Unlike [`Validable`](crate::strategies::Validable), which only reports
+/// whether a strategy is usable, this trait explains what is wrong with it.
+/// It is implemented for every [`Strategable`] type.
+pub trait Validate: Strategable {
+    /// Validates the legs of the strategy against its declared type.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError::ValidationError` describing the first
+    /// inconsistency found, or the error raised while reading the strategy
+    /// information or positions.
+    fn validate_strategy(&self) -> Result<(), StrategyError> {
+        let kind = self.info()?.kind;
+        let positions = self.get_positions()?;
+        validate_legs(&kind, &positions)
+    }
+}
+
+impl<T: Strategable + ?Sized> Validate for T {}
+
+#[cfg(test)]
+mod tests_validation {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::utils::create_sample_position;
+    use crate::strategies::{BullCallSpread, CalendarSpread, IronCondor};
+    use positive::pos_or_panic;
+
+    fn leg(side: Side, style: OptionStyle, strike: f64) -> Position {
+        create_sample_position(
+            style,
+            side,
+            Positive::HUNDRED,
+            Positive::ONE,
+            pos_or_panic!(strike),
+            pos_or_panic!(0.2),
+        )
+    }
+
+    fn kind_of(error: StrategyError) -> ValidationErrorKind {
+        match error {
+            StrategyError::ValidationError(kind) => kind,
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn test_valid_vertical_spread() {
+        let long = leg(Side::Long, OptionStyle::Call, 95.0);
+        let short = leg(Side::Short, OptionStyle::Call, 105.0);
+        assert!(validate_legs(&StrategyType::BullCallSpread, &[&short, &long]).is_ok());
+    }
+
+    #[test]
+    fn test_no_legs() {
+        let error = validate_legs(&StrategyType::Custom, &[]).unwrap_err();
+        assert_eq!(kind_of(error), ValidationErrorKind::NoLegs);
+    }
+
+    #[test]
+    fn test_mixed_underlying_and_zero_quantity() {
+        let long = leg(Side::Long, OptionStyle::Call, 95.0);
+        let mut other = leg(Side::Short, OptionStyle::Call, 105.0);
+        other.option.underlying_symbol = "MSFT".to_string();
+        let error = validate_legs(&StrategyType::BullCallSpread, &[&long, &other]).unwrap_err();
+        assert!(matches!(
+            kind_of(error),
+            ValidationErrorKind::MixedUnderlying { leg: 1, .. }
+        ));
+
+        let mut empty = leg(Side::Short, OptionStyle::Call, 105.0);
+        empty.option.quantity = Positive::ZERO;
+        let error = validate_legs(&StrategyType::Custom, &[&long, &empty]).unwrap_err();
+        assert_eq!(kind_of(error), ValidationErrorKind::ZeroQuantity { leg: 1 });
+    }
+
+    #[test]
+    fn test_leg_count_and_incompatible_leg() {
+        let long = leg(Side::Long, OptionStyle::Call, 95.0);
+        let error = validate_legs(&StrategyType::BullCallSpread, &[&long]).unwrap_err();
+        assert_eq!(
+            kind_of(error),
+            ValidationErrorKind::LegCount {
+                strategy: StrategyType::BullCallSpread,
+                expected: 2,
+                found: 1,
+            }
+        );
+
+        let put = leg(Side::Short, OptionStyle::Put, 105.0);
+        let error = validate_legs(&StrategyType::BullCallSpread, &[&long, &put]).unwrap_err();
+        assert_eq!(
+            kind_of(error),
+            ValidationErrorKind::IncompatibleLeg {
+                strategy: StrategyType::BullCallSpread,
+                leg: 1,
+                side: Side::Short,
+                style: OptionStyle::Put,
+            }
+        );
+
+        let second_long = leg(Side::Long, OptionStyle::Call, 105.0);
+        assert!(validate_legs(&StrategyType::BullCallSpread, &[&long, &second_long]).is_err());
+        assert!(validate_legs(&StrategyType::Custom, &[&long, &put, &second_long]).is_ok());
+    }
+
+    #[test]
+    fn test_expiration_rules() {
+        let long = leg(Side::Long, OptionStyle::Call, 100.0);
+        let mut short = leg(Side::Short, OptionStyle::Call, 100.0);
+        short.option.expiration_date = ExpirationDate::Days(pos_or_panic!(10.0));
+        let error = validate_legs(&StrategyType::BullCallSpread, &[&long, &short]).unwrap_err();
+        assert!(matches!(
+            kind_of(error),
+            ValidationErrorKind::ExpirationMismatch { .. }
+        ));
+
+        // The sample legs expire in 30 days, so the short leg expires first.
+        assert!(validate_legs(&StrategyType::CalendarSpread, &[&short, &long]).is_ok());
+        short.option.expiration_date = ExpirationDate::Days(pos_or_panic!(60.0));
+        assert!(validate_legs(&StrategyType::CalendarSpread, &[&short, &long]).is_err());
+    }
+
+    #[test]
+    fn test_validate_trait_on_strategies() {
+        let spread = BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(6.0),
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(spread.validate_strategy().is_ok());
+
+        let condor = IronCondor::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(105.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(110.0),
+            pos_or_panic!(90.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.0),
+            pos_or_panic!(2.0),
+            pos_or_panic!(1.0),
+            pos_or_panic!(1.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(condor.validate_strategy().is_ok());
+
+        let calendar = CalendarSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            Positive::HUNDRED,
+            OptionStyle::Call,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            ExpirationDate::Days(pos_or_panic!(60.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.0),
+            pos_or_panic!(3.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(calendar.validate_strategy().is_ok());
+    }
+}
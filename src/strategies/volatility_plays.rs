@@ -0,0 +1,342 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Volatility Plays
+//!
+//! Straddles and strangles are bets on the size of the move of the underlying
+//! rather than on its direction. The [`VolatilityPlay`] trait compares their
+//! break-even points with the move implied by the options themselves,
+//! `S · σ · √T`, and collects their net Greeks in a single summary.
+//!
+//! A long straddle or strangle needs the underlying to travel beyond a
+//! break-even; it is favourable when the break-evens sit inside the implied
+//! move. A short one is favourable when they sit outside it.
+
+use crate::error::strategies::StrategyError;
+use crate::greeks::{Greeks, NetGreeks};
+use crate::model::position::Position;
+use crate::strategies::base::Strategies;
+use crate::strategies::{LongStraddle, LongStrangle, ShortStraddle, ShortStrangle};
+use positive::Positive;
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Break-even points of a volatility play compared with the implied move.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExpectedMoveComparison {
+    /// Underlying price.
+    pub underlying_price: Positive,
+    /// One standard deviation move implied by the legs until expiration.
+    pub expected_move: Positive,
+    /// Underlying price minus the expected move.
+    pub lower_expected: Positive,
+    /// Underlying price plus the expected move.
+    pub upper_expected: Positive,
+    /// Lower break-even point.
+    pub lower_break_even: Positive,
+    /// Upper break-even point.
+    pub upper_break_even: Positive,
+    /// Smallest move from the underlying price that reaches a break-even.
+    pub required_move: Positive,
+    /// Required move divided by the expected move.
+    pub move_ratio: Decimal,
+    /// Whether the play is long volatility.
+    pub is_long: bool,
+}
+
+impl ExpectedMoveComparison {
+    /// Returns `true` if both break-evens lie within the expected move range.
+    pub fn break_evens_within_expected_move(&self) -> bool {
+        self.lower_break_even >= self.lower_expected && self.upper_break_even <= self.upper_expected
+    }
+
+    /// Returns `true` if the implied move favours the play: inside the
+    /// expected range for long plays, outside it for short plays.
+    pub fn is_favorable(&self) -> bool {
+        self.break_evens_within_expected_move() == self.is_long
+    }
+}
+
+/// Summary of the metrics of a straddle or strangle.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct VolatilityPlayMetrics {
+    /// Lower and upper break-even points.
+    pub break_even_points: Vec<Positive>,
+    /// Comparison of the break-evens with the implied move.
+    pub expected_move: ExpectedMoveComparison,
+    /// Net Greeks of the two legs, signed by side.
+    pub greeks: NetGreeks,
+}
+
+/// Common analysis of straddles and strangles.
+pub trait VolatilityPlay: Strategies + Greeks {
+    /// Returns the call leg.
+    fn call_leg(&self) -> &Position;
+
+    /// Returns the put leg.
+    fn put_leg(&self) -> &Position;
+
+    /// Returns `true` if the play buys both legs.
+    fn is_long_volatility(&self) -> bool;
+
+    /// One standard deviation move implied by the legs until expiration.
+    ///
+    /// Uses the average implied volatility of both legs and the time to
+    /// expiration of the call leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if the time to expiration cannot be computed.
+    fn expected_move(&self) -> Result<Positive, StrategyError> {
+        let call = &self.call_leg().option;
+        let put = &self.put_leg().option;
+        let volatility = (call.implied_volatility + put.implied_volatility) / Positive::TWO;
+        let years = call.time_to_expiration()?;
+        let sqrt_years = years.to_dec().sqrt().unwrap_or(Decimal::ZERO);
+        Ok(Positive::new_decimal(
+            call.underlying_price.to_dec() * volatility.to_dec() * sqrt_years,
+        )?)
+    }
+
+    /// Compares the break-even points with the implied move.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if the strategy does not have two break-even
+    /// points or the expected move cannot be computed.
+    fn compare_expected_move(&self) -> Result<ExpectedMoveComparison, StrategyError> {
+        let break_evens = self.get_break_even_points()?;
+        let (lower_break_even, upper_break_even) = match break_evens.as_slice() {
+            [lower, upper] => (*lower.min(upper), *lower.max(upper)),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "compare_expected_move",
+                    &format!("{} break-even points", break_evens.len()),
+                ));
+            }
+        };
+        let underlying_price = self.call_leg().option.underlying_price;
+        let expected_move = self.expected_move()?;
+        let required_move = (underlying_price.to_dec() - lower_break_even.to_dec())
+            .abs()
+            .min((upper_break_even.to_dec() - underlying_price.to_dec()).abs());
+        let move_ratio = if expected_move > Positive::ZERO {
+            required_move / expected_move.to_dec()
+        } else {
+            Decimal::MAX
+        };
+        Ok(ExpectedMoveComparison {
+            underlying_price,
+            expected_move,
+            lower_expected: underlying_price.sub_or_zero(&expected_move.to_dec()),
+            upper_expected: underlying_price + expected_move,
+            lower_break_even,
+            upper_break_even,
+            required_move: Positive::new_decimal(required_move)?,
+            move_ratio,
+            is_long: self.is_long_volatility(),
+        })
+    }
+
+    /// Collects the break-even points, the expected move comparison and the net Greeks.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StrategyError` if any of the metrics cannot be computed.
+    fn volatility_play_metrics(&self) -> Result<VolatilityPlayMetrics, StrategyError> {
+        Ok(VolatilityPlayMetrics {
+            break_even_points: self.get_break_even_points()?.clone(),
+            expected_move: self.compare_expected_move()?,
+            greeks: self.net_greeks()?,
+        })
+    }
+}
+
+impl VolatilityPlay for LongStraddle {
+    fn call_leg(&self) -> &Position {
+        &self.long_call
+    }
+
+    fn put_leg(&self) -> &Position {
+        &self.long_put
+    }
+
+    fn is_long_volatility(&self) -> bool {
+        true
+    }
+}
+
+impl VolatilityPlay for ShortStraddle {
+    fn call_leg(&self) -> &Position {
+        &self.short_call
+    }
+
+    fn put_leg(&self) -> &Position {
+        &self.short_put
+    }
+
+    fn is_long_volatility(&self) -> bool {
+        false
+    }
+}
+
+impl VolatilityPlay for LongStrangle {
+    fn call_leg(&self) -> &Position {
+        &self.long_call
+    }
+
+    fn put_leg(&self) -> &Position {
+        &self.long_put
+    }
+
+    fn is_long_volatility(&self) -> bool {
+        true
+    }
+}
+
+impl VolatilityPlay for ShortStrangle {
+    fn call_leg(&self) -> &Position {
+        &self.short_call
+    }
+
+    fn put_leg(&self) -> &Position {
+        &self.short_put
+    }
+
+    fn is_long_volatility(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests_volatility_plays {
+    use super::*;
+    use crate::ExpirationDate;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn long_straddle(premium: f64) -> LongStraddle {
+        LongStraddle::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(365.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(premium),
+            pos_or_panic!(premium),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn short_straddle(premium: f64) -> ShortStraddle {
+        ShortStraddle::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(365.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(premium),
+            pos_or_panic!(premium),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn short_strangle(premium: f64) -> ShortStrangle {
+        ShortStrangle::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(110.0),
+            pos_or_panic!(90.0),
+            ExpirationDate::Days(pos_or_panic!(365.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(premium),
+            pos_or_panic!(premium),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_expected_move() {
+        let strategy = long_straddle(5.0);
+        let expected = strategy.expected_move().unwrap();
+        assert!((expected.to_dec() - dec!(20)).abs() < dec!(0.1));
+    }
+
+    #[test]
+    fn test_long_straddle_comparison() {
+        let cheap = long_straddle(5.0).compare_expected_move().unwrap();
+        assert_eq!(cheap.lower_break_even, pos_or_panic!(90.0));
+        assert_eq!(cheap.upper_break_even, pos_or_panic!(110.0));
+        assert_eq!(cheap.required_move, pos_or_panic!(10.0));
+        assert!(cheap.break_evens_within_expected_move());
+        assert!(cheap.is_favorable());
+
+        let expensive = long_straddle(15.0).compare_expected_move().unwrap();
+        assert!(expensive.move_ratio > Decimal::ONE);
+        assert!(!expensive.is_favorable());
+    }
+
+    #[test]
+    fn test_short_strangle_comparison() {
+        let rich = short_strangle(8.0).compare_expected_move().unwrap();
+        assert!(!rich.is_long);
+        assert!(!rich.break_evens_within_expected_move());
+        assert!(rich.is_favorable());
+
+        let thin = short_strangle(1.0).compare_expected_move().unwrap();
+        assert!(!thin.is_favorable());
+    }
+
+    #[test]
+    fn test_volatility_play_metrics() {
+        let metrics = long_straddle(5.0).volatility_play_metrics().unwrap();
+        assert_eq!(metrics.break_even_points.len(), 2);
+        assert!(metrics.greeks.gamma > Decimal::ZERO);
+        assert!(metrics.greeks.delta.abs() < dec!(0.5));
+
+        let metrics = short_strangle(5.0).volatility_play_metrics().unwrap();
+        assert_eq!(
+            metrics.break_even_points,
+            vec![pos_or_panic!(80.0), pos_or_panic!(120.0)]
+        );
+        assert!(!metrics.expected_move.is_long);
+    }
+
+    #[test]
+    fn test_short_straddle_greeks_are_signed() {
+        let greeks = short_straddle(5.0)
+            .volatility_play_metrics()
+            .unwrap()
+            .greeks;
+        assert!(greeks.gamma < Decimal::ZERO);
+        assert!(greeks.vega < Decimal::ZERO);
+        assert!(greeks.theta > Decimal::ZERO);
+
+        let long = long_straddle(5.0).volatility_play_metrics().unwrap().greeks;
+        assert_eq!(greeks.gamma, -long.gamma);
+        assert_eq!(greeks.vega, -long.vega);
+    }
+}
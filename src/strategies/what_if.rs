@@ -0,0 +1,250 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # What-If Analysis
+//!
+//! Projects a strategy to a hypothetical market state given by three
+//! inputs: the underlying price, a valuation date and a parallel shift of
+//! implied volatility. This is what the price, date and volatility sliders
+//! of options analysis tools show, without callers re-deriving the pricing.
+//!
+//! Legs still alive on the date are priced with Black-Scholes at their
+//! shifted implied volatility and remaining time; legs expired by then settle
+//! at intrinsic value and no longer contribute Greeks.
+
+use crate::ExpirationDate;
+use crate::error::GreeksError;
+use crate::error::strategies::{PriceErrorKind, StrategyError};
+use crate::greeks::{Greeks, NetGreeks};
+use crate::model::option::Options;
+use crate::model::position::Position;
+use crate::strategies::base::{Strategies, Strategy};
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Seconds in a day, used to turn the valuation date into fractional days.
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Projected state of a strategy under a what-if scenario.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WhatIf {
+    /// Underlying price of the scenario.
+    pub underlying_price: Positive,
+    /// Valuation date of the scenario.
+    pub date: DateTime<Utc>,
+    /// Shift applied to the implied volatility of every leg (0.05 = +5 vol).
+    pub iv_shift: Decimal,
+    /// Model value of the legs, positive when the strategy is a net asset.
+    pub value: Decimal,
+    /// Profit or loss against the premiums and fees of the legs.
+    pub pnl: Decimal,
+    /// Net Greeks of the legs still alive on the date.
+    pub greeks: NetGreeks,
+}
+
+/// Projects a strategy to a hypothetical price, date and volatility.
+pub trait WhatIfAnalysis: Strategies {
+    /// Values the strategy with the underlying at `spot` on `date`, with
+    /// the implied volatility of every leg shifted by `iv_shift`. Dates in
+    /// the past value the legs as of now.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::PriceError` if the shifted volatility of a leg
+    /// is not positive, or any error raised while valuing the legs.
+    fn what_if(
+        &self,
+        spot: Positive,
+        date: DateTime<Utc>,
+        iv_shift: Decimal,
+    ) -> Result<WhatIf, StrategyError> {
+        what_if(&self.get_positions()?, spot, date, iv_shift)
+    }
+}
+
+impl<T: Strategies + ?Sized> WhatIfAnalysis for T {}
+
+impl Strategy {
+    /// Values the legs of the strategy with the underlying at `spot` on
+    /// `date`, with their implied volatility shifted by `iv_shift`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::PriceError` if the shifted volatility of a leg
+    /// is not positive, or any error raised while valuing the legs.
+    pub fn what_if(
+        &self,
+        spot: Positive,
+        date: DateTime<Utc>,
+        iv_shift: Decimal,
+    ) -> Result<WhatIf, StrategyError> {
+        what_if(&self.legs.iter().collect::<Vec<_>>(), spot, date, iv_shift)
+    }
+}
+
+/// Options of the legs still alive in a scenario.
+struct LiveLegs(Vec<Options>);
+
+impl Greeks for LiveLegs {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self.0.iter().collect())
+    }
+}
+
+fn what_if(
+    positions: &[&Position],
+    spot: Positive,
+    date: DateTime<Utc>,
+    iv_shift: Decimal,
+) -> Result<WhatIf, StrategyError> {
+    let seconds = (date - Utc::now()).num_seconds().max(0);
+    let elapsed = Positive::new_decimal(Decimal::from(seconds) / Decimal::from(SECONDS_PER_DAY))?;
+    let horizon = ExpirationDate::Days(elapsed);
+
+    let mut value = Decimal::ZERO;
+    let mut pnl = Decimal::ZERO;
+    let mut live = Vec::new();
+    for position in positions {
+        let volatility = position.option.implied_volatility.to_dec() + iv_shift;
+        if volatility <= Decimal::ZERO {
+            return Err(StrategyError::PriceError(
+                PriceErrorKind::InvalidUnderlyingPrice {
+                    reason: format!("implied volatility shifted to {volatility}"),
+                },
+            ));
+        }
+        let mut shifted = (*position).clone();
+        shifted.option.implied_volatility = Positive::new_decimal(volatility)?;
+        pnl += shifted.pnl_at_horizon(&spot, &horizon)?;
+
+        let days =
+            shifted.option.expiration_date.get_days().map_err(|e| {
+                StrategyError::operation_not_supported(&e.to_string(), "expiration")
+            })?;
+        let mut marked = shifted.option;
+        marked.underlying_price = spot;
+        if days > elapsed {
+            marked.expiration_date = ExpirationDate::Days(days - elapsed);
+            value += marked.calculate_price_black_scholes()? * marked.quantity.to_dec();
+            live.push(marked);
+        } else {
+            value += marked.payoff()? * marked.quantity.to_dec();
+        }
+    }
+
+    Ok(WhatIf {
+        underlying_price: spot,
+        date,
+        iv_shift,
+        value,
+        pnl,
+        greeks: LiveLegs(live).net_greeks()?,
+    })
+}
+
+#[cfg(test)]
+mod tests_what_if {
+    use super::*;
+    use crate::pricing::payoff::Profit;
+    use crate::strategies::BullCallSpread;
+    use crate::strategies::base::{Positionable, StrategyType};
+    use chrono::Duration;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn bull_call_spread() -> BullCallSpread {
+        BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(7.0),
+            pos_or_panic!(3.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_today_matches_model_value() {
+        let spread = bull_call_spread();
+        let state = spread
+            .what_if(Positive::HUNDRED, Utc::now(), Decimal::ZERO)
+            .unwrap();
+        // The spread cost 4, so its P&L is its model value less that.
+        assert!((state.pnl - (state.value - dec!(4))).abs() < dec!(0.001));
+        assert!(state.value > Decimal::ZERO && state.value < dec!(10));
+        assert!(state.greeks.delta > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_expiration_settles_at_payoff() {
+        let spread = bull_call_spread();
+        let expired = spread
+            .what_if(
+                pos_or_panic!(110.0),
+                Utc::now() + Duration::days(31),
+                Decimal::ZERO,
+            )
+            .unwrap();
+        assert_eq!(expired.value, dec!(10));
+        assert_eq!(
+            expired.pnl,
+            spread.calculate_profit_at(&pos_or_panic!(110.0)).unwrap()
+        );
+        assert_eq!(expired.greeks.delta, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_volatility_shift() {
+        let spread = bull_call_spread();
+        let date = Utc::now() + Duration::days(10);
+        let calm = spread
+            .what_if(pos_or_panic!(120.0), date, dec!(-0.1))
+            .unwrap();
+        let wild = spread
+            .what_if(pos_or_panic!(120.0), date, dec!(0.1))
+            .unwrap();
+        // Deep in the money, more volatility lowers the value of the spread.
+        assert!(wild.value < calm.value);
+        assert!(wild.greeks.vega < Decimal::ZERO);
+        assert!(spread.what_if(Positive::HUNDRED, date, dec!(-0.2)).is_err());
+    }
+
+    #[test]
+    fn test_strategy_struct() {
+        let spread = bull_call_spread();
+        let mut strategy = Strategy::new(
+            "Spread".to_string(),
+            StrategyType::BullCallSpread,
+            String::new(),
+        );
+        strategy.legs = spread
+            .get_positions()
+            .unwrap()
+            .into_iter()
+            .cloned()
+            .collect();
+        let date = Utc::now() + Duration::days(5);
+        let from_struct = strategy
+            .what_if(pos_or_panic!(103.0), date, dec!(0.02))
+            .unwrap();
+        let from_trait = spread
+            .what_if(pos_or_panic!(103.0), date, dec!(0.02))
+            .unwrap();
+        assert!((from_struct.value - from_trait.value).abs() < dec!(0.0001));
+        assert!((from_struct.greeks.delta - from_trait.greeks.delta).abs() < dec!(0.0001));
+    }
+}
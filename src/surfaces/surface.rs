@@ -32,18 +32,15 @@ use crate::geometrics::{
 use crate::surfaces::Point3D;
 use crate::surfaces::types::Axis;
 use crate::utils::Len;
+use crate::utils::parallel::map_ordered;
 
 use crate::visualization::{Graph, GraphData, Surface3D};
 use num_traits::ToPrimitive;
-use rayon::iter::{
-    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
-};
 use rust_decimal::{Decimal, MathematicalOps};
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 use std::ops::Index;
-use std::sync::Arc;
 use utoipa::ToSchema;
 
 /// Represents a mathematical surface in 3D space.
@@ -512,20 +509,17 @@ impl GeometricObject<Point3D, Point2D> for Surface {
                 let x_step = (x_end - x_start) / Decimal::from(x_steps);
                 let y_step = (y_end - y_start) / Decimal::from(y_steps);
 
-                // Wrap f in an Arc so it can be shared across threads
-                let f = Arc::new(f);
-
-                let points: Result<BTreeSet<Point3D>, SurfaceError> = (0..=x_steps)
-                    .into_par_iter()
-                    .flat_map(|i| {
+                let grid: Vec<_> = (0..=x_steps)
+                    .flat_map(|i| (0..=y_steps).map(move |j| (i, j)))
+                    .collect();
+                let points: Result<BTreeSet<Point3D>, SurfaceError> =
+                    map_ordered(grid, |(i, j)| {
                         let x = x_start + x_step * Decimal::from(i);
-                        let f = Arc::clone(&f);
-                        (0..=y_steps).into_par_iter().map(move |j| {
-                            let y = y_start + y_step * Decimal::from(j);
-                            let t = Point2D::new(x, y);
-                            f(t).map_err(|e| SurfaceError::ConstructionError(e.to_string()))
-                        })
+                        let y = y_start + y_step * Decimal::from(j);
+                        let t = Point2D::new(x, y);
+                        f(t).map_err(|e| SurfaceError::ConstructionError(e.to_string()))
                     })
+                    .into_iter()
                     .collect();
 
                 points.map(Surface::new)
@@ -1226,63 +1220,58 @@ impl Arithmetic<Surface> for Surface {
         let x_step = (max_x - min_x) / Decimal::from(steps);
         let y_step = (max_y - min_y) / Decimal::from(steps);
 
-        let result_points: Result<Vec<Point3D>, SurfaceError> = (0..=steps)
-            .into_par_iter()
-            .flat_map(|i| {
-                let x = min_x + x_step * Decimal::from(i);
-                (0..=steps).into_par_iter().map(move |j| {
-                    let y = min_y + y_step * Decimal::from(j);
-                    let point = Point2D::new(x, y);
+        let grid: Vec<_> = (0..=steps)
+            .flat_map(|i| (0..=steps).map(move |j| (i, j)))
+            .collect();
+        let result_points: Result<Vec<Point3D>, SurfaceError> = map_ordered(grid, |(i, j)| {
+            let x = min_x + x_step * Decimal::from(i);
+            let y = min_y + y_step * Decimal::from(j);
+            let point = Point2D::new(x, y);
 
-                    // Interpolate z values
-                    let z_values: Result<Vec<Decimal>, SurfaceError> = surfaces
-                        .iter()
-                        .map(|surface| {
-                            surface
-                                .interpolate(point, InterpolationType::Cubic)
-                                .map(|point3d| point3d.z)
-                                .map_err(SurfaceError::from)
-                        })
-                        .collect();
-
-                    let z_values = z_values?;
-
-                    // Apply operation
-                    let result_z = match operation {
-                        MergeOperation::Add => z_values.par_iter().sum(),
-                        MergeOperation::Subtract => {
-                            let first = z_values.first().cloned().unwrap_or(Decimal::ZERO);
-                            let remaining_sum: Decimal = z_values.iter().skip(1).sum();
-                            first - remaining_sum
-                        }
-                        MergeOperation::Multiply => z_values.par_iter().product(),
-                        MergeOperation::Divide => {
-                            let first = z_values.first().cloned().unwrap_or(Decimal::ONE);
-                            z_values
-                                .par_iter()
-                                .skip(1)
-                                .fold(
-                                    || first,
-                                    |acc, &val| if val == Decimal::ZERO { acc } else { acc / val },
-                                )
-                                .reduce(|| first, |a, _b| a)
-                        }
-                        MergeOperation::Max => z_values
-                            .par_iter()
-                            .cloned()
-                            .max_by(|a, b| a.partial_cmp(b).unwrap())
-                            .unwrap_or(Decimal::ZERO),
-                        MergeOperation::Min => z_values
-                            .par_iter()
-                            .cloned()
-                            .min_by(|a, b| a.partial_cmp(b).unwrap())
-                            .unwrap_or(Decimal::ZERO),
-                    };
-
-                    Ok(Point3D::new(x, y, result_z))
+            // Interpolate z values
+            let z_values: Result<Vec<Decimal>, SurfaceError> = surfaces
+                .iter()
+                .map(|surface| {
+                    surface
+                        .interpolate(point, InterpolationType::Cubic)
+                        .map(|point3d| point3d.z)
+                        .map_err(SurfaceError::from)
                 })
-            })
-            .collect();
+                .collect();
+
+            let z_values = z_values?;
+
+            // Apply operation
+            let result_z = match operation {
+                MergeOperation::Add => z_values.iter().sum(),
+                MergeOperation::Subtract => {
+                    let first = z_values.first().cloned().unwrap_or(Decimal::ZERO);
+                    let remaining_sum: Decimal = z_values.iter().skip(1).sum();
+                    first - remaining_sum
+                }
+                MergeOperation::Multiply => z_values.iter().product(),
+                MergeOperation::Divide => {
+                    let first = z_values.first().cloned().unwrap_or(Decimal::ONE);
+                    z_values.iter().skip(1).fold(first, |acc, &val| {
+                        if val == Decimal::ZERO { acc } else { acc / val }
+                    })
+                }
+                MergeOperation::Max => z_values
+                    .iter()
+                    .cloned()
+                    .max_by(|a, b| a.partial_cmp(b).unwrap())
+                    .unwrap_or(Decimal::ZERO),
+                MergeOperation::Min => z_values
+                    .iter()
+                    .cloned()
+                    .min_by(|a, b| a.partial_cmp(b).unwrap())
+                    .unwrap_or(Decimal::ZERO),
+            };
+
+            Ok(Point3D::new(x, y, result_z))
+        })
+        .into_iter()
+        .collect();
 
         let result_points = result_points?;
         Ok(Surface::from_vector(result_points))
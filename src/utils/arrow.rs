@@ -0,0 +1,311 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Arrow Export
+//!
+//! Converts option chains and simulated price paths into Apache Arrow
+//! [`RecordBatch`]es and Parquet files, so research pipelines built on
+//! Polars, pandas or DuckDB can load results without a CSV round trip.
+//!
+//! Available with the `arrow` feature. Types implementing [`ToRecordBatch`]:
+//!
+//! - [`OptionChain`]: one row per strike, with the call and put quotes side by
+//!   side, as in [`crate::chains::OptionData`]. Missing quotes and greeks are
+//!   nulls.
+//! - [`PathMatrix`]: a long table with one row per path and observation time
+//!   (`path`, `step`, `time`, `price`), ready to be pivoted or grouped.
+//!
+//! Prices, rates and greeks are written as `Float64`. The schema metadata
+//! records the chain symbol and expiration, or the shape of the simulation.
+//!
+//! ```rust
+//! use optionstratlib::simulation::{PathSimulator, TimeGrid};
+//! use optionstratlib::utils::arrow::ToRecordBatch;
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal_macros::dec;
+//!
+//! let paths = PathSimulator::new(Positive::HUNDRED, pos_or_panic!(0.2), dec!(0.05), Positive::ZERO)
+//!     .with_paths(4)
+//!     .with_seed(7)
+//!     .simulate(&TimeGrid::uniform(Positive::ONE, 12).unwrap())
+//!     .unwrap();
+//! let batch = paths.to_record_batch().unwrap();
+//! assert_eq!(batch.num_rows(), 4 * 13);
+//!
+//! let mut parquet = Vec::new();
+//! paths.write_parquet(&mut parquet).unwrap();
+//! assert!(parquet.starts_with(b"PAR1"));
+//! ```
+
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::error::OptionStratResult;
+use crate::simulation::PathMatrix;
+use arrow_array::{ArrayRef, Float64Array, StringArray, UInt32Array, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use num_traits::ToPrimitive;
+use parquet::arrow::ArrowWriter;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::sync::Arc;
+
+pub use arrow_array::RecordBatch;
+
+/// Conversion into an Arrow [`RecordBatch`], with Parquet output.
+pub trait ToRecordBatch {
+    /// Converts the value into a record batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the columns do not match the schema.
+    fn to_record_batch(&self) -> OptionStratResult<RecordBatch>;
+
+    /// Writes the record batch as a Parquet file to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the conversion or the Parquet encoding fails.
+    fn write_parquet<W: Write + Send>(&self, writer: W) -> OptionStratResult<()> {
+        let batch = self.to_record_batch()?;
+        let mut parquet = ArrowWriter::try_new(writer, batch.schema(), None)?;
+        parquet.write(&batch)?;
+        parquet.close()?;
+        Ok(())
+    }
+
+    /// Saves the record batch as a Parquet file at `file_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    fn save_parquet(&self, file_path: &str) -> OptionStratResult<()> {
+        self.write_parquet(File::create(file_path)?)
+    }
+}
+
+fn float(name: &str, nullable: bool) -> Field {
+    Field::new(name, DataType::Float64, nullable)
+}
+
+fn decimal(value: Option<Decimal>) -> Option<f64> {
+    value.and_then(|value| value.to_f64())
+}
+
+impl ToRecordBatch for OptionChain {
+    fn to_record_batch(&self) -> OptionStratResult<RecordBatch> {
+        let expiration = self.get_expiration_date();
+        let metadata = HashMap::from([
+            ("symbol".to_string(), self.symbol.clone()),
+            ("expiration".to_string(), expiration.clone()),
+        ]);
+        let schema = Schema::new(vec![
+            Field::new("symbol", DataType::Utf8, false),
+            Field::new("expiration", DataType::Utf8, false),
+            float("underlying_price", false),
+            float("strike", false),
+            float("call_bid", true),
+            float("call_ask", true),
+            float("call_mid", true),
+            float("put_bid", true),
+            float("put_ask", true),
+            float("put_mid", true),
+            float("implied_volatility", false),
+            float("delta_call", true),
+            float("delta_put", true),
+            float("gamma", true),
+            float("volume", true),
+            Field::new("open_interest", DataType::UInt64, true),
+            float("risk_free_rate", true),
+            float("dividend_yield", true),
+        ])
+        .with_metadata(metadata);
+
+        let rows = self.options.len();
+        let quotes = |value: fn(&OptionData) -> Option<f64>| -> ArrayRef {
+            Arc::new(self.options.iter().map(value).collect::<Float64Array>())
+        };
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(vec![self.symbol.as_str(); rows])),
+            Arc::new(StringArray::from(vec![expiration.as_str(); rows])),
+            Arc::new(Float64Array::from(vec![
+                self.underlying_price.to_f64();
+                rows
+            ])),
+            quotes(|row| Some(row.strike_price.to_f64())),
+            quotes(|row| row.call_bid.map(|p| p.to_f64())),
+            quotes(|row| row.call_ask.map(|p| p.to_f64())),
+            quotes(|row| row.call_middle.map(|p| p.to_f64())),
+            quotes(|row| row.put_bid.map(|p| p.to_f64())),
+            quotes(|row| row.put_ask.map(|p| p.to_f64())),
+            quotes(|row| row.put_middle.map(|p| p.to_f64())),
+            quotes(|row| Some(row.implied_volatility.to_f64())),
+            quotes(|row| decimal(row.delta_call)),
+            quotes(|row| decimal(row.delta_put)),
+            quotes(|row| decimal(row.gamma)),
+            quotes(|row| row.volume.map(|v| v.to_f64())),
+            Arc::new(
+                self.options
+                    .iter()
+                    .map(|row| row.open_interest)
+                    .collect::<UInt64Array>(),
+            ),
+            Arc::new(Float64Array::from(vec![decimal(self.risk_free_rate); rows])),
+            Arc::new(Float64Array::from(vec![
+                self.dividend_yield
+                    .map(|q| q.to_f64());
+                rows
+            ])),
+        ];
+        Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+    }
+}
+
+impl ToRecordBatch for PathMatrix {
+    fn to_record_batch(&self) -> OptionStratResult<RecordBatch> {
+        let metadata = HashMap::from([
+            ("num_paths".to_string(), self.num_paths().to_string()),
+            ("num_points".to_string(), self.num_points().to_string()),
+            ("antithetic".to_string(), self.is_antithetic().to_string()),
+        ]);
+        let schema = Schema::new(vec![
+            Field::new("path", DataType::UInt64, false),
+            Field::new("step", DataType::UInt32, false),
+            float("time", false),
+            float("price", false),
+        ])
+        .with_metadata(metadata);
+
+        let times = self.grid().times();
+        let rows = self.num_paths() * times.len();
+        let mut path = Vec::with_capacity(rows);
+        let mut step = Vec::with_capacity(rows);
+        let mut time = Vec::with_capacity(rows);
+        let mut price = Vec::with_capacity(rows);
+        for (index, prices) in self.iter().enumerate() {
+            for (point, (&t, &p)) in times.iter().zip(prices).enumerate() {
+                path.push(index as u64);
+                step.push(point as u32);
+                time.push(t);
+                price.push(p);
+            }
+        }
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(UInt64Array::from(path)),
+            Arc::new(UInt32Array::from(step)),
+            Arc::new(Float64Array::from(time)),
+            Arc::new(Float64Array::from(price)),
+        ];
+        Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+    }
+}
+
+#[cfg(test)]
+mod tests_arrow {
+    use super::*;
+    use crate::simulation::{PathSimulator, TimeGrid};
+    use arrow_array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use positive::{Positive, pos_or_panic};
+    use rust_decimal_macros::dec;
+
+    fn chain() -> OptionChain {
+        let mut chain = OptionChain::new(
+            "SPY",
+            pos_or_panic!(450.0),
+            "2026-12-18".to_string(),
+            Some(dec!(0.04)),
+            None,
+        );
+        chain.add_option(
+            pos_or_panic!(440.0),
+            Some(pos_or_panic!(15.0)),
+            Some(pos_or_panic!(15.5)),
+            None,
+            None,
+            pos_or_panic!(0.18),
+            Some(dec!(0.62)),
+            None,
+            None,
+            None,
+            Some(1200),
+            None,
+        );
+        chain.add_option(
+            pos_or_panic!(460.0),
+            None,
+            None,
+            Some(pos_or_panic!(14.0)),
+            Some(pos_or_panic!(14.4)),
+            pos_or_panic!(0.17),
+            None,
+            Some(dec!(-0.58)),
+            None,
+            None,
+            None,
+            None,
+        );
+        chain
+    }
+
+    fn column<'a>(batch: &'a RecordBatch, name: &str) -> &'a Float64Array {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_chain_record_batch() {
+        let batch = chain().to_record_batch().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 18);
+        assert_eq!(batch.schema().metadata()["symbol"], "SPY");
+
+        let strikes = column(&batch, "strike");
+        assert_eq!(strikes.value(0), 440.0);
+        assert_eq!(strikes.value(1), 460.0);
+        let call_bid = column(&batch, "call_bid");
+        assert_eq!(call_bid.value(0), 15.0);
+        assert!(call_bid.is_null(1));
+        assert_eq!(column(&batch, "delta_put").value(1), -0.58);
+        assert_eq!(column(&batch, "risk_free_rate").value(0), 0.04);
+        assert!(column(&batch, "dividend_yield").is_null(0));
+    }
+
+    #[test]
+    fn test_paths_parquet_roundtrip() {
+        let paths = PathSimulator::new(
+            Positive::HUNDRED,
+            pos_or_panic!(0.25),
+            dec!(0.03),
+            Positive::ZERO,
+        )
+        .with_paths(6)
+        .with_seed(11)
+        .with_antithetic(true)
+        .simulate(&TimeGrid::uniform(Positive::ONE, 4).unwrap())
+        .unwrap();
+        let batch = paths.to_record_batch().unwrap();
+        assert_eq!(batch.num_rows(), 30);
+        assert_eq!(batch.schema().metadata()["antithetic"], "true");
+        assert_eq!(column(&batch, "price").value(7), paths.path(1)[2]);
+        assert_eq!(column(&batch, "time").value(4), 1.0);
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        paths.save_parquet(file.path().to_str().unwrap()).unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file.reopen().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(Result::unwrap).collect();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].columns(), batch.columns());
+    }
+}
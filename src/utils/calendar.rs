@@ -0,0 +1,742 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Expiration Calendar
+//!
+//! Generates the standard listed expirations of an underlying over a date
+//! range with [`expirations`]:
+//!
+//! * **Monthly** - the third Friday of every month.
+//! * **Weekly** - every Friday other than the third of the month.
+//! * **End of month** - the last business day of every month.
+//! * **Quarterly** - the last business day of March, June, September and
+//!   December.
+//!
+//! Expirations falling on an exchange holiday move to the previous business
+//! day, as when a monthly expiration is brought forward to the Thursday before
+//! Good Friday. Holidays are supplied by any [`HolidayCalendar`], such as a
+//! [`Holidays`] set of dates or a [`MarketCalendar`] with the holiday rules of
+//! NYSE, CME or Eurex.
+//!
+//! Calendars also count the trading days left until an expiration, so that
+//! pricers can annualize volatility over 252 trading days a year (ACT/252)
+//! instead of 365 calendar days. Trading time is measured to the second within
+//! the [`TradingSession`] of the exchange, so a same-day (0DTE) option decays
+//! continuously while the market is open and not at all overnight, and an
+//! expiration set after the close is cut off at the close.
+//!
+//! Sessions are set in the local time of the exchange and converted to UTC
+//! with its [`ExchangeTimeZone`], so the expiration cutoff of a date is the
+//! real settlement moment: 16:00 New York time for PM-settled options, or the
+//! opening for AM-settled index options, through [`SettlementTiming`] and the
+//! [`ExpirationCutoff`] extension of [`ExpirationDate`].
+
+use crate::ExpirationDate;
+use crate::constants::TRADING_DAYS;
+use crate::utils::timezone::ExchangeTimeZone;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use expiration_date::error::ExpirationDateError;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use utoipa::ToSchema;
+
+/// Calendar of the days on which an exchange is closed.
+pub trait HolidayCalendar {
+    /// Returns `true` if the exchange is closed on `date` for a holiday.
+    fn is_holiday(&self, date: NaiveDate) -> bool;
+
+    /// Returns `true` if `date` is neither a weekend nor a holiday.
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.is_holiday(date)
+    }
+
+    /// Latest business day on or before `date`.
+    fn previous_business_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut day = date;
+        while !self.is_business_day(day) {
+            day -= Duration::days(1);
+        }
+        day
+    }
+
+    /// Number of business days after `from`, up to and including `to`.
+    fn business_days_between(&self, from: NaiveDate, to: NaiveDate) -> u32 {
+        from.iter_days()
+            .skip(1)
+            .take_while(|day| *day <= to)
+            .filter(|day| self.is_business_day(*day))
+            .count() as u32
+    }
+
+    /// Regular trading session of the exchange.
+    fn session(&self) -> TradingSession {
+        TradingSession::default()
+    }
+
+    /// Open of the trading session on `date`.
+    fn session_open(&self, date: NaiveDate) -> DateTime<Utc> {
+        let session = self.session();
+        session.timezone.to_utc(date, session.open)
+    }
+
+    /// Close of the trading session on `date`.
+    fn session_close(&self, date: NaiveDate) -> DateTime<Utc> {
+        let session = self.session();
+        session.timezone.to_utc(date, session.close)
+    }
+
+    /// Moment at which the options expiring on `date` settle.
+    fn expiration_cutoff(&self, date: NaiveDate, timing: SettlementTiming) -> DateTime<Utc> {
+        match timing {
+            SettlementTiming::Pm => self.session_close(date),
+            SettlementTiming::Am => self.session_open(date),
+        }
+    }
+
+    /// Expiration at the close of the trading session on `date`.
+    fn expiration_at_close(&self, date: NaiveDate) -> ExpirationDate {
+        ExpirationDate::DateTime(self.expiration_cutoff(date, SettlementTiming::Pm))
+    }
+
+    /// Trading time between `from` and `to`, in trading days: the time the
+    /// market is open between both instants over the length of a session.
+    fn trading_time_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Decimal {
+        let session = self.session();
+        let length = (session.close - session.open).num_seconds();
+        if to <= from || length <= 0 {
+            return Decimal::ZERO;
+        }
+        let open_seconds: i64 = from
+            .date_naive()
+            .iter_days()
+            .take_while(|day| *day <= to.date_naive())
+            .filter(|day| self.is_business_day(*day))
+            .map(|day| {
+                let open = self.session_open(day).max(from);
+                let close = self.session_close(day).min(to);
+                (close - open).num_seconds().max(0)
+            })
+            .sum();
+        Decimal::from(open_seconds) / Decimal::from(length)
+    }
+
+    /// Trading days left until `expiration`, counting only the time the
+    /// market is open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ExpirationDateError` if the days to expiration cannot be
+    /// resolved.
+    fn trading_days_to_expiry(
+        &self,
+        expiration: &ExpirationDate,
+    ) -> Result<Positive, ExpirationDateError> {
+        let days = expiration.get_days()?;
+        let now = Utc::now();
+        let end = now + Duration::seconds((days.to_f64() * 86_400.0) as i64);
+        Ok(Positive::new_decimal(self.trading_time_between(now, end)).unwrap_or(Positive::ZERO))
+    }
+
+    /// Time to `expiration` in years of 252 trading days (ACT/252).
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ExpirationDateError` if the days to expiration cannot be
+    /// resolved.
+    fn trading_years_to_expiry(
+        &self,
+        expiration: &ExpirationDate,
+    ) -> Result<Positive, ExpirationDateError> {
+        Ok(self.trading_days_to_expiry(expiration)? / TRADING_DAYS)
+    }
+}
+
+/// Opening and closing times of a trading session, in the local time of the
+/// exchange.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct TradingSession {
+    /// Time the market opens.
+    pub open: NaiveTime,
+    /// Time the market closes, after which the options of the day expire.
+    pub close: NaiveTime,
+    /// Time zone of the exchange.
+    pub timezone: ExchangeTimeZone,
+}
+
+impl TradingSession {
+    /// Creates a session from its local opening and closing times.
+    pub fn new(open: NaiveTime, close: NaiveTime, timezone: ExchangeTimeZone) -> Self {
+        Self {
+            open,
+            close,
+            timezone,
+        }
+    }
+
+    fn from_hm(open: (u32, u32), close: (u32, u32), timezone: ExchangeTimeZone) -> Self {
+        Self {
+            open: NaiveTime::from_hms_opt(open.0, open.1, 0).unwrap_or_default(),
+            close: NaiveTime::from_hms_opt(close.0, close.1, 0).unwrap_or_default(),
+            timezone,
+        }
+    }
+}
+
+impl Default for TradingSession {
+    /// US equity session, 9:30 to 16:00 New York time.
+    fn default() -> Self {
+        Self::from_hm((9, 30), (16, 0), ExchangeTimeZone::NewYork)
+    }
+}
+
+/// When options settle on their expiration date.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum SettlementTiming {
+    /// At the close of the expiration date, as with equity options.
+    #[default]
+    Pm,
+    /// At the open of the expiration date, as with monthly index options,
+    /// whose last trading day is the business day before.
+    Am,
+}
+
+/// Pins an [`ExpirationDate`] to the settlement moment of its date.
+pub trait ExpirationCutoff {
+    /// Expiration at the cutoff of `calendar` on the date of this expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ExpirationDateError` if the date of the expiration cannot
+    /// be resolved.
+    fn at_cutoff(
+        &self,
+        calendar: &dyn HolidayCalendar,
+        timing: SettlementTiming,
+    ) -> Result<ExpirationDate, ExpirationDateError>;
+}
+
+impl ExpirationCutoff for ExpirationDate {
+    fn at_cutoff(
+        &self,
+        calendar: &dyn HolidayCalendar,
+        timing: SettlementTiming,
+    ) -> Result<ExpirationDate, ExpirationDateError> {
+        let date = self.get_date()?.date_naive();
+        Ok(ExpirationDate::DateTime(
+            calendar.expiration_cutoff(date, timing),
+        ))
+    }
+}
+
+/// Fixed set of holiday dates.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Holidays {
+    /// Dates on which the exchange is closed.
+    pub dates: BTreeSet<NaiveDate>,
+    /// Regular trading session.
+    #[serde(default)]
+    pub session: TradingSession,
+}
+
+impl Holidays {
+    /// Creates a calendar from a list of holiday dates.
+    pub fn new<I: IntoIterator<Item = NaiveDate>>(dates: I) -> Self {
+        Self {
+            dates: dates.into_iter().collect(),
+            session: TradingSession::default(),
+        }
+    }
+
+    /// Sets the regular trading session.
+    pub fn with_session(mut self, session: TradingSession) -> Self {
+        self.session = session;
+        self
+    }
+}
+
+impl HolidayCalendar for Holidays {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.dates.contains(&date)
+    }
+
+    fn session(&self) -> TradingSession {
+        self.session
+    }
+}
+
+/// Holiday calendar of an exchange.
+///
+/// The presets follow the published full-day closures of each exchange, with
+/// fixed-date US holidays falling on a weekend observed on the nearest
+/// weekday. Partial trading days are treated as business days.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum MarketCalendar {
+    /// New York Stock Exchange.
+    #[default]
+    Nyse,
+    /// CME Group equity and interest rate products, which do not settle on
+    /// the NYSE holidays.
+    Cme,
+    /// Eurex, closed on the TARGET2 holidays and on Christmas and New Year's
+    /// Eve.
+    Eurex,
+    /// Custom set of holidays.
+    Custom(Holidays),
+}
+
+impl HolidayCalendar for MarketCalendar {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        match self {
+            MarketCalendar::Nyse | MarketCalendar::Cme => is_us_holiday(date),
+            MarketCalendar::Eurex => is_eurex_holiday(date),
+            MarketCalendar::Custom(holidays) => holidays.is_holiday(date),
+        }
+    }
+
+    fn session(&self) -> TradingSession {
+        match self {
+            MarketCalendar::Nyse => TradingSession::default(),
+            MarketCalendar::Cme => {
+                TradingSession::from_hm((8, 30), (15, 0), ExchangeTimeZone::Chicago)
+            }
+            MarketCalendar::Eurex => {
+                TradingSession::from_hm((9, 0), (17, 30), ExchangeTimeZone::Frankfurt)
+            }
+            MarketCalendar::Custom(holidays) => holidays.session,
+        }
+    }
+}
+
+/// NYSE full-day closures.
+fn is_us_holiday(date: NaiveDate) -> bool {
+    let year = date.year();
+    let fixed = |month: u32, day: u32| NaiveDate::from_ymd_opt(year, month, day).map(observed);
+    let nth = |month: u32, weekday: Weekday, n: u8| {
+        NaiveDate::from_weekday_of_month_opt(year, month, weekday, n)
+    };
+    let last_monday_of_may = NaiveDate::from_ymd_opt(year, 5, 31)
+        .map(|day| day - Duration::days(day.weekday().num_days_from_monday() as i64));
+    // New Year's Day falling on a Saturday is not observed on the Friday before.
+    let new_year = NaiveDate::from_ymd_opt(year, 1, 1)
+        .filter(|day| day.weekday() != Weekday::Sat)
+        .map(observed);
+    let next_new_year = NaiveDate::from_ymd_opt(year + 1, 1, 1)
+        .filter(|day| day.weekday() == Weekday::Sun)
+        .map(observed);
+    let juneteenth = if year >= 2022 { fixed(6, 19) } else { None };
+
+    [
+        new_year,
+        next_new_year,
+        nth(1, Weekday::Mon, 3),
+        nth(2, Weekday::Mon, 3),
+        easter_sunday(year).map(|easter| easter - Duration::days(2)),
+        last_monday_of_may,
+        juneteenth,
+        fixed(7, 4),
+        nth(9, Weekday::Mon, 1),
+        nth(11, Weekday::Thu, 4),
+        fixed(12, 25),
+    ]
+    .contains(&Some(date))
+}
+
+/// Eurex full-day closures.
+fn is_eurex_holiday(date: NaiveDate) -> bool {
+    let easter = easter_sunday(date.year());
+    let good_friday = easter.map(|easter| easter - Duration::days(2));
+    let easter_monday = easter.map(|easter| easter + Duration::days(1));
+    matches!(
+        (date.month(), date.day()),
+        (1, 1) | (5, 1) | (12, 24) | (12, 25) | (12, 26) | (12, 31)
+    ) || Some(date) == good_friday
+        || Some(date) == easter_monday
+}
+
+/// Moves a holiday on a Saturday to the Friday before and one on a Sunday to
+/// the Monday after.
+fn observed(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Sat => date - Duration::days(1),
+        Weekday::Sun => date + Duration::days(1),
+        _ => date,
+    }
+}
+
+/// Easter Sunday of the Gregorian calendar (anonymous Gregorian algorithm).
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Expiration cycle of a listed option.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema,
+)]
+pub enum ExpiryCycle {
+    /// Third Friday of the month.
+    Monthly,
+    /// Last business day of the quarter.
+    Quarterly,
+    /// Last business day of the month.
+    EndOfMonth,
+    /// Fridays other than the monthly expiration.
+    Weekly,
+}
+
+/// A listed expiration date and the cycle it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ListedExpiration {
+    /// Trading day on which the options expire.
+    pub date: NaiveDate,
+    /// Cycle of the expiration.
+    pub cycle: ExpiryCycle,
+}
+
+impl ListedExpiration {
+    /// Expiration date at 18:30 UTC, the time used for dates without a time.
+    pub fn expiration_date(&self) -> ExpirationDate {
+        let time = self.date.and_hms_opt(18, 30, 0).unwrap_or_default();
+        ExpirationDate::DateTime(Utc.from_utc_datetime(&time))
+    }
+
+    /// Expiration date at the settlement moment of `calendar`.
+    pub fn expiration_at(
+        &self,
+        calendar: &dyn HolidayCalendar,
+        timing: SettlementTiming,
+    ) -> ExpirationDate {
+        ExpirationDate::DateTime(calendar.expiration_cutoff(self.date, timing))
+    }
+}
+
+/// Generates the expirations of the given cycles between `start` and `end`,
+/// both included, sorted by date.
+///
+/// Expirations on a holiday move to the previous business day. When several
+/// cycles expire on the same day, the date is listed once under the first
+/// cycle in [`ExpiryCycle`] order, so a third Friday that is also the end of a
+/// quarter is reported as monthly.
+pub fn expirations(
+    start: NaiveDate,
+    end: NaiveDate,
+    cycles: &[ExpiryCycle],
+    calendar: &dyn HolidayCalendar,
+) -> Vec<ListedExpiration> {
+    let mut listed: Vec<ListedExpiration> = Vec::new();
+    let mut month = NaiveDate::from_ymd_opt(start.year(), start.month(), 1);
+    while let Some(first) = month.filter(|first| *first <= end) {
+        for cycle in cycles {
+            for date in cycle_dates(first, *cycle, calendar) {
+                if date >= start && date <= end {
+                    listed.push(ListedExpiration {
+                        date,
+                        cycle: *cycle,
+                    });
+                }
+            }
+        }
+        month = first.checked_add_months(chrono::Months::new(1));
+    }
+    listed.sort_by_key(|expiration| (expiration.date, expiration.cycle));
+    listed.dedup_by_key(|expiration| expiration.date);
+    listed
+}
+
+/// First expiration of `cycle` at least `min_days` calendar days after
+/// `from`, looking up to five years ahead.
+///
+/// This is the natural roll target of a position, such as the next monthly
+/// expiration with 30 days or more left.
+pub fn next_expiration(
+    from: NaiveDate,
+    cycle: ExpiryCycle,
+    min_days: u32,
+    calendar: &dyn HolidayCalendar,
+) -> Option<ListedExpiration> {
+    let earliest = from + Duration::days(i64::from(min_days));
+    let latest = earliest.checked_add_months(chrono::Months::new(60))?;
+    expirations(earliest, latest, &[cycle], calendar)
+        .into_iter()
+        .find(|expiration| expiration.cycle == cycle)
+}
+
+/// Expirations of `cycle` in the month starting on `first`.
+fn cycle_dates(
+    first: NaiveDate,
+    cycle: ExpiryCycle,
+    calendar: &dyn HolidayCalendar,
+) -> Vec<NaiveDate> {
+    let fridays: Vec<NaiveDate> = first
+        .iter_days()
+        .take_while(|day| day.month() == first.month())
+        .filter(|day| day.weekday() == Weekday::Fri)
+        .collect();
+    let third_friday = fridays[2];
+    let last_day = first
+        .checked_add_months(chrono::Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(first);
+
+    match cycle {
+        ExpiryCycle::Monthly => vec![calendar.previous_business_day(third_friday)],
+        ExpiryCycle::Weekly => fridays
+            .into_iter()
+            .filter(|friday| *friday != third_friday)
+            .map(|friday| calendar.previous_business_day(friday))
+            .collect(),
+        ExpiryCycle::EndOfMonth => vec![calendar.previous_business_day(last_day)],
+        ExpiryCycle::Quarterly if first.month().is_multiple_of(3) => {
+            vec![calendar.previous_business_day(last_day)]
+        }
+        ExpiryCycle::Quarterly => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests_calendar {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_monthly_third_fridays() {
+        let listed = expirations(
+            date(2025, 1, 1),
+            date(2025, 3, 31),
+            &[ExpiryCycle::Monthly],
+            &Holidays::default(),
+        );
+        let dates: Vec<NaiveDate> = listed.iter().map(|e| e.date).collect();
+        assert_eq!(
+            dates,
+            vec![date(2025, 1, 17), date(2025, 2, 21), date(2025, 3, 21)]
+        );
+    }
+
+    #[test]
+    fn test_holiday_moves_to_previous_business_day() {
+        // Good Friday 2025 falls on the third Friday of April.
+        let holidays = Holidays::new([date(2025, 4, 18)]);
+        let listed = expirations(
+            date(2025, 4, 1),
+            date(2025, 4, 30),
+            &[ExpiryCycle::Monthly, ExpiryCycle::Weekly],
+            &holidays,
+        );
+        let monthly: Vec<&ListedExpiration> = listed
+            .iter()
+            .filter(|e| e.cycle == ExpiryCycle::Monthly)
+            .collect();
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly[0].date, date(2025, 4, 17));
+        // Weeklies on the 4th, 11th and 25th.
+        assert_eq!(listed.len(), 4);
+        assert!(listed.windows(2).all(|pair| pair[0].date < pair[1].date));
+    }
+
+    #[test]
+    fn test_end_of_month_and_quarterly() {
+        let listed = expirations(
+            date(2025, 5, 1),
+            date(2025, 6, 30),
+            &[ExpiryCycle::Quarterly, ExpiryCycle::EndOfMonth],
+            &Holidays::default(),
+        );
+        // May 31st 2025 is a Saturday.
+        assert_eq!(
+            listed,
+            vec![
+                ListedExpiration {
+                    date: date(2025, 5, 30),
+                    cycle: ExpiryCycle::EndOfMonth,
+                },
+                ListedExpiration {
+                    date: date(2025, 6, 30),
+                    cycle: ExpiryCycle::Quarterly,
+                },
+            ]
+        );
+        assert!(listed[1].expiration_date().get_date_string().is_ok());
+    }
+
+    #[test]
+    fn test_market_calendar_presets() {
+        let nyse = MarketCalendar::Nyse;
+        for holiday in [
+            date(2025, 1, 1),
+            date(2025, 1, 20),
+            date(2025, 2, 17),
+            date(2025, 4, 18),
+            date(2025, 5, 26),
+            date(2025, 6, 19),
+            date(2025, 7, 4),
+            date(2025, 9, 1),
+            date(2025, 11, 27),
+            date(2025, 12, 25),
+            // Independence Day 2026 falls on a Saturday.
+            date(2026, 7, 3),
+        ] {
+            assert!(nyse.is_holiday(holiday), "{holiday}");
+        }
+        // New Year's Day 2022 fell on a Saturday and was not observed.
+        assert!(nyse.is_business_day(date(2021, 12, 31)));
+        assert!(nyse.is_business_day(date(2025, 4, 21)));
+
+        let eurex = MarketCalendar::Eurex;
+        assert!(eurex.is_holiday(date(2025, 4, 21)));
+        assert!(eurex.is_holiday(date(2025, 12, 24)));
+        assert!(eurex.is_business_day(date(2025, 7, 4)));
+
+        let custom = MarketCalendar::Custom(Holidays::new([date(2025, 3, 3)]));
+        assert!(!custom.is_business_day(date(2025, 3, 3)));
+        assert_eq!(
+            nyse.previous_business_day(date(2025, 4, 20)),
+            date(2025, 4, 17)
+        );
+    }
+
+    #[test]
+    fn test_business_days_to_expiry() {
+        let nyse = MarketCalendar::Nyse;
+        // Christmas week of 2025: the 25th is a holiday.
+        assert_eq!(
+            nyse.business_days_between(date(2025, 12, 19), date(2025, 12, 31)),
+            7
+        );
+
+        let expiration = ExpirationDate::Days(Positive::new(28.0).unwrap());
+        let trading_days = nyse.trading_days_to_expiry(&expiration).unwrap();
+        // Four weeks hold eight weekend days and at most two holidays.
+        assert!(trading_days >= Positive::new(17.0).unwrap());
+        assert!(trading_days <= Positive::new(20.0).unwrap());
+        assert_eq!(
+            nyse.trading_years_to_expiry(&expiration).unwrap(),
+            trading_days / TRADING_DAYS
+        );
+        assert_eq!(
+            nyse.trading_days_to_expiry(&ExpirationDate::Days(Positive::ZERO))
+                .unwrap(),
+            Positive::ZERO
+        );
+    }
+
+    #[test]
+    fn test_intraday_trading_time() {
+        let nyse = MarketCalendar::Nyse;
+        let at = |day: u32, hour: u32, minute: u32| {
+            Utc.from_utc_datetime(&date(2025, 3, day).and_hms_opt(hour, minute, 0).unwrap())
+        };
+        // Wednesday 5th of March: the 0DTE option decays through the session.
+        let close = nyse.session_close(date(2025, 3, 5));
+        assert_eq!(close, at(5, 21, 0));
+        assert_eq!(
+            nyse.trading_time_between(at(5, 14, 30), close),
+            Decimal::ONE
+        );
+        assert_eq!(
+            nyse.trading_time_between(at(5, 17, 45), close),
+            Decimal::new(5, 1)
+        );
+        assert!(
+            nyse.trading_time_between(at(5, 20, 0), close)
+                < nyse.trading_time_between(at(5, 19, 59), close)
+        );
+        // An expiration set after the close is cut off at the close.
+        assert_eq!(
+            nyse.trading_time_between(at(5, 17, 45), at(5, 23, 0)),
+            Decimal::new(5, 1)
+        );
+        // No decay overnight or over the weekend.
+        assert_eq!(
+            nyse.trading_time_between(at(5, 21, 0), at(6, 14, 30)),
+            Decimal::ZERO
+        );
+        // Summer time starts on Sunday 9th, so the Monday session opens at
+        // 13:30 UTC: 3h15 on Friday and 4h15 on Monday.
+        assert_eq!(
+            nyse.trading_time_between(at(7, 17, 45), at(10, 17, 45)),
+            Decimal::from(450) / Decimal::from(390)
+        );
+
+        let eurex = MarketCalendar::Eurex;
+        assert_eq!(eurex.session_close(date(2025, 3, 5)), at(5, 16, 30));
+        assert_eq!(
+            MarketCalendar::Cme.session_close(date(2025, 3, 5)),
+            at(5, 21, 0)
+        );
+        assert!(matches!(
+            nyse.expiration_at_close(date(2025, 3, 5)),
+            ExpirationDate::DateTime(datetime) if datetime == close
+        ));
+    }
+
+    #[test]
+    fn test_expiration_cutoffs() {
+        let nyse = MarketCalendar::Nyse;
+        let utc = |month: u32, day: u32, hour: u32, minute: u32| {
+            Utc.from_utc_datetime(&date(2025, month, day).and_hms_opt(hour, minute, 0).unwrap())
+        };
+        // 16:00 New York is 20:00 UTC in summer and 21:00 UTC in winter.
+        assert_eq!(
+            nyse.expiration_cutoff(date(2025, 7, 18), SettlementTiming::Pm),
+            utc(7, 18, 20, 0)
+        );
+        assert_eq!(
+            nyse.expiration_cutoff(date(2025, 1, 17), SettlementTiming::Pm),
+            utc(1, 17, 21, 0)
+        );
+        assert_eq!(
+            nyse.expiration_cutoff(date(2025, 1, 17), SettlementTiming::Am),
+            utc(1, 17, 14, 30)
+        );
+
+        let listed = ListedExpiration {
+            date: date(2025, 7, 18),
+            cycle: ExpiryCycle::Monthly,
+        };
+        let am = listed.expiration_at(&nyse, SettlementTiming::Am);
+        assert_eq!(am.get_date().unwrap(), utc(7, 18, 13, 30));
+        let pinned = listed
+            .expiration_date()
+            .at_cutoff(&nyse, SettlementTiming::Pm)
+            .unwrap();
+        assert_eq!(pinned.get_date().unwrap(), utc(7, 18, 20, 0));
+    }
+
+    #[test]
+    fn test_next_expiration() {
+        let nyse = MarketCalendar::Nyse;
+        // 30 days after March 1st 2025 is March 31st: the next monthly is April,
+        // brought forward to Thursday 17th by Good Friday.
+        let next = next_expiration(date(2025, 3, 1), ExpiryCycle::Monthly, 30, &nyse).unwrap();
+        assert_eq!(next.date, date(2025, 4, 17));
+        assert_eq!(next.cycle, ExpiryCycle::Monthly);
+
+        let quarterly =
+            next_expiration(date(2025, 3, 1), ExpiryCycle::Quarterly, 0, &nyse).unwrap();
+        assert_eq!(quarterly.date, date(2025, 3, 31));
+        let weekly = next_expiration(date(2025, 3, 1), ExpiryCycle::Weekly, 7, &nyse).unwrap();
+        assert_eq!(weekly.date, date(2025, 3, 14));
+    }
+}
@@ -177,21 +177,41 @@ pub mod others;
 /// reading and writing OHLCV data in CSV format, as well as handling errors related to CSV
 /// parsing.
 mod csv;
+
+/// Apache Arrow and Parquet export of option chains and simulated paths.
+#[cfg(feature = "arrow")]
+pub mod arrow;
 /// This module contains the file reader and writer for OHLCV data.  It provides functionality for
 /// reading and writing OHLCV data in various file formats, including CSV and JSON.
 pub mod file;
 /// Module for time-related utilities.
 pub mod time;
 
+/// Listed expiration calendars and exchange holidays.
+pub mod calendar;
+
+/// Exchange time zones and their daylight saving rules.
+pub mod timezone;
+
+/// Sequential or rayon-parallel execution of batch computations, selected by
+/// the `parallel` feature.
+pub mod parallel;
+
 /// This module contains traits and type definitions used throughout the library.  It provides
 /// functionality for defining and implementing common traits, as well as type aliases for
 /// convenience.
 mod traits;
 
+pub use calendar::{
+    ExpirationCutoff, ExpiryCycle, HolidayCalendar, Holidays, ListedExpiration, MarketCalendar,
+    SettlementTiming, TradingSession, expirations, next_expiration,
+};
 #[cfg(feature = "async")]
 pub use csv::read_ohlcv_from_zip_async;
 pub use csv::{OhlcvCandle, read_ohlcv_from_zip};
 pub use logger::{setup_logger, setup_logger_with_level};
 pub use others::{approx_equal, get_random_element, process_n_times_iter, random_decimal};
+pub use parallel::{is_parallel, map_ordered};
 pub use time::TimeFrame;
+pub use timezone::ExchangeTimeZone;
 pub use traits::Len;
@@ -6,11 +6,11 @@
 
 use crate::constants::TOLERANCE;
 use crate::error::{DecimalError, Error};
+use crate::utils::parallel::map_ordered;
 use itertools::Itertools;
 use num_traits::{FromPrimitive, ToPrimitive};
 use positive::Positive;
 use rand::{Rng, RngExt, rng};
-use rayon::prelude::*;
 use rust_decimal::Decimal;
 use std::collections::BTreeSet;
 
@@ -159,13 +159,13 @@ where
     let combinations: Vec<_> = positions.iter().combinations_with_replacement(n).collect();
     let process_combination = std::sync::Mutex::new(process_combination);
 
-    Ok(combinations
-        .par_iter()
-        .flat_map(|combination| {
-            let mut closure = process_combination.lock().unwrap();
-            closure(combination)
-        })
-        .collect())
+    Ok(map_ordered(combinations, |combination| {
+        let mut closure = process_combination.lock().unwrap();
+        closure(&combination)
+    })
+    .into_iter()
+    .flatten()
+    .collect())
 }
 
 /// # Calculate Logarithmic Returns
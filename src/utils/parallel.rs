@@ -0,0 +1,63 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Parallel Execution
+//!
+//! Switch between sequential and rayon-parallel execution of the heavy batch
+//! computations of the library: curve and surface generation, Monte Carlo
+//! path generation, implied volatility solving and strategy optimization
+//! sweeps.
+//!
+//! With the `parallel` feature, enabled by default, [`map_ordered`] runs on
+//! the rayon thread pool; without it, it is a plain sequential map. Results are always
+//! collected in input order and every reduction happens afterwards on a
+//! single thread, so parallel runs return exactly the same values as
+//! single-threaded ones.
+//!
+//! The thread-safety bounds of [`map_ordered`] are the same with or without
+//! the feature, so enabling it never breaks code that compiles without it.
+
+/// Returns `true` when batch computations run on the rayon thread pool.
+pub const fn is_parallel() -> bool {
+    cfg!(feature = "parallel")
+}
+
+/// Applies `f` to every item and returns the results in input order, in
+/// parallel with the `parallel` feature.
+pub fn map_ordered<T, U, F>(items: Vec<T>, f: F) -> Vec<U>
+where
+    T: Send,
+    U: Send,
+    F: Fn(T) -> U + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items.into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        items.into_iter().map(f).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests_parallel {
+    use super::*;
+
+    #[test]
+    fn test_map_ordered_keeps_input_order() {
+        let items: Vec<u64> = (0..1_000).collect();
+        let squares = map_ordered(items, |x| x * x);
+        assert_eq!(squares.len(), 1_000);
+        assert!(
+            squares
+                .iter()
+                .enumerate()
+                .all(|(i, x)| *x == (i * i) as u64)
+        );
+    }
+}
@@ -0,0 +1,150 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Exchange Time Zones
+//!
+//! Converts the local times of the main options exchanges to UTC, following
+//! their daylight saving rules:
+//!
+//! * **US** (New York, Chicago) - from the second Sunday of March to the first
+//!   Sunday of November.
+//! * **Europe** (London, Frankfurt) - from the last Sunday of March to the last
+//!   Sunday of October.
+//!
+//! Clocks change at night, so the offset is resolved per date and is exact for
+//! any time during trading hours.
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc, Weekday,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Time zone of an exchange.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum ExchangeTimeZone {
+    /// America/New_York, UTC-5 or UTC-4 in summer.
+    #[default]
+    NewYork,
+    /// America/Chicago, UTC-6 or UTC-5 in summer.
+    Chicago,
+    /// Europe/London, UTC or UTC+1 in summer.
+    London,
+    /// Europe/Berlin, UTC+1 or UTC+2 in summer.
+    Frankfurt,
+    /// Coordinated Universal Time, without daylight saving.
+    Utc,
+}
+
+impl ExchangeTimeZone {
+    /// Offset from UTC in force on `date`.
+    pub fn utc_offset(&self, date: NaiveDate) -> FixedOffset {
+        let (standard, summer) = match self {
+            ExchangeTimeZone::NewYork => (-5, us_summer_time(date)),
+            ExchangeTimeZone::Chicago => (-6, us_summer_time(date)),
+            ExchangeTimeZone::London => (0, eu_summer_time(date)),
+            ExchangeTimeZone::Frankfurt => (1, eu_summer_time(date)),
+            ExchangeTimeZone::Utc => (0, false),
+        };
+        let hours = if summer { standard + 1 } else { standard };
+        FixedOffset::east_opt(hours * 3600).unwrap_or(FixedOffset::east_opt(0).unwrap())
+    }
+
+    /// Converts the local `time` of `date` in this time zone to UTC.
+    pub fn to_utc(&self, date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+        let offset = Duration::seconds(self.utc_offset(date).local_minus_utc() as i64);
+        Utc.from_utc_datetime(&(date.and_time(time) - offset))
+    }
+}
+
+/// US daylight saving time, from the second Sunday of March to the first
+/// Sunday of November.
+fn us_summer_time(date: NaiveDate) -> bool {
+    let year = date.year();
+    match (
+        NaiveDate::from_weekday_of_month_opt(year, 3, Weekday::Sun, 2),
+        NaiveDate::from_weekday_of_month_opt(year, 11, Weekday::Sun, 1),
+    ) {
+        (Some(start), Some(end)) => date >= start && date < end,
+        _ => false,
+    }
+}
+
+/// European summer time, from the last Sunday of March to the last Sunday of
+/// October.
+fn eu_summer_time(date: NaiveDate) -> bool {
+    let year = date.year();
+    match (last_sunday(year, 3), last_sunday(year, 10)) {
+        (Some(start), Some(end)) => date >= start && date < end,
+        _ => false,
+    }
+}
+
+fn last_sunday(year: i32, month: u32) -> Option<NaiveDate> {
+    let last = NaiveDate::from_ymd_opt(year, month + 1, 1)?.pred_opt()?;
+    Some(last - Duration::days(last.weekday().num_days_from_sunday() as i64))
+}
+
+#[cfg(test)]
+mod tests_timezone {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_us_daylight_saving() {
+        let new_york = ExchangeTimeZone::NewYork;
+        // 2025 summer time runs from March 9th to November 2nd.
+        assert_eq!(
+            new_york.to_utc(date(2025, 3, 7), time(16, 0)).time(),
+            time(21, 0)
+        );
+        assert_eq!(
+            new_york.to_utc(date(2025, 3, 10), time(16, 0)).time(),
+            time(20, 0)
+        );
+        assert_eq!(
+            new_york.to_utc(date(2025, 11, 3), time(16, 0)).time(),
+            time(21, 0)
+        );
+        assert_eq!(
+            ExchangeTimeZone::Chicago
+                .to_utc(date(2025, 7, 1), time(15, 0))
+                .time(),
+            time(20, 0)
+        );
+    }
+
+    #[test]
+    fn test_european_summer_time() {
+        let frankfurt = ExchangeTimeZone::Frankfurt;
+        // 2025 summer time runs from March 30th to October 26th.
+        assert_eq!(
+            frankfurt.to_utc(date(2025, 3, 28), time(17, 30)).time(),
+            time(16, 30)
+        );
+        assert_eq!(
+            frankfurt.to_utc(date(2025, 3, 31), time(17, 30)).time(),
+            time(15, 30)
+        );
+        assert_eq!(
+            ExchangeTimeZone::London
+                .to_utc(date(2025, 10, 27), time(16, 30))
+                .time(),
+            time(16, 30)
+        );
+        assert_eq!(
+            ExchangeTimeZone::Utc.utc_offset(date(2025, 7, 1)),
+            FixedOffset::east_opt(0).unwrap()
+        );
+    }
+}
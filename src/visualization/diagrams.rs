@@ -0,0 +1,544 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Static Diagrams
+//!
+//! Renders line diagrams to PNG or SVG files with `plotters`, without a
+//! browser or any external tool:
+//!
+//! * [`Diagram::payoff`] draws the P&L of a strategy at expiration, sampled
+//!   with [`PayoffCurvable`], together with its T+0 curve marked with
+//!   Black-Scholes, and marks the strikes and break-even points.
+//! * [`Diagram::greek`] draws the net delta, gamma, theta or vega of a set of
+//!   options across underlying prices.
+//! * [`Diagram::smile`] draws the implied volatility smile of a chain.
+//!
+//! The format is chosen from the extension of the file given to
+//! [`Diagram::save`].
+//!
+//! ```rust,no_run
+//! use optionstratlib::ExpirationDate;
+//! use optionstratlib::strategies::BullCallSpread;
+//! use optionstratlib::visualization::Diagram;
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal::Decimal;
+//!
+//! let spread = BullCallSpread::new(
+//!     "SPY".to_string(),
+//!     pos_or_panic!(445.0),
+//!     pos_or_panic!(440.0),
+//!     pos_or_panic!(450.0),
+//!     ExpirationDate::Days(pos_or_panic!(30.0)),
+//!     pos_or_panic!(0.2),
+//!     Decimal::ZERO,
+//!     Positive::ZERO,
+//!     Positive::ONE,
+//!     pos_or_panic!(12.5),
+//!     pos_or_panic!(7.4),
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//! );
+//! Diagram::payoff(&spread, (pos_or_panic!(420.0), pos_or_panic!(470.0)), 200)
+//!     .unwrap()
+//!     .title("SPY bull call spread")
+//!     .save("payoff.png")
+//!     .unwrap();
+//! ```
+
+use crate::ExpirationDate;
+use crate::error::{GraphError, GreeksError, StrategyError};
+use crate::greeks::Greeks;
+use crate::model::BasicAxisTypes;
+use crate::model::option::Options;
+use crate::strategies::PayoffCurvable;
+use crate::volatility::VolatilitySmile;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use std::path::Path;
+
+/// Default width of a diagram, in pixels.
+const DEFAULT_WIDTH: u32 = 1280;
+
+/// Default height of a diagram, in pixels.
+const DEFAULT_HEIGHT: u32 = 720;
+
+/// A named line of a diagram.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramSeries {
+    /// Name shown in the legend.
+    pub name: String,
+    /// Points of the line, sorted by x.
+    pub points: Vec<(f64, f64)>,
+}
+
+/// A labelled vertical line of a diagram, such as a strike.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramMarker {
+    /// Label drawn at the top of the line.
+    pub label: String,
+    /// Position of the line on the x axis.
+    pub x: f64,
+}
+
+/// A line diagram ready to be rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagram {
+    /// Title drawn above the chart.
+    pub title: String,
+    /// Label of the x axis.
+    pub x_label: String,
+    /// Label of the y axis.
+    pub y_label: String,
+    /// Lines of the diagram.
+    pub series: Vec<DiagramSeries>,
+    /// Vertical lines of the diagram.
+    pub markers: Vec<DiagramMarker>,
+    /// Whether to draw the horizontal line at zero.
+    pub zero_line: bool,
+    /// Width of the image, in pixels.
+    pub width: u32,
+    /// Height of the image, in pixels.
+    pub height: u32,
+}
+
+impl Diagram {
+    /// Creates an empty diagram with the default size.
+    pub fn new(title: impl Into<String>) -> Self {
+        Diagram {
+            title: title.into(),
+            x_label: String::new(),
+            y_label: String::new(),
+            series: Vec::new(),
+            markers: Vec::new(),
+            zero_line: false,
+            width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+        }
+    }
+
+    /// Sets the title.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the labels of the axes.
+    pub fn labels(mut self, x_label: impl Into<String>, y_label: impl Into<String>) -> Self {
+        self.x_label = x_label.into();
+        self.y_label = y_label.into();
+        self
+    }
+
+    /// Sets the size of the image, in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Adds a line.
+    pub fn line(mut self, name: impl Into<String>, points: Vec<(f64, f64)>) -> Self {
+        self.series.push(DiagramSeries {
+            name: name.into(),
+            points,
+        });
+        self
+    }
+
+    /// Adds a vertical line.
+    pub fn marker(mut self, label: impl Into<String>, x: f64) -> Self {
+        self.markers.push(DiagramMarker {
+            label: label.into(),
+            x,
+        });
+        self
+    }
+
+    /// Diagram of the P&L of a strategy at expiration and today (T+0) over
+    /// `steps` intervals of `range`, with its strikes and break-even points.
+    ///
+    /// The T+0 curve marks every leg with Black-Scholes at its current
+    /// implied volatility and time to expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payoff curve cannot be sampled or a leg
+    /// cannot be priced.
+    pub fn payoff<S: PayoffCurvable + ?Sized>(
+        strategy: &S,
+        range: (Positive, Positive),
+        steps: usize,
+    ) -> Result<Self, StrategyError> {
+        let curve = strategy.payoff_curve(range, steps)?;
+        let positions = strategy.get_positions()?;
+        let today = ExpirationDate::Days(Positive::ZERO);
+
+        let mut expiry = Vec::with_capacity(curve.points.len());
+        let mut model = Vec::with_capacity(curve.points.len());
+        for point in &curve.points {
+            let mut marked = Decimal::ZERO;
+            for position in &positions {
+                marked += position.pnl_at_horizon(&point.price, &today)?;
+            }
+            expiry.push((point.price.to_f64(), to_f64(point.profit)));
+            model.push((point.price.to_f64(), to_f64(marked)));
+        }
+
+        let mut diagram = Diagram::new("Payoff")
+            .labels("Underlying price", "Profit and loss")
+            .line("At expiration", expiry)
+            .line("T+0", model);
+        diagram.zero_line = true;
+        for strike in &curve.strikes {
+            diagram = diagram.marker(format!("K {}", strike.strike), strike.strike.to_f64());
+        }
+        for point in &curve.break_even_points {
+            diagram = diagram.marker(format!("BE {}", point.round_to(2)), point.to_f64());
+        }
+        Ok(diagram)
+    }
+
+    /// Diagram of the net delta, gamma, theta or vega of a set of options
+    /// over `steps` intervals of `range` of underlying prices.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `greek` is not one of those four Greeks, the range
+    /// is empty, or a Greek cannot be computed.
+    pub fn greek<G: Greeks + ?Sized>(
+        source: &G,
+        greek: BasicAxisTypes,
+        range: (Positive, Positive),
+        steps: usize,
+    ) -> Result<Self, GreeksError> {
+        let (low, high) = range;
+        if low >= high || steps == 0 {
+            return Err(GreeksError::StdError(
+                "the range must be increasing and sampled on at least one step".to_string(),
+            ));
+        }
+        let name = match greek {
+            BasicAxisTypes::Delta => "Delta",
+            BasicAxisTypes::Gamma => "Gamma",
+            BasicAxisTypes::Theta => "Theta",
+            BasicAxisTypes::Vega => "Vega",
+            other => {
+                return Err(GreeksError::StdError(format!(
+                    "{other:?} cannot be drawn across underlying prices"
+                )));
+            }
+        };
+
+        let options: Vec<Options> = source.get_options()?.into_iter().cloned().collect();
+        let step = (high - low) / steps as f64;
+        let mut points = Vec::with_capacity(steps + 1);
+        for i in 0..=steps {
+            let price = low + step * i as f64;
+            let repriced = Repriced(
+                options
+                    .iter()
+                    .map(|option| Options {
+                        underlying_price: price,
+                        ..option.clone()
+                    })
+                    .collect(),
+            );
+            let net = repriced.net_greeks()?;
+            let value = match greek {
+                BasicAxisTypes::Delta => net.delta,
+                BasicAxisTypes::Gamma => net.gamma,
+                BasicAxisTypes::Theta => net.theta,
+                _ => net.vega,
+            };
+            points.push((price.to_f64(), to_f64(value)));
+        }
+
+        let mut diagram = Diagram::new(name)
+            .labels("Underlying price", name)
+            .line(name, points);
+        diagram.zero_line = true;
+        if let Some(option) = options.first() {
+            diagram = diagram.marker("Spot", option.underlying_price.to_f64());
+        }
+        Ok(diagram)
+    }
+
+    /// Diagram of the implied volatility smile of a chain.
+    pub fn smile<V: VolatilitySmile + ?Sized>(source: &V) -> Self {
+        let points = source
+            .smile()
+            .points
+            .iter()
+            .map(|point| (to_f64(point.x), to_f64(point.y)))
+            .collect();
+        Diagram::new("Volatility smile")
+            .labels("Strike", "Implied volatility")
+            .line("Implied volatility", points)
+    }
+
+    /// Renders the diagram as an SVG document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GraphError::Render` if the diagram cannot be drawn.
+    pub fn to_svg(&self) -> Result<String, GraphError> {
+        let mut svg = String::new();
+        {
+            let root =
+                SVGBackend::with_string(&mut svg, (self.width, self.height)).into_drawing_area();
+            self.draw(&root)?;
+        }
+        Ok(svg)
+    }
+
+    /// Renders the diagram to a PNG or SVG file, chosen by the extension of
+    /// `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GraphError::Render` if the extension is neither `png` nor
+    /// `svg`, or the diagram cannot be drawn or written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GraphError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("png") => {
+                let root = BitMapBackend::new(path, (self.width, self.height)).into_drawing_area();
+                self.draw(&root)
+            }
+            Some("svg") => {
+                let root = SVGBackend::new(path, (self.width, self.height)).into_drawing_area();
+                self.draw(&root)
+            }
+            _ => Err(GraphError::Render(format!(
+                "cannot render {} as a diagram, use a .png or .svg file",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Draws the diagram on a drawing area and presents it.
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> Result<(), GraphError> {
+        let render = |e: &dyn std::fmt::Display| GraphError::Render(e.to_string());
+        let (x_range, y_range) = self.bounds();
+
+        root.fill(&WHITE).map_err(|e| render(&e))?;
+        let mut chart = ChartBuilder::on(root)
+            .caption(&self.title, ("sans-serif", 28))
+            .margin(20)
+            .x_label_area_size(50)
+            .y_label_area_size(70)
+            .build_cartesian_2d(x_range.clone(), y_range.clone())
+            .map_err(|e| render(&e))?;
+        chart
+            .configure_mesh()
+            .x_desc(&self.x_label)
+            .y_desc(&self.y_label)
+            .draw()
+            .map_err(|e| render(&e))?;
+
+        if self.zero_line && y_range.start < 0.0 && y_range.end > 0.0 {
+            chart
+                .draw_series(LineSeries::new(
+                    [(x_range.start, 0.0), (x_range.end, 0.0)],
+                    BLACK.stroke_width(1),
+                ))
+                .map_err(|e| render(&e))?;
+        }
+        for marker in &self.markers {
+            let color = BLACK.mix(0.35);
+            chart
+                .draw_series(LineSeries::new(
+                    [(marker.x, y_range.start), (marker.x, y_range.end)],
+                    color.stroke_width(1),
+                ))
+                .map_err(|e| render(&e))?;
+            chart
+                .draw_series(std::iter::once(Text::new(
+                    marker.label.clone(),
+                    (marker.x, y_range.end),
+                    ("sans-serif", 14).into_font().color(&BLACK),
+                )))
+                .map_err(|e| render(&e))?;
+        }
+        for (index, series) in self.series.iter().enumerate() {
+            let color = Palette99::pick(index).to_rgba();
+            chart
+                .draw_series(LineSeries::new(
+                    series.points.iter().copied(),
+                    color.stroke_width(2),
+                ))
+                .map_err(|e| render(&e))?
+                .label(&series.name)
+                .legend(move |(x, y)| {
+                    PathElement::new([(x, y), (x + 20, y)], color.stroke_width(2))
+                });
+        }
+        if !self.series.is_empty() {
+            chart
+                .configure_series_labels()
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()
+                .map_err(|e| render(&e))?;
+        }
+        root.present().map_err(|e| render(&e))
+    }
+
+    /// Ranges of the axes covering every line and marker, with a margin on
+    /// the y axis.
+    fn bounds(&self) -> (std::ops::Range<f64>, std::ops::Range<f64>) {
+        let points = self.series.iter().flat_map(|series| series.points.iter());
+        let xs = points
+            .clone()
+            .map(|point| point.0)
+            .chain(self.markers.iter().map(|marker| marker.x));
+        let ys = points.map(|point| point.1).chain(
+            self.zero_line
+                .then_some(0.0)
+                .into_iter()
+                .filter(|_| self.series.is_empty()),
+        );
+        let span = |values: &mut dyn Iterator<Item = f64>| {
+            values
+                .filter(|value| value.is_finite())
+                .fold(None, |range: Option<(f64, f64)>, value| {
+                    Some(range.map_or((value, value), |(low, high)| {
+                        (low.min(value), high.max(value))
+                    }))
+                })
+                .unwrap_or((0.0, 1.0))
+        };
+        let (x_low, x_high) = span(&mut xs.into_iter());
+        let (y_low, y_high) = span(&mut ys.into_iter());
+        let pad = |low: f64, high: f64, ratio: f64| {
+            let margin = if high > low {
+                (high - low) * ratio
+            } else {
+                low.abs().max(1.0) * 0.1
+            };
+            (low - margin)..(high + margin)
+        };
+        let x_range = if x_high > x_low {
+            x_low..x_high
+        } else {
+            pad(x_low, x_high, 0.0)
+        };
+        (x_range, pad(y_low, y_high, 0.08))
+    }
+}
+
+/// Options repriced at another underlying price.
+struct Repriced(Vec<Options>);
+
+impl Greeks for Repriced {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self.0.iter().collect())
+    }
+}
+
+fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests_diagrams {
+    use super::*;
+    use crate::chains::chain::OptionChain;
+    use crate::strategies::BullCallSpread;
+    use positive::pos_or_panic;
+
+    fn bull_call_spread() -> BullCallSpread {
+        BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(7.0),
+            pos_or_panic!(3.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_payoff_diagram() {
+        let diagram = Diagram::payoff(
+            &bull_call_spread(),
+            (pos_or_panic!(80.0), pos_or_panic!(120.0)),
+            40,
+        )
+        .unwrap();
+        assert_eq!(diagram.series.len(), 2);
+        let expiry = &diagram.series[0].points;
+        let model = &diagram.series[1].points;
+        assert_eq!(expiry.len(), model.len());
+        // Before expiration the spread is worth more than its intrinsic value
+        // deep out of the money, and less deep in the money.
+        assert!(model[0].1 > expiry[0].1);
+        assert!(model[model.len() - 1].1 < expiry[expiry.len() - 1].1);
+        assert!(diagram.markers.iter().any(|marker| marker.label == "K 95"));
+        assert!(
+            diagram
+                .markers
+                .iter()
+                .any(|marker| marker.label.starts_with("BE"))
+        );
+
+        let svg = diagram.to_svg().unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("T+0"));
+    }
+
+    #[test]
+    fn test_greek_and_smile_diagrams() {
+        let strategy = bull_call_spread();
+        let range = (pos_or_panic!(70.0), pos_or_panic!(130.0));
+        let delta = Diagram::greek(&strategy, BasicAxisTypes::Delta, range, 20).unwrap();
+        let points = &delta.series[0].points;
+        assert_eq!(points.len(), 21);
+        assert!(points.iter().all(|point| point.1 >= -1e-9));
+        assert!(Diagram::greek(&strategy, BasicAxisTypes::Strike, range, 20).is_err());
+
+        let chain = OptionChain::load_from_json("./examples/Chains/SP500-18-oct-2024-5781.88.json")
+            .unwrap();
+        let smile = Diagram::smile(&chain);
+        assert!(!smile.series[0].points.is_empty());
+        assert!(smile.to_svg().unwrap().contains("Volatility smile"));
+    }
+
+    #[test]
+    fn test_save_formats() {
+        let diagram = Diagram::new("Line").line("y = x", vec![(0.0, 0.0), (1.0, 1.0)]);
+        let dir = std::env::temp_dir();
+        for extension in ["png", "svg"] {
+            let path = dir.join(format!("optionstratlib_diagram.{extension}"));
+            diagram.save(&path).unwrap();
+            assert!(std::fs::metadata(&path).unwrap().len() > 0);
+            std::fs::remove_file(&path).unwrap();
+        }
+        assert!(
+            diagram
+                .save(dir.join("optionstratlib_diagram.gif"))
+                .is_err()
+        );
+    }
+}
@@ -298,10 +298,14 @@
 //! Enjoy visualizing your financial data!
 
 mod config;
+/// PNG and SVG diagrams rendered with plotters
+#[cfg(feature = "visualization")]
+pub mod diagrams;
 mod interface;
 mod model;
 mod styles;
 mod tests;
+mod text;
 pub(crate) mod utils;
 
 #[cfg(not(feature = "plotly"))]
@@ -319,10 +323,13 @@ pub use {
 pub use default::Graph;
 
 pub use config::GraphConfig;
+#[cfg(feature = "visualization")]
+pub use diagrams::{Diagram, DiagramMarker, DiagramSeries};
 pub use interface::GraphType;
 pub use model::{
     GraphData, Label2D, Label3D, MultiSeries2D, OutputType, Series2D, Surface3D, VisPoint2D,
     VisPoint3D,
 };
 pub use styles::{ColorScheme, LineStyle, PlotType, TraceMode};
+pub use text::{TextCharset, TextChart};
 pub use utils::get_color_from_scheme;
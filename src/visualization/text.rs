@@ -0,0 +1,391 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Text Charts
+//!
+//! Draws payoff curves and P&L histograms with characters, so command line
+//! tools and test logs can show the shape of a strategy without any graphics
+//! dependency:
+//!
+//! ```text
+//!  6.00 ┤               │   │     ••••••••••••••••
+//!       │               │   │    •│
+//!       │               │   │   • │
+//!       │               │   │  •  │
+//!       │               │   │ •   │
+//!       │               │   │•    │
+//!  0.00 ┼───────────────┼───•─────┼───────────────
+//!       │               │  •│     │
+//!       │               │ • │     │
+//!       │               │•  │     │
+//! -4.00 ┤••••••••••••••••   │     │
+//!       └─────────────────────────────────────────
+//!       80                                     120
+//! ```
+//!
+//! This is the payoff of a bull call spread with strikes at 95 and 105 and
+//! its break-even at 99. Charts use Unicode box-drawing characters by
+//! default; the [`TextCharset::Ascii`] charset restricts them to plain ASCII.
+
+use crate::simulation::PnLBucket;
+use crate::strategies::PayoffCurve;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Default width of the plotting area, in characters.
+const DEFAULT_WIDTH: usize = 60;
+
+/// Default height of the plotting area, in lines.
+const DEFAULT_HEIGHT: usize = 15;
+
+/// Characters used to draw a chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextCharset {
+    /// Box-drawing and block characters.
+    #[default]
+    Unicode,
+    /// Plain ASCII characters.
+    Ascii,
+}
+
+/// Glyphs of a charset.
+struct Glyphs {
+    point: char,
+    zero: char,
+    marker: char,
+    cross: char,
+    axis: char,
+    tick: char,
+    zero_tick: char,
+    corner: char,
+    base: char,
+    bar: char,
+    /// Partial bars, from one eighth to seven eighths of a character.
+    partial: &'static [char],
+}
+
+impl TextCharset {
+    fn glyphs(&self) -> Glyphs {
+        match self {
+            TextCharset::Unicode => Glyphs {
+                point: '•',
+                zero: '─',
+                marker: '│',
+                cross: '┼',
+                axis: '│',
+                tick: '┤',
+                zero_tick: '┼',
+                corner: '└',
+                base: '─',
+                bar: '█',
+                partial: &['▏', '▎', '▍', '▌', '▋', '▊', '▉'],
+            },
+            TextCharset::Ascii => Glyphs {
+                point: '*',
+                zero: '-',
+                marker: '|',
+                cross: '+',
+                axis: '|',
+                tick: '+',
+                zero_tick: '+',
+                corner: '+',
+                base: '-',
+                bar: '#',
+                partial: &[],
+            },
+        }
+    }
+}
+
+/// Renders line charts and histograms as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextChart {
+    /// Width of the plotting area, in characters.
+    pub width: usize,
+    /// Height of the plotting area, in lines.
+    pub height: usize,
+    /// Characters used to draw.
+    pub charset: TextCharset,
+}
+
+impl Default for TextChart {
+    fn default() -> Self {
+        TextChart::new(DEFAULT_WIDTH, DEFAULT_HEIGHT)
+    }
+}
+
+impl TextChart {
+    /// Creates a chart with a plotting area of `width` characters by
+    /// `height` lines, at least two of each.
+    pub fn new(width: usize, height: usize) -> Self {
+        TextChart {
+            width: width.max(2),
+            height: height.max(2),
+            charset: TextCharset::default(),
+        }
+    }
+
+    /// Sets the characters used to draw.
+    pub fn charset(mut self, charset: TextCharset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Draws a payoff curve with vertical lines at its strikes and
+    /// break-even points.
+    pub fn payoff(&self, curve: &PayoffCurve) -> String {
+        let points: Vec<(f64, f64)> = curve
+            .points
+            .iter()
+            .map(|point| (point.price.to_f64(), to_f64(point.profit)))
+            .collect();
+        let markers: Vec<f64> = curve
+            .strikes
+            .iter()
+            .map(|marker| marker.strike.to_f64())
+            .chain(curve.break_even_points.iter().map(|point| point.to_f64()))
+            .collect();
+        self.line(&points, &markers)
+    }
+
+    /// Draws the line through `points`, sorted by x, with vertical lines at
+    /// `markers` and a horizontal line at zero when it is in range.
+    pub fn line(&self, points: &[(f64, f64)], markers: &[f64]) -> String {
+        let glyphs = self.charset.glyphs();
+        let points: Vec<(f64, f64)> = points
+            .iter()
+            .copied()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect();
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return String::new();
+        };
+        let (x_low, x_high) = (first.0, last.0.max(first.0));
+        let (mut y_low, mut y_high) = points
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), point| {
+                (low.min(point.1), high.max(point.1))
+            });
+        if (y_high - y_low).abs() < f64::EPSILON {
+            y_low -= 1.0;
+            y_high += 1.0;
+        }
+
+        let (width, height) = (self.width, self.height);
+        let row_of =
+            |y: f64| (((y_high - y) / (y_high - y_low)) * (height - 1) as f64).round() as usize;
+        let column_of = |x: f64| {
+            (((x - x_low) / (x_high - x_low).max(f64::EPSILON)) * (width - 1) as f64).round()
+                as usize
+        };
+        let mut grid = vec![vec![' '; width]; height];
+
+        let zero_row = (y_low <= 0.0 && y_high >= 0.0).then(|| row_of(0.0));
+        if let Some(row) = zero_row {
+            grid[row].fill(glyphs.zero);
+        }
+        for &marker in markers {
+            if marker < x_low || marker > x_high {
+                continue;
+            }
+            let column = column_of(marker);
+            for (row, line) in grid.iter_mut().enumerate() {
+                line[column] = if Some(row) == zero_row {
+                    glyphs.cross
+                } else {
+                    glyphs.marker
+                };
+            }
+        }
+
+        let mut previous: Option<usize> = None;
+        for column in 0..width {
+            let x = x_low + (x_high - x_low) * column as f64 / (width - 1) as f64;
+            let row = row_of(interpolate(&points, x));
+            // Fill the rows skipped by steep segments so the line stays connected.
+            let (from, to) = match previous {
+                Some(before) if before + 1 < row => (before + 1, row),
+                Some(before) if row + 1 < before => (row, before - 1),
+                _ => (row, row),
+            };
+            for line in &mut grid[from..=to] {
+                line[column] = glyphs.point;
+            }
+            previous = Some(row);
+        }
+
+        let labels: Vec<Option<String>> = (0..height)
+            .map(|row| {
+                let value = if row == 0 {
+                    y_high
+                } else if row == height - 1 {
+                    y_low
+                } else if Some(row) == zero_row {
+                    0.0
+                } else {
+                    return None;
+                };
+                Some(format!("{value:.2}"))
+            })
+            .collect();
+        let label_width = labels.iter().flatten().map(String::len).max().unwrap_or(0);
+
+        let mut text = String::new();
+        for (row, line) in grid.iter().enumerate() {
+            let axis = match &labels[row] {
+                Some(_) if Some(row) == zero_row => glyphs.zero_tick,
+                Some(_) => glyphs.tick,
+                None => glyphs.axis,
+            };
+            let label = labels[row].as_deref().unwrap_or("");
+            text.push_str(&format!("{label:>label_width$} {axis}"));
+            text.extend(line.iter());
+            text.push('\n');
+        }
+        text.push_str(&format!("{:>label_width$} {}", "", glyphs.corner));
+        text.extend(std::iter::repeat_n(glyphs.base, width));
+        text.push('\n');
+        let (low, high) = (format!("{x_low}"), format!("{x_high}"));
+        let gap = (width + 1).saturating_sub(low.len() + high.len()).max(1);
+        text.push_str(&format!("{:>label_width$} {low}{:gap$}{high}\n", "", ""));
+        text
+    }
+
+    /// Draws a P&L histogram as horizontal bars, one line per bucket, with
+    /// the probability of each bucket.
+    pub fn histogram(&self, buckets: &[PnLBucket]) -> String {
+        let glyphs = self.charset.glyphs();
+        let labels: Vec<String> = buckets
+            .iter()
+            .map(|bucket| format!("[{:.2}, {:.2})", bucket.lower, bucket.upper))
+            .collect();
+        let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+        let highest = buckets.iter().map(|bucket| bucket.count).max().unwrap_or(0);
+
+        let mut text = String::new();
+        for (bucket, label) in buckets.iter().zip(&labels) {
+            let length = if highest == 0 {
+                0.0
+            } else {
+                bucket.count as f64 / highest as f64 * self.width as f64
+            };
+            let mut bar: String = std::iter::repeat_n(glyphs.bar, length as usize).collect();
+            let eighths = ((length.fract() * 8.0) as usize).min(7);
+            if eighths > 0 && !glyphs.partial.is_empty() {
+                bar.push(glyphs.partial[eighths - 1]);
+            }
+            let padding = self.width + 1 - bar.chars().count();
+            let percent = bucket.probability * Decimal::ONE_HUNDRED;
+            text.push_str(&format!(
+                "{label:<label_width$} {axis}{bar}{:padding$}{percent:.1}%\n",
+                "",
+                axis = glyphs.axis
+            ));
+        }
+        text
+    }
+}
+
+/// Linear interpolation of `points`, sorted by x, clamped to their ends.
+fn interpolate(points: &[(f64, f64)], x: f64) -> f64 {
+    let after = points.partition_point(|point| point.0 < x);
+    match (after.checked_sub(1).map(|i| points[i]), points.get(after)) {
+        (Some(left), Some(right)) if right.0 > left.0 => {
+            left.1 + (right.1 - left.1) * (x - left.0) / (right.0 - left.0)
+        }
+        (_, Some(right)) => right.1,
+        (Some(left), None) => left.1,
+        (None, None) => 0.0,
+    }
+}
+
+fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests_text {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::strategies::{BullCallSpread, PayoffCurvable};
+    use positive::{Positive, pos_or_panic};
+    use rust_decimal_macros::dec;
+
+    fn payoff_curve() -> PayoffCurve {
+        BullCallSpread::new(
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(7.0),
+            pos_or_panic!(3.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .payoff_curve((pos_or_panic!(80.0), pos_or_panic!(120.0)), 40)
+        .unwrap()
+    }
+
+    #[test]
+    fn test_payoff_chart() {
+        let chart = TextChart::new(41, 11).payoff(&payoff_curve());
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert!(lines[0].starts_with(" 6.00 ┤"));
+        assert!(lines[10].starts_with("-4.00 ┤"));
+        // The maximum loss runs along the bottom left, the maximum profit
+        // along the top right.
+        assert!(lines[10].contains("••••••••"));
+        assert!(lines[0].ends_with("••••••••"));
+        assert!(lines.iter().any(|line| line.starts_with(" 0.00 ┼")));
+        assert!(lines[12].trim_start().starts_with("80"));
+        assert!(lines[12].ends_with("120"));
+
+        let ascii = TextChart::new(41, 11)
+            .charset(TextCharset::Ascii)
+            .payoff(&payoff_curve());
+        assert!(ascii.is_ascii());
+        assert!(ascii.contains('*'));
+    }
+
+    #[test]
+    fn test_histogram() {
+        let bucket = |lower: i64, count: usize| PnLBucket {
+            lower: Decimal::from(lower),
+            upper: Decimal::from(lower + 2),
+            count,
+            probability: Decimal::from(count) / dec!(20),
+        };
+        let buckets = [bucket(-4, 4), bucket(-2, 1), bucket(0, 15)];
+        let text = TextChart::new(20, 5).histogram(&buckets);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        // 4 and 1 of the 15 simulations of the tallest bar, on 20 characters.
+        assert_eq!(
+            lines[0],
+            format!("[-4.00, -2.00) │█████▎{}20.0%", " ".repeat(15))
+        );
+        assert_eq!(
+            lines[1],
+            format!("[-2.00, 0.00)  │█▎{}5.0%", " ".repeat(19))
+        );
+        assert!(lines[2].contains(&"█".repeat(20)));
+        assert!(lines[2].ends_with("75.0%"));
+
+        let ascii = TextChart::new(20, 5)
+            .charset(TextCharset::Ascii)
+            .histogram(&buckets);
+        assert!(ascii.is_ascii());
+        assert!(TextChart::default().histogram(&[]).is_empty());
+    }
+}
@@ -0,0 +1,262 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Earnings Volatility Crush
+//!
+//! Ahead of an earnings release the implied volatility of the expirations
+//! that span it is inflated by the move the market expects on the day, and
+//! it collapses once the news is out. The usual way to model this is an
+//! event variance decomposition: the total variance to an expiration is the
+//! variance of ordinary days plus the variance of each earnings day before
+//! it,
+//!
+//! ```text
+//! σ²(T) · T = σ_base² · T + n · σ_event²
+//! ```
+//!
+//! where `T` is the time to expiration in years, `n` the number of earnings
+//! releases before it and `σ_event` the standard deviation of the return on
+//! the earnings day. Short expirations spanning the event carry the largest
+//! bump, and all of it disappears when the event passes.
+//!
+//! [`EarningsVolModel`] implements the decomposition. It can be calibrated
+//! from the ATM implied volatilities of two expirations after the event,
+//! gives the volatility of any expiration on any date given an
+//! [`EventTimeline`], and carries the volatility of an existing leg forward
+//! through an event, which is how
+//! [`PnLPathParams::with_earnings_model`](crate::simulation::PnLPathParams::with_earnings_model)
+//! marks strategies spanning earnings in what-if projections. Synthetic
+//! chains for backtests can be built with
+//! [`EarningsVolModel::volatility_for_expiration`] so that they crush on the
+//! event date.
+
+use crate::chains::chain::OptionChain;
+use crate::error::VolatilityError;
+use crate::model::events::{EventTimeline, MarketEventKind};
+use crate::volatility::AtmIvProvider;
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+const SECONDS_PER_YEAR: i64 = 365 * 86_400;
+
+/// Implied volatility as a base level plus an earnings event bump.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct EarningsVolModel {
+    /// Annualized volatility of ordinary trading days.
+    pub base_volatility: Positive,
+    /// Standard deviation of the return on an earnings day, not annualized.
+    pub event_move: Positive,
+}
+
+impl EarningsVolModel {
+    /// Creates a model from the base volatility and the earnings day move.
+    pub fn new(base_volatility: Positive, event_move: Positive) -> Self {
+        EarningsVolModel {
+            base_volatility,
+            event_move,
+        }
+    }
+
+    /// Calibrates the model from the ATM implied volatilities of two
+    /// expirations that both span the next earnings release and no other.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if the far expiration is not after the
+    /// near one, or the volatilities imply a negative base or event variance.
+    pub fn calibrate(
+        near_years: Positive,
+        near_volatility: Positive,
+        far_years: Positive,
+        far_volatility: Positive,
+    ) -> Result<Self, VolatilityError> {
+        if far_years <= near_years || near_years == Positive::ZERO {
+            return Err(VolatilityError::InvalidTime {
+                time: far_years,
+                reason: "the far expiration must be after a near one still to come".to_string(),
+            });
+        }
+        let near_variance = near_volatility.to_dec().powi(2) * near_years.to_dec();
+        let far_variance = far_volatility.to_dec().powi(2) * far_years.to_dec();
+        let base_variance = (far_variance - near_variance) / (far_years - near_years).to_dec();
+        let event_variance = near_variance - base_variance * near_years.to_dec();
+        if base_variance < Decimal::ZERO || event_variance < Decimal::ZERO {
+            return Err(format!(
+                "Implied volatilities {near_volatility} and {far_volatility} do not decompose into base and event variance"
+            )
+            .into());
+        }
+        Ok(Self::new(root(base_variance), root(event_variance)))
+    }
+
+    /// Calibrates the model from the ATM implied volatilities of two chains
+    /// whose expirations both span the next earnings release, as of `as_of`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if a chain has no expiration or ATM
+    /// implied volatility, or the calibration fails.
+    pub fn calibrate_from_chains(
+        near: &OptionChain,
+        far: &OptionChain,
+        as_of: DateTime<Utc>,
+    ) -> Result<Self, VolatilityError> {
+        let years = |chain: &OptionChain| -> Result<Positive, VolatilityError> {
+            let expiration = chain
+                .get_expiration()
+                .and_then(|expiration| expiration.get_date().ok())
+                .ok_or_else(|| format!("Chain {} has no expiration date", chain.symbol))?;
+            Ok(years_between(as_of, expiration))
+        };
+        Self::calibrate(years(near)?, *near.atm_iv()?, years(far)?, *far.atm_iv()?)
+    }
+
+    /// Variance added by one earnings release.
+    pub fn event_variance(&self) -> Decimal {
+        self.event_move.to_dec().powi(2)
+    }
+
+    /// Implied volatility of an expiration `years` away with `events`
+    /// earnings releases before it. The base volatility once no time is left.
+    pub fn implied_volatility(&self, years: Positive, events: usize) -> Positive {
+        if years == Positive::ZERO {
+            return self.base_volatility;
+        }
+        let variance = self.base_volatility.to_dec().powi(2)
+            + Decimal::from(events) * self.event_variance() / years.to_dec();
+        root(variance)
+    }
+
+    /// Fall in the implied volatility of an expiration when an earnings
+    /// release it spans passes, with `years` left at the release.
+    pub fn crush(&self, years: Positive) -> Positive {
+        self.implied_volatility(years, 1)
+            .saturating_sub(&self.base_volatility)
+    }
+
+    /// One standard deviation move of the underlying on the earnings day.
+    pub fn expected_move(&self, underlying_price: Positive) -> Positive {
+        underlying_price * self.event_move
+    }
+
+    /// Implied volatility of the expiration of `symbol` at `expiration`, as
+    /// of `as_of`, counting the earnings releases of `timeline` in between.
+    pub fn volatility_for_expiration(
+        &self,
+        timeline: &EventTimeline,
+        symbol: &str,
+        as_of: DateTime<Utc>,
+        expiration: DateTime<Utc>,
+    ) -> Positive {
+        let events = timeline.count_between(symbol, MarketEventKind::Earnings, as_of, expiration);
+        self.implied_volatility(years_between(as_of, expiration), events)
+    }
+
+    /// Carries the implied volatility of an existing leg forward in time.
+    ///
+    /// The base volatility of the leg is inferred from `implied`, with
+    /// `years` and `events` left now, so each leg keeps its own level and
+    /// skew; the event bump is then recomputed with the time and releases
+    /// left later. Only the event move of the model is used.
+    pub fn roll_forward(
+        &self,
+        implied: Positive,
+        years: Positive,
+        events: usize,
+        later_years: Positive,
+        later_events: usize,
+    ) -> Positive {
+        let base_variance = if years == Positive::ZERO {
+            implied.to_dec().powi(2)
+        } else {
+            implied.to_dec().powi(2)
+                - Decimal::from(events) * self.event_variance() / years.to_dec()
+        };
+        let leg = EarningsVolModel::new(root(base_variance), self.event_move);
+        leg.implied_volatility(later_years, later_events)
+    }
+}
+
+fn years_between(from: DateTime<Utc>, to: DateTime<Utc>) -> Positive {
+    let seconds = (to - from).num_seconds().max(0);
+    Positive::new_decimal(Decimal::from(seconds) / Decimal::from(SECONDS_PER_YEAR))
+        .unwrap_or(Positive::ZERO)
+}
+
+/// Square root of a variance, zero when negative.
+fn root(variance: Decimal) -> Positive {
+    variance
+        .max(Decimal::ZERO)
+        .sqrt()
+        .and_then(|value| Positive::new_decimal(value).ok())
+        .unwrap_or(Positive::ZERO)
+}
+
+#[cfg(test)]
+mod tests_earnings {
+    use super::*;
+    use crate::model::events::MarketEvent;
+    use chrono::Duration;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_decomposition_and_calibration() {
+        let model = EarningsVolModel::new(pos_or_panic!(0.25), pos_or_panic!(0.06));
+        let week = pos_or_panic!(7.0 / 365.0);
+        let quarter = pos_or_panic!(90.0 / 365.0);
+
+        // The front expiration carries the largest bump.
+        let near = model.implied_volatility(week, 1);
+        let far = model.implied_volatility(quarter, 1);
+        assert!(near > far && far > model.base_volatility);
+        assert_eq!(model.implied_volatility(week, 0), model.base_volatility);
+        assert_eq!(model.crush(week), near - model.base_volatility);
+        assert_eq!(model.expected_move(Positive::HUNDRED), pos_or_panic!(6.0));
+
+        let calibrated = EarningsVolModel::calibrate(week, near, quarter, far).unwrap();
+        assert!(
+            (calibrated.base_volatility.to_dec() - model.base_volatility.to_dec()).abs()
+                < dec!(1e-9)
+        );
+        assert!((calibrated.event_move.to_dec() - model.event_move.to_dec()).abs() < dec!(1e-9));
+
+        assert!(EarningsVolModel::calibrate(quarter, far, week, near).is_err());
+        // A front volatility below the back one cannot hold an event bump.
+        assert!(EarningsVolModel::calibrate(week, far, quarter, near).is_err());
+    }
+
+    #[test]
+    fn test_timeline_and_roll_forward() {
+        let model = EarningsVolModel::new(pos_or_panic!(0.3), pos_or_panic!(0.05));
+        let now = Utc::now();
+        let timeline =
+            EventTimeline::new().with_event(MarketEvent::earnings("XYZ", now + Duration::days(3)));
+        let expiration = now + Duration::days(10);
+
+        let before = model.volatility_for_expiration(&timeline, "XYZ", now, expiration);
+        let after =
+            model.volatility_for_expiration(&timeline, "XYZ", now + Duration::days(4), expiration);
+        assert!(before > pos_or_panic!(0.4));
+        assert_eq!(after, model.base_volatility);
+        assert_eq!(
+            model.volatility_for_expiration(&timeline, "ABC", now, expiration),
+            model.base_volatility
+        );
+
+        // A leg priced 5 points above the model keeps its premium to the base.
+        let ten_days = pos_or_panic!(10.0 / 365.0);
+        let six_days = pos_or_panic!(6.0 / 365.0);
+        let leg = model.implied_volatility(ten_days, 1) + pos_or_panic!(0.05);
+        let crushed = model.roll_forward(leg, ten_days, 1, six_days, 0);
+        assert!(crushed > model.base_volatility && crushed < pos_or_panic!(0.4));
+        let unchanged = model.roll_forward(leg, ten_days, 1, ten_days, 1);
+        assert!((unchanged.to_dec() - leg.to_dec()).abs() < dec!(1e-9));
+    }
+}
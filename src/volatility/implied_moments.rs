@@ -0,0 +1,216 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Model-Free Implied Moments
+//!
+//! Variance, skewness and kurtosis of the risk-neutral distribution of the
+//! log return to expiration, recovered from the out-of-the-money option
+//! prices of one expiration without assuming a pricing model (Bakshi, Kapadia
+//! and Madan, 2003).
+//!
+//! With `x = ln(K/S)` and `Q(K)` the price of the out-of-the-money option at
+//! strike `K` (puts below the underlying price, calls at or above it), the
+//! prices of the quadratic, cubic and quartic contracts are
+//!
+//! ```text
+//! V = ∫ 2·(1 - x) / K² · Q(K) dK
+//! W = ∫ (6x - 3x²) / K² · Q(K) dK
+//! X = ∫ (12x² - 4x³) / K² · Q(K) dK
+//! ```
+//!
+//! and the moments follow from them and the risk-free growth `e^{rT}`. A
+//! negative implied skewness means downside puts are bid relative to upside
+//! calls; a kurtosis above 3 means fatter tails than the lognormal model.
+//! The integrals are taken with the trapezoidal rule over the listed strikes,
+//! so the estimates need a chain that reaches well into both wings.
+
+use crate::chains::chain::OptionChain;
+use crate::error::VolatilityError;
+use num_traits::{FromPrimitive, ToPrimitive};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Minimum number of out-of-the-money quotes needed for the integrals.
+const MIN_QUOTES: usize = 3;
+
+/// Model-free implied moments of the log return to one expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ImpliedMoments {
+    /// Expiration of the chain.
+    pub expiration: String,
+    /// Time to expiration in years.
+    pub years: Positive,
+    /// Annualized implied volatility, the square root of the variance per year.
+    pub volatility: Positive,
+    /// Implied skewness of the log return.
+    pub skewness: Decimal,
+    /// Implied kurtosis of the log return, 3 for a normal distribution.
+    pub kurtosis: Decimal,
+    /// Number of out-of-the-money quotes integrated.
+    pub quotes: usize,
+}
+
+impl ImpliedMoments {
+    /// Kurtosis in excess of the normal distribution.
+    pub fn excess_kurtosis(&self) -> Decimal {
+        self.kurtosis - Decimal::from(3)
+    }
+}
+
+/// Computes the model-free implied moments of a chain slice.
+///
+/// # Errors
+///
+/// Returns a `VolatilityError` if the expiration of the chain cannot be
+/// parsed or has passed, fewer than three strikes have an out-of-the-money
+/// mid price, or the implied variance is not positive.
+pub fn implied_moments(chain: &OptionChain) -> Result<ImpliedMoments, VolatilityError> {
+    let years = chain
+        .get_expiration()
+        .ok_or_else(|| {
+            VolatilityError::from(format!(
+                "Invalid expiration {} in chain {}",
+                chain.get_expiration_date(),
+                chain.symbol
+            ))
+        })?
+        .get_years()
+        .map_err(|e| VolatilityError::from(e.to_string()))?;
+    if years == Positive::ZERO {
+        return Err(VolatilityError::InvalidTime {
+            time: years,
+            reason: "Implied moments need time to expiration".to_string(),
+        });
+    }
+    let t = years.to_f64();
+    let spot = chain.underlying_price.to_f64();
+    let growth = (chain
+        .risk_free_rate
+        .and_then(|rate| rate.to_f64())
+        .unwrap_or(0.0)
+        * t)
+        .exp();
+
+    let quotes: Vec<(f64, f64)> = chain
+        .get_single_iter()
+        .filter_map(|od| {
+            let strike = od.strike_price.to_f64();
+            let price = if strike < spot {
+                od.put_middle
+            } else {
+                od.call_middle
+            }?;
+            Some((strike, price.to_f64()))
+        })
+        .collect();
+    if quotes.len() < MIN_QUOTES {
+        return Err(VolatilityError::from(format!(
+            "Chain {} needs at least {MIN_QUOTES} out-of-the-money quotes, has {}",
+            chain.symbol,
+            quotes.len()
+        )));
+    }
+
+    let integrate = |weight: fn(f64) -> f64| -> f64 {
+        let values: Vec<(f64, f64)> = quotes
+            .iter()
+            .map(|(strike, price)| {
+                let x = (strike / spot).ln();
+                (*strike, weight(x) / (strike * strike) * price)
+            })
+            .collect();
+        values
+            .windows(2)
+            .map(|pair| (pair[1].0 - pair[0].0) * (pair[0].1 + pair[1].1) / 2.0)
+            .sum()
+    };
+    let quadratic = integrate(|x| 2.0 * (1.0 - x));
+    let cubic = integrate(|x| 6.0 * x - 3.0 * x * x);
+    let quartic = integrate(|x| 12.0 * x * x - 4.0 * x * x * x);
+
+    let mean = growth - 1.0 - growth * (quadratic / 2.0 + cubic / 6.0 + quartic / 24.0);
+    let variance = growth * quadratic - mean * mean;
+    if variance <= 0.0 {
+        return Err(VolatilityError::from(format!(
+            "Implied variance of chain {} is not positive",
+            chain.symbol
+        )));
+    }
+    let skewness = (growth * cubic - 3.0 * mean * growth * quadratic + 2.0 * mean.powi(3))
+        / variance.powf(1.5);
+    let kurtosis = (growth * quartic - 4.0 * mean * growth * cubic
+        + 6.0 * growth * mean * mean * quadratic
+        - 3.0 * mean.powi(4))
+        / (variance * variance);
+
+    Ok(ImpliedMoments {
+        expiration: chain.get_expiration_date(),
+        years,
+        volatility: Positive::new((variance / t).sqrt())?,
+        skewness: Decimal::from_f64(skewness).unwrap_or_default(),
+        kurtosis: Decimal::from_f64(kurtosis).unwrap_or_default(),
+        quotes: quotes.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests_implied_moments {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn build_chain(skew_slope: Decimal) -> OptionChain {
+        let params = OptionChainBuildParams::new(
+            "XYZ".to_string(),
+            spos!(1000.0),
+            60,
+            spos!(1.0),
+            skew_slope,
+            Decimal::ZERO,
+            pos_or_panic!(0.02),
+            4,
+            OptionDataPriceParams::new(
+                Some(Box::new(Positive::HUNDRED)),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(Decimal::ZERO),
+                spos!(0.0),
+                Some("XYZ".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    #[test]
+    fn test_flat_smile_is_close_to_lognormal() {
+        let moments = implied_moments(&build_chain(Decimal::ZERO)).unwrap();
+        assert!((moments.volatility.to_dec() - dec!(0.2)).abs() < dec!(0.01));
+        assert!(moments.skewness.abs() < dec!(0.1));
+        assert!(moments.excess_kurtosis().abs() < dec!(0.3));
+        assert!(moments.quotes > 20);
+    }
+
+    #[test]
+    fn test_put_skew_gives_negative_skewness() {
+        let flat = implied_moments(&build_chain(Decimal::ZERO)).unwrap();
+        let skewed = implied_moments(&build_chain(dec!(-0.6))).unwrap();
+        assert!(skewed.skewness < flat.skewness - dec!(0.1));
+    }
+
+    #[test]
+    fn test_too_few_quotes() {
+        let mut chain = build_chain(Decimal::ZERO);
+        chain.mutate_single_options(|od| {
+            od.call_middle = None;
+            od.put_middle = None;
+        });
+        assert!(implied_moments(&chain).is_err());
+    }
+}
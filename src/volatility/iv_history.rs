@@ -0,0 +1,571 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Historical IV Statistics
+//!
+//! Maintains a per-symbol history of at-the-money implied volatility and
+//! derives the rolling statistics most entry rules are written against:
+//!
+//! * **IV rank** - where the current IV sits between the lowest and highest
+//!   value of the window, from 0 to 100.
+//! * **IV percentile** - percentage of observations in the window below the
+//!   current IV.
+//! * **z-score** - distance of the current IV from the window mean in
+//!   standard deviations.
+//!
+//! The database is fed from stored chain history through [`IvHistoryBuilder`]
+//! (or incrementally through [`IvHistoryDatabase::record`]) and can be
+//! persisted to and restored from JSON. Chains recorded through
+//! [`IvHistoryDatabase::record_chain`] also keep their model-free implied
+//! skewness and kurtosis, reported next to the IV rank so screens can filter
+//! on the shape of the smile as well as its level.
+
+use crate::chains::chain::OptionChain;
+use crate::error::VolatilityError;
+use crate::f2du;
+use crate::volatility::AtmIvProvider;
+use crate::volatility::implied_moments::{ImpliedMoments, implied_moments};
+use chrono::{DateTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::Path;
+use utoipa::ToSchema;
+
+/// Default rolling window, one year of daily observations.
+pub const DEFAULT_IV_WINDOW: usize = 252;
+
+/// A single ATM implied volatility observation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct IvObservation {
+    /// Time of the observation.
+    pub timestamp: DateTime<Utc>,
+    /// At-the-money implied volatility.
+    pub implied_volatility: Positive,
+}
+
+/// Rolling IV statistics of one symbol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct IvStatistics {
+    /// Symbol the statistics belong to.
+    pub symbol: String,
+    /// Most recent implied volatility.
+    pub current: Positive,
+    /// Lowest implied volatility in the window.
+    pub min: Positive,
+    /// Highest implied volatility in the window.
+    pub max: Positive,
+    /// Mean implied volatility of the window.
+    pub mean: Decimal,
+    /// Standard deviation of the implied volatility in the window.
+    pub std_dev: Decimal,
+    /// IV rank (0-100).
+    pub rank: Decimal,
+    /// IV percentile (0-100).
+    pub percentile: Decimal,
+    /// Standard score of the current implied volatility.
+    pub z_score: Decimal,
+    /// Number of observations in the window.
+    pub observations: usize,
+    /// Model-free implied skewness of the most recently recorded chain.
+    pub implied_skewness: Option<Decimal>,
+    /// Model-free implied kurtosis of the most recently recorded chain.
+    pub implied_kurtosis: Option<Decimal>,
+}
+
+/// Per-symbol store of ATM implied volatility history with rolling statistics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct IvHistoryDatabase {
+    /// Number of most recent observations used for the statistics.
+    pub window: usize,
+    series: BTreeMap<String, Vec<IvObservation>>,
+    #[serde(default)]
+    moments: BTreeMap<String, ImpliedMoments>,
+}
+
+impl Default for IvHistoryDatabase {
+    fn default() -> Self {
+        IvHistoryDatabase::new(DEFAULT_IV_WINDOW)
+    }
+}
+
+impl IvHistoryDatabase {
+    /// Creates an empty database using a rolling window of `window` observations.
+    pub fn new(window: usize) -> Self {
+        IvHistoryDatabase {
+            window: window.max(1),
+            series: BTreeMap::new(),
+            moments: BTreeMap::new(),
+        }
+    }
+
+    /// Records an observation, keeping each series ordered by timestamp.
+    ///
+    /// An observation with the same timestamp as an existing one replaces it.
+    pub fn record(&mut self, symbol: &str, timestamp: DateTime<Utc>, implied_volatility: Positive) {
+        let series = self.series.entry(symbol.to_string()).or_default();
+        let observation = IvObservation {
+            timestamp,
+            implied_volatility,
+        };
+        match series.binary_search_by_key(&timestamp, |o| o.timestamp) {
+            Ok(index) => series[index] = observation,
+            Err(index) => series.insert(index, observation),
+        }
+    }
+
+    /// Records the ATM implied volatility of any [`AtmIvProvider`], such as an `OptionChain`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if the provider has no ATM implied volatility.
+    pub fn record_provider<P: AtmIvProvider + ?Sized>(
+        &mut self,
+        symbol: &str,
+        timestamp: DateTime<Utc>,
+        provider: &P,
+    ) -> Result<(), VolatilityError> {
+        let iv = *provider.atm_iv()?;
+        self.record(symbol, timestamp, iv);
+        Ok(())
+    }
+
+    /// Records the implied moments of `symbol`, replacing the previous ones.
+    pub fn record_moments(&mut self, symbol: &str, moments: ImpliedMoments) {
+        self.moments.insert(symbol.to_string(), moments);
+    }
+
+    /// Records the ATM implied volatility and the implied moments of a chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if the chain has no ATM implied volatility
+    /// or its implied moments cannot be computed. Nothing is recorded then.
+    pub fn record_chain(
+        &mut self,
+        symbol: &str,
+        timestamp: DateTime<Utc>,
+        chain: &OptionChain,
+    ) -> Result<(), VolatilityError> {
+        let iv = *chain.atm_iv()?;
+        let moments = implied_moments(chain)?;
+        self.record(symbol, timestamp, iv);
+        self.record_moments(symbol, moments);
+        Ok(())
+    }
+
+    /// Returns the most recently recorded implied moments of `symbol`.
+    pub fn implied_moments(&self, symbol: &str) -> Option<&ImpliedMoments> {
+        self.moments.get(symbol)
+    }
+
+    /// Returns the symbols with at least one observation.
+    pub fn symbols(&self) -> Vec<&str> {
+        self.series.keys().map(String::as_str).collect()
+    }
+
+    /// Returns the full history of `symbol`, oldest first.
+    pub fn history(&self, symbol: &str) -> &[IvObservation] {
+        self.series.get(symbol).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns the observations of the rolling window of `symbol`, oldest first.
+    pub fn window_of(&self, symbol: &str) -> &[IvObservation] {
+        let history = self.history(symbol);
+        &history[history.len().saturating_sub(self.window)..]
+    }
+
+    /// Computes the rolling statistics of `symbol`, or `None` if it has no history.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if the mean, standard deviation or z-score
+    /// of the window cannot be represented as a `Decimal`.
+    pub fn statistics(&self, symbol: &str) -> Result<Option<IvStatistics>, VolatilityError> {
+        let window = self.window_of(symbol);
+        let Some((current, min, max)) = self.range(symbol) else {
+            return Ok(None);
+        };
+        let values: Vec<f64> = window
+            .iter()
+            .map(|o| o.implied_volatility.to_f64())
+            .collect();
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count).sqrt();
+        let z_score = if std_dev > f64::EPSILON {
+            (current.to_f64() - mean) / std_dev
+        } else {
+            0.0
+        };
+        let to_decimal = |name: &str, value: f64| {
+            f2du!(value).map_err(|e| {
+                VolatilityError::from(format!("IV {name} of {symbol} is not representable: {e}"))
+            })
+        };
+
+        Ok(Some(IvStatistics {
+            symbol: symbol.to_string(),
+            current,
+            min,
+            max,
+            mean: to_decimal("mean", mean)?,
+            std_dev: to_decimal("standard deviation", std_dev)?,
+            rank: rank(current, min, max),
+            percentile: percentile(window, current),
+            z_score: to_decimal("z-score", z_score)?,
+            observations: window.len(),
+            implied_skewness: self.moments.get(symbol).map(|m| m.skewness),
+            implied_kurtosis: self.moments.get(symbol).map(|m| m.kurtosis),
+        }))
+    }
+
+    /// Current, lowest and highest IV of the window of `symbol`.
+    fn range(&self, symbol: &str) -> Option<(Positive, Positive, Positive)> {
+        let window = self.window_of(symbol);
+        let current = window.last()?.implied_volatility;
+        let min = window.iter().map(|o| o.implied_volatility).min()?;
+        let max = window.iter().map(|o| o.implied_volatility).max()?;
+        Some((current, min, max))
+    }
+
+    /// Returns the IV rank (0-100) of `symbol`.
+    pub fn iv_rank(&self, symbol: &str) -> Option<Decimal> {
+        self.range(symbol)
+            .map(|(current, min, max)| rank(current, min, max))
+    }
+
+    /// Returns the IV percentile (0-100) of `symbol`.
+    pub fn iv_percentile(&self, symbol: &str) -> Option<Decimal> {
+        self.range(symbol)
+            .map(|(current, _, _)| percentile(self.window_of(symbol), current))
+    }
+
+    /// Returns the IV z-score of `symbol`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if the statistics of `symbol` cannot be computed.
+    pub fn iv_z_score(&self, symbol: &str) -> Result<Option<Decimal>, VolatilityError> {
+        Ok(self.statistics(symbol)?.map(|s| s.z_score))
+    }
+
+    /// Returns the statistics of every symbol whose IV rank lies in `[min, max]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if the statistics of a symbol cannot be computed.
+    pub fn symbols_with_rank_between(
+        &self,
+        min: Decimal,
+        max: Decimal,
+    ) -> Result<Vec<IvStatistics>, VolatilityError> {
+        Ok(self
+            .all_statistics()?
+            .into_iter()
+            .filter(|s| s.rank >= min && s.rank <= max)
+            .collect())
+    }
+
+    /// Returns the statistics of every symbol whose implied skewness is at or
+    /// below `max`, most negative first. Symbols without recorded moments are
+    /// left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if the statistics of a symbol cannot be computed.
+    pub fn symbols_with_skewness_below(
+        &self,
+        max: Decimal,
+    ) -> Result<Vec<IvStatistics>, VolatilityError> {
+        let mut matches: Vec<IvStatistics> = self
+            .all_statistics()?
+            .into_iter()
+            .filter(|s| s.implied_skewness.is_some_and(|skew| skew <= max))
+            .collect();
+        matches.sort_by_key(|s| s.implied_skewness);
+        Ok(matches)
+    }
+
+    /// Statistics of every symbol with history.
+    fn all_statistics(&self) -> Result<Vec<IvStatistics>, VolatilityError> {
+        let mut statistics = Vec::with_capacity(self.series.len());
+        for symbol in self.series.keys() {
+            statistics.extend(self.statistics(symbol)?);
+        }
+        Ok(statistics)
+    }
+
+    /// Saves the database as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if the file cannot be written.
+    pub fn save_to_json<P: AsRef<Path>>(&self, path: P) -> Result<(), VolatilityError> {
+        let file = File::create(path).map_err(|e| VolatilityError::from(e.to_string()))?;
+        serde_json::to_writer_pretty(file, self).map_err(|e| VolatilityError::from(e.to_string()))
+    }
+
+    /// Loads a database previously saved with [`IvHistoryDatabase::save_to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VolatilityError` if the file cannot be read or parsed.
+    pub fn load_from_json<P: AsRef<Path>>(path: P) -> Result<Self, VolatilityError> {
+        let file = File::open(path).map_err(|e| VolatilityError::from(e.to_string()))?;
+        serde_json::from_reader(file).map_err(|e| VolatilityError::from(e.to_string()))
+    }
+}
+
+/// Builds an [`IvHistoryDatabase`] from stored chain or surface history.
+///
+/// Snapshots that do not expose an ATM implied volatility are skipped and
+/// counted, so a few broken archives do not abort the whole build.
+#[derive(Debug, Clone, Default)]
+pub struct IvHistoryBuilder {
+    database: IvHistoryDatabase,
+    skipped: usize,
+}
+
+impl IvHistoryBuilder {
+    /// Creates a builder with the default window.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts from an existing database, for example one loaded from disk.
+    pub fn from_database(database: IvHistoryDatabase) -> Self {
+        IvHistoryBuilder {
+            database,
+            skipped: 0,
+        }
+    }
+
+    /// Sets the rolling window in observations.
+    pub fn window(mut self, window: usize) -> Self {
+        self.database.window = window.max(1);
+        self
+    }
+
+    /// Adds one stored snapshot.
+    pub fn add_snapshot<P: AtmIvProvider + ?Sized>(
+        mut self,
+        symbol: &str,
+        timestamp: DateTime<Utc>,
+        snapshot: &P,
+    ) -> Self {
+        if self
+            .database
+            .record_provider(symbol, timestamp, snapshot)
+            .is_err()
+        {
+            self.skipped += 1;
+        }
+        self
+    }
+
+    /// Adds a sequence of stored snapshots of one symbol.
+    pub fn add_history<'a, P, I>(mut self, symbol: &str, snapshots: I) -> Self
+    where
+        P: AtmIvProvider + 'a,
+        I: IntoIterator<Item = (DateTime<Utc>, &'a P)>,
+    {
+        for (timestamp, snapshot) in snapshots {
+            self = self.add_snapshot(symbol, timestamp, snapshot);
+        }
+        self
+    }
+
+    /// Number of snapshots skipped because they had no ATM implied volatility.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Returns the built database.
+    pub fn build(self) -> IvHistoryDatabase {
+        self.database
+    }
+}
+
+/// Position of `current` between `min` and `max`, from 0 to 100.
+fn rank(current: Positive, min: Positive, max: Positive) -> Decimal {
+    if max > min {
+        (current - min).to_dec() / (max - min).to_dec() * Decimal::ONE_HUNDRED
+    } else {
+        Decimal::ZERO
+    }
+}
+
+/// Percentage of the observations of `window` below `current`.
+fn percentile(window: &[IvObservation], current: Positive) -> Decimal {
+    let below = window
+        .iter()
+        .filter(|o| o.implied_volatility < current)
+        .count();
+    Decimal::from(below) / Decimal::from(window.len()) * Decimal::ONE_HUNDRED
+}
+
+#[cfg(test)]
+mod tests_iv_history {
+    use super::*;
+    use crate::chains::chain::OptionChain;
+    use chrono::{Duration, TimeZone};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn day(n: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap() + Duration::days(n)
+    }
+
+    fn database(values: &[f64]) -> IvHistoryDatabase {
+        let mut db = IvHistoryDatabase::new(10);
+        for (i, v) in values.iter().enumerate() {
+            db.record("SPY", day(i as i64), pos_or_panic!(*v));
+        }
+        db
+    }
+
+    #[test]
+    fn test_rank_percentile_and_z_score() {
+        let db = database(&[0.1, 0.2, 0.3, 0.4, 0.2]);
+        let stats = db.statistics("SPY").unwrap().unwrap();
+        assert_eq!(stats.current, pos_or_panic!(0.2));
+        assert_eq!(stats.min, pos_or_panic!(0.1));
+        assert_eq!(stats.max, pos_or_panic!(0.4));
+        assert_eq!(stats.observations, 5);
+        assert!((stats.rank - dec!(33.333)).abs() < dec!(0.01));
+        assert_eq!(stats.percentile, dec!(20));
+        assert!(stats.z_score < Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_rolling_window() {
+        let values: Vec<f64> = (1..=15).map(|i| i as f64 / 100.0).collect();
+        let db = database(&values);
+        assert_eq!(db.history("SPY").len(), 15);
+        assert_eq!(db.window_of("SPY").len(), 10);
+        let stats = db.statistics("SPY").unwrap().unwrap();
+        assert_eq!(stats.min, pos_or_panic!(0.06));
+        assert_eq!(stats.rank, dec!(100));
+        assert_eq!(db.iv_percentile("SPY"), Some(dec!(90)));
+    }
+
+    #[test]
+    fn test_out_of_order_and_duplicate_records() {
+        let mut db = IvHistoryDatabase::new(10);
+        db.record("QQQ", day(2), pos_or_panic!(0.3));
+        db.record("QQQ", day(1), pos_or_panic!(0.2));
+        db.record("QQQ", day(2), pos_or_panic!(0.25));
+        let history = db.history("QQQ");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].implied_volatility, pos_or_panic!(0.25));
+        assert!(db.statistics("IWM").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_constant_series() {
+        let db = database(&[0.2, 0.2, 0.2]);
+        let stats = db.statistics("SPY").unwrap().unwrap();
+        assert_eq!(stats.rank, Decimal::ZERO);
+        assert_eq!(stats.z_score, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_builder_from_providers() {
+        let empty = OptionChain::new(
+            "SPY",
+            Positive::HUNDRED,
+            "2030-01-18".to_string(),
+            None,
+            None,
+        );
+        let ivs = [pos_or_panic!(0.15), pos_or_panic!(0.25)];
+        let builder = IvHistoryBuilder::new()
+            .window(20)
+            .add_history(
+                "SPY",
+                ivs.iter().enumerate().map(|(i, iv)| (day(i as i64), iv)),
+            )
+            .add_snapshot("SPY", day(5), &empty);
+        assert_eq!(builder.skipped(), 1);
+        let db = builder.build();
+        assert_eq!(db.window, 20);
+        assert_eq!(db.iv_rank("SPY"), Some(dec!(100)));
+    }
+
+    #[test]
+    fn test_rank_screening() {
+        let mut db = database(&[0.1, 0.4, 0.2]);
+        db.record("QQQ", day(0), pos_or_panic!(0.1));
+        db.record("QQQ", day(1), pos_or_panic!(0.5));
+        let high = db.symbols_with_rank_between(dec!(50), dec!(100)).unwrap();
+        assert_eq!(high.len(), 1);
+        assert_eq!(high[0].symbol, "QQQ");
+        assert_eq!(db.symbols(), vec!["QQQ", "SPY"]);
+    }
+
+    #[test]
+    fn test_chain_moments_alongside_rank() {
+        use crate::ExpirationDate;
+        use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+        use positive::spos;
+
+        let chain = |skew_slope| {
+            let params = OptionChainBuildParams::new(
+                "SPY".to_string(),
+                spos!(1000.0),
+                60,
+                spos!(1.0),
+                skew_slope,
+                Decimal::ZERO,
+                pos_or_panic!(0.02),
+                4,
+                OptionDataPriceParams::new(
+                    Some(Box::new(Positive::HUNDRED)),
+                    Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                    Some(Decimal::ZERO),
+                    spos!(0.0),
+                    Some("SPY".to_string()),
+                ),
+                pos_or_panic!(0.2),
+            );
+            OptionChain::build_chain(&params).unwrap()
+        };
+        let mut db = database(&[0.1, 0.3]);
+        db.record_chain("SPY", day(5), &chain(dec!(-0.6))).unwrap();
+        db.record_chain("QQQ", day(5), &chain(Decimal::ZERO))
+            .unwrap();
+        db.record("IWM", day(5), pos_or_panic!(0.2));
+
+        let stats = db.statistics("SPY").unwrap().unwrap();
+        assert_eq!(stats.observations, 3);
+        assert!(stats.implied_skewness.unwrap() < Decimal::ZERO);
+        assert!(stats.implied_kurtosis.is_some());
+        assert!(
+            db.statistics("IWM")
+                .unwrap()
+                .unwrap()
+                .implied_skewness
+                .is_none()
+        );
+
+        let skewed = db.symbols_with_skewness_below(dec!(0.5)).unwrap();
+        assert_eq!(skewed.len(), 2);
+        assert_eq!(skewed[0].symbol, "SPY");
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let db = database(&[0.1, 0.2, 0.3]);
+        let path = std::env::temp_dir().join("optionstratlib_iv_history_test.json");
+        db.save_to_json(&path).unwrap();
+        let loaded = IvHistoryDatabase::load_from_json(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(loaded, db);
+    }
+}
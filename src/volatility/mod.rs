@@ -166,6 +166,9 @@ use positive::pos_or_panic;
 //! - Heston (1993) stochastic volatility model
 //! - GARCH by Bollerslev (1986)
 
+mod earnings;
+mod implied_moments;
+mod iv_history;
 mod traits;
 mod utils;
 
@@ -176,4 +179,9 @@ pub use utils::{
     uncertain_volatility_bounds, volatility_for_dt,
 };
 
+pub use earnings::EarningsVolModel;
+pub use implied_moments::{ImpliedMoments, implied_moments};
+pub use iv_history::{
+    DEFAULT_IV_WINDOW, IvHistoryBuilder, IvHistoryDatabase, IvObservation, IvStatistics,
+};
 pub use traits::{AtmIvProvider, VolatilitySmile};
@@ -6,12 +6,12 @@
 use crate::constants::{MAX_VOLATILITY, MIN_VOLATILITY};
 use crate::error::VolatilityError;
 use crate::model::decimal::decimal_normal_sample;
+use crate::simulation::rng::uniform;
+use crate::utils::parallel::map_ordered;
 use crate::utils::time::TimeFrame;
 use crate::{ExpirationDate, OptionStyle, OptionType, Options, Side};
 use num_traits::{FromPrimitive, ToPrimitive};
 use positive::{Positive, pos_or_panic};
-use rand::random;
-use rayon::prelude::*;
 use rust_decimal::{Decimal, MathematicalOps};
 
 /// Calculates the constant volatility from a series of returns.
@@ -109,24 +109,23 @@ pub fn implied_volatility(
 ) -> Result<Positive, VolatilityError> {
     let base_option = options.clone();
     let iterations = 100 * max_iterations;
-    let result = (1..iterations)
-        .into_par_iter()
-        .map(|i| {
-            let mut option = base_option.clone();
-            option.side = Side::Long; // Ensure the option is long
-            let iv = Positive::new(i as f64 / iterations as f64).unwrap_or(Positive::ZERO);
-            option.implied_volatility = iv;
-
-            match option.calculate_price_black_scholes() {
-                Ok(price) => {
-                    let diff = (price - market_price.to_dec()).abs();
-                    Some((iv, diff))
-                }
-                Err(_) => None,
+    let result = map_ordered((1..iterations).collect(), |i| {
+        let mut option = base_option.clone();
+        option.side = Side::Long; // Ensure the option is long
+        let iv = Positive::new(i as f64 / iterations as f64).unwrap_or(Positive::ZERO);
+        option.implied_volatility = iv;
+
+        match option.calculate_price_black_scholes() {
+            Ok(price) => {
+                let diff = (price - market_price.to_dec()).abs();
+                Some((iv, diff))
             }
-        })
-        .filter_map(|x| x) // Remove errors
-        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+            Err(_) => None,
+        }
+    })
+    .into_iter()
+    .flatten() // Remove errors
+    .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
 
     match result {
         Some((best_iv, _)) => {
@@ -246,7 +245,7 @@ pub fn simulate_heston_volatility(
     let mut v = Positive(v0);
     let mut volatilities = vec![v.sqrt()];
     for _ in 1..steps {
-        let dw = Decimal::from_f64(random::<f64>() * dt.sqrt().unwrap().to_f64().unwrap()).unwrap();
+        let dw = Decimal::from_f64(uniform() * dt.sqrt().unwrap().to_f64().unwrap()).unwrap();
         v += kappa * (theta - v) * dt + xi * v.sqrt() * dw;
         v = v.max(Positive::ZERO); // Ensure variance doesn't become negative
         volatilities.push(v.sqrt());
@@ -4,14 +4,16 @@
    Date: 2024
 ******************************************************************************/
 
-use optionstratlib::model::types::{OptionStyle, OptionType, Side};
-use optionstratlib::pricing::{Priceable, PricingEngine, price_option};
+use num_traits::ToPrimitive;
+use optionstratlib::model::types::{DayCount, OptionStyle, OptionType, SettlementType, Side};
+use optionstratlib::pricing::{Priceable, PricingEngine, price_option, price_option_as};
 use optionstratlib::simulation::simulator::Simulator;
 use optionstratlib::simulation::steps::{Step, Xstep, Ystep};
 use optionstratlib::simulation::{WalkParams, WalkType, WalkTypeAble};
 use optionstratlib::utils::TimeFrame;
 use optionstratlib::{ExpirationDate, Options};
 use positive::{Positive, pos_or_panic};
+use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use std::fmt::Display;
 use std::ops::AddAssign;
@@ -55,6 +57,8 @@ fn create_test_option() -> Options {
         option_style: OptionStyle::Call,
         dividend_yield: pos_or_panic!(0.01),
         exotic_params: None,
+        settlement_type: SettlementType::Physical,
+        day_count: DayCount::Act365Fixed,
     }
 }
 
@@ -338,6 +342,58 @@ fn test_error_handling() {
     assert!(result.is_ok(), "Should handle edge cases gracefully");
 }
 
+#[test]
+fn test_price_option_as_backends_agree_closed_form() {
+    for style in [OptionStyle::Call, OptionStyle::Put] {
+        for side in [Side::Long, Side::Short] {
+            let mut option = create_test_option();
+            option.option_style = style;
+            option.side = side;
+            let engine = PricingEngine::ClosedFormBS;
+
+            let reference = price_option(&option, &engine).unwrap().to_dec();
+            let exact = price_option_as::<Decimal>(&option, &engine).unwrap();
+            let fast = price_option_as::<f64>(&option, &engine).unwrap();
+
+            assert!((exact - reference).abs() < dec!(1e-10));
+            assert!((fast - reference.to_f64().unwrap()).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_price_option_as_backends_agree_monte_carlo() {
+    let option = create_test_option();
+    let size = 30;
+    let init_step = Step {
+        x: Xstep::new(
+            Positive::ONE,
+            TimeFrame::Day,
+            ExpirationDate::Days(pos_or_panic!(size as f64)),
+        ),
+        y: Ystep::new(0, option.underlying_price),
+    };
+    let params = WalkParams {
+        size,
+        init_step,
+        walk_type: WalkType::GeometricBrownian {
+            dt: Positive::ONE,
+            drift: dec!(0.0),
+            volatility: option.implied_volatility,
+        },
+        walker: Box::new(TestWalker),
+    };
+    let simulator = Simulator::new("MC Test".to_string(), 100, &params, simple_generator);
+    let engine = PricingEngine::MonteCarlo { simulator };
+
+    let reference = price_option(&option, &engine).unwrap().to_dec();
+    let exact = price_option_as::<Decimal>(&option, &engine).unwrap();
+    let fast = price_option_as::<f64>(&option, &engine).unwrap();
+
+    assert!((exact - reference).abs() < dec!(1e-9));
+    assert!((fast - exact.to_f64().unwrap()).abs() < 1e-9);
+}
+
 // Note: A full pricing consistency test between Black-Scholes and Monte Carlo
 // would require a more sophisticated random walk generator that properly
 // implements the stochastic differential equations for each model.
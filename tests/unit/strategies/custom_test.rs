@@ -210,6 +210,52 @@ fn create_complex_custom_strategy() -> CustomStrategy {
     )
 }
 
+// Helper function to create a fee-free expiration leg on a 100 underlying
+fn create_leg(
+    side: Side,
+    style: OptionStyle,
+    strike: f64,
+    quantity: f64,
+    premium: f64,
+) -> Position {
+    let option = Options::new(
+        OptionType::European,
+        side,
+        "TEST".to_string(),
+        Positive::new(strike).unwrap(),
+        ExpirationDate::Days(Positive::new(30.0).unwrap()),
+        Positive::new(0.2).unwrap(),
+        Positive::new(quantity).unwrap(),
+        Positive::HUNDRED,
+        dec!(0.0),
+        style,
+        Positive::ZERO,
+        None,
+    );
+    Position::new(
+        option,
+        Positive::new(premium).unwrap(),
+        Utc::now(),
+        Positive::ZERO,
+        Positive::ZERO,
+        None,
+        None,
+    )
+}
+
+fn create_leg_strategy(positions: Vec<Position>) -> CustomStrategy {
+    CustomStrategy::new(
+        "Legs".to_string(),
+        "TEST".to_string(),
+        "Custom legs".to_string(),
+        Positive::HUNDRED,
+        positions,
+        Positive::new(0.01).unwrap(),
+        100,
+        Positive::ONE,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -607,4 +653,84 @@ mod tests {
         // This should succeed or fail gracefully
         assert!(result.is_ok() || result.is_err());
     }
+
+    #[test]
+    fn test_break_even_single_crossing() {
+        let strategy = create_leg_strategy(vec![create_leg(
+            Side::Long,
+            OptionStyle::Call,
+            100.0,
+            1.0,
+            5.0,
+        )]);
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![Positive::new(105.0).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_break_even_two_crossings() {
+        let strategy = create_leg_strategy(vec![
+            create_leg(Side::Long, OptionStyle::Call, 100.0, 1.0, 5.0),
+            create_leg(Side::Long, OptionStyle::Put, 100.0, 1.0, 5.0),
+        ]);
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![Positive::new(90.0).unwrap(), Positive::new(110.0).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_break_even_four_crossings() {
+        let strategy = create_leg_strategy(vec![
+            create_leg(Side::Long, OptionStyle::Call, 80.0, 1.0, 4.0),
+            create_leg(Side::Short, OptionStyle::Call, 90.0, 2.0, 0.0),
+            create_leg(Side::Long, OptionStyle::Call, 100.0, 2.0, 0.0),
+            create_leg(Side::Short, OptionStyle::Call, 110.0, 2.0, 0.0),
+            create_leg(Side::Long, OptionStyle::Call, 120.0, 1.0, 0.0),
+        ]);
+        let expected: Vec<Positive> = [84.0, 96.0, 104.0, 116.0]
+            .into_iter()
+            .map(|price| Positive::new(price).unwrap())
+            .collect();
+        assert_eq!(strategy.get_break_even_points().unwrap(), &expected);
+    }
+
+    #[test]
+    fn test_break_even_beyond_scanned_range() {
+        let strategy = create_leg_strategy(vec![create_leg(
+            Side::Long,
+            OptionStyle::Call,
+            100.0,
+            1.0,
+            60.0,
+        )]);
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![Positive::new(160.0).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_break_even_no_crossing() {
+        // Net credit is smaller than the fees, so the condor never profits
+        let strategy = create_complex_custom_strategy();
+        assert!(
+            strategy
+                .calculate_profit_at(&Positive::new(400.0).unwrap())
+                .unwrap()
+                < dec!(0.0)
+        );
+        assert!(strategy.get_break_even_points().unwrap().is_empty());
+
+        let strategy = create_leg_strategy(vec![create_leg(
+            Side::Long,
+            OptionStyle::Call,
+            100.0,
+            1.0,
+            0.0,
+        )]);
+        assert!(strategy.get_break_even_points().unwrap().is_empty());
+    }
 }